@@ -1,7 +1,10 @@
 //! clap [Args](clap::Args) for database configuration
 
 use clap::Args;
+use humantime::parse_duration;
+use reth_db::mdbx::readers::ReadTxLimits;
 use reth_interfaces::db::LogLevel;
+use std::time::Duration;
 
 /// Parameters for database configuration
 #[derive(Debug, Args, PartialEq, Default, Clone, Copy)]
@@ -10,6 +13,30 @@ pub struct DatabaseArgs {
     /// Database logging level. Levels higher than "notice" require a debug build.
     #[arg(long = "db.log-level", value_enum)]
     pub log_level: Option<LogLevel>,
+
+    /// Logs the backtrace of database read transactions that are open for longer than this, at
+    /// debug level. Defaults to 60 seconds.
+    ///
+    /// Long-lived read transactions prevent the database from reusing freed pages.
+    #[arg(long = "db.read-tx-warn-after", value_name = "DURATION", value_parser = parse_duration)]
+    pub read_tx_warn_after: Option<Duration>,
+
+    /// Resets database read transactions that are open for longer than this, which releases their
+    /// snapshot and fails their following operations. By default read transactions never time
+    /// out.
+    #[arg(long = "db.read-tx-timeout", value_name = "DURATION", value_parser = parse_duration)]
+    pub read_tx_timeout: Option<Duration>,
+}
+
+impl DatabaseArgs {
+    /// Returns the configured thresholds for the duration of read transactions.
+    pub fn read_tx_limits(&self) -> ReadTxLimits {
+        let defaults = ReadTxLimits::default();
+        ReadTxLimits {
+            warn_after: self.read_tx_warn_after.unwrap_or(defaults.warn_after),
+            kill_after: self.read_tx_timeout.or(defaults.kill_after),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -30,4 +57,26 @@ mod tests {
         let args = CommandParser::<DatabaseArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_read_tx_limits() {
+        let args = CommandParser::<DatabaseArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.read_tx_limits(), ReadTxLimits::default());
+
+        let args = CommandParser::<DatabaseArgs>::parse_from([
+            "reth",
+            "--db.read-tx-warn-after",
+            "30s",
+            "--db.read-tx-timeout",
+            "5m",
+        ])
+        .args;
+        assert_eq!(
+            args.read_tx_limits(),
+            ReadTxLimits {
+                warn_after: Duration::from_secs(30),
+                kill_after: Some(Duration::from_secs(300))
+            }
+        );
+    }
 }
@@ -13,13 +13,15 @@ pub struct DebugArgs {
     #[arg(long = "debug.continuous", help_heading = "Debug", conflicts_with = "tip")]
     pub continuous: bool,
 
-    /// Flag indicating whether the node should be terminated after the pipeline sync.
+    /// Flag indicating whether the node should be terminated after the pipeline sync or once
+    /// `--debug.max-block` is reached.
     #[arg(long = "debug.terminate", help_heading = "Debug")]
     pub terminate: bool,
 
     /// Set the chain tip manually for testing purposes.
     ///
-    /// NOTE: This is a temporary flag
+    /// The headers stage downloads the headers backwards from this trusted block hash and the
+    /// pipeline syncs up to it without a consensus client.
     #[arg(long = "debug.tip", help_heading = "Debug", conflicts_with = "continuous")]
     pub tip: Option<B256>,
 
@@ -27,6 +29,12 @@ pub struct DebugArgs {
     #[arg(long = "debug.max-block", help_heading = "Debug")]
     pub max_block: Option<u64>,
 
+    /// Check executor invariants after each executed transaction and halt on violation.
+    ///
+    /// NOTE: This is for debugging purposes only, as it slows down execution.
+    #[arg(long = "debug.invariants", help_heading = "Debug")]
+    pub invariants: bool,
+
     /// Print opcode level traces directly to console during execution.
     #[arg(long = "debug.print-inspector", help_heading = "Debug")]
     pub print_inspector: bool,
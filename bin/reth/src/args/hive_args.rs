@@ -0,0 +1,232 @@
+//! clap [Args](clap::Args) for running the node in a hive simulation
+
+use clap::Args;
+use eyre::WrapErr;
+use reth_primitives::{Genesis, NodeRecord, U256};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The JWT secret hive authenticates engine API requests with.
+pub const HIVE_JWT_SECRET: &str =
+    "0x7365637265747365637265747365637265747365637265747365637265747365";
+
+/// Parameters for running the node as a [hive](https://github.com/ethereum/hive) client
+#[derive(Debug, Args, PartialEq, Eq, Clone)]
+#[clap(next_help_heading = "Hive")]
+pub struct HiveArgs {
+    /// Start the node in hive mode
+    ///
+    /// Uses the genesis in `<hive.dir>/genesis.json` with the forks configured by the `HIVE_*`
+    /// environment variables and imports `<hive.dir>/chain.rlp` and the blocks in
+    /// `<hive.dir>/blocks` before the node is started.
+    /// Serves the HTTP and WS RPC and the engine API on all interfaces, the engine API is
+    /// authenticated with the hive JWT secret.
+    /// Disables network discovery unless `HIVE_BOOTNODE` is set.
+    #[arg(long = "hive", help_heading = "Hive", verbatim_doc_comment)]
+    pub hive: bool,
+
+    /// The directory hive mounts the test files in.
+    #[arg(long = "hive.dir", value_name = "PATH", default_value = "/", help_heading = "Hive")]
+    pub dir: PathBuf,
+}
+
+impl HiveArgs {
+    /// Returns the genesis mounted by hive with the forks configured by the `HIVE_*` environment
+    /// variables applied, or `None` if no genesis is mounted.
+    pub fn genesis(&self) -> eyre::Result<Option<Genesis>> {
+        let path = self.dir.join("genesis.json");
+        if !path.exists() {
+            return Ok(None)
+        }
+        let raw = fs::read_to_string(&path)?;
+        let mut genesis: Genesis =
+            serde_json::from_str(&raw).wrap_err_with(|| format!("invalid genesis {path:?}"))?;
+        apply_fork_config(&mut genesis, |key| std::env::var(key).ok())?;
+        Ok(Some(genesis))
+    }
+
+    /// Returns the chain files to import before the node is started, in import order.
+    ///
+    /// These are `<hive.dir>/chain.rlp` followed by the files in `<hive.dir>/blocks`, sorted by
+    /// their numeric name.
+    pub fn import_paths(&self) -> eyre::Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+
+        let chain = self.dir.join("chain.rlp");
+        if chain.is_file() {
+            paths.push(chain);
+        }
+
+        let blocks_dir = self.dir.join("blocks");
+        if blocks_dir.is_dir() {
+            let mut blocks = fs::read_dir(&blocks_dir)?
+                .map(|entry| Ok(entry?.path()))
+                .filter(|path| path.as_ref().map_or(true, |path: &PathBuf| path.is_file()))
+                .collect::<eyre::Result<Vec<_>>>()?;
+            blocks.sort_by_cached_key(|path| (block_file_number(path), path.clone()));
+            paths.extend(blocks);
+        }
+
+        Ok(paths)
+    }
+
+    /// Returns the path of the JWT secret for the engine API, writing the hive secret to
+    /// `<hive.dir>/jwt.secret` if it doesn't exist.
+    pub fn jwt_secret_path(&self) -> eyre::Result<PathBuf> {
+        let path = self.dir.join("jwt.secret");
+        if !path.exists() {
+            fs::write(&path, HIVE_JWT_SECRET)?;
+        }
+        Ok(path)
+    }
+
+    /// Returns the bootnodes configured by the `HIVE_BOOTNODE` environment variable.
+    pub fn bootnodes(&self) -> eyre::Result<Option<Vec<NodeRecord>>> {
+        let Ok(bootnodes) = std::env::var("HIVE_BOOTNODE") else { return Ok(None) };
+        let bootnodes = bootnodes
+            .split(',')
+            .filter(|enode| !enode.is_empty())
+            .map(|enode| {
+                enode.parse::<NodeRecord>().wrap_err_with(|| format!("invalid bootnode {enode}"))
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+        Ok((!bootnodes.is_empty()).then_some(bootnodes))
+    }
+}
+
+impl Default for HiveArgs {
+    fn default() -> Self {
+        Self { hive: false, dir: PathBuf::from("/") }
+    }
+}
+
+/// Applies the chain id and fork configuration of the `HIVE_*` environment variables to the
+/// genesis.
+fn apply_fork_config(
+    genesis: &mut Genesis,
+    var: impl Fn(&str) -> Option<String>,
+) -> eyre::Result<()> {
+    let parse_u64 = |key: &str, value: String| {
+        value.parse::<u64>().wrap_err_with(|| format!("invalid {key}: {value}"))
+    };
+
+    if let Some(chain_id) = var("HIVE_CHAIN_ID") {
+        genesis.config.chain_id = parse_u64("HIVE_CHAIN_ID", chain_id)?;
+    }
+    let config = &mut genesis.config;
+    for (key, field) in [
+        ("HIVE_FORK_HOMESTEAD", &mut config.homestead_block),
+        ("HIVE_FORK_DAO_BLOCK", &mut config.dao_fork_block),
+        ("HIVE_FORK_TANGERINE", &mut config.eip150_block),
+        ("HIVE_FORK_SPURIOUS", &mut config.eip155_block),
+        ("HIVE_FORK_SPURIOUS", &mut config.eip158_block),
+        ("HIVE_FORK_BYZANTIUM", &mut config.byzantium_block),
+        ("HIVE_FORK_CONSTANTINOPLE", &mut config.constantinople_block),
+        ("HIVE_FORK_PETERSBURG", &mut config.petersburg_block),
+        ("HIVE_FORK_ISTANBUL", &mut config.istanbul_block),
+        ("HIVE_FORK_MUIR_GLACIER", &mut config.muir_glacier_block),
+        ("HIVE_FORK_BERLIN", &mut config.berlin_block),
+        ("HIVE_FORK_LONDON", &mut config.london_block),
+        ("HIVE_FORK_ARROW_GLACIER", &mut config.arrow_glacier_block),
+        ("HIVE_FORK_GRAY_GLACIER", &mut config.gray_glacier_block),
+        ("HIVE_MERGE_BLOCK_ID", &mut config.merge_netsplit_block),
+        ("HIVE_SHANGHAI_TIMESTAMP", &mut config.shanghai_time),
+        ("HIVE_CANCUN_TIMESTAMP", &mut config.cancun_time),
+    ] {
+        if let Some(value) = var(key) {
+            *field = Some(parse_u64(key, value)?);
+        }
+    }
+    if genesis.config.dao_fork_block.is_some() {
+        genesis.config.dao_fork_support = true;
+    }
+    if let Some(ttd) = var("HIVE_TERMINAL_TOTAL_DIFFICULTY") {
+        let ttd = ttd
+            .parse::<U256>()
+            .wrap_err_with(|| format!("invalid HIVE_TERMINAL_TOTAL_DIFFICULTY: {ttd}"))?;
+        genesis.config.terminal_total_difficulty = Some(ttd);
+    }
+    if let Some(passed) = var("HIVE_TERMINAL_TOTAL_DIFFICULTY_PASSED") {
+        genesis.config.terminal_total_difficulty_passed = passed == "1" || passed == "true";
+    }
+
+    Ok(())
+}
+
+/// Returns the number of a block file in the hive `blocks` directory, e.g. `0001.rlp`.
+fn block_file_number(path: &Path) -> Option<u64> {
+    path.file_stem()?.to_str()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use std::collections::HashMap;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[clap(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_hive_args() {
+        let args = CommandParser::<HiveArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, HiveArgs::default());
+
+        let args =
+            CommandParser::<HiveArgs>::parse_from(["reth", "--hive", "--hive.dir", "/tmp/hive"])
+                .args;
+        assert_eq!(args, HiveArgs { hive: true, dir: PathBuf::from("/tmp/hive") });
+    }
+
+    #[test]
+    fn test_apply_fork_config() {
+        let env = HashMap::from([
+            ("HIVE_CHAIN_ID", "7"),
+            ("HIVE_FORK_SPURIOUS", "2"),
+            ("HIVE_FORK_LONDON", "5"),
+            ("HIVE_TERMINAL_TOTAL_DIFFICULTY", "131072"),
+            ("HIVE_SHANGHAI_TIMESTAMP", "1000"),
+        ]);
+        let mut genesis = Genesis::default();
+        apply_fork_config(&mut genesis, |key| env.get(key).map(|v| v.to_string())).unwrap();
+
+        assert_eq!(genesis.config.chain_id, 7);
+        assert_eq!(genesis.config.eip155_block, Some(2));
+        assert_eq!(genesis.config.eip158_block, Some(2));
+        assert_eq!(genesis.config.london_block, Some(5));
+        assert_eq!(genesis.config.berlin_block, None);
+        assert_eq!(genesis.config.terminal_total_difficulty, Some(U256::from(131072)));
+        assert_eq!(genesis.config.shanghai_time, Some(1000));
+
+        let env = HashMap::from([("HIVE_FORK_LONDON", "x")]);
+        assert!(apply_fork_config(&mut genesis, |key| env.get(key).map(|v| v.to_string())).is_err());
+    }
+
+    #[test]
+    fn test_import_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let blocks = dir.path().join("blocks");
+        fs::create_dir(&blocks).unwrap();
+        for name in ["10.rlp", "2.rlp", "1.rlp"] {
+            fs::write(blocks.join(name), []).unwrap();
+        }
+        fs::write(dir.path().join("chain.rlp"), []).unwrap();
+
+        let args = HiveArgs { hive: true, dir: dir.path().to_path_buf() };
+        assert_eq!(
+            args.import_paths().unwrap(),
+            vec![
+                dir.path().join("chain.rlp"),
+                blocks.join("1.rlp"),
+                blocks.join("2.rlp"),
+                blocks.join("10.rlp"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,65 @@
+//! clap [Args](clap::Args) for indexing configuration
+
+use clap::Args;
+
+/// Parameters for the optional indexes of the node
+#[derive(Debug, Args, PartialEq, Default, Clone, Copy)]
+#[clap(next_help_heading = "Indexing")]
+pub struct IndexArgs {
+    /// Index the ether transfers made by contracts, e.g. calls with value and self-destructs, of
+    /// the blocks executed by the pipeline and the blockchain tree.
+    ///
+    /// The transfers can be queried with `reth_getInternalTransfers`. Executing the transactions
+    /// with the inspector that collects them makes the sync slower.
+    #[arg(long = "index.internal-transfers")]
+    pub internal_transfers: bool,
+
+    /// Index the transactions that created each contract, including the contracts created by
+    /// other contracts, of the blocks executed by the pipeline and the blockchain tree.
+    ///
+    /// The creators can be queried with `ots_getContractCreator`. The contracts created before the
+    /// index was enabled can be indexed with `reth db backfill --contract-creations`.
+    #[arg(long = "index.contract-creations")]
+    pub contract_creations: bool,
+
+    /// Index the transactions in which each address appears, as the sender or the recipient, in
+    /// the logs, e.g. as the recipient of a token transfer, or in the internal transfers.
+    ///
+    /// The index is built by an additional stage of the pipeline, which indexes the already synced
+    /// blocks on its first run. The internal transfers are only included if they're indexed too.
+    #[arg(long = "index.address-appearances")]
+    pub address_appearances: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[clap(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_index_args() {
+        let default_args = IndexArgs::default();
+        let args = CommandParser::<IndexArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+
+        let args =
+            CommandParser::<IndexArgs>::parse_from(["reth", "--index.internal-transfers"]).args;
+        assert!(args.internal_transfers);
+
+        let args =
+            CommandParser::<IndexArgs>::parse_from(["reth", "--index.address-appearances"]).args;
+        assert!(args.address_appearances);
+        assert!(!args.internal_transfers);
+
+        let args =
+            CommandParser::<IndexArgs>::parse_from(["reth", "--index.contract-creations"]).args;
+        assert!(args.contract_creations);
+    }
+}
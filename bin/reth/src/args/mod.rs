@@ -47,10 +47,18 @@ pub use txpool_args::TxPoolArgs;
 mod dev_args;
 pub use dev_args::DevArgs;
 
+/// HiveArgs for running the node in a hive simulation
+mod hive_args;
+pub use hive_args::HiveArgs;
+
 /// PruneArgs for configuring the pruning and full node
 mod pruning_args;
 pub use pruning_args::PruningArgs;
 
+/// IndexArgs for configuring the optional indexes
+mod index_args;
+pub use index_args::IndexArgs;
+
 /// RollupArgs for configuring the op-reth rollup
 #[cfg(feature = "optimism")]
 mod rollup_args;
@@ -31,6 +31,7 @@ impl PruningArgs {
                         .as_ref()
                         .map(|contract| PruneMode::Before(contract.block)),
                     account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    account_history_delete_bytecodes: false,
                     storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     receipts_log_filter: ReceiptsLogPruneConfig(
                         chain_spec
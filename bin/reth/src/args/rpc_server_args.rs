@@ -17,13 +17,18 @@ use clap::{
     Arg, Args, Command,
 };
 use futures::TryFutureExt;
+use reth_interfaces::db::DatabaseReaders;
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::{
-    AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    EvmEnvProvider, HeaderProvider, StateProviderFactory,
+    AccountReader, AddressAppearancesReader, BlockReaderIdExt, CanonStateSubscriptions,
+    ChainSpecProvider, ChangeSetReader, ContractCreationsReader, EvmEnvProvider, HeaderProvider,
+    InternalTransfersReader, StageCheckpointReader, StateProviderFactory, WithdrawalHistoryReader,
 };
 use reth_rpc::{
-    eth::{cache::EthStateCacheConfig, gas_oracle::GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP},
+    eth::{
+        cache::EthStateCacheConfig, gas_oracle::GasPriceOracleConfig,
+        DEFAULT_MAX_CONCURRENT_LOGS_CHUNKS, RPC_DEFAULT_GAS_CAP,
+    },
     JwtError, JwtSecret,
 };
 use reth_rpc_builder::{
@@ -40,6 +45,8 @@ use std::{
     ffi::OsStr,
     net::{IpAddr, Ipv4Addr, SocketAddr},
     path::PathBuf,
+    sync::Arc,
+    time::Duration,
 };
 use tracing::{debug, info};
 
@@ -54,16 +61,23 @@ pub(crate) const RPC_DEFAULT_MAX_RESPONSE_SIZE_MB: u32 = 150;
 /// Default number of incoming connections.
 pub(crate) const RPC_DEFAULT_MAX_CONNECTIONS: u32 = 500;
 
+/// The RPC modules served over HTTP and WS in hive mode, see `--hive`.
+const HIVE_RPC_MODULES: &str = "admin,debug,eth,net,txpool,web3";
+
 /// Parameters for configuring the rpc more granularity via CLI
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[clap(next_help_heading = "RPC")]
 pub struct RpcServerArgs {
     /// Enable the HTTP-RPC server
-    #[arg(long, default_value_if("dev", "true", "true"))]
+    #[arg(long, default_value_if("dev", "true", "true"), default_value_if("hive", "true", "true"))]
     pub http: bool,
 
     /// Http server address to listen on
-    #[arg(long = "http.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    #[arg(
+        long = "http.addr",
+        default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST),
+        default_value_if("hive", "true", "0.0.0.0")
+    )]
     pub http_addr: IpAddr,
 
     /// Http server port to listen on
@@ -71,7 +85,11 @@ pub struct RpcServerArgs {
     pub http_port: u16,
 
     /// Rpc Modules to be configured for the HTTP server
-    #[arg(long = "http.api", value_parser = RpcModuleSelectionValueParser::default())]
+    #[arg(
+        long = "http.api",
+        value_parser = RpcModuleSelectionValueParser::default(),
+        default_value_if("hive", "true", HIVE_RPC_MODULES)
+    )]
     pub http_api: Option<RpcModuleSelection>,
 
     /// Http Corsdomain to allow request from
@@ -79,11 +97,15 @@ pub struct RpcServerArgs {
     pub http_corsdomain: Option<String>,
 
     /// Enable the WS-RPC server
-    #[arg(long)]
+    #[arg(long, default_value_if("hive", "true", "true"))]
     pub ws: bool,
 
     /// Ws server address to listen on
-    #[arg(long = "ws.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    #[arg(
+        long = "ws.addr",
+        default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST),
+        default_value_if("hive", "true", "0.0.0.0")
+    )]
     pub ws_addr: IpAddr,
 
     /// Ws server port to listen on
@@ -95,7 +117,11 @@ pub struct RpcServerArgs {
     pub ws_allowed_origins: Option<String>,
 
     /// Rpc Modules to be configured for the WS server
-    #[arg(long = "ws.api", value_parser = RpcModuleSelectionValueParser::default())]
+    #[arg(
+        long = "ws.api",
+        value_parser = RpcModuleSelectionValueParser::default(),
+        default_value_if("hive", "true", HIVE_RPC_MODULES)
+    )]
     pub ws_api: Option<RpcModuleSelection>,
 
     /// Disable the IPC-RPC  server
@@ -106,8 +132,39 @@ pub struct RpcServerArgs {
     #[arg(long, default_value_t = constants::DEFAULT_IPC_ENDPOINT.to_string())]
     pub ipcpath: String,
 
+    /// Enable the GraphQL server (EIP-1767)
+    #[arg(long)]
+    pub graphql: bool,
+
+    /// GraphQL server address to listen on
+    #[arg(long = "graphql.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub graphql_addr: IpAddr,
+
+    /// GraphQL server port to listen on
+    #[arg(long = "graphql.port", default_value_t = constants::DEFAULT_GRAPHQL_PORT)]
+    pub graphql_port: u16,
+
+    /// Enable the gRPC server that streams the canonical chain
+    #[cfg(feature = "grpc")]
+    #[arg(long)]
+    pub grpc: bool,
+
+    /// gRPC server address to listen on
+    #[cfg(feature = "grpc")]
+    #[arg(long = "grpc.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub grpc_addr: IpAddr,
+
+    /// gRPC server port to listen on
+    #[cfg(feature = "grpc")]
+    #[arg(long = "grpc.port", default_value_t = constants::DEFAULT_GRPC_PORT)]
+    pub grpc_port: u16,
+
     /// Auth server address to listen on
-    #[arg(long = "authrpc.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    #[arg(
+        long = "authrpc.addr",
+        default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST),
+        default_value_if("hive", "true", "0.0.0.0")
+    )]
     pub auth_addr: IpAddr,
 
     /// Auth server port to listen on
@@ -159,6 +216,15 @@ pub struct RpcServerArgs {
     #[arg(long, value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64))]
     pub rpc_max_logs_per_response: ZeroAsNoneU64,
 
+    /// Maximum number of chunks of the block range of a log query that are processed
+    /// concurrently.
+    #[arg(long, value_name = "COUNT", default_value_t = DEFAULT_MAX_CONCURRENT_LOGS_CHUNKS, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub rpc_max_concurrent_logs_chunks: usize,
+
+    /// Maximum duration of a log query in seconds. (0 = no timeout)
+    #[arg(long, value_name = "SECONDS", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_LOGS_QUERY_TIMEOUT_SECS))]
+    pub rpc_logs_query_timeout: ZeroAsNoneU64,
+
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     #[arg(
         long,
@@ -224,11 +290,18 @@ impl RpcServerArgs {
     /// Returns the handles for the launched regular RPC server(s) (if any) and the server handle
     /// for the auth server that handles the `engine_` API that's accessed by the consensus
     /// layer.
+    ///
+    /// The open read transactions of the database and the mode of the node are served by the
+    /// `admin` namespace. If `dev_accounts` is set, the `eth` namespace can sign with the funded
+    /// accounts of the dev chain.
     pub async fn start_servers<Reth, Engine, Conf>(
         &self,
         components: &Reth,
         engine_api: Engine,
         jwt_secret: JwtSecret,
+        db_readers: Arc<dyn DatabaseReaders>,
+        node_mode: String,
+        dev_accounts: bool,
         conf: &mut Conf,
     ) -> eyre::Result<RethRpcServerHandles>
     where
@@ -247,8 +320,14 @@ impl RpcServerArgs {
             .with_network(components.network())
             .with_events(components.events())
             .with_executor(components.task_executor())
+            .with_db_readers(db_readers)
+            .with_node_mode(node_mode)
             .build_with_auth_server(module_config, engine_api);
 
+        if dev_accounts {
+            registry.eth_api().with_dev_accounts();
+        }
+
         let rpc_components = RethRpcComponents { registry: &mut registry, modules: &mut modules };
         // apply configured customization
         conf.extend_rpc_modules(self, components, rpc_components)?;
@@ -277,6 +356,27 @@ impl RpcServerArgs {
         let (rpc, auth) = futures::future::try_join(launch_rpc, launch_auth).await?;
         let handles = RethRpcServerHandles { rpc, auth };
 
+        if self.graphql {
+            let addr = SocketAddr::new(self.graphql_addr, self.graphql_port);
+            let executor = Box::new(components.task_executor());
+            let addr =
+                reth_rpc_graphql::start_server(addr, components.provider(), executor).await?;
+            info!(target: "reth::cli", url=%addr, "GraphQL server started");
+        }
+
+        #[cfg(feature = "grpc")]
+        if self.grpc {
+            let addr = SocketAddr::new(self.grpc_addr, self.grpc_port);
+            let addr = reth_rpc_grpc::start_server(
+                addr,
+                components.provider(),
+                components.events(),
+                Box::new(components.task_executor()),
+            )
+            .await?;
+            info!(target: "reth::cli", url=%addr, "gRPC server started");
+        }
+
         // call hook
         let rpc_components = RethRpcComponents { registry: &mut registry, modules: &mut modules };
         conf.on_rpc_server_started(self, components, rpc_components, handles.clone())?;
@@ -298,9 +398,14 @@ impl RpcServerArgs {
             + AccountReader
             + HeaderProvider
             + StateProviderFactory
+            + StageCheckpointReader
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + InternalTransfersReader
+            + ContractCreationsReader
+            + AddressAppearancesReader
+            + WithdrawalHistoryReader
             + Clone
             + Unpin
             + 'static,
@@ -337,6 +442,7 @@ impl RpcServerArgs {
             + EvmEnvProvider
             + HeaderProvider
             + StateProviderFactory
+            + StageCheckpointReader
             + Clone
             + Unpin
             + 'static,
@@ -374,6 +480,8 @@ impl RethRpcConfig for RpcServerArgs {
             .max_tracing_requests(self.rpc_max_tracing_requests)
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
+            .max_concurrent_logs_chunks(self.rpc_max_concurrent_logs_chunks)
+            .logs_query_timeout(self.rpc_logs_query_timeout.0.map(Duration::from_secs))
             .rpc_gas_cap(self.rpc_gas_cap)
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
@@ -504,6 +612,15 @@ impl Default for RpcServerArgs {
             ws_api: None,
             ipcdisable: false,
             ipcpath: constants::DEFAULT_IPC_ENDPOINT.to_string(),
+            graphql: false,
+            graphql_addr: Ipv4Addr::LOCALHOST.into(),
+            graphql_port: constants::DEFAULT_GRAPHQL_PORT,
+            #[cfg(feature = "grpc")]
+            grpc: false,
+            #[cfg(feature = "grpc")]
+            grpc_addr: Ipv4Addr::LOCALHOST.into(),
+            #[cfg(feature = "grpc")]
+            grpc_port: constants::DEFAULT_GRPC_PORT,
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
             auth_jwtsecret: None,
@@ -515,6 +632,8 @@ impl Default for RpcServerArgs {
             rpc_max_tracing_requests: constants::DEFAULT_MAX_TRACING_REQUESTS,
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
+            rpc_max_concurrent_logs_chunks: DEFAULT_MAX_CONCURRENT_LOGS_CHUNKS,
+            rpc_logs_query_timeout: constants::DEFAULT_LOGS_QUERY_TIMEOUT_SECS.into(),
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
@@ -714,12 +833,15 @@ mod tests {
             "0",
             "--rpc-max-logs-per-response",
             "0",
+            "--rpc-logs-query-timeout",
+            "0",
         ])
         .args;
 
         let config = args.eth_config().filter_config();
         assert_eq!(config.max_blocks_per_filter, Some(u64::MAX));
         assert_eq!(config.max_logs_per_response, Some(usize::MAX));
+        assert_eq!(config.logs_query_timeout, None);
     }
 
     #[test]
@@ -730,12 +852,18 @@ mod tests {
             "100",
             "--rpc-max-logs-per-response",
             "200",
+            "--rpc-max-concurrent-logs-chunks",
+            "8",
+            "--rpc-logs-query-timeout",
+            "10",
         ])
         .args;
 
         let config = args.eth_config().filter_config();
         assert_eq!(config.max_blocks_per_filter, Some(100));
         assert_eq!(config.max_logs_per_response, Some(200));
+        assert_eq!(config.max_concurrent_logs_chunks, 8);
+        assert_eq!(config.logs_query_timeout, Some(Duration::from_secs(10)));
     }
 
     #[test]
@@ -3,9 +3,10 @@
 use crate::cli::config::RethTransactionPoolConfig;
 use clap::Args;
 use reth_transaction_pool::{
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-    REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-    TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+    blobstore::DiskFileBlobStoreConfig, LocalTransactionConfig, PoolConfig, PriceBumpConfig,
+    SubPoolLimit, BLOBSTORE_MAX_SIZE_MB_DEFAULT, DEFAULT_PRICE_BUMP, REPLACE_BLOB_PRICE_BUMP,
+    TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+    TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
 
 /// Parameters for debugging purposes
@@ -44,6 +45,11 @@ pub struct TxPoolArgs {
     /// Price bump percentage to replace an already existing blob transaction
     #[arg(long = "blobpool.pricebump", default_value_t = REPLACE_BLOB_PRICE_BUMP)]
     pub blob_transaction_price_bump: u128,
+    /// Max size of the blob sidecars stored on disk in megabytes.
+    ///
+    /// Blob transactions whose sidecar doesn't fit are rejected.
+    #[arg(long = "blobpool.max-size", default_value_t = BLOBSTORE_MAX_SIZE_MB_DEFAULT)]
+    pub blobstore_max_size: usize,
     /// Flag to disable local transaction exemptions.
     #[arg(long = "txpool.nolocals")]
     pub no_locals: bool,
@@ -61,6 +67,7 @@ impl Default for TxPoolArgs {
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bump: DEFAULT_PRICE_BUMP,
             blob_transaction_price_bump: REPLACE_BLOB_PRICE_BUMP,
+            blobstore_max_size: BLOBSTORE_MAX_SIZE_MB_DEFAULT,
             no_locals: false,
         }
     }
@@ -94,6 +101,14 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             },
         }
     }
+
+    /// Returns the blob store configuration.
+    fn blob_store_config(&self) -> DiskFileBlobStoreConfig {
+        DiskFileBlobStoreConfig {
+            max_size: Some(self.blobstore_max_size.saturating_mul(1024 * 1024)),
+            ..Default::default()
+        }
+    }
 }
 
 #[cfg(test)]
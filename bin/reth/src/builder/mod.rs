@@ -2,8 +2,8 @@
 use super::cli::{components::RethRpcServerHandles, ext::DefaultRethNodeCommandConfig};
 use crate::{
     args::{
-        get_secret_key, DatabaseArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        get_secret_key, DatabaseArgs, DebugArgs, DevArgs, IndexArgs, NetworkArgs,
+        PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
     },
     cli::{
         components::RethNodeComponentsImpl,
@@ -11,9 +11,12 @@ use crate::{
         db_type::{DatabaseBuilder, DatabaseInstance},
         ext::{RethCliExt, RethNodeCommandConfig},
     },
-    commands::node::{cl_events::ConsensusLayerHealthEvents, events},
+    commands::node::{
+        cl_events::ConsensusLayerHealthEvents, contract_creations::ContractCreationsIndexer,
+        events, hot_state, internal_transfers::InternalTransfersIndexer,
+    },
     dirs::{ChainPath, DataDirPath, MaybePlatformPath},
-    init::init_genesis,
+    init::{init_genesis, init_node_mode, NodeMode},
     prometheus_exporter,
     utils::{get_single_header, write_peers_to_file},
     version::SHORT_VERSION,
@@ -26,7 +29,8 @@ use once_cell::sync::Lazy;
 use reth_auto_seal_consensus::{AutoSealBuilder, AutoSealConsensus, MiningMode};
 use reth_beacon_consensus::{
     hooks::{EngineHooks, PruneHook},
-    BeaconConsensus, BeaconConsensusEngine, BeaconConsensusEngineError,
+    BeaconConsensus, BeaconConsensusEngineBuilder, BeaconConsensusEngineError,
+    BeaconConsensusEngineHandle, DEFAULT_PAYLOAD_LANE_CAPACITY, DEFAULT_PRIORITY_LANE_CAPACITY,
     MIN_BLOCKS_FOR_PIPELINE_RUN,
 };
 use reth_blockchain_tree::{
@@ -47,6 +51,7 @@ use reth_downloaders::{
 use reth_interfaces::{
     blockchain_tree::BlockchainTreeEngine,
     consensus::Consensus,
+    db::{set_reader_component, DatabaseReaders},
     p2p::{
         bodies::{client::BodiesClient, downloader::BodyDownloader},
         either::EitherDownloader,
@@ -69,21 +74,22 @@ use reth_provider::{
     ProviderFactory, StageCheckpointReader,
 };
 use reth_prune::PrunerBuilder;
-use reth_revm::EvmProcessorFactory;
-use reth_revm_inspectors::stack::Hook;
+use reth_revm::{bytecode_cache::BytecodeCache, EvmProcessorFactory};
+use reth_revm_inspectors::stack::{Hook, InspectorStackConfig};
 use reth_rpc_engine_api::EngineApi;
 use reth_stages::{
     prelude::*,
     stages::{
-        AccountHashingStage, ExecutionStage, ExecutionStageThresholds, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TotalDifficultyStage, TransactionLookupStage,
+        AccountHashingStage, AdaptiveBatchSize, ExecutionStage, ExecutionStageThresholds,
+        IndexAccountHistoryStage, IndexAddressAppearancesStage, IndexStorageHistoryStage,
+        MerkleStage, SenderRecoveryStage, StorageHashingStage, TotalDifficultyStage,
+        TransactionLookupStage,
     },
     MetricEvent,
 };
-use reth_tasks::{TaskExecutor, TaskManager};
+use reth_tasks::{shutdown::Shutdown, TaskExecutor, TaskManager};
 use reth_transaction_pool::{
-    blobstore::InMemoryBlobStore, EthTransactionPool, TransactionPool,
+    blobstore::DiskFileBlobStore, EthTransactionPool, TransactionPool,
     TransactionValidationTaskExecutor,
 };
 use secp256k1::SecretKey;
@@ -91,6 +97,7 @@ use std::{
     net::{SocketAddr, SocketAddrV4},
     path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 use tokio::{
     runtime::Handle,
@@ -106,6 +113,9 @@ use tracing::*;
 pub static PROMETHEUS_RECORDER_HANDLE: Lazy<PrometheusHandle> =
     Lazy::new(|| prometheus_exporter::install_recorder().unwrap());
 
+/// The interval in which the age of idle database read transactions is checked.
+const DB_READERS_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
 /// This includes all necessary configuration to launch the node.
 /// The individual configuration options can be overwritten before launching the node.
 ///
@@ -233,6 +243,9 @@ pub struct NodeConfig {
     /// All pruning related arguments
     pub pruning: PruningArgs,
 
+    /// All indexing related arguments with --index prefix
+    pub index: IndexArgs,
+
     /// Rollup related arguments
     #[cfg(feature = "optimism")]
     pub rollup: crate::args::RollupArgs,
@@ -256,6 +269,7 @@ impl NodeConfig {
             db: DatabaseArgs::default(),
             dev: DevArgs::default(),
             pruning: PruningArgs::default(),
+            index: IndexArgs::default(),
             #[cfg(feature = "optimism")]
             rollup: crate::args::RollupArgs::default(),
         }
@@ -351,6 +365,12 @@ impl NodeConfig {
         self
     }
 
+    /// Set the indexing args for the node
+    pub fn with_index(mut self, index: IndexArgs) -> Self {
+        self.index = index;
+        self
+    }
+
     /// Set the node instance number
     pub fn with_instance_number(mut self, instance: u16) -> Self {
         self.instance = instance;
@@ -392,7 +412,8 @@ impl NodeConfig {
         info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
 
         let database = std::mem::take(&mut self.database);
-        let db_instance = database.init_db(self.db.log_level, self.chain.chain)?;
+        let db_instance =
+            database.init_db(self.db.log_level, self.db.read_tx_limits(), self.chain.chain)?;
 
         match db_instance {
             DatabaseInstance::Real { db, data_dir } => {
@@ -510,16 +531,40 @@ impl NodeConfig {
         prune_config: Option<PruneConfig>,
         sync_metrics_tx: UnboundedSender<MetricEvent>,
         tree_config: BlockchainTreeConfig,
+        bytecode_cache: BytecodeCache,
     ) -> eyre::Result<BlockchainTree<DB, EvmProcessorFactory>>
     where
         DB: Database + Unpin + Clone + 'static,
     {
         // configure blockchain tree
-        let tree_externals = TreeExternals::new(
-            provider_factory.clone(),
-            consensus.clone(),
-            EvmProcessorFactory::new(self.chain.clone()),
-        );
+        let mut executor_factory =
+            EvmProcessorFactory::new(self.chain.clone()).with_bytecode_cache(bytecode_cache);
+        // the internal transfers and contract creations of the blocks executed by the tree are
+        // indexed once they're committed to the canonical chain
+        let internal_transfers =
+            self.index.internal_transfers.then(InternalTransfersIndexer::default);
+        let contract_creations =
+            self.index.contract_creations.then(ContractCreationsIndexer::default);
+        let execution_inspectors = internal_transfers
+            .iter()
+            .map(|indexer| indexer.inspector())
+            .chain(contract_creations.iter().map(|indexer| indexer.inspector()))
+            .collect::<Vec<_>>();
+        if !execution_inspectors.is_empty() {
+            executor_factory = executor_factory.with_stack_config(InspectorStackConfig {
+                execution_inspectors,
+                execution_hook: Some(Hook::All),
+                ..Default::default()
+            });
+        }
+        let mut tree_externals =
+            TreeExternals::new(provider_factory.clone(), consensus.clone(), executor_factory);
+        if let Some(indexer) = internal_transfers {
+            tree_externals = tree_externals.with_indexer(indexer);
+        }
+        if let Some(indexer) = contract_creations {
+            tree_externals = tree_externals.with_indexer(indexer);
+        }
         let tree = BlockchainTree::new(
             tree_externals,
             tree_config,
@@ -537,7 +582,7 @@ impl NodeConfig {
         head: Head,
         executor: &TaskExecutor,
         data_dir: &ChainPath<DataDirPath>,
-    ) -> eyre::Result<EthTransactionPool<BlockchainProvider<DB, Tree>, InMemoryBlobStore>>
+    ) -> eyre::Result<EthTransactionPool<BlockchainProvider<DB, Tree>, DiskFileBlobStore>>
     where
         DB: Database + Unpin + Clone + 'static,
         Tree: BlockchainTreeEngine
@@ -546,7 +591,8 @@ impl NodeConfig {
             + Clone
             + 'static,
     {
-        let blob_store = InMemoryBlobStore::default();
+        let blob_store =
+            DiskFileBlobStore::open(data_dir.blobstore_path(), self.txpool.blob_store_config())?;
         let validator = TransactionValidationTaskExecutor::eth_builder(Arc::clone(&self.chain))
             .with_head_timestamp(head.timestamp)
             .kzg_settings(self.kzg_settings()?)
@@ -865,10 +911,26 @@ impl NodeConfig {
             builder = builder.with_max_block(max_block)
         }
 
+        if let Some(window) = stage_config.bodies.window {
+            debug!(target: "reth::cli", window, "Configuring builder to use bodies window");
+            builder = builder.with_stage_window(StageId::Bodies, window)
+        }
+
         let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
-        use reth_revm_inspectors::stack::InspectorStackConfig;
         let factory = reth_revm::EvmProcessorFactory::new(self.chain.clone());
 
+        // the internal transfers and contract creations of all transactions are collected while
+        // they're executed
+        let internal_transfers =
+            self.index.internal_transfers.then(InternalTransfersIndexer::default);
+        let contract_creations =
+            self.index.contract_creations.then(ContractCreationsIndexer::default);
+        let execution_inspectors = internal_transfers
+            .iter()
+            .map(|indexer| indexer.inspector())
+            .chain(contract_creations.iter().map(|indexer| indexer.inspector()))
+            .collect::<Vec<_>>();
+
         let stack_config = InspectorStackConfig {
             use_printer_tracer: self.debug.print_inspector,
             hook: if let Some(hook_block) = self.debug.hook_block {
@@ -880,9 +942,12 @@ impl NodeConfig {
             } else {
                 Hook::None
             },
+            execution_hook: (!execution_inspectors.is_empty()).then_some(Hook::All),
+            execution_inspectors,
         };
 
-        let factory = factory.with_stack_config(stack_config);
+        let factory =
+            factory.with_stack_config(stack_config).with_invariant_checks(self.debug.invariants);
 
         let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
@@ -922,7 +987,17 @@ impl NodeConfig {
                             .max(stage_config.storage_hashing.clean_threshold),
                         prune_modes.clone(),
                     )
-                    .with_metrics_tx(metrics_tx),
+                    .with_adaptive_batch_size(stage_config.execution.adaptive_batch_size.map(
+                        |adaptive| {
+                            AdaptiveBatchSize::new(
+                                adaptive.min_blocks,
+                                stage_config.execution.max_blocks.unwrap_or(u64::MAX),
+                                adaptive.target_commit_duration,
+                            )
+                        },
+                    ))
+                    .with_metrics_tx(metrics_tx)
+                    .with_indexer((internal_transfers, contract_creations)),
                 )
                 .set(AccountHashingStage::new(
                     stage_config.account_hashing.clean_threshold,
@@ -944,7 +1019,14 @@ impl NodeConfig {
                 .set(IndexStorageHistoryStage::new(
                     stage_config.index_storage_history.commit_threshold,
                     prune_modes.storage_history,
-                )),
+                ))
+                .add_before(
+                    IndexAddressAppearancesStage::new(
+                        stage_config.index_address_appearances.commit_threshold,
+                    ),
+                    StageId::Finish,
+                )
+                .disable_if(StageId::IndexAddressAppearances, || !self.index.address_appearances),
             )
             .build(provider_factory);
 
@@ -975,6 +1057,7 @@ impl Default for NodeConfig {
             db: DatabaseArgs::default(),
             dev: DevArgs::default(),
             pruning: PruningArgs::default(),
+            index: IndexArgs::default(),
             #[cfg(feature = "optimism")]
             rollup: crate::args::RollupArgs::default(),
         }
@@ -994,7 +1077,10 @@ pub struct NodeBuilderWithDatabase<DB> {
     pub data_dir: ChainPath<DataDirPath>,
 }
 
-impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWithDatabase<DB> {
+impl<DB> NodeBuilderWithDatabase<DB>
+where
+    DB: Database + DatabaseMetrics + DatabaseMetadata + DatabaseReaders + 'static,
+{
     /// Launch the node with the given extensions and executor
     pub async fn launch<E: RethCliExt>(
         mut self,
@@ -1036,6 +1122,16 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
 
         let consensus = self.config.consensus();
 
+        // read transactions are checked on every operation, this catches the idle ones
+        let db_readers = Arc::clone(&self.db);
+        executor.spawn_critical("database readers check", async move {
+            let mut interval = tokio::time::interval(DB_READERS_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                db_readers.check_readers();
+            }
+        });
+
         debug!(target: "reth::cli", "Spawning stages metrics listener task");
         let (sync_metrics_tx, sync_metrics_rx) = unbounded_channel();
         let sync_metrics_listener = reth_stages::MetricsListener::new(sync_metrics_rx);
@@ -1047,14 +1143,22 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
             .prune_config(Arc::clone(&self.config.chain))?
             .or(config.prune.clone());
 
+        let node_mode = init_node_mode(
+            &provider_factory,
+            NodeMode::from_prune_modes(prune_config.as_ref().map(|config| &config.segments)),
+        )?;
+        info!(target: "reth::cli", %node_mode, "Node mode");
+
         // configure blockchain tree
         let tree_config = BlockchainTreeConfig::default();
+        let bytecode_cache = BytecodeCache::default();
         let tree = self.config.build_blockchain_tree(
             provider_factory.clone(),
             consensus.clone(),
             prune_config.clone(),
             sync_metrics_tx.clone(),
             tree_config,
+            bytecode_cache.clone(),
         )?;
         let canon_state_notification_sender = tree.canon_state_notification_sender();
         let blockchain_tree = ShareableBlockchainTree::new(tree);
@@ -1070,17 +1174,39 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
         let blockchain_db =
             BlockchainProvider::new(provider_factory.clone(), blockchain_tree.clone())?;
 
+        // warm up the bytecodes of the recently changed accounts in the background
+        let hot_accounts = hot_state::HotAccounts::load(&self.db).unwrap_or_else(|err| {
+            warn!(target: "reth::cli", %err, "Failed to load hot accounts");
+            Default::default()
+        });
+        let db = self.db.clone();
+        let warm_up_accounts = hot_accounts.clone();
+        executor.spawn_blocking(async move {
+            hot_state::warm_up_hot_accounts(&db, &warm_up_accounts, &bytecode_cache)
+        });
+        executor.spawn_named(
+            "hot accounts",
+            hot_state::track_hot_accounts(self.db.clone(), hot_accounts, blockchain_db.clone()),
+        );
+
         // build transaction pool
         let transaction_pool =
             self.config.build_and_spawn_txpool(&blockchain_db, head, &executor, &self.data_dir)?;
 
+        // the network, the pipeline, the consensus engine and the RPC servers spawn their tasks
+        // with their own task managers
+        let network_executor = subsystem_executor(&executor);
+        let pipeline_executor = subsystem_executor(&executor);
+        let engine_executor = subsystem_executor(&executor);
+        let rpc_executor = subsystem_executor(&executor);
+
         // build network
         let (network_client, mut network_builder) = self
             .config
             .build_network(
                 &config,
                 provider_factory.clone(),
-                executor.clone(),
+                network_executor.clone(),
                 head,
                 &self.data_dir,
             )
@@ -1100,7 +1226,7 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
         // launch network
         let network = self.config.start_network(
             network_builder,
-            &executor,
+            &network_executor,
             transaction_pool.clone(),
             network_client,
             &self.data_dir,
@@ -1142,7 +1268,7 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
                     client.clone(),
                     Arc::clone(&consensus),
                     provider_factory.clone(),
-                    &executor,
+                    &pipeline_executor,
                     sync_metrics_tx,
                     prune_config.clone(),
                     max_block,
@@ -1163,7 +1289,7 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
                     network_client.clone(),
                     Arc::clone(&consensus),
                     provider_factory.clone(),
-                    &executor.clone(),
+                    &pipeline_executor,
                     sync_metrics_tx,
                     prune_config.clone(),
                     max_block,
@@ -1185,7 +1311,7 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
                 .build(provider_factory, snapshotter.highest_snapshot_receiver());
 
             let events = pruner.events();
-            hooks.add(PruneHook::new(pruner, Box::new(executor.clone())));
+            hooks.add(PruneHook::new(pruner, Box::new(engine_executor.clone())));
 
             info!(target: "reth::cli", ?prune_config, "Pruner initialized");
             Either::Left(events)
@@ -1194,21 +1320,31 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
         };
 
         // Configure the consensus engine
-        let (beacon_consensus_engine, beacon_engine_handle) = BeaconConsensusEngine::with_channel(
+        let mut engine_builder = BeaconConsensusEngineBuilder::new(
             client,
             pipeline,
             blockchain_db.clone(),
-            Box::new(executor.clone()),
-            Box::new(network.clone()),
-            max_block,
-            self.config.debug.continuous,
+            Box::new(engine_executor.clone()),
             payload_builder.clone(),
-            initial_target,
-            MIN_BLOCKS_FOR_PIPELINE_RUN,
-            consensus_engine_tx,
-            consensus_engine_rx,
-            hooks,
-        )?;
+        )
+        .with_sync_state_updater(Box::new(network.clone()))
+        .with_run_pipeline_continuously(self.config.debug.continuous)
+        .with_pipeline_run_threshold(MIN_BLOCKS_FOR_PIPELINE_RUN)
+        .with_hooks(hooks);
+        if let Some(max_block) = max_block {
+            engine_builder = engine_builder.with_max_block(max_block);
+        }
+        if let Some(target) = initial_target {
+            engine_builder = engine_builder.with_target(target);
+        }
+        // the auto seal task sends its forkchoice updates to the engine directly
+        engine_builder = if self.config.dev.dev {
+            engine_builder.with_message_channel(consensus_engine_tx, consensus_engine_rx)
+        } else {
+            engine_builder
+                .with_message_lanes(DEFAULT_PRIORITY_LANE_CAPACITY, DEFAULT_PAYLOAD_LANE_CAPACITY)
+        };
+        let (beacon_consensus_engine, beacon_engine_handle) = engine_builder.build()?;
         info!(target: "reth::cli", "Consensus engine initialized");
 
         let events = stream_select!(
@@ -1238,9 +1374,9 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
         let engine_api = EngineApi::new(
             blockchain_db.clone(),
             self.config.chain.clone(),
-            beacon_engine_handle,
+            beacon_engine_handle.clone(),
             payload_builder.into(),
-            Box::new(executor.clone()),
+            Box::new(rpc_executor.clone()),
         );
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -1252,13 +1388,28 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
         self.config.adjust_instance_ports();
 
         // Start RPC servers
-        let rpc_server_handles =
-            self.config.rpc.start_servers(&components, engine_api, jwt_secret, &mut ext).await?;
+        let rpc_components =
+            RethNodeComponentsImpl { task_executor: rpc_executor, ..components.clone() };
+        let rpc_server_handles = self
+            .config
+            .rpc
+            .start_servers(
+                &rpc_components,
+                engine_api,
+                jwt_secret,
+                self.db.clone(),
+                // the node mode is exposed via `admin_nodeInfo`
+                node_mode.to_string(),
+                self.config.dev.dev,
+                &mut ext,
+            )
+            .await?;
 
         // Run consensus engine to completion
         let (tx, rx) = oneshot::channel();
         info!(target: "reth::cli", "Starting consensus engine");
-        executor.spawn_critical_blocking("consensus engine", async move {
+        engine_executor.spawn_critical_blocking("consensus engine", async move {
+            let _component = set_reader_component("consensus engine");
             let res = beacon_consensus_engine.await;
             let _ = tx.send(res);
         });
@@ -1291,6 +1442,8 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
             rpc_server_handles,
             consensus_engine_rx: rx,
             terminate: self.config.debug.terminate,
+            beacon_engine_handle,
+            network,
         };
         Ok(node_handle)
     }
@@ -1334,8 +1487,15 @@ pub struct NodeHandle {
     /// This can be used to wait for the consensus engine to exit.
     consensus_engine_rx: oneshot::Receiver<Result<(), BeaconConsensusEngineError>>,
 
-    /// Flag indicating whether the node should be terminated after the pipeline sync.
+    /// Flag indicating whether the node should be terminated after the pipeline sync or once the
+    /// max block is reached.
     terminate: bool,
+
+    /// The handle to the consensus engine, used to shut it down.
+    beacon_engine_handle: BeaconConsensusEngineHandle,
+
+    /// The handle to the network, used to disconnect the peers on shutdown.
+    network: NetworkHandle,
 }
 
 impl NodeHandle {
@@ -1357,6 +1517,74 @@ impl NodeHandle {
             futures::future::pending().await
         }
     }
+
+    /// Waits for the node to exit like [NodeHandle::wait_for_node_exit], or until the `shutdown`
+    /// signal fires. The node is then shut down in order, see [NodeHandle::shutdown].
+    pub async fn run_until_shutdown(mut self, shutdown: Shutdown) -> eyre::Result<()> {
+        tokio::select! {
+            res = &mut self.consensus_engine_rx => {
+                res??;
+                info!(target: "reth::cli", "Consensus engine has exited.");
+
+                if self.terminate {
+                    return Ok(())
+                }
+                // Keep other node components alive until the shutdown signal, same as
+                // `wait_for_node_exit`.
+                shutdown.await;
+            }
+            _ = shutdown.clone() => {}
+        }
+
+        self.shutdown().await
+    }
+
+    /// Shuts the node down in order:
+    ///
+    /// 1. Stops the RPC servers, so no new requests are accepted.
+    /// 2. Lets the consensus engine process the messages it already received, and stops the
+    ///    pipeline at its next checkpoint.
+    /// 3. Disconnects all peers with a `ClientQuitting` message.
+    ///
+    /// The remaining tasks, for example the transaction pool backup and the peers file writer, are
+    /// finished by the graceful shutdown of the [TaskManager] after the node.
+    pub async fn shutdown(self) -> eyre::Result<()> {
+        let Self { rpc_server_handles, consensus_engine_rx, beacon_engine_handle, network, .. } =
+            self;
+        info!(target: "reth::cli", "Shutting down the node");
+
+        // the remaining components are stopped even if a server is gone already
+        if let Err(err) = rpc_server_handles.rpc.stop() {
+            warn!(target: "reth::cli", %err, "RPC server failed to stop");
+        }
+        if let Err(err) = rpc_server_handles.auth.stop() {
+            warn!(target: "reth::cli", %err, "Auth server failed to stop");
+        }
+        debug!(target: "reth::cli", "RPC servers stopped");
+
+        // the engine is gone already if it exited on its own
+        if beacon_engine_handle.shutdown().await.is_ok() {
+            if let Ok(Err(err)) = consensus_engine_rx.await {
+                warn!(target: "reth::cli", %err, "Consensus engine failed to shut down");
+            }
+        }
+        debug!(target: "reth::cli", "Consensus engine stopped");
+
+        let _ = network.shutdown().await;
+        info!(target: "reth::cli", "Node shut down");
+        Ok(())
+    }
+}
+
+/// Returns the executor of a child [TaskManager] for the tasks of a subsystem of the node.
+///
+/// The panic of a critical task of the subsystem is forwarded to the manager of the given
+/// executor, so it shuts down the node.
+fn subsystem_executor(executor: &TaskExecutor) -> TaskExecutor {
+    let manager = executor.child_manager();
+    let subsystem = manager.executor();
+    executor.forward_panics(manager);
+    subsystem
 }
 
 /// A simple function to launch a node with the specified [NodeConfig], spawning tasks on the
@@ -0,0 +1,615 @@
+//! Bootstrapping the database from a trusted state checkpoint.
+//!
+//! A checkpoint consists of a [SignedCheckpointManifest] and the chunks of the state it lists. The
+//! manifest describes the checkpoint block and is signed by a trusted signer, every chunk is
+//! verified against the hash in the manifest before it's imported, and the imported state is
+//! verified against the state root of the checkpoint block.
+//!
+//! After the import, all stages have reached the checkpoint block, so the pipeline starts syncing
+//! from there. The blocks before the checkpoint are not available, except for the canonical hashes
+//! of the [ancestors](CheckpointManifest::ancestor_hashes) that are required to execute the
+//! following blocks, and are recorded as pruned.
+
+use crate::init::insert_genesis_header;
+use alloy_rlp::{Decodable, RlpDecodable, RlpEncodable};
+use reth_db::{
+    cursor::DbCursorRO,
+    database::Database,
+    models::StoredBlockBodyIndices,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_interfaces::provider::ProviderError;
+use reth_primitives::{
+    keccak256,
+    stage::{StageCheckpoint, StageId},
+    Account, Address, Bytecode, Bytes, ChainSpec, Header, PruneCheckpoint, PruneMode, PruneSegment,
+    Signature, StorageEntry, B256, U256,
+};
+use reth_provider::{
+    bundle_state::mark_bytecode_refs_tracked, BytecodeRefCounter, DatabaseProviderRW,
+    HashingWriter, ProviderFactory, PruneCheckpointWriter,
+};
+use reth_trie::{StateRoot, StateRootError, StateRootProgress};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{debug, info};
+
+/// The prune segments whose data before the checkpoint block is missing after the import.
+const MISSING_SEGMENTS: [PruneSegment; 7] = [
+    PruneSegment::SenderRecovery,
+    PruneSegment::TransactionLookup,
+    PruneSegment::Receipts,
+    PruneSegment::AccountHistory,
+    PruneSegment::StorageHistory,
+    PruneSegment::Headers,
+    PruneSegment::Transactions,
+];
+
+/// Checkpoint import error type.
+#[derive(Debug, thiserror::Error)]
+pub enum CheckpointError {
+    /// The manifest isn't signed by a trusted signer.
+    #[error("checkpoint manifest is signed by untrusted signer {0:?}")]
+    UntrustedSigner(Option<Address>),
+    /// The manifest belongs to another chain.
+    #[error("checkpoint is for chain {manifest}, but the node runs chain {expected}")]
+    ChainMismatch {
+        /// The chain ID of the node.
+        expected: u64,
+        /// The chain ID of the manifest.
+        manifest: u64,
+    },
+    /// The ancestor hashes of the manifest don't end with the parent of the checkpoint block.
+    #[error("checkpoint ancestor hashes don't end with the parent hash {0}")]
+    AncestorMismatch(B256),
+    /// The hash of a chunk doesn't match the manifest.
+    #[error("checkpoint chunk {name} has hash {got}, expected {expected}")]
+    ChunkHashMismatch {
+        /// The name of the chunk.
+        name: String,
+        /// The hash of the manifest.
+        expected: B256,
+        /// The hash of the chunk.
+        got: B256,
+    },
+    /// A chunk couldn't be decoded.
+    #[error("checkpoint chunk {0} is malformed: {1}")]
+    MalformedChunk(String, #[source] alloy_rlp::Error),
+    /// The imported state doesn't match the state root of the checkpoint block.
+    #[error("imported state has root {got}, expected {expected}")]
+    StateRootMismatch {
+        /// The state root of the checkpoint block.
+        expected: B256,
+        /// The root of the imported state.
+        got: B256,
+    },
+    /// The database already contains blocks.
+    #[error("checkpoints can only be imported into an empty database")]
+    DatabaseNotEmpty,
+    /// Error while computing the state root.
+    #[error(transparent)]
+    StateRoot(#[from] StateRootError),
+    /// Provider error.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+impl From<reth_db::DatabaseError> for CheckpointError {
+    fn from(error: reth_db::DatabaseError) -> Self {
+        Self::Provider(ProviderError::Database(error))
+    }
+}
+
+/// Describes the state at a checkpoint block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointManifest {
+    /// The ID of the chain.
+    pub chain_id: u64,
+    /// The header of the checkpoint block.
+    pub header: Header,
+    /// The total difficulty at the checkpoint block.
+    pub total_difficulty: U256,
+    /// The canonical hashes of the blocks before the checkpoint block, in ascending order and
+    /// ending with its parent.
+    ///
+    /// These are needed for the `BLOCKHASH` opcode, so this should contain the 256 most recent
+    /// ones.
+    pub ancestor_hashes: Vec<B256>,
+    /// The chunks of the state, in import order.
+    pub chunks: Vec<CheckpointChunk>,
+}
+
+impl CheckpointManifest {
+    /// Returns the hash the manifest is signed over.
+    ///
+    /// This is the keccak256 hash of the chain ID, the hash of the checkpoint block, the total
+    /// difficulty, the number of ancestor hashes, the ancestor hashes and the chunk hashes, all
+    /// big endian and concatenated. The names of the chunks only locate them, so they're not
+    /// signed.
+    pub fn signature_hash(&self) -> B256 {
+        let mut buf =
+            Vec::with_capacity(80 + (self.ancestor_hashes.len() + self.chunks.len()) * 32);
+        buf.extend_from_slice(&self.chain_id.to_be_bytes());
+        buf.extend_from_slice(self.header.hash_slow().as_slice());
+        buf.extend_from_slice(&self.total_difficulty.to_be_bytes::<32>());
+        buf.extend_from_slice(&(self.ancestor_hashes.len() as u64).to_be_bytes());
+        for hash in &self.ancestor_hashes {
+            buf.extend_from_slice(hash.as_slice());
+        }
+        for chunk in &self.chunks {
+            buf.extend_from_slice(chunk.hash.as_slice());
+        }
+        keccak256(buf)
+    }
+
+    /// Checks that the manifest belongs to the given chain and that the ancestor hashes end with
+    /// the parent of the checkpoint block.
+    pub fn validate(&self, chain: &ChainSpec) -> Result<(), CheckpointError> {
+        let expected = chain.chain.id();
+        if self.chain_id != expected {
+            return Err(CheckpointError::ChainMismatch { expected, manifest: self.chain_id })
+        }
+        if self.header.number > 0 && self.ancestor_hashes.last() != Some(&self.header.parent_hash) {
+            return Err(CheckpointError::AncestorMismatch(self.header.parent_hash))
+        }
+        Ok(())
+    }
+}
+
+/// A [CheckpointManifest] with the signature of its signer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedCheckpointManifest {
+    /// The manifest.
+    pub manifest: CheckpointManifest,
+    /// The signature over the [CheckpointManifest::signature_hash].
+    pub signature: Signature,
+}
+
+impl SignedCheckpointManifest {
+    /// Returns the address of the signer, if the signature is valid.
+    pub fn signer(&self) -> Option<Address> {
+        self.signature.recover_signer(self.manifest.signature_hash())
+    }
+
+    /// Returns the manifest if it's signed by one of the trusted signers.
+    pub fn verify(
+        self,
+        trusted_signers: &[Address],
+    ) -> Result<CheckpointManifest, CheckpointError> {
+        let signer = self.signer();
+        if !signer.is_some_and(|signer| trusted_signers.contains(&signer)) {
+            return Err(CheckpointError::UntrustedSigner(signer))
+        }
+        Ok(self.manifest)
+    }
+}
+
+/// A chunk of the state of a checkpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointChunk {
+    /// The name of the chunk, relative to the location of the manifest.
+    pub name: String,
+    /// The keccak256 hash of the chunk.
+    pub hash: B256,
+}
+
+impl CheckpointChunk {
+    /// Returns `true` if the given bytes are the content of the chunk.
+    pub fn matches(&self, bytes: &[u8]) -> bool {
+        keccak256(bytes) == self.hash
+    }
+
+    /// Verifies the hash of the given bytes and decodes the accounts of the chunk.
+    ///
+    /// The content of a chunk is the RLP encoded list of its [CheckpointAccount]s.
+    pub fn decode(&self, bytes: &[u8]) -> Result<Vec<CheckpointAccount>, CheckpointError> {
+        let got = keccak256(bytes);
+        if got != self.hash {
+            return Err(CheckpointError::ChunkHashMismatch {
+                name: self.name.clone(),
+                expected: self.hash,
+                got,
+            })
+        }
+        Vec::<CheckpointAccount>::decode(&mut &bytes[..])
+            .map_err(|err| CheckpointError::MalformedChunk(self.name.clone(), err))
+    }
+}
+
+/// An account in a [CheckpointChunk].
+///
+/// An account whose storage doesn't fit into one chunk is repeated in the following chunks with
+/// the rest of its storage.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct CheckpointAccount {
+    /// The address of the account.
+    pub address: Address,
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The code of the account, empty if it has none.
+    pub code: Bytes,
+    /// The non-zero storage slots of the account.
+    pub storage: Vec<CheckpointStorageSlot>,
+}
+
+/// A storage slot of a [CheckpointAccount].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct CheckpointStorageSlot {
+    /// The key of the slot.
+    pub key: B256,
+    /// The value of the slot.
+    pub value: U256,
+}
+
+/// Returns an error if the database already contains blocks.
+pub fn ensure_empty<DB: Database>(
+    provider: &DatabaseProviderRW<&DB>,
+) -> Result<(), CheckpointError> {
+    if provider.tx_ref().cursor_read::<tables::CanonicalHeaders>()?.first()?.is_some() {
+        return Err(CheckpointError::DatabaseNotEmpty)
+    }
+    Ok(())
+}
+
+/// Removes the state written by an interrupted import, so the chunks can be imported again.
+///
+/// The database must not contain blocks, see [ensure_empty].
+pub fn clear_checkpoint_state<DB: Database>(
+    provider: &DatabaseProviderRW<&DB>,
+) -> Result<(), CheckpointError> {
+    let tx = provider.tx_ref();
+    tx.clear::<tables::PlainAccountState>()?;
+    tx.clear::<tables::PlainStorageState>()?;
+    tx.clear::<tables::HashedAccount>()?;
+    tx.clear::<tables::HashedStorage>()?;
+    tx.clear::<tables::Bytecodes>()?;
+    tx.clear::<tables::BytecodeRefs>()?;
+    tx.clear::<tables::AccountsTrie>()?;
+    tx.clear::<tables::StoragesTrie>()?;
+    Ok(())
+}
+
+/// Writes the accounts of a chunk to the plain and hashed state.
+pub fn insert_checkpoint_accounts<DB: Database>(
+    provider: &DatabaseProviderRW<&DB>,
+    accounts: Vec<CheckpointAccount>,
+) -> Result<(), CheckpointError> {
+    let tx = provider.tx_ref();
+    let mut bytecode_refs = BytecodeRefCounter::new(tx.cursor_write::<tables::BytecodeRefs>()?);
+    let mut hashed_accounts = Vec::with_capacity(accounts.len());
+    let mut hashed_storages = Vec::new();
+
+    for CheckpointAccount { address, nonce, balance, code, storage } in accounts {
+        let bytecode_hash = if code.is_empty() {
+            None
+        } else {
+            let bytecode = Bytecode::new_raw(code);
+            let hash = bytecode.hash_slow();
+            tx.put::<tables::Bytecodes>(hash, bytecode)?;
+            Some(hash)
+        };
+        let account = Account { nonce, balance, bytecode_hash };
+        // an account whose storage spans several chunks is listed in each of them, but its
+        // bytecode is only referenced once
+        match tx.get::<tables::PlainAccountState>(address)? {
+            Some(previous) => {
+                if let Some(hash) = bytecode_refs.replace(Some(&previous), Some(&account))? {
+                    tx.delete::<tables::Bytecodes>(hash, None)?;
+                }
+            }
+            None => bytecode_refs.increment(Some(&account))?,
+        }
+        tx.put::<tables::PlainAccountState>(address, account)?;
+        hashed_accounts.push((address, Some(account)));
+
+        let storage = storage
+            .into_iter()
+            .filter(|slot| slot.value != U256::ZERO)
+            .map(|slot| StorageEntry { key: slot.key, value: slot.value })
+            .collect::<Vec<_>>();
+        for entry in &storage {
+            tx.put::<tables::PlainStorageState>(address, *entry)?;
+        }
+        if !storage.is_empty() {
+            hashed_storages.push((address, storage));
+        }
+    }
+
+    provider.insert_account_for_hashing(hashed_accounts)?;
+    provider.insert_storage_for_hashing(hashed_storages)?;
+    Ok(())
+}
+
+/// Computes the root of the imported state and writes the trie, once all chunks are inserted.
+///
+/// The trie is computed incrementally, each step is committed in its own transaction.
+pub fn compute_checkpoint_state_root<DB: Database>(
+    factory: &ProviderFactory<&DB>,
+) -> Result<B256, CheckpointError> {
+    let mut intermediate_state = None;
+    loop {
+        let provider = factory.provider_rw()?;
+        let tx = provider.tx_ref();
+        let progress = StateRoot::from_tx(tx)
+            .with_intermediate_state(intermediate_state.take())
+            .root_with_progress()?;
+        match progress {
+            StateRootProgress::Progress(state, hashed_entries_walked, updates) => {
+                updates.flush(tx)?;
+                provider.commit()?;
+                debug!(target: "reth::cli", hashed_entries_walked, "Committed state root progress");
+                intermediate_state = Some(*state);
+            }
+            StateRootProgress::Complete(root, _, updates) => {
+                updates.flush(tx)?;
+                provider.commit()?;
+                return Ok(root)
+            }
+        }
+    }
+}
+
+/// Finishes the import of the checkpoint, once its state root is computed.
+///
+/// Verifies the state root against the checkpoint block, writes the genesis and checkpoint
+/// headers and the ancestor hashes, records the missing blocks before the checkpoint block as
+/// pruned, and sets the checkpoints of all stages to the checkpoint block.
+pub fn finish_checkpoint_import<DB: Database>(
+    provider: &DatabaseProviderRW<&DB>,
+    chain: Arc<ChainSpec>,
+    manifest: &CheckpointManifest,
+    state_root: B256,
+) -> Result<(), CheckpointError> {
+    let tx = provider.tx_ref();
+    let header = manifest.header.clone().seal_slow();
+
+    if state_root != header.state_root {
+        return Err(CheckpointError::StateRootMismatch {
+            expected: header.state_root,
+            got: state_root,
+        })
+    }
+
+    insert_genesis_header::<DB>(tx, chain)?;
+
+    let first_ancestor = header.number.saturating_sub(manifest.ancestor_hashes.len() as u64);
+    for (number, hash) in (first_ancestor..).zip(&manifest.ancestor_hashes) {
+        // the genesis is already written
+        if number > 0 {
+            tx.put::<tables::CanonicalHeaders>(number, *hash)?;
+            tx.put::<tables::HeaderNumbers>(*hash, number)?;
+        }
+    }
+
+    tx.put::<tables::CanonicalHeaders>(header.number, header.hash)?;
+    tx.put::<tables::HeaderNumbers>(header.hash, header.number)?;
+    tx.put::<tables::BlockBodyIndices>(header.number, StoredBlockBodyIndices::default())?;
+    tx.put::<tables::HeaderTD>(header.number, manifest.total_difficulty.into())?;
+    tx.put::<tables::Headers>(header.number, header.header.clone())?;
+
+    for stage in StageId::ALL {
+        tx.put::<tables::SyncStage>(stage.to_string(), StageCheckpoint::new(header.number))?;
+    }
+
+    // the references of all imported accounts are counted, and there are no historical states
+    mark_bytecode_refs_tracked(tx)?;
+
+    for segment in MISSING_SEGMENTS {
+        provider.save_prune_checkpoint(
+            segment,
+            PruneCheckpoint {
+                block_number: Some(header.number),
+                tx_number: None,
+                prune_mode: PruneMode::Before(header.number + 1),
+            },
+        )?;
+    }
+
+    info!(target: "reth::cli", number = header.number, hash = ?header.hash, "Checkpoint imported");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use reth_db::{cursor::DbDupCursorRO, test_utils::create_test_rw_db};
+    use reth_primitives::{sign_message, MAINNET};
+    use reth_provider::{
+        bundle_state::bytecode_refs_tracked, AccountReader, BlockHashReader, HeaderProvider,
+        PruneCheckpointReader, StageCheckpointReader,
+    };
+
+    fn encode_chunk(accounts: &[CheckpointAccount]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        accounts.to_vec().encode(&mut buf);
+        buf
+    }
+
+    #[test]
+    fn verify_manifest_signature() {
+        let secret = B256::with_last_byte(1);
+        let manifest = CheckpointManifest {
+            chain_id: 1,
+            header: Header { number: 300, ..Default::default() },
+            total_difficulty: U256::from(1),
+            ancestor_hashes: vec![B256::ZERO],
+            chunks: vec![CheckpointChunk { name: "0.rlp".to_string(), hash: B256::random() }],
+        };
+        let signature = sign_message(secret, manifest.signature_hash()).unwrap();
+        let signed = SignedCheckpointManifest { manifest: manifest.clone(), signature };
+        let signer = signed.signer().unwrap();
+
+        assert_eq!(signed.clone().verify(&[signer]).unwrap(), manifest);
+        assert!(matches!(
+            signed.clone().verify(&[Address::ZERO]),
+            Err(CheckpointError::UntrustedSigner(Some(_)))
+        ));
+
+        // the signature doesn't cover a modified manifest
+        let mut tampered = signed;
+        tampered.manifest.chunks[0].hash = B256::random();
+        assert!(tampered.verify(&[signer]).is_err());
+    }
+
+    #[test]
+    fn import_checkpoint() {
+        let accounts = vec![
+            CheckpointAccount {
+                address: Address::with_last_byte(1),
+                nonce: 1,
+                balance: U256::from(10),
+                code: Bytes::default(),
+                storage: vec![],
+            },
+            CheckpointAccount {
+                address: Address::with_last_byte(2),
+                nonce: 0,
+                balance: U256::ZERO,
+                code: Bytes::from_static(&[0x60, 0x00]),
+                storage: vec![CheckpointStorageSlot {
+                    key: B256::with_last_byte(1),
+                    value: U256::from(2),
+                }],
+            },
+        ];
+        let bytes = encode_chunk(&accounts);
+        let chunk = CheckpointChunk { name: "0.rlp".to_string(), hash: keccak256(&bytes) };
+        assert_eq!(chunk.decode(&bytes).unwrap(), accounts);
+        assert!(matches!(
+            chunk.decode(&bytes[1..]),
+            Err(CheckpointError::ChunkHashMismatch { .. })
+        ));
+
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db.as_ref(), MAINNET.clone());
+
+        // compute the state root of the accounts in a scratch database
+        let state_root = {
+            let scratch = create_test_rw_db();
+            let scratch = ProviderFactory::new(scratch.as_ref(), MAINNET.clone());
+            let provider = scratch.provider_rw().unwrap();
+            insert_checkpoint_accounts(&provider, accounts.clone()).unwrap();
+            StateRoot::from_tx(provider.tx_ref()).root().unwrap()
+        };
+        let parent_hash = B256::random();
+        let manifest = CheckpointManifest {
+            chain_id: MAINNET.chain.id(),
+            header: Header { number: 300, parent_hash, state_root, ..Default::default() },
+            total_difficulty: U256::from(1000),
+            ancestor_hashes: vec![B256::random(), parent_hash],
+            chunks: vec![chunk.clone()],
+        };
+        manifest.validate(&MAINNET).unwrap();
+
+        let provider = factory.provider_rw().unwrap();
+        ensure_empty(&provider).unwrap();
+        // an interrupted import is started over
+        insert_checkpoint_accounts(&provider, chunk.decode(&bytes).unwrap()).unwrap();
+        clear_checkpoint_state(&provider).unwrap();
+        insert_checkpoint_accounts(&provider, chunk.decode(&bytes).unwrap()).unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(compute_checkpoint_state_root(&factory).unwrap(), state_root);
+        let provider = factory.provider_rw().unwrap();
+        finish_checkpoint_import(&provider, MAINNET.clone(), &manifest, state_root).unwrap();
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.block_hash(300).unwrap(), Some(manifest.header.hash_slow()));
+        assert_eq!(provider.block_hash(299).unwrap(), Some(parent_hash));
+        assert_eq!(provider.block_hash(0).unwrap(), Some(MAINNET.genesis_hash()));
+        assert_eq!(provider.header_td_by_number(300).unwrap(), Some(U256::from(1000)));
+        assert_eq!(
+            provider.basic_account(Address::with_last_byte(1)).unwrap().map(|acc| acc.nonce),
+            Some(1)
+        );
+        for stage in StageId::ALL {
+            assert_eq!(provider.get_stage_checkpoint(stage).unwrap().unwrap().block_number, 300);
+        }
+        for segment in MISSING_SEGMENTS {
+            assert_eq!(
+                provider.get_prune_checkpoint(segment).unwrap().unwrap().block_number,
+                Some(300)
+            );
+        }
+        let code_hash = keccak256([0x60, 0x00]);
+        assert_eq!(provider.tx_ref().get::<tables::BytecodeRefs>(code_hash).unwrap(), Some(1));
+        assert!(bytecode_refs_tracked(provider.tx_ref()).unwrap());
+
+        // the database isn't empty anymore
+        let provider = factory.provider_rw().unwrap();
+        assert!(matches!(ensure_empty(&provider), Err(CheckpointError::DatabaseNotEmpty)));
+    }
+
+    #[test]
+    fn import_account_split_across_chunks() {
+        let account = |key| CheckpointAccount {
+            address: Address::with_last_byte(1),
+            nonce: 0,
+            balance: U256::ZERO,
+            code: Bytes::from_static(&[0x60, 0x00]),
+            storage: vec![CheckpointStorageSlot {
+                key: B256::with_last_byte(key),
+                value: U256::from(1),
+            }],
+        };
+
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db.as_ref(), MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        insert_checkpoint_accounts(&provider, vec![account(1)]).unwrap();
+        insert_checkpoint_accounts(&provider, vec![account(2)]).unwrap();
+
+        let tx = provider.tx_ref();
+        let code_hash = keccak256([0x60, 0x00]);
+        assert_eq!(tx.get::<tables::BytecodeRefs>(code_hash).unwrap(), Some(1));
+        assert!(tx.get::<tables::Bytecodes>(code_hash).unwrap().is_some());
+        assert_eq!(
+            tx.cursor_dup_read::<tables::PlainStorageState>()
+                .unwrap()
+                .walk_dup(Some(Address::with_last_byte(1)), None)
+                .unwrap()
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn reject_state_root_mismatch() {
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db.as_ref(), MAINNET.clone());
+        let manifest = CheckpointManifest {
+            chain_id: MAINNET.chain.id(),
+            header: Header { number: 1, state_root: B256::random(), ..Default::default() },
+            total_difficulty: U256::ZERO,
+            ancestor_hashes: vec![],
+            chunks: vec![],
+        };
+
+        let state_root = compute_checkpoint_state_root(&factory).unwrap();
+        let provider = factory.provider_rw().unwrap();
+        assert!(matches!(
+            finish_checkpoint_import(&provider, MAINNET.clone(), &manifest, state_root),
+            Err(CheckpointError::StateRootMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn reject_missing_ancestor_hashes() {
+        let mut manifest = CheckpointManifest {
+            chain_id: MAINNET.chain.id(),
+            header: Header { number: 1, parent_hash: B256::random(), ..Default::default() },
+            total_difficulty: U256::ZERO,
+            ancestor_hashes: vec![],
+            chunks: vec![],
+        };
+        assert!(matches!(manifest.validate(&MAINNET), Err(CheckpointError::AncestorMismatch(_))));
+
+        manifest.ancestor_hashes.push(manifest.header.parent_hash);
+        manifest.validate(&MAINNET).unwrap();
+    }
+}
@@ -4,8 +4,9 @@ use reth_network::{NetworkEvents, NetworkProtocols};
 use reth_network_api::{NetworkInfo, Peers};
 use reth_primitives::ChainSpec;
 use reth_provider::{
-    AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    EvmEnvProvider, StateProviderFactory,
+    AccountReader, AddressAppearancesReader, BlockReaderIdExt, CanonStateSubscriptions,
+    ChainSpecProvider, ChangeSetReader, ContractCreationsReader, EvmEnvProvider,
+    InternalTransfersReader, StageCheckpointReader, StateProviderFactory, WithdrawalHistoryReader,
 };
 use reth_rpc_builder::{
     auth::AuthServerHandle, RethModuleRegistry, RpcServerHandle, TransportRpcModules,
@@ -22,6 +23,11 @@ pub trait FullProvider:
     + EvmEnvProvider
     + ChainSpecProvider
     + ChangeSetReader
+    + InternalTransfersReader
+    + ContractCreationsReader
+    + AddressAppearancesReader
+    + WithdrawalHistoryReader
+    + StageCheckpointReader
     + Clone
     + Unpin
     + 'static
@@ -35,6 +41,11 @@ impl<T> FullProvider for T where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + InternalTransfersReader
+        + ContractCreationsReader
+        + AddressAppearancesReader
+        + WithdrawalHistoryReader
+        + StageCheckpointReader
         + Clone
         + Unpin
         + 'static
@@ -11,7 +11,9 @@ use reth_rpc_builder::{
     auth::AuthServerConfig, error::RpcError, EthConfig, IpcServerBuilder, RpcServerConfig,
     ServerBuilder, TransportRpcModuleConfig,
 };
-use reth_transaction_pool::PoolConfig;
+use reth_transaction_pool::{
+    blobstore::DiskFileBlobStoreConfig, PoolConfig, BLOBSTORE_MAX_SIZE_MB_DEFAULT,
+};
 use std::{borrow::Cow, path::PathBuf, time::Duration};
 
 /// A trait that provides a configured RPC server.
@@ -144,4 +146,12 @@ impl<C> RethNetworkConfig for reth_network::NetworkManager<C> {
 pub trait RethTransactionPoolConfig {
     /// Returns transaction pool configuration.
     fn pool_config(&self) -> PoolConfig;
+
+    /// Returns the configuration of the disk blob store.
+    fn blob_store_config(&self) -> DiskFileBlobStoreConfig {
+        DiskFileBlobStoreConfig {
+            max_size: Some(BLOBSTORE_MAX_SIZE_MB_DEFAULT * 1024 * 1024),
+            ..Default::default()
+        }
+    }
 }
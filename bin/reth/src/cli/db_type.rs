@@ -3,6 +3,7 @@
 use crate::dirs::{ChainPath, DataDirPath, MaybePlatformPath};
 use reth_db::{
     init_db,
+    mdbx::readers::ReadTxLimits,
     test_utils::{create_test_rw_db, TempDatabase},
     DatabaseEnv,
 };
@@ -29,14 +30,16 @@ impl DatabaseBuilder {
     /// Initializes and returns the [DatabaseInstance] depending on the current database type.
     ///
     /// If the [DatabaseBuilder] is test, then the [ChainPath] constructed will be derived from the
-    /// db path of the [TempDatabase] and the given chain. The [LogLevel] will not be used.
+    /// db path of the [TempDatabase] and the given chain. The [LogLevel] and [ReadTxLimits] will
+    /// not be used.
     ///
     /// If the [DatabaseBuilder] is real, then the db will be initialized using the given log level
-    /// and the [ChainPath] will be derived from the given path and chain. This database path is
-    /// then passed into [init_db].
+    /// and read transaction limits, and the [ChainPath] will be derived from the given path and
+    /// chain. This database path is then passed into [init_db].
     pub fn init_db(
         self,
         log_level: Option<LogLevel>,
+        read_tx_limits: ReadTxLimits,
         chain: Chain,
     ) -> eyre::Result<DatabaseInstance> {
         match self {
@@ -54,7 +57,9 @@ impl DatabaseBuilder {
                 let db_path = data_dir.db_path();
 
                 tracing::info!(target: "reth::cli", path = ?db_path, "Opening database");
-                let db = Arc::new(init_db(db_path.clone(), log_level)?);
+                let db = Arc::new(
+                    init_db(db_path.clone(), log_level)?.with_read_tx_limits(read_tx_limits),
+                );
                 Ok(DatabaseInstance::Real { db, data_dir })
             }
         }
@@ -111,7 +116,7 @@ mod tests {
         let expected_db_path = tempdir.path().join("db");
         let datadir_path = MaybePlatformPath::<DataDirPath>::from(tempdir.path().to_path_buf());
         let db = DatabaseBuilder::Real(datadir_path);
-        let db = db.init_db(None, Chain::mainnet()).unwrap();
+        let db = db.init_db(None, ReadTxLimits::default(), Chain::mainnet()).unwrap();
 
         // ensure that the datadir path is correct
         assert_eq!(db.data_dir().data_dir_path(), expected_datadir_path);
@@ -7,7 +7,8 @@ use crate::{
     },
     cli::ext::RethCliExt,
     commands::{
-        config_cmd, db, debug_cmd, import, init_cmd, node, p2p, recover, stage, test_vectors,
+        checkpoint_sync, config_cmd, db, debug_cmd, import, init_cmd, node, p2p, recover, rpc,
+        stage, test_vectors,
     },
     runner::CliRunner,
     version::{LONG_VERSION, SHORT_VERSION},
@@ -83,9 +84,15 @@ impl<Ext: RethCliExt> Cli<Ext> {
 
         let runner = CliRunner;
         match self.command {
-            Commands::Node(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
+            Commands::Node(command) => runner
+                .run_command_until_graceful_exit(command.shutdown_timeout, |ctx, shutdown| {
+                    command.execute_until_shutdown(ctx, shutdown)
+                }),
             Commands::Init(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Import(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::CheckpointSync(command) => {
+                runner.run_blocking_until_ctrl_c(command.execute())
+            }
             Commands::Db(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Stage(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::P2P(command) => runner.run_until_ctrl_c(command.execute()),
@@ -93,6 +100,7 @@ impl<Ext: RethCliExt> Cli<Ext> {
             Commands::Config(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Debug(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::Recover(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
+            Commands::Rpc(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
         }
     }
 
@@ -139,6 +147,9 @@ pub enum Commands<Ext: RethCliExt = ()> {
     /// This syncs RLP encoded blocks from a file.
     #[command(name = "import")]
     Import(import::ImportCommand),
+    /// Bootstrap the database from a trusted state checkpoint.
+    #[command(name = "checkpoint-sync")]
+    CheckpointSync(checkpoint_sync::CheckpointSyncCommand),
     /// Database debugging utilities
     #[command(name = "db")]
     Db(db::Command),
@@ -160,6 +171,9 @@ pub enum Commands<Ext: RethCliExt = ()> {
     /// Scripts for node recovery
     #[command(name = "recover")]
     Recover(recover::Command),
+    /// Serve the JSON-RPC API from an existing database without syncing
+    #[command(name = "rpc")]
+    Rpc(rpc::Command),
 }
 
 impl<Ext: RethCliExt> Commands<Ext> {
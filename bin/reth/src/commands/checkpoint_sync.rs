@@ -0,0 +1,221 @@
+//! Command that bootstraps the database from a trusted state checkpoint.
+
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    checkpoint::{
+        clear_checkpoint_state, compute_checkpoint_state_root, ensure_empty,
+        finish_checkpoint_import, insert_checkpoint_accounts, CheckpointManifest,
+        SignedCheckpointManifest,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::Parser;
+use eyre::Context;
+use reth_db::init_db;
+use reth_primitives::{fs, Address, ChainSpec};
+use reth_provider::ProviderFactory;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::{debug, info, warn};
+
+/// Downloads a trusted state checkpoint and imports it into an empty database.
+///
+/// The node then starts syncing from the checkpoint block instead of the genesis.
+#[derive(Debug, Parser)]
+pub struct CheckpointSyncCommand {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[clap(flatten)]
+    db: DatabaseArgs,
+
+    /// The URLs or paths of the signed checkpoint manifest, tried in order.
+    ///
+    /// The chunks of the checkpoint are downloaded from the same location as the manifest.
+    #[arg(long = "manifest", value_name = "URL_OR_PATH", required = true)]
+    manifests: Vec<String>,
+
+    /// The addresses whose signatures on the manifest are trusted.
+    #[arg(long = "trusted-signer", value_name = "ADDRESS", required = true)]
+    trusted_signers: Vec<Address>,
+}
+
+impl CheckpointSyncCommand {
+    /// Execute the `checkpoint-sync` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", "reth checkpoint-sync starting");
+
+        // add network name to data dir
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db_path = data_dir.db_path();
+        info!(target: "reth::cli", path = ?db_path, "Opening database");
+        let db = Arc::new(init_db(&db_path, self.db.log_level)?);
+        info!(target: "reth::cli", "Database opened");
+
+        let factory = ProviderFactory::new(&db, self.chain.clone());
+        let provider_rw = factory.provider_rw()?;
+        ensure_empty(&provider_rw)?;
+        clear_checkpoint_state(&provider_rw)?;
+        provider_rw.commit()?;
+
+        let (location, manifest) = self.fetch_manifest().await?;
+        info!(
+            target: "reth::cli",
+            %location,
+            number = manifest.header.number,
+            chunks = manifest.chunks.len(),
+            "Checkpoint manifest verified"
+        );
+
+        // the chunks are kept on disk until the import succeeded, so an interrupted sync doesn't
+        // download them again
+        let chunks_dir = data_dir.checkpoint_path();
+        fs::create_dir_all(&chunks_dir)?;
+        let mut chunk_paths = Vec::with_capacity(manifest.chunks.len());
+        for (index, chunk) in manifest.chunks.iter().enumerate() {
+            let path = chunks_dir.join(format!("{index}.rlp"));
+            if fs::read(&path).map(|bytes| chunk.matches(&bytes)).unwrap_or_default() {
+                info!(target: "reth::cli", name = %chunk.name, "Checkpoint chunk already downloaded");
+            } else {
+                info!(target: "reth::cli", name = %chunk.name, index, "Downloading checkpoint chunk");
+                let bytes = fetch(&relative_location(&location, &chunk.name)).await?;
+                // fail early, the hash is checked again before the import
+                chunk.decode(&bytes)?;
+                fs::write(&path, &bytes)?;
+            }
+            chunk_paths.push(path);
+        }
+
+        info!(target: "reth::cli", "Importing checkpoint");
+        for (index, (chunk, path)) in manifest.chunks.iter().zip(&chunk_paths).enumerate() {
+            let accounts = chunk.decode(&fs::read(path)?)?;
+            let provider_rw = factory.provider_rw()?;
+            insert_checkpoint_accounts(&provider_rw, accounts)?;
+            provider_rw.commit()?;
+            debug!(target: "reth::cli", name = %chunk.name, index, "Checkpoint chunk imported");
+        }
+
+        info!(target: "reth::cli", "Computing the state root of the checkpoint");
+        let state_root = compute_checkpoint_state_root(&factory)?;
+        let provider_rw = factory.provider_rw()?;
+        finish_checkpoint_import(&provider_rw, self.chain, &manifest, state_root)?;
+        provider_rw.commit()?;
+
+        fs::remove_dir_all(&chunks_dir)?;
+        info!(target: "reth::cli", number = manifest.header.number, "Checkpoint sync finished");
+        Ok(())
+    }
+
+    /// Returns the first manifest that could be fetched and is signed by a trusted signer,
+    /// together with its location.
+    async fn fetch_manifest(&self) -> eyre::Result<(String, CheckpointManifest)> {
+        for location in &self.manifests {
+            let manifest = fetch(location)
+                .await
+                .and_then(|bytes| {
+                    serde_json::from_slice::<SignedCheckpointManifest>(&bytes)
+                        .wrap_err("malformed checkpoint manifest")
+                })
+                .and_then(|signed| Ok(signed.verify(&self.trusted_signers)?))
+                .and_then(|manifest| {
+                    manifest.validate(&self.chain)?;
+                    Ok(manifest)
+                });
+            match manifest {
+                Ok(manifest) => return Ok((location.clone(), manifest)),
+                Err(err) => {
+                    warn!(target: "reth::cli", %location, %err, "Failed to fetch checkpoint manifest")
+                }
+            }
+        }
+        eyre::bail!("no valid checkpoint manifest found")
+    }
+}
+
+/// Returns the content at the given URL or path.
+async fn fetch(location: &str) -> eyre::Result<Vec<u8>> {
+    if location.starts_with("http://") || location.starts_with("https://") {
+        let response = reqwest::get(location).await?.error_for_status()?;
+        Ok(response.bytes().await?.to_vec())
+    } else {
+        Ok(fs::read(location)?)
+    }
+}
+
+/// Returns the location of the given name relative to the location of the manifest.
+fn relative_location(manifest: &str, name: &str) -> String {
+    if manifest.starts_with("http://") || manifest.starts_with("https://") {
+        match manifest.rsplit_once('/') {
+            Some((base, _)) => format!("{base}/{name}"),
+            None => name.to_string(),
+        }
+    } else {
+        Path::new(manifest)
+            .parent()
+            .map_or_else(|| PathBuf::from(name), |dir| dir.join(name))
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_checkpoint_sync_args() {
+        let command = CheckpointSyncCommand::try_parse_from([
+            "reth",
+            "--manifest",
+            "https://example.com/checkpoint/manifest.json",
+            "--manifest",
+            "/tmp/manifest.json",
+            "--trusted-signer",
+            "0x0000000000000000000000000000000000000001",
+        ])
+        .unwrap();
+        assert_eq!(command.manifests.len(), 2);
+        assert_eq!(command.trusted_signers, vec![Address::with_last_byte(1)]);
+
+        // a trusted signer is required
+        assert!(
+            CheckpointSyncCommand::try_parse_from(["reth", "--manifest", "manifest.json"]).is_err()
+        );
+    }
+
+    #[test]
+    fn chunk_locations() {
+        assert_eq!(
+            relative_location("https://example.com/checkpoint/manifest.json", "0.rlp"),
+            "https://example.com/checkpoint/0.rlp"
+        );
+        assert_eq!(
+            relative_location("/tmp/checkpoint/manifest.json", "0.rlp"),
+            "/tmp/checkpoint/0.rlp"
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use crate::commands::node::contract_creations::ContractCreationsIndexer;
+use clap::Parser;
+use reth_db::{database::Database, open_db, DatabaseEnv};
+use reth_interfaces::{db::LogLevel, provider::ProviderError};
+use reth_primitives::{stage::StageId, BlockNumber, ChainSpec};
+use reth_provider::{
+    BlockReader, ExecutorFactory, HeaderProvider, ProviderFactory, StageCheckpointReader,
+    TransactionVariant,
+};
+use reth_revm::EvmProcessorFactory;
+use reth_revm_inspectors::stack::{Hook, InspectorStackConfig};
+use reth_stages::stages::ExecutionIndexer;
+use std::{ops::RangeInclusive, path::Path, sync::Arc};
+
+/// The arguments for the `reth db backfill` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Indexes the contracts created by the blocks, see `--index.contract-creations`.
+    ///
+    /// The blocks are executed again on top of their historical state, so the account and storage
+    /// history of the blocks must not be pruned.
+    #[arg(long)]
+    contract_creations: bool,
+
+    /// The first block to index.
+    #[arg(long, default_value_t = 1)]
+    from: BlockNumber,
+
+    /// The last block to index, defaults to the last executed block.
+    #[arg(long)]
+    to: Option<BlockNumber>,
+
+    /// The number of blocks that are executed before their data is written.
+    #[arg(long, default_value_t = 1_000)]
+    commit_threshold: u64,
+}
+
+impl Command {
+    /// Execute `db backfill` command
+    pub fn execute(
+        self,
+        db_path: &Path,
+        log_level: Option<LogLevel>,
+        chain: Arc<ChainSpec>,
+    ) -> eyre::Result<()> {
+        if !self.contract_creations {
+            eyre::bail!("No index selected, e.g. --contract-creations")
+        }
+
+        let db = open_db(db_path, log_level)?;
+        let factory = ProviderFactory::new(&db, chain.clone());
+        let to = match self.to {
+            Some(to) => to,
+            None => {
+                factory
+                    .provider()?
+                    .get_stage_checkpoint(StageId::Execution)?
+                    .unwrap_or_default()
+                    .block_number
+            }
+        };
+        let from = self.from.max(1);
+        if from > to {
+            eyre::bail!("No blocks to index, the last block is {to}")
+        }
+
+        let mut indexer = ContractCreationsIndexer::default();
+        let executor_factory =
+            EvmProcessorFactory::new(chain).with_stack_config(InspectorStackConfig {
+                execution_inspectors: vec![indexer.inspector()],
+                execution_hook: Some(Hook::All),
+                ..Default::default()
+            });
+
+        let mut start = from;
+        while start <= to {
+            let end = start.saturating_add(self.commit_threshold.max(1) - 1).min(to);
+            ExecutionIndexer::<&DatabaseEnv>::reset(&mut indexer);
+            execute_blocks(&factory, &executor_factory, start..=end)?;
+
+            let provider = factory.provider_rw()?;
+            indexer.write(&provider)?;
+            provider.commit()?;
+            println!("Indexed the contracts created by blocks {start}..={end}");
+
+            start = end + 1;
+        }
+
+        Ok(())
+    }
+}
+
+/// Executes the blocks on top of the historical state before the first block, for the inspector
+/// of the executor factory.
+fn execute_blocks<DB: Database>(
+    factory: &ProviderFactory<DB>,
+    executor_factory: &EvmProcessorFactory,
+    range: RangeInclusive<BlockNumber>,
+) -> eyre::Result<()> {
+    let provider = factory.provider()?;
+    let state = factory.history_by_block_number(range.start() - 1)?;
+    let mut executor = executor_factory.with_state(state);
+    for block_number in range {
+        let td = provider
+            .header_td_by_number(block_number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+        let block = provider
+            .block_with_senders(block_number.into(), TransactionVariant::NoHash)?
+            .ok_or_else(|| ProviderError::BlockNotFound(block_number.into()))?;
+        executor.execute_and_verify_receipt(&block, td)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,313 @@
+use clap::Parser;
+use reth_db::{
+    cursor::DbCursorRO,
+    database::Database,
+    open_db, open_db_read_only,
+    table::Table,
+    tables,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use reth_interfaces::db::LogLevel;
+use reth_primitives::{
+    stage::{StageCheckpoint, StageId},
+    BlockNumber, ChainSpec, TxNumber,
+};
+use reth_provider::{
+    DatabaseProviderRW, HistoryWriter, ProviderFactory, StageCheckpointReader,
+    StageCheckpointWriter,
+};
+use std::{ops::RangeInclusive, path::Path, sync::Arc};
+
+/// The arguments for the `reth db check` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Checks that the block body indices, the transaction-to-block index and the transactions
+    /// are consistent for the whole chain.
+    #[arg(long)]
+    bodies: bool,
+
+    /// Repairs the inconsistencies by removing the bodies from the first inconsistent block on,
+    /// and the transactions that don't belong to a block.
+    ///
+    /// This is meant for inconsistencies at the tip of the chain, which can be left by an
+    /// interrupted sync. The stages that process the bodies must not have progressed to the first
+    /// inconsistent block.
+    #[arg(long, requires = "bodies")]
+    repair: bool,
+}
+
+impl Command {
+    /// Execute `db check` command
+    pub fn execute(
+        self,
+        db_path: &Path,
+        log_level: Option<LogLevel>,
+        chain: Arc<ChainSpec>,
+    ) -> eyre::Result<()> {
+        if !self.bodies {
+            eyre::bail!("No check selected, e.g. --bodies")
+        }
+
+        let report = check_bodies(&open_db_read_only(db_path, log_level)?.tx()?)?;
+        println!("Checked the bodies of {} blocks", report.checked);
+        for (block, inconsistency) in &report.inconsistencies {
+            println!("Block {block}: {inconsistency}");
+        }
+        if let Some(txs) = &report.dangling_transactions {
+            println!("Transactions {}..={} don't belong to a block", txs.start(), txs.end());
+        }
+
+        if report.is_consistent() {
+            println!("The bodies are consistent");
+            return Ok(())
+        }
+        if !self.repair {
+            eyre::bail!("The bodies are inconsistent, run with --repair to repair them")
+        }
+
+        let db = open_db(db_path, log_level)?;
+        let factory = ProviderFactory::new(&db, chain);
+        let provider = factory.provider_rw()?;
+        // the bodies could have changed since they were checked
+        let report = check_bodies(provider.tx_ref())?;
+        repair_bodies(&provider, &report)?;
+        provider.commit()?;
+        println!("Repaired the bodies, the Bodies stage continues at block {}", report.next_block);
+
+        Ok(())
+    }
+}
+
+/// An inconsistency of the body of a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BodyInconsistency {
+    /// The body indices of the previous block are missing.
+    MissingParentBody,
+    /// The first transaction doesn't follow the last transaction of the previous block.
+    FirstTransaction { expected: TxNumber, found: TxNumber },
+    /// The transaction-to-block index doesn't map the last transaction to the block.
+    TransactionBlock { last_tx: TxNumber, found: Option<BlockNumber> },
+    /// A transaction of the block is missing.
+    MissingTransaction(TxNumber),
+}
+
+impl std::fmt::Display for BodyInconsistency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingParentBody => write!(f, "the body of the previous block is missing"),
+            Self::FirstTransaction { expected, found } => {
+                write!(f, "first transaction is {found}, expected {expected}")
+            }
+            Self::TransactionBlock { last_tx, found } => {
+                write!(f, "last transaction {last_tx} is mapped to block {found:?}")
+            }
+            Self::MissingTransaction(tx) => write!(f, "transaction {tx} is missing"),
+        }
+    }
+}
+
+/// The result of [check_bodies].
+#[derive(Debug, Default)]
+pub(crate) struct BodiesReport {
+    /// The number of blocks whose bodies were checked.
+    checked: u64,
+    /// The inconsistent blocks, in ascending order.
+    inconsistencies: Vec<(BlockNumber, BodyInconsistency)>,
+    /// The transactions after the last transaction of the consistent bodies.
+    dangling_transactions: Option<RangeInclusive<TxNumber>>,
+    /// The first block after the consistent bodies.
+    next_block: BlockNumber,
+    /// The first transaction after the consistent bodies.
+    next_tx: TxNumber,
+}
+
+impl BodiesReport {
+    /// Returns `true` if no inconsistency was found.
+    fn is_consistent(&self) -> bool {
+        self.inconsistencies.is_empty() && self.dangling_transactions.is_none()
+    }
+}
+
+/// Checks that the body indices of all blocks follow each other without gaps, that the last
+/// transaction of each block is mapped to the block, and that the first and last transactions of
+/// each block exist.
+pub(crate) fn check_bodies<TX: DbTx>(tx: &TX) -> Result<BodiesReport, DatabaseError> {
+    let mut report = BodiesReport::default();
+    let mut tx_block_cursor = tx.cursor_read::<tables::TransactionBlock>()?;
+    let mut transactions_cursor = tx.cursor_read::<tables::Transactions>()?;
+
+    // the block and transaction after the previous body
+    let mut next_block = 0;
+    let mut next_tx = 0;
+    for entry in tx.cursor_read::<tables::BlockBodyIndices>()?.walk(None)? {
+        let (number, body) = entry?;
+        report.checked += 1;
+
+        let mut inconsistencies = Vec::new();
+        if number != next_block {
+            inconsistencies.push(BodyInconsistency::MissingParentBody);
+        } else if body.first_tx_num() != next_tx {
+            inconsistencies.push(BodyInconsistency::FirstTransaction {
+                expected: next_tx,
+                found: body.first_tx_num(),
+            });
+        }
+        if !body.is_empty() {
+            let found = tx_block_cursor.seek_exact(body.last_tx_num())?.map(|(_, block)| block);
+            if found != Some(number) {
+                inconsistencies.push(BodyInconsistency::TransactionBlock {
+                    last_tx: body.last_tx_num(),
+                    found,
+                });
+            }
+            for tx_num in [body.first_tx_num(), body.last_tx_num()] {
+                if transactions_cursor.seek_exact(tx_num)?.is_none() {
+                    inconsistencies.push(BodyInconsistency::MissingTransaction(tx_num));
+                }
+            }
+        }
+
+        next_block = number + 1;
+        next_tx = body.next_tx_num();
+        if inconsistencies.is_empty() && report.inconsistencies.is_empty() {
+            report.next_block = next_block;
+            report.next_tx = next_tx;
+        }
+        report.inconsistencies.extend(inconsistencies.into_iter().map(|i| (number, i)));
+    }
+
+    let last_tx = transactions_cursor.last()?.map(|(tx_num, _)| tx_num);
+    if let Some(last_tx) = last_tx.filter(|last_tx| *last_tx >= report.next_tx) {
+        report.dangling_transactions = Some(report.next_tx..=last_tx);
+    }
+
+    Ok(report)
+}
+
+/// Removes the bodies from the first inconsistent block on and all transactions after the
+/// consistent bodies, and moves the checkpoint of the Bodies stage back to the last consistent
+/// block.
+pub(crate) fn repair_bodies<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    report: &BodiesReport,
+) -> eyre::Result<()> {
+    let next_block = report.next_block;
+    if next_block == 0 {
+        eyre::bail!("The genesis body is inconsistent, the database has to be synced again")
+    }
+
+    // The stages after the Bodies stage must be unwound first, because their data is derived from
+    // the removed bodies.
+    for stage_id in StageId::ALL {
+        if matches!(
+            stage_id,
+            StageId::Headers |
+                StageId::TotalDifficulty |
+                StageId::Bodies |
+                StageId::IndexLogsBlooms
+        ) {
+            continue
+        }
+        let checkpoint = provider.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+        if checkpoint.block_number >= next_block {
+            eyre::bail!(
+                "Stage {stage_id} has progressed to block {}, unwind to block {} with `reth stage unwind` first",
+                checkpoint.block_number,
+                next_block - 1
+            )
+        }
+    }
+
+    provider.unwind_withdrawal_history_indices(next_block..=BlockNumber::MAX)?;
+    truncate_table::<_, tables::BlockBodyIndices>(provider, next_block)?;
+    truncate_table::<_, tables::BlockOmmers>(provider, next_block)?;
+    truncate_table::<_, tables::BlockWithdrawals>(provider, next_block)?;
+
+    truncate_table::<_, tables::Transactions>(provider, report.next_tx)?;
+    truncate_table::<_, tables::TransactionBlock>(provider, report.next_tx)?;
+    truncate_table::<_, tables::TxSenders>(provider, report.next_tx)?;
+
+    let checkpoint = provider.get_stage_checkpoint(StageId::Bodies)?.unwrap_or_default();
+    if checkpoint.block_number >= next_block {
+        provider.save_stage_checkpoint(StageId::Bodies, StageCheckpoint::new(next_block - 1))?;
+    }
+
+    Ok(())
+}
+
+/// Removes all entries of the table with a key of at least `start`.
+fn truncate_table<DB: Database, T: Table<Key = u64>>(
+    provider: &DatabaseProviderRW<DB>,
+    start: u64,
+) -> Result<(), DatabaseError> {
+    match start.checked_sub(1) {
+        Some(last) => provider.unwind_table_by_num::<T>(last).map(|_| ()),
+        None => provider.tx_ref().clear::<T>(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::models::StoredBlockBodyIndices;
+    use reth_primitives::TransactionSignedNoHash;
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    fn body(first_tx_num: TxNumber, tx_count: u64) -> StoredBlockBodyIndices {
+        StoredBlockBodyIndices { first_tx_num, tx_count }
+    }
+
+    #[test]
+    fn check_and_repair_interrupted_bodies() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        // blocks 0 and 1 are consistent, block 2 overlaps block 1 and its transactions are
+        // incomplete
+        for (number, body) in [(0, body(0, 0)), (1, body(0, 2)), (2, body(1, 2))] {
+            tx.put::<tables::BlockBodyIndices>(number, body).unwrap();
+        }
+        tx.put::<tables::TransactionBlock>(1, 1).unwrap();
+        for tx_num in 0..3 {
+            tx.put::<tables::Transactions>(tx_num, TransactionSignedNoHash::default()).unwrap();
+        }
+        provider.save_stage_checkpoint(StageId::Bodies, StageCheckpoint::new(2)).unwrap();
+
+        let report = check_bodies(tx).unwrap();
+        assert_eq!(report.checked, 3);
+        assert_eq!(
+            report.inconsistencies,
+            vec![
+                (2, BodyInconsistency::FirstTransaction { expected: 2, found: 1 }),
+                (2, BodyInconsistency::TransactionBlock { last_tx: 2, found: None }),
+            ]
+        );
+        assert_eq!(report.dangling_transactions, Some(2..=2));
+
+        repair_bodies(&provider, &report).unwrap();
+        assert!(check_bodies(tx).unwrap().is_consistent());
+        assert_eq!(tx.entries::<tables::BlockBodyIndices>().unwrap(), 2);
+        assert_eq!(tx.entries::<tables::Transactions>().unwrap(), 2);
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::Bodies).unwrap(),
+            Some(StageCheckpoint::new(1))
+        );
+    }
+
+    #[test]
+    fn repair_requires_unwound_stages() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        tx.put::<tables::BlockBodyIndices>(0, body(0, 0)).unwrap();
+        tx.put::<tables::BlockBodyIndices>(2, body(0, 0)).unwrap();
+        provider.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(2)).unwrap();
+
+        let report = check_bodies(tx).unwrap();
+        assert_eq!(report.inconsistencies, vec![(2, BodyInconsistency::MissingParentBody)]);
+        assert!(repair_bodies(&provider, &report).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_db::{
+    database_compaction::DatabaseCompaction, mdbx::compaction::compact, open_db_exclusive,
+    open_db_read_only,
+};
+use reth_interfaces::db::LogLevel;
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tracing::info;
+
+/// How often the progress of the compaction is reported.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The arguments for the `reth db compact` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory to write the compacted database to. It must not exist yet.
+    ///
+    /// The database is left untouched, it can be replaced with the compacted copy once the
+    /// command is done.
+    #[arg(long, value_name = "PATH")]
+    output: PathBuf,
+}
+
+impl Command {
+    /// Execute `db compact` command
+    pub fn execute(self, db_path: &Path, log_level: Option<LogLevel>) -> eyre::Result<()> {
+        // the node must not write to the database while it's copied
+        let db = open_db_exclusive(db_path, log_level)?;
+
+        let usage = db.page_usage()?;
+        println!(
+            "Database size: {}, free pages: {} ({:.1}%), estimated compacted size: {}",
+            human_bytes(usage.size() as f64),
+            usage.free_pages,
+            usage.free_ratio() * 100.0,
+            human_bytes(usage.compacted_size() as f64),
+        );
+
+        let estimated_size = usage.compacted_size().max(1);
+        compact(&db, db_path, &self.output, PROGRESS_INTERVAL, |written: u64| {
+            info!(
+                target: "reth::cli",
+                written = %human_bytes(written as f64),
+                progress = %format!("{:.1}%", written as f64 / estimated_size as f64 * 100.0),
+                "Compacting database"
+            );
+        })?;
+        drop(db);
+
+        let db = open_db_read_only(&self.output, log_level)?;
+        println!(
+            "Compacted database at {} ({})",
+            self.output.display(),
+            human_bytes(db.page_usage()?.size() as f64)
+        );
+
+        Ok(())
+    }
+}
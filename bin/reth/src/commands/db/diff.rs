@@ -16,11 +16,13 @@ use clap::Parser;
 
 use reth_db::{
     cursor::DbCursorRO, database::Database, open_db_read_only, table::Table, transaction::DbTx,
-    AccountChangeSet, AccountHistory, AccountsTrie, BlockBodyIndices, BlockOmmers,
-    BlockWithdrawals, Bytecodes, CanonicalHeaders, DatabaseEnv, HashedAccount, HashedStorage,
-    HeaderNumbers, HeaderTD, Headers, PlainAccountState, PlainStorageState, PruneCheckpoints,
-    Receipts, StorageChangeSet, StorageHistory, StoragesTrie, SyncStage, SyncStageProgress, Tables,
-    TransactionBlock, Transactions, TxHashNumber, TxSenders,
+    AccountChangeSet, AccountHistory, AccountsTrie, AddressAppearances, BlockBodyIndices,
+    BlockOmmers, BlockWithdrawals, BytecodeRefs, Bytecodes, CanonicalHeaders, ContractCreations,
+    DatabaseEnv, HashedAccount, HashedStorage, HeaderNumbers, HeaderTD, Headers, HotAccounts,
+    InternalTransfers, LogsBlooms4K, LogsBlooms64K, PlainAccountState, PlainStorageState,
+    PruneCheckpoints, Receipts, StorageChangeSet, StorageHistory, StoragesTrie, SyncStage,
+    SyncStageProgress, Tables, TransactionBlock, Transactions, TxHashNumber, TxSenders,
+    WithdrawalHistory,
 };
 use tracing::info;
 
@@ -108,6 +110,9 @@ impl Command {
                     find_diffs::<PlainStorageState>(primary_tx, secondary_tx, output_dir)?
                 }
                 Tables::Bytecodes => find_diffs::<Bytecodes>(primary_tx, secondary_tx, output_dir)?,
+                Tables::BytecodeRefs => {
+                    find_diffs::<BytecodeRefs>(primary_tx, secondary_tx, output_dir)?
+                }
                 Tables::AccountHistory => {
                     find_diffs::<AccountHistory>(primary_tx, secondary_tx, output_dir)?
                 }
@@ -140,6 +145,27 @@ impl Command {
                 Tables::PruneCheckpoints => {
                     find_diffs::<PruneCheckpoints>(primary_tx, secondary_tx, output_dir)?
                 }
+                Tables::HotAccounts => {
+                    find_diffs::<HotAccounts>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::InternalTransfers => {
+                    find_diffs::<InternalTransfers>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::WithdrawalHistory => {
+                    find_diffs::<WithdrawalHistory>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::LogsBlooms4K => {
+                    find_diffs::<LogsBlooms4K>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::LogsBlooms64K => {
+                    find_diffs::<LogsBlooms64K>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::ContractCreations => {
+                    find_diffs::<ContractCreations>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::AddressAppearances => {
+                    find_diffs::<AddressAppearances>(primary_tx, secondary_tx, output_dir)?
+                }
             };
         }
 
@@ -24,7 +24,10 @@ use std::{
     sync::Arc,
 };
 
+mod backfill;
+mod check;
 mod clear;
+mod compact;
 mod diff;
 mod get;
 mod list;
@@ -84,6 +87,16 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Compacts the database into a copy without free pages
+    ///
+    /// The node must not be running.
+    Compact(compact::Command),
+    /// Checks the consistency of the database and optionally repairs it
+    Check(check::Command),
+    /// Indexes the already synced blocks for the optional indexes
+    ///
+    /// The node must not be running.
+    Backfill(backfill::Command),
     /// Snapshots tables from database
     Snapshot(snapshots::Command),
     /// Lists current and local database versions
@@ -224,6 +237,15 @@ impl Command {
                 let db = open_db(&db_path, self.db.log_level)?;
                 command.execute(&db)?;
             }
+            Subcommands::Compact(command) => {
+                command.execute(&db_path, self.db.log_level)?;
+            }
+            Subcommands::Check(command) => {
+                command.execute(&db_path, self.db.log_level, self.chain.clone())?;
+            }
+            Subcommands::Backfill(command) => {
+                command.execute(&db_path, self.db.log_level, self.chain.clone())?;
+            }
             Subcommands::Snapshot(command) => {
                 command.execute(&db_path, self.db.log_level, self.chain.clone())?;
             }
@@ -45,6 +45,9 @@ use tracing::*;
 /// `reth debug build-block` command
 /// This debug routine requires that the node is positioned at the block before the target.
 /// The script will then parse the block and attempt to build a similar one.
+///
+/// The built block is validated and executed, but neither committed nor broadcast. It's printed as
+/// JSON together with its roots and gas usage.
 #[derive(Debug, Parser)]
 pub struct Command {
     /// The path to the data dir for all reth files and subdirectories.
@@ -95,6 +98,11 @@ pub struct Command {
     #[arg(long, value_delimiter = ',')]
     transactions: Vec<String>,
 
+    /// Path to a JSON file with an array of transactions, which are added after the
+    /// `--transactions`.
+    #[arg(long, value_name = "PATH")]
+    transactions_file: Option<PathBuf>,
+
     /// Path to the file that contains a corresponding blobs bundle.
     #[arg(long)]
     blobs_bundle_path: Option<PathBuf>,
@@ -177,6 +185,15 @@ impl Command {
         );
         info!(target: "reth::cli", "Transaction pool initialized");
 
+        let mut transactions = self.transactions.clone();
+        if let Some(path) = &self.transactions_file {
+            let contents =
+                fs::read_to_string(path).wrap_err(format!("could not read {}", path.display()))?;
+            let file_transactions: Vec<String> =
+                serde_json::from_str(&contents).wrap_err("failed to deserialize transactions")?;
+            transactions.extend(file_transactions);
+        }
+
         let mut blobs_bundle = self
             .blobs_bundle_path
             .map(|path| -> eyre::Result<BlobsBundleV1> {
@@ -186,7 +203,7 @@ impl Command {
             })
             .transpose()?;
 
-        for tx_bytes in self.transactions.iter() {
+        for tx_bytes in transactions.iter() {
             debug!(target: "reth::cli", bytes = ?tx_bytes, "Decoding transaction");
             let transaction = TransactionSigned::decode(&mut &Bytes::from_str(tx_bytes)?[..])?
                 .into_ecrecovered()
@@ -304,6 +321,21 @@ impl Command {
                     None,
                 )?;
                 info!(target: "reth::cli", "Successfully appended built block");
+
+                info!(
+                    target: "reth::cli",
+                    number = block.number,
+                    hash = ?block.hash(),
+                    transactions = block.body.len(),
+                    gas_used = block.gas_used,
+                    gas_limit = block.gas_limit,
+                    state_root = ?block.state_root,
+                    transactions_root = ?block.transactions_root,
+                    receipts_root = ?block.receipts_root,
+                    withdrawals_root = ?block.withdrawals_root,
+                    "Built block"
+                );
+                println!("{}", serde_json::to_string_pretty(block)?);
             }
             _ => unreachable!("other outcomes are unreachable"),
         };
@@ -0,0 +1,216 @@
+//! Command for diffing local block execution against a remote archive node.
+
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+    runner::CliContext,
+};
+use clap::Parser;
+use jsonrpsee::http_client::HttpClientBuilder;
+use reth_db::open_db_read_only;
+use reth_primitives::{Address, BlockId, BlockNumber, BlockNumberOrTag, ChainSpec, Receipt, U256};
+use reth_provider::{
+    BlockExecutor, BlockReader, ExecutorFactory, HeaderProvider, ProviderFactory,
+    TransactionVariant,
+};
+use reth_rpc_api::EthApiClient;
+use reth_rpc_types::TransactionReceipt;
+use std::sync::Arc;
+use tracing::*;
+
+/// `reth debug geth-diff` command
+///
+/// Re-executes a range of blocks on top of the local database and compares the receipts, the gas
+/// used and the post-block state of all changed accounts against the answers of a remote archive
+/// node (e.g. geth or erigon). The command stops at the first divergence.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[clap(flatten)]
+    db: DatabaseArgs,
+
+    /// The HTTP RPC URL of the archive node to compare against.
+    #[arg(long, value_name = "URL")]
+    rpc_url: String,
+
+    /// The first block to execute.
+    #[arg(long)]
+    from: BlockNumber,
+
+    /// The last block to execute.
+    #[arg(long)]
+    to: BlockNumber,
+}
+
+impl Command {
+    /// Execute `debug geth-diff` command
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        if self.from == 0 || self.from > self.to {
+            eyre::bail!("Invalid block range {}..={}", self.from, self.to)
+        }
+
+        // add network name to data dir
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db_path = data_dir.db_path();
+
+        // initialize the database
+        let db = Arc::new(open_db_read_only(&db_path, self.db.log_level)?);
+        let factory = ProviderFactory::new(db, self.chain.clone());
+        let executor_factory = reth_revm::EvmProcessorFactory::new(self.chain.clone());
+
+        let client = HttpClientBuilder::default().build(&self.rpc_url)?;
+
+        for block_number in self.from..=self.to {
+            let block = factory
+                .block_with_senders(block_number.into(), TransactionVariant::WithHash)?
+                .ok_or_else(|| eyre::eyre!("Block {block_number} not found"))?;
+            let td = factory
+                .header_td_by_number(block_number)?
+                .ok_or_else(|| eyre::eyre!("Total difficulty of block {block_number} not found"))?;
+
+            let mut executor =
+                executor_factory.with_state(factory.history_by_block_number(block_number - 1)?);
+            executor.execute(&block, td)?;
+            let state = executor.take_output_state();
+
+            let block_id = BlockId::Number(BlockNumberOrTag::Number(block_number));
+            let remote_receipts =
+                EthApiClient::block_receipts(&client, block_id).await?.ok_or_else(|| {
+                    eyre::eyre!("Remote node returned no receipts for block {block_number}")
+                })?;
+            let local_receipts = state.receipts_by_block(block_number);
+
+            if local_receipts.len() != remote_receipts.len() {
+                eyre::bail!(
+                    "Receipt count mismatch in block {block_number}. Local: {}. Remote: {}",
+                    local_receipts.len(),
+                    remote_receipts.len()
+                )
+            }
+
+            for (index, (local, remote)) in local_receipts.iter().zip(&remote_receipts).enumerate()
+            {
+                let local =
+                    local.as_ref().ok_or_else(|| eyre::eyre!("Local receipt was pruned"))?;
+                if let Some(mismatch) = diff_receipt(local, remote) {
+                    let hash = block.body.get(index).map(|tx| tx.hash).unwrap_or_default();
+                    error!(
+                        target: "reth::cli",
+                        block_number,
+                        index,
+                        ?hash,
+                        ?local,
+                        ?remote,
+                        "First divergent transaction"
+                    );
+                    eyre::bail!(
+                        "Transaction {hash} at index {index} of block {block_number} diverged: \
+                         {mismatch}"
+                    )
+                }
+            }
+
+            let local_gas_used =
+                local_receipts.last().and_then(|r| r.as_ref()).map(|r| r.cumulative_gas_used);
+            if local_gas_used.unwrap_or_default() != block.gas_used {
+                eyre::bail!(
+                    "Gas used mismatch in block {block_number}. Local: {}. Header: {}",
+                    local_gas_used.unwrap_or_default(),
+                    block.gas_used
+                )
+            }
+
+            for (address, info) in state.accounts_iter() {
+                let (balance, nonce) =
+                    info.map(|info| (info.balance, info.nonce)).unwrap_or_default();
+                let (remote_balance, remote_nonce) =
+                    remote_account(&client, address, block_id).await?;
+                if balance != remote_balance || U256::from(nonce) != remote_nonce {
+                    eyre::bail!(
+                        "Account {address} diverged after block {block_number}. \
+                         Local: balance {balance}, nonce {nonce}. \
+                         Remote: balance {remote_balance}, nonce {remote_nonce}"
+                    )
+                }
+            }
+
+            info!(target: "reth::cli", block_number, hash = ?block.hash_slow(), "Block matches remote node");
+        }
+
+        info!(target: "reth::cli", from = self.from, to = self.to, "All blocks match remote node");
+        Ok(())
+    }
+}
+
+/// Compares the local receipt with the receipt returned by the remote node and returns the
+/// description of the first mismatch, if any.
+fn diff_receipt(local: &Receipt, remote: &TransactionReceipt) -> Option<String> {
+    let remote_success = remote.status_code.map(|status| status.to::<u64>() == 1);
+    if remote_success.is_some_and(|success| success != local.success) {
+        return Some(format!(
+            "status mismatch. Local: {}. Remote: {:?}",
+            local.success, remote_success
+        ))
+    }
+
+    if U256::from(local.cumulative_gas_used) != remote.cumulative_gas_used {
+        return Some(format!(
+            "cumulative gas used mismatch. Local: {}. Remote: {}",
+            local.cumulative_gas_used, remote.cumulative_gas_used
+        ))
+    }
+
+    if local.logs.len() != remote.logs.len() {
+        return Some(format!(
+            "log count mismatch. Local: {}. Remote: {}",
+            local.logs.len(),
+            remote.logs.len()
+        ))
+    }
+
+    for (index, (local, remote)) in local.logs.iter().zip(&remote.logs).enumerate() {
+        if local.address != remote.address ||
+            local.topics != remote.topics ||
+            local.data != remote.data
+        {
+            return Some(format!("log {index} mismatch. Local: {local:?}. Remote: {remote:?}"))
+        }
+    }
+
+    None
+}
+
+/// Fetches the balance and nonce of the account from the remote node.
+async fn remote_account<C: EthApiClient + Sync>(
+    client: &C,
+    address: Address,
+    block_id: BlockId,
+) -> eyre::Result<(U256, U256)> {
+    let balance = EthApiClient::balance(client, address, Some(block_id)).await?;
+    let nonce = EthApiClient::transaction_count(client, address, Some(block_id)).await?;
+    Ok((balance, nonce))
+}
@@ -0,0 +1,181 @@
+//! Command for inserting blocks through the consensus engine.
+
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+    init::init_genesis,
+    runner::CliContext,
+};
+use alloy_rlp::Decodable;
+use clap::Parser;
+use eyre::Context;
+use reth_beacon_consensus::{BeaconConsensus, BeaconConsensusEngineBuilder};
+use reth_blockchain_tree::{
+    BlockchainTree, BlockchainTreeConfig, ShareableBlockchainTree, TreeExternals,
+};
+use reth_db::init_db;
+use reth_interfaces::{consensus::Consensus, test_utils::NoopFullBlockClient};
+use reth_payload_builder::PayloadBuilderHandle;
+use reth_primitives::{fs, Block, ChainSpec, B256};
+use reth_provider::{providers::BlockchainProvider, BlockHashReader, ProviderFactory};
+use reth_revm::EvmProcessorFactory;
+use reth_rpc_types::engine::{CancunPayloadFields, ForkchoiceState};
+use reth_rpc_types_compat::engine::payload::try_block_to_payload;
+use reth_stages::Pipeline;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::sync::{mpsc, oneshot};
+use tracing::*;
+
+/// `reth debug insert-blocks` command
+/// Inserts RLP encoded blocks from a file through the consensus engine, like a consensus layer
+/// would: every block is sent as a new payload and then made canonical with a forkchoice update.
+/// Unlike `reth import`, this exercises the blockchain tree instead of the pipeline.
+/// The blocks have to extend the canonical chain of the database, blocks that are already
+/// canonical are skipped.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// Database arguments.
+    #[clap(flatten)]
+    db: DatabaseArgs,
+
+    /// The path to the file with the RLP encoded blocks.
+    #[arg(value_name = "RLP_FILE")]
+    path: PathBuf,
+}
+
+impl Command {
+    /// Execute `debug insert-blocks` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        let blocks = read_blocks(&self.path)?;
+        info!(target: "reth::cli", count = blocks.len(), "Read blocks");
+
+        // add network name to data dir
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db_path = data_dir.db_path();
+        fs::create_dir_all(&db_path)?;
+
+        // initialize the database
+        let db = Arc::new(init_db(db_path, self.db.log_level)?);
+        init_genesis(Arc::clone(&db), Arc::clone(&self.chain))?;
+        let provider_factory = ProviderFactory::new(Arc::clone(&db), Arc::clone(&self.chain));
+
+        let consensus: Arc<dyn Consensus> = Arc::new(BeaconConsensus::new(Arc::clone(&self.chain)));
+
+        // configure blockchain tree
+        let tree_externals = TreeExternals::new(
+            provider_factory.clone(),
+            Arc::clone(&consensus),
+            EvmProcessorFactory::new(self.chain.clone()),
+        );
+        let tree = BlockchainTree::new(tree_externals, BlockchainTreeConfig::default(), None)?;
+        let blockchain_tree = ShareableBlockchainTree::new(tree);
+        let blockchain_db = BlockchainProvider::new(provider_factory.clone(), blockchain_tree)?;
+
+        // The engine neither downloads blocks nor builds payloads, and the blocks are expected to
+        // extend the canonical chain, so the pipeline never runs.
+        let (to_payload_service, _payload_service_rx) = mpsc::unbounded_channel();
+        let (engine, handle) = BeaconConsensusEngineBuilder::new(
+            NoopFullBlockClient::default(),
+            Pipeline::builder().build(provider_factory),
+            blockchain_db.clone(),
+            Box::new(ctx.task_executor.clone()),
+            PayloadBuilderHandle::new(to_payload_service),
+        )
+        .build()?;
+
+        let (engine_tx, mut engine_rx) = oneshot::channel();
+        ctx.task_executor.spawn_critical_blocking("consensus engine", async move {
+            let _ = engine_tx.send(engine.await);
+        });
+
+        for block in blocks {
+            let block = block.seal_slow();
+            let (number, hash) = (block.number, block.hash);
+            if blockchain_db.block_hash(number)? == Some(hash) {
+                debug!(target: "reth::cli", number, ?hash, "Skipping canonical block");
+                continue
+            }
+
+            let cancun_fields = block.parent_beacon_block_root.map(|parent_beacon_block_root| {
+                CancunPayloadFields {
+                    parent_beacon_block_root,
+                    versioned_hashes: block.blob_versioned_hashes_iter().copied().collect(),
+                }
+            });
+
+            let insert = async {
+                let status = handle.new_payload(try_block_to_payload(block), cancun_fields).await?;
+                if !status.is_valid() {
+                    eyre::bail!("payload status of block {number} ({hash}) is {status:?}")
+                }
+
+                let state = ForkchoiceState {
+                    head_block_hash: hash,
+                    safe_block_hash: B256::ZERO,
+                    finalized_block_hash: B256::ZERO,
+                };
+                let updated = handle.fork_choice_updated(state, None).await?;
+                if !updated.payload_status.is_valid() {
+                    eyre::bail!(
+                        "forkchoice status of block {number} ({hash}) is {:?}",
+                        updated.payload_status
+                    )
+                }
+                Ok::<_, eyre::Report>(())
+            };
+
+            tokio::select! {
+                res = insert => res?,
+                res = &mut engine_rx => {
+                    res?.wrap_err("consensus engine failed")?;
+                    eyre::bail!("consensus engine terminated")
+                }
+            }
+            debug!(target: "reth::cli", number, ?hash, "Inserted block");
+        }
+
+        info!(target: "reth::cli", "Inserted all blocks");
+        Ok(())
+    }
+}
+
+/// Reads the RLP encoded blocks from the file.
+fn read_blocks(path: &Path) -> eyre::Result<Vec<Block>> {
+    let bytes = fs::read(path)?;
+    let mut buf = bytes.as_slice();
+    let mut blocks = Vec::new();
+    while !buf.is_empty() {
+        let block = Block::decode(&mut buf)
+            .wrap_err_with(|| format!("failed to decode block {}", blocks.len()))?;
+        blocks.push(block);
+    }
+    Ok(blocks)
+}
@@ -6,7 +6,9 @@ use crate::runner::CliContext;
 
 mod build_block;
 mod execution;
+mod geth_diff;
 mod in_memory_merkle;
+mod insert_blocks;
 mod merkle;
 
 /// `reth debug` command
@@ -27,6 +29,10 @@ pub enum Subcommands {
     InMemoryMerkle(in_memory_merkle::Command),
     /// Debug block building.
     BuildBlock(build_block::Command),
+    /// Debug block execution against a remote archive node.
+    GethDiff(geth_diff::Command),
+    /// Insert blocks from a file through the consensus engine instead of the pipeline.
+    InsertBlocks(insert_blocks::Command),
 }
 
 impl Command {
@@ -37,6 +43,8 @@ impl Command {
             Subcommands::Merkle(command) => command.execute(ctx).await,
             Subcommands::InMemoryMerkle(command) => command.execute(ctx).await,
             Subcommands::BuildBlock(command) => command.execute(ctx).await,
+            Subcommands::GethDiff(command) => command.execute(ctx).await,
+            Subcommands::InsertBlocks(command) => command.execute(ctx).await,
         }
     }
 }
@@ -25,7 +25,10 @@ use reth_primitives::{stage::StageId, ChainSpec, B256};
 use reth_provider::{HeaderSyncMode, ProviderFactory, StageCheckpointReader};
 use reth_stages::{
     prelude::*,
-    stages::{ExecutionStage, ExecutionStageThresholds, SenderRecoveryStage, TotalDifficultyStage},
+    stages::{
+        AdaptiveBatchSize, ExecutionStage, ExecutionStageThresholds, SenderRecoveryStage,
+        TotalDifficultyStage,
+    },
 };
 use std::{path::PathBuf, sync::Arc};
 use tokio::sync::watch;
@@ -177,21 +180,32 @@ impl ImportCommand {
                 .set(SenderRecoveryStage {
                     commit_threshold: config.stages.sender_recovery.commit_threshold,
                 })
-                .set(ExecutionStage::new(
-                    factory,
-                    ExecutionStageThresholds {
-                        max_blocks: config.stages.execution.max_blocks,
-                        max_changes: config.stages.execution.max_changes,
-                        max_cumulative_gas: config.stages.execution.max_cumulative_gas,
-                    },
-                    config
-                        .stages
-                        .merkle
-                        .clean_threshold
-                        .max(config.stages.account_hashing.clean_threshold)
-                        .max(config.stages.storage_hashing.clean_threshold),
-                    config.prune.map(|prune| prune.segments).unwrap_or_default(),
-                )),
+                .set(
+                    ExecutionStage::new(
+                        factory,
+                        ExecutionStageThresholds {
+                            max_blocks: config.stages.execution.max_blocks,
+                            max_changes: config.stages.execution.max_changes,
+                            max_cumulative_gas: config.stages.execution.max_cumulative_gas,
+                        },
+                        config
+                            .stages
+                            .merkle
+                            .clean_threshold
+                            .max(config.stages.account_hashing.clean_threshold)
+                            .max(config.stages.storage_hashing.clean_threshold),
+                        config.prune.map(|prune| prune.segments).unwrap_or_default(),
+                    )
+                    .with_adaptive_batch_size(
+                        config.stages.execution.adaptive_batch_size.map(|adaptive| {
+                            AdaptiveBatchSize::new(
+                                adaptive.min_blocks,
+                                config.stages.execution.max_blocks.unwrap_or(u64::MAX),
+                                adaptive.target_commit_duration,
+                            )
+                        }),
+                    ),
+                ),
             )
             .build(provider_factory);
 
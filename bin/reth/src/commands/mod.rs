@@ -1,5 +1,6 @@
 //! This contains all of the `reth` commands
 
+pub mod checkpoint_sync;
 pub mod config_cmd;
 pub mod db;
 pub mod debug_cmd;
@@ -8,5 +9,6 @@ pub mod init_cmd;
 pub mod node;
 pub mod p2p;
 pub mod recover;
+pub mod rpc;
 pub mod stage;
 pub mod test_vectors;
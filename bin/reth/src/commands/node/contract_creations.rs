@@ -0,0 +1,238 @@
+//! Indexing of the contracts created by the blocks executed by the pipeline and the tree.
+
+use reth_blockchain_tree::TreeIndexer;
+use reth_db::{database::Database, models::StoredContractCreation, tables};
+use reth_interfaces::provider::{ProviderError, ProviderResult};
+use reth_primitives::{Address, BlockHash, BlockNumHash, BlockNumber, TxNumber};
+use reth_provider::{BlockReader, ContractCreationsWriter, DatabaseProviderRW};
+use reth_revm::{
+    contract_creations::{ContractCreation, ContractCreationsInspector},
+    stack::SharedExecutionInspector,
+};
+use reth_stages::{stages::ExecutionIndexer, StageError};
+use std::{
+    collections::HashMap,
+    ops::RangeInclusive,
+    sync::{Arc, Mutex},
+};
+
+/// An [ExecutionIndexer] and [TreeIndexer] that writes the contract creations collected by its
+/// [ContractCreationsInspector] into the [tables::ContractCreations] table.
+///
+/// The inspector has to be set on the executor factory of the execution stage or the tree, see
+/// [ContractCreationsIndexer::inspector], and has to inspect all transactions.
+#[derive(Debug, Clone, Default)]
+pub struct ContractCreationsIndexer {
+    inspector: Arc<Mutex<ContractCreationsInspector>>,
+    /// The contract creations of the blocks executed by the tree that aren't finalized yet, which
+    /// are at most the blocks within the maximum reorg depth of the tree, see
+    /// [TreeIndexer::finalize_blocks].
+    executed: Arc<Mutex<HashMap<BlockHash, (BlockNumber, Vec<ContractCreation>)>>>,
+}
+
+impl ContractCreationsIndexer {
+    /// Returns the inspector that collects the contract creations.
+    pub fn inspector(&self) -> SharedExecutionInspector {
+        self.inspector.clone()
+    }
+}
+
+/// Writes the contract creations with the numbers of the transactions that created them, the bodies
+/// of their blocks have to be written already.
+fn write_creations<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    creations: impl IntoIterator<Item = ContractCreation>,
+) -> ProviderResult<()> {
+    let mut first_tx_nums = HashMap::<BlockNumber, TxNumber>::new();
+    let mut created = Vec::<(Address, StoredContractCreation)>::new();
+    for creation in creations {
+        let first_tx_num = match first_tx_nums.get(&creation.block_number) {
+            Some(first_tx_num) => *first_tx_num,
+            None => {
+                let first_tx_num = provider
+                    .block_body_indices(creation.block_number)?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(creation.block_number))?
+                    .first_tx_num();
+                first_tx_nums.insert(creation.block_number, first_tx_num);
+                first_tx_num
+            }
+        };
+        created.push((
+            creation.address,
+            StoredContractCreation {
+                tx_number: first_tx_num + creation.transaction_index,
+                creator: creation.creator,
+            },
+        ));
+    }
+    provider.insert_contract_creations(created)
+}
+
+impl<DB: Database> ExecutionIndexer<DB> for ContractCreationsIndexer {
+    fn reset(&mut self) {
+        self.inspector.lock().expect("not poisoned").clear();
+    }
+
+    fn write(&mut self, provider: &DatabaseProviderRW<DB>) -> Result<(), StageError> {
+        let creations = self.inspector.lock().expect("not poisoned").take_creations();
+        Ok(write_creations(provider, creations)?)
+    }
+
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<(), StageError> {
+        provider.unwind_contract_creations(range)?;
+        Ok(())
+    }
+}
+
+impl<DB: Database> TreeIndexer<DB> for ContractCreationsIndexer {
+    fn block_execution_started(&self) {
+        self.inspector.lock().expect("not poisoned").clear();
+    }
+
+    fn block_executed(&self, block: BlockNumHash) {
+        let creations = self.inspector.lock().expect("not poisoned").take_creations();
+        self.executed.lock().expect("not poisoned").insert(block.hash, (block.number, creations));
+    }
+
+    fn write_blocks(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        blocks: &[BlockNumHash],
+    ) -> ProviderResult<()> {
+        let executed = self.executed.lock().expect("not poisoned");
+        let creations = blocks
+            .iter()
+            .filter_map(|block| executed.get(&block.hash))
+            .flat_map(|(_, creations)| creations.iter().copied());
+        write_creations(provider, creations)
+    }
+
+    fn finalize_blocks(&self, finalized_block: BlockNumber) {
+        self.executed
+            .lock()
+            .expect("not poisoned")
+            .retain(|_, (block_number, _)| *block_number > finalized_block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{
+        models::{AccountBeforeTx, StoredBlockBodyIndices},
+        test_utils::TempDatabase,
+        transaction::DbTxMut,
+        DatabaseEnv,
+    };
+    use reth_primitives::{B256, U256};
+    use reth_provider::{test_utils::create_test_provider_factory, ContractCreationsReader};
+    use reth_revm::{
+        interpreter::{CreateInputs, CreateScheme, InstructionResult},
+        primitives::{Bytes, Env},
+    };
+
+    type TestDatabase = Arc<TempDatabase<DatabaseEnv>>;
+
+    /// Executes a transaction in the block that creates a contract at the address.
+    fn execute_creation(inspector: &SharedExecutionInspector, block_number: u64, address: u8) {
+        let mut env = Env::default();
+        env.block.number = U256::from(block_number);
+        let tx_hash = B256::with_last_byte(address);
+        let create = CreateInputs {
+            caller: Address::with_last_byte(1),
+            scheme: CreateScheme::Create,
+            value: U256::ZERO,
+            init_code: Bytes::new(),
+            gas_limit: 100_000,
+        };
+        let mut inspector = inspector.lock().unwrap();
+        inspector.transaction_start(&env, tx_hash);
+        inspector.create(0, &create);
+        inspector.create_end(
+            0,
+            &create,
+            InstructionResult::Return,
+            Some(Address::with_last_byte(address)),
+        );
+        inspector.transaction_end(&env, tx_hash, true);
+    }
+
+    /// Writes the body indices of the blocks, with two transactions each, and the account
+    /// changesets of the contracts created in them.
+    fn write_blocks(provider: &DatabaseProviderRW<TestDatabase>, contracts: &[(u64, u8)]) {
+        for block_number in 1..=3 {
+            let indices = StoredBlockBodyIndices { first_tx_num: block_number * 2, tx_count: 2 };
+            provider.tx_ref().put::<tables::BlockBodyIndices>(block_number, indices).unwrap();
+        }
+        for (block_number, address) in contracts {
+            let account_before =
+                AccountBeforeTx { address: Address::with_last_byte(*address), info: None };
+            provider
+                .tx_ref()
+                .put::<tables::AccountChangeSet>(*block_number, account_before)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn write_and_unwind() {
+        let factory = create_test_provider_factory();
+        let mut indexer = ContractCreationsIndexer::default();
+
+        // a contract creation transaction in each block, and a second one in the last block
+        let inspector = indexer.inspector();
+        let contracts = [(1, 11), (2, 12), (3, 13), (3, 14)];
+        for (block_number, address) in contracts {
+            execute_creation(&inspector, block_number, address);
+        }
+
+        let provider = factory.provider_rw().unwrap();
+        write_blocks(&provider, &contracts);
+        indexer.write(&provider).unwrap();
+        indexer.unwind(&provider, 3..=3).unwrap();
+        provider.commit().unwrap();
+
+        let tx_number = |address| {
+            let creation = factory.contract_creation(Address::with_last_byte(address)).unwrap();
+            creation.map(|creation| creation.tx_number)
+        };
+        assert_eq!(tx_number(11), Some(2));
+        assert_eq!(tx_number(12), Some(4));
+        assert_eq!(tx_number(13), None);
+        assert_eq!(tx_number(14), None);
+    }
+
+    #[test]
+    fn write_canonical_tree_blocks() {
+        let factory = create_test_provider_factory();
+        let indexer = ContractCreationsIndexer::default();
+        let inspector = indexer.inspector();
+
+        // two competing blocks at the same height
+        let block = |hash| BlockNumHash { number: 1, hash: B256::with_last_byte(hash) };
+        for hash in [1, 2] {
+            TreeIndexer::<TestDatabase>::block_execution_started(&indexer);
+            execute_creation(&inspector, 1, 10 + hash);
+            TreeIndexer::<TestDatabase>::block_executed(&indexer, block(hash));
+        }
+
+        // only the contracts of the canonical block are written
+        let provider = factory.provider_rw().unwrap();
+        write_blocks(&provider, &[]);
+        indexer.write_blocks(&provider, &[block(2)]).unwrap();
+        provider.commit().unwrap();
+        assert_eq!(factory.contract_creation(Address::with_last_byte(11)).unwrap(), None);
+        assert_eq!(
+            factory.contract_creation(Address::with_last_byte(12)).unwrap(),
+            Some(StoredContractCreation { tx_number: 2, creator: Address::with_last_byte(1) })
+        );
+
+        // the contracts of the finalized blocks are discarded
+        TreeIndexer::<TestDatabase>::finalize_blocks(&indexer, 1);
+        assert!(indexer.executed.lock().unwrap().is_empty());
+    }
+}
@@ -38,22 +38,41 @@ struct NodeState<DB> {
     current_stage: Option<CurrentStage>,
     /// The latest block reached by either pipeline or consensus engine.
     latest_block: Option<BlockNumber>,
+    /// The estimated head of the network.
+    ///
+    /// This is the highest block number of the sync target set by the consensus layer and the
+    /// blocks it sent to the consensus engine.
+    network_head: Option<BlockNumber>,
 }
 
 impl<DB> NodeState<DB> {
     fn new(db: DB, network: Option<NetworkHandle>, latest_block: Option<BlockNumber>) -> Self {
-        Self { db, network, current_stage: None, latest_block }
+        Self { db, network, current_stage: None, latest_block, network_head: None }
     }
 
     fn num_connected_peers(&self) -> usize {
         self.network.as_ref().map(|net| net.num_connected_peers()).unwrap_or_default()
     }
 
+    /// Updates the estimated network head if the block is higher.
+    fn update_network_head(&mut self, number: BlockNumber) {
+        self.network_head = Some(self.network_head.map_or(number, |head| head.max(number)));
+    }
+
+    /// Updates the estimated network head with the sync target of the headers stage, which is the
+    /// block the consensus layer requested to sync to.
+    fn update_network_head_from_checkpoint(&mut self, checkpoint: StageCheckpoint) {
+        if let Some(headers) = checkpoint.headers_stage_checkpoint() {
+            self.update_network_head(headers.block_range.to);
+        }
+    }
+
     /// Processes an event emitted by the pipeline
     fn handle_pipeline_event(&mut self, event: PipelineEvent) {
         match event {
             PipelineEvent::Run { pipeline_stages_progress, stage_id, checkpoint, target } => {
                 let checkpoint = checkpoint.unwrap_or_default();
+                self.update_network_head_from_checkpoint(checkpoint);
                 let current_stage = CurrentStage {
                     stage_id,
                     eta: match &self.current_stage {
@@ -66,9 +85,7 @@ impl<DB> NodeState<DB> {
                     target,
                 };
 
-                let stage_progress = OptionalField(
-                    checkpoint.entities().and_then(|entities| entities.fmt_percentage()),
-                );
+                let stage_progress = OptionalField(fmt_stage_progress(stage_id, checkpoint));
 
                 if let Some(stage_eta) = current_stage.eta.fmt_for_stage(stage_id) {
                     info!(
@@ -101,15 +118,14 @@ impl<DB> NodeState<DB> {
                 if stage_id.is_finish() {
                     self.latest_block = Some(checkpoint.block_number);
                 }
+                self.update_network_head_from_checkpoint(checkpoint);
 
                 if let Some(current_stage) = self.current_stage.as_mut() {
                     current_stage.checkpoint = checkpoint;
                     current_stage.eta.update(checkpoint);
 
                     let target = OptionalField(current_stage.target);
-                    let stage_progress = OptionalField(
-                        checkpoint.entities().and_then(|entities| entities.fmt_percentage()),
-                    );
+                    let stage_progress = OptionalField(fmt_stage_progress(stage_id, checkpoint));
 
                     let message =
                         if done { "Stage finished executing" } else { "Stage committed progress" };
@@ -164,16 +180,22 @@ impl<DB> NodeState<DB> {
                 );
             }
             BeaconConsensusEngineEvent::CanonicalBlockAdded(block) => {
+                self.update_network_head(block.number);
                 info!(number=block.number, hash=?block.hash, "Block added to canonical chain");
             }
             BeaconConsensusEngineEvent::CanonicalChainCommitted(head, elapsed) => {
                 self.latest_block = Some(head.number);
+                self.update_network_head(head.number);
 
                 info!(number=head.number, hash=?head.hash, ?elapsed, "Canonical chain committed");
             }
             BeaconConsensusEngineEvent::ForkBlockAdded(block) => {
+                self.update_network_head(block.number);
                 info!(number=block.number, hash=?block.hash, "Block added to fork chain");
             }
+            BeaconConsensusEngineEvent::PipelineRetry { attempt, backoff, error } => {
+                warn!(%error, attempt, ?backoff, "Pipeline failed, restarting after backoff");
+            }
         }
     }
 
@@ -228,6 +250,41 @@ impl<T: Display> Display for OptionalField<T> {
     }
 }
 
+/// Formats the progress of the stage as percentage.
+///
+/// The progress of the [StageId::Execution] stage is measured in gas, so the remaining gas is
+/// appended.
+fn fmt_stage_progress(stage_id: StageId, checkpoint: StageCheckpoint) -> Option<String> {
+    let entities = checkpoint.entities()?;
+    let percentage = entities.fmt_percentage()?;
+    if stage_id == StageId::Execution {
+        let remaining_gas = entities.total.saturating_sub(entities.processed);
+        Some(format!("{percentage} ({} remaining)", fmt_gas(remaining_gas)))
+    } else {
+        Some(percentage)
+    }
+}
+
+/// Formats an amount of gas with a unit prefix, e.g. `1.23 Tgas`.
+fn fmt_gas(gas: u64) -> String {
+    const UNITS: [&str; 5] = ["Kgas", "Mgas", "Ggas", "Tgas", "Pgas"];
+
+    let mut value = gas as f64;
+    let mut unit = None;
+    for next in UNITS {
+        if value < 1000.0 {
+            break
+        }
+        value /= 1000.0;
+        unit = Some(next);
+    }
+
+    match unit {
+        Some(unit) => format!("{value:.2} {unit}"),
+        None => format!("{gas} gas"),
+    }
+}
+
 /// The stage currently being executed.
 struct CurrentStage {
     stage_id: StageId,
@@ -328,9 +385,8 @@ where
             if let Some(CurrentStage { stage_id, eta, checkpoint, target }) =
                 &this.state.current_stage
             {
-                let stage_progress = OptionalField(
-                    checkpoint.entities().and_then(|entities| entities.fmt_percentage()),
-                );
+                let stage_progress = OptionalField(fmt_stage_progress(*stage_id, *checkpoint));
+                let network_head = OptionalField(this.state.network_head);
 
                 if let Some(stage_eta) = eta.fmt_for_stage(*stage_id) {
                     info!(
@@ -340,6 +396,7 @@ where
                         stage = %stage_id,
                         checkpoint = checkpoint.block_number,
                         target = %OptionalField(*target),
+                        %network_head,
                         %stage_progress,
                         %stage_eta,
                         "Status"
@@ -352,6 +409,7 @@ where
                         stage = %stage_id,
                         checkpoint = checkpoint.block_number,
                         target = %OptionalField(*target),
+                        %network_head,
                         %stage_progress,
                         "Status"
                     );
@@ -435,10 +493,11 @@ impl Eta {
     /// Format ETA for a given stage.
     ///
     /// NOTE: Currently ETA is enabled only for the stages that have predictable progress.
-    /// It's not the case for network-dependent ([StageId::Headers] and [StageId::Bodies]) and
-    /// [StageId::Execution] stages.
+    /// It's not the case for network-dependent ([StageId::Headers] and [StageId::Bodies]) stages.
+    /// The progress of the [StageId::Execution] stage is measured in gas, so its ETA is estimated
+    /// from the remaining gas.
     fn fmt_for_stage(&self, stage: StageId) -> Option<String> {
-        if matches!(stage, StageId::Headers | StageId::Bodies | StageId::Execution) {
+        if matches!(stage, StageId::Headers | StageId::Bodies) {
             None
         } else {
             Some(self.to_string())
@@ -466,7 +525,10 @@ impl Display for Eta {
 
 #[cfg(test)]
 mod tests {
-    use crate::commands::node::events::Eta;
+    use crate::commands::node::events::{fmt_gas, fmt_stage_progress, Eta};
+    use reth_primitives::stage::{
+        EntitiesCheckpoint, ExecutionCheckpoint, StageCheckpoint, StageId,
+    };
     use std::time::{Duration, Instant};
 
     #[test]
@@ -484,4 +546,26 @@ mod tests {
 
         assert_eq!(eta, "13m 37s");
     }
+
+    #[test]
+    fn gas_display() {
+        assert_eq!(fmt_gas(999), "999 gas");
+        assert_eq!(fmt_gas(1_000), "1.00 Kgas");
+        assert_eq!(fmt_gas(12_345_678), "12.35 Mgas");
+        assert_eq!(fmt_gas(1_230_000_000_000), "1.23 Tgas");
+    }
+
+    #[test]
+    fn execution_stage_progress_display() {
+        let checkpoint =
+            StageCheckpoint::new(100).with_execution_stage_checkpoint(ExecutionCheckpoint {
+                progress: EntitiesCheckpoint { processed: 25_000_000, total: 100_000_000 },
+                ..Default::default()
+            });
+
+        assert_eq!(
+            fmt_stage_progress(StageId::Execution, checkpoint).as_deref(),
+            Some("25.00% (75.00 Mgas remaining)")
+        );
+    }
 }
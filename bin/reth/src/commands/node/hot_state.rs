@@ -0,0 +1,310 @@
+//! Tracking of the recently changed accounts, whose bytecodes are loaded into the
+//! [BytecodeCache] of the executors when the node starts.
+
+use reth_db::{
+    cursor::{DbCursorRO, DbCursorRW},
+    database::Database,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives::{Address, BlockNumber};
+use reth_provider::{CanonStateSubscriptions, Chain};
+use reth_revm::bytecode_cache::BytecodeCache;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Instant,
+};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, error, info, warn};
+
+/// The maximum number of accounts in the hot set.
+pub const MAX_HOT_ACCOUNTS: usize = 10_000;
+
+/// The number of blocks after which the hot set is written to the database.
+const PERSIST_INTERVAL: u64 = 32;
+
+/// The accounts changed by the most recent canonical blocks.
+#[derive(Debug, Clone, Default)]
+pub struct HotAccounts {
+    /// The number of the last block that changed each account.
+    accounts: HashMap<Address, BlockNumber>,
+    /// The accounts that were recorded or evicted since the hot set was last written.
+    changed: HashSet<Address>,
+}
+
+impl HotAccounts {
+    /// Loads the hot set from the [tables::HotAccounts] table.
+    pub fn load<DB: Database>(db: &DB) -> eyre::Result<Self> {
+        let tx = db.tx()?;
+        let accounts = tx
+            .cursor_read::<tables::HotAccounts>()?
+            .walk(None)?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(Self { accounts, changed: HashSet::new() })
+    }
+
+    /// Returns the number of accounts in the hot set.
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    /// Returns `true` if the hot set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+
+    /// Loads the bytecodes of the accounts of the hot set into the cache of the executors.
+    ///
+    /// Reading the accounts also pulls the database pages they're stored in into the page cache.
+    ///
+    /// Returns the number of accounts that were loaded.
+    pub fn warm_up<DB: Database>(&self, db: &DB, cache: &BytecodeCache) -> eyre::Result<usize> {
+        let tx = db.tx()?;
+        let mut accounts = tx.cursor_read::<tables::PlainAccountState>()?;
+        let mut bytecodes = tx.cursor_read::<tables::Bytecodes>()?;
+
+        // sorted keys make the reads mostly sequential
+        let mut addresses = self.accounts.keys().copied().collect::<Vec<_>>();
+        addresses.sort_unstable();
+
+        let mut loaded = 0;
+        for address in addresses {
+            let Some((_, account)) = accounts.seek_exact(address)? else { continue };
+            if let Some(code_hash) = account.bytecode_hash {
+                if cache.get(&code_hash).is_none() {
+                    if let Some((_, bytecode)) = bytecodes.seek_exact(code_hash)? {
+                        cache.insert(code_hash, bytecode.0);
+                    }
+                }
+            }
+            loaded += 1;
+        }
+        Ok(loaded)
+    }
+
+    /// Records the accounts changed by the blocks of the chain.
+    pub fn on_chain(&mut self, chain: &Chain) {
+        let number = chain.tip().number;
+        for (address, _) in chain.state().accounts_iter() {
+            self.record(address, number);
+        }
+    }
+
+    fn record(&mut self, address: Address, number: BlockNumber) {
+        self.accounts.insert(address, number);
+        self.changed.insert(address);
+    }
+
+    /// Keeps only the `limit` most recently changed accounts.
+    fn retain_most_recent(&mut self, limit: usize) {
+        if self.accounts.len() <= limit {
+            return
+        }
+        let mut numbers = self.accounts.values().copied().collect::<Vec<_>>();
+        let (_, min_number, _) = numbers.select_nth_unstable_by(limit - 1, |a, b| b.cmp(a));
+        let min_number = *min_number;
+        let changed = &mut self.changed;
+        self.accounts.retain(|address, number| {
+            let retain = *number >= min_number;
+            if !retain {
+                // the evicted accounts are removed from the table
+                changed.insert(*address);
+            }
+            retain
+        });
+
+        // drop arbitrary accounts of the oldest block that is kept if it's only partially kept
+        let excess = self.accounts.len() - limit;
+        let drop = self
+            .accounts
+            .iter()
+            .filter(|(_, number)| **number == min_number)
+            .map(|(address, _)| *address)
+            .take(excess)
+            .collect::<Vec<_>>();
+        for address in drop {
+            self.accounts.remove(&address);
+            self.changed.insert(address);
+        }
+    }
+
+    /// Writes the changes of the hot set since it was last written to the [tables::HotAccounts]
+    /// table, keeping the [MAX_HOT_ACCOUNTS] most recently changed accounts.
+    pub fn persist<DB: Database>(&mut self, db: &DB) -> eyre::Result<()> {
+        self.retain_most_recent(MAX_HOT_ACCOUNTS);
+        if self.changed.is_empty() {
+            return Ok(())
+        }
+        let mut changed = self.changed.iter().copied().collect::<Vec<_>>();
+        changed.sort_unstable();
+
+        let tx = db.tx_mut()?;
+        let mut cursor = tx.cursor_write::<tables::HotAccounts>()?;
+        for address in changed {
+            match self.accounts.get(&address) {
+                Some(number) => cursor.upsert(address, *number)?,
+                None => {
+                    if cursor.seek_exact(address)?.is_some() {
+                        cursor.delete_current()?;
+                    }
+                }
+            }
+        }
+        drop(cursor);
+        tx.commit()?;
+        self.changed.clear();
+        Ok(())
+    }
+}
+
+/// Loads the bytecodes of the accounts of the hot set into the cache, see [HotAccounts::warm_up].
+///
+/// This is meant to run on a blocking task while the node starts.
+pub fn warm_up_hot_accounts<DB: Database>(
+    db: &DB,
+    hot_accounts: &HotAccounts,
+    cache: &BytecodeCache,
+) {
+    if hot_accounts.is_empty() {
+        return
+    }
+    let start = Instant::now();
+    match hot_accounts.warm_up(db, cache) {
+        Ok(loaded) => {
+            info!(target: "reth::cli", loaded, elapsed = ?start.elapsed(), "Warmed up hot accounts")
+        }
+        Err(err) => warn!(target: "reth::cli", %err, "Failed to warm up hot accounts"),
+    }
+}
+
+/// Records the accounts changed by the canonical chain and writes the hot set to the database every
+/// [PERSIST_INTERVAL] blocks.
+pub async fn track_hot_accounts<DB, Events>(
+    db: Arc<DB>,
+    mut hot_accounts: HotAccounts,
+    events: Events,
+) where
+    DB: Database + 'static,
+    Events: CanonStateSubscriptions,
+{
+    let mut notifications = events.subscribe_to_canonical_state();
+    let mut next_persist = 0;
+    loop {
+        let notification = match notifications.recv().await {
+            Ok(notification) => notification,
+            Err(RecvError::Lagged(skipped)) => {
+                debug!(target: "reth::cli", skipped, "Hot accounts tracker lagged behind");
+                continue
+            }
+            Err(RecvError::Closed) => return,
+        };
+        let Some(chain) = notification.committed() else { continue };
+        hot_accounts.on_chain(&chain);
+
+        let tip = chain.tip().number;
+        if tip < next_persist {
+            continue
+        }
+        next_persist = tip + PERSIST_INTERVAL;
+
+        let db = db.clone();
+        let res = tokio::task::spawn_blocking(move || {
+            let res = hot_accounts.persist(&db);
+            (hot_accounts, res)
+        })
+        .await;
+        match res {
+            Ok((accounts, res)) => {
+                hot_accounts = accounts;
+                if let Err(err) = res {
+                    error!(target: "reth::cli", %err, "Failed to persist hot accounts");
+                }
+            }
+            Err(err) => {
+                error!(target: "reth::cli", %err, "Hot accounts tracker panicked");
+                return
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::test_utils::create_test_rw_db;
+    use reth_primitives::{Account, Bytecode, B256};
+
+    #[test]
+    fn persist_and_warm_up() {
+        let db = create_test_rw_db();
+        let code_hash = B256::with_last_byte(1);
+        let tx = db.tx_mut().unwrap();
+        tx.put::<tables::PlainAccountState>(
+            Address::with_last_byte(1),
+            Account { bytecode_hash: Some(code_hash), ..Default::default() },
+        )
+        .unwrap();
+        tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(vec![0x00].into())).unwrap();
+        tx.put::<tables::PlainAccountState>(Address::with_last_byte(2), Account::default())
+            .unwrap();
+        tx.commit().unwrap();
+
+        let mut hot_accounts = HotAccounts::default();
+        hot_accounts.record(Address::with_last_byte(1), 1);
+        hot_accounts.record(Address::with_last_byte(2), 2);
+        // destroyed account without state
+        hot_accounts.record(Address::with_last_byte(3), 3);
+        hot_accounts.persist(&db).unwrap();
+
+        let hot_accounts = HotAccounts::load(&db).unwrap();
+        assert_eq!(hot_accounts.len(), 3);
+        let cache = BytecodeCache::default();
+        assert_eq!(hot_accounts.warm_up(&db, &cache).unwrap(), 2);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(&code_hash).is_some());
+    }
+
+    #[test]
+    fn persist_changes() {
+        let db = create_test_rw_db();
+        let mut hot_accounts = HotAccounts::default();
+        for i in 0..4u8 {
+            hot_accounts.record(Address::with_last_byte(i), i as u64);
+        }
+        hot_accounts.persist(&db).unwrap();
+        assert!(hot_accounts.changed.is_empty());
+
+        // only the recorded and the evicted accounts are written
+        hot_accounts.record(Address::with_last_byte(1), 4);
+        hot_accounts.retain_most_recent(3);
+        assert_eq!(
+            hot_accounts.changed,
+            HashSet::from([Address::with_last_byte(0), Address::with_last_byte(1)])
+        );
+        hot_accounts.persist(&db).unwrap();
+
+        let loaded = HotAccounts::load(&db).unwrap();
+        assert_eq!(loaded.accounts, hot_accounts.accounts);
+        assert_eq!(loaded.accounts.get(&Address::with_last_byte(1)), Some(&4));
+    }
+
+    #[test]
+    fn retain_most_recent() {
+        let mut hot_accounts = HotAccounts::default();
+        for i in 0..10u8 {
+            hot_accounts.record(Address::with_last_byte(i), i as u64 / 2);
+        }
+
+        hot_accounts.retain_most_recent(5);
+        assert_eq!(hot_accounts.len(), 5);
+        for i in 6..10u8 {
+            assert!(hot_accounts.accounts.contains_key(&Address::with_last_byte(i)));
+        }
+        // one of the accounts changed by block 2
+        assert_eq!(hot_accounts.accounts.values().filter(|number| **number == 2).count(), 1);
+
+        hot_accounts.retain_most_recent(10);
+        assert_eq!(hot_accounts.len(), 5);
+    }
+}
@@ -0,0 +1,189 @@
+//! Indexing of the internal transfers of the blocks executed by the pipeline and the tree.
+
+use reth_blockchain_tree::TreeIndexer;
+use reth_db::{
+    cursor::DbCursorRO, database::Database, models::StoredBlockInternalTransfers, tables,
+    transaction::DbTxMut,
+};
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{BlockHash, BlockNumHash, BlockNumber, InternalTransfer};
+use reth_provider::DatabaseProviderRW;
+use reth_revm::{internal_transfers::InternalTransfersInspector, stack::SharedExecutionInspector};
+use reth_stages::{stages::ExecutionIndexer, StageError};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::RangeInclusive,
+    sync::{Arc, Mutex},
+};
+
+/// An [ExecutionIndexer] and [TreeIndexer] that writes the transfers collected by its
+/// [InternalTransfersInspector] into the [tables::InternalTransfers] table.
+///
+/// The inspector has to be set on the executor factory of the execution stage or the tree, see
+/// [InternalTransfersIndexer::inspector], and has to inspect all transactions.
+#[derive(Debug, Clone, Default)]
+pub struct InternalTransfersIndexer {
+    inspector: Arc<Mutex<InternalTransfersInspector>>,
+    /// The transfers of the blocks executed by the tree that aren't finalized yet, which are at
+    /// most the blocks within the maximum reorg depth of the tree, see
+    /// [TreeIndexer::finalize_blocks].
+    executed: Arc<Mutex<HashMap<BlockHash, (BlockNumber, Vec<InternalTransfer>)>>>,
+}
+
+impl InternalTransfersIndexer {
+    /// Returns the inspector that collects the transfers.
+    pub fn inspector(&self) -> SharedExecutionInspector {
+        self.inspector.clone()
+    }
+}
+
+/// Writes the transfers into the [tables::InternalTransfers] table, grouped by block.
+fn write_transfers<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    transfers: impl IntoIterator<Item = InternalTransfer>,
+) -> ProviderResult<()> {
+    let mut blocks = BTreeMap::<BlockNumber, Vec<InternalTransfer>>::new();
+    for transfer in transfers {
+        blocks.entry(transfer.block_number).or_default().push(transfer);
+    }
+    for (block_number, transfers) in blocks {
+        provider.tx_ref().put::<tables::InternalTransfers>(
+            block_number,
+            StoredBlockInternalTransfers { transfers },
+        )?;
+    }
+    Ok(())
+}
+
+impl<DB: Database> ExecutionIndexer<DB> for InternalTransfersIndexer {
+    fn reset(&mut self) {
+        self.inspector.lock().expect("not poisoned").clear();
+    }
+
+    fn write(&mut self, provider: &DatabaseProviderRW<DB>) -> Result<(), StageError> {
+        let transfers = self.inspector.lock().expect("not poisoned").take_transfers();
+        Ok(write_transfers(provider, transfers)?)
+    }
+
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<(), StageError> {
+        let mut cursor = provider.tx_ref().cursor_write::<tables::InternalTransfers>()?;
+        let mut walker = cursor.walk_range(range)?;
+        while walker.next().transpose()?.is_some() {
+            walker.delete_current()?;
+        }
+        Ok(())
+    }
+}
+
+impl<DB: Database> TreeIndexer<DB> for InternalTransfersIndexer {
+    fn block_execution_started(&self) {
+        self.inspector.lock().expect("not poisoned").clear();
+    }
+
+    fn block_executed(&self, block: BlockNumHash) {
+        let transfers = self.inspector.lock().expect("not poisoned").take_transfers();
+        self.executed.lock().expect("not poisoned").insert(block.hash, (block.number, transfers));
+    }
+
+    fn write_blocks(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        blocks: &[BlockNumHash],
+    ) -> ProviderResult<()> {
+        let executed = self.executed.lock().expect("not poisoned");
+        let transfers = blocks
+            .iter()
+            .filter_map(|block| executed.get(&block.hash))
+            .flat_map(|(_, transfers)| transfers.iter().cloned());
+        write_transfers(provider, transfers)
+    }
+
+    fn finalize_blocks(&self, finalized_block: BlockNumber) {
+        self.executed
+            .lock()
+            .expect("not poisoned")
+            .retain(|_, (block_number, _)| *block_number > finalized_block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{test_utils::TempDatabase, DatabaseEnv};
+    use reth_primitives::{Address, B256, U256};
+    use reth_provider::{test_utils::create_test_provider_factory, InternalTransfersReader};
+    use reth_revm::primitives::Env;
+
+    type TestDatabase = Arc<TempDatabase<DatabaseEnv>>;
+
+    /// Executes a transaction with a self-destruct in the block with the inspector.
+    fn execute_selfdestruct(inspector: &SharedExecutionInspector, block_number: u64, tx: u8) {
+        let mut env = Env::default();
+        env.block.number = U256::from(block_number);
+        let tx_hash = B256::with_last_byte(tx);
+        let mut inspector = inspector.lock().unwrap();
+        inspector.transaction_start(&env, tx_hash);
+        inspector.selfdestruct(
+            Address::with_last_byte(1),
+            Address::with_last_byte(2),
+            U256::from(1),
+        );
+        inspector.transaction_end(&env, tx_hash, true);
+    }
+
+    #[test]
+    fn write_and_unwind() {
+        let factory = create_test_provider_factory();
+        let mut indexer = InternalTransfersIndexer::default();
+
+        // a transaction with a self-destruct in each block
+        let inspector = indexer.inspector();
+        for block_number in 1..=3u64 {
+            execute_selfdestruct(&inspector, block_number, block_number as u8);
+        }
+
+        let provider = factory.provider_rw().unwrap();
+        indexer.write(&provider).unwrap();
+        indexer.unwind(&provider, 3..=3).unwrap();
+        provider.commit().unwrap();
+
+        let transfers = factory.internal_transfers_by_block_range(0..=3).unwrap();
+        assert_eq!(
+            transfers.iter().map(|transfer| transfer.block_number).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(transfers[1].transaction_hash, B256::with_last_byte(2));
+    }
+    #[test]
+    fn write_canonical_tree_blocks() {
+        let factory = create_test_provider_factory();
+        let indexer = InternalTransfersIndexer::default();
+        let inspector = indexer.inspector();
+
+        // two competing blocks at the same height
+        let block = |hash| BlockNumHash { number: 1, hash: B256::with_last_byte(hash) };
+        for hash in [1, 2] {
+            TreeIndexer::<TestDatabase>::block_execution_started(&indexer);
+            execute_selfdestruct(&inspector, 1, hash);
+            TreeIndexer::<TestDatabase>::block_executed(&indexer, block(hash));
+        }
+
+        // only the transfers of the canonical block are written
+        let provider = factory.provider_rw().unwrap();
+        indexer.write_blocks(&provider, &[block(2)]).unwrap();
+        provider.commit().unwrap();
+        let transfers = factory.internal_transfers_by_block_range(1..=1).unwrap();
+        assert_eq!(
+            transfers.iter().map(|transfer| transfer.transaction_hash).collect::<Vec<_>>(),
+            vec![B256::with_last_byte(2)]
+        );
+
+        // the transfers of the finalized blocks are discarded
+        TreeIndexer::<TestDatabase>::finalize_blocks(&indexer, 1);
+        assert!(indexer.executed.lock().unwrap().is_empty());
+    }
+}
@@ -5,23 +5,30 @@
 use crate::{
     args::{
         utils::{chain_help, genesis_value_parser, parse_socket_address, SUPPORTED_CHAINS},
-        DatabaseArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs, PruningArgs,
-        RpcServerArgs, TxPoolArgs,
+        DatabaseArgs, DebugArgs, DevArgs, HiveArgs, IndexArgs, NetworkArgs, PayloadBuilderArgs,
+        PruningArgs, RpcServerArgs, TxPoolArgs,
     },
-    builder::NodeConfig,
+    builder::{NodeConfig, NodeHandle},
     cli::{db_type::DatabaseBuilder, ext::RethCliExt},
     dirs::{DataDirPath, MaybePlatformPath},
     runner::CliContext,
 };
 use clap::{value_parser, Parser};
+use humantime::parse_duration;
 use reth_auto_seal_consensus::AutoSealConsensus;
 use reth_beacon_consensus::BeaconConsensus;
 use reth_interfaces::consensus::Consensus;
 use reth_primitives::ChainSpec;
-use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+use reth_tasks::shutdown::Shutdown;
+use std::{fs, net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
+use tokio::process::Command;
+use tracing::info;
 
 pub mod cl_events;
+pub mod contract_creations;
 pub mod events;
+pub mod hot_state;
+pub mod internal_transfers;
 
 /// Start the node
 #[derive(Debug, Parser)]
@@ -80,6 +87,11 @@ pub struct NodeCommand<Ext: RethCliExt = ()> {
     #[arg(long, value_name = "PATH")]
     pub trusted_setup_file: Option<PathBuf>,
 
+    /// The maximum time to wait for the node to shut down after receiving `SIGINT` or `SIGTERM`,
+    /// before exiting anyway. A second signal exits right away.
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration, default_value = "30s")]
+    pub shutdown_timeout: Duration,
+
     /// All networking related arguments
     #[clap(flatten)]
     pub network: NetworkArgs,
@@ -112,6 +124,14 @@ pub struct NodeCommand<Ext: RethCliExt = ()> {
     #[clap(flatten)]
     pub pruning: PruningArgs,
 
+    /// All indexing related arguments with --index prefix
+    #[clap(flatten)]
+    pub index: IndexArgs,
+
+    /// All hive related arguments with --hive prefix
+    #[clap(flatten)]
+    pub hive: HiveArgs,
+
     /// Rollup related arguments
     #[cfg(feature = "optimism")]
     #[clap(flatten)]
@@ -132,6 +152,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             chain,
             metrics,
             trusted_setup_file,
+            shutdown_timeout,
             instance,
             network,
             rpc,
@@ -141,6 +162,8 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             db,
             dev,
             pruning,
+            index,
+            hive,
             #[cfg(feature = "optimism")]
             rollup,
             ..
@@ -152,6 +175,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             metrics,
             instance,
             trusted_setup_file,
+            shutdown_timeout,
             network,
             rpc,
             txpool,
@@ -160,6 +184,8 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             db,
             dev,
             pruning,
+            index,
+            hive,
             #[cfg(feature = "optimism")]
             rollup,
             ext,
@@ -168,26 +194,88 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
 
     /// Execute `node` command
     pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        // wait for node exit
+        self.launch(ctx).await?.wait_for_node_exit().await
+    }
+
+    /// Execute `node` command until the node exits or the `shutdown` signal fires, in which case
+    /// the node is shut down in order.
+    ///
+    /// See also [NodeHandle::run_until_shutdown].
+    pub async fn execute_until_shutdown(
+        self,
+        ctx: CliContext,
+        shutdown: Shutdown,
+    ) -> eyre::Result<()> {
+        self.launch(ctx).await?.run_until_shutdown(shutdown).await
+    }
+
+    /// Launches the node.
+    async fn launch(self, ctx: CliContext) -> eyre::Result<NodeHandle> {
         let Self {
             datadir,
             config,
-            chain,
+            mut chain,
             metrics,
             trusted_setup_file,
+            shutdown_timeout: _,
             instance,
-            network,
-            rpc,
+            mut network,
+            mut rpc,
             txpool,
             builder,
             debug,
             db,
             dev,
             pruning,
+            index,
+            hive,
             #[cfg(feature = "optimism")]
             rollup,
             ext,
         } = self;
 
+        if hive.hive {
+            let genesis = hive.genesis()?;
+            let chain_arg = match genesis {
+                Some(genesis) => {
+                    // write the genesis with the hive fork configuration for the imports
+                    let path = std::env::temp_dir().join("reth-hive-genesis.json");
+                    fs::write(&path, serde_json::to_string(&genesis)?)?;
+                    chain = Arc::new(genesis.into());
+                    path.into_os_string()
+                }
+                None => chain.chain.to_string().into(),
+            };
+
+            match hive.bootnodes()? {
+                Some(bootnodes) => network.bootnodes = Some(bootnodes),
+                None => network.discovery.disable_discovery = true,
+            }
+            if rpc.auth_jwtsecret.is_none() {
+                rpc.auth_jwtsecret = Some(hive.jwt_secret_path()?);
+            }
+
+            // import the chain files the same way the hive client scripts do, so the database is
+            // closed before the node opens it
+            for path in hive.import_paths()? {
+                info!(target: "reth::cli", ?path, "Importing hive chain file");
+                let mut cmd = Command::new(std::env::current_exe()?);
+                cmd.arg("import")
+                    .arg("--chain")
+                    .arg(&chain_arg)
+                    .arg("--datadir")
+                    .arg(datadir.to_string());
+                if let Some(config) = &config {
+                    cmd.arg("--config").arg(config);
+                }
+                let status = cmd.arg(&path).status().await?;
+                if !status.success() {
+                    eyre::bail!("failed to import {path:?}: {status}");
+                }
+            }
+        }
+
         // set up real database
         let database = DatabaseBuilder::Real(datadir);
 
@@ -207,6 +295,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             db,
             dev,
             pruning,
+            index,
             #[cfg(feature = "optimism")]
             rollup,
         };
@@ -214,10 +303,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         let executor = ctx.task_executor;
 
         // launch the node
-        let handle = node_config.launch::<Ext>(ext, executor).await?;
-
-        // wait for node exit
-        handle.wait_for_node_exit().await
+        node_config.launch::<Ext>(ext, executor).await
     }
 
     /// Returns the [Consensus] instance to use.
@@ -257,6 +343,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_shutdown_timeout() {
+        let cmd = NodeCommand::<()>::try_parse_from(["reth"]).unwrap();
+        assert_eq!(cmd.shutdown_timeout, Duration::from_secs(30));
+
+        let cmd = NodeCommand::<()>::try_parse_from(["reth", "--shutdown-timeout", "2m"]).unwrap();
+        assert_eq!(cmd.shutdown_timeout, Duration::from_secs(120));
+    }
+
     #[test]
     fn parse_discovery_addr() {
         let cmd =
@@ -358,6 +453,23 @@ mod tests {
         assert!(cmd.dev.dev);
     }
 
+    #[test]
+    fn parse_hive() {
+        let cmd = NodeCommand::<()>::parse_from(["reth", "--hive"]);
+        assert!(cmd.hive.hive);
+        assert!(cmd.rpc.http);
+        assert!(cmd.rpc.ws);
+        assert_eq!(cmd.rpc.http_addr, IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+        assert_eq!(cmd.rpc.ws_addr, IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+        assert_eq!(cmd.rpc.auth_addr, IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+        assert_eq!(cmd.rpc.auth_port, 8551);
+        assert!(cmd.rpc.http_api.is_some());
+
+        // explicit arguments take precedence
+        let cmd = NodeCommand::<()>::parse_from(["reth", "--hive", "--http.addr", "127.0.0.1"]);
+        assert_eq!(cmd.rpc.http_addr, IpAddr::V4(Ipv4Addr::LOCALHOST));
+    }
+
     #[test]
     fn parse_instance() {
         let mut cmd = NodeCommand::<()>::parse_from(["reth"]);
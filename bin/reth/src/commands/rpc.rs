@@ -0,0 +1,148 @@
+//! Command that serves the JSON-RPC API from an existing database.
+
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs, RpcServerArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+    runner::CliContext,
+};
+use clap::Parser;
+use humantime::parse_duration;
+use reth_blockchain_tree::noop::NoopBlockchainTree;
+use reth_db::open_db_read_only;
+use reth_network_api::noop::NoopNetwork;
+use reth_primitives::ChainSpec;
+use reth_provider::{
+    providers::BlockchainProvider, BlockNumReader, CanonChainTracker, HeaderProvider,
+    ProviderFactory,
+};
+use reth_transaction_pool::noop::NoopTransactionPool;
+use std::{sync::Arc, time::Duration};
+use tracing::*;
+
+/// The default IPC endpoint of the command, distinct from the one of the node.
+#[cfg(windows)]
+pub const DEFAULT_RPC_IPC_ENDPOINT: &str = r"\\.\pipe\reth-rpc.ipc";
+
+/// The default IPC endpoint of the command, distinct from the one of the node.
+#[cfg(not(windows))]
+pub const DEFAULT_RPC_IPC_ENDPOINT: &str = "/tmp/reth-rpc.ipc";
+
+/// `reth rpc` command
+///
+/// Serves the JSON-RPC API from the database of a node without syncing or connecting to the
+/// network. The database is opened read-only, so the command can run next to the node that owns
+/// the data directory. Transactions can't be submitted and the network and txpool namespaces only
+/// return empty results.
+///
+/// The IPC server listens on [DEFAULT_RPC_IPC_ENDPOINT] by default, so it doesn't collide with
+/// the endpoint of the node.
+#[derive(Debug, Parser)]
+#[command(mut_arg("ipcpath", |arg| arg.default_value(DEFAULT_RPC_IPC_ENDPOINT)))]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// How often the database is checked for a new canonical head.
+    ///
+    /// Parses strings using [humantime::parse_duration]
+    /// --head-poll-interval 2s
+    #[arg(
+        long = "head-poll-interval",
+        value_name = "DURATION",
+        default_value = "1s",
+        value_parser = parse_duration,
+        verbatim_doc_comment
+    )]
+    head_poll_interval: Duration,
+
+    #[clap(flatten)]
+    db: DatabaseArgs,
+
+    #[clap(flatten)]
+    rpc: RpcServerArgs,
+}
+
+impl Command {
+    /// Execute `rpc` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        info!(target: "reth::cli", "reth rpc starting");
+
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db_path = data_dir.db_path();
+        info!(target: "reth::cli", path = ?db_path, "Opening database");
+        let db = Arc::new(open_db_read_only(&db_path, self.db.log_level)?);
+        let factory = ProviderFactory::new(db, self.chain);
+        let tree = NoopBlockchainTree::default();
+        let provider = BlockchainProvider::new(factory.clone(), tree.clone())?;
+        info!(target: "reth::cli", head = provider.best_block_number()?, "Database opened");
+
+        let handle = self
+            .rpc
+            .start_rpc_server(
+                provider.clone(),
+                NoopTransactionPool::default(),
+                NoopNetwork::default(),
+                ctx.task_executor.clone(),
+                tree,
+            )
+            .await?;
+        if let Some(addr) = handle.http_local_addr() {
+            info!(target: "reth::cli", url = %addr, "RPC HTTP server started");
+        }
+        if let Some(addr) = handle.ws_local_addr() {
+            info!(target: "reth::cli", url = %addr, "RPC WS server started");
+        }
+
+        // the chain is written by another process, so the head is picked up from the database
+        let mut interval = tokio::time::interval(self.head_poll_interval);
+        loop {
+            interval.tick().await;
+
+            let best = factory.chain_info()?;
+            if best.best_hash == provider.chain_info()?.best_hash {
+                continue
+            }
+            let Some(header) = factory.header_by_number(best.best_number)? else { continue };
+            debug!(target: "reth::cli", number = best.best_number, hash = ?best.best_hash, "New canonical head");
+            provider.set_canonical_head(header.seal(best.best_hash));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_builder::constants::DEFAULT_IPC_ENDPOINT;
+
+    #[test]
+    fn parse_ipc_path() {
+        let command = Command::try_parse_from(["reth"]).unwrap();
+        assert_eq!(command.rpc.ipcpath, DEFAULT_RPC_IPC_ENDPOINT);
+        assert_ne!(command.rpc.ipcpath, DEFAULT_IPC_ENDPOINT);
+
+        let command = Command::try_parse_from(["reth", "--ipcpath", "/tmp/custom.ipc"]).unwrap();
+        assert_eq!(command.rpc.ipcpath, "/tmp/custom.ipc");
+    }
+}
@@ -66,6 +66,7 @@ impl Command {
                     tx.clear::<tables::TransactionBlock>()?;
                     tx.clear::<tables::BlockOmmers>()?;
                     tx.clear::<tables::BlockWithdrawals>()?;
+                    tx.clear::<tables::WithdrawalHistory>()?;
                     tx.put::<tables::SyncStage>(StageId::Bodies.to_string(), Default::default())?;
                     insert_genesis_header::<DatabaseEnv>(tx, self.chain)?;
                 }
@@ -82,7 +83,10 @@ impl Command {
                     tx.clear::<tables::AccountChangeSet>()?;
                     tx.clear::<tables::StorageChangeSet>()?;
                     tx.clear::<tables::Bytecodes>()?;
+                    tx.clear::<tables::BytecodeRefs>()?;
                     tx.clear::<tables::Receipts>()?;
+                    tx.clear::<tables::InternalTransfers>()?;
+                    tx.clear::<tables::ContractCreations>()?;
                     tx.put::<tables::SyncStage>(
                         StageId::Execution.to_string(),
                         Default::default(),
@@ -146,6 +150,23 @@ impl Command {
                         Default::default(),
                     )?;
                 }
+                StageEnum::LogsBlooms => {
+                    tx.clear::<tables::LogsBlooms4K>()?;
+                    tx.clear::<tables::LogsBlooms64K>()?;
+                    tx.put::<tables::SyncStage>(
+                        StageId::IndexLogsBlooms.to_string(),
+                        Default::default(),
+                    )?;
+                }
+                StageEnum::AddressAppearances => {
+                    tx.clear::<tables::AddressAppearances>()?;
+                    // the checkpoint of the optional stage is removed, so the blockchain tree
+                    // stops extending the index until the stage runs again
+                    tx.delete::<tables::SyncStage>(
+                        StageId::IndexAddressAppearances.to_string(),
+                        None,
+                    )?;
+                }
                 StageEnum::TotalDifficulty => {
                     tx.clear::<tables::HeaderTD>()?;
                     tx.put::<tables::SyncStage>(
@@ -116,6 +116,7 @@ async fn unwind_and_copy<DB: Database>(
         .update(|tx| tx.import_dupsort::<tables::PlainStorageState, _>(&unwind_inner_tx))??;
     output_db.update(|tx| tx.import_table::<tables::PlainAccountState, _>(&unwind_inner_tx))??;
     output_db.update(|tx| tx.import_table::<tables::Bytecodes, _>(&unwind_inner_tx))??;
+    output_db.update(|tx| tx.import_table::<tables::BytecodeRefs, _>(&unwind_inner_tx))??;
 
     Ok(())
 }
@@ -8,22 +8,23 @@ use crate::{
         utils::{chain_help, chain_spec_value_parser, SUPPORTED_CHAINS},
         DatabaseArgs, NetworkArgs, StageEnum,
     },
-    dirs::{DataDirPath, MaybePlatformPath},
+    dirs::{ChainPath, DataDirPath, MaybePlatformPath},
     prometheus_exporter,
     version::SHORT_VERSION,
 };
 use clap::Parser;
 use reth_beacon_consensus::BeaconConsensus;
 use reth_config::Config;
-use reth_db::init_db;
+use reth_db::{database::Database, init_db, overlay::OverlayDatabase, DatabaseEnv};
 use reth_downloaders::bodies::bodies::BodiesDownloaderBuilder;
 use reth_primitives::ChainSpec;
 use reth_provider::{ProviderFactory, StageCheckpointReader};
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, ExecutionStageThresholds,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
-        StorageHashingStage, TransactionLookupStage,
+        IndexAccountHistoryStage, IndexAddressAppearancesStage, IndexLogsBloomsStage,
+        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
+        TransactionLookupStage,
     },
     ExecInput, Stage, StageExt, UnwindInput,
 };
@@ -97,6 +98,8 @@ pub struct Command {
 
     /// Commits the changes in the database. WARNING: potentially destructive.
     ///
+    /// Without it, the changes are kept in memory and discarded when the command exits.
+    ///
     /// Useful when you want to run diagnostics on the database.
     // TODO: We should consider allowing to run hooks at the end of the stage run,
     // e.g. query the DB size, or any table data.
@@ -125,9 +128,6 @@ impl Command {
         let db = Arc::new(init_db(db_path, self.db.log_level)?);
         info!(target: "reth::cli", "Database opened");
 
-        let factory = ProviderFactory::new(Arc::clone(&db), self.chain.clone());
-        let mut provider_rw = factory.provider_rw()?;
-
         if let Some(listen_addr) = self.metrics {
             info!(target: "reth::cli", "Starting metrics endpoint at {}", listen_addr);
             prometheus_exporter::serve(
@@ -139,6 +139,26 @@ impl Command {
             .await?;
         }
 
+        if self.commit {
+            self.run(config, data_dir, Arc::clone(&db), db).await
+        } else {
+            // buffer the changes in memory, so the stages see their own writes across batches
+            self.run(config, data_dir, Arc::clone(&db), OverlayDatabase::new(db)).await
+        }
+    }
+
+    /// Runs the stage against the given database. The network of the bodies stage always reads
+    /// the headers from the on-disk database.
+    async fn run<DB: Database + 'static>(
+        self,
+        config: Config,
+        data_dir: ChainPath<DataDirPath>,
+        db: Arc<DatabaseEnv>,
+        stage_db: DB,
+    ) -> eyre::Result<()> {
+        let factory = ProviderFactory::new(stage_db, self.chain.clone());
+        let mut provider_rw = factory.provider_rw()?;
+
         let batch_size = self.batch_size.unwrap_or(self.to - self.from + 1);
 
         let (mut exec_stage, mut unwind_stage): (Box<dyn Stage<_>>, Option<Box<dyn Stage<_>>>) =
@@ -226,6 +246,10 @@ impl Command {
                 ),
                 StageEnum::AccountHistory => (Box::<IndexAccountHistoryStage>::default(), None),
                 StageEnum::StorageHistory => (Box::<IndexStorageHistoryStage>::default(), None),
+                StageEnum::LogsBlooms => (Box::<IndexLogsBloomsStage>::default(), None),
+                StageEnum::AddressAppearances => {
+                    (Box::<IndexAddressAppearancesStage>::default(), None)
+                }
                 _ => return Ok(()),
             };
         if let Some(unwind_stage) = &unwind_stage {
@@ -247,10 +271,8 @@ impl Command {
                 let unwind_output = unwind_stage.unwind(&provider_rw, unwind)?;
                 unwind.checkpoint = unwind_output.checkpoint;
 
-                if self.commit {
-                    provider_rw.commit()?;
-                    provider_rw = factory.provider_rw()?;
-                }
+                provider_rw.commit()?;
+                provider_rw = factory.provider_rw()?;
             }
         }
 
@@ -265,10 +287,8 @@ impl Command {
 
             input.checkpoint = Some(output.checkpoint);
 
-            if self.commit {
-                provider_rw.commit()?;
-                provider_rw = factory.provider_rw()?;
-            }
+            provider_rw.commit()?;
+            provider_rw = factory.provider_rw()?;
 
             if output.done {
                 break
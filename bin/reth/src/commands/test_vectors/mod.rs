@@ -2,6 +2,7 @@
 
 use clap::{Parser, Subcommand};
 
+mod network;
 mod tables;
 
 /// Generate test-vectors for different data types.
@@ -19,6 +20,12 @@ pub enum Subcommands {
         /// List of table names. Case-sensitive.
         names: Vec<String>,
     },
+    /// Generates RLP test vectors for specified network messages. If no message is specified,
+    /// generate for all.
+    Network {
+        /// List of message names. Case-sensitive.
+        names: Vec<String>,
+    },
 }
 
 impl Command {
@@ -28,6 +35,9 @@ impl Command {
             Subcommands::Tables { names } => {
                 tables::generate_vectors(names)?;
             }
+            Subcommands::Network { names } => {
+                network::generate_vectors(names)?;
+            }
         }
         Ok(())
     }
@@ -0,0 +1,110 @@
+use alloy_rlp::{Decodable, Encodable};
+use eyre::Result;
+use proptest::{
+    arbitrary::Arbitrary,
+    prelude::{any, ProptestConfig},
+    strategy::{Strategy, ValueTree},
+    test_runner::TestRunner,
+};
+use reth_eth_wire::{
+    BlockBodies, BlockHeaders, GetBlockBodies, GetBlockHeaders, GetNodeData, GetPooledTransactions,
+    GetReceipts, NewBlock, NewBlockHashes, NewPooledTransactionHashes66,
+    NewPooledTransactionHashes68, NodeData, Receipts, Transactions,
+};
+use reth_primitives::{fs, Bytes};
+use serde::Serialize;
+use tracing::error;
+
+const VECTORS_FOLDER: &str = "testdata/micro/network";
+const PER_MESSAGE: usize = 100;
+
+/// A network message and its RLP encoding.
+#[derive(Serialize)]
+struct MessageVector<T> {
+    value: T,
+    rlp: Bytes,
+}
+
+/// Generates test vectors for specified network `messages`. If list is empty, then generate for
+/// all messages.
+pub(crate) fn generate_vectors(mut messages: Vec<String>) -> Result<()> {
+    let mut runner = TestRunner::new(ProptestConfig::default());
+    fs::create_dir_all(VECTORS_FOLDER)?;
+
+    macro_rules! generate {
+        ([$(($message_type:ident, $per_message:expr)),*]) => {
+            let all_messages = vec![$(stringify!($message_type).to_string(),)*];
+
+            if messages.is_empty() {
+                messages = all_messages;
+            }
+
+            for message in messages {
+                match message.as_str() {
+                    $(
+                        stringify!($message_type) => {
+                            println!("Generating test vectors for <{}>.", stringify!($message_type));
+
+                            generate_message_vector::<$message_type>(
+                                &mut runner,
+                                stringify!($message_type),
+                                $per_message,
+                            )?;
+                        },
+                    )*
+                    _ => {
+                        error!(target: "reth::cli", "Unknown message: {}", message);
+                    }
+                }
+            }
+        }
+    }
+
+    generate!([
+        (GetBlockHeaders, PER_MESSAGE),
+        (BlockHeaders, 10),
+        (GetBlockBodies, PER_MESSAGE),
+        (BlockBodies, 10),
+        (NewBlockHashes, PER_MESSAGE),
+        (NewBlock, 10),
+        (Transactions, 10),
+        (NewPooledTransactionHashes66, PER_MESSAGE),
+        (NewPooledTransactionHashes68, PER_MESSAGE),
+        (GetPooledTransactions, PER_MESSAGE),
+        (GetNodeData, PER_MESSAGE),
+        (NodeData, PER_MESSAGE),
+        (GetReceipts, PER_MESSAGE),
+        (Receipts, 10)
+    ]);
+
+    Ok(())
+}
+
+/// Generates test vectors for a network message. Every vector is checked to decode to the same
+/// message.
+fn generate_message_vector<T>(runner: &mut TestRunner, name: &str, per_message: usize) -> Result<()>
+where
+    T: Arbitrary + Encodable + Decodable + Serialize + PartialEq,
+{
+    let messages = proptest::collection::vec(any::<T>(), per_message)
+        .no_shrink()
+        .new_tree(runner)
+        .map_err(|e| eyre::eyre!("{e}"))?
+        .current();
+
+    let vectors = messages
+        .into_iter()
+        .map(|value| {
+            let rlp = alloy_rlp::encode(&value);
+            let decoded = T::decode(&mut rlp.as_slice())?;
+            eyre::ensure!(decoded == value, "<{name}> doesn't roundtrip");
+            Ok(MessageVector { value, rlp: rlp.into() })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    serde_json::to_writer_pretty(
+        std::io::BufWriter::new(std::fs::File::create(format!("{VECTORS_FOLDER}/{name}.json"))?),
+        &vectors,
+    )
+    .map_err(|e| eyre::eyre!({ e }))
+}
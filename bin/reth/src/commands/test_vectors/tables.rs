@@ -63,8 +63,15 @@ pub(crate) fn generate_vectors(mut tables: Vec<String>) -> Result<()> {
         (Headers, PER_TABLE, TABLE),
         (BlockBodyIndices, PER_TABLE, TABLE),
         (BlockOmmers, 100, TABLE),
+        (BlockWithdrawals, 100, TABLE),
         (TxHashNumber, PER_TABLE, TABLE),
         (Transactions, 100, TABLE),
+        (TransactionBlock, PER_TABLE, TABLE),
+        (Receipts, 100, TABLE),
+        (BytecodeRefs, PER_TABLE, TABLE),
+        (HotAccounts, PER_TABLE, TABLE),
+        (InternalTransfers, 100, TABLE),
+        (ContractCreations, PER_TABLE, TABLE),
         (PlainStorageState, PER_TABLE, DUPSORT),
         (PlainAccountState, PER_TABLE, TABLE)
     ]);
@@ -290,6 +290,14 @@ impl<D> ChainPath<D> {
         self.0.join("snapshots").into()
     }
 
+    /// Returns the path to the directory the chunks of a checkpoint are downloaded to, see
+    /// [CheckpointSyncCommand](crate::commands::checkpoint_sync::CheckpointSyncCommand).
+    ///
+    /// `<DIR>/<CHAIN_ID>/checkpoint`
+    pub fn checkpoint_path(&self) -> PathBuf {
+        self.0.join("checkpoint").into()
+    }
+
     /// Returns the path to the reth p2p secret key for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/discovery-secret`
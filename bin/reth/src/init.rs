@@ -8,13 +8,14 @@ use reth_db::{
 };
 use reth_interfaces::{db::DatabaseError, provider::ProviderResult};
 use reth_primitives::{
-    stage::StageId, Account, Bytecode, ChainSpec, Receipts, StorageEntry, B256, U256,
+    stage::StageId, Account, Bytecode, ChainSpec, PruneModes, Receipts, StorageEntry, B256, U256,
 };
 use reth_provider::{
-    bundle_state::{BundleStateInit, RevertsInit},
+    bundle_state::{mark_bytecode_refs_tracked, BundleStateInit, RevertsInit},
     BundleStateWithReceipts, DatabaseProviderRW, HashingWriter, HistoryWriter, OriginalValuesKnown,
     ProviderError, ProviderFactory,
 };
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{BTreeMap, HashMap},
     sync::Arc,
@@ -45,6 +46,121 @@ impl From<DatabaseError> for InitDatabaseError {
     }
 }
 
+/// The mode the node was initialized in.
+///
+/// The mode is persisted in the database the first time the node is launched, and the node refuses
+/// to start with a prune configuration that doesn't match it. This prevents history from being
+/// pruned on an archive node, or a pruned node from serving as an archive node, because the
+/// configuration drifted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+pub enum NodeMode {
+    /// All historical data is retained.
+    Archive,
+    /// Historical data is pruned according to the prune configuration.
+    Full {
+        /// The prune configuration of the node.
+        prune_modes: PruneModes,
+    },
+}
+
+impl NodeMode {
+    /// Returns the node mode that corresponds to the given prune configuration.
+    pub fn from_prune_modes(prune_modes: Option<&PruneModes>) -> Self {
+        match prune_modes {
+            Some(prune_modes) if prune_modes != &PruneModes::none() => {
+                NodeMode::Full { prune_modes: prune_modes.clone() }
+            }
+            _ => NodeMode::Archive,
+        }
+    }
+}
+
+impl std::fmt::Display for NodeMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeMode::Archive => f.write_str("archive"),
+            NodeMode::Full { .. } => f.write_str("full"),
+        }
+    }
+}
+
+/// The key of the node mode in [tables::SyncStageProgress].
+const NODE_MODE_KEY: &str = "NodeMode";
+
+/// Node mode initialization error type.
+#[derive(Debug, thiserror::Error)]
+pub enum NodeModeError {
+    /// The configured node mode doesn't match the one of the database.
+    #[error(
+        "configured node mode {configured:?} does not match the node mode {persisted:?} of the \
+         database"
+    )]
+    Mismatch {
+        /// The node mode of the database.
+        persisted: NodeMode,
+        /// The configured node mode.
+        configured: NodeMode,
+    },
+    /// The node is configured as an archive node, but the database was pruned.
+    #[error("configured node mode is archive, but the database was pruned")]
+    Pruned,
+    /// The persisted node mode is malformed.
+    #[error("persisted node mode is malformed: {0}")]
+    Malformed(#[source] serde_json::Error),
+    /// Provider error.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+impl From<DatabaseError> for NodeModeError {
+    fn from(error: DatabaseError) -> Self {
+        Self::Provider(ProviderError::Database(error))
+    }
+}
+
+/// Writes the node mode to the database if it has not already been written, or checks that the
+/// configured node mode matches the persisted one otherwise.
+///
+/// Databases that were synced before the node mode was persisted get the mode of their data: a
+/// database with prune checkpoints was pruned, and one without them that contains blocks holds the
+/// whole history.
+pub fn init_node_mode<DB: Database>(
+    factory: &ProviderFactory<DB>,
+    mode: NodeMode,
+) -> Result<NodeMode, NodeModeError> {
+    let provider_rw = factory.provider_rw()?;
+    let tx = provider_rw.tx_ref();
+
+    if let Some(persisted) = tx.get::<tables::SyncStageProgress>(NODE_MODE_KEY.to_string())? {
+        let persisted: NodeMode =
+            serde_json::from_slice(&persisted).map_err(NodeModeError::Malformed)?;
+        if persisted != mode {
+            return Err(NodeModeError::Mismatch { persisted, configured: mode })
+        }
+        return Ok(mode)
+    }
+
+    let pruned = tx.entries::<tables::PruneCheckpoints>()? > 0;
+    let synced = tx.cursor_read::<tables::CanonicalHeaders>()?.last()?.is_some_and(|(n, _)| n > 0);
+    match &mode {
+        NodeMode::Archive if pruned => return Err(NodeModeError::Pruned),
+        NodeMode::Full { .. } if synced && !pruned => {
+            return Err(NodeModeError::Mismatch { persisted: NodeMode::Archive, configured: mode })
+        }
+        _ => {}
+    }
+
+    debug!(%mode, "Writing node mode.");
+    tx.put::<tables::SyncStageProgress>(
+        NODE_MODE_KEY.to_string(),
+        serde_json::to_vec(&mode).expect("node mode serializes"),
+    )?;
+    provider_rw.commit()?;
+
+    Ok(mode)
+}
+
 /// Write the genesis block if it has not already been written
 pub fn init_genesis<DB: Database>(
     db: Arc<DB>,
@@ -153,7 +269,10 @@ pub fn insert_genesis_state<DB: Database>(
         0,
     );
 
+    // the references of the genesis state are counted as it is written, so they cover the whole
+    // state from now on
     bundle.write_to_db(tx, OriginalValuesKnown::Yes)?;
+    mark_bytecode_refs_tracked(tx)?;
 
     Ok(())
 }
@@ -229,8 +348,13 @@ mod tests {
         DatabaseEnv,
     };
     use reth_primitives::{
-        Address, Chain, ForkTimestamps, Genesis, GenesisAccount, IntegerList, GOERLI,
-        GOERLI_GENESIS_HASH, MAINNET, MAINNET_GENESIS_HASH, SEPOLIA, SEPOLIA_GENESIS_HASH,
+        Address, Chain, ForkTimestamps, Genesis, GenesisAccount, IntegerList, PruneCheckpoint,
+        PruneMode, PruneSegment, GOERLI, GOERLI_GENESIS_HASH, MAINNET, MAINNET_GENESIS_HASH,
+        SEPOLIA, SEPOLIA_GENESIS_HASH,
+    };
+    use reth_provider::{
+        bundle_state::bytecode_refs_tracked, test_utils::create_test_provider_factory,
+        PruneCheckpointWriter,
     };
     use std::collections::HashMap;
 
@@ -341,5 +465,55 @@ mod tests {
                 IntegerList::new([0]).unwrap()
             )],
         );
+
+        // the genesis state has its bytecode references counted
+        assert!(bytecode_refs_tracked(&tx).unwrap());
+    }
+
+    #[test]
+    fn init_node_mode_rejects_mismatch() {
+        let factory = create_test_provider_factory();
+        let full = NodeMode::from_prune_modes(Some(&PruneModes::all()));
+
+        assert_eq!(init_node_mode(&factory, full.clone()).unwrap(), full);
+        assert_eq!(init_node_mode(&factory, full.clone()).unwrap(), full);
+        assert!(matches!(
+            init_node_mode(&factory, NodeMode::from_prune_modes(None)),
+            Err(NodeModeError::Mismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn init_node_mode_of_synced_database() {
+        let full = NodeMode::from_prune_modes(Some(&PruneModes::all()));
+
+        // a synced database without prune checkpoints holds the whole history
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        provider.tx_ref().put::<tables::CanonicalHeaders>(1, B256::ZERO).unwrap();
+        provider.commit().unwrap();
+        assert!(matches!(
+            init_node_mode(&factory, full.clone()),
+            Err(NodeModeError::Mismatch { persisted: NodeMode::Archive, .. })
+        ));
+        assert_eq!(init_node_mode(&factory, NodeMode::Archive).unwrap(), NodeMode::Archive);
+
+        // a database with prune checkpoints was pruned
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        provider.tx_ref().put::<tables::CanonicalHeaders>(1, B256::ZERO).unwrap();
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::Receipts,
+                PruneCheckpoint {
+                    block_number: Some(1),
+                    tx_number: None,
+                    prune_mode: PruneMode::Full,
+                },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+        assert!(matches!(init_node_mode(&factory, NodeMode::Archive), Err(NodeModeError::Pruned)));
+        assert_eq!(init_node_mode(&factory, full.clone()).unwrap(), full);
     }
 }
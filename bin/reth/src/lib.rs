@@ -30,6 +30,7 @@
 
 pub mod args;
 pub mod builder;
+pub mod checkpoint;
 pub mod cli;
 pub mod commands;
 pub mod dirs;
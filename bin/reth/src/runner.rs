@@ -1,9 +1,15 @@
 //! Entrypoint for running commands.
 
 use futures::pin_mut;
-use reth_tasks::{TaskExecutor, TaskManager};
-use std::future::Future;
-use tracing::trace;
+use reth_tasks::{
+    shutdown::{signal, Shutdown, Signal},
+    TaskExecutor, TaskManager,
+};
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+use tracing::{trace, warn};
 
 /// Executes CLI commands.
 #[derive(Clone, Debug, Default)]
@@ -29,7 +35,7 @@ impl CliRunner {
         let AsyncCliRunner { context, task_manager, tokio_runtime } = AsyncCliRunner::new()?;
 
         // Executes the command until it finished or ctrl-c was fired
-        let task_manager = tokio_runtime.block_on(run_to_completion_or_panic(
+        let (task_manager, ()) = tokio_runtime.block_on(run_to_completion_or_panic(
             task_manager,
             run_until_ctrl_c(command(context)),
         ))?;
@@ -52,6 +58,45 @@ impl CliRunner {
         Ok(())
     }
 
+    /// Executes the given _async_ command on the tokio runtime until the command future resolves,
+    /// like [CliRunner::run_command_until_exit], but lets the command shut down on its own.
+    ///
+    /// On the first `SIGINT` or `SIGTERM` signal, the [Shutdown] future passed to the command
+    /// resolves and the command is expected to finish. The process exits without waiting for the
+    /// command if it receives a second signal or if the command and the tasks spawned via the
+    /// [TaskExecutor] didn't shut down within the `shutdown_timeout`.
+    pub fn run_command_until_graceful_exit<F, E>(
+        self,
+        shutdown_timeout: Duration,
+        command: impl FnOnce(CliContext, Shutdown) -> F,
+    ) -> Result<(), E>
+    where
+        F: Future<Output = Result<(), E>>,
+        E: Send + Sync + From<std::io::Error> + From<reth_tasks::PanickedTaskError> + 'static,
+    {
+        let AsyncCliRunner { context, task_manager, tokio_runtime } = AsyncCliRunner::new()?;
+        let (signal, shutdown) = signal();
+
+        // Executes the command until it finished on its own or after the shutdown signal
+        let (task_manager, deadline) = tokio_runtime.block_on(run_to_completion_or_panic(
+            task_manager,
+            run_until_graceful_exit(command(context, shutdown), signal, shutdown_timeout),
+        ))?;
+
+        // the tasks spawned via the task executor get the remaining time to shut down
+        let timeout = deadline.map_or(shutdown_timeout, |deadline| {
+            deadline.saturating_duration_since(Instant::now())
+        });
+        if !task_manager.graceful_shutdown_with_timeout(timeout) {
+            warn!(target: "reth::cli", ?shutdown_timeout, "Tasks didn't shut down in time, exiting");
+        }
+
+        // drop the tokio runtime on a separate thread, see `run_command_until_exit`
+        std::thread::spawn(move || drop(tokio_runtime));
+
+        Ok(())
+    }
+
     /// Executes a regular future until completion or until external signal received.
     pub fn run_until_ctrl_c<F, E>(self, fut: F) -> Result<(), E>
     where
@@ -120,12 +165,15 @@ pub fn tokio_runtime() -> Result<tokio::runtime::Runtime, std::io::Error> {
 }
 
 /// Runs the given future to completion or until a critical task panicked
-async fn run_to_completion_or_panic<F, E>(mut tasks: TaskManager, fut: F) -> Result<TaskManager, E>
+async fn run_to_completion_or_panic<F, T, E>(
+    mut tasks: TaskManager,
+    fut: F,
+) -> Result<(TaskManager, T), E>
 where
-    F: Future<Output = Result<(), E>>,
+    F: Future<Output = Result<T, E>>,
     E: Send + Sync + From<reth_tasks::PanickedTaskError> + 'static,
 {
-    {
+    let output = {
         pin_mut!(fut);
         tokio::select! {
             err = &mut tasks => {
@@ -133,8 +181,80 @@ where
             },
             res = fut => res?,
         }
+    };
+    Ok((tasks, output))
+}
+
+/// Runs the future to completion, firing the shutdown [Signal] on the first `ctrl-c` or `SIGTERM`.
+///
+/// After the signal, the future gets the `timeout` to finish and is abandoned once it elapsed or on
+/// the second signal. Returns the deadline of the shutdown, if the signal was fired.
+async fn run_until_graceful_exit<F, E>(
+    fut: F,
+    signal: Signal,
+    timeout: Duration,
+) -> Result<Option<Instant>, E>
+where
+    F: Future<Output = Result<(), E>>,
+    E: Send + Sync + 'static + From<std::io::Error>,
+{
+    pin_mut!(fut);
+
+    tokio::select! {
+        res = wait_for_exit_signal() => res?,
+        res = &mut fut => {
+            res?;
+            return Ok(None)
+        },
     }
-    Ok(tasks)
+
+    warn!(target: "reth::cli", ?timeout, "Received shutdown signal, shutting down");
+    signal.fire();
+    let deadline = Instant::now() + timeout;
+
+    tokio::select! {
+        res = fut => res?,
+        _ = tokio::time::sleep(timeout) => {
+            warn!(target: "reth::cli", "Shutdown timed out, exiting");
+        },
+        res = wait_for_exit_signal() => {
+            res?;
+            warn!(target: "reth::cli", "Received second shutdown signal, exiting");
+            return Ok(Some(Instant::now()))
+        },
+    }
+
+    Ok(Some(deadline))
+}
+
+/// Waits until `ctrl-c` or `SIGTERM` (unix only) is received.
+async fn wait_for_exit_signal() -> Result<(), std::io::Error> {
+    let ctrl_c = tokio::signal::ctrl_c();
+
+    #[cfg(unix)]
+    {
+        let mut stream = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
+        let sigterm = stream.recv();
+        pin_mut!(sigterm, ctrl_c);
+
+        tokio::select! {
+            res = ctrl_c => {
+                res?;
+                trace!(target: "reth::cli",  "Received ctrl-c");
+            },
+            _ = sigterm => {
+                trace!(target: "reth::cli",  "Received SIGTERM");
+            },
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        ctrl_c.await?;
+        trace!(target: "reth::cli",  "Received ctrl-c");
+    }
+
+    Ok(())
 }
 
 /// Runs the future to completion or until:
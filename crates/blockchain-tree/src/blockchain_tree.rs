@@ -7,7 +7,7 @@ use crate::{
     state::{BlockChainId, TreeState},
     AppendableChain, BlockIndices, BlockchainTreeConfig, BundleStateData, TreeExternals,
 };
-use reth_db::{database::Database, DatabaseError};
+use reth_db::{database::Database, transaction::DbTx, DatabaseError};
 use reth_interfaces::{
     blockchain_tree::{
         error::{BlockchainTreeError, CanonicalError, InsertBlockError, InsertBlockErrorKind},
@@ -29,6 +29,7 @@ use reth_provider::{
     ChainSpecProvider, DisplayBlocksChain, ExecutorFactory, HeaderProvider, ProviderError,
 };
 use reth_stages::{MetricEvent, MetricEventsSender};
+use reth_trie::{hashed_cursor::HashedPostState, updates::TrieUpdates};
 use std::{
     collections::{BTreeMap, HashSet},
     sync::Arc,
@@ -805,6 +806,10 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
         }
         // clean block buffer.
         self.state.buffered_blocks.remove_old_blocks(finalized_block);
+        // the indexed data of the finalized blocks can't be committed again
+        for indexer in &self.externals.indexers {
+            indexer.finalize_blocks(finalized_block);
+        }
     }
 
     /// Reads the last `N` canonical hashes from the database and updates the block indices of the
@@ -1018,6 +1023,10 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
             })
             .into());
         };
+
+        // reject the reorg before touching the tree state if it's too deep
+        self.ensure_reorg_depth(chain_id)?;
+
         let chain = self.state.chains.remove(&chain_id).expect("To be present");
 
         trace!(target: "blockchain_tree", ?chain, "Found chain to make canonical");
@@ -1071,7 +1080,15 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
             chain_notification =
                 CanonStateNotification::Commit { new: Arc::new(new_canon_chain.clone()) };
             // append to database
-            self.commit_canonical_to_database(new_canon_chain, &mut durations_recorder)?;
+            let provider = self.externals.provider_factory.provider()?;
+            let state_root_updates = Self::state_root_updates(&new_canon_chain, provider.tx_ref())?;
+            drop(provider);
+            durations_recorder.record_relative(MakeCanonicalAction::RetrieveStateTrieUpdates);
+            self.commit_canonical_to_database(
+                new_canon_chain,
+                state_root_updates,
+                &mut durations_recorder,
+            )?;
         } else {
             // it forks to canonical block that is not the tip.
 
@@ -1087,6 +1104,11 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
                 unreachable!("all chains should point to canonical chain.");
             }
 
+            // Check the state root of the new chain on top of the reverted canonical chain before
+            // changing the database, with the revert buffered in memory.
+            let state_root_updates = self.reorg_state_root_updates(&new_canon_chain)?;
+            durations_recorder.record_relative(MakeCanonicalAction::RetrieveStateTrieUpdates);
+
             let old_canon_chain = self.revert_canonical_from_database(canon_fork.number);
             durations_recorder
                 .record_relative(MakeCanonicalAction::RevertCanonicalChainFromDatabase);
@@ -1106,7 +1128,11 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
                 Ok(val) => val,
             };
             // commit new canonical chain.
-            self.commit_canonical_to_database(new_canon_chain.clone(), &mut durations_recorder)?;
+            self.commit_canonical_to_database(
+                new_canon_chain.clone(),
+                state_root_updates,
+                &mut durations_recorder,
+            )?;
 
             if let Some(old_canon_chain) = old_canon_chain {
                 // state action
@@ -1156,20 +1182,17 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
         self.canon_state_notification_sender.clone()
     }
 
-    /// Write the given chain to the database as canonical.
-    fn commit_canonical_to_database(
-        &self,
-        chain: Chain,
-        recorder: &mut MakeCanonicalDurationsRecorder,
-    ) -> RethResult<()> {
-        // Compute state root before opening write transaction.
+    /// Computes the state root of the given chain on top of the database transaction, checks it
+    /// against the state root of the chain tip and returns the hashed state and trie updates of
+    /// the chain.
+    fn state_root_updates<TX: DbTx>(
+        chain: &Chain,
+        tx: &TX,
+    ) -> RethResult<(HashedPostState, TrieUpdates)> {
         let hashed_state = chain.state().hash_state_slow();
         let (state_root, trie_updates) = chain
             .state()
-            .state_root_calculator(
-                self.externals.provider_factory.provider()?.tx_ref(),
-                &hashed_state,
-            )
+            .state_root_calculator(tx, &hashed_state)
             .root_with_updates()
             .map_err(Into::<DatabaseError>::into)?;
         let tip = chain.tip();
@@ -1182,9 +1205,38 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
                 },
             ))));
         }
-        recorder.record_relative(MakeCanonicalAction::RetrieveStateTrieUpdates);
+        Ok((hashed_state, trie_updates))
+    }
 
+    /// Computes the state root updates of the given chain on top of the canonical chain reverted
+    /// down to the fork block of the chain.
+    ///
+    /// The revert is buffered in memory, the database isn't changed.
+    fn reorg_state_root_updates(
+        &self,
+        chain: &Chain,
+    ) -> RethResult<(HashedPostState, TrieUpdates)> {
+        let provider = self.externals.provider_factory.provider_overlay()?;
+        let tip = provider.last_block_number()?;
+        provider
+            .take_block_and_execution_range(
+                self.externals.provider_factory.chain_spec().as_ref(),
+                (chain.fork_block().number + 1)..=tip,
+            )
+            .map_err(|e| BlockExecutionError::CanonicalRevert { inner: Box::new(e) })?;
+        Self::state_root_updates(chain, provider.tx_ref())
+    }
+
+    /// Write the given chain to the database as canonical, with the state root updates of the
+    /// chain on top of the database.
+    fn commit_canonical_to_database(
+        &self,
+        chain: Chain,
+        (hashed_state, trie_updates): (HashedPostState, TrieUpdates),
+        recorder: &mut MakeCanonicalDurationsRecorder,
+    ) -> RethResult<()> {
         let (blocks, state) = chain.into_inner();
+        let num_hashes = blocks.iter().map(|(_, block)| block.num_hash()).collect::<Vec<_>>();
         let provider_rw = self.externals.provider_factory.provider_rw()?;
         provider_rw
             .append_blocks_with_state(
@@ -1194,11 +1246,25 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
                 trie_updates,
                 self.prune_modes.as_ref(),
             )
-            .map_err(|e| BlockExecutionError::CanonicalCommit { inner: e.to_string() })?;
+            .map_err(|e| BlockExecutionError::CanonicalCommit { inner: Box::new(e) })?;
+        for indexer in &self.externals.indexers {
+            indexer
+                .write_blocks(&provider_rw, &num_hashes)
+                .map_err(|e| BlockExecutionError::CanonicalCommit { inner: Box::new(e) })?;
+        }
 
         provider_rw.commit()?;
         recorder.record_relative(MakeCanonicalAction::CommitCanonicalChainToDatabase);
 
+        // the blocks deeper than the maximum reorg depth aren't reorged anymore, so their indexed
+        // data can't be committed again even if no block is finalized
+        if let Some(tip) = num_hashes.last() {
+            let max_reorg_block = tip.number.saturating_sub(self.config.max_reorg_depth());
+            for indexer in &self.externals.indexers {
+                indexer.finalize_blocks(max_reorg_block);
+            }
+        }
+
         Ok(())
     }
 
@@ -1241,7 +1307,7 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
                 self.externals.provider_factory.chain_spec().as_ref(),
                 revert_range,
             )
-            .map_err(|e| BlockExecutionError::CanonicalRevert { inner: e.to_string() })?;
+            .map_err(|e| BlockExecutionError::CanonicalRevert { inner: Box::new(e) })?;
 
         provider_rw.commit()?;
 
@@ -1252,6 +1318,41 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
         }
     }
 
+    /// Checks that making the chain with the given id canonical does not unwind more canonical
+    /// blocks than the configured reorg depth limit and does not unwind the last finalized block.
+    ///
+    /// This protects pruned nodes from unwinding data they no longer have.
+    fn ensure_reorg_depth(&self, chain_id: BlockChainId) -> Result<(), CanonicalError> {
+        // find the canonical block the chain and all of its parent chains fork from
+        let mut fork_block = self.state.chains.get(&chain_id).expect("To be present").fork_block();
+        while let Some(chain) = self
+            .block_indices()
+            .get_blocks_chain_id(&fork_block.hash)
+            .and_then(|chain_id| self.state.chains.get(&chain_id))
+        {
+            fork_block = chain.fork_block();
+        }
+
+        let tip = self.block_indices().canonical_tip();
+        let depth = tip.number.saturating_sub(fork_block.number);
+        let last_finalized = self.block_indices().last_finalized_block();
+        let error =
+            if let Some(limit) = self.config.reorg_depth_limit().filter(|limit| depth > *limit) {
+                BlockchainTreeError::ReorgTooDeep { fork_block: fork_block.number, depth, limit }
+            } else if fork_block.number < last_finalized {
+                BlockchainTreeError::ReorgBelowFinalized {
+                    fork_block: fork_block.number,
+                    last_finalized,
+                }
+            } else {
+                return Ok(())
+            };
+
+        self.metrics.rejected_reorgs.increment(1);
+        warn!(target: "blockchain_tree", ?fork_block, ?tip, depth, last_finalized, "Rejecting deep reorg");
+        Err(error.into())
+    }
+
     fn update_reorg_metrics(&mut self, reorg_depth: f64) {
         self.metrics.reorgs.increment(1);
         self.metrics.latest_reorg_depth.set(reorg_depth);
@@ -1282,10 +1383,11 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::TreeIndexer;
     use assert_matches::assert_matches;
     use linked_hash_set::LinkedHashSet;
     use reth_db::{tables, test_utils::TempDatabase, transaction::DbTxMut, DatabaseEnv};
-    use reth_interfaces::test_utils::TestConsensus;
+    use reth_interfaces::{provider::ProviderResult, test_utils::TestConsensus};
     use reth_primitives::{
         constants::{EIP1559_INITIAL_BASE_FEE, EMPTY_ROOT_HASH, ETHEREUM_BLOCK_GAS_LIMIT},
         keccak256,
@@ -1301,7 +1403,7 @@ mod tests {
             blocks::BlockChainTestData, create_test_provider_factory_with_chain_spec,
             TestExecutorFactory,
         },
-        BlockWriter, BundleStateWithReceipts, ProviderFactory,
+        BlockWriter, BundleStateWithReceipts, DatabaseProviderRW, ProviderFactory,
     };
     use reth_revm::EvmProcessorFactory;
     use std::{
@@ -1621,6 +1723,99 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rejects_deep_reorg() {
+        let data = BlockChainTestData::default_with_numbers(11, 12);
+        let (block1, exec1) = data.blocks[0].clone();
+        let (block2, exec2) = data.blocks[1].clone();
+        let genesis = data.genesis;
+
+        // test pops execution results from vector, so order is from last to first.
+        let externals = setup_externals(vec![exec1.clone(), exec2, exec1]);
+        setup_genesis(&externals.provider_factory, genesis);
+
+        // allow reorgs of a single block only
+        let config = BlockchainTreeConfig::new(1, 2, 3, 2).with_reorg_depth_limit(1);
+        let mut tree = BlockchainTree::new(externals, config, None).expect("failed to create tree");
+
+        tree.insert_block(block1.clone(), BlockValidationKind::Exhaustive).unwrap();
+        tree.insert_block(block2.clone(), BlockValidationKind::Exhaustive).unwrap();
+        tree.make_canonical(&block2.hash()).unwrap();
+
+        // side block forking from genesis, making it canonical would unwind two blocks
+        let mut block1a = block1.clone();
+        let block1a_hash = B256::new([0x33; 32]);
+        block1a.hash = block1a_hash;
+        assert_eq!(
+            tree.insert_block(block1a.clone(), BlockValidationKind::Exhaustive).unwrap(),
+            InsertPayloadOk::Inserted(BlockStatus::Accepted)
+        );
+
+        let err = tree.make_canonical(&block1a_hash).unwrap_err();
+        assert_eq!(
+            err,
+            RethError::Canonical(CanonicalError::BlockchainTree(
+                BlockchainTreeError::ReorgTooDeep {
+                    fork_block: block1.number - 1,
+                    depth: 2,
+                    limit: 1,
+                }
+            ))
+        );
+
+        // the tree is left untouched
+        assert_eq!(tree.block_indices().canonical_tip(), block2.num_hash());
+        assert!(tree.block_by_hash(block1a_hash).is_some());
+    }
+
+    /// Records the blocks the indexer is asked to finalize.
+    #[derive(Debug, Default, Clone)]
+    struct FinalizedBlocksIndexer {
+        finalized: Arc<std::sync::Mutex<Vec<BlockNumber>>>,
+    }
+
+    impl<DB: Database> TreeIndexer<DB> for FinalizedBlocksIndexer {
+        fn block_execution_started(&self) {}
+
+        fn block_executed(&self, _block: BlockNumHash) {}
+
+        fn write_blocks(
+            &self,
+            _provider: &DatabaseProviderRW<DB>,
+            _blocks: &[BlockNumHash],
+        ) -> ProviderResult<()> {
+            Ok(())
+        }
+
+        fn finalize_blocks(&self, finalized_block: BlockNumber) {
+            self.finalized.lock().unwrap().push(finalized_block);
+        }
+    }
+
+    #[test]
+    fn finalizes_indexed_blocks_below_max_reorg_depth() {
+        let data = BlockChainTestData::default_with_numbers(11, 12);
+        let (block1, exec1) = data.blocks[0].clone();
+        let (block2, exec2) = data.blocks[1].clone();
+        let genesis = data.genesis;
+
+        // test pops execution results from vector, so order is from last to first.
+        let indexer = FinalizedBlocksIndexer::default();
+        let externals = setup_externals(vec![exec2, exec1]).with_indexer(indexer.clone());
+        setup_genesis(&externals.provider_factory, genesis);
+
+        let config = BlockchainTreeConfig::new(1, 2, 3, 2);
+        let mut tree = BlockchainTree::new(externals, config, None).expect("failed to create tree");
+
+        tree.insert_block(block1.clone(), BlockValidationKind::Exhaustive).unwrap();
+        tree.insert_block(block2.clone(), BlockValidationKind::Exhaustive).unwrap();
+        tree.make_canonical(&block2.hash()).unwrap();
+
+        // the block at the maximum reorg depth below the new tip is finalized, even though no
+        // block was finalized by the consensus layer
+        assert_eq!(indexer.finalized.lock().unwrap().last(), Some(&(block2.number - 1)));
+    }
+
     #[tokio::test]
     async fn sanity_path() {
         let data = BlockChainTestData::default_with_numbers(11, 12);
@@ -210,6 +210,11 @@ impl AppendableChain {
 
         let provider = BundleStateProvider::new(state_provider, bundle_state_data_provider);
 
+        for indexer in &externals.indexers {
+            indexer.block_execution_started();
+        }
+
+        let num_hash = block.num_hash();
         let mut executor = externals.executor_factory.with_state(&provider);
         let block = block.unseal();
         executor.execute_and_verify_receipt(&block, U256::MAX)?;
@@ -228,6 +233,10 @@ impl AppendableChain {
             }
         }
 
+        for indexer in &externals.indexers {
+            indexer.block_executed(num_hash);
+        }
+
         Ok(bundle_state)
     }
 
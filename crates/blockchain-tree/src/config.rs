@@ -19,6 +19,10 @@ pub struct BlockchainTreeConfig {
     /// be 256. It covers both number of blocks required for reorg, and number of blocks
     /// required for `BLOCKHASH` EVM opcode.
     num_of_additional_canonical_block_hashes: u64,
+    /// The maximum number of canonical blocks a reorg may unwind, if any.
+    ///
+    /// Reorgs that unwind the last finalized block are always rejected.
+    reorg_depth_limit: Option<u64>,
 }
 
 impl Default for BlockchainTreeConfig {
@@ -33,6 +37,7 @@ impl Default for BlockchainTreeConfig {
             num_of_additional_canonical_block_hashes: 256,
             // max unconnected blocks.
             max_unconnected_blocks: 200,
+            reorg_depth_limit: None,
         }
     }
 }
@@ -53,9 +58,21 @@ impl BlockchainTreeConfig {
             max_reorg_depth,
             num_of_additional_canonical_block_hashes,
             max_unconnected_blocks,
+            reorg_depth_limit: None,
         }
     }
 
+    /// Set the maximum number of canonical blocks a reorg may unwind.
+    pub fn with_reorg_depth_limit(mut self, limit: u64) -> Self {
+        self.reorg_depth_limit = Some(limit);
+        self
+    }
+
+    /// Return the maximum number of canonical blocks a reorg may unwind, if any.
+    pub fn reorg_depth_limit(&self) -> Option<u64> {
+        self.reorg_depth_limit
+    }
+
     /// Return the maximum reorg depth.
     pub fn max_reorg_depth(&self) -> u64 {
         self.max_reorg_depth
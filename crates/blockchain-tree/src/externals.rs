@@ -1,5 +1,6 @@
 //! Blockchain tree externals.
 
+use crate::TreeIndexer;
 use reth_db::{cursor::DbCursorRO, database::Database, tables, transaction::DbTx};
 use reth_interfaces::{consensus::Consensus, RethResult};
 use reth_primitives::{BlockHash, BlockNumber};
@@ -15,28 +16,35 @@ use std::{collections::BTreeMap, sync::Arc};
 /// - A handle to the consensus engine
 /// - The executor factory to execute blocks with
 /// - The chain spec
+/// - The indexers of the data collected while executing blocks
 #[derive(Debug)]
-pub struct TreeExternals<DB, EF> {
+pub struct TreeExternals<DB: Database, EF> {
     /// The provider factory, used to commit the canonical chain, or unwind it.
     pub(crate) provider_factory: ProviderFactory<DB>,
     /// The consensus engine.
     pub(crate) consensus: Arc<dyn Consensus>,
     /// The executor factory to execute blocks with.
     pub(crate) executor_factory: EF,
+    /// The indexers of the data collected while executing blocks.
+    pub(crate) indexers: Vec<Box<dyn TreeIndexer<DB>>>,
 }
 
-impl<DB, EF> TreeExternals<DB, EF> {
+impl<DB: Database, EF> TreeExternals<DB, EF> {
     /// Create new tree externals.
     pub fn new(
         provider_factory: ProviderFactory<DB>,
         consensus: Arc<dyn Consensus>,
         executor_factory: EF,
     ) -> Self {
-        Self { provider_factory, consensus, executor_factory }
+        Self { provider_factory, consensus, executor_factory, indexers: Vec::new() }
+    }
+
+    /// Add an indexer of the data that is collected by the executor factory.
+    pub fn with_indexer(mut self, indexer: impl TreeIndexer<DB> + 'static) -> Self {
+        self.indexers.push(Box::new(indexer));
+        self
     }
-}
 
-impl<DB: Database, EF> TreeExternals<DB, EF> {
     /// Fetches the latest canonical block hashes by walking backwards from the head.
     ///
     /// Returns the hashes sorted by increasing block numbers
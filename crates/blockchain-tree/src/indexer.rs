@@ -0,0 +1,35 @@
+//! Indexing of the data collected while the tree executes blocks.
+
+use reth_db::database::Database;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{BlockNumHash, BlockNumber};
+use reth_provider::DatabaseProviderRW;
+use std::fmt::Debug;
+
+/// Indexes data that is collected while the tree executes blocks, e.g. by an inspector of the
+/// executor factory, and writes it once the blocks are committed to the canonical chain.
+///
+/// This is the counterpart of the indexers of the execution stage for the blocks that are executed
+/// by the tree. The data of the unwound canonical blocks is removed by the provider, so the data of
+/// a block is kept until the block is finalized or deeper than the maximum reorg depth, in case
+/// it's committed again after a reorg.
+pub trait TreeIndexer<DB: Database>: Debug + Send + Sync {
+    /// Called before a block is executed, discards the data of a failed execution.
+    fn block_execution_started(&self);
+
+    /// Called after a block was executed and validated, assigns the collected data to it.
+    fn block_executed(&self, block: BlockNumHash);
+
+    /// Writes the data of the blocks that are committed to the canonical chain.
+    fn write_blocks(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        blocks: &[BlockNumHash],
+    ) -> ProviderResult<()>;
+
+    /// Discards the data of the finalized blocks and the blocks below them.
+    ///
+    /// This is also called with the block at the maximum reorg depth below the canonical tip
+    /// whenever blocks are committed, since the tree doesn't reorg deeper blocks.
+    fn finalize_blocks(&self, finalized_block: BlockNumber);
+}
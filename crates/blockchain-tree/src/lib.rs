@@ -34,6 +34,9 @@ pub use config::BlockchainTreeConfig;
 pub mod externals;
 pub use externals::TreeExternals;
 
+pub mod indexer;
+pub use indexer::TreeIndexer;
+
 pub mod shareable;
 pub use shareable::ShareableBlockchainTree;
 
@@ -17,6 +17,9 @@ pub struct TreeMetrics {
     pub reorgs: Counter,
     /// The latest reorg depth
     pub latest_reorg_depth: Gauge,
+    /// The number of reorgs that were rejected because they exceeded the reorg depth limit or
+    /// unwound the last finalized block
+    pub rejected_reorgs: Counter,
     /// Longest sidechain height
     pub longest_sidechain_height: Gauge,
 }
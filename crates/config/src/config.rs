@@ -5,7 +5,7 @@ use reth_network::{NetworkConfigBuilder, PeersConfig, SessionsConfig};
 use reth_primitives::PruneModes;
 use secp256k1::SecretKey;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 /// Configuration for the reth node.
 #[derive(Debug, Clone, Default, Deserialize, PartialEq, Serialize)]
@@ -72,6 +72,8 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Address Appearances stage configuration, only used if the index is enabled.
+    pub index_address_appearances: IndexHistoryConfig,
 }
 
 /// Header stage configuration.
@@ -136,6 +138,9 @@ pub struct BodiesConfig {
     pub downloader_stream_batch_size: usize,
     /// The size of the internal block buffer in bytes.
     ///
+    /// This includes the blocks that are ready to be written to the database. Once the buffer is
+    /// full, no new requests are sent and the ready blocks are returned to the stage early.
+    ///
     /// Default: 2GB
     pub downloader_max_buffered_blocks_size_bytes: usize,
     /// The minimum number of requests to send concurrently.
@@ -147,6 +152,11 @@ pub struct BodiesConfig {
     ///
     /// Default: 100
     pub downloader_max_concurrent_requests: usize,
+    /// The maximum number of blocks the stage runs ahead of the following stages, like the
+    /// execution. The bodies after the window are downloaded while the following stages run.
+    ///
+    /// Default: None (the stage downloads all bodies before the following stages run)
+    pub window: Option<u64>,
 }
 
 impl Default for BodiesConfig {
@@ -157,6 +167,7 @@ impl Default for BodiesConfig {
             downloader_max_buffered_blocks_size_bytes: 2 * 1024 * 1024 * 1024, // ~2GB
             downloader_min_concurrent_requests: 5,
             downloader_max_concurrent_requests: 100,
+            window: None,
         }
     }
 }
@@ -185,6 +196,10 @@ pub struct ExecutionConfig {
     pub max_changes: Option<u64>,
     /// The maximum gas to process before the execution stage commits.
     pub max_cumulative_gas: Option<u64>,
+    /// Adjusts the number of blocks per commit to the observed commit durations and memory usage,
+    /// with `max_blocks` as the upper bound.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adaptive_batch_size: Option<AdaptiveBatchSizeConfig>,
 }
 
 impl Default for ExecutionConfig {
@@ -194,10 +209,28 @@ impl Default for ExecutionConfig {
             max_changes: Some(5_000_000),
             // 50k full blocks of 30M gas
             max_cumulative_gas: Some(30_000_000 * 50_000),
+            adaptive_batch_size: None,
         }
     }
 }
 
+/// Adaptive execution batch size configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct AdaptiveBatchSizeConfig {
+    /// The minimum number of blocks to process before the execution stage commits.
+    pub min_blocks: u64,
+    /// The targeted duration of a commit. Batches shrink if committing them takes longer.
+    #[serde(with = "humantime_serde")]
+    pub target_commit_duration: Duration,
+}
+
+impl Default for AdaptiveBatchSizeConfig {
+    fn default() -> Self {
+        Self { min_blocks: 1_000, target_commit_duration: Duration::from_secs(60) }
+    }
+}
+
 /// Hashing stage configuration.
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
@@ -426,4 +459,20 @@ storage_history = { distance = 16384 }
 #";
         let _conf: Config = toml::from_str(alpha_0_0_11).unwrap();
     }
+
+    #[test]
+    fn test_adaptive_batch_size_config() {
+        let config = r"#
+[stages.execution]
+max_blocks = 500000
+
+[stages.execution.adaptive_batch_size]
+min_blocks = 5000
+target_commit_duration = '30s'
+#";
+        let conf: Config = toml::from_str(config).unwrap();
+        let adaptive = conf.stages.execution.adaptive_batch_size.unwrap();
+        assert_eq!(adaptive.min_blocks, 5000);
+        assert_eq!(adaptive.target_commit_duration, std::time::Duration::from_secs(30));
+    }
 }
@@ -260,7 +260,7 @@ impl StorageInner {
         let base_fee_per_gas = self
             .headers
             .get(&self.best_block)
-            .and_then(|parent| parent.next_block_base_fee(chain_spec.base_fee_params(timestamp)));
+            .and_then(|parent| chain_spec.next_block_base_fee(parent, timestamp));
 
         let mut header = Header {
             parent_hash: self.best_hash,
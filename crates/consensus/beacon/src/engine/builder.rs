@@ -0,0 +1,253 @@
+use crate::engine::{
+    handle::{EngineMessageSender, ENGINE_MESSAGE_LANES_SCOPE},
+    hooks::EngineHooks,
+    BeaconConsensusEngine, BeaconConsensusEngineHandle, BeaconEngineMessage, EngineMessageOverflow,
+    MIN_BLOCKS_FOR_PIPELINE_RUN,
+};
+use futures::{stream, StreamExt};
+use reth_db::database::Database;
+use reth_interfaces::{
+    blockchain_tree::BlockchainTreeEngine,
+    p2p::{bodies::client::BodiesClient, headers::client::HeadersClient},
+    sync::{NetworkSyncUpdater, NoopSyncStateUpdater},
+    RethResult,
+};
+use reth_metrics::common::lanes::lane_channel;
+use reth_payload_builder::PayloadBuilderHandle;
+use reth_primitives::{BlockNumber, B256};
+use reth_provider::{
+    BlockIdReader, BlockReader, CanonChainTracker, ChainSpecProvider, StageCheckpointReader,
+};
+use reth_stages::Pipeline;
+use reth_tasks::TaskSpawner;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio_stream::wrappers::{ReceiverStream, UnboundedReceiverStream};
+
+/// Builds a [`BeaconConsensusEngine`] and the [`BeaconConsensusEngineHandle`] to interact with it.
+#[must_use = "call `build` to construct the engine"]
+#[allow(missing_debug_implementations)]
+pub struct BeaconConsensusEngineBuilder<DB, BT, Client>
+where
+    DB: Database,
+{
+    client: Client,
+    pipeline: Pipeline<DB>,
+    blockchain: BT,
+    task_spawner: Box<dyn TaskSpawner>,
+    payload_builder: PayloadBuilderHandle,
+    sync_state_updater: Box<dyn NetworkSyncUpdater>,
+    max_block: Option<BlockNumber>,
+    run_pipeline_continuously: bool,
+    target: Option<B256>,
+    pipeline_run_threshold: u64,
+    hooks: EngineHooks,
+    channel: MessageChannel,
+}
+
+impl<DB, BT, Client> BeaconConsensusEngineBuilder<DB, BT, Client>
+where
+    DB: Database + Unpin + 'static,
+    BT: BlockchainTreeEngine
+        + BlockReader
+        + BlockIdReader
+        + CanonChainTracker
+        + StageCheckpointReader
+        + ChainSpecProvider
+        + 'static,
+    Client: HeadersClient + BodiesClient + Clone + Unpin + 'static,
+{
+    /// Creates a new builder with the required components.
+    ///
+    /// By default, the engine message channel is unbounded, the sync state isn't reported and the
+    /// pipeline runs if the engine is more than [`MIN_BLOCKS_FOR_PIPELINE_RUN`] blocks behind.
+    pub fn new(
+        client: Client,
+        pipeline: Pipeline<DB>,
+        blockchain: BT,
+        task_spawner: Box<dyn TaskSpawner>,
+        payload_builder: PayloadBuilderHandle,
+    ) -> Self {
+        Self {
+            client,
+            pipeline,
+            blockchain,
+            task_spawner,
+            payload_builder,
+            sync_state_updater: Box::<NoopSyncStateUpdater>::default(),
+            max_block: None,
+            run_pipeline_continuously: false,
+            target: None,
+            pipeline_run_threshold: MIN_BLOCKS_FOR_PIPELINE_RUN,
+            hooks: EngineHooks::new(),
+            channel: MessageChannel::Unbounded,
+        }
+    }
+
+    /// Sets the updater for the network sync state.
+    pub fn with_sync_state_updater(mut self, updater: Box<dyn NetworkSyncUpdater>) -> Self {
+        self.sync_state_updater = updater;
+        self
+    }
+
+    /// Sets the block to sync to, the engine terminates once it's reached.
+    pub fn with_max_block(mut self, max_block: BlockNumber) -> Self {
+        self.max_block = Some(max_block);
+        self
+    }
+
+    /// Sets whether the pipeline is run continuously.
+    pub fn with_run_pipeline_continuously(mut self, run_pipeline_continuously: bool) -> Self {
+        self.run_pipeline_continuously = run_pipeline_continuously;
+        self
+    }
+
+    /// Sets the hash of the block the pipeline syncs to on startup.
+    pub fn with_target(mut self, target: B256) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Sets the distance to the canonical head above which the pipeline is used to sync.
+    pub fn with_pipeline_run_threshold(mut self, pipeline_run_threshold: u64) -> Self {
+        self.pipeline_run_threshold = pipeline_run_threshold;
+        self
+    }
+
+    /// Sets the hooks that are run by the engine.
+    pub fn with_hooks(mut self, hooks: EngineHooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// Bounds the engine message channel to the given capacity.
+    ///
+    /// The [`EngineMessageOverflow`] policy configures what the handle does if the channel is
+    /// full.
+    ///
+    /// # Panics
+    ///
+    /// If the capacity is zero.
+    pub fn with_channel_capacity(
+        mut self,
+        capacity: usize,
+        overflow: EngineMessageOverflow,
+    ) -> Self {
+        assert!(capacity > 0, "engine message channel capacity must be greater than zero");
+        self.channel = MessageChannel::Bounded { capacity, overflow };
+        self
+    }
+
+    /// Bounds the engine message channel with separate lanes for forkchoice updates and payloads.
+    ///
+    /// Forkchoice updates and event listeners are never dropped, if their lane is full the handle
+    /// waits until the engine has processed enough messages. Payloads with the same block hash
+    /// are coalesced, and if the payload lane is full the oldest queued payload is dropped. The
+    /// replaced and dropped payloads are answered with `SYNCING`.
+    ///
+    /// The messages of both lanes are processed in the order they were sent, because a forkchoice
+    /// update usually refers to a payload that was sent before it.
+    ///
+    /// See also [`DEFAULT_PRIORITY_LANE_CAPACITY`](crate::DEFAULT_PRIORITY_LANE_CAPACITY) and
+    /// [`DEFAULT_PAYLOAD_LANE_CAPACITY`](crate::DEFAULT_PAYLOAD_LANE_CAPACITY).
+    ///
+    /// # Panics
+    ///
+    /// If a capacity is zero.
+    pub fn with_message_lanes(mut self, priority_capacity: usize, payload_capacity: usize) -> Self {
+        assert!(priority_capacity > 0, "priority lane capacity must be greater than zero");
+        assert!(payload_capacity > 0, "payload lane capacity must be greater than zero");
+        self.channel = MessageChannel::Lanes { priority_capacity, payload_capacity };
+        self
+    }
+
+    /// Receives the engine messages from the given unbounded channel, e.g. if other components
+    /// send messages to the engine directly.
+    pub fn with_message_channel(
+        mut self,
+        to_engine: UnboundedSender<BeaconEngineMessage>,
+        rx: UnboundedReceiver<BeaconEngineMessage>,
+    ) -> Self {
+        self.channel = MessageChannel::External { to_engine, rx };
+        self
+    }
+
+    /// Builds the [`BeaconConsensusEngine`] and its [`BeaconConsensusEngineHandle`].
+    ///
+    /// See also [`BeaconConsensusEngine::with_channel`].
+    pub fn build(
+        self,
+    ) -> RethResult<(BeaconConsensusEngine<DB, BT, Client>, BeaconConsensusEngineHandle)> {
+        let Self {
+            client,
+            pipeline,
+            blockchain,
+            task_spawner,
+            payload_builder,
+            sync_state_updater,
+            max_block,
+            run_pipeline_continuously,
+            target,
+            pipeline_run_threshold,
+            hooks,
+            channel,
+        } = self;
+
+        let (to_engine, engine_message_rx) = match channel {
+            MessageChannel::Unbounded => {
+                let (tx, rx) = mpsc::unbounded_channel();
+                (EngineMessageSender::Unbounded(tx), UnboundedReceiverStream::new(rx).boxed())
+            }
+            MessageChannel::Bounded { capacity, overflow } => {
+                let (tx, rx) = mpsc::channel(capacity);
+                let (listeners, listeners_rx) = mpsc::unbounded_channel();
+                let rx = stream::select(
+                    ReceiverStream::new(rx),
+                    UnboundedReceiverStream::new(listeners_rx),
+                );
+                (EngineMessageSender::Bounded { tx, overflow, listeners }, rx.boxed())
+            }
+            MessageChannel::Lanes { priority_capacity, payload_capacity } => {
+                let (tx, rx) =
+                    lane_channel(priority_capacity, payload_capacity, ENGINE_MESSAGE_LANES_SCOPE);
+                let (listeners, listeners_rx) = mpsc::unbounded_channel();
+                let rx = stream::select(rx, UnboundedReceiverStream::new(listeners_rx));
+                (EngineMessageSender::Lanes { tx, listeners }, rx.boxed())
+            }
+            MessageChannel::External { to_engine, rx } => (
+                EngineMessageSender::Unbounded(to_engine),
+                UnboundedReceiverStream::new(rx).boxed(),
+            ),
+        };
+
+        BeaconConsensusEngine::with_message_stream(
+            client,
+            pipeline,
+            blockchain,
+            task_spawner,
+            sync_state_updater,
+            max_block,
+            run_pipeline_continuously,
+            payload_builder,
+            target,
+            pipeline_run_threshold,
+            BeaconConsensusEngineHandle { to_engine },
+            engine_message_rx,
+            hooks,
+        )
+    }
+}
+
+/// The engine message channel the builder creates.
+enum MessageChannel {
+    /// An unbounded channel.
+    Unbounded,
+    /// A bounded channel with the given capacity and overflow policy.
+    Bounded { capacity: usize, overflow: EngineMessageOverflow },
+    /// A bounded channel with separate lanes for forkchoice updates and payloads.
+    Lanes { priority_capacity: usize, payload_capacity: usize },
+    /// An unbounded channel created by the caller.
+    External {
+        to_engine: UnboundedSender<BeaconEngineMessage>,
+        rx: UnboundedReceiver<BeaconEngineMessage>,
+    },
+}
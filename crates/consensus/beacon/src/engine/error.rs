@@ -58,6 +58,9 @@ pub enum BeaconForkChoiceUpdateError {
     /// Thrown when the engine task is unavailable/stopped.
     #[error("beacon consensus engine task stopped")]
     EngineUnavailable,
+    /// Thrown when the bounded engine message channel is full.
+    #[error("beacon consensus engine is busy")]
+    EngineBusy,
 }
 
 impl From<RethError> for BeaconForkChoiceUpdateError {
@@ -80,6 +83,9 @@ pub enum BeaconOnNewPayloadError {
     /// Thrown when the engine task is unavailable/stopped.
     #[error("beacon consensus engine task stopped")]
     EngineUnavailable,
+    /// Thrown when the bounded engine message channel is full.
+    #[error("beacon consensus engine is busy")]
+    EngineBusy,
     /// Thrown when a block has blob transactions, but is not after the Cancun fork.
     #[error("block has blob transactions, but is not after the Cancun fork")]
     PreCancunBlockWithBlobTransactions,
@@ -14,4 +14,13 @@ pub enum BeaconConsensusEngineEvent {
     CanonicalChainCommitted(Box<SealedHeader>, Duration),
     /// A block was added to the fork chain.
     ForkBlockAdded(Arc<SealedBlock>),
+    /// The pipeline failed with a retryable error and is restarted after the backoff.
+    PipelineRetry {
+        /// The number of consecutive retries, including this one.
+        attempt: u32,
+        /// The backoff before the pipeline is restarted.
+        backoff: Duration,
+        /// The error the pipeline failed with.
+        error: String,
+    },
 }
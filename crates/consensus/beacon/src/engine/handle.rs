@@ -6,11 +6,16 @@ use crate::{
 };
 use futures::TryFutureExt;
 use reth_interfaces::RethResult;
+use reth_metrics::common::lanes::LaneSender;
 use reth_rpc_types::engine::{
     CancunPayloadFields, ExecutionPayload, ForkchoiceState, ForkchoiceUpdated, PayloadAttributes,
-    PayloadStatus,
+    PayloadStatus, PayloadStatusEnum,
+};
+use tokio::sync::{
+    mpsc,
+    mpsc::{error::TrySendError, UnboundedSender},
+    oneshot,
 };
-use tokio::sync::{mpsc, mpsc::UnboundedSender, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 /// A _shareable_ beacon consensus frontend type. Used to interact with the spawned beacon consensus
@@ -19,7 +24,7 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 /// See also [`BeaconConsensusEngine`](crate::engine::BeaconConsensusEngine).
 #[derive(Clone, Debug)]
 pub struct BeaconConsensusEngineHandle {
-    pub(crate) to_engine: UnboundedSender<BeaconEngineMessage>,
+    pub(crate) to_engine: EngineMessageSender,
 }
 
 // === impl BeaconConsensusEngineHandle ===
@@ -27,7 +32,7 @@ pub struct BeaconConsensusEngineHandle {
 impl BeaconConsensusEngineHandle {
     /// Creates a new beacon consensus engine handle.
     pub fn new(to_engine: UnboundedSender<BeaconEngineMessage>) -> Self {
-        Self { to_engine }
+        Self { to_engine: EngineMessageSender::Unbounded(to_engine) }
     }
 
     /// Sends a new payload message to the beacon consensus engine and waits for a response.
@@ -39,7 +44,13 @@ impl BeaconConsensusEngineHandle {
         cancun_fields: Option<CancunPayloadFields>,
     ) -> Result<PayloadStatus, BeaconOnNewPayloadError> {
         let (tx, rx) = oneshot::channel();
-        let _ = self.to_engine.send(BeaconEngineMessage::NewPayload { payload, cancun_fields, tx });
+        self.to_engine
+            .send(BeaconEngineMessage::NewPayload { payload, cancun_fields, tx })
+            .await
+            .map_err(|err| match err {
+            SendError::Closed => BeaconOnNewPayloadError::EngineUnavailable,
+            SendError::Full => BeaconOnNewPayloadError::EngineBusy,
+        })?;
         rx.await.map_err(|_| BeaconOnNewPayloadError::EngineUnavailable)?
     }
 
@@ -53,6 +64,7 @@ impl BeaconConsensusEngineHandle {
     ) -> Result<ForkchoiceUpdated, BeaconForkChoiceUpdateError> {
         Ok(self
             .send_fork_choice_updated(state, payload_attrs)
+            .await?
             .map_err(|_| BeaconForkChoiceUpdateError::EngineUnavailable)
             .await??
             .await?)
@@ -60,31 +72,208 @@ impl BeaconConsensusEngineHandle {
 
     /// Sends a forkchoice update message to the beacon consensus engine and returns the receiver to
     /// wait for a response.
-    fn send_fork_choice_updated(
+    async fn send_fork_choice_updated(
         &self,
         state: ForkchoiceState,
         payload_attrs: Option<PayloadAttributes>,
-    ) -> oneshot::Receiver<RethResult<OnForkChoiceUpdated>> {
+    ) -> Result<oneshot::Receiver<RethResult<OnForkChoiceUpdated>>, BeaconForkChoiceUpdateError>
+    {
         let (tx, rx) = oneshot::channel();
-        let _ = self.to_engine.send(BeaconEngineMessage::ForkchoiceUpdated {
-            state,
-            payload_attrs,
-            tx,
-        });
-        rx
+        self.to_engine
+            .send(BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx })
+            .await
+            .map_err(|err| match err {
+                SendError::Closed => BeaconForkChoiceUpdateError::EngineUnavailable,
+                SendError::Full => BeaconForkChoiceUpdateError::EngineBusy,
+            })?;
+        Ok(rx)
     }
 
     /// Sends a transition configuration exchagne message to the beacon consensus engine.
     ///
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/paris.md#engine_exchangetransitionconfigurationv1>
     pub async fn transition_configuration_exchanged(&self) {
-        let _ = self.to_engine.send(BeaconEngineMessage::TransitionConfigurationExchanged);
+        let _ = self.to_engine.send(BeaconEngineMessage::TransitionConfigurationExchanged).await;
     }
 
     /// Creates a new [`BeaconConsensusEngineEvent`] listener stream.
+    ///
+    /// The listener is registered even if the engine message channel is bounded and full.
     pub fn event_listener(&self) -> UnboundedReceiverStream<BeaconConsensusEngineEvent> {
         let (tx, rx) = mpsc::unbounded_channel();
-        let _ = self.to_engine.send(BeaconEngineMessage::EventListener(tx));
+        let _ = self.to_engine.listeners().send(BeaconEngineMessage::EventListener(tx));
         UnboundedReceiverStream::new(rx)
     }
+
+    /// Sends a shutdown message to the beacon consensus engine and waits until it has shut down.
+    ///
+    /// The engine first processes the messages it received before and stops a running pipeline at
+    /// its next checkpoint, then the engine future resolves.
+    pub async fn shutdown(&self) -> Result<(), oneshot::error::RecvError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(BeaconEngineMessage::Shutdown { tx }).await;
+        rx.await
+    }
+}
+
+/// The default capacity of the lane for forkchoice updates and event listeners.
+///
+/// See also
+/// [`BeaconConsensusEngineBuilder::with_message_lanes`](crate::BeaconConsensusEngineBuilder::with_message_lanes).
+pub const DEFAULT_PRIORITY_LANE_CAPACITY: usize = 64;
+
+/// The default capacity of the lane for payloads.
+///
+/// See also
+/// [`BeaconConsensusEngineBuilder::with_message_lanes`](crate::BeaconConsensusEngineBuilder::with_message_lanes).
+pub const DEFAULT_PAYLOAD_LANE_CAPACITY: usize = 128;
+
+/// The metrics scope of the engine message lanes.
+pub(crate) const ENGINE_MESSAGE_LANES_SCOPE: &str = "consensus.engine.beacon.messages";
+
+/// Configures what the [`BeaconConsensusEngineHandle`] does if the bounded engine message channel
+/// is full.
+///
+/// See also
+/// [`BeaconConsensusEngineBuilder::with_channel_capacity`](crate::BeaconConsensusEngineBuilder::with_channel_capacity).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EngineMessageOverflow {
+    /// Wait until the engine has processed enough messages.
+    #[default]
+    Wait,
+    /// Reject the message with an `EngineBusy` error.
+    Reject,
+}
+
+/// The sending half of the channel the engine receives [`BeaconEngineMessage`]s from.
+///
+/// The bounded channels come with an unbounded channel for event listeners, so registering a
+/// listener never fails because the engine is busy.
+#[derive(Clone, Debug)]
+pub(crate) enum EngineMessageSender {
+    /// An unbounded channel.
+    Unbounded(UnboundedSender<BeaconEngineMessage>),
+    /// A bounded channel and what to do if it's full.
+    Bounded {
+        tx: mpsc::Sender<BeaconEngineMessage>,
+        overflow: EngineMessageOverflow,
+        listeners: UnboundedSender<BeaconEngineMessage>,
+    },
+    /// A bounded channel whose forkchoice updates are never dropped and whose payloads are
+    /// coalesced.
+    Lanes { tx: LaneSender<BeaconEngineMessage>, listeners: UnboundedSender<BeaconEngineMessage> },
+}
+
+impl EngineMessageSender {
+    /// Sends the message to the engine, respecting the [`EngineMessageOverflow`] policy.
+    async fn send(&self, msg: BeaconEngineMessage) -> Result<(), SendError> {
+        match self {
+            Self::Unbounded(tx) => tx.send(msg).map_err(|_| SendError::Closed),
+            Self::Bounded { tx, overflow: EngineMessageOverflow::Wait, .. } => {
+                tx.send(msg).await.map_err(|_| SendError::Closed)
+            }
+            Self::Bounded { tx, overflow: EngineMessageOverflow::Reject, .. } => {
+                tx.try_send(msg).map_err(Into::into)
+            }
+            Self::Lanes { tx, .. } => {
+                tx.send(msg).await.map(respond_dropped).map_err(|_| SendError::Closed)
+            }
+        }
+    }
+
+    /// Returns the unbounded channel for [`BeaconEngineMessage::EventListener`] messages.
+    fn listeners(&self) -> &UnboundedSender<BeaconEngineMessage> {
+        match self {
+            Self::Unbounded(tx) => tx,
+            Self::Bounded { listeners, .. } | Self::Lanes { listeners, .. } => listeners,
+        }
+    }
+}
+
+/// Responds to a message that was replaced or dropped by the engine message lanes.
+///
+/// A payload is answered with `SYNCING`, like a payload the engine can't validate yet, so the
+/// consensus layer sends it again if it's still needed.
+fn respond_dropped(msg: Option<BeaconEngineMessage>) {
+    if let Some(BeaconEngineMessage::NewPayload { tx, .. }) = msg {
+        let _ = tx.send(Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing)));
+    }
+}
+
+/// Error returned if a message couldn't be sent to the engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SendError {
+    /// The engine task stopped.
+    Closed,
+    /// The bounded channel is full.
+    Full,
+}
+
+impl<T> From<TrySendError<T>> for SendError {
+    fn from(err: TrySendError<T>) -> Self {
+        match err {
+            TrySendError::Full(_) => Self::Full,
+            TrySendError::Closed(_) => Self::Closed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reject_on_full_channel() {
+        let (tx, _rx) = mpsc::channel(1);
+        let (listeners, _listeners_rx) = mpsc::unbounded_channel();
+        let handle = BeaconConsensusEngineHandle {
+            to_engine: EngineMessageSender::Bounded {
+                tx,
+                overflow: EngineMessageOverflow::Reject,
+                listeners,
+            },
+        };
+
+        // occupies the only slot
+        handle.transition_configuration_exchanged().await;
+
+        let err = handle.fork_choice_updated(ForkchoiceState::default(), None).await.unwrap_err();
+        assert!(matches!(err, BeaconForkChoiceUpdateError::EngineBusy));
+    }
+
+    #[tokio::test]
+    async fn unavailable_on_closed_channel() {
+        let (tx, rx) = mpsc::channel(1);
+        drop(rx);
+        let (listeners, _listeners_rx) = mpsc::unbounded_channel();
+        let handle = BeaconConsensusEngineHandle {
+            to_engine: EngineMessageSender::Bounded {
+                tx,
+                overflow: EngineMessageOverflow::Wait,
+                listeners,
+            },
+        };
+
+        let err = handle.fork_choice_updated(ForkchoiceState::default(), None).await.unwrap_err();
+        assert!(matches!(err, BeaconForkChoiceUpdateError::EngineUnavailable));
+    }
+
+    #[tokio::test]
+    async fn register_listener_on_full_channel() {
+        let (tx, _rx) = mpsc::channel(1);
+        let (listeners, mut listeners_rx) = mpsc::unbounded_channel();
+        let handle = BeaconConsensusEngineHandle {
+            to_engine: EngineMessageSender::Bounded {
+                tx,
+                overflow: EngineMessageOverflow::Wait,
+                listeners,
+            },
+        };
+
+        // occupies the only slot
+        handle.transition_configuration_exchanged().await;
+
+        let _events = handle.event_listener();
+        assert!(matches!(listeners_rx.try_recv(), Ok(BeaconEngineMessage::EventListener(_))));
+    }
 }
@@ -9,7 +9,7 @@ use crate::{
 use futures::FutureExt;
 use metrics::Counter;
 use reth_db::database::Database;
-use reth_interfaces::{RethError, RethResult};
+use reth_interfaces::{db::set_reader_component, RethError, RethResult};
 use reth_primitives::BlockNumber;
 use reth_prune::{Pruner, PrunerError, PrunerWithResult};
 use reth_tasks::TaskSpawner;
@@ -103,6 +103,7 @@ impl<DB: Database + 'static> PruneHook<DB> {
                     self.pruner_task_spawner.spawn_critical_blocking(
                         "pruner task",
                         Box::pin(async move {
+                            let _component = set_reader_component("pruner");
                             let result = pruner.run(tip_block_number);
                             let _ = tx.send((pruner, result));
                         }),
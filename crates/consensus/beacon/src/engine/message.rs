@@ -4,7 +4,9 @@ use crate::{
 };
 use futures::{future::Either, FutureExt};
 use reth_interfaces::{consensus::ForkchoiceState, RethResult};
+use reth_metrics::common::lanes::{Lane, LaneMessage};
 use reth_payload_builder::error::PayloadBuilderError;
+use reth_primitives::B256;
 use reth_rpc_types::engine::{
     CancunPayloadFields, ExecutionPayload, ForkChoiceUpdateResult, ForkchoiceUpdateError,
     ForkchoiceUpdated, PayloadAttributes, PayloadId, PayloadStatus, PayloadStatusEnum,
@@ -164,4 +166,26 @@ pub enum BeaconEngineMessage {
     TransitionConfigurationExchanged,
     /// Add a new listener for [`BeaconEngineMessage`].
     EventListener(UnboundedSender<BeaconConsensusEngineEvent>),
+    /// Request to shut the engine down once the messages received before are processed.
+    Shutdown {
+        /// The sender for acknowledging the shutdown.
+        tx: oneshot::Sender<()>,
+    },
+}
+
+/// Forkchoice updates, event listeners and shutdown requests are never dropped. Payloads are
+/// coalesced by block hash, and transition configuration exchanges with each other.
+impl LaneMessage for BeaconEngineMessage {
+    /// The block hash of a payload, `None` for a transition configuration exchange.
+    type Key = Option<B256>;
+
+    fn lane(&self) -> Lane<Self::Key> {
+        match self {
+            Self::NewPayload { payload, .. } => Lane::Coalesce(Some(payload.block_hash())),
+            Self::TransitionConfigurationExchanged => Lane::Coalesce(None),
+            Self::ForkchoiceUpdated { .. } | Self::EventListener(_) | Self::Shutdown { .. } => {
+                Lane::Priority
+            }
+        }
+    }
 }
@@ -7,7 +7,7 @@ use crate::{
     hooks::{EngineContext, EngineHooksController},
     sync::{EngineSyncController, EngineSyncEvent},
 };
-use futures::{Future, StreamExt};
+use futures::{stream::BoxStream, Future, StreamExt};
 use reth_db::database::Database;
 use reth_interfaces::{
     blockchain_tree::{
@@ -67,7 +67,14 @@ mod event;
 pub use event::BeaconConsensusEngineEvent;
 
 mod handle;
-pub use handle::BeaconConsensusEngineHandle;
+use handle::EngineMessageSender;
+pub use handle::{
+    BeaconConsensusEngineHandle, EngineMessageOverflow, DEFAULT_PAYLOAD_LANE_CAPACITY,
+    DEFAULT_PRIORITY_LANE_CAPACITY,
+};
+
+mod builder;
+pub use builder::BeaconConsensusEngineBuilder;
 
 mod forkchoice;
 use crate::hooks::{EngineHookEvent, EngineHooks, PolledHook};
@@ -77,6 +84,9 @@ use reth_payload_validator::ExecutionPayloadValidator;
 
 mod metrics;
 
+mod response_cache;
+use response_cache::EngineResponseCache;
+
 pub(crate) mod sync;
 
 /// Hooks for running during the main loop of
@@ -89,6 +99,9 @@ pub mod test_utils;
 /// The maximum number of invalid headers that can be tracked by the engine.
 const MAX_INVALID_HEADERS: u32 = 512u32;
 
+/// The maximum number of `newPayload` and `forkchoiceUpdated` responses that are cached each.
+const MAX_CACHED_RESPONSES: u32 = 64u32;
+
 /// The largest gap for which the tree will be used for sync. See docs for `pipeline_run_threshold`
 /// for more information.
 ///
@@ -181,7 +194,7 @@ where
     /// Used for emitting updates about whether the engine is syncing or not.
     sync_state_updater: Box<dyn NetworkSyncUpdater>,
     /// The Engine API message receiver.
-    engine_message_rx: UnboundedReceiverStream<BeaconEngineMessage>,
+    engine_message_rx: BoxStream<'static, BeaconEngineMessage>,
     /// A clone of the handle
     handle: BeaconConsensusEngineHandle,
     /// Tracks the received forkchoice state updates received by the CL.
@@ -195,6 +208,8 @@ where
     /// Tracks the header of invalid payloads that were rejected by the engine because they're
     /// invalid.
     invalid_headers: InvalidHeaderCache,
+    /// Caches the responses to repeated `newPayload` and `forkchoiceUpdated` requests.
+    response_cache: EngineResponseCache,
     /// Consensus engine metrics.
     metrics: EngineMetrics,
     /// After downloading a block corresponding to a recent forkchoice update, the engine will
@@ -210,6 +225,9 @@ where
     /// be used to download and execute the missing blocks.
     pipeline_run_threshold: u64,
     hooks: EngineHooksController,
+    /// The sender to acknowledge a requested shutdown, see
+    /// [BeaconConsensusEngineHandle::shutdown].
+    shutdown_tx: Option<oneshot::Sender<()>>,
 }
 
 impl<DB, BT, Client> BeaconConsensusEngine<DB, BT, Client>
@@ -285,7 +303,43 @@ where
         rx: UnboundedReceiver<BeaconEngineMessage>,
         hooks: EngineHooks,
     ) -> RethResult<(Self, BeaconConsensusEngineHandle)> {
-        let handle = BeaconConsensusEngineHandle { to_engine };
+        Self::with_message_stream(
+            client,
+            pipeline,
+            blockchain,
+            task_spawner,
+            sync_state_updater,
+            max_block,
+            run_pipeline_continuously,
+            payload_builder,
+            target,
+            pipeline_run_threshold,
+            BeaconConsensusEngineHandle { to_engine: EngineMessageSender::Unbounded(to_engine) },
+            UnboundedReceiverStream::new(rx).boxed(),
+            hooks,
+        )
+    }
+
+    /// Create a new instance of the [BeaconConsensusEngine] that receives the
+    /// [BeaconEngineMessage]s sent by the given handle from the stream.
+    ///
+    /// See also [BeaconConsensusEngine::with_channel].
+    #[allow(clippy::too_many_arguments)]
+    fn with_message_stream(
+        client: Client,
+        pipeline: Pipeline<DB>,
+        blockchain: BT,
+        task_spawner: Box<dyn TaskSpawner>,
+        sync_state_updater: Box<dyn NetworkSyncUpdater>,
+        max_block: Option<BlockNumber>,
+        run_pipeline_continuously: bool,
+        payload_builder: PayloadBuilderHandle,
+        target: Option<B256>,
+        pipeline_run_threshold: u64,
+        handle: BeaconConsensusEngineHandle,
+        engine_message_rx: BoxStream<'static, BeaconEngineMessage>,
+        hooks: EngineHooks,
+    ) -> RethResult<(Self, BeaconConsensusEngineHandle)> {
         let sync = EngineSyncController::new(
             pipeline,
             client,
@@ -299,15 +353,17 @@ where
             payload_validator: ExecutionPayloadValidator::new(blockchain.chain_spec()),
             blockchain,
             sync_state_updater,
-            engine_message_rx: UnboundedReceiverStream::new(rx),
+            engine_message_rx,
             handle: handle.clone(),
             forkchoice_state_tracker: Default::default(),
             payload_builder,
             listeners: EventListeners::default(),
             invalid_headers: InvalidHeaderCache::new(MAX_INVALID_HEADERS),
+            response_cache: EngineResponseCache::new(MAX_CACHED_RESPONSES),
             metrics: EngineMetrics::default(),
             pipeline_run_threshold,
             hooks: EngineHooksController::new(hooks),
+            shutdown_tx: None,
         };
 
         let maybe_pipeline_target = match target {
@@ -662,6 +718,16 @@ where
             return Ok(OnForkChoiceUpdated::syncing());
         }
 
+        // a repeated update without payload attributes doesn't change anything, as long as the
+        // canonical chain hasn't changed since
+        if attrs.is_none() && self.blockchain.canonical_tip().hash == state.head_block_hash {
+            if let Some(status) = self.response_cache.forkchoice_status(&state) {
+                trace!(target: "consensus::engine", ?status, ?state, "Returning cached forkchoice status");
+                return Ok(OnForkChoiceUpdated::valid(status));
+            }
+        }
+
+        let previous_tip = self.blockchain.canonical_tip();
         let start = Instant::now();
         let make_canonical_result = self.blockchain.make_canonical(&state.head_block_hash);
         let elapsed = self.record_make_canonical_latency(start, &make_canonical_result);
@@ -681,6 +747,7 @@ where
                                         "[Optimism] Allowing beacon reorg to old head"
                                     );
                                     let _ = self.update_head(header.clone());
+                                    self.response_cache.clear();
                                     self.listeners.notify(
                                         BeaconConsensusEngineEvent::CanonicalChainCommitted(
                                             Box::new(header.clone()),
@@ -715,6 +782,7 @@ where
 
                         // new VALID update that moved the canonical chain forward
                         let _ = self.update_head(head.clone());
+                        self.response_cache.on_canonical_head(previous_tip.hash, head);
                         self.listeners.notify(BeaconConsensusEngineEvent::CanonicalChainCommitted(
                             Box::new(head.clone()),
                             elapsed,
@@ -763,6 +831,7 @@ where
             return Ok(invalid_fcu_response);
         }
 
+        self.response_cache.insert_forkchoice_status(state, &status);
         trace!(target: "consensus::engine", ?status, ?state, "Returning forkchoice status");
         Ok(OnForkChoiceUpdated::valid(status))
     }
@@ -985,6 +1054,17 @@ where
                 })
                 .with_latest_valid_hash(B256::ZERO);
             }
+            RethError::Canonical(
+                error @ CanonicalError::BlockchainTree(
+                    BlockchainTreeError::ReorgTooDeep { .. } |
+                    BlockchainTreeError::ReorgBelowFinalized { .. },
+                ),
+            ) => {
+                // the new head isn't invalid, but the reorg must not be retried by syncing to it
+                // either, since the pipeline would unwind the same blocks
+                warn!(target: "consensus::engine", ?error, ?state, "Rejecting forkchoice update that reorgs too deep");
+                return PayloadStatus::from_status(PayloadStatusEnum::Syncing)
+            }
             RethError::Canonical(CanonicalError::BlockchainTree(
                 BlockchainTreeError::BlockHashNotFoundInChain { .. },
             )) => {
@@ -1137,7 +1217,11 @@ where
             return Ok(status);
         }
 
-        let res = if self.sync.is_pipeline_idle() {
+        let res = if let Some(status) = self.response_cache.payload_status(&block_hash) {
+            // the block was already inserted
+            trace!(target: "consensus::engine", ?status, "Returning cached payload status");
+            Ok(status)
+        } else if self.sync.is_pipeline_idle() {
             // we can only insert new payloads if the pipeline is _not_ running, because it holds
             // exclusive access to the database
             self.try_insert_new_payload(block)
@@ -1148,6 +1232,7 @@ where
         let status = match res {
             Ok(status) => {
                 if status.is_valid() {
+                    self.response_cache.insert_payload_status(block_hash, &status);
                     if let Some(target) = self.forkchoice_state_tracker.sync_target_state() {
                         // if we're currently syncing and the inserted block is the targeted FCU
                         // head block, we can try to make it canonical.
@@ -1475,12 +1560,14 @@ where
             // optimistically try to make the head of the current FCU target canonical, the sync
             // target might have changed since the block download request was issued
             // (new FCU received)
+            let previous_tip = self.blockchain.canonical_tip();
             let start = Instant::now();
             let make_canonical_result = self.blockchain.make_canonical(&target.head_block_hash);
             let elapsed = self.record_make_canonical_latency(start, &make_canonical_result);
             match make_canonical_result {
                 Ok(outcome) => {
                     if let CanonicalOutcome::Committed { head } = &outcome {
+                        self.response_cache.on_canonical_head(previous_tip.hash, head);
                         self.listeners.notify(BeaconConsensusEngineEvent::CanonicalChainCommitted(
                             Box::new(head.clone()),
                             elapsed,
@@ -1516,7 +1603,11 @@ where
                         if let Some(target_hash) = ForkchoiceStateHash::find(&target, inserted.hash)
                             .filter(|h| !h.is_head())
                         {
-                            let _ = self.blockchain.make_canonical(target_hash.as_ref());
+                            if let Ok(CanonicalOutcome::Committed { head }) =
+                                self.blockchain.make_canonical(target_hash.as_ref())
+                            {
+                                self.response_cache.on_canonical_head(previous_tip.hash, &head);
+                            }
                         }
                     }
                 }
@@ -1534,6 +1625,11 @@ where
         match event {
             EngineSyncEvent::FetchedFullBlock(block) => {
                 self.on_downloaded_block(block);
+                if self.sync.has_reached_max_block(self.blockchain.canonical_tip().number) {
+                    // Terminate the sync early if the downloaded blocks reached the maximum user
+                    // configured block.
+                    return Some(Ok(()))
+                }
             }
             EngineSyncEvent::PipelineStarted(target) => {
                 trace!(target: "consensus::engine", ?target, continuous = target.is_none(), "Started the pipeline");
@@ -1567,6 +1663,8 @@ where
         reached_max_block: bool,
     ) -> Option<Result<(), BeaconConsensusEngineError>> {
         trace!(target: "consensus::engine", ?result, ?reached_max_block, "Pipeline finished");
+        // the pipeline could have unwound the blocks of the cached responses
+        self.response_cache.clear();
         match result {
             Ok(ctrl) => {
                 if reached_max_block {
@@ -1686,7 +1784,20 @@ where
                     }
                 }
             }
-            // Any pipeline error at this point is fatal.
+            Err(error) if error.severity().is_retryable() => {
+                let Some(backoff) = self.sync.retry_pipeline() else {
+                    error!(target: "consensus::engine", %error, "Pipeline failed too many times in a row");
+                    return Some(Err(error.into()))
+                };
+                let attempt = self.sync.pipeline_retries();
+                warn!(target: "consensus::engine", %error, attempt, ?backoff, "Pipeline failed with a non-fatal error, retrying");
+                self.listeners.notify(BeaconConsensusEngineEvent::PipelineRetry {
+                    attempt,
+                    backoff,
+                    error: error.to_string(),
+                });
+            }
+            // Any other pipeline error at this point is fatal.
             Err(error) => return Some(Err(error.into())),
         };
 
@@ -1805,6 +1916,10 @@ where
                         BeaconEngineMessage::EventListener(tx) => {
                             this.listeners.push_listener(tx);
                         }
+                        BeaconEngineMessage::Shutdown { tx } => {
+                            info!(target: "consensus::engine", "Shutdown requested");
+                            this.shutdown_tx = Some(tx);
+                        }
                     }
                     continue;
                 }
@@ -1814,6 +1929,34 @@ where
                 break;
             }
 
+            // All messages received before the shutdown request are processed at this point, so
+            // the engine only waits for the pipeline to stop at its next checkpoint.
+            if this.shutdown_tx.is_some() {
+                if this.sync.is_pipeline_active() {
+                    this.sync.cancel_pipeline();
+                    match this.sync.poll(cx) {
+                        Poll::Ready(EngineSyncEvent::PipelineFinished { result, .. }) => {
+                            match result {
+                                Ok(_) | Err(PipelineError::Cancelled) => {}
+                                Err(err) => return Poll::Ready(Err(err.into())),
+                            }
+                        }
+                        Poll::Ready(EngineSyncEvent::PipelineTaskDropped) => {
+                            return Poll::Ready(Err(
+                                BeaconConsensusEngineError::PipelineChannelClosed,
+                            ))
+                        }
+                        // blocks downloaded in the meantime are discarded
+                        Poll::Ready(_) => continue 'main,
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+
+                info!(target: "consensus::engine", "Consensus engine shut down");
+                let _ = this.shutdown_tx.take().expect("is some").send(());
+                return Poll::Ready(Ok(()))
+            }
+
             // process sync events if any
             match this.sync.poll(cx) {
                 Poll::Ready(sync_event) => {
@@ -1921,6 +2064,76 @@ mod tests {
         );
     }
 
+    // Test that the consensus engine runs the pipeline again after a retryable error. The
+    // consensus engine will propagate the second result (fatal error) only if it retries the
+    // pipeline.
+    #[tokio::test]
+    async fn retries_pipeline_on_retryable_error() {
+        let mut rng = generators::rng();
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(MAINNET.genesis.clone())
+                .paris_activated()
+                .build(),
+        );
+
+        let (consensus_engine, env) = TestConsensusEngineBuilder::new(chain_spec.clone())
+            .with_pipeline_exec_outputs(VecDeque::from([
+                Err(StageError::Recoverable(Box::new(std::fmt::Error))),
+                Err(StageError::ChannelClosed),
+            ]))
+            .disable_blockchain_tree_sync()
+            .with_max_block(1)
+            .build();
+
+        let mut engine_rx = env.event_listener();
+        let res = spawn_consensus_engine(consensus_engine);
+
+        let _ = env
+            .send_forkchoice_updated(ForkchoiceState {
+                head_block_hash: rng.gen(),
+                ..Default::default()
+            })
+            .await;
+        assert_matches!(
+            res.await,
+            Ok(Err(BeaconConsensusEngineError::Pipeline(n))) if matches!(*n.as_ref(),PipelineError::Stage(StageError::ChannelClosed))
+        );
+        loop {
+            match engine_rx.next().await {
+                Some(BeaconConsensusEngineEvent::PipelineRetry { attempt, .. }) => {
+                    assert_eq!(attempt, 1);
+                    break
+                }
+                Some(_) => {}
+                None => panic!("no pipeline retry event"),
+            }
+        }
+    }
+
+    // Test that the consensus engine resolves once it processed a shutdown request.
+    #[tokio::test]
+    async fn shuts_down_on_request() {
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(MAINNET.genesis.clone())
+                .paris_activated()
+                .build(),
+        );
+
+        let (consensus_engine, env) = TestConsensusEngineBuilder::new(chain_spec.clone())
+            .with_pipeline_exec_outputs(VecDeque::from([Err(StageError::ChannelClosed)]))
+            .disable_blockchain_tree_sync()
+            .build();
+
+        let rx = spawn_consensus_engine(consensus_engine);
+
+        assert_matches!(env.send_shutdown().await, Ok(()));
+        assert_matches!(rx.await, Ok(Ok(())));
+    }
+
     // Test that the consensus engine is idle until first forkchoice updated is received.
     #[tokio::test]
     async fn is_idle_until_forkchoice_is_set() {
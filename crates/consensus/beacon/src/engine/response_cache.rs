@@ -0,0 +1,143 @@
+use reth_interfaces::consensus::ForkchoiceState;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_primitives::{SealedHeader, B256};
+use reth_rpc_types::engine::PayloadStatus;
+use schnellru::{ByLength, LruMap};
+
+/// Keeps the most recent `VALID` responses to `newPayload` and `forkchoiceUpdated` requests, so
+/// repeated identical requests, e.g. after a restart of the CL, can be answered without inserting
+/// the block into the tree or making it canonical again.
+///
+/// Only `VALID` responses are cached, because the other statuses can change once the engine has
+/// synced. The cache is cleared whenever the canonical chain is reorged or unwound. Invalid
+/// payloads are tracked by the
+/// [`InvalidHeaderCache`](crate::engine::invalid_headers::InvalidHeaderCache).
+pub(crate) struct EngineResponseCache {
+    /// The responses to `newPayload` requests by block hash.
+    payloads: LruMap<B256, PayloadStatus>,
+    /// The responses to `forkchoiceUpdated` requests without payload attributes by head hash.
+    forkchoice_updates: LruMap<B256, (ForkchoiceState, PayloadStatus)>,
+    /// Metrics for the cache.
+    metrics: EngineResponseCacheMetrics,
+}
+
+impl EngineResponseCache {
+    pub(crate) fn new(max_length: u32) -> Self {
+        Self {
+            payloads: LruMap::new(ByLength::new(max_length)),
+            forkchoice_updates: LruMap::new(ByLength::new(max_length)),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Returns the cached response to a `newPayload` request for the block.
+    pub(crate) fn payload_status(&mut self, block_hash: &B256) -> Option<PayloadStatus> {
+        let status = self.payloads.get(block_hash).cloned();
+        if status.is_some() {
+            self.metrics.payload_hits.increment(1);
+        }
+        status
+    }
+
+    /// Caches the response to a `newPayload` request for the block, if it's `VALID`.
+    pub(crate) fn insert_payload_status(&mut self, block_hash: B256, status: &PayloadStatus) {
+        if status.is_valid() {
+            self.payloads.insert(block_hash, status.clone());
+        }
+    }
+
+    /// Returns the cached response to a `forkchoiceUpdated` request with the same state.
+    ///
+    /// The caller has to make sure the canonical chain hasn't changed since the response was
+    /// cached, e.g. by checking that the head is still the canonical tip.
+    pub(crate) fn forkchoice_status(&mut self, state: &ForkchoiceState) -> Option<PayloadStatus> {
+        let (cached_state, status) = self.forkchoice_updates.get(&state.head_block_hash)?;
+        if cached_state != state {
+            return None
+        }
+        self.metrics.forkchoice_hits.increment(1);
+        Some(status.clone())
+    }
+
+    /// Caches the response to a `forkchoiceUpdated` request, if it's `VALID`.
+    pub(crate) fn insert_forkchoice_status(
+        &mut self,
+        state: ForkchoiceState,
+        status: &PayloadStatus,
+    ) {
+        if status.is_valid() {
+            self.forkchoice_updates.insert(state.head_block_hash, (state, status.clone()));
+        }
+    }
+
+    /// Removes all cached responses, e.g. after the pipeline changed the database.
+    pub(crate) fn clear(&mut self) {
+        self.payloads.clear();
+        self.forkchoice_updates.clear();
+    }
+
+    /// Removes all cached responses if the new canonical head doesn't extend the previous
+    /// canonical tip, i.e. the canonical chain was reorged.
+    pub(crate) fn on_canonical_head(&mut self, previous_tip: B256, head: &SealedHeader) {
+        if head.parent_hash != previous_tip {
+            self.clear();
+        }
+    }
+}
+
+/// Metrics for the engine response cache.
+#[derive(Metrics)]
+#[metrics(scope = "consensus.engine.beacon.response_cache")]
+struct EngineResponseCacheMetrics {
+    /// The number of `newPayload` requests answered from the cache.
+    payload_hits: Counter,
+    /// The number of `forkchoiceUpdated` requests answered from the cache.
+    forkchoice_hits: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Header;
+    use reth_rpc_types::engine::PayloadStatusEnum;
+
+    #[test]
+    fn caches_valid_responses() {
+        let mut cache = EngineResponseCache::new(2);
+        let valid = PayloadStatus::new(PayloadStatusEnum::Valid, Some(B256::with_last_byte(1)));
+        let syncing = PayloadStatus::from_status(PayloadStatusEnum::Syncing);
+
+        cache.insert_payload_status(B256::with_last_byte(1), &valid);
+        cache.insert_payload_status(B256::with_last_byte(2), &syncing);
+        assert_eq!(cache.payload_status(&B256::with_last_byte(1)), Some(valid.clone()));
+        assert_eq!(cache.payload_status(&B256::with_last_byte(2)), None);
+
+        let state =
+            ForkchoiceState { head_block_hash: B256::with_last_byte(1), ..Default::default() };
+        cache.insert_forkchoice_status(state, &valid);
+        assert_eq!(cache.forkchoice_status(&state), Some(valid));
+        // the same head with a different finalized block
+        let other = ForkchoiceState { finalized_block_hash: B256::with_last_byte(3), ..state };
+        assert_eq!(cache.forkchoice_status(&other), None);
+
+        cache.clear();
+        assert_eq!(cache.forkchoice_status(&state), None);
+    }
+
+    #[test]
+    fn clears_on_reorg() {
+        let mut cache = EngineResponseCache::new(2);
+        let valid = PayloadStatus::new(PayloadStatusEnum::Valid, Some(B256::with_last_byte(1)));
+        cache.insert_payload_status(B256::with_last_byte(1), &valid);
+
+        // a new head on top of the previous tip keeps the responses
+        let mut head = Header { parent_hash: B256::with_last_byte(1), ..Default::default() };
+        cache.on_canonical_head(B256::with_last_byte(1), &head.clone().seal_slow());
+        assert_eq!(cache.payload_status(&B256::with_last_byte(1)), Some(valid));
+
+        // a new head on another fork clears them
+        head.parent_hash = B256::with_last_byte(2);
+        cache.on_canonical_head(B256::with_last_byte(1), &head.seal_slow());
+        assert_eq!(cache.payload_status(&B256::with_last_byte(1)), None);
+    }
+}
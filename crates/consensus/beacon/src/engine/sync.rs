@@ -2,24 +2,41 @@
 
 use crate::{engine::metrics::EngineSyncMetrics, BeaconConsensus};
 use futures::FutureExt;
+use rand::Rng;
 use reth_db::database::Database;
-use reth_interfaces::p2p::{
-    bodies::client::BodiesClient,
-    full_block::{FetchFullBlockFuture, FetchFullBlockRangeFuture, FullBlockClient},
-    headers::client::HeadersClient,
+use reth_interfaces::{
+    db::set_reader_component,
+    p2p::{
+        bodies::client::BodiesClient,
+        full_block::{FetchFullBlockFuture, FetchFullBlockRangeFuture, FullBlockClient},
+        headers::client::HeadersClient,
+    },
 };
 use reth_primitives::{BlockNumber, ChainSpec, SealedBlock, B256};
-use reth_stages::{ControlFlow, Pipeline, PipelineError, PipelineWithResult};
+use reth_stages::{ControlFlow, Pipeline, PipelineCancelHandle, PipelineError, PipelineWithResult};
 use reth_tasks::TaskSpawner;
 use std::{
     cmp::{Ordering, Reverse},
     collections::{binary_heap::PeekMut, BinaryHeap},
+    future::Future,
+    mem,
+    pin::Pin,
     sync::Arc,
-    task::{ready, Context, Poll},
+    task::{ready, Context, Poll, Waker},
+    time::Duration,
 };
-use tokio::sync::oneshot;
+use tokio::{sync::oneshot, time::Sleep};
 use tracing::trace;
 
+/// The backoff before the first retry of a pipeline run that failed with a retryable error.
+const PIPELINE_RETRY_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The maximum backoff between retries of failed pipeline runs.
+const PIPELINE_RETRY_MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// The maximum number of consecutive retries of failed pipeline runs.
+const MAX_PIPELINE_RETRIES: u32 = 10;
+
 /// Manages syncing under the control of the engine.
 ///
 /// This type controls the [Pipeline] and supports (single) full block downloads.
@@ -39,8 +56,16 @@ where
     /// The current state of the pipeline.
     /// The pipeline is used for large ranges.
     pipeline_state: PipelineState<DB>,
+    /// Stops the pipeline at its next checkpoint, see [EngineSyncController::cancel_pipeline].
+    pipeline_cancel: PipelineCancelHandle,
     /// Pending target block for the pipeline to sync
     pending_pipeline_target: Option<B256>,
+    /// Target block of the last spawned pipeline run
+    last_pipeline_target: Option<B256>,
+    /// Number of consecutive failed pipeline runs that were retried, reset by a successful run.
+    pipeline_retries: u32,
+    /// The backoff to wait for before the next pipeline run is spawned, if it's a retry.
+    pipeline_retry_backoff: Option<Pin<Box<Sleep>>>,
     /// In-flight full block requests in progress.
     inflight_full_block_requests: Vec<FetchFullBlockFuture<Client>>,
     /// In-flight full block _range_ requests in progress.
@@ -55,6 +80,9 @@ where
     max_block: Option<BlockNumber>,
     /// Engine sync metrics.
     metrics: EngineSyncMetrics,
+    /// The waker of the last [EngineSyncController::poll] that returned [Poll::Pending], woken
+    /// when new work is queued outside of `poll`.
+    waker: Option<Waker>,
 }
 
 impl<DB, Client> EngineSyncController<DB, Client>
@@ -77,14 +105,26 @@ where
                 Arc::new(BeaconConsensus::new(chain_spec)),
             ),
             pipeline_task_spawner,
-            pipeline_state: PipelineState::Idle(Some(pipeline)),
+            pipeline_cancel: pipeline.cancel_handle(),
+            pipeline_state: PipelineState::Idle(pipeline),
             pending_pipeline_target: None,
+            last_pipeline_target: None,
+            pipeline_retries: 0,
+            pipeline_retry_backoff: None,
             inflight_full_block_requests: Vec::new(),
             inflight_block_range_requests: Vec::new(),
             range_buffered_blocks: BinaryHeap::new(),
             run_pipeline_continuously,
             max_block,
             metrics: EngineSyncMetrics::default(),
+            waker: None,
+        }
+    }
+
+    /// Wakes the task that last polled the controller, so the queued work is picked up.
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
         }
     }
 
@@ -114,6 +154,32 @@ where
         self.update_block_download_metrics();
     }
 
+    /// Schedules another pipeline run towards the target of the last run, unless a new target has
+    /// been set in the meantime.
+    ///
+    /// The run is spawned after an exponential backoff with jitter. Returns the backoff, or [None]
+    /// if the pipeline already failed [MAX_PIPELINE_RETRIES] times in a row.
+    pub(crate) fn retry_pipeline(&mut self) -> Option<Duration> {
+        if self.pipeline_retries >= MAX_PIPELINE_RETRIES {
+            return None
+        }
+        self.pipeline_retries += 1;
+
+        let backoff = pipeline_retry_backoff(self.pipeline_retries);
+        self.pipeline_retry_backoff = Some(Box::pin(tokio::time::sleep(backoff)));
+        if self.pending_pipeline_target.is_none() {
+            self.pending_pipeline_target = self.last_pipeline_target;
+        }
+        self.wake();
+
+        Some(backoff)
+    }
+
+    /// Returns the number of consecutive failed pipeline runs that were retried.
+    pub(crate) fn pipeline_retries(&self) -> u32 {
+        self.pipeline_retries
+    }
+
     /// Returns whether or not the sync controller is set to run the pipeline continuously.
     pub(crate) fn run_pipeline_continuously(&self) -> bool {
         self.run_pipeline_continuously
@@ -135,6 +201,12 @@ where
         !self.is_pipeline_idle()
     }
 
+    /// Cancels the pipeline, so a running pipeline finishes with [PipelineError::Cancelled] at its
+    /// next checkpoint and any later run is cancelled right away.
+    pub(crate) fn cancel_pipeline(&self) {
+        self.pipeline_cancel.cancel();
+    }
+
     /// Returns true if there's already a request for the given hash.
     pub(crate) fn is_inflight_request(&self, hash: B256) -> bool {
         self.inflight_full_block_requests.iter().any(|req| *req.hash() == hash)
@@ -157,6 +229,7 @@ where
 
             let request = self.full_block_client.get_full_block_range(hash, count);
             self.inflight_block_range_requests.push(request);
+            self.wake();
         }
 
         // // TODO: need more metrics for block ranges
@@ -180,6 +253,7 @@ where
         self.inflight_full_block_requests.push(request);
 
         self.update_block_download_metrics();
+        self.wake();
 
         true
     }
@@ -187,6 +261,7 @@ where
     /// Sets a new target to sync the pipeline to.
     pub(crate) fn set_pipeline_sync_target(&mut self, target: B256) {
         self.pending_pipeline_target = Some(target);
+        self.wake();
     }
 
     /// Check if the engine reached max block as specified by `max_block` parameter.
@@ -218,10 +293,13 @@ where
         };
         let ev = match res {
             Ok((pipeline, result)) => {
+                if result.is_ok() {
+                    self.pipeline_retries = 0;
+                }
                 let minimum_block_number = pipeline.minimum_block_number();
                 let reached_max_block =
                     self.has_reached_max_block(minimum_block_number.unwrap_or_default());
-                self.pipeline_state = PipelineState::Idle(Some(pipeline));
+                self.pipeline_state = PipelineState::Idle(pipeline);
                 EngineSyncEvent::PipelineFinished { result, reached_max_block }
             }
             Err(_) => {
@@ -234,42 +312,53 @@ where
 
     /// This will spawn the pipeline if it is idle and a target is set or if the pipeline is set to
     /// run continuously.
-    fn try_spawn_pipeline(&mut self) -> Option<EngineSyncEvent> {
-        match &mut self.pipeline_state {
-            PipelineState::Idle(pipeline) => {
-                let target = self.pending_pipeline_target.take();
-
-                if target.is_none() && !self.run_pipeline_continuously {
-                    // nothing to sync
-                    return None
-                }
+    ///
+    /// If the last run failed, the pipeline is spawned once the retry backoff has elapsed.
+    fn try_spawn_pipeline(&mut self, cx: &mut Context<'_>) -> Option<EngineSyncEvent> {
+        if self.pending_pipeline_target.is_none() && !self.run_pipeline_continuously {
+            // nothing to sync
+            return None
+        }
+
+        if let Some(backoff) = self.pipeline_retry_backoff.as_mut() {
+            if backoff.as_mut().poll(cx).is_pending() {
+                return None
+            }
+            self.pipeline_retry_backoff = None;
+        }
 
-                let (tx, rx) = oneshot::channel();
+        let (tx, rx) = oneshot::channel();
+        let pipeline = match mem::replace(&mut self.pipeline_state, PipelineState::Running(rx)) {
+            PipelineState::Idle(pipeline) => pipeline,
+            running @ PipelineState::Running(_) => {
+                // the pending target is picked up once the running pipeline is finished
+                self.pipeline_state = running;
+                return None
+            }
+        };
 
-                let pipeline = pipeline.take().expect("exists");
-                self.pipeline_task_spawner.spawn_critical_blocking(
-                    "pipeline task",
-                    Box::pin(async move {
-                        let result = pipeline.run_as_fut(target).await;
-                        let _ = tx.send(result);
-                    }),
-                );
-                self.pipeline_state = PipelineState::Running(rx);
+        let target = self.pending_pipeline_target.take();
+        self.last_pipeline_target = target;
+        self.pipeline_task_spawner.spawn_critical_blocking(
+            "pipeline task",
+            Box::pin(async move {
+                let _component = set_reader_component("pipeline");
+                let result = pipeline.run_as_fut(target).await;
+                let _ = tx.send(result);
+            }),
+        );
 
-                // we also clear any pending full block requests because we expect them to be
-                // outdated (included in the range the pipeline is syncing anyway)
-                self.clear_block_download_requests();
+        // we also clear any pending full block requests because we expect them to be
+        // outdated (included in the range the pipeline is syncing anyway)
+        self.clear_block_download_requests();
 
-                Some(EngineSyncEvent::PipelineStarted(target))
-            }
-            PipelineState::Running(_) => None,
-        }
+        Some(EngineSyncEvent::PipelineStarted(target))
     }
 
     /// Advances the sync process.
     pub(crate) fn poll(&mut self, cx: &mut Context<'_>) -> Poll<EngineSyncEvent> {
         // try to spawn a pipeline if a target is set
-        if let Some(event) = self.try_spawn_pipeline() {
+        if let Some(event) = self.try_spawn_pipeline(cx) {
             return Poll::Ready(event)
         }
 
@@ -321,10 +410,27 @@ where
             return Poll::Ready(EngineSyncEvent::FetchedFullBlock(block.0 .0))
         }
 
+        // the pipeline and the downloads registered the waker already, this one is woken if new
+        // work is queued before the next poll
+        if !self.waker.as_ref().is_some_and(|waker| waker.will_wake(cx.waker())) {
+            self.waker = Some(cx.waker().clone());
+        }
         Poll::Pending
     }
 }
 
+/// Returns the backoff before the given retry of a failed pipeline run.
+///
+/// The backoff doubles with every retry, starting at [PIPELINE_RETRY_INITIAL_BACKOFF] and capped at
+/// [PIPELINE_RETRY_MAX_BACKOFF]. A random jitter of up to half of the backoff is subtracted, so
+/// that nodes that failed at the same time don't retry at the same time.
+fn pipeline_retry_backoff(retry: u32) -> Duration {
+    let backoff = PIPELINE_RETRY_INITIAL_BACKOFF
+        .saturating_mul(1 << retry.saturating_sub(1).min(16))
+        .min(PIPELINE_RETRY_MAX_BACKOFF);
+    backoff.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+}
+
 /// A wrapper type around [SealedBlock] that implements the [Ord] trait by block number.
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct OrderedSealedBlock(SealedBlock);
@@ -377,7 +483,7 @@ pub(crate) enum EngineSyncEvent {
 /// deadlock.
 enum PipelineState<DB: Database> {
     /// Pipeline is idle.
-    Idle(Option<Pipeline<DB>>),
+    Idle(Pipeline<DB>),
     /// Pipeline is running and waiting for a response
     Running(oneshot::Receiver<PipelineWithResult<DB>>),
 }
@@ -393,7 +499,7 @@ impl<DB: Database> PipelineState<DB> {
 mod tests {
     use super::*;
     use assert_matches::assert_matches;
-    use futures::poll;
+    use futures::{poll, task::ArcWake};
     use reth_db::{mdbx::DatabaseEnv, test_utils::TempDatabase};
     use reth_interfaces::{p2p::either::EitherDownloader, test_utils::TestFullBlockClient};
     use reth_primitives::{
@@ -406,8 +512,12 @@ mod tests {
     };
     use reth_stages::{test_utils::TestStages, ExecOutput, StageError};
     use reth_tasks::TokioTaskExecutor;
-    use std::{collections::VecDeque, future::poll_fn, sync::Arc};
-    use tokio::sync::watch;
+    use std::{
+        collections::VecDeque,
+        future::poll_fn,
+        sync::atomic::{AtomicUsize, Ordering as AtomicOrdering},
+    };
+    use tokio::sync::{watch, Notify};
 
     struct TestPipelineBuilder {
         pipeline_exec_outputs: VecDeque<Result<ExecOutput, StageError>>,
@@ -575,6 +685,37 @@ mod tests {
         });
     }
 
+    #[tokio::test]
+    async fn pipeline_retries_back_off() {
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(MAINNET.genesis.clone())
+                .paris_activated()
+                .build(),
+        );
+
+        let pipeline = TestPipelineBuilder::new().build(chain_spec.clone());
+        let mut sync_controller =
+            TestSyncControllerBuilder::<TestFullBlockClient>::new().build(pipeline, chain_spec);
+
+        for retry in 1..=MAX_PIPELINE_RETRIES {
+            let max_backoff = PIPELINE_RETRY_INITIAL_BACKOFF
+                .saturating_mul(1 << (retry - 1))
+                .min(PIPELINE_RETRY_MAX_BACKOFF);
+            let backoff = sync_controller.retry_pipeline().expect("retry budget is not exhausted");
+            assert!(backoff >= max_backoff / 2 && backoff <= max_backoff, "{backoff:?}");
+            assert_eq!(sync_controller.pipeline_retries(), retry);
+        }
+        assert_eq!(sync_controller.retry_pipeline(), None);
+
+        // the pipeline isn't spawned before the backoff has elapsed
+        sync_controller.set_pipeline_sync_target(B256::random());
+        let sync_future = poll_fn(|cx| sync_controller.poll(cx));
+        assert!(poll!(sync_future).is_pending());
+        assert!(sync_controller.is_pipeline_idle());
+    }
+
     #[tokio::test]
     async fn controller_sends_range_request() {
         let chain_spec = Arc::new(
@@ -630,4 +771,80 @@ mod tests {
             });
         }
     }
+
+    /// A waker that counts how often it was woken.
+    #[derive(Default)]
+    struct WakeCounter {
+        wakes: AtomicUsize,
+        notify: Notify,
+    }
+
+    impl WakeCounter {
+        fn wakes(&self) -> usize {
+            self.wakes.load(AtomicOrdering::SeqCst)
+        }
+    }
+
+    impl ArcWake for WakeCounter {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.wakes.fetch_add(1, AtomicOrdering::SeqCst);
+            arc_self.notify.notify_one();
+        }
+    }
+
+    #[tokio::test]
+    async fn wakes_only_on_new_work() {
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(MAINNET.genesis.clone())
+                .paris_activated()
+                .build(),
+        );
+
+        let pipeline = TestPipelineBuilder::new()
+            .with_pipeline_exec_outputs(VecDeque::from([Ok(ExecOutput {
+                checkpoint: StageCheckpoint::new(5),
+                done: true,
+            })]))
+            .build(chain_spec.clone());
+        let mut sync_controller =
+            TestSyncControllerBuilder::<TestFullBlockClient>::new().build(pipeline, chain_spec);
+
+        let counter = Arc::new(WakeCounter::default());
+        let waker = futures::task::waker(counter.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        // nothing to do, polling again doesn't wake the task
+        assert!(sync_controller.poll(&mut cx).is_pending());
+        assert!(sync_controller.poll(&mut cx).is_pending());
+        assert_eq!(counter.wakes(), 0);
+
+        // queuing a target wakes the task once
+        sync_controller.set_pipeline_sync_target(B256::random());
+        sync_controller.set_pipeline_sync_target(B256::random());
+        assert_eq!(counter.wakes(), 1);
+        assert_matches!(
+            sync_controller.poll(&mut cx),
+            Poll::Ready(EngineSyncEvent::PipelineStarted(Some(_)))
+        );
+
+        // the task is only woken by the finished pipeline
+        let wakes = counter.wakes();
+        if sync_controller.poll(&mut cx).is_pending() {
+            counter.notify.notified().await;
+            assert_eq!(counter.wakes(), wakes + 1);
+        }
+        assert_matches!(
+            sync_controller.poll(&mut cx),
+            Poll::Ready(EngineSyncEvent::PipelineFinished { result: Ok(_), .. })
+        );
+
+        // idle again
+        let wakes = counter.wakes();
+        assert!(sync_controller.poll(&mut cx).is_pending());
+        tokio::task::yield_now().await;
+        assert!(sync_controller.poll(&mut cx).is_pending());
+        assert_eq!(counter.wakes(), wakes);
+    }
 }
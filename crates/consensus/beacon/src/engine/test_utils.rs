@@ -1,7 +1,8 @@
 use crate::{
     engine::hooks::PruneHook, hooks::EngineHooks, BeaconConsensus, BeaconConsensusEngine,
-    BeaconConsensusEngineError, BeaconConsensusEngineHandle, BeaconForkChoiceUpdateError,
-    BeaconOnNewPayloadError, MIN_BLOCKS_FOR_PIPELINE_RUN,
+    BeaconConsensusEngineBuilder, BeaconConsensusEngineError, BeaconConsensusEngineEvent,
+    BeaconConsensusEngineHandle, BeaconForkChoiceUpdateError, BeaconOnNewPayloadError,
+    MIN_BLOCKS_FOR_PIPELINE_RUN,
 };
 use reth_blockchain_tree::{
     config::BlockchainTreeConfig, externals::TreeExternals, BlockchainTree, ShareableBlockchainTree,
@@ -19,7 +20,6 @@ use reth_interfaces::{
     consensus::Consensus,
     executor::BlockExecutionError,
     p2p::{bodies::client::BodiesClient, either::EitherDownloader, headers::client::HeadersClient},
-    sync::NoopSyncStateUpdater,
     test_utils::{NoopFullBlockClient, TestConsensus},
 };
 use reth_payload_builder::test_utils::spawn_test_payload_service;
@@ -38,6 +38,7 @@ use reth_stages::{sets::DefaultStages, test_utils::TestStages, ExecOutput, Pipel
 use reth_tasks::TokioTaskExecutor;
 use std::{collections::VecDeque, sync::Arc};
 use tokio::sync::{oneshot, watch};
+use tokio_stream::wrappers::UnboundedReceiverStream;
 
 type TestBeaconConsensusEngine<Client> = BeaconConsensusEngine<
     Arc<DatabaseEnv>,
@@ -69,6 +70,14 @@ impl<DB> TestEnv<DB> {
         Self { db, tip_rx, engine_handle }
     }
 
+    pub async fn send_shutdown(&self) -> Result<(), oneshot::error::RecvError> {
+        self.engine_handle.shutdown().await
+    }
+
+    pub fn event_listener(&self) -> UnboundedReceiverStream<BeaconConsensusEngineEvent> {
+        self.engine_handle.event_listener()
+    }
+
     pub async fn send_new_payload<T: Into<ExecutionPayload>>(
         &self,
         payload: T,
@@ -529,19 +538,18 @@ where
         let mut hooks = EngineHooks::new();
         hooks.add(PruneHook::new(pruner, Box::<TokioTaskExecutor>::default()));
 
-        let (mut engine, handle) = BeaconConsensusEngine::new(
+        let (mut engine, handle) = BeaconConsensusEngineBuilder::new(
             client,
             pipeline,
             blockchain_provider,
             Box::<TokioTaskExecutor>::default(),
-            Box::<NoopSyncStateUpdater>::default(),
-            None,
-            false,
             payload_builder,
-            None,
+        )
+        .with_pipeline_run_threshold(
             self.base_config.pipeline_run_threshold.unwrap_or(MIN_BLOCKS_FOR_PIPELINE_RUN),
-            hooks,
         )
+        .with_hooks(hooks)
+        .build()
         .expect("failed to create consensus engine");
 
         if let Some(max_block) = self.base_config.max_block {
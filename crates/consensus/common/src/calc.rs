@@ -5,8 +5,8 @@ use reth_primitives::{constants::ETH_TO_WEI, BlockNumber, Chain, ChainSpec, Hard
 /// The base block reward is defined as:
 ///
 /// - For Paris and later: `None`
-/// - For Petersburg and later: `Some(2 ETH)`
-/// - For Byzantium and later: `Some(3 ETH)`
+/// - For Constantinople and later: `Some(2 ETH)` ([EIP-1234])
+/// - For Byzantium and later: `Some(3 ETH)` ([EIP-649])
 /// - Otherwise: `Some(5 ETH)`
 ///
 /// # Note
@@ -19,6 +19,8 @@ use reth_primitives::{constants::ETH_TO_WEI, BlockNumber, Chain, ChainSpec, Hard
 /// - Definition: [Yellow Paper][yp] (page 15, 11.3)
 ///
 /// [yp]: https://ethereum.github.io/yellowpaper/paper.pdf
+/// [EIP-649]: https://eips.ethereum.org/EIPS/eip-649
+/// [EIP-1234]: https://eips.ethereum.org/EIPS/eip-1234
 pub fn base_block_reward(
     chain_spec: &ChainSpec,
     block_number: BlockNumber,
@@ -29,7 +31,7 @@ pub fn base_block_reward(
         chain_spec.fork(Hardfork::Paris).active_at_ttd(total_difficulty, block_difficulty)
     {
         None
-    } else if chain_spec.fork(Hardfork::Petersburg).active_at_block(block_number) {
+    } else if chain_spec.fork(Hardfork::Constantinople).active_at_block(block_number) {
         Some(ETH_TO_WEI * 2)
     } else if chain_spec.fork(Hardfork::Byzantium).active_at_block(block_number) {
         Some(ETH_TO_WEI * 3)
@@ -103,7 +105,7 @@ pub fn ommer_reward(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use reth_primitives::{MAINNET, U256};
+    use reth_primitives::{ChainSpecBuilder, ForkCondition, MAINNET, U256};
 
     #[test]
     fn calc_base_block_reward() {
@@ -111,9 +113,11 @@ mod tests {
         let cases = [
             // Pre-byzantium
             ((0, U256::ZERO), Some(ETH_TO_WEI * 5)),
+            ((4369999, U256::ZERO), Some(ETH_TO_WEI * 5)),
             // Byzantium
             ((4370000, U256::ZERO), Some(ETH_TO_WEI * 3)),
-            // Petersburg
+            ((7279999, U256::ZERO), Some(ETH_TO_WEI * 3)),
+            // Constantinople
             ((7280000, U256::ZERO), Some(ETH_TO_WEI * 2)),
             // Merge
             ((10000000, U256::from(58_750_000_000_000_000_000_000_u128)), None),
@@ -140,4 +144,44 @@ mod tests {
             assert_eq!(block_reward(base_reward, num_ommers), expected_reward);
         }
     }
+
+    #[test]
+    fn calc_base_block_reward_constantinople_before_petersburg() {
+        // Constantinople reduced the reward, even on chains that activated it before Petersburg.
+        let chain_spec = ChainSpecBuilder::mainnet()
+            .byzantium_activated()
+            .with_fork(Hardfork::Constantinople, ForkCondition::Block(10))
+            .with_fork(Hardfork::Petersburg, ForkCondition::Block(20))
+            .build();
+
+        // (block number, reward)
+        let cases =
+            [(9, Some(ETH_TO_WEI * 3)), (10, Some(ETH_TO_WEI * 2)), (20, Some(ETH_TO_WEI * 2))];
+
+        for (block_number, expected_reward) in cases {
+            assert_eq!(
+                base_block_reward(&chain_spec, block_number, U256::ZERO, U256::ZERO),
+                expected_reward
+            );
+        }
+    }
+
+    #[test]
+    fn calc_ommer_reward() {
+        let base_reward = ETH_TO_WEI * 5;
+
+        // (ommer distance, reward)
+        let cases = [
+            (1, base_reward * 7 / 8),
+            (2, base_reward * 6 / 8),
+            (3, base_reward * 5 / 8),
+            (4, base_reward * 4 / 8),
+            (5, base_reward * 3 / 8),
+            (6, base_reward * 2 / 8),
+        ];
+
+        for (distance, expected_reward) in cases {
+            assert_eq!(ommer_reward(base_reward, 100, 100 - distance), expected_reward);
+        }
+    }
 }
@@ -2,10 +2,7 @@
 
 use reth_interfaces::{consensus::ConsensusError, RethResult};
 use reth_primitives::{
-    constants::{
-        self,
-        eip4844::{DATA_GAS_PER_BLOB, MAX_DATA_GAS_PER_BLOCK},
-    },
+    constants::eip4844::{DATA_GAS_PER_BLOB, MAX_DATA_GAS_PER_BLOCK},
     eip4844::calculate_excess_blob_gas,
     BlockNumber, ChainSpec, GotExpected, Hardfork, Header, InvalidTransactionError, SealedBlock,
     SealedHeader, Transaction, TransactionSignedEcRecovered, TxEip1559, TxEip2930, TxEip4844,
@@ -339,15 +336,10 @@ pub fn validate_header_regarding_parent(
     if chain_spec.fork(Hardfork::London).active_at_block(child.number) {
         let base_fee = child.base_fee_per_gas.ok_or(ConsensusError::BaseFeeMissing)?;
 
-        let expected_base_fee =
-            if chain_spec.fork(Hardfork::London).transitions_at_block(child.number) {
-                constants::EIP1559_INITIAL_BASE_FEE
-            } else {
-                // This BaseFeeMissing will not happen as previous blocks are checked to have them.
-                parent
-                    .next_block_base_fee(chain_spec.base_fee_params(child.timestamp))
-                    .ok_or(ConsensusError::BaseFeeMissing)?
-            };
+        // This BaseFeeMissing will not happen as previous blocks are checked to have them.
+        let expected_base_fee = chain_spec
+            .next_block_base_fee(parent, child.timestamp)
+            .ok_or(ConsensusError::BaseFeeMissing)?;
         if expected_base_fee != base_fee {
             return Err(ConsensusError::BaseFeeDiff(GotExpected {
                 expected: expected_base_fee,
@@ -46,6 +46,30 @@ pub enum BlockchainTreeError {
         /// The block hash of the block that failed to buffer.
         block_hash: BlockHash,
     },
+    /// Thrown if making a block canonical would unwind more canonical blocks than the configured
+    /// reorg depth limit.
+    #[error(
+        "reorg to fork block #{fork_block} unwinds {depth} blocks, \
+         exceeding the reorg depth limit of {limit}"
+    )]
+    ReorgTooDeep {
+        /// The canonical block number the new chain forks from.
+        fork_block: BlockNumber,
+        /// The number of canonical blocks the reorg would unwind.
+        depth: u64,
+        /// The configured reorg depth limit.
+        limit: u64,
+    },
+    /// Thrown if making a block canonical would unwind the last finalized block.
+    #[error(
+        "reorg to fork block #{fork_block} unwinds the last finalized block #{last_finalized}"
+    )]
+    ReorgBelowFinalized {
+        /// The canonical block number the new chain forks from.
+        fork_block: BlockNumber,
+        /// The block number of the last finalized block.
+        last_finalized: BlockNumber,
+    },
 }
 
 /// Result alias for `CanonicalError`
@@ -244,6 +268,7 @@ impl InsertBlockErrorKind {
                     BlockExecutionError::CanonicalRevert { .. } |
                     BlockExecutionError::CanonicalCommit { .. } |
                     BlockExecutionError::AppendChainDoesntConnect { .. } |
+                    BlockExecutionError::InvariantViolation { .. } |
                     BlockExecutionError::UnavailableForTest => false,
                     #[cfg(feature = "optimism")]
                     BlockExecutionError::OptimismBlockExecution(_) => false,
@@ -259,7 +284,9 @@ impl InsertBlockErrorKind {
                     BlockchainTreeError::CanonicalChain { .. } |
                     BlockchainTreeError::BlockNumberNotFoundInChain { .. } |
                     BlockchainTreeError::BlockHashNotFoundInChain { .. } |
-                    BlockchainTreeError::BlockBufferingFailed { .. } => false,
+                    BlockchainTreeError::BlockBufferingFailed { .. } |
+                    BlockchainTreeError::ReorgTooDeep { .. } |
+                    BlockchainTreeError::ReorgBelowFinalized { .. } => false,
                 }
             }
             InsertBlockErrorKind::Provider(_) | InsertBlockErrorKind::Internal(_) => {
@@ -1,3 +1,5 @@
+use crate::ErrorSeverity;
+use std::{cell::Cell, time::Duration};
 use thiserror::Error;
 
 /// Database error type.
@@ -36,6 +38,24 @@ pub enum DatabaseError {
     /// Failed to use the specified log level, as it's not available.
     #[error("log level {0:?} is not available")]
     LogLevelUnavailable(LogLevel),
+    /// Failed to copy the database.
+    #[error("failed to copy the database ({0})")]
+    Copy(i32),
+    /// The read transaction was open for longer than allowed and can't be used anymore.
+    #[error("read transaction timed out after {0:?}")]
+    ReadTransactionTimeout(Duration),
+}
+
+impl DatabaseError {
+    /// Returns the [ErrorSeverity] of the error.
+    ///
+    /// A read transaction that timed out can be opened again, all other errors are fatal.
+    pub fn severity(&self) -> ErrorSeverity {
+        match self {
+            DatabaseError::ReadTransactionTimeout(_) => ErrorSeverity::Retryable,
+            _ => ErrorSeverity::Fatal,
+        }
+    }
 }
 
 impl From<DatabaseWriteError> for DatabaseError {
@@ -98,3 +118,62 @@ pub enum LogLevel {
     /// Enables logging for extra debug-level messages.
     Extra,
 }
+
+/// An open read transaction of the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseReader {
+    /// The ID of the snapshot the transaction reads.
+    pub txn_id: u64,
+    /// How long the transaction has been open.
+    pub age: Duration,
+    /// The component that opened the transaction, see [set_reader_component], or the name of its
+    /// thread if the thread isn't labeled.
+    pub component: String,
+}
+
+/// Lists the open read transactions of a database.
+///
+/// Long-lived read transactions keep their snapshot alive, so the pages freed after it can't be
+/// reused and the database grows.
+pub trait DatabaseReaders: std::fmt::Debug + Send + Sync {
+    /// Returns the open read transactions, the oldest first.
+    fn readers(&self) -> Vec<DatabaseReader>;
+
+    /// Checks the age of the open read transactions against the limits of the database.
+    ///
+    /// The operations on a read transaction check its age as well, this is needed to detect the
+    /// transactions that are idle.
+    fn check_readers(&self);
+}
+
+thread_local! {
+    static READER_COMPONENT: Cell<Option<&'static str>> = Cell::new(None);
+}
+
+/// Labels the read transactions that are opened on the current thread with the component, until
+/// the returned guard is dropped.
+///
+/// This is meant for threads that run a single component, e.g. its blocking tasks.
+pub fn set_reader_component(component: &'static str) -> ReaderComponentGuard {
+    let previous = READER_COMPONENT.with(|current| current.replace(Some(component)));
+    ReaderComponentGuard { previous }
+}
+
+/// Returns the component that the current thread is labeled with, see [set_reader_component].
+pub fn reader_component() -> Option<&'static str> {
+    READER_COMPONENT.with(Cell::get)
+}
+
+/// Restores the previous component label of the thread when it's dropped, see
+/// [set_reader_component].
+#[derive(Debug)]
+#[must_use = "the thread is only labeled until the guard is dropped"]
+pub struct ReaderComponentGuard {
+    previous: Option<&'static str>,
+}
+
+impl Drop for ReaderComponentGuard {
+    fn drop(&mut self) {
+        READER_COMPONENT.with(|current| current.set(self.previous));
+    }
+}
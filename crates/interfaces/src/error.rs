@@ -2,7 +2,7 @@ use crate::{
     blockchain_tree::error::{BlockchainTreeError, CanonicalError},
     consensus::ConsensusError,
     db::DatabaseError,
-    executor::BlockExecutionError,
+    executor::{BlockExecutionError, BlockValidationError},
     provider::ProviderError,
 };
 use reth_network_api::NetworkError;
@@ -47,6 +47,58 @@ pub enum RethError {
     Custom(String),
 }
 
+impl RethError {
+    /// Returns the [ErrorSeverity] of the error.
+    pub fn severity(&self) -> ErrorSeverity {
+        match self {
+            RethError::Execution(BlockExecutionError::Validation(
+                BlockValidationError::EVM { .. } |
+                BlockValidationError::SenderRecoveryError |
+                BlockValidationError::ReceiptRootDiff(_) |
+                BlockValidationError::BloomLogDiff(_) |
+                BlockValidationError::TransactionGasLimitMoreThanAvailableBlockGas { .. } |
+                BlockValidationError::BlockGasUsed { .. },
+            )) |
+            RethError::Consensus(_) |
+            RethError::Network(_) => ErrorSeverity::Retryable,
+            RethError::Execution(
+                BlockExecutionError::CanonicalRevert { inner } |
+                BlockExecutionError::CanonicalCommit { inner },
+            ) => inner.severity(),
+            RethError::Database(err) => err.severity(),
+            RethError::Provider(err) => err.severity(),
+            RethError::Canonical(err) if !err.is_fatal() => ErrorSeverity::Retryable,
+            RethError::Execution(_) | RethError::Canonical(_) | RethError::Custom(_) => {
+                ErrorSeverity::Fatal
+            }
+        }
+    }
+}
+
+/// Classification of an error by how the caller is expected to react to it.
+///
+/// Errors caused by invalid data received from peers are retryable: the downloaders report the
+/// peers whose responses fail validation and request the data again from other peers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorSeverity {
+    /// The error is transient and the operation can be retried.
+    Retryable,
+    /// The error is unrecoverable and the operation must be aborted.
+    Fatal,
+}
+
+impl ErrorSeverity {
+    /// Returns `true` if the error is unrecoverable.
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, ErrorSeverity::Fatal)
+    }
+
+    /// Returns `true` if the failed operation can be retried.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ErrorSeverity::Retryable)
+    }
+}
+
 impl From<BlockchainTreeError> for RethError {
     fn from(error: BlockchainTreeError) -> Self {
         RethError::Canonical(CanonicalError::BlockchainTree(error))
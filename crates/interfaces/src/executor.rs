@@ -91,14 +91,16 @@ pub enum BlockExecutionError {
     /// Transaction error on revert with inner details
     #[error("transaction error on revert: {inner}")]
     CanonicalRevert {
-        /// The inner error message
-        inner: String,
+        /// The inner error
+        #[source]
+        inner: Box<ProviderError>,
     },
     /// Transaction error on commit with inner details
     #[error("transaction error on commit: {inner}")]
     CanonicalCommit {
-        /// The inner error message
-        inner: String,
+        /// The inner error
+        #[source]
+        inner: Box<ProviderError>,
     },
     /// Error when appending chain on fork is not possible
     #[error(
@@ -110,6 +112,16 @@ pub enum BlockExecutionError {
         /// The fork on the other chain
         other_chain_fork: Box<BlockNumHash>,
     },
+    /// Error when a debug check of the executor invariants failed.
+    ///
+    /// This indicates a bug in the executor rather than an invalid block.
+    #[error("executor invariant violated by transaction {hash}: {message}")]
+    InvariantViolation {
+        /// The hash of the transaction
+        hash: Box<B256>,
+        /// Description of the violated invariant.
+        message: String,
+    },
     /// Only used for TestExecutor
     ///
     /// Note: this is not feature gated for convenience.
@@ -140,8 +152,13 @@ pub enum OptimismBlockExecutionError {
 impl BlockExecutionError {
     /// Returns `true` if the error is fatal.
     ///
-    /// This represents an unrecoverable database related error.
+    /// This represents an unrecoverable database related error or a bug in the executor.
     pub fn is_fatal(&self) -> bool {
-        matches!(self, Self::CanonicalCommit { .. } | Self::CanonicalRevert { .. })
+        matches!(
+            self,
+            Self::CanonicalCommit { .. } |
+                Self::CanonicalRevert { .. } |
+                Self::InvariantViolation { .. }
+        )
     }
 }
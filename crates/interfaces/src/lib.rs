@@ -30,7 +30,7 @@ pub mod executor;
 
 /// Possible errors when interacting with the chain.
 mod error;
-pub use error::{RethError, RethResult};
+pub use error::{ErrorSeverity, RethError, RethResult};
 
 /// P2P traits.
 pub mod p2p;
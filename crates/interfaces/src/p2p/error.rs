@@ -1,5 +1,5 @@
 use super::headers::client::HeadersRequest;
-use crate::{consensus::ConsensusError, db::DatabaseError, provider::ProviderError};
+use crate::{consensus::ConsensusError, db::DatabaseError, provider::ProviderError, ErrorSeverity};
 use reth_network_api::ReputationChangeKind;
 use reth_primitives::{
     BlockHashOrNumber, BlockNumber, GotExpected, GotExpectedBoxed, Header, WithPeerId, B256,
@@ -99,6 +99,17 @@ impl RequestError {
     pub fn is_channel_closed(&self) -> bool {
         matches!(self, RequestError::ChannelClosed)
     }
+
+    /// Returns the [ErrorSeverity] of the error.
+    pub fn severity(&self) -> ErrorSeverity {
+        match self {
+            RequestError::UnsupportedCapability |
+            RequestError::BadResponse |
+            RequestError::Timeout |
+            RequestError::ConnectionDropped => ErrorSeverity::Retryable,
+            RequestError::ChannelClosed => ErrorSeverity::Fatal,
+        }
+    }
 }
 
 impl<T> From<mpsc::error::SendError<T>> for RequestError {
@@ -182,6 +193,33 @@ pub enum DownloadError {
     Provider(#[from] ProviderError),
 }
 
+// === impl DownloadError ===
+
+impl DownloadError {
+    /// Returns the [ErrorSeverity] of the error.
+    ///
+    /// Errors caused by invalid responses are retried with other peers, errors caused by the local
+    /// state of the node are fatal.
+    pub fn severity(&self) -> ErrorSeverity {
+        match self {
+            DownloadError::HeaderValidation { .. } |
+            DownloadError::InvalidTip(_) |
+            DownloadError::InvalidTipNumber(_) |
+            DownloadError::HeadersResponseStartBlockMismatch(_) |
+            DownloadError::HeadersResponseTooShort(_) |
+            DownloadError::BodyValidation { .. } |
+            DownloadError::TooManyBodies(_) |
+            DownloadError::EmptyResponse |
+            DownloadError::Timeout => ErrorSeverity::Retryable,
+            DownloadError::RequestError(err) => err.severity(),
+            DownloadError::Provider(err) => err.severity(),
+            DownloadError::MissingHeader { .. } | DownloadError::InvalidBodyRange { .. } => {
+                ErrorSeverity::Fatal
+            }
+        }
+    }
+}
+
 impl From<DatabaseError> for DownloadError {
     fn from(error: DatabaseError) -> Self {
         Self::Provider(ProviderError::Database(error))
@@ -204,4 +242,22 @@ mod tests {
         let headers: Vec<Header> = vec![];
         assert!(Ok(headers).is_likely_bad_headers_response(&request));
     }
+
+    #[test]
+    fn test_download_error_severity() {
+        assert_eq!(DownloadError::EmptyResponse.severity(), ErrorSeverity::Retryable);
+        assert_eq!(DownloadError::Timeout.severity(), ErrorSeverity::Retryable);
+        assert_eq!(
+            DownloadError::RequestError(RequestError::ConnectionDropped).severity(),
+            ErrorSeverity::Retryable
+        );
+        assert_eq!(
+            DownloadError::RequestError(RequestError::ChannelClosed).severity(),
+            ErrorSeverity::Fatal
+        );
+        assert_eq!(
+            DownloadError::MissingHeader { block_number: 1 }.severity(),
+            ErrorSeverity::Fatal
+        );
+    }
 }
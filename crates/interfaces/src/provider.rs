@@ -1,3 +1,4 @@
+use crate::ErrorSeverity;
 use reth_primitives::{
     Address, BlockHash, BlockHashOrNumber, BlockNumber, GotExpected, SnapshotSegment,
     TxHashOrNumber, TxNumber, B256, U256,
@@ -14,6 +15,16 @@ pub enum ProviderError {
     /// Database error.
     #[error(transparent)]
     Database(#[from] crate::db::DatabaseError),
+    /// A database operation failed while writing the state to a table.
+    #[error("failed to write state to table {table} at key {key}: {error}")]
+    StateWrite {
+        /// The table that was written to.
+        table: &'static str,
+        /// The key the operation failed at.
+        key: String,
+        /// The database error.
+        error: crate::db::DatabaseError,
+    },
     /// Filesystem path error.
     #[error("{0}")]
     FsPathError(String),
@@ -110,6 +121,14 @@ pub enum ProviderError {
     /// State is not available for the given block number because it is pruned.
     #[error("state at block #{0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// State at the given block number is too far behind the tip to be proven.
+    #[error("state at block #{block_number} is more than {max_blocks} blocks behind the tip to be proven")]
+    ProofTooDeep {
+        /// The block number of the requested state.
+        block_number: BlockNumber,
+        /// The maximum number of blocks a proof can be reverted by.
+        max_blocks: u64,
+    },
     /// Provider does not support this particular request.
     #[error("this provider does not support this request")]
     UnsupportedProvider,
@@ -122,11 +141,32 @@ pub enum ProviderError {
     /// Snapshot file is not found for requested transaction.
     #[error("not able to find {0} snapshot file for transaction id {1}")]
     MissingSnapshotTx(SnapshotSegment, TxNumber),
+    /// A block or transaction number derived from a start and an offset overflows u64.
+    #[error("number {start} with offset {offset} overflows u64")]
+    NumberOverflow {
+        /// The first number.
+        start: u64,
+        /// The offset from the first number.
+        offset: u64,
+    },
     /// Error encountered when the block number conversion from U256 to u64 causes an overflow.
     #[error("failed to convert block number U256 to u64: {0}")]
     BlockNumberOverflow(U256),
 }
 
+impl ProviderError {
+    /// Returns the [ErrorSeverity] of the error.
+    ///
+    /// Only read transactions that timed out can be retried, all other errors are caused by the
+    /// state of the database.
+    pub fn severity(&self) -> ErrorSeverity {
+        match self {
+            ProviderError::Database(err) => err.severity(),
+            _ => ErrorSeverity::Fatal,
+        }
+    }
+}
+
 impl From<reth_nippy_jar::NippyJarError> for ProviderError {
     fn from(err: reth_nippy_jar::NippyJarError) -> Self {
         ProviderError::NippyJar(err.to_string())
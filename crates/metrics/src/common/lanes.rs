@@ -0,0 +1,352 @@
+//! A bounded channel with lanes that handle a full channel differently, see [lane_channel].
+
+use futures::{task::AtomicWaker, Stream};
+use metrics::{Counter, Gauge};
+use reth_metrics_derive::Metrics;
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Context, Poll},
+};
+use tokio::sync::{
+    mpsc::error::{SendError, TrySendError},
+    Notify,
+};
+
+/// The lane a message of a [lane_channel] is queued in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lane<K> {
+    /// The message is never dropped. If the lane is full, sending waits for capacity.
+    Priority,
+    /// The message replaces a queued message with the same key. If the lane is full, the oldest
+    /// queued message of the lane is dropped.
+    Coalesce(K),
+}
+
+/// A message that can be sent over a [lane_channel].
+pub trait LaneMessage {
+    /// The key by which messages in the [Lane::Coalesce] lane are coalesced.
+    type Key: PartialEq;
+
+    /// Returns the lane the message is queued in.
+    fn lane(&self) -> Lane<Self::Key>;
+}
+
+/// Returns a new bounded channel with a [Lane::Priority] and a [Lane::Coalesce] lane with the
+/// given capacities.
+///
+/// The lanes only differ in how they handle a full lane, the messages of both lanes are received in
+/// the order they were sent.
+///
+/// # Panics
+///
+/// If a capacity is zero.
+pub fn lane_channel<T: LaneMessage>(
+    priority_capacity: usize,
+    coalesce_capacity: usize,
+    scope: &'static str,
+) -> (LaneSender<T>, LaneReceiver<T>) {
+    assert!(priority_capacity > 0, "priority lane capacity must be greater than zero");
+    assert!(coalesce_capacity > 0, "coalesce lane capacity must be greater than zero");
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            priority_len: 0,
+            coalesce_len: 0,
+            senders: 1,
+            closed: false,
+        }),
+        priority_capacity,
+        coalesce_capacity,
+        receiver_waker: AtomicWaker::new(),
+        priority_capacity_available: Notify::new(),
+        metrics: LaneChannelMetrics::new(scope),
+    });
+    (LaneSender { shared: Arc::clone(&shared) }, LaneReceiver { shared })
+}
+
+/// The sending half of a [lane_channel].
+#[derive(Debug)]
+pub struct LaneSender<T: LaneMessage> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: LaneMessage> LaneSender<T> {
+    /// Sends the message, waiting for capacity if it's a [Lane::Priority] message and the lane is
+    /// full.
+    ///
+    /// Returns the queued message that was replaced or dropped to make room for a
+    /// [Lane::Coalesce] message, if any.
+    pub async fn send(&self, mut message: T) -> Result<Option<T>, SendError<T>> {
+        loop {
+            let notified = self.shared.priority_capacity_available.notified();
+            tokio::pin!(notified);
+            // register for notifications before checking the capacity, so a notification that's
+            // sent in between isn't missed
+            notified.as_mut().enable();
+
+            match self.try_send(message) {
+                Ok(replaced) => return Ok(replaced),
+                Err(TrySendError::Closed(message)) => return Err(SendError(message)),
+                Err(TrySendError::Full(full)) => {
+                    self.shared.metrics.priority_back_pressure.increment(1);
+                    message = full;
+                }
+            }
+            notified.await;
+        }
+    }
+
+    /// Sends the message without waiting for capacity.
+    ///
+    /// Returns the queued message that was replaced or dropped to make room for a
+    /// [Lane::Coalesce] message, if any.
+    pub fn try_send(&self, message: T) -> Result<Option<T>, TrySendError<T>> {
+        let shared = &self.shared;
+        let mut state = shared.lock();
+        if state.closed {
+            return Err(TrySendError::Closed(message))
+        }
+
+        let replaced = match message.lane() {
+            Lane::Priority => {
+                if state.priority_len == shared.priority_capacity {
+                    return Err(TrySendError::Full(message))
+                }
+                state.priority_len += 1;
+                state.queue.push_back(message);
+                None
+            }
+            Lane::Coalesce(key) => {
+                let queued = state
+                    .queue
+                    .iter_mut()
+                    .find(|queued| matches!(queued.lane(), Lane::Coalesce(k) if k == key));
+                if let Some(queued) = queued {
+                    shared.metrics.coalesced_messages.increment(1);
+                    Some(std::mem::replace(queued, message))
+                } else if state.coalesce_len == shared.coalesce_capacity {
+                    let oldest = state
+                        .queue
+                        .iter()
+                        .position(|queued| matches!(queued.lane(), Lane::Coalesce(_)))
+                        .expect("coalesce lane is full");
+                    shared.metrics.dropped_messages.increment(1);
+                    let dropped = state.queue.remove(oldest);
+                    state.queue.push_back(message);
+                    dropped
+                } else {
+                    state.coalesce_len += 1;
+                    state.queue.push_back(message);
+                    None
+                }
+            }
+        };
+        shared.update_queue_depth(&state);
+        drop(state);
+
+        shared.receiver_waker.wake();
+        Ok(replaced)
+    }
+
+    /// Returns `true` if the receiver was dropped.
+    pub fn is_closed(&self) -> bool {
+        self.shared.lock().closed
+    }
+}
+
+impl<T: LaneMessage> Clone for LaneSender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Self { shared: Arc::clone(&self.shared) }
+    }
+}
+
+impl<T: LaneMessage> Drop for LaneSender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            drop(state);
+            self.shared.receiver_waker.wake();
+        }
+    }
+}
+
+/// The receiving half of a [lane_channel].
+#[derive(Debug)]
+pub struct LaneReceiver<T: LaneMessage> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: LaneMessage> LaneReceiver<T> {
+    /// Polls to receive the next message on this channel.
+    ///
+    /// Returns `None` if all senders were dropped and no message is queued.
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let shared = &self.shared;
+        shared.receiver_waker.register(cx.waker());
+
+        let mut state = shared.lock();
+        if let Some(message) = state.queue.pop_front() {
+            match message.lane() {
+                Lane::Priority => {
+                    state.priority_len -= 1;
+                    shared.priority_capacity_available.notify_one();
+                }
+                Lane::Coalesce(_) => state.coalesce_len -= 1,
+            }
+            shared.update_queue_depth(&state);
+            return Poll::Ready(Some(message))
+        }
+
+        if state.senders == 0 {
+            return Poll::Ready(None)
+        }
+        Poll::Pending
+    }
+}
+
+impl<T: LaneMessage> Stream for LaneReceiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_recv(cx)
+    }
+}
+
+impl<T: LaneMessage> Drop for LaneReceiver<T> {
+    fn drop(&mut self) {
+        self.shared.lock().closed = true;
+        // wake the senders waiting for capacity, so they return an error
+        self.shared.priority_capacity_available.notify_waiters();
+    }
+}
+
+/// The state shared by the senders and the receiver of a [lane_channel].
+#[derive(Debug)]
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    priority_capacity: usize,
+    coalesce_capacity: usize,
+    /// Wakes the receiver if a message is queued or all senders are dropped.
+    receiver_waker: AtomicWaker,
+    /// Notifies the senders waiting for capacity of the priority lane.
+    priority_capacity_available: Notify,
+    metrics: LaneChannelMetrics,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn update_queue_depth(&self, state: &State<T>) {
+        self.metrics.priority_queue_depth.set(state.priority_len as f64);
+        self.metrics.coalesce_queue_depth.set(state.coalesce_len as f64);
+    }
+}
+
+/// The queued messages of a [lane_channel].
+#[derive(Debug)]
+struct State<T> {
+    /// The messages of both lanes in the order they were sent.
+    queue: VecDeque<T>,
+    /// The number of queued [Lane::Priority] messages.
+    priority_len: usize,
+    /// The number of queued [Lane::Coalesce] messages.
+    coalesce_len: usize,
+    /// The number of [LaneSender]s.
+    senders: usize,
+    /// Whether the [LaneReceiver] was dropped.
+    closed: bool,
+}
+
+/// Queue metrics for [lane_channel]
+#[derive(Clone, Metrics)]
+#[metrics(dynamic = true)]
+struct LaneChannelMetrics {
+    /// Number of queued messages in the priority lane
+    priority_queue_depth: Gauge,
+    /// Number of queued messages in the coalesce lane
+    coalesce_queue_depth: Gauge,
+    /// Number of sends that waited for capacity of the priority lane
+    priority_back_pressure: Counter,
+    /// Number of queued messages replaced by a message with the same key
+    coalesced_messages: Counter,
+    /// Number of queued messages dropped because the coalesce lane was full
+    dropped_messages: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Message {
+        Priority(u64),
+        Coalesce { key: u64, value: u64 },
+    }
+
+    impl LaneMessage for Message {
+        type Key = u64;
+
+        fn lane(&self) -> Lane<u64> {
+            match self {
+                Self::Priority(_) => Lane::Priority,
+                Self::Coalesce { key, .. } => Lane::Coalesce(*key),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn coalesce_and_drop() {
+        let (tx, rx) = lane_channel(1, 2, "test");
+
+        assert_eq!(tx.send(Message::Coalesce { key: 1, value: 1 }).await, Ok(None));
+        assert_eq!(tx.send(Message::Priority(1)).await, Ok(None));
+        assert_eq!(tx.send(Message::Coalesce { key: 2, value: 1 }).await, Ok(None));
+        // replaces the queued message with the same key
+        assert_eq!(
+            tx.send(Message::Coalesce { key: 1, value: 2 }).await,
+            Ok(Some(Message::Coalesce { key: 1, value: 1 }))
+        );
+        // drops the oldest message of the full lane
+        assert_eq!(
+            tx.send(Message::Coalesce { key: 3, value: 1 }).await,
+            Ok(Some(Message::Coalesce { key: 1, value: 2 }))
+        );
+        assert!(matches!(tx.try_send(Message::Priority(2)), Err(TrySendError::Full(_))));
+
+        drop(tx);
+        assert_eq!(
+            rx.collect::<Vec<_>>().await,
+            vec![
+                Message::Priority(1),
+                Message::Coalesce { key: 2, value: 1 },
+                Message::Coalesce { key: 3, value: 1 },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn priority_waits_for_capacity() {
+        let (tx, mut rx) = lane_channel(1, 1, "test");
+        tx.send(Message::Priority(1)).await.unwrap();
+
+        let sender = tx.clone();
+        let send = tokio::spawn(async move { sender.send(Message::Priority(2)).await });
+        tokio::task::yield_now().await;
+        assert!(!send.is_finished());
+
+        assert_eq!(rx.next().await, Some(Message::Priority(1)));
+        assert_eq!(send.await.unwrap(), Ok(None));
+        assert_eq!(rx.next().await, Some(Message::Priority(2)));
+
+        drop(rx);
+        assert!(tx.is_closed());
+        assert!(tx.send(Message::Priority(3)).await.is_err());
+    }
+}
@@ -1 +1,2 @@
+pub mod lanes;
 pub mod mpsc;
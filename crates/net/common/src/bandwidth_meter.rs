@@ -33,12 +33,9 @@ use std::{
     },
     task::{ready, Context, Poll},
 };
-use tokio::{
-    io::{AsyncRead, AsyncWrite, ReadBuf},
-    net::TcpStream,
-};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 
-use crate::stream::HasRemoteAddr;
+use crate::{io_meter::NetworkIOMeterMetrics, stream::HasRemoteAddr};
 
 /// Meters bandwidth usage of streams
 #[derive(Debug)]
@@ -94,19 +91,36 @@ pub struct MeteredStream<S> {
     inner: S,
     /// The [`BandwidthMeter`] struct this uses to meter bandwidth
     meter: BandwidthMeter,
+    /// The metrics the traffic is recorded with, if any
+    metrics: Option<NetworkIOMeterMetrics>,
 }
 
 impl<S> MeteredStream<S> {
     /// Creates a new [`MeteredStream`] wrapping around the provided stream,
     /// along with a new [`BandwidthMeter`]
     pub fn new(inner: S) -> Self {
-        Self { inner, meter: BandwidthMeter::default() }
+        Self { inner, meter: BandwidthMeter::default(), metrics: None }
     }
 
     /// Creates a new [`MeteredStream`] wrapping around the provided stream,
     /// attaching the provided [`BandwidthMeter`]
     pub fn new_with_meter(inner: S, meter: BandwidthMeter) -> Self {
-        Self { inner, meter }
+        Self { inner, meter, metrics: None }
+    }
+
+    /// Records the traffic through this stream with the given [`NetworkIOMeterMetrics`] as well.
+    ///
+    /// `None` disables the metrics, e.g. if no metrics recorder is installed, see
+    /// [`PeerIOMeters`](crate::io_meter::PeerIOMeters).
+    pub fn with_metrics(mut self, metrics: Option<NetworkIOMeterMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Replaces the [`NetworkIOMeterMetrics`] the traffic through this stream is recorded with,
+    /// e.g. once the peer of an incoming connection is known.
+    pub fn set_metrics(&mut self, metrics: Option<NetworkIOMeterMetrics>) {
+        self.metrics = metrics;
     }
 
     /// Provides a reference to the [`BandwidthMeter`] attached to this [`MeteredStream`]
@@ -136,6 +150,9 @@ impl<Stream: AsyncRead> AsyncRead for MeteredStream<Stream> {
             .inner
             .inbound
             .fetch_add(u64::try_from(num_bytes).unwrap_or(u64::max_value()), Ordering::Relaxed);
+        if let Some(metrics) = this.metrics {
+            metrics.record_read(num_bytes);
+        }
         Poll::Ready(Ok(()))
     }
 }
@@ -152,6 +169,9 @@ impl<Stream: AsyncWrite> AsyncWrite for MeteredStream<Stream> {
             .inner
             .outbound
             .fetch_add(u64::try_from(num_bytes).unwrap_or(u64::max_value()), Ordering::Relaxed);
+        if let Some(metrics) = this.metrics {
+            metrics.record_write(num_bytes);
+        }
         Poll::Ready(Ok(num_bytes))
     }
 
@@ -166,7 +186,7 @@ impl<Stream: AsyncWrite> AsyncWrite for MeteredStream<Stream> {
     }
 }
 
-impl HasRemoteAddr for MeteredStream<TcpStream> {
+impl<S: HasRemoteAddr> HasRemoteAddr for MeteredStream<S> {
     fn remote_addr(&self) -> Option<SocketAddr> {
         self.inner.remote_addr()
     }
@@ -0,0 +1,123 @@
+//! Support for metering the compression of messages.
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+/// The number of bytes of messages before and after compression.
+#[derive(Debug, Default)]
+struct CompressionMeterInner {
+    /// Number of compressed bytes received
+    inbound_compressed: AtomicU64,
+    /// Number of bytes received after decompression
+    inbound_raw: AtomicU64,
+    /// Number of compressed bytes sent
+    outbound_compressed: AtomicU64,
+    /// Number of bytes sent before compression
+    outbound_raw: AtomicU64,
+}
+
+impl CompressionMeterInner {
+    fn record_inbound(&self, compressed: usize, raw: usize) {
+        self.inbound_compressed.fetch_add(compressed as u64, Ordering::Relaxed);
+        self.inbound_raw.fetch_add(raw as u64, Ordering::Relaxed);
+    }
+
+    fn record_outbound(&self, compressed: usize, raw: usize) {
+        self.outbound_compressed.fetch_add(compressed as u64, Ordering::Relaxed);
+        self.outbound_raw.fetch_add(raw as u64, Ordering::Relaxed);
+    }
+}
+
+/// Public shareable struct used for metering the size of messages on the wire, after compression,
+/// and their size before compression.
+///
+/// A meter can be split into meters for single sessions with [CompressionMeter::session], which
+/// also record into the meter they were created from.
+#[derive(Clone, Debug, Default)]
+pub struct CompressionMeter {
+    inner: Arc<CompressionMeterInner>,
+    /// The meter this was created from, if any.
+    parent: Option<Arc<CompressionMeterInner>>,
+}
+
+impl CompressionMeter {
+    /// Returns a new meter for a single session, that also records into this meter.
+    pub fn session(&self) -> Self {
+        Self { inner: Default::default(), parent: Some(self.inner.clone()) }
+    }
+
+    /// Records a received message with its `compressed` size on the wire and its `raw` size after
+    /// decompression.
+    pub fn record_inbound(&self, compressed: usize, raw: usize) {
+        self.inner.record_inbound(compressed, raw);
+        if let Some(parent) = &self.parent {
+            parent.record_inbound(compressed, raw);
+        }
+    }
+
+    /// Records a sent message with its `raw` size before compression and its `compressed` size on
+    /// the wire.
+    pub fn record_outbound(&self, compressed: usize, raw: usize) {
+        self.inner.record_outbound(compressed, raw);
+        if let Some(parent) = &self.parent {
+            parent.record_outbound(compressed, raw);
+        }
+    }
+
+    /// Returns the total number of compressed bytes received.
+    pub fn total_inbound_compressed(&self) -> u64 {
+        self.inner.inbound_compressed.load(Ordering::Relaxed)
+    }
+
+    /// Returns the total number of bytes received after decompression.
+    pub fn total_inbound_raw(&self) -> u64 {
+        self.inner.inbound_raw.load(Ordering::Relaxed)
+    }
+
+    /// Returns the total number of compressed bytes sent.
+    pub fn total_outbound_compressed(&self) -> u64 {
+        self.inner.outbound_compressed.load(Ordering::Relaxed)
+    }
+
+    /// Returns the total number of bytes sent before compression.
+    pub fn total_outbound_raw(&self) -> u64 {
+        self.inner.outbound_raw.load(Ordering::Relaxed)
+    }
+
+    /// Returns the ratio of the raw size to the compressed size of all messages, or `1.0` if no
+    /// messages were metered yet.
+    ///
+    /// > **Note**: This method is by design subject to race conditions. The returned value should
+    /// > only ever be used for statistics purposes.
+    pub fn compression_ratio(&self) -> f64 {
+        let compressed = self.total_inbound_compressed() + self.total_outbound_compressed();
+        if compressed == 0 {
+            return 1.0
+        }
+        (self.total_inbound_raw() + self.total_outbound_raw()) as f64 / compressed as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_records_into_parent() {
+        let meter = CompressionMeter::default();
+        assert_eq!(meter.compression_ratio(), 1.0);
+
+        let session_1 = meter.session();
+        let session_2 = meter.session();
+        session_1.record_inbound(10, 40);
+        session_2.record_outbound(30, 40);
+
+        assert_eq!(session_1.compression_ratio(), 4.0);
+        assert_eq!(session_2.total_inbound_compressed(), 0);
+        assert_eq!(meter.total_inbound_raw(), 40);
+        assert_eq!(meter.total_outbound_compressed(), 30);
+        assert_eq!(meter.compression_ratio(), 2.0);
+    }
+}
@@ -0,0 +1,175 @@
+//! Metrics for the traffic of network streams.
+
+use reth_metrics::{
+    metrics::{self, Counter, Histogram},
+    Metrics,
+};
+use reth_primitives::PeerId;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// The default number of peers that get their own label set, see [PeerIOMeters].
+pub const DEFAULT_MAX_LABELED_PEERS: usize = 64;
+
+/// The label value of the peers that don't get their own label set.
+const OTHER_PEERS_LABEL: &str = "other";
+
+/// The label value of the connections whose peer isn't known yet, i.e. incoming connections before
+/// the handshake.
+const UNKNOWN_PEER_LABEL: &str = "unknown";
+
+/// Metrics for the traffic of network streams, see
+/// [MeteredStream](crate::bandwidth_meter::MeteredStream).
+#[derive(Clone, Metrics)]
+#[metrics(dynamic = true)]
+pub struct NetworkIOMeterMetrics {
+    /// Number of bytes read from the streams
+    pub(crate) inbound_bytes: Counter,
+    /// Number of bytes written to the streams
+    pub(crate) outbound_bytes: Counter,
+    /// The sizes of the reads from the streams
+    pub(crate) read_size: Histogram,
+    /// The sizes of the writes to the streams
+    pub(crate) write_size: Histogram,
+}
+
+impl NetworkIOMeterMetrics {
+    /// Records a read of `num_bytes` bytes.
+    pub(crate) fn record_read(&self, num_bytes: usize) {
+        self.inbound_bytes.increment(num_bytes as u64);
+        self.read_size.record(num_bytes as f64);
+    }
+
+    /// Records a write of `num_bytes` bytes.
+    pub(crate) fn record_write(&self, num_bytes: usize) {
+        self.outbound_bytes.increment(num_bytes as u64);
+        self.write_size.record(num_bytes as f64);
+    }
+}
+
+/// Hands out [NetworkIOMeterMetrics] labeled by peer.
+///
+/// Every label set stays registered with the metrics recorder for the lifetime of the process, so
+/// only the first `max_labeled_peers` peers get their own label set. The traffic of all other peers
+/// is recorded with the `peer="other"` label, which keeps the number of label sets bounded no
+/// matter how many peers connect over time.
+///
+/// If no metrics recorder is installed, no metrics are created and nothing is recorded.
+#[derive(Clone, Debug)]
+pub struct PeerIOMeters {
+    inner: Option<Arc<PeerIOMetersInner>>,
+}
+
+impl PeerIOMeters {
+    /// Creates a new [PeerIOMeters] with the given metrics scope that creates label sets for up to
+    /// `max_labeled_peers` peers.
+    ///
+    /// Returns a disabled instance if no metrics recorder is installed.
+    pub fn new(scope: &'static str, max_labeled_peers: usize) -> Self {
+        if metrics::try_recorder().is_none() {
+            return Self::disabled()
+        }
+        Self::enabled(scope, max_labeled_peers)
+    }
+
+    fn enabled(scope: &'static str, max_labeled_peers: usize) -> Self {
+        Self {
+            inner: Some(Arc::new(PeerIOMetersInner {
+                scope,
+                max_labeled_peers,
+                peers: Mutex::new(HashMap::new()),
+                other: NetworkIOMeterMetrics::new_with_labels(
+                    scope,
+                    &[("peer", OTHER_PEERS_LABEL)],
+                ),
+                unknown: NetworkIOMeterMetrics::new_with_labels(
+                    scope,
+                    &[("peer", UNKNOWN_PEER_LABEL)],
+                ),
+            })),
+        }
+    }
+
+    /// Returns an instance that doesn't record anything.
+    pub fn disabled() -> Self {
+        Self { inner: None }
+    }
+
+    /// Returns `true` if metrics are recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Returns the number of peers that got their own label set.
+    pub fn labeled_peers(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.lock_peers().len())
+    }
+
+    /// Returns the metrics for the streams of the given peer, or `None` if disabled.
+    pub fn peer(&self, peer_id: PeerId) -> Option<NetworkIOMeterMetrics> {
+        let inner = self.inner.as_ref()?;
+        let mut peers = inner.lock_peers();
+        if let Some(metrics) = peers.get(&peer_id) {
+            return Some(metrics.clone())
+        }
+        if peers.len() >= inner.max_labeled_peers {
+            return Some(inner.other.clone())
+        }
+        let metrics =
+            NetworkIOMeterMetrics::new_with_labels(inner.scope, &[("peer", peer_id.to_string())]);
+        peers.insert(peer_id, metrics.clone());
+        Some(metrics)
+    }
+
+    /// Returns the metrics for the streams whose peer isn't known yet, or `None` if disabled.
+    pub fn unknown_peer(&self) -> Option<NetworkIOMeterMetrics> {
+        self.inner.as_ref().map(|inner| inner.unknown.clone())
+    }
+}
+
+#[derive(Debug)]
+struct PeerIOMetersInner {
+    scope: &'static str,
+    max_labeled_peers: usize,
+    /// The peers that got their own label set.
+    peers: Mutex<HashMap<PeerId, NetworkIOMeterMetrics>>,
+    /// The metrics of the peers that didn't get their own label set.
+    other: NetworkIOMeterMetrics,
+    /// The metrics of the streams whose peer isn't known yet.
+    unknown: NetworkIOMeterMetrics,
+}
+
+impl PeerIOMetersInner {
+    fn lock_peers(&self) -> MutexGuard<'_, HashMap<PeerId, NetworkIOMeterMetrics>> {
+        self.peers.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_without_recorder() {
+        let meters = PeerIOMeters::new("test", DEFAULT_MAX_LABELED_PEERS);
+        assert!(!meters.is_enabled());
+        assert!(meters.peer(PeerId::random()).is_none());
+        assert!(meters.unknown_peer().is_none());
+    }
+
+    #[test]
+    fn bounded_label_sets() {
+        let meters = PeerIOMeters::enabled("test", 2);
+
+        let peers = [PeerId::random(), PeerId::random(), PeerId::random()];
+        for peer_id in peers {
+            assert!(meters.peer(peer_id).is_some());
+        }
+        assert_eq!(meters.labeled_peers(), 2);
+        // a peer that already got a label set keeps it
+        assert!(meters.peer(peers[0]).is_some());
+        assert_eq!(meters.labeled_peers(), 2);
+    }
+}
@@ -11,6 +11,8 @@
 
 pub mod ban_list;
 pub mod bandwidth_meter;
+pub mod compression_meter;
+pub mod io_meter;
 /// Traits related to tokio streams
 pub mod stream;
 
@@ -27,6 +27,7 @@
 
 use crate::{
     error::{DecodePacketError, Discv4Error},
+    metrics::{Discv4Metrics, PacketMetrics},
     proto::{FindNode, Message, Neighbours, Packet, Ping, Pong},
 };
 use alloy_rlp::{RlpDecodable, RlpEncodable};
@@ -72,6 +73,8 @@ pub mod proto;
 mod config;
 pub use config::{Discv4Config, Discv4ConfigBuilder};
 
+mod metrics;
+
 mod node;
 use node::{kad_key, NodeKey};
 
@@ -460,6 +463,12 @@ pub struct Discv4Service {
     received_pongs: PongTable,
     /// Interval used to expire additionally tracked nodes
     expire_interval: Interval,
+    /// Metrics of the service
+    metrics: Discv4Metrics,
+    /// Metrics of the received packets
+    ingress_packets: PacketMetrics,
+    /// Metrics of the sent packets
+    egress_packets: PacketMetrics,
 }
 
 impl Discv4Service {
@@ -561,6 +570,9 @@ impl Discv4Service {
             queued_events: Default::default(),
             received_pongs: Default::default(),
             expire_interval: tokio::time::interval(EXPIRE_DURATION),
+            metrics: Default::default(),
+            ingress_packets: PacketMetrics::ingress(),
+            egress_packets: PacketMetrics::egress(),
         }
     }
 
@@ -726,6 +738,7 @@ impl Discv4Service {
                 .map(|n| (target_key.distance(&n.key), n.value.record)),
             tx,
         );
+        self.metrics.lookups_started.increment(1);
 
         // From those 16, pick the 3 closest to start the concurrent lookup.
         let closest = ctx.closest(ALPHA);
@@ -953,6 +966,7 @@ impl Discv4Service {
     pub(crate) fn send_packet(&mut self, msg: Message, to: SocketAddr) -> B256 {
         let (payload, hash) = msg.encode(&self.secret_key);
         trace!(target: "discv4",  r#type=?msg.msg_type(), ?to, ?hash, "sending packet");
+        self.egress_packets.increment(msg.msg_type());
         let _ = self.egress.try_send((payload, to)).map_err(|err| {
             debug!(
                 target: "discv4",
@@ -1127,9 +1141,10 @@ impl Discv4Service {
 
     /// Sends an enr request message to the node's UDP address.
     ///
-    /// Returns the echo hash of the ping message.
+    /// This is a noop if there's already a pending request for the node, because only the response
+    /// to the most recent request is accepted.
     pub(crate) fn send_enr_request(&mut self, node: NodeRecord) {
-        if !self.config.enable_eip868 {
+        if !self.config.enable_eip868 || self.pending_enr_requests.contains_key(&node.id) {
             return
         }
         let remote_addr = node.udp_addr();
@@ -1203,32 +1218,47 @@ impl Discv4Service {
     }
 
     /// Handler for incoming `EnrResponse` message
+    ///
+    /// The response is only accepted if it answers the pending request and the ENR is signed by
+    /// the node that sent it. The sequence number of the ENR is tracked, so that the ENR is only
+    /// requested again once the node announces a higher sequence number in a ping or pong.
     fn on_enr_response(&mut self, msg: EnrResponse, remote_addr: SocketAddr, id: PeerId) {
         trace!(target: "discv4", ?remote_addr, ?msg, "received ENR response");
         if let Some(resp) = self.pending_enr_requests.remove(&id) {
-            if resp.echo_hash == msg.request_hash {
-                let key = kad_key(id);
-                let fork_id = msg.eth_fork_id();
-                let (record, old_fork_id) = match self.kbuckets.entry(&key) {
-                    kbucket::Entry::Present(mut entry, _) => {
-                        let id = entry.value_mut().update_with_fork_id(fork_id);
-                        (entry.value().record, id)
-                    }
-                    kbucket::Entry::Pending(mut entry, _) => {
-                        let id = entry.value().update_with_fork_id(fork_id);
-                        (entry.value().record, id)
-                    }
-                    _ => return,
-                };
-                match (fork_id, old_fork_id) {
-                    (Some(new), Some(old)) => {
-                        if new != old {
-                            self.notify(DiscoveryUpdate::EnrForkId(record, new))
-                        }
+            if resp.echo_hash != msg.request_hash {
+                trace!(target: "discv4", ?remote_addr, expected=?resp.echo_hash, request_hash=?msg.request_hash, "Got unexpected ENR response");
+                return
+            }
+            let signer = msg.enr.0.public_key();
+            if PeerId::from_slice(&signer.serialize_uncompressed()[1..]) != id {
+                debug!(target: "discv4", ?remote_addr, ?id, "ENR response with ENR of another node");
+                return
+            }
+
+            let key = kad_key(id);
+            let enr_seq = Some(msg.enr.0.seq());
+            let fork_id = msg.eth_fork_id();
+            let (record, old_fork_id) = match self.kbuckets.entry(&key) {
+                kbucket::Entry::Present(mut entry, _) => {
+                    entry.value_mut().update_with_enr(enr_seq);
+                    let id = entry.value_mut().update_with_fork_id(fork_id);
+                    (entry.value().record, id)
+                }
+                kbucket::Entry::Pending(mut entry, _) => {
+                    entry.value().update_with_enr(enr_seq);
+                    let id = entry.value().update_with_fork_id(fork_id);
+                    (entry.value().record, id)
+                }
+                _ => return,
+            };
+            match (fork_id, old_fork_id) {
+                (Some(new), Some(old)) => {
+                    if new != old {
+                        self.notify(DiscoveryUpdate::EnrForkId(record, new))
                     }
-                    (Some(new), None) => self.notify(DiscoveryUpdate::EnrForkId(record, new)),
-                    _ => {}
                 }
+                (Some(new), None) => self.notify(DiscoveryUpdate::EnrForkId(record, new)),
+                _ => {}
             }
         }
     }
@@ -1332,6 +1362,7 @@ impl Discv4Service {
                         },
                     ) {
                         BucketInsertResult::Inserted | BucketInsertResult::Pending { .. } => {
+                            self.metrics.discovered_nodes.increment(1);
                             // only ping if the node was added to the table
                             self.try_ping(closest, PingReason::Lookup(closest, ctx.clone()))
                         }
@@ -1379,6 +1410,7 @@ impl Discv4Service {
         });
 
         debug!(target: "discv4", num=%failed_pings.len(), "evicting nodes due to failed pong");
+        self.metrics.bond_failures.increment(failed_pings.len() as u64);
 
         // remove nodes that failed to pong
         for node_id in failed_pings {
@@ -1613,6 +1645,7 @@ impl Discv4Service {
                     }
                     IngressEvent::Packet(remote_addr, Packet { msg, node_id, hash }) => {
                         trace!(target: "discv4",  r#type=?msg.msg_type(), from=?remote_addr,"received packet");
+                        self.ingress_packets.increment(msg.msg_type());
                         let event = match msg {
                             Message::Ping(ping) => {
                                 self.on_ping(ping, remote_addr, node_id, hash);
@@ -1952,12 +1985,27 @@ struct LookupContextInner {
 
 impl Drop for LookupContextInner {
     fn drop(&mut self) {
+        // there's only 1 instance shared across `FindNode` requests, if this is dropped then all
+        // requests finished
+        let closest_nodes = self.closest_nodes.take();
+        Discv4Metrics::default().lookups_completed.increment(1);
+
+        // the path of the lookup: the queried nodes, ordered by their distance to the target, and
+        // whether they responded
+        debug!(
+            target: "discv4::lookup",
+            lookup=?self.target.preimage().0,
+            path=?closest_nodes
+                .values()
+                .filter(|node| node.queried)
+                .map(|node| (node.record.id, node.responded))
+                .collect::<Vec<_>>(),
+            "Lookup finished"
+        );
+
         if let Some(tx) = self.listener.take() {
-            // there's only 1 instance shared across `FindNode` requests, if this is dropped then
-            // all requests finished, and we can send all results back
-            let nodes = self
-                .closest_nodes
-                .take()
+            // send all results back
+            let nodes = closest_nodes
                 .into_values()
                 .filter(|node| node.responded)
                 .map(|node| node.record)
@@ -2206,6 +2254,54 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_enr_response() {
+        let (_, mut service) = create_discv4().await;
+        let (_, remote) = create_discv4().await;
+        let (_, other) = create_discv4().await;
+
+        let record = remote.local_node_record;
+        let key = kad_key(record.id);
+        let kbucket::Entry::Absent(entry) = service.kbuckets.entry(&key) else { unreachable!() };
+        let _ = entry.insert(
+            NodeEntry::new_proven(record),
+            NodeStatus {
+                direction: ConnectionDirection::Outgoing,
+                state: ConnectionState::Connected,
+            },
+        );
+
+        // an ENR signed by another node is rejected
+        service.send_enr_request(record);
+        let request_hash = service.pending_enr_requests[&record.id].echo_hash;
+        let msg =
+            EnrResponse { request_hash, enr: EnrWrapper::new(other.local_eip_868_enr.clone()) };
+        service.on_enr_response(msg, record.udp_addr(), record.id);
+        assert!(!service.pending_enr_requests.contains_key(&record.id));
+        let kbucket::Entry::Present(entry, _) = service.kbuckets.entry(&key) else {
+            unreachable!()
+        };
+        assert_eq!(entry.value().fork_id, None);
+        assert_eq!(entry.value().last_enr_seq, None);
+
+        // only one request is pending at a time
+        service.send_enr_request(record);
+        let request_hash = service.pending_enr_requests[&record.id].echo_hash;
+        service.send_enr_request(record);
+        assert_eq!(service.pending_enr_requests[&record.id].echo_hash, request_hash);
+
+        let enr = remote.local_eip_868_enr.clone();
+        let msg = EnrResponse { request_hash, enr: EnrWrapper::new(enr.clone()) };
+        let fork_id = msg.eth_fork_id();
+        assert!(fork_id.is_some());
+        service.on_enr_response(msg, record.udp_addr(), record.id);
+        let kbucket::Entry::Present(entry, _) = service.kbuckets.entry(&key) else {
+            unreachable!()
+        };
+        assert_eq!(entry.value().fork_id, fork_id);
+        assert_eq!(entry.value().last_enr_seq, Some(enr.seq()));
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_lookup() {
@@ -0,0 +1,60 @@
+//! Discovery metrics.
+
+use crate::proto::MessageId;
+use reth_metrics::{metrics::Counter, Metrics};
+
+/// Metrics for the discv4 service
+#[derive(Metrics)]
+#[metrics(scope = "discv4")]
+pub(crate) struct Discv4Metrics {
+    /// Total number of started lookups
+    pub(crate) lookups_started: Counter,
+    /// Total number of finished lookups
+    pub(crate) lookups_completed: Counter,
+    /// Total number of new nodes that were inserted into the table during lookups
+    pub(crate) discovered_nodes: Counter,
+    /// Total number of nodes that failed to answer a ping in time
+    pub(crate) bond_failures: Counter,
+}
+
+/// Number of discv4 packets, by packet type, labeled by direction
+#[derive(Metrics)]
+#[metrics(scope = "discv4.packets")]
+pub(crate) struct PacketMetrics {
+    /// Total number of `Ping` packets
+    ping: Counter,
+    /// Total number of `Pong` packets
+    pong: Counter,
+    /// Total number of `FindNode` packets
+    find_node: Counter,
+    /// Total number of `Neighbours` packets
+    neighbours: Counter,
+    /// Total number of `EnrRequest` packets
+    enr_request: Counter,
+    /// Total number of `EnrResponse` packets
+    enr_response: Counter,
+}
+
+impl PacketMetrics {
+    /// Returns the metrics for the received packets.
+    pub(crate) fn ingress() -> Self {
+        Self::new_with_labels(&[("direction", "ingress")])
+    }
+
+    /// Returns the metrics for the sent packets.
+    pub(crate) fn egress() -> Self {
+        Self::new_with_labels(&[("direction", "egress")])
+    }
+
+    /// Increments the counter of the given packet type.
+    pub(crate) fn increment(&self, msg: MessageId) {
+        match msg {
+            MessageId::Ping => self.ping.increment(1),
+            MessageId::Pong => self.pong.increment(1),
+            MessageId::FindNode => self.find_node.increment(1),
+            MessageId::Neighbours => self.neighbours.increment(1),
+            MessageId::EnrRequest => self.enr_request.increment(1),
+            MessageId::EnrResponse => self.enr_response.increment(1),
+        }
+    }
+}
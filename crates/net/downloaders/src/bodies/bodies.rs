@@ -46,10 +46,13 @@ pub struct BodiesDownloader<B: BodiesClient, Provider> {
     stream_batch_size: usize,
     /// The allowed range for number of concurrent requests.
     concurrent_requests_range: RangeInclusive<usize>,
-    /// Maximum number of bytes of received blocks to buffer internally.
+    /// Maximum number of bytes of received blocks to hold in memory, including both the buffered
+    /// and the queued blocks.
     max_buffered_blocks_size_bytes: usize,
     /// Current estimated size of buffered blocks in bytes.
     buffered_blocks_size_bytes: usize,
+    /// Current estimated size of queued blocks in bytes.
+    queued_bodies_size_bytes: usize,
     /// The range of block numbers for body download.
     download_range: RangeInclusive<BlockNumber>,
     /// The latest block number returned.
@@ -152,9 +155,10 @@ where
         max_requests.min(*self.concurrent_requests_range.end())
     }
 
-    /// Returns true if the size of buffered blocks is lower than the configured maximum
+    /// Returns true if the size of buffered and queued blocks is lower than the configured maximum
     fn has_buffer_capacity(&self) -> bool {
-        self.buffered_blocks_size_bytes < self.max_buffered_blocks_size_bytes
+        self.buffered_blocks_size_bytes + self.queued_bodies_size_bytes <
+            self.max_buffered_blocks_size_bytes
     }
 
     // Check if the stream is terminated
@@ -181,6 +185,7 @@ where
         self.latest_queued_block_number.take();
         self.in_progress_queue.clear();
         self.queued_bodies = Vec::new();
+        self.queued_bodies_size_bytes = 0;
         self.buffered_responses = BinaryHeap::new();
         self.buffered_blocks_size_bytes = 0;
 
@@ -190,13 +195,27 @@ where
         self.metrics.buffered_blocks.set(0.);
         self.metrics.buffered_blocks_size_bytes.set(0.);
         self.metrics.queued_blocks.set(0.);
+        self.metrics.queued_blocks_size_bytes.set(0.);
     }
 
     /// Queues bodies and sets the latest queued block number
     fn queue_bodies(&mut self, bodies: Vec<BlockResponse>) {
         self.latest_queued_block_number = Some(bodies.last().expect("is not empty").block_number());
+        self.queued_bodies_size_bytes += bodies.iter().map(queued_body_size).sum::<usize>();
         self.queued_bodies.extend(bodies);
         self.metrics.queued_blocks.set(self.queued_bodies.len() as f64);
+        self.metrics.queued_blocks_size_bytes.set(self.queued_bodies_size_bytes as f64);
+    }
+
+    /// Removes the first `len` queued bodies and returns them.
+    fn split_queued_bodies(&mut self, len: usize) -> Vec<BlockResponse> {
+        let next_batch = self.queued_bodies.drain(..len).collect::<Vec<_>>();
+        self.queued_bodies.shrink_to_fit();
+        self.queued_bodies_size_bytes -= next_batch.iter().map(queued_body_size).sum::<usize>();
+        self.metrics.total_flushed.increment(next_batch.len() as u64);
+        self.metrics.queued_blocks.set(self.queued_bodies.len() as f64);
+        self.metrics.queued_blocks_size_bytes.set(self.queued_bodies_size_bytes as f64);
+        next_batch
     }
 
     /// Removes the next response from the buffer.
@@ -252,14 +271,17 @@ where
     }
 
     /// Returns the next batch of block bodies that can be returned if we have enough buffered
-    /// bodies
+    /// bodies.
+    ///
+    /// If the downloader ran out of buffer capacity, the contiguous prefix of queued bodies is
+    /// returned right away, even if it's shorter than the stream batch size, so the bodies can be
+    /// written to the database and new requests can be submitted.
     fn try_split_next_batch(&mut self) -> Option<Vec<BlockResponse>> {
         if self.queued_bodies.len() >= self.stream_batch_size {
-            let next_batch = self.queued_bodies.drain(..self.stream_batch_size).collect::<Vec<_>>();
-            self.queued_bodies.shrink_to_fit();
-            self.metrics.total_flushed.increment(next_batch.len() as u64);
-            self.metrics.queued_blocks.set(self.queued_bodies.len() as f64);
-            return Some(next_batch)
+            return Some(self.split_queued_bodies(self.stream_batch_size))
+        }
+        if !self.queued_bodies.is_empty() && !self.has_buffer_capacity() {
+            return Some(self.split_queued_bodies(self.queued_bodies.len()))
         }
         None
     }
@@ -405,23 +427,29 @@ where
             }
         }
 
+        // Yield the bodies that were queued in the last iteration
+        if let Some(next_batch) = this.try_split_next_batch() {
+            return Poll::Ready(Some(Ok(next_batch)))
+        }
+
         // All requests are handled, stream is finished
         if this.in_progress_queue.is_empty() {
             if this.queued_bodies.is_empty() {
                 return Poll::Ready(None)
             }
             let batch_size = this.stream_batch_size.min(this.queued_bodies.len());
-            let next_batch = this.queued_bodies.drain(..batch_size).collect::<Vec<_>>();
-            this.queued_bodies.shrink_to_fit();
-            this.metrics.total_flushed.increment(next_batch.len() as u64);
-            this.metrics.queued_blocks.set(this.queued_bodies.len() as f64);
-            return Poll::Ready(Some(Ok(next_batch)))
+            return Poll::Ready(Some(Ok(this.split_queued_bodies(batch_size))))
         }
 
         Poll::Pending
     }
 }
 
+/// Returns the estimated size of a queued body in bytes.
+fn queued_body_size(body: &BlockResponse) -> usize {
+    body.size() + mem::size_of::<BlockResponse>()
+}
+
 #[derive(Debug)]
 struct OrderedBodiesResponse {
     resp: Vec<BlockResponse>,
@@ -584,6 +612,7 @@ impl BodiesDownloaderBuilder {
             buffered_responses: Default::default(),
             queued_bodies: Default::default(),
             buffered_blocks_size_bytes: 0,
+            queued_bodies_size_bytes: 0,
         }
     }
 }
@@ -769,6 +798,39 @@ mod tests {
         }
     }
 
+    // Check that the queued bodies are returned before the stream batch is full once the size
+    // limit is reached.
+    #[tokio::test]
+    async fn flushes_queued_bodies_after_exceeding_limit() {
+        // Generate some random blocks
+        let db = create_test_rw_db();
+        let (headers, mut bodies) = generate_bodies(0..=99);
+
+        insert_headers(db.db(), &headers);
+
+        let stream_batch_size = 100;
+        let client = Arc::new(TestBodiesClient::default().with_bodies(bodies.clone()));
+        let mut downloader = BodiesDownloaderBuilder::default()
+            .with_stream_batch_size(stream_batch_size)
+            .with_request_limit(10)
+            .with_max_buffered_blocks_size_bytes(1)
+            .build(
+                client.clone(),
+                Arc::new(TestConsensus::default()),
+                ProviderFactory::new(db, MAINNET.clone()),
+            );
+
+        downloader.set_download_range(0..=99).expect("failed to set download range");
+        let mut header = 0;
+        while let Some(Ok(resp)) = downloader.next().await {
+            assert!(resp.len() < stream_batch_size);
+            assert_eq!(resp, zip_blocks(headers.iter().skip(header).take(resp.len()), &mut bodies));
+            header += resp.len();
+        }
+        assert_eq!(header, headers.len());
+        assert_eq!(downloader.queued_bodies_size_bytes, 0);
+    }
+
     // Check that the downloader can tolerate a few completely empty responses
     #[tokio::test]
     async fn can_tolerate_empty_responses() {
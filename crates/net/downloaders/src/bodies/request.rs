@@ -1,7 +1,7 @@
 use crate::metrics::{BodyDownloaderMetrics, ResponseMetrics};
 use futures::{Future, FutureExt};
 use reth_interfaces::{
-    consensus::{Consensus as ConsensusTrait, Consensus},
+    consensus::{Consensus as ConsensusTrait, Consensus, ConsensusError},
     p2p::{
         bodies::{client::BodiesClient, response::BlockResponse},
         error::{DownloadError, DownloadResult},
@@ -18,6 +18,11 @@ use std::{
     sync::Arc,
     task::{ready, Context, Poll},
 };
+use tokio::sync::oneshot;
+
+/// The validated body responses in request order, and the index and error of the first invalid
+/// block, if any.
+type ValidationOutcome = (Vec<BlockResponse>, Option<(usize, ConsensusError)>);
 
 /// Body request implemented as a [Future].
 ///
@@ -25,8 +30,9 @@ use std::{
 /// If the response arrived with insufficient number of bodies, the future
 /// will issue another request until all bodies are collected.
 ///
-/// It then proceeds to verify the downloaded bodies. In case of an validation error,
-/// the future will start over.
+/// It then proceeds to verify the downloaded bodies. The validation recomputes the roots of the
+/// bodies, so it's offloaded to the rayon pool, which bounds the number of concurrent validations
+/// by its number of threads. In case of an validation error, the future will start over.
 ///
 /// The future will filter out any empty headers (see [reth_primitives::Header::is_empty]) from the
 /// request. If [BodiesRequestFuture] was initialized with all empty headers, no request will be
@@ -50,6 +56,8 @@ pub(crate) struct BodiesRequestFuture<B: BodiesClient> {
     /// Internal buffer for all blocks
     buffer: Vec<BlockResponse>,
     fut: Option<B::Output>,
+    /// The pending validation of the last response and the peer that sent it.
+    validation: Option<(PeerId, oneshot::Receiver<ValidationOutcome>)>,
     /// Tracks how many bodies we requested in the last request.
     last_request_len: Option<usize>,
 }
@@ -73,6 +81,7 @@ where
             buffer: Default::default(),
             last_request_len: None,
             fut: None,
+            validation: None,
         }
     }
 
@@ -141,56 +150,41 @@ where
             }))
         }
 
-        // Buffer block responses
-        self.try_buffer_blocks(bodies)?;
-
-        // Submit next request if any
-        if let Some(req) = self.next_request() {
-            self.submit_request(req, Priority::High);
-        } else {
-            self.fut = None;
-        }
+        // Validate block responses, the next request is submitted once they're buffered
+        self.fut = None;
+        self.validate_blocks(peer_id, bodies);
 
         Ok(())
     }
 
-    /// Attempt to buffer body responses. Returns an error if body response fails validation.
-    /// Every body preceeding the failed one will be buffered.
+    /// Spawns the validation of the body responses on the rayon pool.
     ///
-    /// This method removes headers from the internal collection.
-    /// If the response fails validation, then the header will be put back.
-    fn try_buffer_blocks(&mut self, bodies: Vec<BlockBody>) -> DownloadResult<()> {
+    /// This method removes headers from the internal collection. Once the validation is finished,
+    /// the responses are buffered with [Self::on_validated].
+    fn validate_blocks(&mut self, peer_id: PeerId, bodies: Vec<BlockBody>) {
         let bodies_capacity = bodies.capacity();
         let bodies_len = bodies.len();
         let mut bodies = bodies.into_iter().peekable();
+        let mut responses = Vec::with_capacity(bodies_len);
 
         let mut total_size = bodies_capacity * mem::size_of::<BlockBody>();
         while bodies.peek().is_some() {
-            let next_header = match self.pending_headers.pop_front() {
-                Some(header) => header,
-                None => return Ok(()), // no more headers
+            let Some(next_header) = self.pending_headers.pop_front() else {
+                // no more headers
+                break
             };
 
             if next_header.is_empty() {
                 // increment empty block body metric
                 total_size += mem::size_of::<BlockBody>();
-                self.buffer.push(BlockResponse::Empty(next_header));
+                responses.push(BlockResponse::Empty(next_header));
             } else {
                 let next_body = bodies.next().unwrap();
 
                 // increment full block body metric
                 total_size += next_body.size();
 
-                let block = SealedBlock::new(next_header, next_body);
-
-                if let Err(error) = self.consensus.validate_block(&block) {
-                    // Body is invalid, put the header back and return an error
-                    let hash = block.hash();
-                    self.pending_headers.push_front(block.header);
-                    return Err(DownloadError::BodyValidation { hash, error: Box::new(error) })
-                }
-
-                self.buffer.push(BlockResponse::Full(block));
+                responses.push(BlockResponse::Full(SealedBlock::new(next_header, next_body)));
             }
         }
 
@@ -198,6 +192,49 @@ where
         self.response_metrics.response_size_bytes.set(total_size as f64);
         self.response_metrics.response_length.set(bodies_len as f64);
 
+        let consensus = Arc::clone(&self.consensus);
+        let (tx, rx) = oneshot::channel();
+        rayon::spawn(move || {
+            let invalid = responses.iter().enumerate().find_map(|(idx, response)| match response {
+                BlockResponse::Full(block) => {
+                    consensus.validate_block(block).err().map(|error| (idx, error))
+                }
+                BlockResponse::Empty(_) => None,
+            });
+            let _ = tx.send((responses, invalid));
+        });
+        self.validation = Some((peer_id, rx));
+    }
+
+    /// Buffers the validated body responses. Returns an error if a body failed validation.
+    /// Every body preceeding the failed one will be buffered.
+    ///
+    /// If the response failed validation, then the headers of the failed body and all following
+    /// ones will be put back. Otherwise, the next request is submitted.
+    fn on_validated(&mut self, outcome: ValidationOutcome) -> DownloadResult<()> {
+        let (mut responses, invalid) = outcome;
+
+        if let Some((idx, error)) = invalid {
+            // Body is invalid, put the headers back and return an error
+            let hash = responses[idx].header().hash();
+            for response in responses.drain(idx..).rev() {
+                let header = match response {
+                    BlockResponse::Full(block) => block.header,
+                    BlockResponse::Empty(header) => header,
+                };
+                self.pending_headers.push_front(header);
+            }
+            self.buffer.append(&mut responses);
+            return Err(DownloadError::BodyValidation { hash, error: Box::new(error) })
+        }
+
+        self.buffer.append(&mut responses);
+
+        // Submit next request if any
+        if let Some(req) = self.next_request() {
+            self.submit_request(req, Priority::High);
+        }
+
         Ok(())
     }
 }
@@ -212,6 +249,22 @@ where
         let this = self.get_mut();
 
         loop {
+            // Wait for the validation of the last response, the responses are buffered in order
+            if let Some((peer_id, validation)) = this.validation.as_mut() {
+                let peer_id = *peer_id;
+                let outcome = ready!(validation.poll_unpin(cx)).expect("validation task panicked");
+                this.validation = None;
+                if let Err(error) = this.on_validated(outcome) {
+                    this.on_error(error, Some(peer_id));
+                }
+            }
+
+            // Buffer any empty headers
+            while this.pending_headers.front().map(|h| h.is_empty()).unwrap_or_default() {
+                let header = this.pending_headers.pop_front().unwrap();
+                this.buffer.push(BlockResponse::Empty(header));
+            }
+
             if this.pending_headers.is_empty() {
                 return Poll::Ready(Ok(std::mem::take(&mut this.buffer)))
             }
@@ -235,12 +288,6 @@ where
                     }
                 }
             }
-
-            // Buffer any empty headers
-            while this.pending_headers.front().map(|h| h.is_empty()).unwrap_or_default() {
-                let header = this.pending_headers.pop_front().unwrap();
-                this.buffer.push(BlockResponse::Empty(header));
-            }
         }
     }
 }
@@ -304,4 +351,33 @@ mod tests {
             (headers.into_iter().filter(|h| !h.is_empty()).count() as u64 + 1) / 2
         );
     }
+
+    /// Check that the request future requests the bodies again if they fail validation
+    #[tokio::test]
+    async fn request_retries_invalid_bodies() {
+        let (headers, mut bodies) = generate_bodies(0..=19);
+
+        let client = Arc::new(TestBodiesClient::default().with_bodies(bodies.clone()));
+        let consensus = Arc::new(TestConsensus::default());
+        consensus.set_fail_validation(true);
+        let mut fut = BodiesRequestFuture::new(
+            client.clone(),
+            consensus.clone(),
+            BodyDownloaderMetrics::default(),
+        )
+        .with_headers(headers.clone());
+
+        std::future::poll_fn(|cx| {
+            assert!(fut.poll_unpin(cx).is_pending());
+            if client.times_requested() > 2 {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+
+        consensus.set_fail_validation(false);
+        assert_eq!(fut.await.unwrap(), zip_blocks(headers.iter(), &mut bodies));
+    }
 }
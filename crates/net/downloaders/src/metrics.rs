@@ -37,6 +37,8 @@ pub struct BodyDownloaderMetrics {
     pub buffered_blocks_size_bytes: Gauge,
     /// The number blocks that are contiguous and are queued for insertion into the db.
     pub queued_blocks: Gauge,
+    /// Total amount of memory used by the queued blocks in bytes
+    pub queued_blocks_size_bytes: Gauge,
     /// The number of out-of-order requests sent by the downloader.
     /// The consumer of the download stream is able to re-request data (bodies) in case
     /// it encountered a recoverable error (e.g. during insertion).
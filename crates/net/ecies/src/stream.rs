@@ -101,6 +101,11 @@ where
     pub fn remote_id(&self) -> PeerId {
         self.remote_id
     }
+
+    /// Returns a mutable reference to the underlying transport.
+    pub fn transport_mut(&mut self) -> &mut Io {
+        self.stream.get_mut()
+    }
 }
 
 impl<Io> Stream for ECIESStream<Io>
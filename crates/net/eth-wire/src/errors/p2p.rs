@@ -2,9 +2,7 @@
 
 use crate::{
     capability::SharedCapabilityError, disconnect::UnknownDisconnectReason, DisconnectReason,
-    ProtocolVersion,
 };
-use reth_primitives::GotExpected;
 use std::io;
 
 /// Errors when sending/receiving p2p messages. These should result in kicking the peer.
@@ -33,8 +31,6 @@ pub enum P2PStreamError {
     ParseSharedCapability(#[from] SharedCapabilityError),
     #[error("capability not supported on stream to this peer")]
     CapabilityNotShared,
-    #[error("mismatched protocol version in Hello message: {0}")]
-    MismatchedProtocolVersion(GotExpected<ProtocolVersion>),
     #[error("started ping task before the handshake completed")]
     PingBeforeHandshake,
     #[error("too many messages buffered before sending")]
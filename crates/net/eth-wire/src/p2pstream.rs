@@ -10,9 +10,10 @@ use futures::{Sink, SinkExt, StreamExt};
 use pin_project::pin_project;
 use reth_codecs::derive_arbitrary;
 use reth_metrics::metrics::counter;
+use reth_net_common::compression_meter::CompressionMeter;
 use reth_primitives::{
     bytes::{Buf, BufMut, Bytes, BytesMut},
-    hex, GotExpected,
+    hex,
 };
 use std::{
     collections::VecDeque,
@@ -148,14 +149,10 @@ where
             "validating incoming p2p hello from peer"
         );
 
-        if (hello.protocol_version as u8) != their_hello.protocol_version as u8 {
-            // send a disconnect message notifying the peer of the protocol version mismatch
-            self.send_disconnect(DisconnectReason::IncompatibleP2PProtocolVersion).await?;
-            return Err(P2PStreamError::MismatchedProtocolVersion(GotExpected {
-                got: their_hello.protocol_version,
-                expected: hello.protocol_version,
-            }))
-        }
+        // messages are only snappy compressed if both peers support `p2p` version 5, see
+        // <https://github.com/ethereum/devp2p/blob/master/rlpx.md#message-encoding>
+        let snappy = hello.protocol_version >= ProtocolVersion::V5 &&
+            their_hello.protocol_version >= ProtocolVersion::V5;
 
         // determine shared capabilities (currently returns only one capability)
         let capability_res =
@@ -170,7 +167,8 @@ where
             Ok(cap) => Ok(cap),
         }?;
 
-        let stream = P2PStream::new(self.inner, shared_capability);
+        let mut stream = P2PStream::new(self.inner, shared_capability);
+        stream.snappy = snappy;
 
         Ok((stream, their_hello))
     }
@@ -235,12 +233,18 @@ pub struct P2PStream<S> {
     #[pin]
     inner: S,
 
+    /// Whether messages are snappy compressed, which was negotiated during the handshake.
+    snappy: bool,
+
     /// The snappy encoder used for compressing outgoing messages
     encoder: snap::raw::Encoder,
 
     /// The snappy decoder used for decompressing incoming messages
     decoder: snap::raw::Decoder,
 
+    /// Meters the size of messages before and after compression.
+    compression_meter: CompressionMeter,
+
     /// The state machine used for keeping track of the peer's ping status.
     pinger: Pinger,
 
@@ -266,8 +270,10 @@ impl<S> P2PStream<S> {
     pub fn new(inner: S, shared_capabilities: SharedCapabilities) -> Self {
         Self {
             inner,
+            snappy: true,
             encoder: snap::raw::Encoder::new(),
             decoder: snap::raw::Decoder::new(),
+            compression_meter: CompressionMeter::default(),
             pinger: Pinger::new(PING_INTERVAL, PING_TIMEOUT),
             shared_capabilities,
             outgoing_messages: VecDeque::new(),
@@ -298,25 +304,76 @@ impl<S> P2PStream<S> {
         &self.shared_capabilities
     }
 
+    /// Returns `true` if messages are snappy compressed, which is the case if both peers support
+    /// `p2p` version 5.
+    pub fn is_snappy(&self) -> bool {
+        self.snappy
+    }
+
+    /// Returns the [`CompressionMeter`] that meters the messages of this stream.
+    pub fn compression_meter(&self) -> &CompressionMeter {
+        &self.compression_meter
+    }
+
+    /// Sets the [`CompressionMeter`] that meters the messages of this stream.
+    pub fn set_compression_meter(&mut self, compression_meter: CompressionMeter) {
+        self.compression_meter = compression_meter;
+    }
+
     /// Returns `true` if the stream has outgoing capacity.
     fn has_outgoing_capacity(&self) -> bool {
         self.outgoing_messages.len() < self.outgoing_message_buffer_capacity
     }
 
-    /// Queues in a _snappy_ encoded [`P2PMessage::Pong`] message.
+    /// Queues in a [`P2PMessage::Pong`] message.
     fn send_pong(&mut self) {
-        let pong = P2PMessage::Pong;
-        let mut pong_bytes = BytesMut::with_capacity(pong.length());
-        pong.encode(&mut pong_bytes);
-        self.outgoing_messages.push_back(pong_bytes.freeze());
+        self.send_ping_pong(P2PMessage::Pong)
     }
 
-    /// Queues in a _snappy_ encoded [`P2PMessage::Ping`] message.
+    /// Queues in a [`P2PMessage::Ping`] message.
     fn send_ping(&mut self) {
-        let ping = P2PMessage::Ping;
-        let mut ping_bytes = BytesMut::with_capacity(ping.length());
-        ping.encode(&mut ping_bytes);
-        self.outgoing_messages.push_back(ping_bytes.freeze());
+        self.send_ping_pong(P2PMessage::Ping)
+    }
+
+    /// Queues in a [`P2PMessage::Ping`] or [`P2PMessage::Pong`] message. The [`Encodable`]
+    /// implementation of these always prepends the _snappy_ header, so the uncompressed message is
+    /// encoded here.
+    fn send_ping_pong(&mut self, msg: P2PMessage) {
+        let bytes = if self.snappy {
+            let mut bytes = BytesMut::with_capacity(msg.length());
+            msg.encode(&mut bytes);
+            bytes.freeze()
+        } else {
+            Bytes::from(vec![msg.message_id() as u8, EMPTY_LIST_CODE])
+        };
+        self.outgoing_messages.push_back(bytes);
+    }
+
+    /// Compresses the payload of the message, which is everything after the message ID byte, if
+    /// snappy compression was negotiated.
+    fn compress(&mut self, msg: &[u8]) -> Result<BytesMut, snap::Error> {
+        if !self.snappy {
+            self.compression_meter.record_outbound(msg.len() - 1, msg.len() - 1);
+            return Ok(BytesMut::from(msg))
+        }
+
+        let mut compressed = BytesMut::zeroed(1 + snap::raw::max_compress_len(msg.len() - 1));
+        let compressed_size =
+            self.encoder.compress(&msg[1..], &mut compressed[1..]).map_err(|err| {
+                debug!(
+                    ?err,
+                    msg=%hex::encode(&msg[1..]),
+                    "error compressing p2p message"
+                );
+                err
+            })?;
+        self.compression_meter.record_outbound(compressed_size, msg.len() - 1);
+
+        // truncate the compressed buffer to the actual compressed size (plus one for the message
+        // id)
+        compressed.truncate(compressed_size + 1);
+        compressed[0] = msg[0];
+        Ok(compressed)
     }
 }
 
@@ -346,24 +403,9 @@ impl<S> DisconnectP2P for P2PStream<S> {
         let mut buf = BytesMut::with_capacity(disconnect.length());
         disconnect.encode(&mut buf);
 
-        let mut compressed = BytesMut::zeroed(1 + snap::raw::max_compress_len(buf.len() - 1));
-        let compressed_size =
-            self.encoder.compress(&buf[1..], &mut compressed[1..]).map_err(|err| {
-                debug!(
-                    ?err,
-                    msg=%hex::encode(&buf[1..]),
-                    "error compressing disconnect"
-                );
-                err
-            })?;
-
-        // truncate the compressed buffer to the actual compressed size (plus one for the message
-        // id)
-        compressed.truncate(compressed_size + 1);
-
         // we do not add the capability offset because the disconnect message is a `p2p` reserved
         // message
-        compressed[0] = buf[0];
+        let compressed = self.compress(&buf)?;
 
         self.outgoing_messages.push_back(compressed.freeze());
         self.disconnecting = true;
@@ -419,32 +461,40 @@ where
                 return Poll::Ready(Some(Err(P2PStreamError::EmptyProtocolMessage)))
             }
 
-            // first check that the compressed message length does not exceed the max
+            // first check that the decompressed message length does not exceed the max
             // payload size
-            let decompressed_len = snap::raw::decompress_len(&bytes[1..])?;
+            let decompressed_len =
+                if this.snappy { snap::raw::decompress_len(&bytes[1..])? } else { bytes.len() - 1 };
             if decompressed_len > MAX_PAYLOAD_SIZE {
                 return Poll::Ready(Some(Err(P2PStreamError::MessageTooBig {
                     message_size: decompressed_len,
                     max_size: MAX_PAYLOAD_SIZE,
                 })))
             }
+            this.compression_meter.record_inbound(bytes.len() - 1, decompressed_len);
 
-            // create a buffer to hold the decompressed message, adding a byte to the length for
-            // the message ID byte, which is the first byte in this buffer
-            let mut decompress_buf = BytesMut::zeroed(decompressed_len + 1);
+            let id = bytes[0];
 
-            // each message following a successful handshake is compressed with snappy, so we need
-            // to decompress the message before we can decode it.
-            this.decoder.decompress(&bytes[1..], &mut decompress_buf[1..]).map_err(|err| {
-                debug!(
-                    ?err,
-                    msg=%hex::encode(&bytes[1..]),
-                    "error decompressing p2p message"
-                );
-                err
-            })?;
+            let mut decompress_buf = if this.snappy {
+                // create a buffer to hold the decompressed message, adding a byte to the length
+                // for the message ID byte, which is the first byte in this buffer
+                let mut decompress_buf = BytesMut::zeroed(decompressed_len + 1);
+
+                // each message following a successful handshake is compressed with snappy, so we
+                // need to decompress the message before we can decode it.
+                this.decoder.decompress(&bytes[1..], &mut decompress_buf[1..]).map_err(|err| {
+                    debug!(
+                        ?err,
+                        msg=%hex::encode(&bytes[1..]),
+                        "error decompressing p2p message"
+                    );
+                    err
+                })?;
+                decompress_buf
+            } else {
+                bytes
+            };
 
-            let id = bytes[0];
             match id {
                 _ if id == P2PMessageID::Ping as u8 => {
                     trace!("Received Ping, Sending Pong");
@@ -501,7 +551,7 @@ where
                     //  * `eth/67` is reserved message IDs 0x10 - 0x19.
                     //  * `qrs/65` is reserved message IDs 0x1a - 0x21.
                     //
-                    decompress_buf[0] = bytes[0] - MAX_RESERVED_MESSAGE_ID - 1;
+                    decompress_buf[0] = id - MAX_RESERVED_MESSAGE_ID - 1;
 
                     return Poll::Ready(Some(Ok(decompress_buf)))
                 }
@@ -561,22 +611,9 @@ where
             return Err(P2PStreamError::SendBufferFull)
         }
 
-        let this = self.project();
-
-        let mut compressed = BytesMut::zeroed(1 + snap::raw::max_compress_len(item.len() - 1));
-        let compressed_size =
-            this.encoder.compress(&item[1..], &mut compressed[1..]).map_err(|err| {
-                debug!(
-                    ?err,
-                    msg=%hex::encode(&item[1..]),
-                    "error compressing p2p message"
-                );
-                err
-            })?;
+        let this = self.get_mut();
 
-        // truncate the compressed buffer to the actual compressed size (plus one for the message
-        // id)
-        compressed.truncate(compressed_size + 1);
+        let mut compressed = this.compress(&item)?;
 
         // all messages sent in this stream are subprotocol messages, so we need to switch the
         // message id based on the offset
@@ -762,7 +799,7 @@ impl TryFrom<u8> for P2PMessageID {
 
 /// RLPx `p2p` protocol version
 #[derive_arbitrary(rlp)]
-#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ProtocolVersion {
     /// `p2p` version 4
@@ -895,32 +932,26 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_handshake_disconnect() {
-        // create a p2p stream and server, then confirm that the two are authed
-        // create tcpstream
+    async fn test_handshake_without_snappy() {
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let local_addr = listener.local_addr().unwrap();
 
         let handle = tokio::spawn(Box::pin(async move {
-            // roughly based off of the design of tokio::net::TcpListener
             let (incoming, _) = listener.accept().await.unwrap();
             let stream = crate::PassthroughCodec::default().framed(incoming);
 
             let (server_hello, _) = eth_hello();
+            assert_eq!(server_hello.protocol_version, ProtocolVersion::V5);
 
             let unauthed_stream = UnauthedP2PStream::new(stream);
-            match unauthed_stream.handshake(server_hello.clone()).await {
-                Ok((_, hello)) => {
-                    panic!("expected handshake to fail, instead got a successful Hello: {hello:?}")
-                }
-                Err(P2PStreamError::MismatchedProtocolVersion(GotExpected { got, expected })) => {
-                    assert_ne!(expected, got);
-                    assert_eq!(expected, server_hello.protocol_version);
-                }
-                Err(other_err) => {
-                    panic!("expected mismatched protocol version error, got {other_err:?}")
-                }
-            }
+            let (mut p2p_stream, hello) = unauthed_stream.handshake(server_hello).await.unwrap();
+            assert_eq!(hello.protocol_version, ProtocolVersion::V4);
+            assert!(!p2p_stream.is_snappy());
+
+            let msg = p2p_stream.next().await.unwrap().unwrap();
+            assert_eq!(&msg[..], &[0x00, 0x01, 0x02][..]);
+            assert_eq!(p2p_stream.compression_meter().total_inbound_compressed(), 2);
+            assert_eq!(p2p_stream.compression_meter().total_inbound_raw(), 2);
         }));
 
         let outgoing = TcpStream::connect(local_addr).await.unwrap();
@@ -928,22 +959,15 @@ mod tests {
 
         let (mut client_hello, _) = eth_hello();
 
-        // modify the hello to include an incompatible p2p protocol version
+        // messages are only compressed if both peers support p2p v5
         client_hello.protocol_version = ProtocolVersion::V4;
 
         let unauthed_stream = UnauthedP2PStream::new(sink);
-        match unauthed_stream.handshake(client_hello.clone()).await {
-            Ok((_, hello)) => {
-                panic!("expected handshake to fail, instead got a successful Hello: {hello:?}")
-            }
-            Err(P2PStreamError::MismatchedProtocolVersion(GotExpected { got, expected })) => {
-                assert_ne!(expected, got);
-                assert_eq!(expected, client_hello.protocol_version);
-            }
-            Err(other_err) => {
-                panic!("expected mismatched protocol version error, got {other_err:?}")
-            }
-        }
+        let (mut p2p_stream, _) = unauthed_stream.handshake(client_hello).await.unwrap();
+        assert!(!p2p_stream.is_snappy());
+
+        p2p_stream.send(Bytes::from_static(&[0x00, 0x01, 0x02])).await.unwrap();
+        assert_eq!(p2p_stream.compression_meter().compression_ratio(), 1.0);
 
         // make sure the server receives the message and asserts before ending the test
         handle.await.unwrap();
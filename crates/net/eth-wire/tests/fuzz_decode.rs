@@ -0,0 +1,81 @@
+//! Decoding fuzzing for the `eth-wire` crate.
+//!
+//! Messages are sent by untrusted peers, so malformed input must be rejected with an error instead
+//! of panicking. Messages captured on mainnet are used as seeds, mutating them reaches much deeper
+//! into the decoders than random bytes.
+
+use alloy_rlp::Decodable;
+use proptest::{prelude::*, sample::Index};
+use reth_eth_wire::{EthMessageID, EthVersion, P2PMessage, ProtocolMessage};
+use reth_primitives::hex;
+use std::{fs, path::PathBuf, sync::OnceLock};
+use test_fuzz::test_fuzz;
+
+/// Decodes the bytes as an `eth` message of every version and as a `p2p` message.
+#[test_fuzz]
+fn decode_message(data: Vec<u8>) {
+    for version in [EthVersion::Eth66, EthVersion::Eth67, EthVersion::Eth68] {
+        let _ = ProtocolMessage::decode_message(version, &mut &data[..]);
+    }
+    let _ = P2PMessage::decode(&mut &data[..]);
+}
+
+/// Returns the messages captured on mainnet, prefixed with their message id.
+fn mainnet_seeds() -> &'static [Vec<u8>] {
+    static SEEDS: OnceLock<Vec<Vec<u8>>> = OnceLock::new();
+    SEEDS.get_or_init(|| {
+        [
+            (EthMessageID::NewBlock, "testdata/new_block_network_rlp"),
+            (
+                EthMessageID::NewPooledTransactionHashes,
+                "testdata/new_pooled_transactions_network_rlp",
+            ),
+        ]
+        .into_iter()
+        .map(|(id, path)| {
+            let network_data_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(path);
+            let data = fs::read_to_string(network_data_path).expect("Unable to read file");
+            let mut message = vec![id as u8];
+            message.extend(hex::decode(data.trim()).unwrap());
+            message
+        })
+        .collect()
+    })
+}
+
+#[test]
+fn decode_mainnet_seeds() {
+    for seed in mainnet_seeds() {
+        ProtocolMessage::decode_message(EthVersion::Eth66, &mut &seed[..]).unwrap();
+        // records the seed in the corpus of the fuzz target
+        decode_message(seed.clone());
+    }
+}
+
+proptest! {
+    #[test]
+    fn decode_random_message(
+        id in 0u8..=0x10,
+        payload in proptest::collection::vec(any::<u8>(), 0..1024),
+    ) {
+        let mut data = vec![id];
+        data.extend(payload);
+        decode_message(data);
+    }
+
+    #[test]
+    fn decode_mutated_mainnet_message(
+        seed in any::<Index>(),
+        truncate in any::<Index>(),
+        mutations in proptest::collection::vec(any::<(Index, u8)>(), 0..8),
+    ) {
+        let seeds = mainnet_seeds();
+        let mut data = seeds[seed.index(seeds.len())].clone();
+        for (index, byte) in mutations {
+            let index = index.index(data.len());
+            data[index] = byte;
+        }
+        data.truncate(truncate.index(data.len() + 1));
+        decode_message(data);
+    }
+}
@@ -0,0 +1,33 @@
+use reth_eth_wire::EthMessageID;
+use std::time::Duration;
+
+/// Summary of the latencies of the requests of a single message type sent to a peer, measured from
+/// sending the request until receiving the response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestLatency {
+    /// Number of responses received.
+    pub count: u64,
+    /// Mean latency of the responses.
+    pub mean: Duration,
+    /// Upper bound of the median latency.
+    pub p50: Duration,
+    /// Upper bound of the 95th percentile latency.
+    pub p95: Duration,
+    /// Maximum latency of the responses.
+    pub max: Duration,
+}
+
+/// Summary of the latencies of the requests sent to a peer, by message type of the request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeerLatency {
+    /// The request message types the peer responded to, with their latencies.
+    pub requests: Vec<(EthMessageID, RequestLatency)>,
+}
+
+impl PeerLatency {
+    /// Returns the latency of the requests with the given message type, if the peer responded to
+    /// any.
+    pub fn get(&self, id: EthMessageID) -> Option<&RequestLatency> {
+        self.requests.iter().find(|(request, _)| *request == id).map(|(_, latency)| latency)
+    }
+}
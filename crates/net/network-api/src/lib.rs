@@ -22,11 +22,14 @@ use reth_rpc_types::NetworkStatus;
 use std::{net::SocketAddr, sync::Arc, time::Instant};
 
 pub use error::NetworkError;
+pub use latency::{PeerLatency, RequestLatency};
 pub use reputation::{Reputation, ReputationChangeKind};
 use reth_eth_wire::capability::Capabilities;
 
 /// Network Error
 pub mod error;
+/// Request latencies of peers
+pub mod latency;
 /// Reputation score
 pub mod reputation;
 
@@ -157,6 +160,8 @@ pub struct PeerInfo {
     pub status: Arc<Status>,
     /// The timestamp when the session to that peer has been established.
     pub session_established: Instant,
+    /// The latencies of the requests sent to the peer.
+    pub latency: PeerLatency,
 }
 
 /// The direction of the connection.
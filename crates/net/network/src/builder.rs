@@ -1,8 +1,8 @@
 //! Builder support for configuring the entire setup.
 
 use crate::{
-    eth_requests::EthRequestHandler, transactions::TransactionsManager, NetworkHandle,
-    NetworkManager,
+    eth_requests::EthRequestHandler, protocol::IntoRlpxSubProtocol,
+    transactions::TransactionsManager, NetworkHandle, NetworkManager,
 };
 use reth_transaction_pool::TransactionPool;
 use tokio::sync::mpsc;
@@ -50,6 +50,17 @@ impl<C, Tx, Eth> NetworkBuilder<C, Tx, Eth> {
         (handle, network, transactions, request_handler)
     }
 
+    /// Adds an additional RLPx sub-protocol that is negotiated alongside `eth` with every peer that
+    /// also supports it.
+    ///
+    /// The protocol rides on the discovery, handshake and session management of the network, see
+    /// [`ProtocolHandler`](crate::protocol::ProtocolHandler) for how the connections of the
+    /// protocol are handled.
+    pub fn add_protocol(mut self, protocol: impl IntoRlpxSubProtocol) -> Self {
+        self.network.add_rlpx_sub_protocol(protocol);
+        self
+    }
+
     /// Creates a new [`TransactionsManager`] and wires it to the network.
     pub fn transactions<Pool: TransactionPool>(
         self,
@@ -5,14 +5,16 @@ use crate::{
     import::{BlockImport, ProofOfStakeBlockImport},
     peers::PeersConfig,
     session::SessionsConfig,
+    transport::{TcpTransport, Transport},
     NetworkHandle, NetworkManager,
 };
 use reth_discv4::{Discv4Config, Discv4ConfigBuilder, DEFAULT_DISCOVERY_ADDRESS};
 use reth_dns_discovery::DnsDiscoveryConfig;
 use reth_ecies::util::pk2id;
-use reth_eth_wire::{HelloMessage, HelloMessageWithProtocols, Status};
+use reth_eth_wire::{HelloMessage, HelloMessageWithProtocols, ProtocolVersion, Status};
 use reth_primitives::{
-    mainnet_nodes, sepolia_nodes, ChainSpec, ForkFilter, Head, NodeRecord, PeerId, MAINNET,
+    holesky_nodes, mainnet_nodes, sepolia_nodes, ChainSpec, ForkFilter, Head, NodeRecord, PeerId,
+    MAINNET,
 };
 use reth_provider::{BlockReader, HeaderProvider};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
@@ -74,6 +76,8 @@ pub struct NetworkConfig<C> {
     pub extra_protocols: RlpxSubProtocols,
     /// Whether to disable transaction gossip
     pub tx_gossip_disabled: bool,
+    /// The transport over which connections to peers are established.
+    pub transport: Box<dyn Transport>,
     /// Optimism Network Config
     #[cfg(feature = "optimism")]
     pub optimism_network_config: OptimismNetworkConfig,
@@ -169,9 +173,14 @@ pub struct NetworkConfigBuilder {
     head: Option<Head>,
     /// Whether tx gossip is disabled
     tx_gossip_disabled: bool,
+    /// Whether snappy compression of messages is disabled
+    snappy_disabled: bool,
     /// The block importer type
     #[serde(skip)]
     block_import: Option<Box<dyn BlockImport>>,
+    /// The transport over which connections to peers are established
+    #[serde(skip)]
+    transport: Option<Box<dyn Transport>>,
     /// Optimism Network Config Builder
     #[cfg(feature = "optimism")]
     optimism_network_config: OptimismNetworkConfigBuilder,
@@ -207,7 +216,9 @@ impl NetworkConfigBuilder {
             extra_protocols: Default::default(),
             head: None,
             tx_gossip_disabled: false,
+            snappy_disabled: false,
             block_import: None,
+            transport: None,
             #[cfg(feature = "optimism")]
             optimism_network_config: OptimismNetworkConfigBuilder::default(),
         }
@@ -338,6 +349,11 @@ impl NetworkConfigBuilder {
         self.boot_nodes(sepolia_nodes())
     }
 
+    /// Convenience function for setting [Self::boot_nodes] to the holesky boot nodes.
+    pub fn holesky_boot_nodes(self) -> Self {
+        self.boot_nodes(holesky_nodes())
+    }
+
     /// Sets the boot nodes.
     pub fn boot_nodes(mut self, nodes: impl IntoIterator<Item = NodeRecord>) -> Self {
         self.boot_nodes = nodes.into_iter().collect();
@@ -400,12 +416,30 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Sets whether snappy compression of messages is disabled.
+    ///
+    /// Messages are only compressed if both peers support `p2p` version 5, so this announces
+    /// version 4 in the hello message. This is intended for testing on local networks, where the
+    /// uncompressed messages are easier to inspect.
+    pub fn disable_snappy(mut self, disable_snappy: bool) -> Self {
+        self.snappy_disabled = disable_snappy;
+        self
+    }
+
     /// Sets the block import type.
     pub fn block_import(mut self, block_import: Box<dyn BlockImport>) -> Self {
         self.block_import = Some(block_import);
         self
     }
 
+    /// Sets the transport over which connections to peers are established.
+    ///
+    /// Defaults to [`TcpTransport`].
+    pub fn transport(mut self, transport: Box<dyn Transport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
     /// Sets the sequencer HTTP endpoint.
     #[cfg(feature = "optimism")]
     pub fn sequencer_endpoint(mut self, endpoint: Option<String>) -> Self {
@@ -437,7 +471,9 @@ impl NetworkConfigBuilder {
             extra_protocols,
             head,
             tx_gossip_disabled,
+            snappy_disabled,
             block_import,
+            transport,
             #[cfg(feature = "optimism")]
                 optimism_network_config: OptimismNetworkConfigBuilder { sequencer_endpoint },
         } = self;
@@ -447,6 +483,9 @@ impl NetworkConfigBuilder {
         let mut hello_message =
             hello_message.unwrap_or_else(|| HelloMessage::builder(peer_id).build());
         hello_message.port = listener_addr.port();
+        if snappy_disabled {
+            hello_message.protocol_version = ProtocolVersion::V4;
+        }
 
         let head = head.unwrap_or(Head {
             hash: chain_spec.genesis_hash(),
@@ -492,6 +531,7 @@ impl NetworkConfigBuilder {
             extra_protocols,
             fork_filter,
             tx_gossip_disabled,
+            transport: transport.unwrap_or_else(|| Box::<TcpTransport>::default()),
             #[cfg(feature = "optimism")]
             optimism_network_config: OptimismNetworkConfig { sequencer_endpoint },
         }
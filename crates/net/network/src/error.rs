@@ -160,8 +160,7 @@ impl SessionError for EthStreamError {
                         P2PStreamError::Disconnected(
                             DisconnectReason::IncompatibleP2PProtocolVersion
                         ) |
-                        P2PStreamError::Disconnected(DisconnectReason::ProtocolBreach) |
-                        P2PStreamError::MismatchedProtocolVersion { .. }
+                        P2PStreamError::Disconnected(DisconnectReason::ProtocolBreach)
                 )
             }
             EthStreamError::EthHandshakeError(err) => !matches!(err, EthHandshakeError::NoResponse),
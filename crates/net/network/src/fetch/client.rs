@@ -61,7 +61,7 @@ impl HeadersClient for FetchClient {
         let (response, rx) = oneshot::channel();
         if self
             .request_tx
-            .send(DownloadRequest::GetBlockHeaders { request, response, priority })
+            .send(DownloadRequest::GetBlockHeaders { request, response, priority, retries: 0 })
             .is_ok()
         {
             Either::Left(FlattenedResponse::from(rx))
@@ -83,7 +83,7 @@ impl BodiesClient for FetchClient {
         let (response, rx) = oneshot::channel();
         if self
             .request_tx
-            .send(DownloadRequest::GetBlockBodies { request, response, priority })
+            .send(DownloadRequest::GetBlockBodies { request, response, priority, retries: 0 })
             .is_ok()
         {
             Box::pin(FlattenedResponse::from(rx))
@@ -1,15 +1,15 @@
 //! Fetch data from the network.
 
-use crate::{message::BlockRequest, peers::PeersHandle};
+use crate::{message::BlockRequest, peers::PeersHandle, session::PeerLatencyTracker};
 use futures::StreamExt;
-use reth_eth_wire::{GetBlockBodies, GetBlockHeaders};
+use reth_eth_wire::{capability::Capabilities, EthMessageID, GetBlockBodies, GetBlockHeaders};
 use reth_interfaces::p2p::{
     error::{EthResponseValidator, PeerRequestResult, RequestError, RequestResult},
     headers::client::HeadersRequest,
     priority::Priority,
 };
 use reth_network_api::ReputationChangeKind;
-use reth_primitives::{BlockBody, Header, PeerId, B256};
+use reth_primitives::{BlockBody, BlockHashOrNumber, Header, PeerId, B256};
 use std::{
     collections::{HashMap, VecDeque},
     sync::{
@@ -24,12 +24,19 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 mod client;
 pub use client::FetchClient;
 
+/// The number of times an inflight request is requeued when the session of its peer closes,
+/// before it fails.
+const MAX_REQUEST_RETRIES: u8 = 3;
+
 /// Manages data fetching operations.
 ///
 /// This type is hooked into the staged sync pipeline and delegates download request to available
 /// peers and sends the response once ready.
 ///
-/// This type maintains a list of connected peers that are available for requests.
+/// This type maintains a list of connected peers that are available for requests, together with
+/// the capabilities and the best block they advertised. Requests are only assigned to peers that
+/// can serve them, see [`Peer::can_serve`], and preferably to peers that have the requested
+/// block, see [`Peer::has_block`].
 #[derive(Debug)]
 pub struct StateFetcher {
     /// Currently active [`GetBlockHeaders`] requests
@@ -75,10 +82,14 @@ impl StateFetcher {
         peer_id: PeerId,
         best_hash: B256,
         best_number: u64,
+        capabilities: Arc<Capabilities>,
         timeout: Arc<AtomicU64>,
+        latency: Arc<PeerLatencyTracker>,
     ) {
-        self.peers
-            .insert(peer_id, Peer { state: PeerState::Idle, best_hash, best_number, timeout });
+        self.peers.insert(
+            peer_id,
+            Peer { state: PeerState::Idle, best_hash, best_number, capabilities, timeout, latency },
+        );
     }
 
     /// Removes the peer from the peer list, after which it is no longer available for future
@@ -86,14 +97,39 @@ impl StateFetcher {
     ///
     /// Invoked when an active session was closed.
     ///
-    /// This cancels also inflight request and sends an error to the receiver.
+    /// Inflight requests of the peer are moved back to the front of the queue, so they are
+    /// reassigned to the next available peer. Requests the receiver is no longer interested in
+    /// are dropped, and requests that were already requeued [`MAX_REQUEST_RETRIES`] times fail
+    /// with [`RequestError::ConnectionDropped`].
     pub(crate) fn on_session_closed(&mut self, peer: &PeerId) {
         self.peers.remove(peer);
-        if let Some(req) = self.inflight_headers_requests.remove(peer) {
-            let _ = req.response.send(Err(RequestError::ConnectionDropped));
+        if let Some(Request { request, response, priority, retries }) =
+            self.inflight_headers_requests.remove(peer)
+        {
+            if retries >= MAX_REQUEST_RETRIES {
+                let _ = response.send(Err(RequestError::ConnectionDropped));
+            } else if !response.is_closed() {
+                self.queued_requests.push_front(DownloadRequest::GetBlockHeaders {
+                    request,
+                    response,
+                    priority,
+                    retries: retries + 1,
+                });
+            }
         }
-        if let Some(req) = self.inflight_bodies_requests.remove(peer) {
-            let _ = req.response.send(Err(RequestError::ConnectionDropped));
+        if let Some(Request { request, response, priority, retries }) =
+            self.inflight_bodies_requests.remove(peer)
+        {
+            if retries >= MAX_REQUEST_RETRIES {
+                let _ = response.send(Err(RequestError::ConnectionDropped));
+            } else if !response.is_closed() {
+                self.queued_requests.push_front(DownloadRequest::GetBlockBodies {
+                    request,
+                    response,
+                    priority,
+                    retries: retries + 1,
+                });
+            }
         }
     }
 
@@ -118,14 +154,25 @@ impl StateFetcher {
         }
     }
 
-    /// Returns the _next_ idle peer that's ready to accept a request,
-    /// prioritizing those with the lowest timeout/latency.
-    /// Once a peer has been yielded, it will be moved to the end of the map
-    fn next_peer(&mut self) -> Option<PeerId> {
+    /// Returns the _next_ idle peer that's ready to accept the given request,
+    /// prioritizing those with the lowest latency for the request type, or the lowest timeout if
+    /// the peer hasn't responded to such a request yet.
+    ///
+    /// Peers that can't serve the request are skipped, see [`Peer::can_serve`]. Peers that have
+    /// the requested block are preferred, but since the best block of a peer is not updated by
+    /// block announcements after the merge, any other peer is used if none of them has it.
+    fn next_peer(&self, request: &DownloadRequest) -> Option<PeerId> {
+        let message_id = request.message_id();
         self.peers
             .iter()
-            .filter(|(_, peer)| peer.state.is_idle())
-            .min_by_key(|(_, peer)| peer.timeout())
+            .filter(|(_, peer)| peer.state.is_idle() && peer.can_serve())
+            .min_by_key(|(_, peer)| {
+                let latency = peer
+                    .latency
+                    .mean(message_id)
+                    .map_or_else(|| peer.timeout(), |latency| latency.as_millis() as u64);
+                (!peer.has_block(request), latency)
+            })
             .map(|(id, _)| *id)
     }
 
@@ -136,9 +183,17 @@ impl StateFetcher {
             return PollAction::NoRequests
         }
 
-        let Some(peer_id) = self.next_peer() else { return PollAction::NoPeersAvailable };
+        // assign the first queued request that any of the idle peers can serve
+        let Some((idx, peer_id)) = self
+            .queued_requests
+            .iter()
+            .enumerate()
+            .find_map(|(idx, req)| Some((idx, self.next_peer(req)?)))
+        else {
+            return PollAction::NoPeersAvailable
+        };
 
-        let request = self.queued_requests.pop_front().expect("not empty; qed");
+        let request = self.queued_requests.remove(idx).expect("exists; qed");
         let request = self.prepare_block_request(peer_id, request);
 
         PollAction::Ready(FetchAction::BlockRequest { peer_id, request })
@@ -195,8 +250,8 @@ impl StateFetcher {
         }
 
         match req {
-            DownloadRequest::GetBlockHeaders { request, response, .. } => {
-                let inflight = Request { request: request.clone(), response };
+            DownloadRequest::GetBlockHeaders { request, response, priority, retries } => {
+                let inflight = Request { request: request.clone(), response, priority, retries };
                 self.inflight_headers_requests.insert(peer_id, inflight);
                 let HeadersRequest { start, limit, direction } = request;
                 BlockRequest::GetBlockHeaders(GetBlockHeaders {
@@ -206,19 +261,22 @@ impl StateFetcher {
                     direction,
                 })
             }
-            DownloadRequest::GetBlockBodies { request, response, .. } => {
-                let inflight = Request { request: request.clone(), response };
+            DownloadRequest::GetBlockBodies { request, response, priority, retries } => {
+                let inflight = Request { request: request.clone(), response, priority, retries };
                 self.inflight_bodies_requests.insert(peer_id, inflight);
                 BlockRequest::GetBlockBodies(GetBlockBodies(request))
             }
         }
     }
 
-    /// Returns a new followup request for the peer.
+    /// Returns a new followup request for the peer, which is the first queued request the peer has
+    /// the block of, or else the first queued request.
     ///
     /// Caution: this expects that the peer is _not_ closed.
     fn followup_request(&mut self, peer_id: PeerId) -> Option<BlockResponseOutcome> {
-        let req = self.queued_requests.pop_front()?;
+        let peer = self.peers.get(&peer_id).filter(|peer| peer.can_serve())?;
+        let idx = self.queued_requests.iter().position(|req| peer.has_block(req)).unwrap_or(0);
+        let req = self.queued_requests.remove(idx)?;
         let req = self.prepare_block_request(peer_id, req);
         Some(BlockResponseOutcome::Request(peer_id, req))
     }
@@ -304,15 +362,43 @@ struct Peer {
     /// Best known hash that the peer has
     best_hash: B256,
     /// Tracks the best number of the peer.
+    ///
+    /// This is `0` if the peer's best hash is unknown to us, which usually means the peer is
+    /// ahead.
     best_number: u64,
+    /// The capabilities the peer advertised in the handshake.
+    capabilities: Arc<Capabilities>,
     /// Tracks the current timeout value we use for the peer.
     timeout: Arc<AtomicU64>,
+    /// Tracks the latencies of the requests sent to the peer.
+    latency: Arc<PeerLatencyTracker>,
 }
 
 impl Peer {
     fn timeout(&self) -> u64 {
         self.timeout.load(Ordering::Relaxed)
     }
+
+    /// Returns `true` if the peer is able to serve requests, which requires the `eth` capability.
+    fn can_serve(&self) -> bool {
+        self.capabilities.supports_eth()
+    }
+
+    /// Returns `true` if the peer is known to have the requested block.
+    ///
+    /// For header requests by number this requires that the first requested header is not beyond
+    /// the best block of the peer, if known.
+    fn has_block(&self, request: &DownloadRequest) -> bool {
+        match request {
+            DownloadRequest::GetBlockHeaders { request, .. } => match request.start {
+                BlockHashOrNumber::Number(number) => {
+                    self.best_number == 0 || number <= self.best_number
+                }
+                BlockHashOrNumber::Hash(_) => true,
+            },
+            DownloadRequest::GetBlockBodies { .. } => true,
+        }
+    }
 }
 
 /// Tracks the state of an individual peer
@@ -355,10 +441,12 @@ impl PeerState {
 #[derive(Debug)]
 struct Request<Req, Resp> {
     /// The issued request object
-    // TODO: this can be attached to the response in error case
-    #[allow(dead_code)]
     request: Req,
     response: oneshot::Sender<Resp>,
+    /// The priority of the request, kept so the request can be requeued.
+    priority: Priority,
+    /// The number of times the request was requeued because the session of its peer closed.
+    retries: u8,
 }
 
 /// Requests that can be sent to the Syncer from a [`FetchClient`]
@@ -369,12 +457,14 @@ pub(crate) enum DownloadRequest {
         request: HeadersRequest,
         response: oneshot::Sender<PeerRequestResult<Vec<Header>>>,
         priority: Priority,
+        retries: u8,
     },
     /// Download the requested headers and send response through channel
     GetBlockBodies {
         request: Vec<B256>,
         response: oneshot::Sender<PeerRequestResult<Vec<BlockBody>>>,
         priority: Priority,
+        retries: u8,
     },
 }
 
@@ -389,6 +479,14 @@ impl DownloadRequest {
         }
     }
 
+    /// Returns the message id of the request that's sent to the peer.
+    fn message_id(&self) -> EthMessageID {
+        match self {
+            DownloadRequest::GetBlockHeaders { .. } => EthMessageID::GetBlockHeaders,
+            DownloadRequest::GetBlockBodies { .. } => EthMessageID::GetBlockBodies,
+        }
+    }
+
     /// Returns the requested priority of this request
     fn get_priority(&self) -> &Priority {
         match self {
@@ -429,8 +527,27 @@ pub(crate) enum BlockResponseOutcome {
 mod tests {
     use super::*;
     use crate::{peers::PeersManager, PeersConfig};
+    use reth_eth_wire::{capability::Capability, EthVersion};
     use reth_primitives::{SealedHeader, B256, B512};
-    use std::future::poll_fn;
+    use std::{future::poll_fn, time::Duration};
+
+    fn capabilities() -> Arc<Capabilities> {
+        Arc::new(vec![Capability::from(EthVersion::Eth67)].into())
+    }
+
+    fn headers_request(start: u64) -> DownloadRequest {
+        let (tx, _rx) = oneshot::channel();
+        DownloadRequest::GetBlockHeaders {
+            request: HeadersRequest {
+                start: start.into(),
+                limit: 1,
+                direction: Default::default(),
+            },
+            response: tx,
+            priority: Priority::default(),
+            retries: 0,
+        }
+    }
 
     #[tokio::test(flavor = "multi_thread")]
     async fn test_poll_fetcher() {
@@ -444,6 +561,7 @@ mod tests {
                 request: vec![],
                 response: tx,
                 priority: Priority::default(),
+                retries: 0,
             });
             assert!(fetcher.poll(cx).is_pending());
 
@@ -459,20 +577,34 @@ mod tests {
         // Add a few random peers
         let peer1 = B512::random();
         let peer2 = B512::random();
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(1)));
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(1)));
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            1,
+            capabilities(),
+            Arc::new(AtomicU64::new(1)),
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer2,
+            B256::random(),
+            2,
+            capabilities(),
+            Arc::new(AtomicU64::new(1)),
+            Default::default(),
+        );
 
-        let first_peer = fetcher.next_peer().unwrap();
+        let first_peer = fetcher.next_peer(&headers_request(1)).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         // Pending disconnect for first_peer
         fetcher.on_pending_disconnect(&first_peer);
         // first_peer now isn't idle, so we should get other peer
-        let second_peer = fetcher.next_peer().unwrap();
+        let second_peer = fetcher.next_peer(&headers_request(1)).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         assert_ne!(first_peer, second_peer);
         // without idle peers, returns None
         fetcher.on_pending_disconnect(&second_peer);
-        assert_eq!(fetcher.next_peer(), None);
+        assert_eq!(fetcher.next_peer(&headers_request(1)), None);
     }
 
     #[tokio::test]
@@ -486,18 +618,229 @@ mod tests {
 
         let peer2_timeout = Arc::new(AtomicU64::new(300));
 
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(30)));
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::clone(&peer2_timeout));
-        fetcher.new_active_peer(peer3, B256::random(), 3, Arc::new(AtomicU64::new(50)));
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            1,
+            capabilities(),
+            Arc::new(AtomicU64::new(30)),
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer2,
+            B256::random(),
+            2,
+            capabilities(),
+            Arc::clone(&peer2_timeout),
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer3,
+            B256::random(),
+            3,
+            capabilities(),
+            Arc::new(AtomicU64::new(50)),
+            Default::default(),
+        );
 
         // Must always get peer1 (lowest timeout)
-        assert_eq!(fetcher.next_peer(), Some(peer1));
-        assert_eq!(fetcher.next_peer(), Some(peer1));
+        assert_eq!(fetcher.next_peer(&headers_request(1)), Some(peer1));
+        assert_eq!(fetcher.next_peer(&headers_request(1)), Some(peer1));
         // peer2's timeout changes below peer1's
         peer2_timeout.store(10, Ordering::Relaxed);
         // Then we get peer 2 always (now lowest)
-        assert_eq!(fetcher.next_peer(), Some(peer2));
-        assert_eq!(fetcher.next_peer(), Some(peer2));
+        assert_eq!(fetcher.next_peer(&headers_request(1)), Some(peer2));
+        assert_eq!(fetcher.next_peer(&headers_request(1)), Some(peer2));
+    }
+
+    #[tokio::test]
+    async fn test_peer_prioritization_by_latency() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+        let peer1 = B512::random();
+        let peer2 = B512::random();
+
+        let peer2_latency = Arc::new(PeerLatencyTracker::default());
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            1,
+            capabilities(),
+            Arc::new(AtomicU64::new(30)),
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer2,
+            B256::random(),
+            2,
+            capabilities(),
+            Arc::new(AtomicU64::new(300)),
+            Arc::clone(&peer2_latency),
+        );
+        assert_eq!(fetcher.next_peer(&headers_request(1)), Some(peer1));
+
+        // peer2 responded to a headers request faster than peer1's timeout
+        peer2_latency.record(EthMessageID::GetBlockHeaders, Duration::from_millis(5));
+        assert_eq!(fetcher.next_peer(&headers_request(1)), Some(peer2));
+
+        // the latency of headers requests doesn't affect bodies requests
+        let (tx, _rx) = oneshot::channel();
+        let bodies_request = DownloadRequest::GetBlockBodies {
+            request: vec![],
+            response: tx,
+            priority: Priority::default(),
+            retries: 0,
+        };
+        assert_eq!(fetcher.next_peer(&bodies_request), Some(peer1));
+    }
+
+    #[tokio::test]
+    async fn test_peer_routing_by_best_block() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+        let peer1 = B512::random();
+        let peer2 = B512::random();
+        let peer3 = B512::random();
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            10,
+            capabilities(),
+            Arc::new(AtomicU64::new(1)),
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer2,
+            B256::random(),
+            20,
+            capabilities(),
+            Arc::new(AtomicU64::new(2)),
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer3,
+            B256::random(),
+            30,
+            Arc::new(Vec::new().into()),
+            Arc::new(AtomicU64::new(0)),
+            Default::default(),
+        );
+
+        // peer3 has the lowest timeout but doesn't support eth
+        assert_eq!(fetcher.next_peer(&headers_request(5)), Some(peer1));
+        // peer1 is behind the requested block
+        assert_eq!(fetcher.next_peer(&headers_request(15)), Some(peer2));
+        // no peer has the requested block, so the best block is ignored
+        assert_eq!(fetcher.next_peer(&headers_request(25)), Some(peer1));
+
+        // peer2 is preferred once it advances past the requested block
+        fetcher.update_peer_block(&peer2, B256::random(), 40);
+        assert_eq!(fetcher.next_peer(&headers_request(25)), Some(peer2));
+
+        // a peer with an unknown best block may serve any request
+        fetcher.on_pending_disconnect(&peer1);
+        fetcher.new_active_peer(
+            peer3,
+            B256::random(),
+            0,
+            capabilities(),
+            Arc::new(AtomicU64::new(0)),
+            Default::default(),
+        );
+        assert_eq!(fetcher.next_peer(&headers_request(50)), Some(peer3));
+    }
+
+    #[tokio::test]
+    async fn test_requeue_on_session_closed() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+        let peer1 = B512::random();
+        let peer2 = B512::random();
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            10,
+            capabilities(),
+            Arc::new(AtomicU64::new(1)),
+            Default::default(),
+        );
+
+        let (tx, mut rx) = oneshot::channel();
+        fetcher.queued_requests.push_back(DownloadRequest::GetBlockHeaders {
+            request: HeadersRequest { start: 5u64.into(), limit: 1, direction: Default::default() },
+            response: tx,
+            priority: Priority::default(),
+            retries: 0,
+        });
+        let PollAction::Ready(FetchAction::BlockRequest { peer_id, .. }) = fetcher.poll_action()
+        else {
+            unreachable!()
+        };
+        assert_eq!(peer_id, peer1);
+        assert!(fetcher.queued_requests.is_empty());
+
+        // the inflight request is reassigned to the next peer instead of failing
+        fetcher.on_session_closed(&peer1);
+        assert!(rx.try_recv().is_err());
+        assert_eq!(fetcher.queued_requests.len(), 1);
+
+        fetcher.new_active_peer(
+            peer2,
+            B256::random(),
+            10,
+            capabilities(),
+            Arc::new(AtomicU64::new(1)),
+            Default::default(),
+        );
+        let PollAction::Ready(FetchAction::BlockRequest { peer_id, .. }) = fetcher.poll_action()
+        else {
+            unreachable!()
+        };
+        assert_eq!(peer_id, peer2);
+        assert!(fetcher.inflight_headers_requests.contains_key(&peer2));
+    }
+
+    #[tokio::test]
+    async fn test_requeue_retries_are_bounded() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+
+        let (tx, mut rx) = oneshot::channel();
+        fetcher.queued_requests.push_back(DownloadRequest::GetBlockBodies {
+            request: vec![],
+            response: tx,
+            priority: Priority::default(),
+            retries: 0,
+        });
+
+        // every peer the request is assigned to disconnects
+        for retry in 0..=MAX_REQUEST_RETRIES {
+            let peer = B512::random();
+            fetcher.new_active_peer(
+                peer,
+                B256::random(),
+                10,
+                capabilities(),
+                Arc::new(AtomicU64::new(1)),
+                Default::default(),
+            );
+            let PollAction::Ready(FetchAction::BlockRequest { peer_id, .. }) =
+                fetcher.poll_action()
+            else {
+                unreachable!()
+            };
+            assert_eq!(peer_id, peer);
+            fetcher.on_session_closed(&peer);
+
+            if retry < MAX_REQUEST_RETRIES {
+                assert!(rx.try_recv().is_err());
+                assert_eq!(fetcher.queued_requests.len(), 1);
+            }
+        }
+
+        // the request fails instead of being requeued again
+        assert!(fetcher.queued_requests.is_empty());
+        assert!(matches!(rx.try_recv(), Ok(Err(RequestError::ConnectionDropped))));
     }
 
     #[tokio::test]
@@ -545,6 +888,7 @@ mod tests {
                     direction: Default::default(),
                 },
                 response: tx,
+                priority: Priority::default(),
             };
             let mut header = SealedHeader::default().unseal();
             header.number = 0u64;
@@ -555,6 +899,8 @@ mod tests {
             peer_id,
             Default::default(),
             Default::default(),
+            capabilities(),
+            Default::default(),
             Default::default(),
         );
 
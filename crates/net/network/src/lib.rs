@@ -134,19 +134,21 @@ mod session;
 mod state;
 mod swarm;
 pub mod transactions;
+pub mod transport;
 
 pub use builder::NetworkBuilder;
 pub use config::{NetworkConfig, NetworkConfigBuilder};
 pub use discovery::{Discovery, DiscoveryEvent};
 pub use fetch::FetchClient;
+pub use listener::{ConnectionListener, ListenerEvent};
 pub use manager::{NetworkEvent, NetworkManager};
 pub use message::PeerRequest;
 pub use network::{NetworkEvents, NetworkHandle, NetworkProtocols};
 pub use peers::PeersConfig;
 pub use session::{
-    ActiveSessionHandle, ActiveSessionMessage, Direction, PeerInfo, PendingSessionEvent,
-    PendingSessionHandle, PendingSessionHandshakeError, SessionCommand, SessionEvent, SessionId,
-    SessionLimits, SessionManager, SessionsConfig,
+    ActiveSessionHandle, ActiveSessionMessage, Direction, PeerInfo, PeerLatencyTracker,
+    PendingSessionEvent, PendingSessionHandle, PendingSessionHandshakeError, SessionCommand,
+    SessionEvent, SessionId, SessionLimits, SessionManager, SessionsConfig,
 };
 
 pub use reth_eth_wire::{DisconnectReason, HelloMessageWithProtocols};
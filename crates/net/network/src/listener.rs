@@ -1,27 +1,25 @@
 //! Contains connection-oriented interfaces.
 
-use futures::{ready, Stream};
-
+use crate::transport::PeerStream;
+use futures::{ready, stream::BoxStream, Stream, StreamExt};
 use std::{
-    io,
+    fmt, io,
     net::SocketAddr,
     pin::Pin,
     task::{Context, Poll},
 };
 use tokio::net::{TcpListener, TcpStream};
 
-/// A tcp connection listener.
+/// A connection listener.
 ///
-/// Listens for incoming connections.
+/// Listens for incoming connections, over TCP unless created by another
+/// [`Transport`](crate::transport::Transport).
 #[must_use = "Transport does nothing unless polled."]
-#[pin_project::pin_project]
-#[derive(Debug)]
 pub struct ConnectionListener {
     /// Local address of the listener stream.
     local_address: SocketAddr,
-    /// The active tcp listener for incoming connections.
-    #[pin]
-    incoming: TcpListenerStream,
+    /// The stream of incoming connections.
+    incoming: BoxStream<'static, io::Result<(PeerStream, SocketAddr)>>,
 }
 
 impl ConnectionListener {
@@ -34,22 +32,36 @@ impl ConnectionListener {
 
     /// Creates a new connection listener stream.
     pub(crate) fn new(listener: TcpListener, local_address: SocketAddr) -> Self {
-        Self { local_address, incoming: TcpListenerStream { inner: listener } }
+        let incoming = TcpListenerStream { inner: listener }.map(|conn| {
+            let (stream, remote_addr) = conn?;
+            if let Err(err) = stream.set_nodelay(true) {
+                tracing::warn!(target: "net", "set nodelay failed: {:?}", err);
+            }
+            Ok((PeerStream::Tcp(stream), remote_addr))
+        });
+        Self::from_stream(local_address, incoming)
+    }
+
+    /// Creates a new connection listener that yields the connections of the given stream.
+    ///
+    /// This is used by [`Transport`](crate::transport::Transport)s other than TCP.
+    pub fn from_stream(
+        local_address: SocketAddr,
+        incoming: impl Stream<Item = io::Result<(PeerStream, SocketAddr)>> + Send + 'static,
+    ) -> Self {
+        Self { local_address, incoming: incoming.boxed() }
     }
 
     /// Polls the type to make progress.
     pub fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<ListenerEvent> {
-        let this = self.project();
-        match ready!(this.incoming.poll_next(cx)) {
+        let this = self.get_mut();
+        match ready!(this.incoming.poll_next_unpin(cx)) {
             Some(Ok((stream, remote_addr))) => {
-                if let Err(err) = stream.set_nodelay(true) {
-                    tracing::warn!(target: "net", "set nodelay failed: {:?}", err);
-                }
                 Poll::Ready(ListenerEvent::Incoming { stream, remote_addr })
             }
             Some(Err(err)) => Poll::Ready(ListenerEvent::Error(err)),
             None => {
-                Poll::Ready(ListenerEvent::ListenerClosed { local_address: *this.local_address })
+                Poll::Ready(ListenerEvent::ListenerClosed { local_address: this.local_address })
             }
         }
     }
@@ -60,18 +72,26 @@ impl ConnectionListener {
     }
 }
 
-/// Event type produced by the [`TcpListenerStream`].
+impl fmt::Debug for ConnectionListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionListener")
+            .field("local_address", &self.local_address)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Event type produced by the [`ConnectionListener`].
 pub enum ListenerEvent {
     /// Received a new incoming.
     Incoming {
         /// Accepted connection
-        stream: TcpStream,
+        stream: PeerStream,
         /// Address of the remote peer.
         remote_addr: SocketAddr,
     },
     /// Returned when the underlying connection listener has been closed.
     ///
-    /// This is the case if the [`TcpListenerStream`] should ever return `None`
+    /// This is the case if the stream of incoming connections should ever return `None`
     ListenerClosed {
         /// Address of the closed listener.
         local_address: SocketAddr,
@@ -21,7 +21,6 @@ use crate::{
     error::{NetworkError, ServiceKind},
     eth_requests::IncomingEthRequest,
     import::{BlockImport, BlockImportOutcome, BlockValidation},
-    listener::ConnectionListener,
     message::{NewBlockMessage, PeerMessage, PeerRequest, PeerRequestSender},
     metrics::{DisconnectMetrics, NetworkMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
     network::{NetworkHandle, NetworkHandleMessage},
@@ -40,7 +39,7 @@ use reth_eth_wire::{
     DisconnectReason, EthVersion, Status,
 };
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
-use reth_net_common::bandwidth_meter::BandwidthMeter;
+use reth_net_common::{bandwidth_meter::BandwidthMeter, compression_meter::CompressionMeter};
 use reth_network_api::ReputationChangeKind;
 use reth_primitives::{ForkId, NodeRecord, PeerId, B256};
 use reth_provider::{BlockNumReader, BlockReader};
@@ -56,11 +55,18 @@ use std::{
         Arc,
     },
     task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    sync::mpsc::{self, error::TrySendError},
+    time::{Instant, Interval},
 };
-use tokio::sync::mpsc::{self, error::TrySendError};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{debug, error, trace, warn};
 
+/// Interval at which the compression ratio of the sessions is reported.
+const COMPRESSION_METRICS_INTERVAL: Duration = Duration::from_secs(10);
+
 #[cfg_attr(doc, aquamarine::aquamarine)]
 /// Manages the _entire_ state of the network.
 ///
@@ -108,6 +114,8 @@ pub struct NetworkManager<C> {
     metrics: NetworkMetrics,
     /// Disconnect metrics for the Network
     disconnect_metrics: DisconnectMetrics,
+    /// Interval for updating the compression ratio metric.
+    compression_metrics_interval: Interval,
 }
 
 // === impl NetworkManager ===
@@ -143,6 +151,12 @@ impl<C> NetworkManager<C> {
         self.handle.bandwidth_meter()
     }
 
+    /// Returns a shareable reference to the [`CompressionMeter`] stored
+    /// inside of the [`NetworkHandle`]
+    pub fn compression_meter(&self) -> &CompressionMeter {
+        self.handle.compression_meter()
+    }
+
     /// Returns the secret key used for authenticating sessions.
     pub fn secret_key(&self) -> SecretKey {
         self.swarm.sessions().secret_key()
@@ -177,6 +191,7 @@ where
             dns_discovery_config,
             extra_protocols,
             tx_gossip_disabled,
+            transport,
             #[cfg(feature = "optimism")]
                 optimism_network_config: crate::config::OptimismNetworkConfig { sequencer_endpoint },
         } = config;
@@ -184,7 +199,7 @@ where
         let peers_manager = PeersManager::new(peers_config);
         let peers_handle = peers_manager.handle();
 
-        let incoming = ConnectionListener::bind(listener_addr).await.map_err(|err| {
+        let incoming = transport.listen(listener_addr).await.map_err(|err| {
             NetworkError::from_io_error(err, ServiceKind::Listener(listener_addr))
         })?;
         let listener_address = Arc::new(Mutex::new(incoming.local_address()));
@@ -204,6 +219,7 @@ where
 
         let num_active_peers = Arc::new(AtomicUsize::new(0));
         let bandwidth_meter: BandwidthMeter = BandwidthMeter::default();
+        let compression_meter = CompressionMeter::default();
 
         let sessions = SessionManager::new(
             secret_key,
@@ -213,7 +229,9 @@ where
             hello_message,
             fork_filter,
             extra_protocols,
+            transport.into(),
             bandwidth_meter.clone(),
+            compression_meter.clone(),
         );
 
         let state = NetworkState::new(
@@ -237,6 +255,7 @@ where
             peers_handle,
             network_mode,
             bandwidth_meter,
+            compression_meter,
             Arc::new(AtomicU64::new(chain_spec.chain.id())),
             tx_gossip_disabled,
             #[cfg(feature = "optimism")]
@@ -254,6 +273,10 @@ where
             num_active_peers,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
+            compression_metrics_interval: tokio::time::interval_at(
+                Instant::now() + COMPRESSION_METRICS_INTERVAL,
+                COMPRESSION_METRICS_INTERVAL,
+            ),
         })
     }
 
@@ -321,6 +344,12 @@ where
         self.swarm.state().peers().iter_peers()
     }
 
+    /// Returns an iterator over all peers in the peer set that should be persisted across
+    /// restarts, see [PersistedPeer](crate::peers::PersistedPeer).
+    pub fn persisted_peers(&self) -> impl Iterator<Item = crate::peers::PersistedPeer> + '_ {
+        self.swarm.state().peers().iter_persisted_peers()
+    }
+
     /// Returns a new [`PeersHandle`] that can be cloned and shared.
     ///
     /// The [`PeersHandle`] can be used to interact with the network's peer set.
@@ -634,6 +663,10 @@ where
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
 
+        if this.compression_metrics_interval.poll_tick(cx).is_ready() {
+            this.metrics.compression_ratio.set(this.handle.compression_meter().compression_ratio());
+        }
+
         // poll new block imports
         while let Poll::Ready(outcome) = this.block_import.poll(cx) {
             this.on_block_import_result(outcome);
@@ -1,12 +1,15 @@
 use reth_eth_wire::DisconnectReason;
 use reth_metrics::{
-    metrics::{Counter, Gauge},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
 
 /// Scope for monitoring transactions sent from the manager to the tx manager
 pub(crate) const NETWORK_POOL_TRANSACTIONS_SCOPE: &str = "network.pool.transactions";
 
+/// Scope for the traffic of the peer streams, labeled by peer
+pub(crate) const NETWORK_IO_SCOPE: &str = "network.io";
+
 /// Metrics for the entire network, handled by NetworkManager
 #[derive(Metrics)]
 #[metrics(scope = "network")]
@@ -26,6 +29,9 @@ pub struct NetworkMetrics {
     /// Total number of sessions closed
     pub(crate) closed_sessions: Counter,
 
+    /// Ratio of the raw size to the snappy compressed size of all messages, updated periodically
+    pub(crate) compression_ratio: Gauge,
+
     /// Number of active incoming connections
     pub(crate) incoming_connections: Gauge,
 
@@ -53,6 +59,15 @@ pub struct SessionManagerMetrics {
     pub(crate) total_dial_successes: Counter,
 }
 
+/// Metrics for the latency of the requests sent to peers, labeled by the message type of the
+/// request
+#[derive(Metrics)]
+#[metrics(scope = "network")]
+pub struct EthRequestLatencyMetrics {
+    /// The time from sending a request until receiving the response, in seconds
+    pub(crate) request_latency: Histogram,
+}
+
 /// Metrics for the TransactionsManager
 #[derive(Metrics)]
 #[metrics(scope = "network")]
@@ -6,9 +6,9 @@ use async_trait::async_trait;
 use parking_lot::Mutex;
 use reth_eth_wire::{DisconnectReason, NewBlock, NewPooledTransactionHashes, SharedTransactions};
 use reth_interfaces::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
-use reth_net_common::bandwidth_meter::BandwidthMeter;
+use reth_net_common::{bandwidth_meter::BandwidthMeter, compression_meter::CompressionMeter};
 use reth_network_api::{
-    NetworkError, NetworkInfo, PeerInfo, PeerKind, Peers, PeersInfo, Reputation,
+    NetworkError, NetworkInfo, PeerInfo, PeerKind, PeerLatency, Peers, PeersInfo, Reputation,
     ReputationChangeKind,
 };
 use reth_primitives::{Head, NodeRecord, PeerId, TransactionSigned, B256};
@@ -47,6 +47,7 @@ impl NetworkHandle {
         peers: PeersHandle,
         network_mode: NetworkMode,
         bandwidth_meter: BandwidthMeter,
+        compression_meter: CompressionMeter,
         chain_id: Arc<AtomicU64>,
         tx_gossip_disabled: bool,
         #[cfg(feature = "optimism")] sequencer_endpoint: Option<String>,
@@ -60,6 +61,7 @@ impl NetworkHandle {
             peers,
             network_mode,
             bandwidth_meter,
+            compression_meter,
             is_syncing: Arc::new(AtomicBool::new(false)),
             initial_sync_done: Arc::new(AtomicBool::new(false)),
             chain_id,
@@ -142,6 +144,19 @@ impl NetworkHandle {
         &self.inner.bandwidth_meter
     }
 
+    /// Provides a shareable reference to the [`CompressionMeter`] stored on the `NetworkInner`.
+    pub fn compression_meter(&self) -> &CompressionMeter {
+        &self.inner.compression_meter
+    }
+
+    /// Returns the summary of the latencies of the requests sent to the given peer, by message
+    /// type.
+    ///
+    /// Returns `None` if the peer is not connected.
+    pub async fn peer_latency(&self, peer_id: PeerId) -> Result<Option<PeerLatency>, NetworkError> {
+        Ok(self.get_peer_by_id(peer_id).await?.map(|info| info.latency))
+    }
+
     /// Send message to gracefully shutdown node.
     ///
     /// This will disconnect all active and pending sessions and prevent
@@ -346,6 +361,8 @@ struct NetworkInner {
     network_mode: NetworkMode,
     /// Used to measure inbound & outbound bandwidth across network streams (currently unused)
     bandwidth_meter: BandwidthMeter,
+    /// Used to measure the compression of messages across all sessions
+    compression_meter: CompressionMeter,
     /// Represents if the network is currently syncing.
     is_syncing: Arc<AtomicBool>,
     /// Used to differentiate between an initial pipeline sync or a live sync
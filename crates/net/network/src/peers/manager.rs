@@ -2,8 +2,8 @@ use crate::{
     error::{BackoffKind, SessionError},
     peers::{
         reputation::{is_banned_reputation, DEFAULT_REPUTATION},
-        ReputationChangeWeights, DEFAULT_MAX_CONCURRENT_DIALS, DEFAULT_MAX_PEERS_INBOUND,
-        DEFAULT_MAX_PEERS_OUTBOUND,
+        ReputationChangeWeights, DEFAULT_MAX_CONCURRENT_DIALS, DEFAULT_MAX_OUTBOUND_PER_SUBNET,
+        DEFAULT_MAX_PEERS_INBOUND, DEFAULT_MAX_PEERS_OUTBOUND, DEFAULT_RESERVED_TRUSTED_SLOTS,
     },
     session::{Direction, PendingSessionHandshakeError},
 };
@@ -16,10 +16,10 @@ use std::{
     collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
     fmt::Display,
     io::{self, ErrorKind},
-    net::{IpAddr, SocketAddr},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
     path::Path,
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use thiserror::Error;
 use tokio::{
@@ -129,6 +129,7 @@ impl PeersManager {
             trusted_nodes,
             connect_trusted_nodes_only,
             basic_nodes,
+            persisted_peers,
             max_backoff_count,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
@@ -137,12 +138,26 @@ impl PeersManager {
         // We use half of the interval to decrease the max duration to `150%` in worst case
         let unban_interval = ban_duration.min(backoff_durations.low) / 2;
 
-        let mut peers = HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len());
+        let mut peers =
+            HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len() + persisted_peers.len());
 
         for NodeRecord { address, tcp_port, udp_port: _, id } in trusted_nodes {
             peers.entry(id).or_insert_with(|| Peer::trusted(SocketAddr::from((address, tcp_port))));
         }
 
+        for persisted in persisted_peers {
+            // peers that were banned are not restored
+            if is_banned_reputation(persisted.reputation) {
+                continue
+            }
+            let NodeRecord { address, tcp_port, udp_port: _, id } = persisted.record;
+            peers.entry(id).or_insert_with(|| Peer {
+                reputation: persisted.reputation,
+                last_seen: persisted.last_seen.map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+                ..Peer::new(SocketAddr::from((address, tcp_port)))
+            });
+        }
+
         for NodeRecord { address, tcp_port, udp_port: _, id } in basic_nodes {
             peers.entry(id).or_insert_with(|| Peer::new(SocketAddr::from((address, tcp_port))));
         }
@@ -185,6 +200,21 @@ impl PeersManager {
         self.peers.iter().map(|(peer_id, v)| NodeRecord::new(v.addr, *peer_id))
     }
 
+    /// Returns an iterator over all peers that are worth persisting across restarts, i.e. all
+    /// peers that are not banned, with their reputation and the last time they were seen.
+    pub(crate) fn iter_persisted_peers(&self) -> impl Iterator<Item = PersistedPeer> + '_ {
+        self.peers.iter().filter(|(_, peer)| !peer.is_banned()).map(|(peer_id, peer)| {
+            PersistedPeer {
+                record: NodeRecord::new(peer.addr, *peer_id),
+                reputation: peer.reputation,
+                last_seen: peer
+                    .last_seen
+                    .and_then(|last_seen| last_seen.duration_since(UNIX_EPOCH).ok())
+                    .map(|last_seen| last_seen.as_secs()),
+            }
+        })
+    }
+
     /// Returns an iterator over all peer ids for peers with the given kind
     pub(crate) fn peers_by_kind(&self, kind: PeerKind) -> impl Iterator<Item = PeerId> + '_ {
         self.peers.iter().filter_map(move |(peer_id, peer)| (peer.kind == kind).then_some(*peer_id))
@@ -212,6 +242,9 @@ impl PeersManager {
     ///
     /// returns an error if the inbound ip address is on the ban list or
     /// we have reached our limit for max inbound connections
+    ///
+    /// Connections from the ip address of a trusted peer are accepted even if all slots are
+    /// occupied, see [Self::on_incoming_session_established].
     pub(crate) fn on_incoming_pending_session(
         &mut self,
         addr: IpAddr,
@@ -219,8 +252,12 @@ impl PeersManager {
         if self.ban_list.is_banned_ip(&addr) {
             return Err(InboundConnectionError::IpBanned)
         }
-        if !self.connection_info.has_in_capacity() {
-            return Err(InboundConnectionError::ExceedsLimit(self.connection_info.max_inbound))
+        if !self.connection_info.has_in_capacity() &&
+            !self.peers.values().any(|peer| peer.is_trusted() && peer.addr.ip() == addr)
+        {
+            return Err(InboundConnectionError::ExceedsLimit(
+                self.connection_info.max_inbound_with_reserved(),
+            ))
         }
         // keep track of new connection
         self.connection_info.inc_in();
@@ -262,6 +299,11 @@ impl PeersManager {
     ///
     /// If the reputation of the peer is below the `BANNED_REPUTATION` threshold, a disconnect will
     /// be scheduled.
+    ///
+    /// Peers that are not trusted can't occupy the inbound slots that are reserved for trusted
+    /// peers, their session is disconnected if all other inbound slots are occupied. If a trusted
+    /// peer exceeds the reserved slots as well, the incoming session of the peer with the lowest
+    /// reputation that is not trusted is disconnected instead.
     pub(crate) fn on_incoming_session_established(&mut self, peer_id: PeerId, addr: SocketAddr) {
         // we only need to check the peer id here as the ip address will have been checked at
         // on_inbound_pending_session. We also check if the peer is in the backoff list here.
@@ -281,6 +323,7 @@ impl PeersManager {
                     return
                 }
                 value.state = PeerConnectionState::In;
+                value.last_seen = Some(SystemTime::now());
             }
             Entry::Vacant(entry) => {
                 // peer is missing in the table, we add it but mark it as to be removed after
@@ -291,6 +334,52 @@ impl PeersManager {
                 self.queued_actions.push_back(PeerAction::PeerAdded(peer_id));
             }
         }
+
+        self.enforce_inbound_slots(peer_id);
+    }
+
+    /// Disconnects an incoming session if the inbound peers occupy more slots than they're allowed
+    /// to after the given peer established an incoming session.
+    ///
+    /// Trusted peers occupy the reserved slots first.
+    fn enforce_inbound_slots(&mut self, peer_id: PeerId) {
+        let (num_inbound, num_trusted_inbound) = self
+            .peers
+            .values()
+            .filter(|peer| peer.state.is_incoming())
+            .fold((0, 0), |(all, trusted), peer| (all + 1, trusted + peer.is_trusted() as usize));
+        let num_basic_inbound =
+            num_inbound - num_trusted_inbound.min(self.connection_info.reserved_trusted_inbound);
+        let is_trusted = self.peers.get(&peer_id).map_or(false, |peer| peer.is_trusted());
+
+        let evicted = if is_trusted {
+            if num_inbound <= self.connection_info.max_inbound_with_reserved() {
+                return
+            }
+            // evict the worst peer that is not trusted
+            let worst = self
+                .peers
+                .iter()
+                .filter(|(_, peer)| peer.state.is_incoming() && !peer.is_trusted())
+                .min_by_key(|(_, peer)| peer.reputation);
+            match worst {
+                Some((worst, _)) => *worst,
+                None => return,
+            }
+        } else if num_basic_inbound > self.connection_info.max_inbound {
+            peer_id
+        } else {
+            return
+        };
+
+        trace!(target: "net::peers", peer_id=?evicted, "disconnecting inbound peer, no free slots");
+        if let Some(peer) = self.peers.get_mut(&evicted) {
+            peer.state.disconnect();
+        }
+        self.queued_actions.push_back(PeerAction::Disconnect {
+            peer_id: evicted,
+            reason: Some(DisconnectReason::TooManyPeers),
+        });
     }
 
     /// Bans the peer temporarily with the configured ban timeout
@@ -333,8 +422,10 @@ impl PeersManager {
             if self.last_tick > now { 0 } else { (now - self.last_tick).as_secs() as i32 };
         self.last_tick = now;
 
-        // update reputation via seconds connected
+        // update reputation via seconds connected and track when the peers were last seen
+        let seen = SystemTime::now();
         for peer in self.peers.iter_mut().filter(|(_, peer)| peer.state.is_connected()) {
+            peer.1.last_seen = Some(seen);
             // update reputation via seconds connected, but keep the target _around_ the default
             // reputation.
             if peer.1.reputation < DEFAULT_REPUTATION {
@@ -416,6 +507,7 @@ impl PeersManager {
                     // session to that peer
                     entry.get_mut().severe_backoff_counter = 0;
                     entry.get_mut().state = PeerConnectionState::Idle;
+                    entry.get_mut().last_seen = Some(SystemTime::now());
                     return
                 }
             }
@@ -651,12 +743,22 @@ impl PeersManager {
     /// `trusted` peers.
     ///
     /// Returns `None` if no peer is available.
+    ///
+    /// Non-trusted peers in a subnet that already has
+    /// [`max_outbound_per_subnet`](ConnectionInfo::max_outbound_per_subnet) outbound connections
+    /// are skipped, so a single operator can't occupy all outbound slots.
     fn best_unconnected(&mut self) -> Option<(PeerId, &mut Peer)> {
+        let outbound_subnets = self.outbound_subnets();
+        let max_per_subnet = self.connection_info.max_outbound_per_subnet;
         let mut unconnected = self.peers.iter_mut().filter(|(_, peer)| {
             !peer.is_backed_off() &&
                 !peer.is_banned() &&
                 peer.state.is_unconnected() &&
-                (!self.connect_trusted_nodes_only || peer.is_trusted())
+                (!self.connect_trusted_nodes_only || peer.is_trusted()) &&
+                (peer.is_trusted() ||
+                    subnet(peer.addr.ip()).map_or(true, |subnet| {
+                        outbound_subnets.get(&subnet).copied().unwrap_or_default() < max_per_subnet
+                    }))
         });
 
         // keep track of the best peer, if there's one
@@ -672,14 +774,28 @@ impl PeersManager {
                 return Some((*maybe_better.0, maybe_better.1))
             }
 
-            // otherwise we keep track of the best peer using the reputation
-            if maybe_better.1.reputation > best_peer.1.reputation {
+            // otherwise we keep track of the best peer, preferring peers that were seen more
+            // recently and then the ones with the higher reputation
+            if (maybe_better.1.last_seen, maybe_better.1.reputation) >
+                (best_peer.1.last_seen, best_peer.1.reputation)
+            {
                 best_peer = maybe_better;
             }
         }
         Some((*best_peer.0, best_peer.1))
     }
 
+    /// Returns the number of outbound connections per subnet, see [subnet].
+    fn outbound_subnets(&self) -> HashMap<IpAddr, usize> {
+        let mut subnets = HashMap::new();
+        for peer in self.peers.values().filter(|peer| peer.state.is_outgoing()) {
+            if let Some(subnet) = subnet(peer.addr.ip()) {
+                *subnets.entry(subnet).or_default() += 1;
+            }
+        }
+        subnets
+    }
+
     /// If there's capacity for new outbound connections, this will queue new
     /// [`PeerAction::Connect`] actions.
     ///
@@ -688,15 +804,31 @@ impl PeersManager {
     fn fill_outbound_slots(&mut self) {
         self.tick();
 
+        let mut num_trusted_outbound = self
+            .peers
+            .values()
+            .filter(|peer| peer.is_trusted() && peer.state.is_outgoing())
+            .count();
+
         // as long as there a slots available try to fill them with the best peers
         let mut new_outbound_dials = 1;
         while self.connection_info.has_out_capacity() {
+            let has_basic_capacity =
+                self.connection_info.has_basic_out_capacity(num_trusted_outbound);
             let action = {
                 let (peer_id, peer) = match self.best_unconnected() {
                     Some(peer) => peer,
                     _ => break,
                 };
 
+                // the reserved slots can only be used by trusted peers, which are always returned
+                // first
+                if peer.is_trusted() {
+                    num_trusted_outbound += 1;
+                } else if !has_basic_capacity {
+                    break
+                }
+
                 // If best peer does not meet reputation threshold exit immediately.
                 if peer.is_banned() {
                     break
@@ -808,19 +940,52 @@ pub struct ConnectionInfo {
     /// Maximum allowed concurrent outbound dials.
     #[cfg_attr(feature = "serde", serde(default))]
     max_concurrent_outbound_dials: usize,
+    /// Maximum allowed outbound connections to peers in the same subnet, i.e. the same /24 for
+    /// IPv4 and the same /64 for IPv6 addresses.
+    ///
+    /// Trusted peers and peers in local networks are not limited.
+    ///
+    /// Peers are not bucketed by their autonomous system, since mapping an address to its AS
+    /// requires an ASN database that the node doesn't ship. The /24 and /64 subnets approximate
+    /// the networks of a single operator instead.
+    max_outbound_per_subnet: usize,
+    /// Additional slots for outbound connections that can only be used by trusted peers.
+    reserved_trusted_outbound: usize,
+    /// Additional slots for inbound connections that can only be used by trusted peers.
+    ///
+    /// Pending inbound connections can use these slots since the peer is not known yet. Once the
+    /// session is established, it's disconnected if the peer is not trusted.
+    reserved_trusted_inbound: usize,
 }
 
 // === impl ConnectionInfo ===
 
 impl ConnectionInfo {
-    ///  Returns `true` if there's still capacity for a new outgoing connection.
+    ///  Returns `true` if there's still capacity for a new outgoing connection, including the
+    ///  slots that are reserved for trusted peers.
     fn has_out_capacity(&self) -> bool {
-        self.num_outbound < self.max_outbound
+        self.num_outbound < self.max_outbound + self.reserved_trusted_outbound
+    }
+
+    /// Returns `true` if there's still capacity for a new outgoing connection to a peer that is not
+    /// trusted, given the number of outgoing connections to trusted peers.
+    ///
+    /// Trusted peers occupy the reserved slots first.
+    fn has_basic_out_capacity(&self, num_trusted_outbound: usize) -> bool {
+        self.num_outbound - num_trusted_outbound.min(self.reserved_trusted_outbound) <
+            self.max_outbound
     }
 
-    ///  Returns `true` if there's still capacity for a new incoming connection.
+    ///  Returns `true` if there's still capacity for a new incoming connection, including the
+    ///  slots that are reserved for trusted peers.
     fn has_in_capacity(&self) -> bool {
-        self.num_inbound < self.max_inbound
+        self.num_inbound < self.max_inbound_with_reserved()
+    }
+
+    /// Returns the maximum number of inbound connections, including the slots that are reserved
+    /// for trusted peers.
+    fn max_inbound_with_reserved(&self) -> usize {
+        self.max_inbound + self.reserved_trusted_inbound
     }
 
     fn decr_state(&mut self, state: PeerConnectionState) {
@@ -856,6 +1021,9 @@ impl Default for ConnectionInfo {
             max_outbound: DEFAULT_MAX_PEERS_OUTBOUND,
             max_inbound: DEFAULT_MAX_PEERS_INBOUND,
             max_concurrent_outbound_dials: DEFAULT_MAX_CONCURRENT_DIALS,
+            max_outbound_per_subnet: DEFAULT_MAX_OUTBOUND_PER_SUBNET,
+            reserved_trusted_outbound: DEFAULT_RESERVED_TRUSTED_SLOTS,
+            reserved_trusted_inbound: DEFAULT_RESERVED_TRUSTED_SLOTS,
         }
     }
 }
@@ -879,6 +1047,8 @@ pub struct Peer {
     backed_off: bool,
     /// Counts number of times the peer was backed off due to a severe [BackoffKind].
     severe_backoff_counter: u32,
+    /// The last time there was an active session with the peer, if any.
+    last_seen: Option<SystemTime>,
 }
 
 // === impl Peer ===
@@ -902,6 +1072,7 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            last_seen: None,
         }
     }
 
@@ -1018,6 +1189,12 @@ impl PeerConnectionState {
         matches!(self, PeerConnectionState::In | PeerConnectionState::Out)
     }
 
+    /// Returns whether there's an outgoing connection to this peer.
+    #[inline]
+    fn is_outgoing(&self) -> bool {
+        matches!(self, PeerConnectionState::Out | PeerConnectionState::DisconnectingOut)
+    }
+
     /// Returns if there's currently no connection to that peer.
     #[inline]
     fn is_unconnected(&self) -> bool {
@@ -1118,6 +1295,9 @@ pub struct PeersConfig {
     /// Basic nodes to connect to.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub basic_nodes: HashSet<NodeRecord>,
+    /// Peers persisted by a previous run, see [PersistedPeer].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub persisted_peers: Vec<PersistedPeer>,
     /// How long to ban bad peers.
     #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
     pub ban_duration: Duration,
@@ -1148,6 +1328,7 @@ impl Default for PeersConfig {
             trusted_nodes: Default::default(),
             connect_trusted_nodes_only: false,
             basic_nodes: Default::default(),
+            persisted_peers: Default::default(),
             max_backoff_count: 5,
         }
     }
@@ -1212,6 +1393,24 @@ impl PeersConfig {
         self
     }
 
+    /// Maximum allowed outbound connections to peers in the same subnet.
+    pub fn with_max_outbound_per_subnet(mut self, max_outbound_per_subnet: usize) -> Self {
+        self.connection_info.max_outbound_per_subnet = max_outbound_per_subnet;
+        self
+    }
+
+    /// Additional outbound slots that can only be used by trusted peers.
+    pub fn with_reserved_trusted_outbound(mut self, reserved_trusted_outbound: usize) -> Self {
+        self.connection_info.reserved_trusted_outbound = reserved_trusted_outbound;
+        self
+    }
+
+    /// Additional inbound slots that can only be used by trusted peers.
+    pub fn with_reserved_trusted_inbound(mut self, reserved_trusted_inbound: usize) -> Self {
+        self.connection_info.reserved_trusted_inbound = reserved_trusted_inbound;
+        self
+    }
+
     /// Nodes to always connect to.
     pub fn with_trusted_nodes(mut self, nodes: HashSet<NodeRecord>) -> Self {
         self.trusted_nodes = nodes;
@@ -1230,6 +1429,12 @@ impl PeersConfig {
         self
     }
 
+    /// Peers persisted by a previous run, which are restored with their reputation.
+    pub fn with_persisted_peers(mut self, peers: Vec<PersistedPeer>) -> Self {
+        self.persisted_peers = peers;
+        self
+    }
+
     /// Configures the max allowed backoff count.
     pub fn with_max_backoff_count(mut self, max_backoff_count: u32) -> Self {
         self.max_backoff_count = max_backoff_count;
@@ -1249,6 +1454,9 @@ impl PeersConfig {
     }
 
     /// Read from file nodes available at launch. Ignored if None.
+    ///
+    /// The file contains a list of [PersistedPeer]s. For backwards compatibility, plain
+    /// [NodeRecord]s are accepted as well and added as basic nodes.
     pub fn with_basic_nodes_from_file(
         self,
         optional_file: Option<impl AsRef<Path>>,
@@ -1260,11 +1468,44 @@ impl PeersConfig {
             Err(e) => Err(e)?,
         };
         info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peers");
-        let nodes: HashSet<NodeRecord> = serde_json::from_reader(reader)?;
-        Ok(self.with_basic_nodes(nodes))
+        let entries: Vec<PeersFileEntry> = serde_json::from_reader(reader)?;
+        let mut nodes = HashSet::new();
+        let mut persisted = Vec::new();
+        for entry in entries {
+            match entry {
+                PeersFileEntry::Peer(peer) => persisted.push(peer),
+                PeersFileEntry::Record(record) => {
+                    nodes.insert(record);
+                }
+            }
+        }
+        Ok(self.with_basic_nodes(nodes).with_persisted_peers(persisted))
     }
 }
 
+/// A peer as it's persisted across restarts.
+///
+/// On startup, persisted peers are restored with their reputation. Peers that were seen more
+/// recently are dialed first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PersistedPeer {
+    /// The node record of the peer.
+    pub record: NodeRecord,
+    /// The reputation of the peer when it was persisted.
+    pub reputation: i32,
+    /// The unix timestamp in seconds of the last time there was an active session with the peer.
+    pub last_seen: Option<u64>,
+}
+
+/// An entry of the peers file.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum PeersFileEntry {
+    Peer(PersistedPeer),
+    Record(NodeRecord),
+}
+
 /// The durations to use when a backoff should be applied to a peer.
 ///
 /// See also [`BackoffKind`].
@@ -1322,6 +1563,35 @@ impl Default for PeerBackoffDurations {
     }
 }
 
+/// Returns the subnet of the address that outbound connections are limited by, i.e. the /24 of
+/// IPv4 and the /64 of IPv6 addresses.
+///
+/// Returns `None` for addresses in local networks, which are not limited.
+fn subnet(ip: IpAddr) -> Option<IpAddr> {
+    match ip {
+        IpAddr::V4(ip) => {
+            if ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified() {
+                return None
+            }
+            let [a, b, c, _] = ip.octets();
+            Some(Ipv4Addr::new(a, b, c, 0).into())
+        }
+        IpAddr::V6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return subnet(ip.into())
+            }
+            let segments = ip.segments();
+            // unique local (fc00::/7) and link local (fe80::/10) addresses
+            let is_local = (segments[0] & 0xfe00) == 0xfc00 || (segments[0] & 0xffc0) == 0xfe80;
+            if ip.is_loopback() || ip.is_unspecified() || is_local {
+                return None
+            }
+            let [a, b, c, d, ..] = segments;
+            Some(Ipv6Addr::new(a, b, c, d, 0, 0, 0, 0).into())
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum InboundConnectionError {
     ExceedsLimit(usize),
@@ -1336,12 +1606,12 @@ impl Display for InboundConnectionError {
 
 #[cfg(test)]
 mod test {
-    use super::PeersManager;
+    use super::{subnet, PeersManager, PersistedPeer};
     use crate::{
         error::BackoffKind,
         peers::{
             manager::{ConnectionInfo, PeerBackoffDurations, PeerConnectionState},
-            reputation::DEFAULT_REPUTATION,
+            reputation::{BANNED_REPUTATION, DEFAULT_REPUTATION},
             PeerAction,
         },
         session::PendingSessionHandshakeError,
@@ -1362,7 +1632,7 @@ mod test {
         net::{IpAddr, Ipv4Addr, SocketAddr},
         pin::Pin,
         task::{Context, Poll},
-        time::Duration,
+        time::{Duration, SystemTime, UNIX_EPOCH},
     };
 
     struct PeerActionFuture<'a> {
@@ -2236,6 +2506,109 @@ mod test {
         assert!(!peer.remove_after_disconnect);
     }
 
+    #[tokio::test]
+    async fn test_reserved_inbound_slots() {
+        let config = PeersConfig::default().with_max_inbound(1).with_reserved_trusted_inbound(1);
+        let mut peers = PeersManager::new(config);
+        let trusted_peer = PeerId::random();
+        let trusted_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 1)), 8008);
+        peers.add_trusted_peer(trusted_peer, trusted_addr);
+        peers.queued_actions.clear();
+
+        let basic_peer = PeerId::random();
+        let basic_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        peers.on_incoming_pending_session(basic_addr.ip()).unwrap();
+        peers.on_incoming_session_established(basic_peer, basic_addr);
+
+        // the reserved slot can't be used by a peer that isn't trusted
+        let other_peer = PeerId::random();
+        let other_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 3)), 8008);
+        peers.on_incoming_pending_session(other_addr.ip()).unwrap();
+        peers.on_incoming_session_established(other_peer, other_addr);
+        assert!(peers.queued_actions.iter().any(|action| matches!(
+            action,
+            PeerAction::Disconnect { peer_id, reason: Some(DisconnectReason::TooManyPeers) }
+                if *peer_id == other_peer
+        )));
+        assert_eq!(peers.peers[&other_peer].state, PeerConnectionState::DisconnectingIn);
+        peers.queued_actions.clear();
+
+        // but by a trusted peer
+        peers.on_incoming_pending_session(trusted_addr.ip()).unwrap();
+        peers.on_incoming_session_established(trusted_peer, trusted_addr);
+        assert!(!peers
+            .queued_actions
+            .iter()
+            .any(|action| matches!(action, PeerAction::Disconnect { .. })));
+        assert_eq!(peers.peers[&trusted_peer].state, PeerConnectionState::In);
+        assert_eq!(peers.peers[&basic_peer].state, PeerConnectionState::In);
+    }
+
+    #[tokio::test]
+    async fn test_trusted_inbound_evicts_worst_peer() {
+        let config = PeersConfig::default().with_max_inbound(2).with_reserved_trusted_inbound(0);
+        let mut peers = PeersManager::new(config);
+        let trusted_peer = PeerId::random();
+        let trusted_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 1)), 8008);
+        peers.add_trusted_peer(trusted_peer, trusted_addr);
+
+        let good_peer = PeerId::random();
+        let bad_peer = PeerId::random();
+        for (i, peer_id) in [good_peer, bad_peer].into_iter().enumerate() {
+            let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2 + i as u8)), 8008);
+            peers.on_incoming_pending_session(addr.ip()).unwrap();
+            peers.on_incoming_session_established(peer_id, addr);
+        }
+        peers.apply_reputation_change(&bad_peer, ReputationChangeKind::BadMessage);
+        peers.queued_actions.clear();
+
+        // all slots are occupied, but the trusted peer is accepted
+        assert!(peers
+            .on_incoming_pending_session(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 9)))
+            .is_err());
+        peers.on_incoming_pending_session(trusted_addr.ip()).unwrap();
+        peers.on_incoming_session_established(trusted_peer, trusted_addr);
+
+        let disconnected = peers
+            .queued_actions
+            .iter()
+            .filter_map(|action| match action {
+                PeerAction::Disconnect { peer_id, .. } => Some(*peer_id),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(disconnected, vec![bad_peer]);
+        assert_eq!(peers.peers[&trusted_peer].state, PeerConnectionState::In);
+        assert_eq!(peers.peers[&good_peer].state, PeerConnectionState::In);
+    }
+
+    #[tokio::test]
+    async fn test_reserved_outbound_slots() {
+        let config = PeersConfig::default().with_max_outbound(1).with_reserved_trusted_outbound(1);
+        let mut peers = PeersManager::new(config);
+        for i in 0..2 {
+            let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, i)), 8008);
+            peers.add_peer(PeerId::random(), addr, None);
+        }
+        let trusted_peer = PeerId::random();
+        let trusted_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 9)), 8008);
+        peers.add_trusted_peer(trusted_peer, trusted_addr);
+        peers.queued_actions.clear();
+
+        // the trusted peer and one basic peer fill the slots
+        peers.fill_outbound_slots();
+        let dials = peers
+            .queued_actions
+            .iter()
+            .filter_map(|action| match action {
+                PeerAction::Connect { peer_id, .. } => Some(*peer_id),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(dials.len(), 2);
+        assert_eq!(dials[0], trusted_peer);
+    }
+
     #[tokio::test]
     async fn test_max_concurrent_dials() {
         let config = PeersConfig::default();
@@ -2254,4 +2627,91 @@ mod test {
             .count();
         assert_eq!(dials, peer_manager.connection_info.max_concurrent_outbound_dials);
     }
+
+    #[tokio::test]
+    async fn test_max_outbound_per_subnet() {
+        let config = PeersConfig::default().with_max_outbound_per_subnet(2);
+        let mut peer_manager = PeersManager::new(config);
+        for i in 0..4 {
+            let ip = IpAddr::V4(Ipv4Addr::new(1, 2, 3, i));
+            peer_manager.add_peer(PeerId::random(), SocketAddr::new(ip, 8008), None);
+        }
+        // peers in local networks are not limited
+        for i in 0..4 {
+            let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 1, i));
+            peer_manager.add_peer(PeerId::random(), SocketAddr::new(ip, 8008), None);
+        }
+        let trusted_ip = IpAddr::V4(Ipv4Addr::new(1, 2, 3, 10));
+        peer_manager.add_trusted_peer(PeerId::random(), SocketAddr::new(trusted_ip, 8008));
+
+        peer_manager.fill_outbound_slots();
+        let dials = peer_manager
+            .queued_actions
+            .iter()
+            .filter_map(|ev| match ev {
+                PeerAction::Connect { remote_addr, .. } => Some(remote_addr.ip()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(dials.len(), 7);
+        assert!(dials.contains(&trusted_ip));
+        assert_eq!(dials.iter().filter(|ip| ip.is_loopback()).count(), 4);
+    }
+
+    #[test]
+    fn test_subnet() {
+        assert_eq!(subnet("1.2.3.4".parse().unwrap()), Some(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 0))));
+        assert_eq!(subnet("::ffff:1.2.3.4".parse().unwrap()), subnet("1.2.3.5".parse().unwrap()));
+        assert_eq!(
+            subnet("2001:db8:1:2:3:4:5:6".parse().unwrap()),
+            Some("2001:db8:1:2::".parse().unwrap())
+        );
+        assert_eq!(subnet("192.168.1.1".parse().unwrap()), None);
+        assert_eq!(subnet("fd00::1".parse().unwrap()), None);
+        assert_eq!(subnet("fe80::1".parse().unwrap()), None);
+    }
+
+    #[tokio::test]
+    async fn test_persisted_peers() {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let persisted = |last_seen, reputation| PersistedPeer {
+            record: NodeRecord::new(addr, PeerId::random()),
+            reputation,
+            last_seen,
+        };
+        let recent = persisted(Some(now - 60), DEFAULT_REPUTATION);
+        let old = persisted(Some(now - 60 * 60), DEFAULT_REPUTATION + 1024);
+        let banned = persisted(Some(now), BANNED_REPUTATION - 1);
+
+        let config = PeersConfig::default().with_persisted_peers(vec![old, banned, recent]);
+        let mut peers = PeersManager::new(config);
+        peers.add_peer(PeerId::random(), addr, None);
+
+        assert_eq!(peers.peers.len(), 3);
+        assert!(!peers.peers.contains_key(&banned.record.id));
+        let persisted = peers.iter_persisted_peers().collect::<HashSet<_>>();
+        assert!(persisted.contains(&recent));
+        assert!(persisted.contains(&old));
+
+        // the most recently seen peer is dialed first, even if an older one has a higher reputation
+        let (peer_id, _) = peers.best_unconnected().unwrap();
+        assert_eq!(peer_id, recent.record.id);
+    }
+
+    #[test]
+    fn test_peers_file() {
+        let record = NodeRecord::new(
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 30303),
+            PeerId::random(),
+        );
+        let peer = PersistedPeer { record, reputation: -1024, last_seen: Some(1700000000) };
+        let json = format!(r#"["{record}", {}]"#, serde_json::to_string(&peer).unwrap());
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), json).unwrap();
+
+        let config = PeersConfig::default().with_basic_nodes_from_file(Some(file.path())).unwrap();
+        assert_eq!(config.basic_nodes, HashSet::from([record]));
+        assert_eq!(config.persisted_peers, vec![peer]);
+    }
 }
@@ -4,7 +4,9 @@ mod manager;
 mod reputation;
 
 pub(crate) use manager::InboundConnectionError;
-pub use manager::{ConnectionInfo, Peer, PeerAction, PeersConfig, PeersHandle, PeersManager};
+pub use manager::{
+    ConnectionInfo, Peer, PeerAction, PeersConfig, PeersHandle, PeersManager, PersistedPeer,
+};
 pub use reputation::ReputationChangeWeights;
 pub use reth_network_api::PeerKind;
 
@@ -16,3 +18,9 @@ pub(crate) const DEFAULT_MAX_PEERS_INBOUND: usize = 30;
 
 /// Maximum number of available slots concurrent outgoing dials.
 pub(crate) const DEFAULT_MAX_CONCURRENT_DIALS: usize = 10;
+
+/// Maximum number of outbound sessions to peers in the same subnet.
+pub(crate) const DEFAULT_MAX_OUTBOUND_PER_SUBNET: usize = 2;
+
+/// Number of additional slots per direction that can only be used by trusted peers.
+pub(crate) const DEFAULT_RESERVED_TRUSTED_SLOTS: usize = 10;
@@ -205,7 +205,7 @@ pub(crate) trait DynConnectionHandler: Send + Sync + 'static {
     fn protocol(&self) -> Protocol;
 
     fn on_unsupported_by_peer(
-        self,
+        self: Box<Self>,
         supported: &SharedCapabilities,
         direction: Direction,
         peer_id: PeerId,
@@ -228,12 +228,12 @@ where
     }
 
     fn on_unsupported_by_peer(
-        self,
+        self: Box<Self>,
         supported: &SharedCapabilities,
         direction: Direction,
         peer_id: PeerId,
     ) -> OnNotSupported {
-        T::on_unsupported_by_peer(self, supported, direction, peer_id)
+        T::on_unsupported_by_peer(*self, supported, direction, peer_id)
     }
 
     fn into_connection(
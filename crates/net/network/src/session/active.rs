@@ -6,7 +6,7 @@ use crate::{
         config::INITIAL_REQUEST_TIMEOUT,
         conn::EthRlpxConnection,
         handle::{ActiveSessionMessage, SessionCommand},
-        SessionId,
+        PeerLatencyTracker, SessionId,
     },
 };
 use core::sync::atomic::Ordering;
@@ -17,7 +17,7 @@ use reth_eth_wire::{
     capability::Capabilities,
     errors::{EthHandshakeError, EthStreamError, P2PStreamError},
     message::{EthBroadcastMessage, RequestPair},
-    DisconnectP2P, DisconnectReason, EthMessage,
+    DisconnectP2P, DisconnectReason, EthMessage, EthMessageID,
 };
 use reth_interfaces::p2p::error::RequestError;
 use reth_metrics::common::mpsc::MeteredPollSender;
@@ -95,6 +95,8 @@ pub(crate) struct ActiveSession {
     /// If an [ActiveSession] does not receive a response at all within this duration then it is
     /// considered a protocol violation and the session will initiate a drop.
     pub(crate) protocol_breach_request_timeout: Duration,
+    /// Tracks the latencies of the responses to the requests sent to the remote peer.
+    pub(crate) latency: Arc<PeerLatencyTracker>,
     /// Used to reserve a slot to guarantee that the termination message is delivered
     pub(crate) terminate_message: Option<(PollSender<ActiveSessionMessage>, ActiveSessionMessage)>,
 }
@@ -153,6 +155,7 @@ impl ActiveSession {
                         RequestState::Waiting(PeerRequest::$item { response, .. }) => {
                             let _ = response.send(Ok(message));
                             self.update_request_timeout(req.timestamp, Instant::now());
+                            self.latency.record(EthMessageID::$item, req.timestamp.elapsed());
                         }
                         RequestState::Waiting(request) => {
                             request.send_bad_response();
@@ -160,6 +163,7 @@ impl ActiveSession {
                         RequestState::TimedOut => {
                             // request was already timed out internally
                             self.update_request_timeout(req.timestamp, Instant::now());
+                            self.latency.record(EthMessageID::$item, req.timestamp.elapsed());
                         }
                     };
                 } else {
@@ -771,7 +775,10 @@ mod tests {
         EthStream, GetBlockBodies, HelloMessageWithProtocols, P2PStream, Status, StatusBuilder,
         UnauthedEthStream, UnauthedP2PStream,
     };
-    use reth_net_common::bandwidth_meter::{BandwidthMeter, MeteredStream};
+    use reth_net_common::{
+        bandwidth_meter::{BandwidthMeter, MeteredStream},
+        io_meter::PeerIOMeters,
+    };
     use reth_primitives::{ForkFilter, Hardfork, MAINNET};
     use secp256k1::{SecretKey, SECP256K1};
     use std::time::Duration;
@@ -842,7 +849,7 @@ mod tests {
             let (_disconnect_tx, disconnect_rx) = oneshot::channel();
             let (pending_sessions_tx, pending_sessions_rx) = mpsc::channel(1);
             let metered_stream =
-                MeteredStream::new_with_meter(stream, self.bandwidth_meter.clone());
+                MeteredStream::new_with_meter(stream.into(), self.bandwidth_meter.clone());
 
             tokio::task::spawn(start_pending_incoming_session(
                 disconnect_rx,
@@ -854,6 +861,7 @@ mod tests {
                 self.hello.clone(),
                 self.status,
                 self.fork_filter.clone(),
+                PeerIOMeters::disabled(),
                 Default::default(),
             ));
 
@@ -898,6 +906,7 @@ mod tests {
                             INITIAL_REQUEST_TIMEOUT.as_millis() as u64,
                         )),
                         protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
+                        latency: Default::default(),
                         terminate_message: None,
                     }
                 }
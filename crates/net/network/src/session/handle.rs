@@ -2,7 +2,7 @@
 
 use crate::{
     message::PeerMessage,
-    session::{conn::EthRlpxConnection, Direction, SessionId},
+    session::{conn::EthRlpxConnection, Direction, PeerLatencyTracker, SessionId},
 };
 use reth_ecies::ECIESError;
 use reth_eth_wire::{
@@ -10,6 +10,7 @@ use reth_eth_wire::{
     errors::EthStreamError,
     DisconnectReason, EthVersion, Status,
 };
+use reth_net_common::compression_meter::CompressionMeter;
 use reth_network_api::PeerInfo;
 use reth_primitives::PeerId;
 use std::{io, net::SocketAddr, sync::Arc, time::Instant};
@@ -74,6 +75,10 @@ pub struct ActiveSessionHandle {
     pub(crate) local_addr: Option<SocketAddr>,
     /// The Status message the peer sent for the `eth` handshake
     pub(crate) status: Arc<Status>,
+    /// Meters the compression of the messages of this session.
+    pub(crate) compression_meter: CompressionMeter,
+    /// Tracks the latencies of the requests sent to the peer.
+    pub(crate) latency: Arc<PeerLatencyTracker>,
 }
 
 // === impl ActiveSessionHandle ===
@@ -134,6 +139,16 @@ impl ActiveSessionHandle {
         self.remote_addr
     }
 
+    /// Returns the [CompressionMeter] of this session.
+    pub fn compression_meter(&self) -> &CompressionMeter {
+        &self.compression_meter
+    }
+
+    /// Returns the [PeerLatencyTracker] of this session.
+    pub fn latency(&self) -> &PeerLatencyTracker {
+        &self.latency
+    }
+
     /// Extracts the [PeerInfo] from the session handle.
     pub(crate) fn peer_info(&self) -> PeerInfo {
         PeerInfo {
@@ -146,6 +161,7 @@ impl ActiveSessionHandle {
             eth_version: self.version,
             status: self.status.clone(),
             session_established: self.established,
+            latency: self.latency.summary(),
         }
     }
 }
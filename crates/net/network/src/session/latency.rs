@@ -0,0 +1,167 @@
+//! Tracking of the latencies of the requests sent to a peer.
+
+use crate::metrics::EthRequestLatencyMetrics;
+use reth_eth_wire::EthMessageID;
+use reth_network_api::{PeerLatency, RequestLatency};
+use std::{
+    fmt,
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
+
+/// The request message types whose latencies are tracked.
+const TRACKED_REQUESTS: [EthMessageID; 5] = [
+    EthMessageID::GetBlockHeaders,
+    EthMessageID::GetBlockBodies,
+    EthMessageID::GetPooledTransactions,
+    EthMessageID::GetNodeData,
+    EthMessageID::GetReceipts,
+];
+
+/// The upper bounds of the histogram buckets in milliseconds, the last bucket is unbounded.
+const BUCKETS_MS: [u64; 11] = [5, 10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000];
+
+/// Tracks the latencies of the requests sent to a peer by message type, from sending the request
+/// until receiving the response.
+///
+/// This is shared by the session that records the latencies and the consumers of the summary, see
+/// [`PeerLatency`].
+pub struct PeerLatencyTracker {
+    histograms: Mutex<[LatencyHistogram; TRACKED_REQUESTS.len()]>,
+    metrics: [EthRequestLatencyMetrics; TRACKED_REQUESTS.len()],
+}
+
+impl PeerLatencyTracker {
+    /// Records the latency of a response to a request with the given message type.
+    pub(crate) fn record(&self, id: EthMessageID, latency: Duration) {
+        let Some(index) = tracked_index(id) else { return };
+        self.metrics[index].request_latency.record(latency.as_secs_f64());
+        self.lock()[index].record(latency);
+    }
+
+    /// Returns the mean latency of the requests with the given message type, if the peer responded
+    /// to any.
+    pub(crate) fn mean(&self, id: EthMessageID) -> Option<Duration> {
+        let index = tracked_index(id)?;
+        self.lock()[index].summary().map(|latency| latency.mean)
+    }
+
+    /// Returns the summary of the latencies of all message types the peer responded to.
+    pub fn summary(&self) -> PeerLatency {
+        let histograms = self.lock();
+        let requests = TRACKED_REQUESTS
+            .iter()
+            .zip(histograms.iter())
+            .filter_map(|(id, histogram)| Some((*id, histogram.summary()?)))
+            .collect();
+        PeerLatency { requests }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, [LatencyHistogram; TRACKED_REQUESTS.len()]> {
+        self.histograms.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for PeerLatencyTracker {
+    fn default() -> Self {
+        Self {
+            histograms: Default::default(),
+            metrics: TRACKED_REQUESTS.map(|id| {
+                EthRequestLatencyMetrics::new_with_labels(&[("message", format!("{id:?}"))])
+            }),
+        }
+    }
+}
+
+impl fmt::Debug for PeerLatencyTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PeerLatencyTracker").field("summary", &self.summary()).finish()
+    }
+}
+
+/// Returns the index of the tracked request message type.
+fn tracked_index(id: EthMessageID) -> Option<usize> {
+    TRACKED_REQUESTS.iter().position(|tracked| *tracked == id)
+}
+
+/// A histogram of latencies with fixed buckets, see [BUCKETS_MS].
+#[derive(Debug, Default)]
+struct LatencyHistogram {
+    buckets: [u64; BUCKETS_MS.len() + 1],
+    count: u64,
+    sum: Duration,
+    max: Duration,
+}
+
+impl LatencyHistogram {
+    fn record(&mut self, latency: Duration) {
+        let millis = latency.as_millis();
+        let bucket = BUCKETS_MS.iter().position(|bound| millis <= *bound as u128);
+        self.buckets[bucket.unwrap_or(BUCKETS_MS.len())] += 1;
+        self.count += 1;
+        self.sum = self.sum.saturating_add(latency);
+        self.max = self.max.max(latency);
+    }
+
+    fn summary(&self) -> Option<RequestLatency> {
+        if self.count == 0 {
+            return None
+        }
+        Some(RequestLatency {
+            count: self.count,
+            mean: Duration::from_nanos((self.sum.as_nanos() / self.count as u128) as u64),
+            p50: self.quantile(0.5),
+            p95: self.quantile(0.95),
+            max: self.max,
+        })
+    }
+
+    /// Returns the upper bound of the bucket that contains the quantile, at most the maximum.
+    fn quantile(&self, quantile: f64) -> Duration {
+        let rank = (self.count as f64 * quantile).ceil() as u64;
+        let mut seen = 0;
+        for (bucket, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return BUCKETS_MS
+                    .get(bucket)
+                    .map_or(self.max, |bound| Duration::from_millis(*bound).min(self.max))
+            }
+        }
+        self.max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarize_latencies() {
+        let tracker = PeerLatencyTracker::default();
+        assert_eq!(tracker.summary(), PeerLatency::default());
+
+        for millis in [1, 20, 30, 49, 400] {
+            tracker.record(EthMessageID::GetBlockHeaders, Duration::from_millis(millis));
+        }
+        // not a request
+        tracker.record(EthMessageID::BlockHeaders, Duration::from_millis(1));
+
+        let summary = tracker.summary();
+        assert_eq!(
+            summary.requests,
+            vec![(
+                EthMessageID::GetBlockHeaders,
+                RequestLatency {
+                    count: 5,
+                    mean: Duration::from_millis(100),
+                    p50: Duration::from_millis(50),
+                    p95: Duration::from_millis(400),
+                    max: Duration::from_millis(400),
+                }
+            )]
+        );
+        assert_eq!(tracker.mean(EthMessageID::GetBlockHeaders), Some(Duration::from_millis(100)));
+        assert_eq!(tracker.mean(EthMessageID::GetBlockBodies), None);
+    }
+}
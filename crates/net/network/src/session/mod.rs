@@ -2,21 +2,24 @@
 
 use crate::{
     message::PeerMessage,
-    metrics::SessionManagerMetrics,
+    metrics::{SessionManagerMetrics, NETWORK_IO_SCOPE},
     session::{active::ActiveSession, config::SessionCounter},
+    transport::{PeerStream, Transport},
 };
 use fnv::FnvHashMap;
 use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
     capability::{Capabilities, CapabilityMessage},
-    errors::EthStreamError,
+    errors::{EthStreamError, P2PStreamError},
     DisconnectReason, EthVersion, HelloMessageWithProtocols, Status, UnauthedEthStream,
     UnauthedP2PStream,
 };
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_net_common::{
     bandwidth_meter::{BandwidthMeter, MeteredStream},
+    compression_meter::CompressionMeter,
+    io_meter::{NetworkIOMeterMetrics, PeerIOMeters, DEFAULT_MAX_LABELED_PEERS},
     stream::HasRemoteAddr,
 };
 use reth_primitives::{ForkFilter, ForkId, ForkTransition, Head, PeerId};
@@ -32,7 +35,6 @@ use std::{
 };
 use tokio::{
     io::{AsyncRead, AsyncWrite},
-    net::TcpStream,
     sync::{mpsc, oneshot},
 };
 use tokio_stream::wrappers::ReceiverStream;
@@ -43,13 +45,17 @@ mod active;
 mod config;
 mod conn;
 mod handle;
+mod latency;
 pub use crate::message::PeerRequestSender;
-use crate::protocol::{IntoRlpxSubProtocol, RlpxSubProtocolHandlers, RlpxSubProtocols};
+use crate::protocol::{
+    IntoRlpxSubProtocol, OnNotSupported, RlpxSubProtocolHandlers, RlpxSubProtocols,
+};
 pub use config::{SessionLimits, SessionsConfig};
 pub use handle::{
     ActiveSessionHandle, ActiveSessionMessage, PendingSessionEvent, PendingSessionHandle,
     SessionCommand,
 };
+pub use latency::PeerLatencyTracker;
 use reth_eth_wire::multiplex::RlpxProtocolMultiplexer;
 pub use reth_network_api::{Direction, PeerInfo};
 
@@ -106,8 +112,14 @@ pub struct SessionManager {
     active_session_rx: ReceiverStream<ActiveSessionMessage>,
     /// Additional RLPx sub-protocols to be used by the session manager.
     extra_protocols: RlpxSubProtocols,
+    /// The transport over which outgoing connections are established.
+    transport: Arc<dyn Transport>,
     /// Used to measure inbound & outbound bandwidth across all managed streams
     bandwidth_meter: BandwidthMeter,
+    /// Used to measure the compression of messages across all managed streams
+    compression_meter: CompressionMeter,
+    /// Used to record the traffic of the managed streams by peer
+    io_meters: PeerIOMeters,
     /// Metrics for the session manager.
     metrics: SessionManagerMetrics,
 }
@@ -125,7 +137,9 @@ impl SessionManager {
         hello_message: HelloMessageWithProtocols,
         fork_filter: ForkFilter,
         extra_protocols: RlpxSubProtocols,
+        transport: Arc<dyn Transport>,
         bandwidth_meter: BandwidthMeter,
+        compression_meter: CompressionMeter,
     ) -> Self {
         let (pending_sessions_tx, pending_sessions_rx) = mpsc::channel(config.session_event_buffer);
         let (active_session_tx, active_session_rx) = mpsc::channel(config.session_event_buffer);
@@ -149,7 +163,10 @@ impl SessionManager {
             active_session_tx: MeteredPollSender::new(active_session_tx, "network_active_session"),
             active_session_rx: ReceiverStream::new(active_session_rx),
             bandwidth_meter,
+            compression_meter,
+            io_meters: PeerIOMeters::new(NETWORK_IO_SCOPE, DEFAULT_MAX_LABELED_PEERS),
             extra_protocols,
+            transport,
             metrics: Default::default(),
         }
     }
@@ -208,13 +225,13 @@ impl SessionManager {
         transition
     }
 
-    /// An incoming TCP connection was received. This starts the authentication process to turn this
+    /// An incoming connection was received. This starts the authentication process to turn this
     /// stream into an active peer session.
     ///
     /// Returns an error if the configured limit has been reached.
     pub(crate) fn on_incoming(
         &mut self,
-        stream: TcpStream,
+        stream: PeerStream,
         remote_addr: SocketAddr,
     ) -> Result<SessionId, ExceedsSessionLimit> {
         self.counter.ensure_pending_inbound()?;
@@ -230,11 +247,14 @@ impl SessionManager {
 
         let (disconnect_tx, disconnect_rx) = oneshot::channel();
         let pending_events = self.pending_sessions_tx.clone();
-        let metered_stream = MeteredStream::new_with_meter(stream, self.bandwidth_meter.clone());
+        // the peer is only known after the handshake
+        let metered_stream = MeteredStream::new_with_meter(stream, self.bandwidth_meter.clone())
+            .with_metrics(self.io_meters.unknown_peer());
         let secret_key = self.secret_key;
         let hello_message = self.hello_message.clone();
         let status = self.status;
         let fork_filter = self.fork_filter.clone();
+        let io_meters = self.io_meters.clone();
         let extra_handlers = self.extra_protocols.on_incoming(remote_addr);
         self.spawn(start_pending_incoming_session(
             disconnect_rx,
@@ -246,6 +266,7 @@ impl SessionManager {
             hello_message,
             status,
             fork_filter,
+            io_meters,
             extra_handlers,
         ));
 
@@ -269,7 +290,9 @@ impl SessionManager {
             let hello_message = self.hello_message.clone();
             let fork_filter = self.fork_filter.clone();
             let status = self.status;
+            let transport = Arc::clone(&self.transport);
             let band_with_meter = self.bandwidth_meter.clone();
+            let io_metrics = self.io_meters.peer(remote_peer_id);
             let extra_handlers = self.extra_protocols.on_outgoing(remote_addr, remote_peer_id);
             self.spawn(start_pending_outbound_session(
                 disconnect_rx,
@@ -281,7 +304,9 @@ impl SessionManager {
                 hello_message,
                 status,
                 fork_filter,
+                transport,
                 band_with_meter,
+                io_metrics,
                 extra_handlers,
             ));
 
@@ -307,7 +332,7 @@ impl SessionManager {
     /// Sends a disconnect message to the peer with the given [DisconnectReason].
     pub(crate) fn disconnect_incoming_connection(
         &mut self,
-        stream: TcpStream,
+        stream: PeerStream,
         reason: DisconnectReason,
     ) {
         let secret_key = self.secret_key;
@@ -420,7 +445,7 @@ impl SessionManager {
                 local_addr,
                 peer_id,
                 capabilities,
-                conn,
+                mut conn,
                 status,
                 direction,
                 client_id,
@@ -462,9 +487,14 @@ impl SessionManager {
                     self.initial_internal_request_timeout.as_millis() as u64,
                 ));
 
+                let latency = Arc::new(PeerLatencyTracker::default());
+
                 // negotiated version
                 let version = conn.version();
 
+                let compression_meter = self.compression_meter.session();
+                conn.inner_mut().set_compression_meter(compression_meter.clone());
+
                 let session = ActiveSession {
                     next_id: 0,
                     remote_peer_id: peer_id,
@@ -484,6 +514,7 @@ impl SessionManager {
                     ),
                     internal_request_timeout: Arc::clone(&timeout),
                     protocol_breach_request_timeout: self.protocol_breach_request_timeout,
+                    latency: Arc::clone(&latency),
                     terminate_message: None,
                 };
 
@@ -502,6 +533,8 @@ impl SessionManager {
                     client_version: Arc::clone(&client_version),
                     remote_addr,
                     local_addr,
+                    compression_meter,
+                    latency: Arc::clone(&latency),
                 };
 
                 self.active_sessions.insert(peer_id, handle);
@@ -521,6 +554,7 @@ impl SessionManager {
                     messages,
                     direction,
                     timeout,
+                    latency,
                 })
             }
             PendingSessionEvent::Disconnected { remote_addr, session_id, direction, error } => {
@@ -650,6 +684,8 @@ pub enum SessionEvent {
         /// The maximum time that the session waits for a response from the peer before timing out
         /// the connection
         timeout: Arc<AtomicU64>,
+        /// The latencies of the requests sent to the peer
+        latency: Arc<PeerLatencyTracker>,
     },
     /// The peer was already connected with another session.
     AlreadyConnected {
@@ -761,13 +797,14 @@ pub struct ExceedsSessionLimit(pub(crate) u32);
 pub(crate) async fn start_pending_incoming_session(
     disconnect_rx: oneshot::Receiver<()>,
     session_id: SessionId,
-    stream: MeteredStream<TcpStream>,
+    stream: MeteredStream<PeerStream>,
     events: mpsc::Sender<PendingSessionEvent>,
     remote_addr: SocketAddr,
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
     status: Status,
     fork_filter: ForkFilter,
+    io_meters: PeerIOMeters,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
     authenticate(
@@ -781,6 +818,7 @@ pub(crate) async fn start_pending_incoming_session(
         hello,
         status,
         fork_filter,
+        Some(io_meters),
         extra_handlers,
     )
     .await
@@ -799,15 +837,14 @@ async fn start_pending_outbound_session(
     hello: HelloMessageWithProtocols,
     status: Status,
     fork_filter: ForkFilter,
+    transport: Arc<dyn Transport>,
     bandwidth_meter: BandwidthMeter,
+    io_metrics: Option<NetworkIOMeterMetrics>,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
-    let stream = match TcpStream::connect(remote_addr).await {
+    let stream = match transport.connect(remote_addr).await {
         Ok(stream) => {
-            if let Err(err) = stream.set_nodelay(true) {
-                tracing::warn!(target: "net::session", "set nodelay failed: {:?}", err);
-            }
-            MeteredStream::new_with_meter(stream, bandwidth_meter)
+            MeteredStream::new_with_meter(stream, bandwidth_meter).with_metrics(io_metrics)
         }
         Err(error) => {
             let _ = events
@@ -832,17 +869,21 @@ async fn start_pending_outbound_session(
         hello,
         status,
         fork_filter,
+        None,
         extra_handlers,
     )
     .await
 }
 
 /// Authenticates a session
+///
+/// If `io_meters` are given, the traffic of the stream is recorded with the metrics of the remote
+/// peer once it's known from the `ECIES` handshake.
 #[allow(clippy::too_many_arguments)]
 async fn authenticate(
     disconnect_rx: oneshot::Receiver<()>,
     events: mpsc::Sender<PendingSessionEvent>,
-    stream: MeteredStream<TcpStream>,
+    stream: MeteredStream<PeerStream>,
     session_id: SessionId,
     remote_addr: SocketAddr,
     secret_key: SecretKey,
@@ -850,10 +891,11 @@ async fn authenticate(
     hello: HelloMessageWithProtocols,
     status: Status,
     fork_filter: ForkFilter,
+    io_meters: Option<PeerIOMeters>,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
-    let local_addr = stream.inner().local_addr().ok();
-    let stream = match get_eciess_stream(stream, secret_key, direction).await {
+    let local_addr = stream.inner().local_addr();
+    let mut stream = match get_eciess_stream(stream, secret_key, direction).await {
         Ok(stream) => stream,
         Err(error) => {
             let _ = events
@@ -868,6 +910,11 @@ async fn authenticate(
         }
     };
 
+    if let Some(io_meters) = io_meters {
+        let metrics = io_meters.peer(stream.remote_id());
+        stream.transport_mut().set_metrics(metrics);
+    }
+
     let unauthed = UnauthedP2PStream::new(stream);
 
     let auth = authenticate_stream(
@@ -923,7 +970,7 @@ async fn get_eciess_stream<Io: AsyncRead + AsyncWrite + Unpin + HasRemoteAddr>(
 /// negotiate the additional protocols.
 #[allow(clippy::too_many_arguments)]
 async fn authenticate_stream(
-    stream: UnauthedP2PStream<ECIESStream<MeteredStream<TcpStream>>>,
+    stream: UnauthedP2PStream<ECIESStream<MeteredStream<PeerStream>>>,
     session_id: SessionId,
     remote_addr: SocketAddr,
     local_addr: Option<SocketAddr>,
@@ -937,7 +984,7 @@ async fn authenticate_stream(
     extra_handlers.retain(|handler| hello.try_add_protocol(handler.protocol()).is_ok());
 
     // conduct the p2p handshake and return the authenticated stream
-    let (p2p_stream, their_hello) = match stream.handshake(hello).await {
+    let (mut p2p_stream, their_hello) = match stream.handshake(hello).await {
         Ok(stream_res) => stream_res,
         Err(err) => {
             return PendingSessionEvent::Disconnected {
@@ -962,11 +1009,34 @@ async fn authenticate_stream(
         }
     };
 
-    let (conn, their_status) = if p2p_stream.shared_capabilities().len() == 1 {
+    // Let the handlers of the extra protocols the remote doesn't support decide whether the
+    // connection is still useful
+    let remote_peer_id = their_hello.id;
+    let mut supported_handlers = Vec::with_capacity(extra_handlers.len());
+    for handler in extra_handlers.into_iter() {
+        let shared_capabilities = p2p_stream.shared_capabilities();
+        if shared_capabilities.ensure_matching_capability(&handler.protocol().cap).is_ok() {
+            supported_handlers.push(handler);
+            continue
+        }
+        if handler.on_unsupported_by_peer(shared_capabilities, direction, remote_peer_id) ==
+            OnNotSupported::Disconnect
+        {
+            let _ = p2p_stream.disconnect(DisconnectReason::UselessPeer).await;
+            return PendingSessionEvent::Disconnected {
+                remote_addr,
+                session_id,
+                direction,
+                error: Some(P2PStreamError::CapabilityNotShared.into()),
+            }
+        }
+    }
+
+    // Before trying status handshake, set up the version to negotiated shared version
+    status.set_eth_version(eth_version);
+
+    let (conn, their_status) = if supported_handlers.is_empty() {
         // if the hello handshake was successful we can try status handshake
-        //
-        // Before trying status handshake, set up the version to negotiated shared version
-        status.set_eth_version(eth_version);
         let eth_unauthed = UnauthedEthStream::new(p2p_stream);
         let (eth_stream, their_status) = match eth_unauthed.handshake(status, fork_filter).await {
             Ok(stream_res) => stream_res,
@@ -981,16 +1051,26 @@ async fn authenticate_stream(
         };
         (eth_stream.into(), their_status)
     } else {
-        // Multiplex the stream with the extra protocols
-        let (mut multiplex_stream, their_status) = RlpxProtocolMultiplexer::new(p2p_stream)
+        // Multiplex the stream with the extra protocols, the messages of each protocol are routed
+        // to its handler by their message id
+        let (mut multiplex_stream, their_status) = match RlpxProtocolMultiplexer::new(p2p_stream)
             .into_eth_satellite_stream(status, fork_filter)
             .await
-            .unwrap();
+        {
+            Ok(stream_res) => stream_res,
+            Err(err) => {
+                return PendingSessionEvent::Disconnected {
+                    remote_addr,
+                    session_id,
+                    direction,
+                    error: Some(err),
+                }
+            }
+        };
 
         // install additional handlers
-        for handler in extra_handlers.into_iter() {
+        for handler in supported_handlers {
             let cap = handler.protocol().cap;
-            let remote_peer_id = their_hello.id;
             multiplex_stream
                 .install_protocol(&cap, move |conn| {
                     handler.into_connection(direction, remote_peer_id, conn)
@@ -1005,7 +1085,7 @@ async fn authenticate_stream(
         session_id,
         remote_addr,
         local_addr,
-        peer_id: their_hello.id,
+        peer_id: remote_peer_id,
         capabilities: Arc::new(Capabilities::from(their_hello.capabilities)),
         status: Arc::new(their_status),
         conn,
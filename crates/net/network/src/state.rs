@@ -10,6 +10,7 @@ use crate::{
         PeerResponseResult,
     },
     peers::{PeerAction, PeersManager},
+    session::PeerLatencyTracker,
     FetchClient,
 };
 use reth_eth_wire::{
@@ -133,13 +134,21 @@ where
         status: Arc<Status>,
         request_tx: PeerRequestSender,
         timeout: Arc<AtomicU64>,
+        latency: Arc<PeerLatencyTracker>,
     ) {
         debug_assert!(!self.active_peers.contains_key(&peer), "Already connected; not possible");
 
         // find the corresponding block number
         let block_number =
             self.client.block_number(status.blockhash).ok().flatten().unwrap_or_default();
-        self.state_fetcher.new_active_peer(peer, status.blockhash, block_number, timeout);
+        self.state_fetcher.new_active_peer(
+            peer,
+            status.blockhash,
+            block_number,
+            Arc::clone(&capabilities),
+            timeout,
+            latency,
+        );
 
         self.active_peers.insert(
             peer,
@@ -575,6 +584,7 @@ mod tests {
             Arc::default(),
             peer_tx,
             Arc::new(AtomicU64::new(1)),
+            Default::default(),
         );
 
         assert!(state.active_peers.contains_key(&peer_id));
@@ -0,0 +1,443 @@
+//! An in-process network simulator for testing multiple nodes without real sockets.
+
+use crate::{
+    listener::ConnectionListener,
+    transport::{PeerIo, PeerStream, Transport},
+};
+use futures::{future::BoxFuture, FutureExt};
+use reth_net_common::stream::HasRemoteAddr;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    future::Future,
+    io,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    sync::mpsc,
+    time::{Instant, Sleep},
+};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+/// The port the nodes of the [`SimNetwork`] listen on if no port is configured.
+const DEFAULT_PORT: u16 = 30303;
+
+/// A simulated network that connects nodes over in-memory streams.
+///
+/// Every node gets its own [`SimTransport`] with a unique IP address, see
+/// [`SimNetwork::transport`]. Nodes that use these transports can connect to each other like over
+/// TCP, but the latency of every link can be controlled and nodes can be partitioned from each
+/// other.
+///
+/// The latency is measured with the tokio clock, so tests that pause the time can simulate high
+/// latencies without waiting for them.
+#[derive(Debug, Clone, Default)]
+pub struct SimNetwork {
+    inner: Arc<Mutex<SimNetworkInner>>,
+}
+
+// === impl SimNetwork ===
+
+impl SimNetwork {
+    /// Creates a new [`SimNetwork`] without latency.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new [`SimNetwork`] with the given latency for all links.
+    pub fn with_latency(latency: Duration) -> Self {
+        let this = Self::default();
+        this.lock().default_latency = latency;
+        this
+    }
+
+    /// Returns a new [`Transport`] for a node with its own IP address in this network.
+    pub fn transport(&self) -> SimTransport {
+        let mut inner = self.lock();
+        inner.num_nodes += 1;
+        let [_, _, hi, lo] = inner.num_nodes.to_be_bytes();
+        SimTransport { network: self.clone(), ip: IpAddr::V4(Ipv4Addr::new(10, 0, hi, lo)) }
+    }
+
+    /// Sets the latency of the link between the nodes with the given addresses, in both
+    /// directions.
+    ///
+    /// Only affects connections that are established afterwards.
+    pub fn set_latency(&self, a: SocketAddr, b: SocketAddr, latency: Duration) {
+        self.lock().latencies.insert(Link::new(a.ip(), b.ip()), latency);
+    }
+
+    /// Partitions the nodes with the given addresses from each other.
+    ///
+    /// Established connections between the nodes are reset and new connections are refused until
+    /// the partition is healed, see [`SimNetwork::heal`].
+    pub fn partition(&self, a: SocketAddr, b: SocketAddr) {
+        let link = Link::new(a.ip(), b.ip());
+        let mut inner = self.lock();
+        inner.partitions.insert(link);
+        inner.connections.retain(|conn| {
+            if conn.link == link {
+                conn.reset();
+                return false
+            }
+            conn.is_open()
+        });
+    }
+
+    /// Heals the partition between the nodes with the given addresses.
+    pub fn heal(&self, a: SocketAddr, b: SocketAddr) {
+        self.lock().partitions.remove(&Link::new(a.ip(), b.ip()));
+    }
+
+    /// Heals all partitions.
+    pub fn heal_all(&self) {
+        self.lock().partitions.clear();
+    }
+
+    /// Returns `true` if the nodes with the given addresses are partitioned from each other.
+    pub fn is_partitioned(&self, a: SocketAddr, b: SocketAddr) -> bool {
+        self.lock().partitions.contains(&Link::new(a.ip(), b.ip()))
+    }
+
+    fn listen(&self, addr: SocketAddr) -> io::Result<ConnectionListener> {
+        let mut inner = self.lock();
+        if inner.listeners.get(&addr).is_some_and(|listener| !listener.is_closed()) {
+            return Err(io::ErrorKind::AddrInUse.into())
+        }
+        let (tx, rx) = mpsc::unbounded_channel();
+        inner.listeners.insert(addr, tx);
+        Ok(ConnectionListener::from_stream(addr, UnboundedReceiverStream::new(rx)))
+    }
+
+    fn connect(&self, local_ip: IpAddr, remote_addr: SocketAddr) -> io::Result<PeerStream> {
+        let mut inner = self.lock();
+        let link = Link::new(local_ip, remote_addr.ip());
+        if inner.partitions.contains(&link) {
+            return Err(io::ErrorKind::ConnectionRefused.into())
+        }
+        let Some(listener) =
+            inner.listeners.get(&remote_addr).filter(|tx| !tx.is_closed()).cloned()
+        else {
+            return Err(io::ErrorKind::ConnectionRefused.into())
+        };
+
+        inner.next_port = inner.next_port.checked_add(1).unwrap_or(DEFAULT_PORT + 1);
+        let local_addr = SocketAddr::new(local_ip, inner.next_port);
+        let latency = inner.latencies.get(&link).copied().unwrap_or(inner.default_latency);
+        let (local, remote, conn) = SimStream::pair(local_addr, remote_addr, latency);
+
+        listener
+            .send(Ok((PeerStream::Other(Box::new(remote)), local_addr)))
+            .map_err(|_| io::Error::from(io::ErrorKind::ConnectionRefused))?;
+        inner.connections.retain(SimConnection::is_open);
+        inner.connections.push(conn);
+        Ok(PeerStream::Other(Box::new(local)))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SimNetworkInner> {
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[derive(Debug, Default)]
+struct SimNetworkInner {
+    /// The number of transports handed out, used to assign IP addresses.
+    num_nodes: u32,
+    /// The last port assigned to the local side of a connection.
+    next_port: u16,
+    /// The latency of links without a configured latency.
+    default_latency: Duration,
+    /// The configured latencies by link.
+    latencies: HashMap<Link, Duration>,
+    /// The partitioned links.
+    partitions: HashSet<Link>,
+    /// The listeners for incoming connections by address.
+    listeners: HashMap<SocketAddr, mpsc::UnboundedSender<io::Result<(PeerStream, SocketAddr)>>>,
+    /// The established connections.
+    connections: Vec<SimConnection>,
+}
+
+/// A link between two nodes, independent of the direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Link(IpAddr, IpAddr);
+
+impl Link {
+    fn new(a: IpAddr, b: IpAddr) -> Self {
+        if a <= b {
+            Self(a, b)
+        } else {
+            Self(b, a)
+        }
+    }
+}
+
+/// The [`Transport`] of a node in a [`SimNetwork`].
+#[derive(Debug, Clone)]
+pub struct SimTransport {
+    network: SimNetwork,
+    ip: IpAddr,
+}
+
+impl SimTransport {
+    /// Returns the IP address of the node.
+    pub fn ip(&self) -> IpAddr {
+        self.ip
+    }
+}
+
+impl Transport for SimTransport {
+    fn listen(&self, addr: SocketAddr) -> BoxFuture<'static, io::Result<ConnectionListener>> {
+        let port = if addr.port() == 0 { DEFAULT_PORT } else { addr.port() };
+        let res = self.network.listen(SocketAddr::new(self.ip, port));
+        futures::future::ready(res).boxed()
+    }
+
+    fn connect(&self, remote_addr: SocketAddr) -> BoxFuture<'static, io::Result<PeerStream>> {
+        let res = self.network.connect(self.ip, remote_addr);
+        futures::future::ready(res).boxed()
+    }
+}
+
+/// A chunk of bytes written to a [`SimStream`].
+#[derive(Debug)]
+enum Frame {
+    /// Bytes that are delivered to the reader at the given time.
+    Data { deliver_at: Instant, bytes: Vec<u8> },
+    /// The connection was reset.
+    Reset,
+}
+
+/// The handle of the [`SimNetwork`] to an established connection, used to reset it.
+#[derive(Debug)]
+struct SimConnection {
+    link: Link,
+    reset: Arc<AtomicBool>,
+    /// The senders of both directions.
+    senders: [mpsc::UnboundedSender<Frame>; 2],
+}
+
+impl SimConnection {
+    fn reset(&self) {
+        self.reset.store(true, Ordering::Relaxed);
+        for sender in &self.senders {
+            let _ = sender.send(Frame::Reset);
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        self.senders.iter().any(|sender| !sender.is_closed())
+    }
+}
+
+/// One side of a connection in a [`SimNetwork`].
+///
+/// Everything that is written is delivered to the other side after the latency of the link.
+struct SimStream {
+    local_addr: SocketAddr,
+    remote_addr: SocketAddr,
+    latency: Duration,
+    /// Whether the connection was reset by a partition.
+    reset: Arc<AtomicBool>,
+    tx: mpsc::UnboundedSender<Frame>,
+    rx: mpsc::UnboundedReceiver<Frame>,
+    /// The bytes that were received but not read yet.
+    pending: Option<(Instant, Vec<u8>)>,
+    /// The offset of the unread bytes in `pending`.
+    offset: usize,
+    /// The timer until the pending bytes are delivered.
+    delay: Option<Pin<Box<Sleep>>>,
+}
+
+impl SimStream {
+    /// Returns both sides of a new connection and the handle to reset it.
+    fn pair(
+        local_addr: SocketAddr,
+        remote_addr: SocketAddr,
+        latency: Duration,
+    ) -> (Self, Self, SimConnection) {
+        let (local_tx, remote_rx) = mpsc::unbounded_channel();
+        let (remote_tx, local_rx) = mpsc::unbounded_channel();
+        let reset = Arc::new(AtomicBool::new(false));
+        let conn = SimConnection {
+            link: Link::new(local_addr.ip(), remote_addr.ip()),
+            reset: Arc::clone(&reset),
+            senders: [local_tx.clone(), remote_tx.clone()],
+        };
+        let local =
+            Self::new(local_addr, remote_addr, latency, Arc::clone(&reset), local_tx, local_rx);
+        let remote = Self::new(remote_addr, local_addr, latency, reset, remote_tx, remote_rx);
+        (local, remote, conn)
+    }
+
+    fn new(
+        local_addr: SocketAddr,
+        remote_addr: SocketAddr,
+        latency: Duration,
+        reset: Arc<AtomicBool>,
+        tx: mpsc::UnboundedSender<Frame>,
+        rx: mpsc::UnboundedReceiver<Frame>,
+    ) -> Self {
+        Self {
+            local_addr,
+            remote_addr,
+            latency,
+            reset,
+            tx,
+            rx,
+            pending: None,
+            offset: 0,
+            delay: None,
+        }
+    }
+}
+
+impl AsyncRead for SimStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if let Some((deliver_at, bytes)) = &this.pending {
+                if Instant::now() < *deliver_at {
+                    let delay = this
+                        .delay
+                        .get_or_insert_with(|| Box::pin(tokio::time::sleep_until(*deliver_at)));
+                    ready!(delay.as_mut().poll(cx));
+                }
+                this.delay = None;
+
+                let unread = &bytes[this.offset..];
+                let len = unread.len().min(buf.remaining());
+                buf.put_slice(&unread[..len]);
+                this.offset += len;
+                if this.offset == bytes.len() {
+                    this.pending = None;
+                    this.offset = 0;
+                }
+                return Poll::Ready(Ok(()))
+            }
+
+            match ready!(this.rx.poll_recv(cx)) {
+                Some(Frame::Data { deliver_at, bytes }) => this.pending = Some((deliver_at, bytes)),
+                Some(Frame::Reset) => {
+                    return Poll::Ready(Err(io::ErrorKind::ConnectionReset.into()))
+                }
+                // the other side was dropped
+                None => return Poll::Ready(Ok(())),
+            }
+        }
+    }
+}
+
+impl AsyncWrite for SimStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, io::Error>> {
+        if self.reset.load(Ordering::Relaxed) {
+            return Poll::Ready(Err(io::ErrorKind::ConnectionReset.into()))
+        }
+        let frame = Frame::Data { deliver_at: Instant::now() + self.latency, bytes: buf.to_vec() };
+        if self.tx.send(frame).is_err() {
+            return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()))
+        }
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl HasRemoteAddr for SimStream {
+    fn remote_addr(&self) -> Option<SocketAddr> {
+        Some(self.remote_addr)
+    }
+}
+
+impl PeerIo for SimStream {
+    fn local_addr(&self) -> Option<SocketAddr> {
+        Some(self.local_addr)
+    }
+}
+
+impl fmt::Debug for SimStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SimStream")
+            .field("local_addr", &self.local_addr)
+            .field("remote_addr", &self.remote_addr)
+            .field("latency", &self.latency)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn connected_pair(network: &SimNetwork) -> (PeerStream, PeerStream, ConnectionListener) {
+        let server = network.transport();
+        let client = network.transport();
+        let mut listener = server.listen(SocketAddr::new(server.ip(), 0)).await.unwrap();
+        let outgoing = client.connect(listener.local_address()).await.unwrap();
+        let incoming = match futures::future::poll_fn(|cx| Pin::new(&mut listener).poll(cx)).await {
+            crate::ListenerEvent::Incoming { stream, remote_addr } => {
+                assert_eq!(Some(remote_addr), outgoing.local_addr());
+                stream
+            }
+            _ => panic!("unexpected listener event"),
+        };
+        (outgoing, incoming, listener)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn delivers_after_latency() {
+        let latency = Duration::from_millis(200);
+        let network = SimNetwork::with_latency(latency);
+        let (mut outgoing, mut incoming, _listener) = connected_pair(&network).await;
+
+        let start = Instant::now();
+        outgoing.write_all(b"ping").await.unwrap();
+        let mut buf = [0u8; 4];
+        incoming.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"ping");
+        assert!(start.elapsed() >= latency);
+    }
+
+    #[tokio::test]
+    async fn partition_resets_connections() {
+        let network = SimNetwork::new();
+        let (mut outgoing, mut incoming, _listener) = connected_pair(&network).await;
+        let (a, b) = (outgoing.local_addr().unwrap(), incoming.local_addr().unwrap());
+
+        network.partition(a, b);
+        assert!(network.is_partitioned(b, a));
+        let mut buf = [0u8; 1];
+        let err = incoming.read(&mut buf).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+        assert!(outgoing.write_all(b"x").await.is_err());
+
+        // new connections are refused until healed
+        let client = SimTransport { network: network.clone(), ip: a.ip() };
+        let err = client.connect(b).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+        network.heal(a, b);
+        assert!(client.connect(b).await.is_ok());
+    }
+}
@@ -5,7 +5,9 @@ use crate::{
     error::NetworkError,
     eth_requests::EthRequestHandler,
     protocol::IntoRlpxSubProtocol,
+    test_utils::SimNetwork,
     transactions::{TransactionsHandle, TransactionsManager},
+    transport::Transport,
     NetworkConfig, NetworkConfigBuilder, NetworkEvent, NetworkEvents, NetworkHandle,
     NetworkManager,
 };
@@ -67,6 +69,26 @@ where
         Ok(this)
     }
 
+    /// Same as [`Self::try_create_simulated_with`] but panics on error
+    pub async fn create_simulated_with(num_peers: usize, provider: C, sim: &SimNetwork) -> Self {
+        Self::try_create_simulated_with(num_peers, provider, sim).await.unwrap()
+    }
+
+    /// Creates a new [`Testnet`] with the given number of peers and the provider, whose peers
+    /// connect to each other over the given [`SimNetwork`] instead of TCP.
+    pub async fn try_create_simulated_with(
+        num_peers: usize,
+        provider: C,
+        sim: &SimNetwork,
+    ) -> Result<Self, NetworkError> {
+        let mut this = Self { peers: Vec::with_capacity(num_peers) };
+        for _ in 0..num_peers {
+            let config = PeerConfig::new(provider.clone()).with_transport(sim.transport());
+            this.add_peer_with_config(config).await?;
+        }
+        Ok(this)
+    }
+
     /// Extend the list of peers with new peers that are configured with each of the given
     /// [`PeerConfig`]s.
     pub async fn extend_peer_with_config(
@@ -548,6 +570,13 @@ where
         Self { config, client, secret_key }
     }
 
+    /// Establishes the connections of the peer over the given transport instead of TCP, see also
+    /// [`SimNetwork::transport`].
+    pub fn with_transport(mut self, transport: impl Transport) -> Self {
+        self.config.transport = Box::new(transport);
+        self
+    }
+
     fn network_config_builder(secret_key: SecretKey) -> NetworkConfigBuilder {
         NetworkConfigBuilder::new(secret_key)
             .listener_addr(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0)))
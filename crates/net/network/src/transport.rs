@@ -0,0 +1,142 @@
+//! The transport over which connections to peers are established.
+//!
+//! By default the network uses [`TcpTransport`]. A custom [`Transport`] can be configured with
+//! [`NetworkConfigBuilder::transport`](crate::NetworkConfigBuilder::transport), for example to
+//! connect multiple networks in the same process without real sockets.
+
+use crate::listener::ConnectionListener;
+use futures::{future::BoxFuture, FutureExt};
+use reth_net_common::stream::HasRemoteAddr;
+use std::{
+    fmt, io,
+    net::SocketAddr,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::TcpStream,
+};
+
+/// Establishes connections to peers and listens for incoming connections.
+pub trait Transport: fmt::Debug + Send + Sync + 'static {
+    /// Starts listening for incoming connections on the given address.
+    fn listen(&self, addr: SocketAddr) -> BoxFuture<'static, io::Result<ConnectionListener>>;
+
+    /// Opens a connection to the given address.
+    fn connect(&self, remote_addr: SocketAddr) -> BoxFuture<'static, io::Result<PeerStream>>;
+}
+
+/// The default [`Transport`] that connects to peers over TCP.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct TcpTransport;
+
+impl Transport for TcpTransport {
+    fn listen(&self, addr: SocketAddr) -> BoxFuture<'static, io::Result<ConnectionListener>> {
+        ConnectionListener::bind(addr).boxed()
+    }
+
+    fn connect(&self, remote_addr: SocketAddr) -> BoxFuture<'static, io::Result<PeerStream>> {
+        async move {
+            let stream = TcpStream::connect(remote_addr).await?;
+            if let Err(err) = stream.set_nodelay(true) {
+                tracing::warn!(target: "net", "set nodelay failed: {:?}", err);
+            }
+            Ok(PeerStream::Tcp(stream))
+        }
+        .boxed()
+    }
+}
+
+/// The IO of a connection established by a [`Transport`] other than TCP.
+pub trait PeerIo: AsyncRead + AsyncWrite + HasRemoteAddr + Send + Unpin + 'static {
+    /// Returns the local address of the connection.
+    fn local_addr(&self) -> Option<SocketAddr>;
+}
+
+/// A connection to a peer, established by a [`Transport`].
+pub enum PeerStream {
+    /// A TCP connection.
+    Tcp(TcpStream),
+    /// A connection of another transport.
+    Other(Box<dyn PeerIo>),
+}
+
+impl PeerStream {
+    /// Returns the local address of the connection.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        match self {
+            PeerStream::Tcp(stream) => stream.local_addr().ok(),
+            PeerStream::Other(stream) => stream.local_addr(),
+        }
+    }
+}
+
+impl From<TcpStream> for PeerStream {
+    fn from(stream: TcpStream) -> Self {
+        PeerStream::Tcp(stream)
+    }
+}
+
+impl HasRemoteAddr for PeerStream {
+    fn remote_addr(&self) -> Option<SocketAddr> {
+        match self {
+            PeerStream::Tcp(stream) => stream.remote_addr(),
+            PeerStream::Other(stream) => stream.remote_addr(),
+        }
+    }
+}
+
+impl AsyncRead for PeerStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            PeerStream::Tcp(stream) => Pin::new(stream).poll_read(cx, buf),
+            PeerStream::Other(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for PeerStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, io::Error>> {
+        match self.get_mut() {
+            PeerStream::Tcp(stream) => Pin::new(stream).poll_write(cx, buf),
+            PeerStream::Other(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        match self.get_mut() {
+            PeerStream::Tcp(stream) => Pin::new(stream).poll_flush(cx),
+            PeerStream::Other(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        match self.get_mut() {
+            PeerStream::Tcp(stream) => Pin::new(stream).poll_shutdown(cx),
+            PeerStream::Other(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
+
+impl fmt::Debug for PeerStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PeerStream::Tcp(stream) => f.debug_tuple("Tcp").field(stream).finish(),
+            PeerStream::Other(stream) => f
+                .debug_struct("Other")
+                .field("local_addr", &stream.local_addr())
+                .field("remote_addr", &stream.remote_addr())
+                .finish(),
+        }
+    }
+}
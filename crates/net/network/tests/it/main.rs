@@ -5,6 +5,7 @@ mod geth;
 mod multiplex;
 mod requests;
 mod session;
+mod sim;
 mod startup;
 #[cfg(not(feature = "optimism"))]
 mod txgossip;
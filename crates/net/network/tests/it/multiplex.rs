@@ -173,6 +173,9 @@ enum ProtocolEvent {
         peer_id: PeerId,
         to_connection: mpsc::UnboundedSender<Command>,
     },
+    UnsupportedByPeer {
+        peer_id: PeerId,
+    },
 }
 
 enum Command {
@@ -199,8 +202,9 @@ impl ConnectionHandler for PingPongConnectionHandler {
         self,
         _supported: &SharedCapabilities,
         _direction: Direction,
-        _peer_id: PeerId,
+        peer_id: PeerId,
     ) -> OnNotSupported {
+        self.state.events.send(ProtocolEvent::UnsupportedByPeer { peer_id }).ok();
         OnNotSupported::KeepAlive
     }
 
@@ -302,6 +306,7 @@ async fn test_proto_multiplex() {
             assert_eq!(peer_id, *handle.peers()[1].peer_id());
             to_connection
         }
+        ev => panic!("unexpected event {ev:?}"),
     };
 
     let peer1_to_peer0 = from_peer1.recv().await.unwrap();
@@ -310,6 +315,7 @@ async fn test_proto_multiplex() {
             assert_eq!(peer_id, *handle.peers()[0].peer_id());
             to_connection
         }
+        ev => panic!("unexpected event {ev:?}"),
     };
 
     let (tx, rx) = oneshot::channel();
@@ -328,3 +334,26 @@ async fn test_proto_multiplex() {
     let response = rx.await.unwrap();
     assert_eq!(response, "hello from peer1!");
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_proto_unsupported_by_peer() {
+    reth_tracing::init_test_tracing();
+    let provider = MockEthProvider::default();
+    let mut net = Testnet::create_with(2, provider.clone()).await;
+
+    // only peer0 supports the protocol
+    let (tx, mut from_peer0) = mpsc::unbounded_channel();
+    net.peers_mut()[0]
+        .add_rlpx_sub_protocol(PingPongProtoHandler { state: ProtocolState { events: tx } });
+
+    let handle = net.spawn();
+    // the session is established with `eth` only
+    handle.connect_peers().await;
+
+    match from_peer0.recv().await.unwrap() {
+        ProtocolEvent::UnsupportedByPeer { peer_id } => {
+            assert_eq!(peer_id, *handle.peers()[1].peer_id());
+        }
+        ev => panic!("unexpected event {ev:?}"),
+    }
+}
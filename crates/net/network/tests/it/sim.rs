@@ -0,0 +1,86 @@
+//! Tests for multiple nodes connected over the simulated network.
+
+use rand::Rng;
+use reth_interfaces::p2p::headers::client::{HeadersClient, HeadersRequest};
+use reth_network::{
+    test_utils::{NetworkEventStream, SimNetwork, Testnet},
+    NetworkEvents,
+};
+use reth_network_api::{NetworkInfo, Peers};
+use reth_primitives::{Header, HeadersDirection};
+use reth_provider::test_utils::MockEthProvider;
+use std::{sync::Arc, time::Duration};
+use tokio::time::Instant;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sim_get_header_with_latency() {
+    reth_tracing::init_test_tracing();
+    let mut rng = rand::thread_rng();
+    let mock_provider = Arc::new(MockEthProvider::default());
+    let latency = Duration::from_millis(50);
+    let sim = SimNetwork::with_latency(latency);
+
+    let mut net = Testnet::create_simulated_with(2, mock_provider.clone(), &sim).await;
+
+    // install request handlers
+    net.for_each_mut(|peer| peer.install_request_handler());
+
+    let handle0 = net.peers()[0].handle();
+    let mut events0 = NetworkEventStream::new(handle0.event_listener());
+
+    let handle1 = net.peers()[1].handle();
+
+    let _handle = net.spawn();
+
+    let fetch0 = handle0.fetch_client().await.unwrap();
+
+    handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
+    let connected = events0.next_session_established().await.unwrap();
+    assert_eq!(connected, *handle1.peer_id());
+
+    let hash = rng.gen();
+    let header = Header { number: rng.gen(), ..Default::default() };
+    mock_provider.add_header(hash, header.clone());
+
+    let req = HeadersRequest { start: hash.into(), limit: 1, direction: HeadersDirection::Falling };
+    let start = Instant::now();
+    let res = fetch0.get_headers(req).await;
+    assert!(res.is_ok(), "{res:?}");
+    // the request and the response are both delayed
+    assert!(start.elapsed() >= latency * 2);
+
+    let headers = res.unwrap().1;
+    assert_eq!(headers, vec![header]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sim_partition() {
+    reth_tracing::init_test_tracing();
+    let sim = SimNetwork::new();
+    let net = Testnet::create_simulated_with(3, MockEthProvider::default(), &sim).await;
+
+    let handle0 = net.peers()[0].handle();
+    let mut events0 = NetworkEventStream::new(handle0.event_listener());
+    let handle1 = net.peers()[1].handle();
+    let mut events1 = NetworkEventStream::new(handle1.event_listener());
+    let handle2 = net.peers()[2].handle();
+
+    let _handle = net.spawn();
+
+    // peer 0 can't reach peer 1, but still reaches peer 2
+    sim.partition(handle0.local_addr(), handle1.local_addr());
+    handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
+    handle0.add_peer(*handle2.peer_id(), handle2.local_addr());
+    let connected = events0.next_session_established().await.unwrap();
+    assert_eq!(connected, *handle2.peer_id());
+
+    sim.heal(handle0.local_addr(), handle1.local_addr());
+    handle1.add_peer(*handle0.peer_id(), handle0.local_addr());
+    let connected = events1.next_session_established().await.unwrap();
+    assert_eq!(connected, *handle0.peer_id());
+
+    // partitioning resets the established session
+    sim.partition(handle0.local_addr(), handle1.local_addr());
+    let (closed, _) = events1.next_session_closed().await.unwrap();
+    assert_eq!(closed, *handle0.peer_id());
+}
@@ -1,10 +1,11 @@
 //! Testing gossiping of transactions.
 
 use rand::thread_rng;
-use reth_network::test_utils::Testnet;
+use reth_network::test_utils::{SimNetwork, Testnet};
 use reth_primitives::U256;
 use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
 use reth_transaction_pool::{test_utils::TransactionGenerator, PoolTransaction, TransactionPool};
+use std::time::Duration;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_tx_gossip() {
@@ -43,3 +44,33 @@ async fn test_tx_gossip() {
     let received = peer1_tx_listener.recv().await.unwrap();
     assert_eq!(received, hash);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tx_gossip_simulated() {
+    reth_tracing::init_test_tracing();
+
+    let provider = MockEthProvider::default();
+    let sim = SimNetwork::with_latency(Duration::from_millis(10));
+    let net = Testnet::create_simulated_with(3, provider.clone(), &sim).await;
+
+    // install request handlers
+    let net = net.with_eth_pool();
+    let handle = net.spawn();
+    // connect all the peers
+    handle.connect_peers().await;
+
+    let peer0_pool = handle.peers()[0].pool().unwrap();
+    let mut peer2_tx_listener = handle.peers()[2].pool().unwrap().pending_transactions_listener();
+
+    let mut gen = TransactionGenerator::new(thread_rng());
+    let tx = gen.gen_eip1559_pooled();
+
+    // ensure the sender has balance
+    provider.add_account(tx.sender(), ExtendedAccount::new(0, U256::from(100_000_000)));
+
+    let hash = peer0_pool.add_external_transaction(tx).await.unwrap();
+
+    // ensure tx is gossiped to peer2
+    let received = peer2_tx_listener.recv().await.unwrap();
+    assert_eq!(received, hash);
+}
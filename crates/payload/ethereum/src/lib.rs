@@ -28,12 +28,13 @@ mod builder {
     };
     use reth_provider::{BundleStateWithReceipts, StateProviderFactory};
     use reth_revm::database::StateProviderDatabase;
-    use reth_transaction_pool::TransactionPool;
+    use reth_transaction_pool::{validate::validate_conditional, PoolTransaction, TransactionPool};
     use revm::{
         db::states::bundle_state::BundleRetention,
         primitives::{EVMError, Env, InvalidTransaction, ResultAndState},
         DatabaseCommit, State,
     };
+    use std::collections::HashSet;
     use tracing::{debug, trace};
 
     /// Ethereum payload builder
@@ -109,6 +110,11 @@ mod builder {
             &attributes,
         )?;
 
+        // accounts whose storage was changed by the transactions of the block, the conditions of
+        // transactions that expect the storage of these accounts can't be checked against the
+        // state of the parent block anymore
+        let mut changed_storage = HashSet::new();
+
         let mut receipts = Vec::new();
         while let Some(pool_tx) = best_txs.next() {
             // ensure we still have capacity for this transaction
@@ -125,6 +131,32 @@ mod builder {
                 return Ok(BuildOutcome::Cancelled)
             }
 
+            // revalidate the conditions of transactions submitted with
+            // `eth_sendRawTransactionConditional` against this block
+            if let Some(conditional) = pool_tx.transaction.conditional() {
+                let res = validate_conditional(
+                    conditional,
+                    block_number,
+                    initialized_block_env.timestamp.saturating_to(),
+                    &state_provider,
+                );
+                let storage_changed = conditional
+                    .known_accounts
+                    .keys()
+                    .any(|address| changed_storage.contains(address));
+                if res.is_err() || storage_changed {
+                    trace!(
+                        target: "payload_builder",
+                        ?res,
+                        storage_changed,
+                        tx=?pool_tx.hash(),
+                        "skipping transaction with unmet conditions"
+                    );
+                    best_txs.mark_invalid(&pool_tx);
+                    continue
+                }
+            }
+
             // convert tx to a signed transaction
             let tx = pool_tx.to_recovered_transaction();
 
@@ -178,6 +210,13 @@ mod builder {
                 }
             };
 
+            changed_storage.extend(
+                state
+                    .iter()
+                    .filter(|(_, account)| account.storage.values().any(|slot| slot.is_changed()))
+                    .map(|(address, _)| *address),
+            );
+
             // commit changes
             db.commit(state);
 
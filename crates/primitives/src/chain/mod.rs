@@ -14,8 +14,8 @@ use strum::{AsRefStr, EnumCount, EnumIter, EnumString, EnumVariantNames};
 mod spec;
 pub use spec::{
     AllGenesisFormats, BaseFeeParams, BaseFeeParamsKind, ChainSpec, ChainSpecBuilder,
-    DisplayHardforks, ForkBaseFeeParams, ForkCondition, ForkTimestamps, DEV, GOERLI, HOLESKY,
-    MAINNET, SEPOLIA,
+    CustomHardfork, DisplayHardforks, ForkBaseFeeParams, ForkCondition, ForkTimestamps, DEV,
+    GOERLI, HOLESKY, MAINNET, SEPOLIA,
 };
 
 #[cfg(feature = "optimism")]
@@ -55,6 +55,7 @@ pub static MAINNET: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
             ),
             (Hardfork::Shanghai, ForkCondition::Timestamp(1681338455)),
         ]),
+        custom_hardforks: BTreeMap::new(),
         // https://etherscan.io/tx/0xe75fb554e433e03763a1560646ee22dcb74e5274b34c5ad644e7c0f619a7e1d0
         deposit_contract: Some(DepositContract::new(
             address!("00000000219ab540356cbb839cbe05303d7705fa"),
@@ -99,6 +100,7 @@ pub static GOERLI: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
             (Hardfork::Shanghai, ForkCondition::Timestamp(1678832736)),
             (Hardfork::Cancun, ForkCondition::Timestamp(1705473120)),
         ]),
+        custom_hardforks: BTreeMap::new(),
         // https://goerli.etherscan.io/tx/0xa3c07dc59bfdb1bfc2d50920fed2ef2c1c4e0a09fe2325dbc14e07702f965a78
         deposit_contract: Some(DepositContract::new(
             address!("ff50ed3d0ec03ac01d4c79aad74928bff48a7b2b"),
@@ -147,6 +149,7 @@ pub static SEPOLIA: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
             (Hardfork::Shanghai, ForkCondition::Timestamp(1677557088)),
             (Hardfork::Cancun, ForkCondition::Timestamp(1706655072)),
         ]),
+        custom_hardforks: BTreeMap::new(),
         // https://sepolia.etherscan.io/tx/0x025ecbf81a2f1220da6285d1701dc89fb5a956b62562ee922e1a9efd73eb4b14
         deposit_contract: Some(DepositContract::new(
             address!("7f02c3e3c98b133055b8b348b2ac625669ed295d"),
@@ -191,6 +194,7 @@ pub static HOLESKY: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
             (Hardfork::Shanghai, ForkCondition::Timestamp(1696000704)),
             (Hardfork::Cancun, ForkCondition::Timestamp(1707305664)),
         ]),
+        custom_hardforks: BTreeMap::new(),
         deposit_contract: Some(DepositContract::new(
             address!("4242424242424242424242424242424242424242"),
             0,
@@ -278,6 +282,7 @@ pub static OP_GOERLI: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
             (Hardfork::Shanghai, ForkCondition::Timestamp(1699981200)),
             (Hardfork::Canyon, ForkCondition::Timestamp(1699981200)),
         ]),
+        custom_hardforks: BTreeMap::new(),
         base_fee_params: BaseFeeParamsKind::Variable(
             vec![
                 (Hardfork::London, BaseFeeParams::optimism_goerli()),
@@ -327,6 +332,7 @@ pub static BASE_GOERLI: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
             (Hardfork::Shanghai, ForkCondition::Timestamp(1699981200)),
             (Hardfork::Canyon, ForkCondition::Timestamp(1699981200)),
         ]),
+        custom_hardforks: BTreeMap::new(),
         base_fee_params: BaseFeeParamsKind::Variable(
             vec![
                 (Hardfork::London, BaseFeeParams::optimism_goerli()),
@@ -376,6 +382,7 @@ pub static BASE_SEPOLIA: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
             (Hardfork::Shanghai, ForkCondition::Timestamp(1699981200)),
             (Hardfork::Canyon, ForkCondition::Timestamp(1699981200)),
         ]),
+        custom_hardforks: BTreeMap::new(),
         base_fee_params: BaseFeeParamsKind::Variable(
             vec![
                 (Hardfork::London, BaseFeeParams::optimism_sepolia()),
@@ -423,6 +430,7 @@ pub static BASE_MAINNET: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
             (Hardfork::Bedrock, ForkCondition::Block(0)),
             (Hardfork::Regolith, ForkCondition::Timestamp(0)),
         ]),
+        custom_hardforks: BTreeMap::new(),
         base_fee_params: BaseFeeParamsKind::Variable(
             vec![
                 (Hardfork::London, BaseFeeParams::optimism()),
@@ -592,6 +600,13 @@ pub struct ChainSpec {
     /// The active hard forks and their activation conditions
     pub hardforks: BTreeMap<Hardfork, ForkCondition>,
 
+    /// The custom hardforks that aren't part of [Hardfork], by name, and their activation
+    /// conditions.
+    ///
+    /// These are not part of the fork ID, see [CustomHardfork].
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom_hardforks: BTreeMap<String, ForkCondition>,
+
     /// The deposit contract deployed for PoS
     #[serde(skip, default)]
     pub deposit_contract: Option<DepositContract>,
@@ -618,6 +633,7 @@ impl Default for ChainSpec {
             paris_block_and_final_difficulty: Default::default(),
             fork_timestamps: Default::default(),
             hardforks: Default::default(),
+            custom_hardforks: Default::default(),
             deposit_contract: Default::default(),
             base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
             prune_delete_limit: MAINNET.prune_delete_limit,
@@ -726,6 +742,23 @@ impl ChainSpec {
         }
     }
 
+    /// Calculate the EIP-1559 base fee of the child of the given parent block with the given
+    /// timestamp, using the [BaseFeeParams] of the chain at that timestamp.
+    ///
+    /// The first block of [Hardfork::London] has the [EIP1559_INITIAL_BASE_FEE]. Returns `None` if
+    /// London is not active at the child block, or if the parent has no base fee after the fork.
+    pub fn next_block_base_fee(&self, parent: &Header, timestamp: u64) -> Option<u64> {
+        let london = self.fork(Hardfork::London);
+        let number = parent.number + 1;
+        if !london.active_at_block(number) {
+            None
+        } else if london.transitions_at_block(number) {
+            Some(EIP1559_INITIAL_BASE_FEE)
+        } else {
+            parent.next_block_base_fee(self.base_fee_params(timestamp))
+        }
+    }
+
     /// Get the hash of the genesis block.
     pub fn genesis_hash(&self) -> B256 {
         if let Some(hash) = self.genesis_hash {
@@ -799,6 +832,18 @@ impl ChainSpec {
         self.hardforks.get(&fork).copied().unwrap_or(ForkCondition::Never)
     }
 
+    /// Get the activation condition of the custom hardfork `F`.
+    ///
+    /// Returns [ForkCondition::Never] if the fork was not registered.
+    pub fn custom_fork<F: CustomHardfork>(&self) -> ForkCondition {
+        self.custom_hardforks.get(F::NAME).copied().unwrap_or(ForkCondition::Never)
+    }
+
+    /// Returns `true` if the custom hardfork `F` is active at the given head block.
+    pub fn is_fork_active<F: CustomHardfork>(&self, head: &Head) -> bool {
+        self.custom_fork::<F>().active_at_head(head)
+    }
+
     /// Get an iterator of all hardforks with their respective activation conditions.
     pub fn forks_iter(&self) -> impl Iterator<Item = (Hardfork, ForkCondition)> + '_ {
         self.hardforks.iter().map(|(f, b)| (*f, *b))
@@ -1129,6 +1174,7 @@ pub struct ChainSpecBuilder {
     chain: Option<Chain>,
     genesis: Option<Genesis>,
     hardforks: BTreeMap<Hardfork, ForkCondition>,
+    custom_hardforks: BTreeMap<String, ForkCondition>,
 }
 
 impl ChainSpecBuilder {
@@ -1138,6 +1184,7 @@ impl ChainSpecBuilder {
             chain: Some(MAINNET.chain),
             genesis: Some(MAINNET.genesis.clone()),
             hardforks: MAINNET.hardforks.clone(),
+            custom_hardforks: MAINNET.custom_hardforks.clone(),
         }
     }
 
@@ -1159,6 +1206,14 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Add the custom hardfork `F` with the given activation condition to the spec.
+    ///
+    /// This replaces the condition of a custom hardfork with the same [CustomHardfork::NAME].
+    pub fn with_custom_fork<F: CustomHardfork>(mut self, condition: ForkCondition) -> Self {
+        self.custom_hardforks.insert(F::NAME.to_string(), condition);
+        self
+    }
+
     /// Enable the Paris hardfork at the given TTD.
     ///
     /// Does not set the merge netsplit block.
@@ -1203,9 +1258,16 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Enable Constantinople at genesis.
+    pub fn constantinople_activated(mut self) -> Self {
+        self = self.byzantium_activated();
+        self.hardforks.insert(Hardfork::Constantinople, ForkCondition::Block(0));
+        self
+    }
+
     /// Enable Petersburg at genesis.
     pub fn petersburg_activated(mut self) -> Self {
-        self = self.byzantium_activated();
+        self = self.constantinople_activated();
         self.hardforks.insert(Hardfork::Petersburg, ForkCondition::Block(0));
         self
     }
@@ -1294,6 +1356,7 @@ impl ChainSpecBuilder {
             genesis_hash: None,
             fork_timestamps: ForkTimestamps::from_hardforks(&self.hardforks),
             hardforks: self.hardforks,
+            custom_hardforks: self.custom_hardforks,
             paris_block_and_final_difficulty: None,
             deposit_contract: None,
             ..Default::default()
@@ -1307,10 +1370,22 @@ impl From<&Arc<ChainSpec>> for ChainSpecBuilder {
             chain: Some(value.chain),
             genesis: Some(value.genesis.clone()),
             hardforks: value.hardforks.clone(),
+            custom_hardforks: value.custom_hardforks.clone(),
         }
     }
 }
 
+/// A hardfork that is not part of [Hardfork], e.g. an experimental EIP that is scheduled on a
+/// devnet.
+///
+/// Custom hardforks are registered with [ChainSpecBuilder::with_custom_fork] and queried with
+/// [ChainSpec::is_fork_active]. Unlike the forks in [Hardfork], they don't change the fork ID, so
+/// all nodes of the network are expected to use the same schedule.
+pub trait CustomHardfork {
+    /// The name that identifies the hardfork in the chain spec.
+    const NAME: &'static str;
+}
+
 /// The condition at which a fork is activated.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum ForkCondition {
@@ -1603,7 +1678,7 @@ mod tests {
     use super::*;
     use crate::{
         b256, hex, trie::TrieAccount, ChainConfig, GenesisAccount, NamedChain, B256, DEV, GOERLI,
-        HOLESKY, MAINNET, SEPOLIA, U256,
+        HOLESKY, HOLESKY_GENESIS_HASH, MAINNET, SEPOLIA, SEPOLIA_GENESIS_HASH, U256,
     };
     use alloy_rlp::Encodable;
     use bytes::BytesMut;
@@ -2857,6 +2932,16 @@ Post-merge hard forks (timestamp based):
         assert_eq!(ForkHash::from(genesis_hash), expected_forkhash);
     }
 
+    #[test]
+    fn testnet_genesis_hashes() {
+        for (spec, expected) in [(&SEPOLIA, SEPOLIA_GENESIS_HASH), (&HOLESKY, HOLESKY_GENESIS_HASH)]
+        {
+            assert_eq!(spec.genesis_hash(), expected);
+            // the preset hash matches the hash of the genesis file
+            assert_eq!(spec.genesis_header().hash_slow(), expected);
+        }
+    }
+
     #[test]
     fn holesky_paris_activated_at_genesis() {
         assert!(HOLESKY
@@ -2921,6 +3006,62 @@ Post-merge hard forks (timestamp based):
         assert!(matches!(deserialized_chain_spec, AllGenesisFormats::Reth(_)))
     }
 
+    #[test]
+    fn mainnet_london_base_fee() {
+        // London is not active yet
+        let parent = Header { number: 12964998, ..Default::default() };
+        assert_eq!(MAINNET.next_block_base_fee(&parent, 0), None);
+
+        // <https://etherscan.io/block/12965000>
+        let parent = Header { number: 12964999, ..Default::default() };
+        assert_eq!(MAINNET.next_block_base_fee(&parent, 0), Some(EIP1559_INITIAL_BASE_FEE));
+
+        // <https://etherscan.io/block/12965001>
+        let parent = Header {
+            number: 12965000,
+            gas_used: 30025257,
+            gas_limit: 30029122,
+            base_fee_per_gas: Some(1000000000),
+            ..Default::default()
+        };
+        assert_eq!(MAINNET.next_block_base_fee(&parent, 0), Some(1124967822));
+
+        // a block after the fork must have a base fee
+        let parent = Header { number: 12965001, ..Default::default() };
+        assert_eq!(MAINNET.next_block_base_fee(&parent, 0), None);
+    }
+
+    #[test]
+    fn custom_hardfork_activation() {
+        struct Eip9999;
+
+        impl CustomHardfork for Eip9999 {
+            const NAME: &'static str = "eip9999";
+        }
+
+        struct Eip9998;
+
+        impl CustomHardfork for Eip9998 {
+            const NAME: &'static str = "eip9998";
+        }
+
+        let spec = ChainSpecBuilder::mainnet()
+            .with_custom_fork::<Eip9999>(ForkCondition::Timestamp(100))
+            .build();
+        assert_eq!(spec.custom_fork::<Eip9999>(), ForkCondition::Timestamp(100));
+        assert!(!spec.is_fork_active::<Eip9999>(&Head { timestamp: 99, ..Default::default() }));
+        assert!(spec.is_fork_active::<Eip9999>(&Head { timestamp: 100, ..Default::default() }));
+        assert!(!spec.is_fork_active::<Eip9998>(&Head { timestamp: 100, ..Default::default() }));
+
+        // custom hardforks don't change the fork ID
+        let head = Head { number: 20_000_000, timestamp: 1_700_000_000, ..Default::default() };
+        assert_eq!(spec.fork_id(&head), MAINNET.fork_id(&head));
+
+        let serialized = serde_json::to_string(&spec).unwrap();
+        let deserialized: ChainSpec = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.custom_hardforks, spec.custom_hardforks);
+    }
+
     #[test]
     fn check_fork_id_chainspec_with_fork_condition_never() {
         let spec = ChainSpec {
@@ -0,0 +1,47 @@
+use crate::{serde_helper::u64_hex, Address, BlockNumber, TxHash, U256};
+use reth_codecs::{main_codec, Compact};
+
+/// A transfer of ether made by a contract while a transaction was executed, e.g. by a call with
+/// value, a contract creation with an endowment or a self-destruct.
+///
+/// The value transferred by the transaction itself isn't an internal transfer.
+#[main_codec]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct InternalTransfer {
+    /// Number of the block of the transaction.
+    #[serde(with = "u64_hex")]
+    pub block_number: BlockNumber,
+    /// Hash of the transaction that made the transfer.
+    pub transaction_hash: TxHash,
+    /// The sender of the ether.
+    pub from: Address,
+    /// The recipient of the ether.
+    pub to: Address,
+    /// The transferred value in wei.
+    pub value: U256,
+}
+
+impl InternalTransfer {
+    /// Returns `true` if the address is the sender or the recipient of the transfer.
+    pub fn involves(&self, address: Address) -> bool {
+        self.from == address || self.to == address
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address;
+
+    #[test]
+    fn internal_transfer_serde_roundtrip() {
+        let input = r#"{"blockNumber":"0x10","transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000001","from":"0x0000000000000000000000000000000000001000","to":"0x0000000000000000000000000000000000001001","value":"0x2a"}"#;
+
+        let transfer: InternalTransfer = serde_json::from_str(input).unwrap();
+        assert_eq!(transfer.block_number, 16);
+        assert!(transfer.involves(address!("0000000000000000000000000000000000001001")));
+        assert!(!transfer.involves(Address::ZERO));
+        assert_eq!(serde_json::to_string(&transfer).unwrap(), input);
+    }
+}
@@ -74,6 +74,7 @@ mod tests {
     };
 
     use super::*;
+    use crate::Chain;
     use alloy_rlp::{Decodable, Encodable};
     use bytes::BytesMut;
     use rand::{thread_rng, Rng, RngCore};
@@ -204,4 +205,12 @@ mod tests {
             id: PeerId::from_str("6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0").unwrap(),
         })
     }
+
+    #[test]
+    fn parse_testnet_bootnodes() {
+        assert_eq!(sepolia_nodes().len(), SEPOLIA_BOOTNODES.len());
+        assert_eq!(holesky_nodes().len(), HOLESKY_BOOTNODES.len());
+        assert_eq!(Chain::sepolia().bootnodes(), Some(sepolia_nodes()));
+        assert_eq!(Chain::holesky().bootnodes(), Some(holesky_nodes()));
+    }
 }
@@ -33,6 +33,13 @@ pub struct PruneModes {
         deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<MINIMUM_PRUNING_DISTANCE, _>"
     )]
     pub account_history: Option<PruneMode>,
+    /// Whether to delete the bytecodes that are no longer referenced by any current or historical
+    /// account state once `account_history` prunes the last historical state referencing them.
+    ///
+    /// NOTE: Bytecodes are only deleted in databases that counted the references since they were
+    /// initialized. Databases created before the `BytecodeRefs` table keep all bytecodes.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub account_history_delete_bytecodes: bool,
     /// Storage History pruning configuration.
     #[serde(
         skip_serializing_if = "Option::is_none",
@@ -60,6 +67,7 @@ impl PruneModes {
             transaction_lookup: Some(PruneMode::Full),
             receipts: Some(PruneMode::Full),
             account_history: Some(PruneMode::Full),
+            account_history_delete_bytecodes: false,
             storage_history: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
         }
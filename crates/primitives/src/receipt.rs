@@ -700,4 +700,27 @@ mod tests {
         let (decoded, _) = Receipt::from_compact(&data[..], data.len());
         assert_eq!(decoded, receipt);
     }
+
+    proptest::proptest! {
+        #[test]
+        fn receipt_rlp_roundtrip(
+            receipt in proptest::prelude::any::<Receipt>(),
+            mutations in proptest::collection::vec(
+                proptest::prelude::any::<(proptest::sample::Index, u8)>(),
+                1..8
+            ),
+        ) {
+            let receipt = receipt.with_bloom();
+            let mut data = vec![];
+            receipt.encode(&mut data);
+            let decoded = ReceiptWithBloom::decode(&mut &data[..]).unwrap();
+            assert_eq!(decoded, receipt);
+
+            // malformed receipts, e.g. sent by a peer, must be rejected without panicking
+            for (index, byte) in mutations {
+                data[index.index(data.len())] = byte;
+            }
+            let _ = ReceiptWithBloom::decode(&mut &data[..]);
+        }
+    }
 }
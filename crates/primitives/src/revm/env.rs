@@ -2,7 +2,9 @@ use crate::{
     constants::{BEACON_ROOTS_ADDRESS, SYSTEM_ADDRESS},
     recover_signer_unchecked,
     revm::config::revm_spec,
-    revm_primitives::{AnalysisKind, BlockEnv, CfgEnv, Env, SpecId, TransactTo, TxEnv},
+    revm_primitives::{
+        AnalysisKind, BlobExcessGasAndPrice, BlockEnv, CfgEnv, Env, SpecId, TransactTo, TxEnv,
+    },
     Address, Bytes, Chain, ChainSpec, Head, Header, Transaction, TransactionKind,
     TransactionSignedEcRecovered, B256, U256,
 };
@@ -84,9 +86,7 @@ pub fn fill_block_env_with_coinbase(
     block_env.gas_limit = U256::from(header.gas_limit);
 
     // EIP-4844 excess blob gas of this block, introduced in Cancun
-    if let Some(excess_blob_gas) = header.excess_blob_gas {
-        block_env.set_blob_excess_gas_and_price(excess_blob_gas);
-    }
+    block_env.blob_excess_gas_and_price = header.excess_blob_gas.map(BlobExcessGasAndPrice::new);
 }
 
 /// Return the coinbase address for the given header and chain spec.
@@ -262,14 +262,7 @@ pub fn fill_tx_env<T>(
             tx_env.data = tx.input.clone();
             tx_env.chain_id = Some(tx.chain_id);
             tx_env.nonce = Some(tx.nonce);
-            tx_env.access_list = tx
-                .access_list
-                .0
-                .iter()
-                .map(|l| {
-                    (l.address, l.storage_keys.iter().map(|k| U256::from_be_bytes(k.0)).collect())
-                })
-                .collect();
+            tx_env.access_list = tx.access_list.flattened();
             tx_env.blob_hashes.clear();
             tx_env.max_fee_per_blob_gas.take();
 
@@ -288,14 +281,7 @@ pub fn fill_tx_env<T>(
             tx_env.data = tx.input.clone();
             tx_env.chain_id = Some(tx.chain_id);
             tx_env.nonce = Some(tx.nonce);
-            tx_env.access_list = tx
-                .access_list
-                .0
-                .iter()
-                .map(|l| {
-                    (l.address, l.storage_keys.iter().map(|k| U256::from_be_bytes(k.0)).collect())
-                })
-                .collect();
+            tx_env.access_list = tx.access_list.flattened();
             tx_env.blob_hashes.clear();
             tx_env.max_fee_per_blob_gas.take();
 
@@ -314,14 +300,7 @@ pub fn fill_tx_env<T>(
             tx_env.data = tx.input.clone();
             tx_env.chain_id = Some(tx.chain_id);
             tx_env.nonce = Some(tx.nonce);
-            tx_env.access_list = tx
-                .access_list
-                .0
-                .iter()
-                .map(|l| {
-                    (l.address, l.storage_keys.iter().map(|k| U256::from_be_bytes(k.0)).collect())
-                })
-                .collect();
+            tx_env.access_list = tx.access_list.flattened();
             tx_env.blob_hashes = tx.blob_versioned_hashes.clone();
             tx_env.max_fee_per_blob_gas = Some(U256::from(tx.max_fee_per_blob_gas));
 
@@ -342,6 +321,8 @@ pub fn fill_tx_env<T>(
             tx_env.data = tx.input.clone();
             tx_env.chain_id = None;
             tx_env.nonce = None;
+            tx_env.blob_hashes.clear();
+            tx_env.max_fee_per_blob_gas.take();
 
             fill_op_tx_env(tx_env, transaction, envelope);
         }
@@ -373,6 +354,7 @@ fn fill_op_tx_env<T: AsRef<Transaction>>(tx_env: &mut TxEnv, transaction: T, env
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{AccessList, AccessListItem, TransactionSignedNoHash, TxEip4844, TxLegacy};
 
     #[test]
     #[ignore]
@@ -393,4 +375,70 @@ mod tests {
 
         assert_eq!(cfg_env.chain_id, chain_spec.chain().id());
     }
+
+    #[test]
+    #[cfg(not(feature = "optimism"))]
+    fn fill_tx_env_resets_previous_transaction() {
+        let sender = Address::with_last_byte(1);
+        let to = Address::with_last_byte(2);
+        let blob_tx = TransactionSignedNoHash {
+            transaction: Transaction::Eip4844(TxEip4844 {
+                chain_id: 1,
+                nonce: 3,
+                gas_limit: 21_000,
+                max_fee_per_gas: 10,
+                max_priority_fee_per_gas: 2,
+                to: TransactionKind::Call(to),
+                access_list: AccessList(vec![AccessListItem {
+                    address: to,
+                    storage_keys: vec![B256::with_last_byte(1)],
+                }]),
+                blob_versioned_hashes: vec![B256::with_last_byte(3)],
+                max_fee_per_blob_gas: 4,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let mut tx_env = TxEnv::default();
+        fill_tx_env(&mut tx_env, &blob_tx, sender);
+        assert_eq!(tx_env.caller, sender);
+        assert_eq!(tx_env.transact_to, TransactTo::Call(to));
+        assert_eq!(tx_env.nonce, Some(3));
+        assert_eq!(tx_env.gas_price, U256::from(10));
+        assert_eq!(tx_env.gas_priority_fee, Some(U256::from(2)));
+        assert_eq!(tx_env.access_list, vec![(to, vec![U256::from(1)])]);
+        assert_eq!(tx_env.blob_hashes, vec![B256::with_last_byte(3)]);
+        assert_eq!(tx_env.max_fee_per_blob_gas, Some(U256::from(4)));
+
+        // reusing the environment must not leak fields of the previous transaction
+        let legacy_tx = TransactionSignedNoHash {
+            transaction: Transaction::Legacy(TxLegacy {
+                gas_price: 7,
+                to: TransactionKind::Create,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        fill_tx_env(&mut tx_env, &legacy_tx, sender);
+        assert_eq!(tx_env.transact_to, TransactTo::create());
+        assert_eq!(tx_env.chain_id, None);
+        assert_eq!(tx_env.gas_price, U256::from(7));
+        assert_eq!(tx_env.gas_priority_fee, None);
+        assert!(tx_env.access_list.is_empty());
+        assert!(tx_env.blob_hashes.is_empty());
+        assert_eq!(tx_env.max_fee_per_blob_gas, None);
+    }
+
+    #[test]
+    fn fill_block_env_resets_blob_gas() {
+        let mut block_env = BlockEnv::default();
+        let header = Header { excess_blob_gas: Some(0), ..Default::default() };
+        fill_block_env_with_coinbase(&mut block_env, &header, true, Address::ZERO);
+        assert_eq!(block_env.blob_excess_gas_and_price, Some(BlobExcessGasAndPrice::new(0)));
+
+        let header = Header::default();
+        fill_block_env_with_coinbase(&mut block_env, &header, true, Address::ZERO);
+        assert_eq!(block_env.blob_excess_gas_and_price, None);
+    }
 }
@@ -16,13 +16,18 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    IndexLogsBlooms,
+    IndexAddressAppearances,
     Finish,
     Other(&'static str),
 }
 
 impl StageId {
     /// All supported Stages
-    pub const ALL: [StageId; 13] = [
+    ///
+    /// The optional [StageId::IndexAddressAppearances] isn't included, because its checkpoint
+    /// doesn't follow the other stages while it's disabled.
+    pub const ALL: [StageId; 14] = [
         StageId::Headers,
         StageId::TotalDifficulty,
         StageId::Bodies,
@@ -35,6 +40,7 @@ impl StageId {
         StageId::TransactionLookup,
         StageId::IndexStorageHistory,
         StageId::IndexAccountHistory,
+        StageId::IndexLogsBlooms,
         StageId::Finish,
     ];
 
@@ -53,6 +59,8 @@ impl StageId {
             StageId::TransactionLookup => "TransactionLookup",
             StageId::IndexAccountHistory => "IndexAccountHistory",
             StageId::IndexStorageHistory => "IndexStorageHistory",
+            StageId::IndexLogsBlooms => "IndexLogsBlooms",
+            StageId::IndexAddressAppearances => "IndexAddressAppearances",
             StageId::Finish => "Finish",
             StageId::Other(s) => s,
         }
@@ -92,6 +100,8 @@ mod tests {
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
+        assert_eq!(StageId::IndexLogsBlooms.to_string(), "IndexLogsBlooms");
+        assert_eq!(StageId::IndexAddressAppearances.to_string(), "IndexAddressAppearances");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
@@ -23,6 +23,7 @@ pub use legacy::TxLegacy;
 pub use meta::TransactionMeta;
 #[cfg(feature = "c-kzg")]
 pub use pooled::{PooledTransactionsElement, PooledTransactionsElementEcRecovered};
+pub use reth_rpc_types::{KnownAccount, TransactionConditional};
 #[cfg(feature = "c-kzg")]
 pub use sidecar::{BlobTransaction, BlobTransactionSidecar, BlobTransactionValidationError};
 pub use signature::Signature;
@@ -845,6 +846,18 @@ impl TransactionSignedNoHash {
         self.into()
     }
 
+    /// Converts a list of transactions into transaction types with their hashes, see
+    /// [Self::with_hash].
+    ///
+    /// The hashes of large lists are computed in parallel.
+    pub fn with_hashes(txes: Vec<Self>) -> Vec<TransactionSigned> {
+        if txes.len() < *PARALLEL_SENDER_RECOVERY_THRESHOLD {
+            txes.into_iter().map(Self::with_hash).collect()
+        } else {
+            txes.into_par_iter().map(Self::with_hash).collect()
+        }
+    }
+
     /// Recovers a list of signers from a transaction list iterator
     ///
     /// Returns `None`, if some transaction's signature is invalid, see also
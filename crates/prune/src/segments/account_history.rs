@@ -4,19 +4,31 @@ use crate::{
     },
     PrunerError,
 };
-use reth_db::{database::Database, models::ShardedKey, tables};
+use reth_db::{database::Database, models::ShardedKey, tables, transaction::DbTxMut};
 use reth_primitives::{PruneMode, PruneSegment};
-use reth_provider::DatabaseProviderRW;
+use reth_provider::{bundle_state::bytecode_refs_tracked, BytecodeRefCounter, DatabaseProviderRW};
 use tracing::{instrument, trace};
 
 #[derive(Debug)]
 pub struct AccountHistory {
     mode: PruneMode,
+    /// Whether to delete bytecodes that are no longer referenced after pruning.
+    delete_bytecodes: bool,
 }
 
 impl AccountHistory {
     pub fn new(mode: PruneMode) -> Self {
-        Self { mode }
+        Self { mode, delete_bytecodes: false }
+    }
+
+    /// Delete the bytecodes from the [Bytecodes](tables::Bytecodes) table once the last account
+    /// state referencing them is pruned.
+    ///
+    /// Bytecodes are only deleted if the references are tracked for the whole state, see
+    /// [bytecode_refs_tracked].
+    pub fn with_deleted_bytecodes(mut self, delete_bytecodes: bool) -> Self {
+        self.delete_bytecodes = delete_bytecodes;
+        self
     }
 }
 
@@ -45,15 +57,38 @@ impl<DB: Database> Segment<DB> for AccountHistory {
         let range_end = *range.end();
 
         let mut last_changeset_pruned_block = None;
+        let mut pruned_accounts = Vec::new();
         let (pruned_changesets, done) = provider
             .prune_table_with_range::<tables::AccountChangeSet>(
                 range,
                 input.delete_limit / 2,
                 |_| false,
-                |row| last_changeset_pruned_block = Some(row.0),
+                |row| {
+                    last_changeset_pruned_block = Some(row.0);
+                    pruned_accounts.extend(row.1.info.filter(|info| info.has_bytecode()));
+                },
             )?;
         trace!(target: "pruner", pruned = %pruned_changesets, %done, "Pruned account history (changesets)");
 
+        // the references of databases created before they were tracked are incomplete, so
+        // bytecodes without references may still be used
+        let delete_bytecodes = self.delete_bytecodes && bytecode_refs_tracked(provider.tx_ref())?;
+        if self.delete_bytecodes && !delete_bytecodes {
+            trace!(target: "pruner", "Bytecode references are not tracked, keeping bytecodes");
+        }
+
+        let mut bytecode_refs =
+            BytecodeRefCounter::new(provider.tx_ref().cursor_write::<tables::BytecodeRefs>()?);
+        let mut pruned_bytecodes = 0;
+        for account in &pruned_accounts {
+            if let Some(hash) = bytecode_refs.decrement(Some(account))? {
+                if delete_bytecodes && provider.tx_ref().delete::<tables::Bytecodes>(hash, None)? {
+                    pruned_bytecodes += 1;
+                }
+            }
+        }
+        trace!(target: "pruner", pruned = %pruned_bytecodes, "Pruned account history (bytecodes)");
+
         let last_changeset_pruned_block = last_changeset_pruned_block
             // If there's more account account changesets to prune, set the checkpoint block number
             // to previous, so we could finish pruning its account changesets on the next run.
@@ -70,7 +105,7 @@ impl<DB: Database> Segment<DB> for AccountHistory {
 
         Ok(PruneOutput {
             done,
-            pruned: pruned_changesets + pruned_indices,
+            pruned: pruned_changesets + pruned_indices + pruned_bytecodes,
             checkpoint: Some(PruneOutputCheckpoint {
                 block_number: Some(last_changeset_pruned_block),
                 tx_number: None,
@@ -83,13 +118,16 @@ impl<DB: Database> Segment<DB> for AccountHistory {
 mod tests {
     use crate::segments::{AccountHistory, PruneInput, PruneOutput, Segment};
     use assert_matches::assert_matches;
-    use reth_db::{tables, BlockNumberList};
+    use reth_db::{models::AccountBeforeTx, tables, transaction::DbTxMut, BlockNumberList};
     use reth_interfaces::test_utils::{
         generators,
         generators::{random_block_range, random_changeset_range, random_eoa_account_range},
     };
-    use reth_primitives::{BlockNumber, PruneCheckpoint, PruneMode, PruneSegment, B256};
-    use reth_provider::PruneCheckpointReader;
+    use reth_primitives::{
+        Account, Address, BlockNumber, Bytecode, Bytes, PruneCheckpoint, PruneMode, PruneSegment,
+        B256,
+    };
+    use reth_provider::{bundle_state::mark_bytecode_refs_tracked, PruneCheckpointReader};
     use reth_stages::test_utils::TestStageDB;
     use std::{collections::BTreeMap, ops::AddAssign};
 
@@ -240,4 +278,47 @@ mod tests {
         test_prune(998, 2, (true, 998));
         test_prune(1400, 3, (true, 804));
     }
+
+    #[test]
+    fn prune_unreferenced_bytecodes() {
+        let code_hash = B256::with_last_byte(1);
+        let prune = |refs_tracked: bool| {
+            let db = TestStageDB::default();
+            let mut rng = generators::rng();
+            let blocks = random_block_range(&mut rng, 1..=2, B256::ZERO, 0..1);
+            db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+            db.commit(|tx| {
+                let account = Account { bytecode_hash: Some(code_hash), ..Default::default() };
+                tx.put::<tables::AccountChangeSet>(
+                    1,
+                    AccountBeforeTx { address: Address::with_last_byte(1), info: Some(account) },
+                )?;
+                tx.put::<tables::Bytecodes>(
+                    code_hash,
+                    Bytecode::new_raw(Bytes::from_static(&[0x00])),
+                )?;
+                tx.put::<tables::BytecodeRefs>(code_hash, 1)?;
+                if refs_tracked {
+                    mark_bytecode_refs_tracked(tx)?;
+                }
+                Ok(())
+            })
+            .expect("insert changeset");
+
+            let input = PruneInput { previous_checkpoint: None, to_block: 1, delete_limit: 10 };
+            let provider = db.factory.provider_rw().unwrap();
+            AccountHistory::new(PruneMode::Before(2))
+                .with_deleted_bytecodes(true)
+                .prune(&provider, input)
+                .unwrap();
+            provider.commit().expect("commit");
+
+            assert!(db.table::<tables::BytecodeRefs>().unwrap().is_empty());
+            db.table::<tables::Bytecodes>().unwrap().len()
+        };
+
+        // bytecodes are kept if the references weren't tracked since the database was created
+        assert_eq!(prune(false), 1);
+        assert_eq!(prune(true), 0);
+    }
 }
@@ -44,6 +44,7 @@ impl<DB: Database> SegmentSet<DB> {
             transaction_lookup,
             receipts,
             account_history,
+            account_history_delete_bytecodes,
             storage_history,
             receipts_log_filter,
         } = prune_modes;
@@ -61,7 +62,9 @@ impl<DB: Database> SegmentSet<DB> {
             // Sender recovery
             .segment_opt(sender_recovery.map(SenderRecovery::new))
             // Account history
-            .segment_opt(account_history.map(AccountHistory::new))
+            .segment_opt(account_history.map(|mode| {
+                AccountHistory::new(mode).with_deleted_bytecodes(account_history_delete_bytecodes)
+            }))
             // Storage history
             .segment_opt(storage_history.map(StorageHistory::new))
     }
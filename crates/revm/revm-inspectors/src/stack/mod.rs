@@ -2,19 +2,25 @@ use alloy_primitives::{Address, Bytes, B256, U256};
 use revm::{
     inspectors::CustomPrintTracer,
     interpreter::{CallInputs, CreateInputs, Gas, InstructionResult, Interpreter},
-    primitives::Env,
+    primitives::{Env, TransactTo},
     Database, EVMData, Inspector,
 };
-use std::fmt::Debug;
+use std::{
+    collections::HashSet,
+    fmt::Debug,
+    ops::RangeInclusive,
+    sync::{Arc, Mutex},
+};
 
 /// A wrapped [Inspector] that can be reused in the stack
 mod maybe_owned;
 pub use maybe_owned::MaybeOwnedInspector;
 
-/// One can hook on inspector execution in 3 ways:
+/// One can hook on inspector execution in these ways:
 /// - Block: Hook on block execution
-/// - BlockWithIndex: Hook on block execution transaction index
+/// - BlockRange: Hook on the execution of a range of blocks
 /// - Transaction: Hook on a specific transaction hash
+/// - Addresses: Hook on transactions sent from or to a set of addresses
 #[derive(Debug, Default, Clone)]
 pub enum Hook {
     #[default]
@@ -22,12 +28,76 @@ pub enum Hook {
     None,
     /// Hook on a specific block.
     Block(u64),
+    /// Hook on every transaction in a range of blocks.
+    BlockRange(RangeInclusive<u64>),
     /// Hook on a specific transaction hash.
     Transaction(B256),
+    /// Hook on the transactions that are sent from or to one of the addresses.
+    ///
+    /// Calls to the addresses made by other contracts aren't matched.
+    Addresses(HashSet<Address>),
     /// Hooks on every transaction in a block.
     All,
 }
 
+impl Hook {
+    /// Returns whether the transaction is hooked.
+    pub fn matches(&self, env: &Env, tx_hash: B256) -> bool {
+        match self {
+            Hook::None => false,
+            Hook::Block(block) => env.block.number.to::<u64>() == *block,
+            Hook::BlockRange(blocks) => blocks.contains(&env.block.number.to::<u64>()),
+            Hook::Transaction(hash) => *hash == tx_hash,
+            Hook::Addresses(addresses) => {
+                addresses.contains(&env.tx.caller) ||
+                    matches!(env.tx.transact_to, TransactTo::Call(to) if addresses.contains(&to))
+            }
+            Hook::All => true,
+        }
+    }
+}
+
+/// An inspector supplied by the user that is called for the transactions matching the execution
+/// hook of the [InspectorStack], e.g. to index data while blocks are executed.
+///
+/// Unlike an [Inspector], it doesn't depend on the database, so it can be shared by all executors
+/// and the code that consumes what it collected, see [SharedExecutionInspector].
+pub trait ExecutionInspector: Debug + Send {
+    /// Called before an inspected transaction is executed.
+    fn transaction_start(&mut self, _env: &Env, _tx_hash: B256) {}
+
+    /// Called when a call starts, with the call depth.
+    fn call(&mut self, _depth: u64, _inputs: &CallInputs) {}
+
+    /// Called when a call ends, with the call depth and the result of the call.
+    fn call_end(&mut self, _depth: u64, _inputs: &CallInputs, _result: InstructionResult) {}
+
+    /// Called when a contract creation starts, with the call depth.
+    fn create(&mut self, _depth: u64, _inputs: &CreateInputs) {}
+
+    /// Called when a contract creation ends, with the call depth, the result of the creation and
+    /// the address of the created contract.
+    fn create_end(
+        &mut self,
+        _depth: u64,
+        _inputs: &CreateInputs,
+        _result: InstructionResult,
+        _address: Option<Address>,
+    ) {
+    }
+
+    /// Called when a contract self-destructs and its balance is sent to the target.
+    fn selfdestruct(&mut self, _contract: Address, _target: Address, _value: U256) {}
+
+    /// Called after an inspected transaction was executed, with whether it was successful.
+    ///
+    /// This is not called if the transaction is invalid.
+    fn transaction_end(&mut self, _env: &Env, _tx_hash: B256, _success: bool) {}
+}
+
+/// An [ExecutionInspector] that is shared between the executors and its owner.
+pub type SharedExecutionInspector = Arc<Mutex<dyn ExecutionInspector>>;
+
 /// An inspector that calls multiple inspectors in sequence.
 ///
 /// If a call to an inspector returns a value other than [InstructionResult::Continue] (or
@@ -36,15 +106,25 @@ pub enum Hook {
 pub struct InspectorStack {
     /// An inspector that prints the opcode traces to the console.
     pub custom_print_tracer: Option<CustomPrintTracer>,
+    /// The inspectors supplied by the user.
+    pub execution_inspectors: Vec<SharedExecutionInspector>,
     /// The provided hook
     pub hook: Hook,
+    /// The hook of the execution inspectors, the provided hook is used if it's not set.
+    pub execution_hook: Option<Hook>,
+    /// Whether the current transaction is traced by the print tracer.
+    trace_transaction: bool,
+    /// Whether the current transaction is inspected by the execution inspectors.
+    inspect_transaction: bool,
 }
 
 impl Debug for InspectorStack {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("InspectorStack")
             .field("custom_print_tracer", &self.custom_print_tracer.is_some())
+            .field("execution_inspectors", &self.execution_inspectors.len())
             .field("hook", &self.hook)
+            .field("execution_hook", &self.execution_hook)
             .finish()
     }
 }
@@ -52,7 +132,12 @@ impl Debug for InspectorStack {
 impl InspectorStack {
     /// Create a new inspector stack.
     pub fn new(config: InspectorStackConfig) -> Self {
-        let mut stack = InspectorStack { hook: config.hook, ..Default::default() };
+        let mut stack = InspectorStack {
+            hook: config.hook,
+            execution_hook: config.execution_hook,
+            execution_inspectors: config.execution_inspectors,
+            ..Default::default()
+        };
 
         if config.use_printer_tracer {
             stack.custom_print_tracer = Some(CustomPrintTracer::default());
@@ -63,11 +148,40 @@ impl InspectorStack {
 
     /// Check if the inspector should be used.
     pub fn should_inspect(&self, env: &Env, tx_hash: B256) -> bool {
-        match self.hook {
-            Hook::None => false,
-            Hook::Block(block) => env.block.number.to::<u64>() == block,
-            Hook::Transaction(hash) => hash == tx_hash,
-            Hook::All => true,
+        self.hook.matches(env, tx_hash) ||
+            self.execution_hook.as_ref().is_some_and(|hook| hook.matches(env, tx_hash))
+    }
+
+    /// Selects the inspectors for a transaction that is about to be executed with the stack, and
+    /// notifies the [ExecutionInspector]s if they inspect the transaction.
+    pub fn transaction_start(&mut self, env: &Env, tx_hash: B256) {
+        self.trace_transaction = self.hook.matches(env, tx_hash);
+        self.inspect_transaction =
+            self.execution_hook.as_ref().unwrap_or(&self.hook).matches(env, tx_hash);
+
+        for inspector in self.active_execution_inspectors() {
+            inspector.lock().expect("not poisoned").transaction_start(env, tx_hash);
+        }
+    }
+
+    /// Notifies the [ExecutionInspector]s that an inspected transaction was executed.
+    pub fn transaction_end(&self, env: &Env, tx_hash: B256, success: bool) {
+        for inspector in self.active_execution_inspectors() {
+            inspector.lock().expect("not poisoned").transaction_end(env, tx_hash, success);
+        }
+    }
+
+    /// Returns the print tracer if it traces the current transaction.
+    fn active_print_tracer(&mut self) -> Option<&mut CustomPrintTracer> {
+        self.custom_print_tracer.as_mut().filter(|_| self.trace_transaction)
+    }
+
+    /// Returns the [ExecutionInspector]s if they inspect the current transaction.
+    fn active_execution_inspectors(&self) -> &[SharedExecutionInspector] {
+        if self.inspect_transaction {
+            &self.execution_inspectors
+        } else {
+            &[]
         }
     }
 }
@@ -81,6 +195,13 @@ pub struct InspectorStackConfig {
 
     /// Hook on a specific block or transaction.
     pub hook: Hook,
+
+    /// The inspectors supplied by the user, that are called for the hooked transactions.
+    pub execution_inspectors: Vec<SharedExecutionInspector>,
+
+    /// The transactions the execution inspectors are called for, instead of the ones of the hook,
+    /// e.g. to index all transactions without tracing them.
+    pub execution_hook: Option<Hook>,
 }
 
 /// Helper macro to call the same method on multiple inspectors without resorting to dynamic
@@ -101,13 +222,13 @@ where
     DB: Database,
 {
     fn initialize_interp(&mut self, interpreter: &mut Interpreter<'_>, data: &mut EVMData<'_, DB>) {
-        call_inspectors!(inspector, [&mut self.custom_print_tracer], {
+        call_inspectors!(inspector, [self.active_print_tracer()], {
             inspector.initialize_interp(interpreter, data);
         });
     }
 
     fn step(&mut self, interpreter: &mut Interpreter<'_>, data: &mut EVMData<'_, DB>) {
-        call_inspectors!(inspector, [&mut self.custom_print_tracer], {
+        call_inspectors!(inspector, [self.active_print_tracer()], {
             inspector.step(interpreter, data);
         });
     }
@@ -119,13 +240,13 @@ where
         topics: &[B256],
         data: &Bytes,
     ) {
-        call_inspectors!(inspector, [&mut self.custom_print_tracer], {
+        call_inspectors!(inspector, [self.active_print_tracer()], {
             inspector.log(evm_data, address, topics, data);
         });
     }
 
     fn step_end(&mut self, interpreter: &mut Interpreter<'_>, data: &mut EVMData<'_, DB>) {
-        call_inspectors!(inspector, [&mut self.custom_print_tracer], {
+        call_inspectors!(inspector, [self.active_print_tracer()], {
             inspector.step_end(interpreter, data);
         });
     }
@@ -135,7 +256,11 @@ where
         data: &mut EVMData<'_, DB>,
         inputs: &mut CallInputs,
     ) -> (InstructionResult, Gas, Bytes) {
-        call_inspectors!(inspector, [&mut self.custom_print_tracer], {
+        for inspector in self.active_execution_inspectors() {
+            inspector.lock().expect("not poisoned").call(data.journaled_state.depth(), inputs);
+        }
+
+        call_inspectors!(inspector, [self.active_print_tracer()], {
             let (status, gas, retdata) = inspector.call(data, inputs);
 
             // Allow inspectors to exit early
@@ -155,7 +280,15 @@ where
         ret: InstructionResult,
         out: Bytes,
     ) -> (InstructionResult, Gas, Bytes) {
-        call_inspectors!(inspector, [&mut self.custom_print_tracer], {
+        for inspector in self.active_execution_inspectors() {
+            inspector.lock().expect("not poisoned").call_end(
+                data.journaled_state.depth(),
+                inputs,
+                ret,
+            );
+        }
+
+        call_inspectors!(inspector, [self.active_print_tracer()], {
             let (new_ret, new_gas, new_out) =
                 inspector.call_end(data, inputs, remaining_gas, ret, out.clone());
 
@@ -174,7 +307,11 @@ where
         data: &mut EVMData<'_, DB>,
         inputs: &mut CreateInputs,
     ) -> (InstructionResult, Option<Address>, Gas, Bytes) {
-        call_inspectors!(inspector, [&mut self.custom_print_tracer], {
+        for inspector in self.active_execution_inspectors() {
+            inspector.lock().expect("not poisoned").create(data.journaled_state.depth(), inputs);
+        }
+
+        call_inspectors!(inspector, [self.active_print_tracer()], {
             let (status, addr, gas, retdata) = inspector.create(data, inputs);
 
             // Allow inspectors to exit early
@@ -195,7 +332,16 @@ where
         remaining_gas: Gas,
         out: Bytes,
     ) -> (InstructionResult, Option<Address>, Gas, Bytes) {
-        call_inspectors!(inspector, [&mut self.custom_print_tracer], {
+        for inspector in self.active_execution_inspectors() {
+            inspector.lock().expect("not poisoned").create_end(
+                data.journaled_state.depth(),
+                inputs,
+                ret,
+                address,
+            );
+        }
+
+        call_inspectors!(inspector, [self.active_print_tracer()], {
             let (new_ret, new_address, new_gas, new_retdata) =
                 inspector.create_end(data, inputs, ret, address, remaining_gas, out.clone());
 
@@ -208,7 +354,11 @@ where
     }
 
     fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
-        call_inspectors!(inspector, [&mut self.custom_print_tracer], {
+        for inspector in self.active_execution_inspectors() {
+            inspector.lock().expect("not poisoned").selfdestruct(contract, target, value);
+        }
+
+        call_inspectors!(inspector, [self.active_print_tracer()], {
             Inspector::<DB>::selfdestruct(inspector, contract, target, value);
         });
     }
@@ -354,13 +354,25 @@ impl CallTraceNode {
         }
 
         if include_logs && !self.logs.is_empty() {
+            // the position of a log is the number of child calls made before it was emitted
+            let mut positions = vec![0u64; self.logs.len()];
+            let mut calls = 0;
+            for order in &self.ordering {
+                match order {
+                    LogCallOrder::Log(idx) => positions[*idx] = calls,
+                    LogCallOrder::Call(_) => calls += 1,
+                }
+            }
+
             call_frame.logs = self
                 .logs
                 .iter()
-                .map(|log| CallLogFrame {
+                .zip(positions)
+                .map(|(log, position)| CallLogFrame {
                     address: Some(self.execution_address()),
                     topics: Some(log.topics().to_vec()),
                     data: Some(log.data.clone()),
+                    position: Some(U64::from(position)),
                 })
                 .collect();
         }
@@ -0,0 +1,130 @@
+use parking_lot::Mutex;
+use reth_primitives::{Address, B256, U256};
+use revm::{
+    primitives::{AccountInfo, Bytecode},
+    Database,
+};
+use schnellru::{ByLength, LruMap};
+use std::sync::Arc;
+
+/// The default maximum number of bytecodes kept by a [BytecodeCache].
+pub const DEFAULT_MAX_CACHED_BYTECODES: u32 = 4096;
+
+/// An LRU cache of bytecodes that is shared by all executors of an [EvmProcessorFactory].
+///
+/// Bytecodes are stored by their hash and never change, so the cached bytecodes are valid for the
+/// state of any block and never have to be invalidated, e.g. on reorgs.
+///
+/// [EvmProcessorFactory]: crate::EvmProcessorFactory
+#[derive(Debug, Clone)]
+pub struct BytecodeCache {
+    bytecodes: Arc<Mutex<LruMap<B256, Bytecode, ByLength>>>,
+}
+
+impl BytecodeCache {
+    /// Creates a cache that keeps at most `max_bytecodes` bytecodes.
+    pub fn new(max_bytecodes: u32) -> Self {
+        Self { bytecodes: Arc::new(Mutex::new(LruMap::new(ByLength::new(max_bytecodes)))) }
+    }
+
+    /// Returns the cached bytecode with the hash.
+    pub fn get(&self, code_hash: &B256) -> Option<Bytecode> {
+        self.bytecodes.lock().get(code_hash).cloned()
+    }
+
+    /// Caches the bytecode with the hash.
+    pub fn insert(&self, code_hash: B256, bytecode: Bytecode) {
+        self.bytecodes.lock().insert(code_hash, bytecode);
+    }
+
+    /// Returns the number of cached bytecodes.
+    pub fn len(&self) -> usize {
+        self.bytecodes.lock().len()
+    }
+
+    /// Returns `true` if no bytecodes are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for BytecodeCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CACHED_BYTECODES)
+    }
+}
+
+/// A [Database] that reads bytecodes through a [BytecodeCache] and everything else from the
+/// wrapped database.
+#[derive(Debug)]
+pub struct BytecodeCacheDatabase<DB> {
+    /// The wrapped database.
+    db: DB,
+    /// The shared bytecode cache.
+    cache: BytecodeCache,
+}
+
+impl<DB> BytecodeCacheDatabase<DB> {
+    /// Wraps the database.
+    pub fn new(db: DB, cache: BytecodeCache) -> Self {
+        Self { db, cache }
+    }
+}
+
+impl<DB: Database> Database for BytecodeCacheDatabase<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.db.basic(address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if let Some(bytecode) = self.cache.get(&code_hash) {
+            return Ok(bytecode)
+        }
+        let bytecode = self.db.code_by_hash(code_hash)?;
+        // missing bytecodes are returned as empty bytecodes, which aren't cached
+        if !bytecode.is_empty() {
+            self.cache.insert(code_hash, bytecode.clone());
+        }
+        Ok(bytecode)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.db.storage(address, index)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        self.db.block_hash(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        primitives::Bytes,
+    };
+
+    #[test]
+    fn caches_bytecodes() {
+        let code_hash = B256::with_last_byte(1);
+        let bytecode = Bytecode::new_raw(Bytes::from_static(&[0x00]));
+        let mut state = CacheDB::new(EmptyDB::default());
+        state.contracts.insert(code_hash, bytecode.clone());
+
+        let cache = BytecodeCache::new(1);
+        let mut db = BytecodeCacheDatabase::new(state, cache.clone());
+        assert_eq!(db.code_by_hash(code_hash).unwrap(), bytecode);
+        assert_eq!(cache.get(&code_hash), Some(bytecode.clone()));
+
+        // the cached bytecode is returned without reading the database
+        db.db.contracts.clear();
+        assert_eq!(db.code_by_hash(code_hash).unwrap(), bytecode);
+
+        // missing bytecodes aren't cached
+        assert!(db.code_by_hash(B256::with_last_byte(2)).unwrap().is_empty());
+        assert_eq!(cache.len(), 1);
+    }
+}
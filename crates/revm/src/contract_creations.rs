@@ -0,0 +1,215 @@
+use crate::stack::ExecutionInspector;
+use reth_primitives::{Address, BlockNumber, B256};
+use revm::{
+    interpreter::{CallInputs, CreateInputs, InstructionResult},
+    primitives::Env,
+};
+
+/// A contract created by a transaction or by a contract it called.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContractCreation {
+    /// The number of the block of the transaction.
+    pub block_number: BlockNumber,
+    /// The index of the transaction in the block.
+    pub transaction_index: u64,
+    /// The address of the created contract.
+    pub address: Address,
+    /// The address that created the contract, the sender of the transaction or a contract.
+    pub creator: Address,
+}
+
+/// An [ExecutionInspector] that collects the [ContractCreation]s of the inspected transactions.
+///
+/// It has to inspect all transactions of the executed blocks, since the index of a transaction in
+/// its block is derived from the order in which they're executed. Contracts whose creation, or one
+/// of the calls around it, was reverted, and the ones of transactions that failed, are discarded.
+#[derive(Debug, Default)]
+pub struct ContractCreationsInspector {
+    /// The number of the block of the current transaction, unset until a transaction starts.
+    block_number: Option<BlockNumber>,
+    /// The index of the current transaction in its block.
+    transaction_index: u64,
+    /// The contracts created by the current transaction.
+    pending: Vec<ContractCreation>,
+    /// For each active call or contract creation, the number of pending creations when it started.
+    checkpoints: Vec<usize>,
+    /// The contracts created by the transactions that were executed successfully.
+    creations: Vec<ContractCreation>,
+}
+
+impl ContractCreationsInspector {
+    /// Returns the collected contract creations of all successful transactions, in the order they
+    /// were made, and clears them.
+    pub fn take_creations(&mut self) -> Vec<ContractCreation> {
+        std::mem::take(&mut self.creations)
+    }
+
+    /// Discards all collected contract creations.
+    ///
+    /// This has to be called before a block is executed again.
+    pub fn clear(&mut self) {
+        self.block_number = None;
+        self.transaction_index = 0;
+        self.pending.clear();
+        self.checkpoints.clear();
+        self.creations.clear();
+    }
+
+    /// Removes the pending creations of the call or contract creation that ended, if it failed.
+    fn end_frame(&mut self, result: InstructionResult) {
+        let checkpoint = self.checkpoints.pop().unwrap_or_default();
+        if !result.is_ok() {
+            self.pending.truncate(checkpoint);
+        }
+    }
+}
+
+impl ExecutionInspector for ContractCreationsInspector {
+    fn transaction_start(&mut self, env: &Env, _tx_hash: B256) {
+        let block_number = env.block.number.saturating_to();
+        if self.block_number == Some(block_number) {
+            self.transaction_index += 1;
+        } else {
+            self.block_number = Some(block_number);
+            self.transaction_index = 0;
+        }
+        self.pending.clear();
+        self.checkpoints.clear();
+    }
+
+    fn call(&mut self, _depth: u64, _inputs: &CallInputs) {
+        self.checkpoints.push(self.pending.len());
+    }
+
+    fn call_end(&mut self, _depth: u64, _inputs: &CallInputs, result: InstructionResult) {
+        self.end_frame(result);
+    }
+
+    fn create(&mut self, _depth: u64, _inputs: &CreateInputs) {
+        self.checkpoints.push(self.pending.len());
+    }
+
+    fn create_end(
+        &mut self,
+        _depth: u64,
+        inputs: &CreateInputs,
+        result: InstructionResult,
+        address: Option<Address>,
+    ) {
+        // the contract is recorded after the creations of its constructor
+        if let Some(address) = address.filter(|_| result.is_ok()) {
+            self.pending.push(ContractCreation {
+                block_number: self.block_number.unwrap_or_default(),
+                transaction_index: self.transaction_index,
+                address,
+                creator: inputs.caller,
+            });
+        }
+        self.end_frame(result);
+    }
+
+    fn transaction_end(&mut self, _env: &Env, _tx_hash: B256, success: bool) {
+        if success {
+            self.creations.append(&mut self.pending);
+        }
+        self.pending.clear();
+        self.checkpoints.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Bytes, U256};
+    use revm::interpreter::{CallContext, CreateScheme, Transfer};
+
+    fn call() -> CallInputs {
+        CallInputs {
+            contract: Address::with_last_byte(2),
+            transfer: Transfer {
+                source: Address::with_last_byte(1),
+                target: Address::with_last_byte(2),
+                value: U256::ZERO,
+            },
+            input: Bytes::new(),
+            gas_limit: 100_000,
+            context: CallContext::default(),
+            is_static: false,
+        }
+    }
+
+    fn create(caller: u8) -> CreateInputs {
+        CreateInputs {
+            caller: Address::with_last_byte(caller),
+            scheme: CreateScheme::Create,
+            value: U256::ZERO,
+            init_code: Bytes::new(),
+            gas_limit: 100_000,
+        }
+    }
+
+    /// Ends a contract creation of the caller with the result, creating the contract at the
+    /// address.
+    fn create_end(
+        inspector: &mut ContractCreationsInspector,
+        depth: u64,
+        caller: u8,
+        result: InstructionResult,
+        address: u8,
+    ) {
+        let address = Some(Address::with_last_byte(address));
+        inspector.create_end(depth, &create(caller), result, address);
+    }
+
+    #[test]
+    fn collects_internal_creations() {
+        let mut env = Env::default();
+        env.block.number = U256::from(7);
+        let mut inspector = ContractCreationsInspector::default();
+
+        // a contract creation transaction whose constructor creates another contract
+        inspector.transaction_start(&env, B256::with_last_byte(1));
+        inspector.create(0, &create(1));
+        inspector.create(1, &create(2));
+        create_end(&mut inspector, 1, 2, InstructionResult::Return, 3);
+        create_end(&mut inspector, 0, 1, InstructionResult::Return, 2);
+        inspector.transaction_end(&env, B256::with_last_byte(1), true);
+
+        // a call that creates a contract in a reverted sub call and in a successful one
+        inspector.transaction_start(&env, B256::with_last_byte(2));
+        inspector.call(0, &call());
+        inspector.call(1, &call());
+        inspector.create(2, &create(2));
+        create_end(&mut inspector, 2, 2, InstructionResult::Return, 4);
+        inspector.call_end(1, &call(), InstructionResult::Revert);
+        inspector.create(1, &create(2));
+        create_end(&mut inspector, 1, 2, InstructionResult::Return, 5);
+        inspector.call_end(0, &call(), InstructionResult::Stop);
+        inspector.transaction_end(&env, B256::with_last_byte(2), true);
+
+        // a failed transaction doesn't create contracts
+        inspector.transaction_start(&env, B256::with_last_byte(3));
+        inspector.create(0, &create(1));
+        create_end(&mut inspector, 0, 1, InstructionResult::Return, 6);
+        inspector.transaction_end(&env, B256::with_last_byte(3), false);
+
+        let creation = |block_number, transaction_index, creator, address| ContractCreation {
+            block_number,
+            transaction_index,
+            address: Address::with_last_byte(address),
+            creator: Address::with_last_byte(creator),
+        };
+        assert_eq!(
+            inspector.take_creations(),
+            vec![creation(7, 0, 2, 3), creation(7, 0, 1, 2), creation(7, 1, 2, 5)]
+        );
+
+        // the transaction index restarts in the next block
+        env.block.number = U256::from(8);
+        inspector.transaction_start(&env, B256::with_last_byte(4));
+        inspector.create(0, &create(1));
+        create_end(&mut inspector, 0, 1, InstructionResult::Return, 7);
+        inspector.transaction_end(&env, B256::with_last_byte(4), true);
+        assert_eq!(inspector.take_creations(), vec![creation(8, 0, 1, 7)]);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    bytecode_cache::{BytecodeCache, BytecodeCacheDatabase},
     database::StateProviderDatabase,
     processor::EVMProcessor,
     stack::{InspectorStack, InspectorStackConfig},
@@ -12,12 +13,14 @@ use std::sync::Arc;
 pub struct EvmProcessorFactory {
     chain_spec: Arc<ChainSpec>,
     stack: Option<InspectorStack>,
+    check_invariants: bool,
+    bytecode_cache: Option<BytecodeCache>,
 }
 
 impl EvmProcessorFactory {
     /// Create new factory
     pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { chain_spec, stack: None }
+        Self { chain_spec, stack: None, check_invariants: false, bytecode_cache: None }
     }
 
     /// Sets the inspector stack for all generated executors.
@@ -31,6 +34,20 @@ impl EvmProcessorFactory {
         self.stack = Some(InspectorStack::new(config));
         self
     }
+
+    /// Enables executor invariant checks for all generated executors.
+    ///
+    /// See [EVMProcessor::set_check_invariants].
+    pub fn with_invariant_checks(mut self, check_invariants: bool) -> Self {
+        self.check_invariants = check_invariants;
+        self
+    }
+
+    /// Reads the bytecodes of all generated executors through the shared cache.
+    pub fn with_bytecode_cache(mut self, cache: BytecodeCache) -> Self {
+        self.bytecode_cache = Some(cache);
+        self
+    }
 }
 
 impl ExecutorFactory for EvmProcessorFactory {
@@ -39,10 +56,17 @@ impl ExecutorFactory for EvmProcessorFactory {
         sp: SP,
     ) -> Box<dyn PrunableBlockExecutor + 'a> {
         let database_state = StateProviderDatabase::new(sp);
-        let mut evm = Box::new(EVMProcessor::new_with_db(self.chain_spec.clone(), database_state));
+        let mut evm = Box::new(match &self.bytecode_cache {
+            Some(cache) => EVMProcessor::new_with_database(
+                self.chain_spec.clone(),
+                BytecodeCacheDatabase::new(database_state, cache.clone()),
+            ),
+            None => EVMProcessor::new_with_db(self.chain_spec.clone(), database_state),
+        });
         if let Some(ref stack) = self.stack {
             evm.set_stack(stack.clone());
         }
+        evm.set_check_invariants(self.check_invariants);
         evm
     }
 
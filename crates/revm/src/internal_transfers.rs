@@ -0,0 +1,202 @@
+use crate::stack::ExecutionInspector;
+use reth_primitives::{Address, BlockNumber, InternalTransfer, TxHash, B256, U256};
+use revm::{
+    interpreter::{CallInputs, CreateInputs, InstructionResult},
+    primitives::Env,
+};
+
+/// An [ExecutionInspector] that collects the [InternalTransfer]s of the inspected transactions.
+///
+/// Transfers of calls and contract creations that were reverted, and of transactions that failed,
+/// are discarded.
+#[derive(Debug, Default)]
+pub struct InternalTransfersInspector {
+    /// The number of the block of the current transaction.
+    block_number: BlockNumber,
+    /// The hash of the current transaction.
+    transaction_hash: TxHash,
+    /// The transfers of the current transaction.
+    pending: Vec<InternalTransfer>,
+    /// For each active call or contract creation, the number of pending transfers when it
+    /// started.
+    checkpoints: Vec<usize>,
+    /// The transfers of the transactions that were executed successfully.
+    transfers: Vec<InternalTransfer>,
+}
+
+impl InternalTransfersInspector {
+    /// Returns the collected transfers of all successful transactions, in the order they were
+    /// made, and clears them.
+    pub fn take_transfers(&mut self) -> Vec<InternalTransfer> {
+        std::mem::take(&mut self.transfers)
+    }
+
+    /// Discards all collected transfers.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.checkpoints.clear();
+        self.transfers.clear();
+    }
+
+    fn push_transfer(&mut self, from: Address, to: Address, value: U256) {
+        if value.is_zero() || from == to {
+            return
+        }
+        self.pending.push(InternalTransfer {
+            block_number: self.block_number,
+            transaction_hash: self.transaction_hash,
+            from,
+            to,
+            value,
+        });
+    }
+
+    /// Removes the pending transfers of the call or contract creation that ended, if it failed.
+    fn end_frame(&mut self, result: InstructionResult) {
+        let checkpoint = self.checkpoints.pop().unwrap_or_default();
+        if !result.is_ok() {
+            self.pending.truncate(checkpoint);
+        }
+    }
+}
+
+impl ExecutionInspector for InternalTransfersInspector {
+    fn transaction_start(&mut self, env: &Env, tx_hash: B256) {
+        self.block_number = env.block.number.saturating_to();
+        self.transaction_hash = tx_hash;
+        self.pending.clear();
+        self.checkpoints.clear();
+    }
+
+    fn call(&mut self, depth: u64, inputs: &CallInputs) {
+        self.checkpoints.push(self.pending.len());
+        // the value of the transaction itself isn't an internal transfer
+        if depth > 0 {
+            let transfer = &inputs.transfer;
+            self.push_transfer(transfer.source, transfer.target, transfer.value);
+        }
+    }
+
+    fn call_end(&mut self, _depth: u64, _inputs: &CallInputs, result: InstructionResult) {
+        self.end_frame(result);
+    }
+
+    fn create(&mut self, depth: u64, inputs: &CreateInputs) {
+        self.checkpoints.push(self.pending.len());
+        // the address of the created contract is set once the creation ended
+        if depth > 0 {
+            self.push_transfer(inputs.caller, Address::ZERO, inputs.value);
+        }
+    }
+
+    fn create_end(
+        &mut self,
+        depth: u64,
+        inputs: &CreateInputs,
+        result: InstructionResult,
+        address: Option<Address>,
+    ) {
+        if let Some(address) = address.filter(|_| depth > 0 && !inputs.value.is_zero()) {
+            let checkpoint = self.checkpoints.last().copied().unwrap_or_default();
+            if let Some(transfer) = self.pending.get_mut(checkpoint) {
+                transfer.to = address;
+            }
+        }
+        self.end_frame(result);
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        self.push_transfer(contract, target, value);
+    }
+
+    fn transaction_end(&mut self, _env: &Env, _tx_hash: B256, success: bool) {
+        if success {
+            self.transfers.append(&mut self.pending);
+        }
+        self.pending.clear();
+        self.checkpoints.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Bytes;
+    use revm::interpreter::{CallContext, CreateScheme, Transfer};
+
+    fn call(from: u8, to: u8, value: u64) -> CallInputs {
+        CallInputs {
+            contract: Address::with_last_byte(to),
+            transfer: Transfer {
+                source: Address::with_last_byte(from),
+                target: Address::with_last_byte(to),
+                value: U256::from(value),
+            },
+            input: Bytes::new(),
+            gas_limit: 100_000,
+            context: CallContext::default(),
+            is_static: false,
+        }
+    }
+
+    #[test]
+    fn discards_reverted_transfers() {
+        let mut env = Env::default();
+        env.block.number = U256::from(7);
+        let tx_hash = B256::with_last_byte(1);
+        let mut inspector = InternalTransfersInspector::default();
+
+        inspector.transaction_start(&env, tx_hash);
+        // the value of the transaction isn't collected
+        inspector.call(0, &call(1, 2, 10));
+        inspector.call(1, &call(2, 3, 5));
+        inspector.call_end(1, &call(2, 3, 5), InstructionResult::Return);
+        // a reverted call discards the transfers of its sub calls
+        inspector.call(1, &call(2, 4, 1));
+        inspector.call(2, &call(4, 5, 1));
+        inspector.call_end(2, &call(4, 5, 1), InstructionResult::Stop);
+        inspector.call_end(1, &call(2, 4, 1), InstructionResult::Revert);
+        let create = CreateInputs {
+            caller: Address::with_last_byte(2),
+            scheme: CreateScheme::Create,
+            value: U256::from(2),
+            init_code: Bytes::new(),
+            gas_limit: 100_000,
+        };
+        inspector.create(1, &create);
+        inspector.create_end(
+            1,
+            &create,
+            InstructionResult::Return,
+            Some(Address::with_last_byte(6)),
+        );
+        inspector.selfdestruct(
+            Address::with_last_byte(2),
+            Address::with_last_byte(1),
+            U256::from(3),
+        );
+        inspector.call_end(0, &call(1, 2, 10), InstructionResult::Stop);
+        inspector.transaction_end(&env, tx_hash, true);
+
+        let transfer = |from, to, value| InternalTransfer {
+            block_number: 7,
+            transaction_hash: tx_hash,
+            from: Address::with_last_byte(from),
+            to: Address::with_last_byte(to),
+            value: U256::from(value),
+        };
+        assert_eq!(
+            inspector.take_transfers(),
+            vec![transfer(2, 3, 5), transfer(2, 6, 2), transfer(2, 1, 3)]
+        );
+
+        // the transfers of a failed transaction are discarded
+        inspector.transaction_start(&env, B256::with_last_byte(2));
+        inspector.call(0, &call(1, 2, 0));
+        inspector.call(1, &call(2, 3, 5));
+        inspector.call_end(1, &call(2, 3, 5), InstructionResult::Return);
+        inspector.call_end(0, &call(1, 2, 0), InstructionResult::Revert);
+        inspector.transaction_end(&env, B256::with_last_byte(2), false);
+        assert!(inspector.take_transfers().is_empty());
+    }
+}
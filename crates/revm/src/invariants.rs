@@ -0,0 +1,87 @@
+use reth_interfaces::executor::BlockExecutionError;
+use reth_primitives::{Address, Header, TransactionSigned, U256};
+use revm::{
+    primitives::{ExecutionResult, State as EvmState},
+    Database,
+};
+
+/// Checks the invariants that must hold after a transaction was executed, before its state changes
+/// are committed to the database:
+///
+/// 1. The cumulative gas used by the block does not exceed the block gas limit.
+/// 2. The balance of the block beneficiary increases at least by the priority fee paid by the
+///    transaction, unless the beneficiary is the sender.
+/// 3. The total balance of all touched accounts decreases exactly by the burnt base fee. If an
+///    account was selfdestructed or blob gas was paid, the total balance may decrease further.
+///
+/// A violation of any of these indicates a bug in the executor rather than an invalid block.
+pub(crate) fn check_transaction_invariants<DB: Database>(
+    db: &mut DB,
+    header: &Header,
+    transaction: &TransactionSigned,
+    sender: Address,
+    result: &ExecutionResult,
+    state: &EvmState,
+    cumulative_gas_used: u64,
+) -> Result<(), BlockExecutionError> {
+    let violation = |message: String| BlockExecutionError::InvariantViolation {
+        hash: Box::new(transaction.hash),
+        message,
+    };
+
+    if cumulative_gas_used > header.gas_limit {
+        return Err(violation(format!(
+            "cumulative gas used {cumulative_gas_used} exceeds block gas limit {}",
+            header.gas_limit
+        )))
+    }
+
+    let gas_used = U256::from(result.gas_used());
+    let base_fee = header.base_fee_per_gas;
+    let priority_fee =
+        U256::from(transaction.effective_tip_per_gas(base_fee).unwrap_or_default()) * gas_used;
+    let burnt_fee = U256::from(base_fee.unwrap_or_default()) * gas_used;
+
+    let mut balance_before = U256::ZERO;
+    let mut balance_after = U256::ZERO;
+    let mut may_burn_more = transaction.is_eip4844();
+    for (address, account) in state {
+        let before = db
+            .basic(*address)
+            .map_err(|_| BlockExecutionError::ProviderError)?
+            .map(|info| info.balance)
+            .unwrap_or_default();
+        balance_before += before;
+        balance_after += account.info.balance;
+        may_burn_more |= account.is_selfdestructed();
+
+        if *address == header.beneficiary &&
+            *address != sender &&
+            account.info.balance < before + priority_fee
+        {
+            return Err(violation(format!(
+                "beneficiary balance increased from {before} to {}, expected at least {priority_fee} in fees",
+                account.info.balance
+            )))
+        }
+    }
+
+    if !priority_fee.is_zero() &&
+        header.beneficiary != sender &&
+        !state.contains_key(&header.beneficiary)
+    {
+        return Err(violation(format!("beneficiary was not paid {priority_fee} in fees")))
+    }
+
+    let expected_balance_after = balance_before.saturating_sub(burnt_fee);
+    if balance_after > expected_balance_after ||
+        (balance_after < expected_balance_after && !may_burn_more)
+    {
+        return Err(violation(format!(
+            "total balance of touched accounts changed from {balance_before} to {balance_after}, \
+             expected {expected_balance_after} after burning {burnt_fee}"
+        )))
+    }
+
+    Ok(())
+}
@@ -15,12 +15,28 @@ pub mod database;
 /// revm implementation of reth block and transaction executors.
 mod factory;
 
+/// Caching of bytecodes across executors.
+pub mod bytecode_cache;
+
+/// Collection of the contracts created by transactions and contracts.
+pub mod contract_creations;
+
+/// Collection of the ether transfers made by contracts.
+pub mod internal_transfers;
+
 /// new revm account state executor
 pub mod processor;
 
+/// Debug checks of executor invariants.
+#[cfg(not(feature = "optimism"))]
+mod invariants;
+
 /// State changes that are not related to transactions.
 pub mod state_change;
 
+/// Recording of the state accessed during execution.
+pub mod witness;
+
 /// revm executor factory.
 pub use factory::EvmProcessorFactory;
 
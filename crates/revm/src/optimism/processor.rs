@@ -77,6 +77,8 @@ impl<'a> BlockExecutor for EVMProcessor<'a> {
             )
         })?;
 
+        self.prewarm_access_lists(block);
+
         let mut cumulative_gas_used = 0;
         let mut receipts = Vec::with_capacity(block.body.len());
         for (sender, transaction) in block.transactions_with_sender() {
@@ -17,10 +17,12 @@ use reth_provider::{
 use revm::{
     db::{states::bundle_state::BundleRetention, StateDBBox},
     primitives::ResultAndState,
-    State, EVM,
+    Database, State, EVM,
 };
 use std::{sync::Arc, time::Instant};
 
+#[cfg(not(feature = "optimism"))]
+use crate::invariants::check_transaction_invariants;
 #[cfg(not(feature = "optimism"))]
 use reth_primitives::revm::compat::into_reth_log;
 #[cfg(not(feature = "optimism"))]
@@ -28,7 +30,7 @@ use reth_provider::BundleStateWithReceipts;
 #[cfg(not(feature = "optimism"))]
 use revm::DatabaseCommit;
 #[cfg(not(feature = "optimism"))]
-use tracing::{debug, trace};
+use tracing::{debug, error, trace};
 
 /// EVMProcessor is a block executor that uses revm to execute blocks or multiple blocks.
 ///
@@ -74,6 +76,9 @@ pub struct EVMProcessor<'a> {
     pruning_address_filter: Option<(u64, Vec<Address>)>,
     /// Execution stats
     pub(crate) stats: BlockExecutorStats,
+    /// Whether to check executor invariants after each transaction.
+    #[cfg_attr(feature = "optimism", allow(dead_code))]
+    check_invariants: bool,
 }
 
 impl<'a> EVMProcessor<'a> {
@@ -95,6 +100,7 @@ impl<'a> EVMProcessor<'a> {
             prune_modes: PruneModes::none(),
             pruning_address_filter: None,
             stats: BlockExecutorStats::default(),
+            check_invariants: false,
         }
     }
 
@@ -102,6 +108,16 @@ impl<'a> EVMProcessor<'a> {
     pub fn new_with_db<DB: StateProvider + 'a>(
         chain_spec: Arc<ChainSpec>,
         db: StateProviderDatabase<DB>,
+    ) -> Self {
+        EVMProcessor::new_with_database(chain_spec, db)
+    }
+
+    /// Creates a new executor from the given chain spec and revm database, e.g. a
+    /// [StateProviderDatabase] wrapped in a
+    /// [BytecodeCacheDatabase](crate::bytecode_cache::BytecodeCacheDatabase).
+    pub fn new_with_database<DB: Database<Error = ProviderError> + Send + 'a>(
+        chain_spec: Arc<ChainSpec>,
+        db: DB,
     ) -> Self {
         let state = State::builder()
             .with_database_boxed(Box::new(db))
@@ -128,6 +144,7 @@ impl<'a> EVMProcessor<'a> {
             prune_modes: PruneModes::none(),
             pruning_address_filter: None,
             stats: BlockExecutorStats::default(),
+            check_invariants: false,
         }
     }
 
@@ -136,6 +153,14 @@ impl<'a> EVMProcessor<'a> {
         self.stack = stack;
     }
 
+    /// Enables checking the executor invariants after each transaction.
+    ///
+    /// A violation halts the execution with [BlockExecutionError::InvariantViolation]. This is a
+    /// debugging aid for catching consensus bugs and has no effect with the `optimism` feature.
+    pub fn set_check_invariants(&mut self, check_invariants: bool) {
+        self.check_invariants = check_invariants;
+    }
+
     /// Configure the executor with the given block.
     pub fn set_first_block(&mut self, num: BlockNumber) {
         self.first_block = Some(num);
@@ -223,6 +248,30 @@ impl<'a> EVMProcessor<'a> {
         Ok(())
     }
 
+    /// Loads the accounts and storage slots named in the access lists of the block's transactions
+    /// into the state cache, before any of the transactions is executed.
+    ///
+    /// This reads the state up front instead of interleaving the reads with the execution. The gas
+    /// costs are unaffected, the EVM tracks warm and cold accesses per transaction. Loading stops
+    /// at the first database error, which the execution then runs into and reports.
+    pub(crate) fn prewarm_access_lists(&mut self, block: &BlockWithSenders) {
+        let time = Instant::now();
+        let db = self.db_mut();
+        'prewarm: for (address, storage_keys) in
+            block.body.iter().filter_map(|tx| tx.access_list()).flat_map(|list| list.flatten())
+        {
+            if db.basic(address).is_err() {
+                break
+            }
+            for key in storage_keys {
+                if db.storage(address, key).is_err() {
+                    break 'prewarm
+                }
+            }
+        }
+        self.stats.prewarm_duration += time.elapsed();
+    }
+
     /// Runs a single transaction in the configured environment and proceeds
     /// to return the result and state diff (without applying it).
     ///
@@ -246,12 +295,16 @@ impl<'a> EVMProcessor<'a> {
         let hash = transaction.hash();
         let out = if self.stack.should_inspect(&self.evm.env, hash) {
             // execution with inspector.
+            self.stack.transaction_start(&self.evm.env, hash);
             let output = self.evm.inspect(&mut self.stack);
             tracing::trace!(
                 target: "evm",
                 ?hash, ?output, ?transaction, env = ?self.evm.env,
                 "Executed transaction"
             );
+            if let Ok(ResultAndState { result, .. }) = &output {
+                self.stack.transaction_end(&self.evm.env, hash, result.is_success());
+            }
             output
         } else {
             // main execution.
@@ -422,6 +475,8 @@ impl<'a> BlockExecutor for EVMProcessor<'a> {
             return Ok((Vec::new(), 0));
         }
 
+        self.prewarm_access_lists(block);
+
         let mut cumulative_gas_used = 0;
         let mut receipts = Vec::with_capacity(block.body.len());
         for (sender, transaction) in block.transactions_with_sender() {
@@ -444,15 +499,39 @@ impl<'a> BlockExecutor for EVMProcessor<'a> {
                 "Executed transaction"
             );
             self.stats.execution_duration += time.elapsed();
+
+            // append gas used
+            cumulative_gas_used += result.gas_used();
+
+            if self.check_invariants {
+                let header = &block.header;
+                check_transaction_invariants(
+                    self.db_mut(),
+                    header,
+                    transaction,
+                    *sender,
+                    &result,
+                    &state,
+                    cumulative_gas_used,
+                )
+                .map_err(|error| {
+                    error!(
+                        target: "evm",
+                        block = header.number,
+                        ?transaction,
+                        %error,
+                        "Executor invariant check failed"
+                    );
+                    error
+                })?;
+            }
+
             let time = Instant::now();
 
             self.db_mut().commit(state);
 
             self.stats.apply_state_duration += time.elapsed();
 
-            // append gas used
-            cumulative_gas_used += result.gas_used();
-
             // Push transaction changeset and calculate header bloom filter for receipt.
             receipts.push(Receipt {
                 tx_type: transaction.tx_type(),
@@ -537,20 +616,25 @@ pub fn verify_receipt<'a>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::stack::{ExecutionInspector, Hook};
     use reth_interfaces::provider::ProviderResult;
     use reth_primitives::{
         bytes,
         constants::{BEACON_ROOTS_ADDRESS, SYSTEM_ADDRESS},
         keccak256,
         trie::AccountProof,
-        Account, Bytecode, Bytes, ChainSpecBuilder, ForkCondition, StorageKey, MAINNET,
+        AccessList, AccessListItem, Account, Bytecode, Bytes, ChainSpecBuilder, ForkCondition,
+        Signature, StorageKey, Transaction, TransactionKind, TxEip2930, TxLegacy, MAINNET,
     };
     use reth_provider::{
         AccountReader, BlockHashReader, BundleStateWithReceipts, StateRootProvider,
     };
     use reth_trie::updates::TrieUpdates;
-    use revm::{Database, TransitionState};
-    use std::collections::HashMap;
+    use revm::{primitives::Env, Database, TransitionState};
+    use std::{
+        collections::{HashMap, HashSet},
+        sync::Mutex,
+    };
 
     static BEACON_ROOT_CONTRACT_CODE: Bytes = bytes!("3373fffffffffffffffffffffffffffffffffffffffe14604d57602036146024575f5ffd5b5f35801560495762001fff810690815414603c575f5ffd5b62001fff01545f5260205ff35b5f5ffd5b62001fff42064281555f359062001fff015500");
 
@@ -615,6 +699,14 @@ mod tests {
         ) -> ProviderResult<(B256, TrieUpdates)> {
             unimplemented!("state root computation is not supported")
         }
+
+        fn storage_root(
+            &self,
+            _address: Address,
+            _bundle_state: &BundleStateWithReceipts,
+        ) -> ProviderResult<B256> {
+            unimplemented!("storage root computation is not supported")
+        }
     }
 
     impl StateProvider for StateProviderTest {
@@ -1008,4 +1100,104 @@ mod tests {
             .unwrap();
         assert_eq!(parent_beacon_block_root_storage, U256::from(0x69));
     }
+
+    #[test]
+    fn execution_inspector_hooks_addresses() {
+        #[derive(Debug, Default)]
+        struct RecordingInspector {
+            transactions: Vec<(B256, bool)>,
+        }
+
+        impl ExecutionInspector for RecordingInspector {
+            fn transaction_end(&mut self, _env: &Env, tx_hash: B256, success: bool) {
+                self.transactions.push((tx_hash, success));
+            }
+        }
+
+        let sender = Address::with_last_byte(1);
+        let watched = Address::with_last_byte(2);
+        let transfer = |nonce, to| {
+            TransactionSigned::from_transaction_and_signature(
+                Transaction::Legacy(TxLegacy {
+                    nonce,
+                    gas_limit: 21_000,
+                    to: TransactionKind::Call(to),
+                    ..Default::default()
+                }),
+                Signature::default(),
+            )
+        };
+        let transactions = vec![transfer(0, watched), transfer(1, Address::with_last_byte(3))];
+        let header =
+            Header { number: 1, gas_used: 42_000, gas_limit: 30_000_000, ..Default::default() };
+        let block = BlockWithSenders {
+            block: Block { header, body: transactions.clone(), ommers: vec![], withdrawals: None },
+            senders: vec![sender; 2],
+        };
+
+        let mut db = StateProviderTest::default();
+        db.insert_account(sender, Account::default(), None, HashMap::new());
+
+        let inspector = Arc::new(Mutex::new(RecordingInspector::default()));
+        let mut executor = EVMProcessor::new_with_db(
+            Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build()),
+            StateProviderDatabase::new(db),
+        );
+        executor.set_stack(InspectorStack::new(InspectorStackConfig {
+            hook: Hook::Addresses(HashSet::from([watched])),
+            execution_inspectors: vec![inspector.clone()],
+            ..Default::default()
+        }));
+        executor.execute(&block, U256::ZERO).unwrap();
+
+        assert_eq!(inspector.lock().unwrap().transactions, vec![(transactions[0].hash(), true)]);
+    }
+
+    #[test]
+    fn prewarm_access_lists() {
+        let account = Address::with_last_byte(1);
+        let missing = Address::with_last_byte(2);
+        let slot = B256::with_last_byte(3);
+
+        let mut db = StateProviderTest::default();
+        db.insert_account(
+            account,
+            Account { nonce: 1, ..Default::default() },
+            None,
+            HashMap::from([(slot, U256::from(4))]),
+        );
+
+        let transaction = TransactionSigned::from_transaction_and_signature(
+            Transaction::Eip2930(TxEip2930 {
+                access_list: AccessList(vec![
+                    AccessListItem { address: account, storage_keys: vec![slot] },
+                    AccessListItem { address: missing, storage_keys: vec![] },
+                ]),
+                ..Default::default()
+            }),
+            Signature::default(),
+        );
+        let block = BlockWithSenders {
+            block: Block {
+                header: Header::default(),
+                body: vec![transaction],
+                ommers: vec![],
+                withdrawals: None,
+            },
+            senders: vec![Address::ZERO],
+        };
+
+        let mut executor = EVMProcessor::new_with_db(
+            Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build()),
+            StateProviderDatabase::new(db),
+        );
+        executor.prewarm_access_lists(&block);
+
+        // both accounts and the slot are cached before any transaction is executed
+        let cache = &executor.db_mut().cache.accounts;
+        let cached = cache[&account].account.as_ref().unwrap();
+        assert_eq!(cached.info.nonce, 1);
+        assert_eq!(cached.storage.get(&U256::from(3)), Some(&U256::from(4)));
+        assert!(cache[&missing].account.is_none());
+    }
 }
@@ -0,0 +1,103 @@
+use reth_primitives::{Address, B256, U256};
+use revm::{
+    primitives::{AccountInfo, Bytecode},
+    DatabaseRef,
+};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+};
+
+/// The accounts, storage slots and bytecodes read from a [WitnessDatabase].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StateWitness {
+    /// The accessed accounts with their accessed storage slots.
+    pub accounts: BTreeMap<Address, BTreeSet<B256>>,
+    /// The hashes of the accessed bytecodes.
+    pub code_hashes: BTreeSet<B256>,
+}
+
+/// A [DatabaseRef] that records the accounts, storage slots and bytecodes read from the wrapped
+/// database.
+///
+/// Wrapped in a caching database like [CacheDB](revm::db::CacheDB), every key is read once before
+/// the execution changes it, so the recorded keys are the pre-state a block needs to be executed
+/// without the rest of the state.
+#[derive(Debug)]
+pub struct WitnessDatabase<DB> {
+    /// The wrapped database.
+    db: DB,
+    /// The keys read so far.
+    witness: RefCell<StateWitness>,
+}
+
+impl<DB> WitnessDatabase<DB> {
+    /// Wraps the database.
+    pub fn new(db: DB) -> Self {
+        Self { db, witness: RefCell::default() }
+    }
+
+    /// Returns the wrapped database and the keys that were read from it.
+    pub fn into_inner(self) -> (DB, StateWitness) {
+        (self.db, self.witness.into_inner())
+    }
+}
+
+impl<DB: DatabaseRef> DatabaseRef for WitnessDatabase<DB> {
+    type Error = DB::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.witness.borrow_mut().accounts.entry(address).or_default();
+        self.db.basic_ref(address)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.witness.borrow_mut().code_hashes.insert(code_hash);
+        self.db.code_by_hash_ref(code_hash)
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.witness
+            .borrow_mut()
+            .accounts
+            .entry(address)
+            .or_default()
+            .insert(B256::new(index.to_be_bytes()));
+        self.db.storage_ref(address, index)
+    }
+
+    fn block_hash_ref(&self, number: U256) -> Result<B256, Self::Error> {
+        self.db.block_hash_ref(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        Database,
+    };
+
+    #[test]
+    fn records_reads() {
+        let mut db = CacheDB::new(WitnessDatabase::new(EmptyDB::default()));
+        let first = Address::with_last_byte(1);
+        let second = Address::with_last_byte(2);
+
+        db.basic(first).unwrap();
+        db.storage(second, U256::from(1)).unwrap();
+        db.storage(second, U256::from(1)).unwrap();
+        db.code_by_hash(B256::with_last_byte(3)).unwrap();
+
+        let (_, witness) = db.db.into_inner();
+        assert_eq!(
+            witness.accounts,
+            BTreeMap::from([
+                (first, BTreeSet::new()),
+                (second, BTreeSet::from([B256::with_last_byte(1)])),
+            ])
+        );
+        assert_eq!(witness.code_hashes, BTreeSet::from([B256::with_last_byte(3)]));
+    }
+}
@@ -1,6 +1,6 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::NodeRecord;
-use reth_rpc_types::{NodeInfo, PeerInfo};
+use reth_rpc_types::{DbReaderInfo, NodeInfo, PeerInfo};
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -46,4 +46,10 @@ pub trait AdminApi {
     /// Returns the ENR of the node.
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
+
+    /// Returns the open read transactions of the database, oldest first.
+    ///
+    /// Long-lived read transactions prevent the database from reusing freed pages.
+    #[method(name = "dbReaders")]
+    fn db_readers(&self) -> RpcResult<Vec<DbReaderInfo>>;
 }
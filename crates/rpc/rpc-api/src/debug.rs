@@ -5,7 +5,7 @@ use reth_rpc_types::{
         BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
         TraceResult,
     },
-    Bundle, CallRequest, RichBlock, StateContext,
+    Bundle, CallRequest, ExecutionWitness, RichBlock, StateContext,
 };
 
 /// Debug rpc interface.
@@ -130,6 +130,14 @@ pub trait DebugApi {
         opts: Option<GethDebugTracingCallOptions>,
     ) -> RpcResult<Vec<Vec<GethTrace>>>;
 
+    /// The `debug_executionWitness` method re-executes the block on top of its parent's state and
+    /// returns the state the block accessed: the proofs of the accessed accounts and storage slots
+    /// against the parent's state root, and the accessed bytecodes.
+    ///
+    /// This is the witness needed to execute the block without the rest of the state.
+    #[method(name = "executionWitness")]
+    async fn debug_execution_witness(&self, block_id: BlockId) -> RpcResult<ExecutionWitness>;
+
     /// Sets the logging backtrace location. When a backtrace location is set and a log message is
     /// emitted at that location,  the stack of the goroutine executing the log statement will
     /// be printed to stderr.
@@ -0,0 +1,20 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_primitives::{Address, BlockNumberOrTag};
+use reth_rpc_types::BlockWithdrawal;
+
+/// Rpc Interface for querying the withdrawals to an address.
+///
+/// This is a non-standard extension of the `eth` namespace.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait EthWithdrawalsApi {
+    /// Returns the withdrawals to the address that were included in the blocks in the range
+    /// 'from' - 'to', in the order they were made.
+    #[method(name = "getWithdrawals")]
+    async fn withdrawals(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<Vec<BlockWithdrawal>>;
+}
@@ -22,6 +22,7 @@ mod engine;
 mod eth;
 mod eth_filter;
 mod eth_pubsub;
+mod eth_withdrawals;
 mod mev;
 mod net;
 mod otterscan;
@@ -45,6 +46,7 @@ pub mod servers {
         eth::EthApiServer,
         eth_filter::EthFilterApiServer,
         eth_pubsub::EthPubSubApiServer,
+        eth_withdrawals::EthWithdrawalsApiServer,
         mev::MevApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
@@ -71,6 +73,7 @@ pub mod clients {
         engine::{EngineApiClient, EngineEthApiClient},
         eth::EthApiClient,
         eth_filter::EthFilterApiClient,
+        eth_withdrawals::EthWithdrawalsApiClient,
         mev::MevApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
@@ -1,5 +1,5 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockId, U256};
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, InternalTransfer, U256};
 use std::collections::HashMap;
 
 /// Reth API namespace for reth-specific methods
@@ -12,4 +12,16 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Returns the internal transfers of the blocks in the range, or only the ones sent from or to
+    /// the address if one is given.
+    ///
+    /// The node only has the transfers it indexed, see `--index.internal-transfers`.
+    #[method(name = "getInternalTransfers")]
+    async fn reth_get_internal_transfers(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        address: Option<Address>,
+    ) -> RpcResult<Vec<InternalTransfer>>;
 }
@@ -13,7 +13,7 @@ use jsonrpsee::{
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::{
     BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider, ReceiptProviderIdExt,
-    StateProviderFactory,
+    StageCheckpointReader, StateProviderFactory,
 };
 use reth_rpc::{
     eth::{
@@ -49,6 +49,7 @@ where
         + HeaderProvider
         + ReceiptProviderIdExt
         + StateProviderFactory
+        + StageCheckpointReader
         + Clone
         + Unpin
         + 'static,
@@ -98,6 +99,7 @@ where
         + EvmEnvProvider
         + HeaderProvider
         + StateProviderFactory
+        + StageCheckpointReader
         + Clone
         + Unpin
         + 'static,
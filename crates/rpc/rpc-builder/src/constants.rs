@@ -10,6 +10,12 @@ pub const DEFAULT_HTTP_RPC_PORT: u16 = 8545;
 /// The default port for the ws server
 pub const DEFAULT_WS_RPC_PORT: u16 = 8546;
 
+/// The default port for the GraphQL server.
+pub const DEFAULT_GRAPHQL_PORT: u16 = 8547;
+
+/// The default port for the gRPC server.
+pub const DEFAULT_GRPC_PORT: u16 = 8548;
+
 /// The default port for the auth server.
 pub const DEFAULT_AUTH_PORT: u16 = 8551;
 
@@ -19,6 +25,9 @@ pub const DEFAULT_MAX_BLOCKS_PER_FILTER: u64 = 100_000;
 /// The default maximum of logs in a single response.
 pub const DEFAULT_MAX_LOGS_PER_RESPONSE: usize = 20_000;
 
+/// The default maximum duration of a log query, in seconds.
+pub const DEFAULT_LOGS_QUERY_TIMEOUT_SECS: u64 = 30;
+
 /// The default maximum number of concurrently executed tracing calls
 pub const DEFAULT_MAX_TRACING_REQUESTS: u32 = 25;
 
@@ -1,11 +1,13 @@
 use crate::constants::{
-    DEFAULT_MAX_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_TRACING_REQUESTS,
+    DEFAULT_LOGS_QUERY_TIMEOUT_SECS, DEFAULT_MAX_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE,
+    DEFAULT_MAX_TRACING_REQUESTS,
 };
 use reth_rpc::{
     eth::{
         cache::{EthStateCache, EthStateCacheConfig},
         gas_oracle::GasPriceOracleConfig,
-        EthFilterConfig, FeeHistoryCacheConfig, RPC_DEFAULT_GAS_CAP,
+        EthFilterConfig, FeeHistoryCacheConfig, DEFAULT_MAX_CONCURRENT_LOGS_CHUNKS,
+        RPC_DEFAULT_GAS_CAP,
     },
     BlockingTaskPool, EthApi, EthFilter, EthPubSub,
 };
@@ -39,6 +41,13 @@ pub struct EthConfig {
     pub max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
     pub max_logs_per_response: usize,
+    /// Maximum number of chunks of the block range of an `eth_getLogs` call that are processed
+    /// concurrently.
+    pub max_concurrent_logs_chunks: usize,
+    /// Maximum duration of an `eth_getLogs` call over a range of blocks.
+    ///
+    /// If `None` then no deadline is enforced.
+    pub logs_query_timeout: Option<std::time::Duration>,
     /// Gas limit for `eth_call` and call tracing RPC methods.
     ///
     /// Defaults to [RPC_DEFAULT_GAS_CAP]
@@ -53,9 +62,10 @@ pub struct EthConfig {
 impl EthConfig {
     /// Returns the filter config for the `eth_filter` handler.
     pub fn filter_config(&self) -> EthFilterConfig {
-        EthFilterConfig::default()
+        EthFilterConfig { logs_query_timeout: self.logs_query_timeout, ..Default::default() }
             .max_blocks_per_filter(self.max_blocks_per_filter)
             .max_logs_per_response(self.max_logs_per_response)
+            .max_concurrent_logs_chunks(self.max_concurrent_logs_chunks)
             .stale_filter_ttl(self.stale_filter_ttl)
     }
 }
@@ -71,6 +81,10 @@ impl Default for EthConfig {
             max_tracing_requests: DEFAULT_MAX_TRACING_REQUESTS,
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
+            max_concurrent_logs_chunks: DEFAULT_MAX_CONCURRENT_LOGS_CHUNKS,
+            logs_query_timeout: Some(std::time::Duration::from_secs(
+                DEFAULT_LOGS_QUERY_TIMEOUT_SECS,
+            )),
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
@@ -109,6 +123,19 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum number of chunks of an `eth_getLogs` request that are processed
+    /// concurrently
+    pub fn max_concurrent_logs_chunks(mut self, max_chunks: usize) -> Self {
+        self.max_concurrent_logs_chunks = max_chunks;
+        self
+    }
+
+    /// Configures the maximum duration of an `eth_getLogs` request, `None` for no deadline
+    pub fn logs_query_timeout(mut self, timeout: Option<std::time::Duration>) -> Self {
+        self.logs_query_timeout = timeout;
+        self
+    }
+
     /// Configures the maximum gas limit for `eth_call` and call tracing RPC methods
     pub fn rpc_gas_cap(mut self, rpc_gas_cap: u64) -> Self {
         self.rpc_gas_cap = rpc_gas_cap;
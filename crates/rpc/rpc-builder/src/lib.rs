@@ -19,8 +19,10 @@
 //! ```
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_provider::{
-//!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     AccountReader, AddressAppearancesReader, BlockReaderIdExt, CanonStateSubscriptions,
+//!     ChainSpecProvider, ChangeSetReader, ContractCreationsReader, EvmEnvProvider,
+//!     InternalTransfersReader, StageCheckpointReader, StateProviderFactory,
+//!     WithdrawalHistoryReader,
 //! };
 //! use reth_rpc_builder::{
 //!     RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig,
@@ -37,6 +39,11 @@
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + InternalTransfersReader
+//!         + ContractCreationsReader
+//!         + AddressAppearancesReader
+//!         + WithdrawalHistoryReader
+//!         + StageCheckpointReader
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -70,8 +77,10 @@
 //! ```
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_provider::{
-//!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     AccountReader, AddressAppearancesReader, BlockReaderIdExt, CanonStateSubscriptions,
+//!     ChainSpecProvider, ChangeSetReader, ContractCreationsReader, EvmEnvProvider,
+//!     InternalTransfersReader, StageCheckpointReader, StateProviderFactory,
+//!     WithdrawalHistoryReader,
 //! };
 //! use reth_rpc::JwtSecret;
 //! use reth_rpc_api::EngineApiServer;
@@ -93,6 +102,11 @@
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + InternalTransfersReader
+//!         + ContractCreationsReader
+//!         + AddressAppearancesReader
+//!         + WithdrawalHistoryReader
+//!         + StageCheckpointReader
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -141,6 +155,7 @@ use std::{
     fmt,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     str::FromStr,
+    sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
@@ -158,19 +173,22 @@ use tracing::{instrument, trace};
 
 use constants::*;
 use error::{RpcError, ServerKind};
+use reth_interfaces::db::DatabaseReaders;
 use reth_ipc::server::IpcServer;
 pub use reth_ipc::server::{Builder as IpcServerBuilder, Endpoint};
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_provider::{
-    AccountReader, BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-    ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+    AccountReader, AddressAppearancesReader, BlockReader, BlockReaderIdExt,
+    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, ContractCreationsReader,
+    EvmEnvProvider, InternalTransfersReader, StageCheckpointReader, StateProviderFactory,
+    WithdrawalHistoryReader,
 };
 use reth_rpc::{
     eth::{
         cache::{cache_new_blocks_task, EthStateCache},
         fee_history_cache_new_blocks_task,
         gas_oracle::GasPriceOracle,
-        EthBundle, FeeHistoryCache,
+        EthBundle, EthWithdrawals, FeeHistoryCache,
     },
     AdminApi, AuthLayer, BlockingTaskGuard, BlockingTaskPool, Claims, DebugApi, EngineEthApi,
     EthApi, EthFilter, EthPubSub, EthSubscriptionIdProvider, JwtAuthValidator, JwtSecret, NetApi,
@@ -222,6 +240,11 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + InternalTransfersReader
+        + ContractCreationsReader
+        + AddressAppearancesReader
+        + WithdrawalHistoryReader
+        + StageCheckpointReader
         + Clone
         + Unpin
         + 'static,
@@ -253,6 +276,10 @@ pub struct RpcModuleBuilder<Provider, Pool, Network, Tasks, Events> {
     executor: Tasks,
     /// Provides access to chain events, such as new blocks, required by pubsub.
     events: Events,
+    /// The open read transactions of the database, served by the admin namespace.
+    db_readers: Option<Arc<dyn DatabaseReaders>>,
+    /// The mode of the node, served by the admin namespace.
+    node_mode: Option<String>,
 }
 
 // === impl RpcBuilder ===
@@ -268,7 +295,7 @@ impl<Provider, Pool, Network, Tasks, Events>
         executor: Tasks,
         events: Events,
     ) -> Self {
-        Self { provider, pool, network, executor, events }
+        Self { provider, pool, network, executor, events, db_readers: None, node_mode: None }
     }
 
     /// Configure the provider instance.
@@ -276,8 +303,8 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         P: BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     {
-        let Self { pool, network, executor, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { pool, network, executor, events, db_readers, node_mode, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, db_readers, node_mode }
     }
 
     /// Configure the transaction pool instance.
@@ -285,8 +312,8 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         P: TransactionPool + 'static,
     {
-        let Self { provider, network, executor, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { provider, network, executor, events, db_readers, node_mode, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, db_readers, node_mode }
     }
 
     /// Configure a [NoopTransactionPool] instance.
@@ -297,13 +324,15 @@ impl<Provider, Pool, Network, Tasks, Events>
     pub fn with_noop_pool(
         self,
     ) -> RpcModuleBuilder<Provider, NoopTransactionPool, Network, Tasks, Events> {
-        let Self { provider, executor, events, network, .. } = self;
+        let Self { provider, executor, events, network, db_readers, node_mode, .. } = self;
         RpcModuleBuilder {
             provider,
             executor,
             events,
             network,
             pool: NoopTransactionPool::default(),
+            db_readers,
+            node_mode,
         }
     }
 
@@ -312,8 +341,8 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         N: NetworkInfo + Peers + 'static,
     {
-        let Self { provider, pool, executor, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { provider, pool, executor, events, db_readers, node_mode, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, db_readers, node_mode }
     }
 
     /// Configure a [NoopNetwork] instance.
@@ -322,8 +351,16 @@ impl<Provider, Pool, Network, Tasks, Events>
     /// This is only intended for allow easier setup of namespaces that depend on the [EthApi] which
     /// requires a [NetworkInfo] implementation.
     pub fn with_noop_network(self) -> RpcModuleBuilder<Provider, Pool, NoopNetwork, Tasks, Events> {
-        let Self { provider, pool, executor, events, .. } = self;
-        RpcModuleBuilder { provider, pool, executor, events, network: NoopNetwork::default() }
+        let Self { provider, pool, executor, events, db_readers, node_mode, .. } = self;
+        RpcModuleBuilder {
+            provider,
+            pool,
+            executor,
+            events,
+            network: NoopNetwork::default(),
+            db_readers,
+            node_mode,
+        }
     }
 
     /// Configure the task executor to use for additional tasks.
@@ -334,8 +371,8 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         T: TaskSpawner + 'static,
     {
-        let Self { pool, network, provider, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { pool, network, provider, events, db_readers, node_mode, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, db_readers, node_mode }
     }
 
     /// Configure [TokioTaskExecutor] as the task executor to use for additional tasks.
@@ -345,8 +382,16 @@ impl<Provider, Pool, Network, Tasks, Events>
     pub fn with_tokio_executor(
         self,
     ) -> RpcModuleBuilder<Provider, Pool, Network, TokioTaskExecutor, Events> {
-        let Self { pool, network, provider, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, events, executor: TokioTaskExecutor::default() }
+        let Self { pool, network, provider, events, db_readers, node_mode, .. } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            events,
+            executor: TokioTaskExecutor::default(),
+            db_readers,
+            node_mode,
+        }
     }
 
     /// Configure the event subscriber instance
@@ -354,8 +399,22 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         E: CanonStateSubscriptions + 'static,
     {
-        let Self { provider, pool, executor, network, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { provider, pool, executor, network, db_readers, node_mode, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, db_readers, node_mode }
+    }
+
+    /// Configures the source of the open database read transactions, which are served by
+    /// `admin_dbReaders`.
+    pub fn with_db_readers(mut self, db_readers: Arc<dyn DatabaseReaders>) -> Self {
+        self.db_readers = Some(db_readers);
+        self
+    }
+
+    /// Configures the mode of the node, e.g. `archive` or `full`, which is served by
+    /// `admin_nodeInfo`.
+    pub fn with_node_mode(mut self, node_mode: impl Into<String>) -> Self {
+        self.node_mode = Some(node_mode.into());
+        self
     }
 }
 
@@ -368,6 +427,11 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + InternalTransfersReader
+        + ContractCreationsReader
+        + AddressAppearancesReader
+        + WithdrawalHistoryReader
+        + StageCheckpointReader
         + Clone
         + Unpin
         + 'static,
@@ -395,7 +459,7 @@ where
     {
         let mut modules = TransportRpcModules::default();
 
-        let Self { provider, pool, network, executor, events } = self;
+        let Self { provider, pool, network, executor, events, db_readers, node_mode } = self;
 
         let TransportRpcModuleConfig { http, ws, ipc, config } = module_config.clone();
 
@@ -407,6 +471,8 @@ where
             events,
             config.unwrap_or_default(),
         );
+        registry.db_readers = db_readers;
+        registry.node_mode = node_mode;
 
         modules.config = module_config;
         modules.http = registry.maybe_module(http.as_ref());
@@ -445,8 +511,12 @@ where
         self,
         config: RpcModuleConfig,
     ) -> RethModuleRegistry<Provider, Pool, Network, Tasks, Events> {
-        let Self { provider, pool, network, executor, events } = self;
-        RethModuleRegistry::new(provider, pool, network, executor, events, config)
+        let Self { provider, pool, network, executor, events, db_readers, node_mode } = self;
+        let mut registry =
+            RethModuleRegistry::new(provider, pool, network, executor, events, config);
+        registry.db_readers = db_readers;
+        registry.node_mode = node_mode;
+        registry
     }
 
     /// Configures all [RpcModule]s specific to the given [TransportRpcModuleConfig] which can be
@@ -456,7 +526,7 @@ where
     pub fn build(self, module_config: TransportRpcModuleConfig) -> TransportRpcModules<()> {
         let mut modules = TransportRpcModules::default();
 
-        let Self { provider, pool, network, executor, events } = self;
+        let Self { provider, pool, network, executor, events, db_readers, node_mode } = self;
 
         if !module_config.is_empty() {
             let TransportRpcModuleConfig { http, ws, ipc, config } = module_config.clone();
@@ -469,6 +539,8 @@ where
                 events,
                 config.unwrap_or_default(),
             );
+            registry.db_readers = db_readers;
+            registry.node_mode = node_mode;
 
             modules.config = module_config;
             modules.http = registry.maybe_module(http.as_ref());
@@ -654,6 +726,11 @@ impl RpcModuleSelection {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + InternalTransfersReader
+            + ContractCreationsReader
+            + AddressAppearancesReader
+            + WithdrawalHistoryReader
+            + StageCheckpointReader
             + Clone
             + Unpin
             + 'static,
@@ -801,6 +878,18 @@ impl RethRpcModule {
     pub fn as_str(&self) -> &'static str {
         self.into()
     }
+
+    /// Returns true if the namespace is served by the [EthHandlers].
+    fn requires_eth_handlers(&self) -> bool {
+        !matches!(
+            self,
+            RethRpcModule::Admin |
+                RethRpcModule::Web3 |
+                RethRpcModule::Txpool |
+                RethRpcModule::Rpc |
+                RethRpcModule::Reth
+        )
+    }
 }
 
 impl FromStr for RethRpcModule {
@@ -862,6 +951,10 @@ pub struct RethModuleRegistry<Provider, Pool, Network, Tasks, Events> {
     blocking_pool_guard: BlockingTaskGuard,
     /// Contains the [Methods] of a module
     modules: HashMap<RethRpcModule, Methods>,
+    /// The open read transactions of the database, served by the admin namespace.
+    db_readers: Option<Arc<dyn DatabaseReaders>>,
+    /// The mode of the node, served by the admin namespace.
+    node_mode: Option<String>,
 }
 
 // === impl RethModuleRegistry ===
@@ -888,6 +981,8 @@ impl<Provider, Pool, Network, Tasks, Events>
             blocking_pool_guard: BlockingTaskGuard::new(config.eth.max_tracing_requests),
             config,
             events,
+            db_readers: None,
+            node_mode: None,
         }
     }
 
@@ -933,7 +1028,14 @@ where
 {
     /// Instantiates AdminApi
     pub fn admin_api(&mut self) -> AdminApi<Network> {
-        AdminApi::new(self.network.clone())
+        let mut admin = AdminApi::new(self.network.clone());
+        if let Some(db_readers) = self.db_readers.clone() {
+            admin = admin.with_db_readers(db_readers);
+        }
+        if let Some(node_mode) = self.node_mode.clone() {
+            admin = admin.with_node_mode(node_mode);
+        }
+        admin
     }
 
     /// Instantiates Web3Api
@@ -965,6 +1067,11 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + InternalTransfersReader
+        + ContractCreationsReader
+        + AddressAppearancesReader
+        + WithdrawalHistoryReader
+        + StageCheckpointReader
         + Clone
         + Unpin
         + 'static,
@@ -980,7 +1087,11 @@ where
     /// If called outside of the tokio runtime. See also [Self::eth_api]
     pub fn register_eth(&mut self) -> &mut Self {
         let eth_api = self.eth_api();
-        self.modules.insert(RethRpcModule::Eth, eth_api.into_rpc().into());
+        let withdrawals =
+            EthWithdrawals::new(self.provider.clone(), Box::new(self.executor.clone()));
+        let mut module = eth_api.into_rpc();
+        module.merge(withdrawals.into_rpc()).expect("No conflicts");
+        self.modules.insert(RethRpcModule::Eth, module.into());
         self
     }
 
@@ -1017,6 +1128,13 @@ where
         self
     }
 
+    /// Register Txpool Namespace
+    pub fn register_txpool(&mut self) -> &mut Self {
+        let txpool_api = self.txpool_api();
+        self.modules.insert(RethRpcModule::Txpool, txpool_api.into_rpc().into());
+        self
+    }
+
     /// Configures the auth module that includes the
     ///   * `engine_` namespace
     ///   * `api_` namespace
@@ -1094,16 +1212,21 @@ where
         &mut self,
         namespaces: impl Iterator<Item = RethRpcModule>,
     ) -> Vec<Methods> {
-        let EthHandlers {
-            api: eth_api,
-            filter: eth_filter,
-            pubsub: eth_pubsub,
-            cache: _,
-            blocking_task_pool: _,
-        } = self.with_eth(|eth| eth.clone());
-
         // Create a copy, so we can list out all the methods for rpc_ api
         let namespaces: Vec<_> = namespaces.collect();
+
+        // The eth handlers are only spawned if a namespace is served by them, so that for example
+        // a web3 only server doesn't spawn the cache.
+        let eth_handlers = namespaces
+            .iter()
+            .any(RethRpcModule::requires_eth_handlers)
+            .then(|| self.with_eth(|eth| eth.clone()));
+        let eth = || {
+            eth_handlers
+                .as_ref()
+                .expect("eth handlers are spawned for namespaces that require them")
+        };
+
         namespaces
             .iter()
             .copied()
@@ -1112,11 +1235,18 @@ where
                     .entry(namespace)
                     .or_insert_with(|| match namespace {
                         RethRpcModule::Admin => {
-                            AdminApi::new(self.network.clone()).into_rpc().into()
+                            let mut admin = AdminApi::new(self.network.clone());
+                            if let Some(db_readers) = self.db_readers.clone() {
+                                admin = admin.with_db_readers(db_readers);
+                            }
+                            if let Some(node_mode) = self.node_mode.clone() {
+                                admin = admin.with_node_mode(node_mode);
+                            }
+                            admin.into_rpc().into()
                         }
                         RethRpcModule::Debug => DebugApi::new(
                             self.provider.clone(),
-                            eth_api.clone(),
+                            eth().api.clone(),
                             Box::new(self.executor.clone()),
                             self.blocking_pool_guard.clone(),
                         )
@@ -1124,18 +1254,24 @@ where
                         .into(),
                         RethRpcModule::Eth => {
                             // merge all eth handlers
-                            let mut module = eth_api.clone().into_rpc();
-                            module.merge(eth_filter.clone().into_rpc()).expect("No conflicts");
-                            module.merge(eth_pubsub.clone().into_rpc()).expect("No conflicts");
+                            let mut module = eth().api.clone().into_rpc();
+                            module.merge(eth().filter.clone().into_rpc()).expect("No conflicts");
+                            module.merge(eth().pubsub.clone().into_rpc()).expect("No conflicts");
+                            let withdrawals = EthWithdrawals::new(
+                                self.provider.clone(),
+                                Box::new(self.executor.clone()),
+                            );
+                            module.merge(withdrawals.into_rpc()).expect("No conflicts");
 
                             module.into()
                         }
                         RethRpcModule::Net => {
-                            NetApi::new(self.network.clone(), eth_api.clone()).into_rpc().into()
+                            NetApi::new(self.network.clone(), eth().api.clone()).into_rpc().into()
                         }
                         RethRpcModule::Trace => TraceApi::new(
                             self.provider.clone(),
-                            eth_api.clone(),
+                            eth().api.clone(),
+                            eth().cache.clone(),
                             self.blocking_pool_guard.clone(),
                         )
                         .into_rpc()
@@ -1152,14 +1288,18 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
+                        RethRpcModule::Ots => {
+                            OtterscanApi::new(self.provider.clone(), eth().api.clone())
+                                .into_rpc()
+                                .into()
+                        }
                         RethRpcModule::Reth => {
                             RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
                                 .into_rpc()
                                 .into()
                         }
                         RethRpcModule::EthCallBundle => {
-                            EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
+                            EthBundle::new(eth().api.clone(), self.blocking_pool_guard.clone())
                                 .into_rpc()
                                 .into()
                         }
@@ -1283,7 +1423,7 @@ where
     /// If called outside of the tokio runtime. See also [Self::eth_api]
     pub fn trace_api(&mut self) -> TraceApi<Provider, EthApi<Provider, Pool, Network>> {
         let eth = self.eth_handlers();
-        TraceApi::new(self.provider.clone(), eth.api, self.blocking_pool_guard.clone())
+        TraceApi::new(self.provider.clone(), eth.api, eth.cache, self.blocking_pool_guard.clone())
     }
 
     /// Instantiates [EthBundle] Api
@@ -1301,9 +1441,9 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime. See also [Self::eth_api]
-    pub fn otterscan_api(&mut self) -> OtterscanApi<EthApi<Provider, Pool, Network>> {
+    pub fn otterscan_api(&mut self) -> OtterscanApi<Provider, EthApi<Provider, Pool, Network>> {
         let eth_api = self.eth_api();
-        OtterscanApi::new(eth_api)
+        OtterscanApi::new(self.provider.clone(), eth_api)
     }
 
     /// Instantiates DebugApi
@@ -1335,6 +1475,11 @@ where
     pub fn reth_api(&mut self) -> RethApi<Provider> {
         RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
     }
+
+    /// Instantiates TxPoolApi
+    pub fn txpool_api(&mut self) -> TxPoolApi<Pool> {
+        TxPoolApi::new(self.pool.clone())
+    }
 }
 
 /// A builder type for configuring and launching the servers that will handle RPC requests.
@@ -2210,6 +2355,7 @@ impl fmt::Debug for RpcServerHandle {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reth_provider::test_utils::{NoopProvider, TestCanonStateSubscriptions};
 
     #[test]
     fn parse_eth_call_bundle() {
@@ -2233,6 +2379,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn eth_handlers_only_spawned_if_required() {
+        let mut registry = RpcModuleBuilder::default()
+            .with_provider(NoopProvider::default())
+            .with_noop_pool()
+            .with_noop_network()
+            .with_executor(TokioTaskExecutor::default())
+            .with_events(TestCanonStateSubscriptions::default())
+            .into_registry(Default::default());
+
+        let selection =
+            RpcModuleSelection::Selection(vec![RethRpcModule::Web3, RethRpcModule::Txpool]);
+        let module = registry.module_for(&selection);
+        assert!(module.method_names().any(|name| name == "txpool_content"));
+        assert!(registry.eth.is_none());
+    }
+
     #[test]
     fn parse_rpc_module_selection() {
         let selection = "all".parse::<RpcModuleSelection>().unwrap();
@@ -157,6 +157,7 @@ impl From<EngineApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             EngineApiError::ForkChoiceUpdate(ref err) => match err {
                 BeaconForkChoiceUpdateError::ForkchoiceUpdateError(err) => (*err).into(),
                 BeaconForkChoiceUpdateError::EngineUnavailable |
+                BeaconForkChoiceUpdateError::EngineBusy |
                 BeaconForkChoiceUpdateError::Internal(_) => {
                     jsonrpsee_types::error::ErrorObject::owned(
                         INTERNAL_ERROR_CODE,
@@ -178,13 +179,12 @@ impl From<EngineApiError> for jsonrpsee_types::error::ErrorObject<'static> {
                         Some(ErrorData::new(error)),
                     )
                 }
-                BeaconOnNewPayloadError::EngineUnavailable => {
-                    jsonrpsee_types::error::ErrorObject::owned(
-                        INTERNAL_ERROR_CODE,
-                        SERVER_ERROR_MSG,
-                        Some(ErrorData::new(error)),
-                    )
-                }
+                BeaconOnNewPayloadError::EngineUnavailable |
+                BeaconOnNewPayloadError::EngineBusy => jsonrpsee_types::error::ErrorObject::owned(
+                    INTERNAL_ERROR_CODE,
+                    SERVER_ERROR_MSG,
+                    Some(ErrorData::new(error)),
+                ),
             },
             // Optimism errors
             #[cfg(feature = "optimism")]
@@ -0,0 +1,75 @@
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{
+    BlockHashOrNumber, BlockNumber, Receipt, SealedBlock, TransactionMeta, TransactionSigned,
+    TxHash,
+};
+use reth_provider::{
+    BlockNumReader, BlockReader, BlockReaderIdExt, ChainSpecProvider, StateProviderBox,
+    StateProviderFactory,
+};
+
+/// The data access required by the GraphQL schema.
+///
+/// Unlike the provider traits, this trait is object safe, so the schema types don't need to be
+/// generic over the provider. It's implemented for all providers that can back the JSON-RPC
+/// server.
+pub trait GraphQlBackend: Send + Sync + 'static {
+    /// Returns the chain id.
+    fn chain_id(&self) -> u64;
+
+    /// Returns the number of the best canonical block.
+    fn best_block_number(&self) -> ProviderResult<BlockNumber>;
+
+    /// Returns the block with the given hash or number.
+    fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<SealedBlock>>;
+
+    /// Returns the receipts of the block with the given hash or number.
+    fn receipts(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>>;
+
+    /// Returns the transaction with the given hash and its location in the chain.
+    fn transaction(
+        &self,
+        hash: TxHash,
+    ) -> ProviderResult<Option<(TransactionSigned, TransactionMeta)>>;
+
+    /// Returns the state after the block with the given hash or number.
+    fn state(&self, id: BlockHashOrNumber) -> ProviderResult<StateProviderBox>;
+}
+
+impl<Provider> GraphQlBackend for Provider
+where
+    Provider: BlockReaderIdExt + StateProviderFactory + ChainSpecProvider + 'static,
+{
+    fn chain_id(&self) -> u64 {
+        self.chain_spec().chain.id()
+    }
+
+    fn best_block_number(&self) -> ProviderResult<BlockNumber> {
+        BlockNumReader::best_block_number(self)
+    }
+
+    fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<SealedBlock>> {
+        Ok(BlockReader::block(self, id)?.map(|block| match id {
+            BlockHashOrNumber::Hash(hash) => block.seal(hash),
+            BlockHashOrNumber::Number(_) => block.seal_slow(),
+        }))
+    }
+
+    fn receipts(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
+        self.receipts_by_block(id)
+    }
+
+    fn transaction(
+        &self,
+        hash: TxHash,
+    ) -> ProviderResult<Option<(TransactionSigned, TransactionMeta)>> {
+        self.transaction_by_hash_with_meta(hash)
+    }
+
+    fn state(&self, id: BlockHashOrNumber) -> ProviderResult<StateProviderBox> {
+        match id {
+            BlockHashOrNumber::Hash(hash) => self.history_by_block_hash(hash),
+            BlockHashOrNumber::Number(number) => self.history_by_block_number(number),
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! GraphQL server for reth, implementing the read-only part of the standard Ethereum schema
+//! ([EIP-1767](https://eips.ethereum.org/EIPS/eip-1767)).
+//!
+//! The schema exposes blocks, transactions, receipts, logs and accounts and is backed by the same
+//! providers as the JSON-RPC server. Pending state and the `sendRawTransaction` mutation are not
+//! supported.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![warn(missing_debug_implementations, missing_docs, unreachable_pub, rustdoc::all)]
+#![deny(unused_must_use, rust_2018_idioms)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod backend;
+mod scalars;
+mod schema;
+mod server;
+
+pub use backend::GraphQlBackend;
+pub use server::{start_server, GRAPHQL_PATH};
@@ -0,0 +1,152 @@
+//! The scalar types of the EIP-1767 schema.
+
+use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+use reth_primitives::{B256, U256};
+
+/// Parses a scalar that is encoded as a string.
+fn parse_str<T: std::str::FromStr>(value: Value) -> InputValueResult<T>
+where
+    T::Err: std::fmt::Display,
+{
+    match value {
+        Value::String(s) => s.parse().map_err(InputValueError::custom),
+        value => Err(InputValueError::expected_type(value)),
+    }
+}
+
+/// A 32 byte binary string, represented as 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Bytes32(pub(crate) B256);
+
+/// A 32 byte binary string, represented as 0x-prefixed hexadecimal.
+#[Scalar(name = "Bytes32")]
+impl ScalarType for Bytes32 {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_str(value).map(Self)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// A 20 byte Ethereum address, represented as 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Address(pub(crate) reth_primitives::Address);
+
+/// A 20 byte Ethereum address, represented as 0x-prefixed hexadecimal.
+#[Scalar(name = "Address")]
+impl ScalarType for Address {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_str(value).map(Self)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// An arbitrary length binary string, represented as 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Bytes(pub(crate) reth_primitives::Bytes);
+
+/// An arbitrary length binary string, represented as 0x-prefixed hexadecimal.
+#[Scalar(name = "Bytes")]
+impl ScalarType for Bytes {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_str(value).map(Self)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// A 256 bit unsigned integer, represented as 0x-prefixed hexadecimal. Decimal strings are
+/// accepted as input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BigInt(pub(crate) U256);
+
+/// A 256 bit unsigned integer, represented as 0x-prefixed hexadecimal.
+#[Scalar(name = "BigInt")]
+impl ScalarType for BigInt {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_str(value).map(Self)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format!("{:#x}", self.0))
+    }
+}
+
+impl From<U256> for BigInt {
+    fn from(value: U256) -> Self {
+        Self(value)
+    }
+}
+
+impl From<u128> for BigInt {
+    fn from(value: u128) -> Self {
+        Self(U256::from(value))
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(value: u64) -> Self {
+        Self(U256::from(value))
+    }
+}
+
+/// A 64 bit unsigned integer. Both numbers and hexadecimal or decimal strings are accepted as
+/// input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Long(pub(crate) u64);
+
+/// A 64 bit unsigned integer.
+#[Scalar(name = "Long")]
+impl ScalarType for Long {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match value {
+            Value::Number(n) => n
+                .as_u64()
+                .map(Self)
+                .ok_or_else(|| InputValueError::custom(format!("invalid Long {n}"))),
+            Value::String(s) => {
+                let n = match s.strip_prefix("0x") {
+                    Some(hex) => u64::from_str_radix(hex, 16),
+                    None => s.parse(),
+                };
+                n.map(Self).map_err(InputValueError::custom)
+            }
+            value => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Number(self.0.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_long() {
+        assert_eq!(Long::parse(Value::Number(10u64.into())).unwrap(), Long(10));
+        assert_eq!(Long::parse(Value::String("0xa".to_string())).unwrap(), Long(10));
+        assert_eq!(Long::parse(Value::String("10".to_string())).unwrap(), Long(10));
+        assert!(Long::parse(Value::Boolean(true)).is_err());
+    }
+
+    #[test]
+    fn big_int_roundtrip() {
+        let value = BigInt(U256::from(255)).to_value();
+        assert_eq!(value, Value::String("0xff".to_string()));
+        assert_eq!(BigInt::parse(value).unwrap(), BigInt(U256::from(255)));
+        assert_eq!(
+            BigInt::parse(Value::String("255".to_string())).unwrap(),
+            BigInt(U256::from(255))
+        );
+    }
+}
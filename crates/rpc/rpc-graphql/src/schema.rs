@@ -0,0 +1,688 @@
+//! The EIP-1767 query types.
+
+use crate::{
+    backend::GraphQlBackend,
+    scalars::{Address, BigInt, Bytes, Bytes32, Long},
+};
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, InputObject, Object, Result, Schema,
+};
+use reth_primitives::{
+    BlockHashOrNumber, Bloom, BloomInput, Receipt, SealedBlock, TransactionMeta, TransactionSigned,
+};
+use reth_provider::StateProvider;
+use reth_tasks::TaskSpawner;
+use std::{ops::RangeInclusive, sync::Arc};
+use tokio::sync::oneshot;
+
+/// The maximum number of blocks a `blocks` or `logs` query may span.
+pub(crate) const MAX_BLOCK_RANGE: u64 = 1_000;
+
+/// The maximum nesting depth of a query, e.g. of `parent` fields.
+pub(crate) const MAX_QUERY_DEPTH: usize = 16;
+
+/// The maximum number of fields a query may select.
+pub(crate) const MAX_QUERY_COMPLEXITY: usize = 1_000;
+
+/// The schema served by the GraphQL server.
+pub(crate) type RethSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+/// Builds the schema backed by the given provider, which spawns the blocking reads of the
+/// provider with the executor.
+pub(crate) fn build_schema(
+    backend: Arc<dyn GraphQlBackend>,
+    executor: Box<dyn TaskSpawner>,
+) -> RethSchema {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(backend)
+        .data(executor)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+/// Runs the closure with the backend stored in the schema data on a blocking task, since the
+/// provider reads from the database.
+async fn blocking<T, F>(ctx: &Context<'_>, f: F) -> Result<T>
+where
+    F: FnOnce(&dyn GraphQlBackend) -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    let backend = ctx.data_unchecked::<Arc<dyn GraphQlBackend>>().clone();
+    let (tx, rx) = oneshot::channel();
+    ctx.data_unchecked::<Box<dyn TaskSpawner>>().spawn_blocking(Box::pin(async move {
+        let _ = tx.send(f(backend.as_ref()));
+    }));
+    rx.await?
+}
+
+/// Returns the inclusive block range `from..=to`, with both ends defaulting to the latest block.
+fn block_range(
+    backend: &dyn GraphQlBackend,
+    from: Option<Long>,
+    to: Option<Long>,
+) -> Result<RangeInclusive<u64>> {
+    let latest = backend.best_block_number()?;
+    let from = from.map_or(latest, |from| from.0);
+    let to = to.map_or(latest, |to| to.0.min(latest));
+    if to.saturating_sub(from) >= MAX_BLOCK_RANGE {
+        return Err(format!("block range exceeds the limit of {MAX_BLOCK_RANGE} blocks").into())
+    }
+    Ok(from..=to)
+}
+
+/// The root query type.
+#[derive(Debug)]
+pub(crate) struct Query;
+
+#[Object]
+impl Query {
+    /// Fetches a block by number or hash. If neither is supplied, the latest block is returned.
+    async fn block(
+        &self,
+        ctx: &Context<'_>,
+        number: Option<Long>,
+        hash: Option<Bytes32>,
+    ) -> Result<Option<Block>> {
+        if number.is_some() && hash.is_some() {
+            return Err("only one of number or hash may be supplied".into())
+        }
+        blocking(ctx, move |backend| {
+            let id = match (number, hash) {
+                (Some(number), _) => number.0.into(),
+                (None, Some(hash)) => hash.0.into(),
+                (None, None) => backend.best_block_number()?.into(),
+            };
+            Ok(backend.block(id)?.map(Block::new))
+        })
+        .await
+    }
+
+    /// Returns all blocks in the inclusive range `from..=to`. If `to` is not supplied, it
+    /// defaults to the latest block.
+    async fn blocks(&self, ctx: &Context<'_>, from: Long, to: Option<Long>) -> Result<Vec<Block>> {
+        blocking(ctx, move |backend| {
+            let mut blocks = Vec::new();
+            for number in block_range(backend, Some(from), to)? {
+                let Some(block) = backend.block(number.into())? else { break };
+                blocks.push(Block::new(block));
+            }
+            Ok(blocks)
+        })
+        .await
+    }
+
+    /// Fetches a transaction by its hash.
+    async fn transaction(&self, ctx: &Context<'_>, hash: Bytes32) -> Result<Option<Transaction>> {
+        blocking(ctx, move |backend| {
+            Ok(backend.transaction(hash.0)?.map(|(tx, meta)| Transaction { tx, meta }))
+        })
+        .await
+    }
+
+    /// Returns the logs matching the filter.
+    async fn logs(&self, ctx: &Context<'_>, filter: FilterCriteria) -> Result<Vec<Log>> {
+        blocking(ctx, move |backend| {
+            let range = block_range(backend, filter.from_block, filter.to_block)?;
+            let criteria =
+                BlockFilterCriteria { addresses: filter.addresses, topics: filter.topics };
+            let mut logs = Vec::new();
+            for number in range {
+                let Some(block) = backend.block(number.into())? else { break };
+                logs.extend(Block::new(block).filter_logs(backend, &criteria)?);
+            }
+            Ok(logs)
+        })
+        .await
+    }
+
+    /// Returns the chain ID of the network.
+    #[graphql(name = "chainID")]
+    async fn chain_id(&self, ctx: &Context<'_>) -> BigInt {
+        ctx.data_unchecked::<Arc<dyn GraphQlBackend>>().chain_id().into()
+    }
+}
+
+/// A filter on the logs of a range of blocks.
+#[derive(Debug, InputObject)]
+pub(crate) struct FilterCriteria {
+    /// The first block to include. Defaults to the latest block.
+    from_block: Option<Long>,
+    /// The last block to include. Defaults to the latest block.
+    to_block: Option<Long>,
+    /// The addresses that emitted the logs. If empty, logs of all addresses match.
+    #[graphql(default)]
+    addresses: Vec<Address>,
+    /// The topics of the logs, by position. An empty list at a position matches any topic.
+    #[graphql(default)]
+    topics: Vec<Vec<Bytes32>>,
+}
+
+/// A filter on the logs of a single block.
+#[derive(Debug, InputObject)]
+pub(crate) struct BlockFilterCriteria {
+    /// The addresses that emitted the logs. If empty, logs of all addresses match.
+    #[graphql(default)]
+    addresses: Vec<Address>,
+    /// The topics of the logs, by position. An empty list at a position matches any topic.
+    #[graphql(default)]
+    topics: Vec<Vec<Bytes32>>,
+}
+
+impl BlockFilterCriteria {
+    /// Returns `false` if the bloom filter rules out any matching log.
+    fn may_match(&self, bloom: &Bloom) -> bool {
+        let contains = |input: &[u8]| bloom.contains_input(BloomInput::Raw(input));
+        let addresses = self.addresses.is_empty() ||
+            self.addresses.iter().any(|address| contains(address.0.as_slice()));
+        addresses &&
+            self.topics.iter().all(|topics| {
+                topics.is_empty() || topics.iter().any(|topic| contains(topic.0.as_slice()))
+            })
+    }
+
+    /// Returns `true` if the log matches the filter.
+    fn matches(&self, log: &reth_primitives::Log) -> bool {
+        if !self.addresses.is_empty() && !self.addresses.iter().any(|a| a.0 == log.address) {
+            return false
+        }
+        self.topics.iter().enumerate().all(|(idx, topics)| {
+            topics.is_empty() ||
+                log.topics.get(idx).is_some_and(|topic| topics.iter().any(|t| t.0 == *topic))
+        })
+    }
+}
+
+/// An Ethereum block.
+#[derive(Debug, Clone)]
+pub(crate) struct Block {
+    block: Arc<SealedBlock>,
+}
+
+impl Block {
+    fn new(block: SealedBlock) -> Self {
+        Self { block: Arc::new(block) }
+    }
+
+    /// Returns the transaction at the given index, with its location in the block.
+    fn transaction(&self, index: usize) -> Option<Transaction> {
+        let tx = self.block.body.get(index)?.clone();
+        let header = &self.block.header;
+        let meta = TransactionMeta {
+            tx_hash: tx.hash(),
+            index: index as u64,
+            block_hash: header.hash(),
+            block_number: header.number,
+            base_fee: header.base_fee_per_gas,
+            excess_blob_gas: header.excess_blob_gas,
+        };
+        Some(Transaction { tx, meta })
+    }
+
+    /// Returns the logs of the block that match the filter.
+    fn filter_logs(
+        &self,
+        backend: &dyn GraphQlBackend,
+        filter: &BlockFilterCriteria,
+    ) -> Result<Vec<Log>> {
+        if !filter.may_match(&self.block.logs_bloom) {
+            return Ok(Vec::new())
+        }
+        let receipts = backend.receipts(self.block.hash().into())?.unwrap_or_default();
+        let mut logs = Vec::new();
+        let mut log_index = 0;
+        for (tx_index, receipt) in receipts.into_iter().enumerate() {
+            for log in receipt.logs {
+                if filter.matches(&log) {
+                    let transaction = self.transaction(tx_index).ok_or("missing transaction")?;
+                    logs.push(Log { log, index: log_index, transaction });
+                }
+                log_index += 1;
+            }
+        }
+        Ok(logs)
+    }
+}
+
+#[Object]
+impl Block {
+    /// The block number.
+    async fn number(&self) -> Long {
+        Long(self.block.number)
+    }
+
+    /// The block hash.
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.block.hash())
+    }
+
+    /// The parent block.
+    async fn parent(&self, ctx: &Context<'_>) -> Result<Option<Block>> {
+        if self.block.number == 0 {
+            return Ok(None)
+        }
+        let parent_hash = self.block.parent_hash;
+        blocking(ctx, move |backend| Ok(backend.block(parent_hash.into())?.map(Block::new))).await
+    }
+
+    /// The block nonce, an 8 byte sequence.
+    async fn nonce(&self) -> Bytes {
+        Bytes(reth_primitives::Bytes::copy_from_slice(&self.block.nonce.to_be_bytes()))
+    }
+
+    /// The root of the transaction trie.
+    async fn transactions_root(&self) -> Bytes32 {
+        Bytes32(self.block.transactions_root)
+    }
+
+    /// The number of transactions in the block.
+    async fn transaction_count(&self) -> Long {
+        Long(self.block.body.len() as u64)
+    }
+
+    /// The root of the state trie after the block.
+    async fn state_root(&self) -> Bytes32 {
+        Bytes32(self.block.state_root)
+    }
+
+    /// The root of the receipts trie.
+    async fn receipts_root(&self) -> Bytes32 {
+        Bytes32(self.block.receipts_root)
+    }
+
+    /// The root of the withdrawals trie, after Shanghai.
+    async fn withdrawals_root(&self) -> Option<Bytes32> {
+        self.block.withdrawals_root.map(Bytes32)
+    }
+
+    /// The account that received the block rewards, at the given block or this block.
+    async fn miner(&self, block: Option<Long>) -> Account {
+        Account::new(self.block.beneficiary, block, self.block.hash())
+    }
+
+    /// Arbitrary data included by the miner.
+    async fn extra_data(&self) -> Bytes {
+        Bytes(self.block.extra_data.clone())
+    }
+
+    /// The gas limit of the block.
+    async fn gas_limit(&self) -> Long {
+        Long(self.block.gas_limit)
+    }
+
+    /// The gas used by all transactions of the block.
+    async fn gas_used(&self) -> Long {
+        Long(self.block.gas_used)
+    }
+
+    /// The base fee per gas, after London.
+    async fn base_fee_per_gas(&self) -> Option<BigInt> {
+        self.block.base_fee_per_gas.map(Into::into)
+    }
+
+    /// The unix timestamp of the block.
+    async fn timestamp(&self) -> Long {
+        Long(self.block.timestamp)
+    }
+
+    /// The bloom filter of the logs of the block.
+    async fn logs_bloom(&self) -> Bytes {
+        Bytes(reth_primitives::Bytes::copy_from_slice(self.block.logs_bloom.as_slice()))
+    }
+
+    /// The mix hash of the block.
+    async fn mix_hash(&self) -> Bytes32 {
+        Bytes32(self.block.mix_hash)
+    }
+
+    /// The difficulty of the block.
+    async fn difficulty(&self) -> BigInt {
+        BigInt(self.block.difficulty)
+    }
+
+    /// The hash of the ommer list of the block.
+    async fn ommer_hash(&self) -> Bytes32 {
+        Bytes32(self.block.ommers_hash)
+    }
+
+    /// The number of ommers of the block.
+    async fn ommer_count(&self) -> Long {
+        Long(self.block.ommers.len() as u64)
+    }
+
+    /// The transactions of the block.
+    async fn transactions(&self) -> Vec<Transaction> {
+        (0..self.block.body.len()).filter_map(|index| self.transaction(index)).collect()
+    }
+
+    /// The transaction at the given index.
+    async fn transaction_at(&self, index: Long) -> Option<Transaction> {
+        self.transaction(index.0 as usize)
+    }
+
+    /// The logs of the block that match the filter.
+    async fn logs(&self, ctx: &Context<'_>, filter: BlockFilterCriteria) -> Result<Vec<Log>> {
+        let block = self.clone();
+        blocking(ctx, move |backend| block.filter_logs(backend, &filter)).await
+    }
+
+    /// The account with the given address, at this block.
+    async fn account(&self, address: Address) -> Account {
+        Account::new(address.0, None, self.block.hash())
+    }
+}
+
+/// An Ethereum transaction.
+#[derive(Debug, Clone)]
+pub(crate) struct Transaction {
+    tx: TransactionSigned,
+    meta: TransactionMeta,
+}
+
+impl Transaction {
+    /// Returns the receipt of the transaction, the gas it used and the index of its first log in
+    /// the block.
+    async fn receipt(&self, ctx: &Context<'_>) -> Result<Option<(Receipt, u64, usize)>> {
+        let meta = self.meta;
+        blocking(ctx, move |backend| {
+            let Some(receipts) = backend.receipts(meta.block_hash.into())? else { return Ok(None) };
+            let index = meta.index as usize;
+            let Some(receipt) = receipts.get(index).cloned() else { return Ok(None) };
+            let previous = &receipts[..index];
+            let gas_used = receipt.cumulative_gas_used -
+                previous.last().map_or(0, |receipt| receipt.cumulative_gas_used);
+            let log_index = previous.iter().map(|receipt| receipt.logs.len()).sum();
+            Ok(Some((receipt, gas_used, log_index)))
+        })
+        .await
+    }
+
+    fn sender(&self) -> Result<reth_primitives::Address> {
+        Ok(self.tx.recover_signer().ok_or("invalid transaction signature")?)
+    }
+}
+
+#[Object]
+impl Transaction {
+    /// The transaction hash.
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.tx.hash())
+    }
+
+    /// The nonce of the sender.
+    async fn nonce(&self) -> Long {
+        Long(self.tx.nonce())
+    }
+
+    /// The index of the transaction in its block.
+    async fn index(&self) -> Long {
+        Long(self.meta.index)
+    }
+
+    /// The sender of the transaction, at the given block or the block of the transaction.
+    async fn from(&self, block: Option<Long>) -> Result<Account> {
+        Ok(Account::new(self.sender()?, block, self.meta.block_hash))
+    }
+
+    /// The recipient of the transaction, or null for contract creations.
+    async fn to(&self, block: Option<Long>) -> Option<Account> {
+        self.tx.to().map(|to| Account::new(to, block, self.meta.block_hash))
+    }
+
+    /// The value transferred by the transaction, in wei.
+    async fn value(&self) -> BigInt {
+        BigInt(self.tx.value().into())
+    }
+
+    /// The price per gas paid by the transaction, in wei.
+    async fn gas_price(&self) -> BigInt {
+        self.tx.effective_gas_price(self.meta.base_fee).into()
+    }
+
+    /// The price per gas paid by the transaction, in wei.
+    async fn effective_gas_price(&self) -> BigInt {
+        self.tx.effective_gas_price(self.meta.base_fee).into()
+    }
+
+    /// The maximum fee per gas of the transaction.
+    async fn max_fee_per_gas(&self) -> BigInt {
+        self.tx.max_fee_per_gas().into()
+    }
+
+    /// The maximum priority fee per gas of the transaction, for EIP-1559 and blob transactions.
+    async fn max_priority_fee_per_gas(&self) -> Option<BigInt> {
+        self.tx.max_priority_fee_per_gas().map(Into::into)
+    }
+
+    /// The priority fee per gas paid to the miner.
+    async fn effective_tip_per_gas(&self) -> Option<BigInt> {
+        self.tx.effective_tip_per_gas(self.meta.base_fee).map(Into::into)
+    }
+
+    /// The gas limit of the transaction.
+    async fn gas(&self) -> Long {
+        Long(self.tx.gas_limit())
+    }
+
+    /// The input data of the transaction.
+    async fn input_data(&self) -> Bytes {
+        Bytes(self.tx.input().clone())
+    }
+
+    /// The block the transaction was included in.
+    async fn block(&self, ctx: &Context<'_>) -> Result<Option<Block>> {
+        let block_hash = self.meta.block_hash;
+        blocking(ctx, move |backend| Ok(backend.block(block_hash.into())?.map(Block::new))).await
+    }
+
+    /// The status of the transaction, 1 for success and 0 for failure.
+    async fn status(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        Ok(self.receipt(ctx).await?.map(|(receipt, ..)| Long(receipt.success as u64)))
+    }
+
+    /// The gas used by the transaction.
+    async fn gas_used(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        Ok(self.receipt(ctx).await?.map(|(_, gas_used, _)| Long(gas_used)))
+    }
+
+    /// The gas used by the transaction and all preceding transactions of the block.
+    async fn cumulative_gas_used(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        Ok(self.receipt(ctx).await?.map(|(receipt, ..)| Long(receipt.cumulative_gas_used)))
+    }
+
+    /// The contract created by the transaction, if it's a contract creation.
+    async fn created_contract(&self, block: Option<Long>) -> Result<Option<Account>> {
+        if self.tx.to().is_some() {
+            return Ok(None)
+        }
+        let address = self.sender()?.create(self.tx.nonce());
+        Ok(Some(Account::new(address, block, self.meta.block_hash)))
+    }
+
+    /// The logs emitted by the transaction.
+    async fn logs(&self, ctx: &Context<'_>) -> Result<Option<Vec<Log>>> {
+        Ok(self.receipt(ctx).await?.map(|(receipt, _, log_index)| {
+            receipt
+                .logs
+                .into_iter()
+                .enumerate()
+                .map(|(idx, log)| Log { log, index: log_index + idx, transaction: self.clone() })
+                .collect()
+        }))
+    }
+
+    /// The EIP-2718 type of the transaction.
+    #[graphql(name = "type")]
+    async fn tx_type(&self) -> Long {
+        Long(self.tx.tx_type() as u64)
+    }
+
+    /// The EIP-2718 encoding of the transaction.
+    async fn raw(&self) -> Bytes {
+        Bytes(self.tx.envelope_encoded())
+    }
+}
+
+/// A log emitted by a transaction.
+#[derive(Debug, Clone)]
+pub(crate) struct Log {
+    log: reth_primitives::Log,
+    /// The index of the log in the block.
+    index: usize,
+    transaction: Transaction,
+}
+
+#[Object]
+impl Log {
+    /// The index of the log in the block.
+    async fn index(&self) -> Long {
+        Long(self.index as u64)
+    }
+
+    /// The account that emitted the log, at the given block or the block of the log.
+    async fn account(&self, block: Option<Long>) -> Account {
+        Account::new(self.log.address, block, self.transaction.meta.block_hash)
+    }
+
+    /// The topics of the log.
+    async fn topics(&self) -> Vec<Bytes32> {
+        self.log.topics.iter().copied().map(Bytes32).collect()
+    }
+
+    /// The data of the log.
+    async fn data(&self) -> Bytes {
+        Bytes(self.log.data.clone())
+    }
+
+    /// The transaction that emitted the log.
+    async fn transaction(&self) -> &Transaction {
+        &self.transaction
+    }
+}
+
+/// An account at a specific block.
+#[derive(Debug, Clone)]
+pub(crate) struct Account {
+    address: reth_primitives::Address,
+    block: BlockHashOrNumber,
+}
+
+impl Account {
+    /// Returns the account at the given block number, or at the block with the given hash.
+    fn new(
+        address: reth_primitives::Address,
+        number: Option<Long>,
+        hash: reth_primitives::B256,
+    ) -> Self {
+        let block = number.map_or(BlockHashOrNumber::Hash(hash), |number| number.0.into());
+        Self { address, block }
+    }
+}
+
+#[Object]
+impl Account {
+    /// The address of the account.
+    async fn address(&self) -> Address {
+        Address(self.address)
+    }
+
+    /// The balance of the account, in wei.
+    async fn balance(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        let Self { address, block } = self.clone();
+        blocking(ctx, move |backend| {
+            let state = backend.state(block)?;
+            Ok(BigInt(state.account_balance(address)?.unwrap_or_default()))
+        })
+        .await
+    }
+
+    /// The number of transactions sent from the account.
+    async fn transaction_count(&self, ctx: &Context<'_>) -> Result<Long> {
+        let Self { address, block } = self.clone();
+        blocking(ctx, move |backend| {
+            let state = backend.state(block)?;
+            Ok(Long(state.account_nonce(address)?.unwrap_or_default()))
+        })
+        .await
+    }
+
+    /// The code of the account, empty for externally owned accounts.
+    async fn code(&self, ctx: &Context<'_>) -> Result<Bytes> {
+        let Self { address, block } = self.clone();
+        blocking(ctx, move |backend| {
+            let code = backend.state(block)?.account_code(address)?;
+            Ok(Bytes(code.map(|code| code.original_bytes()).unwrap_or_default()))
+        })
+        .await
+    }
+
+    /// The value of the storage slot of the account.
+    async fn storage(&self, ctx: &Context<'_>, slot: Bytes32) -> Result<Bytes32> {
+        let Self { address, block } = self.clone();
+        blocking(ctx, move |backend| {
+            let value = backend.state(block)?.storage(address, slot.0)?.unwrap_or_default();
+            Ok(Bytes32(value.into()))
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Block as PrimitiveBlock, Header, B256};
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_tasks::TokioTaskExecutor;
+    use serde_json::json;
+
+    /// Returns a schema backed by a provider with a chain of blocks with the given numbers.
+    fn schema(numbers: impl IntoIterator<Item = u64>) -> RethSchema {
+        let provider = MockEthProvider::default();
+        let mut parent_hash = B256::ZERO;
+        for number in numbers {
+            let header = Header { number, parent_hash, ..Default::default() };
+            let hash = header.hash_slow();
+            provider.add_block(hash, PrimitiveBlock { header, ..Default::default() });
+            parent_hash = hash;
+        }
+        build_schema(Arc::new(provider), Box::<TokioTaskExecutor>::default())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn query_blocks() {
+        let schema = schema(0..=2);
+
+        let response = schema.execute("{ block { number parent { number } } }").await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            json!({ "block": { "number": 2, "parent": { "number": 1 } } })
+        );
+
+        let response = schema.execute("{ blocks(from: 1) { number } }").await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            json!({ "blocks": [{ "number": 1 }, { "number": 2 }] })
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn reject_deep_queries() {
+        let schema = schema(0..=2);
+        let depth = MAX_QUERY_DEPTH;
+        let query =
+            format!("{{ block {}{{ number }}{} }}", "{ parent ".repeat(depth), " }".repeat(depth));
+        let response = schema.execute(query).await;
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].message.contains("nested too deep"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn reject_large_block_ranges() {
+        let schema = schema([0, MAX_BLOCK_RANGE]);
+        let response = schema.execute("{ blocks(from: 0) { number } }").await;
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].message.contains("block range exceeds the limit"));
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    backend::GraphQlBackend,
+    schema::{build_schema, RethSchema},
+};
+use async_graphql::http::GraphiQLSource;
+use hyper::{
+    body::HttpBody,
+    header::CONTENT_TYPE,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use reth_tasks::TaskSpawner;
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+use tracing::error;
+
+/// The path the GraphQL endpoint is served at.
+pub const GRAPHQL_PATH: &str = "/graphql";
+
+/// The maximum size of the body of a request, in bytes.
+const MAX_REQUEST_BODY_SIZE: usize = 1024 * 1024;
+
+/// Starts the GraphQL server at the given address and returns the address it's bound to.
+///
+/// Queries are accepted as JSON `POST` requests at [GRAPHQL_PATH]. A `GET` request to the same
+/// path serves the GraphiQL UI. The server and the blocking database reads of the queries are
+/// spawned with the given executor.
+pub async fn start_server<Provider>(
+    addr: SocketAddr,
+    provider: Provider,
+    executor: Box<dyn TaskSpawner>,
+) -> Result<SocketAddr, hyper::Error>
+where
+    Provider: GraphQlBackend,
+{
+    let schema = build_schema(Arc::new(provider), executor.clone());
+    let make_svc = make_service_fn(move |_| {
+        let schema = schema.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                let schema = schema.clone();
+                async move { Ok::<_, Infallible>(handle_request(schema, req).await) }
+            }))
+        }
+    });
+
+    let server = Server::try_bind(&addr)?.serve(make_svc);
+    let local_addr = server.local_addr();
+    executor.spawn_critical(
+        "graphql server",
+        Box::pin(async move {
+            if let Err(err) = server.await {
+                error!(target: "rpc::graphql", %err, "GraphQL server failed");
+            }
+        }),
+    );
+
+    Ok(local_addr)
+}
+
+async fn handle_request(schema: RethSchema, req: Request<Body>) -> Response<Body> {
+    if req.uri().path() != GRAPHQL_PATH {
+        return response(StatusCode::NOT_FOUND, "text/plain", "not found")
+    }
+
+    match *req.method() {
+        Method::GET => response(
+            StatusCode::OK,
+            "text/html",
+            GraphiQLSource::build().endpoint(GRAPHQL_PATH).finish(),
+        ),
+        Method::POST => {
+            let body = match read_body(req.into_body()).await {
+                Ok(body) => body,
+                Err(response) => return response,
+            };
+            let request = match serde_json::from_slice::<async_graphql::Request>(&body) {
+                Ok(request) => request,
+                Err(err) => return response(StatusCode::BAD_REQUEST, "text/plain", err.to_string()),
+            };
+            let result = schema.execute(request).await;
+            match serde_json::to_vec(&result) {
+                Ok(json) => response(StatusCode::OK, "application/json", json),
+                Err(err) => {
+                    response(StatusCode::INTERNAL_SERVER_ERROR, "text/plain", err.to_string())
+                }
+            }
+        }
+        _ => response(StatusCode::METHOD_NOT_ALLOWED, "text/plain", "method not allowed"),
+    }
+}
+
+/// Reads the body of a request, rejecting it if it's larger than [MAX_REQUEST_BODY_SIZE].
+async fn read_body(mut body: Body) -> Result<Vec<u8>, Response<Body>> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk
+            .map_err(|err| response(StatusCode::BAD_REQUEST, "text/plain", err.to_string()))?;
+        if bytes.len() + chunk.len() > MAX_REQUEST_BODY_SIZE {
+            return Err(response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "text/plain",
+                format!("request body exceeds the limit of {MAX_REQUEST_BODY_SIZE} bytes"),
+            ))
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+fn response(status: StatusCode, content_type: &str, body: impl Into<Body>) -> Response<Body> {
+    let mut response = Response::new(body.into());
+    *response.status_mut() = status;
+    response.headers_mut().insert(CONTENT_TYPE, content_type.parse().expect("valid header value"));
+    response
+}
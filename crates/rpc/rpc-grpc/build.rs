@@ -0,0 +1,4 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tonic_build::configure().build_client(false).compile(&["proto/events.proto"], &["proto"])?;
+    Ok(())
+}
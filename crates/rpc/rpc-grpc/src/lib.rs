@@ -0,0 +1,27 @@
+//! gRPC streaming API for the canonical chain.
+//!
+//! The [ChainEventsService] streams canonical blocks together with their receipts and state diffs
+//! as they are committed. Unlike WS subscriptions, the stream is backed by the database, so it
+//! never drops blocks for slow clients and can be resumed from any block. See
+//! `proto/events.proto` for the protocol.
+//!
+//! Compiling the protocol requires `protoc` to be installed.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![warn(missing_debug_implementations, missing_docs, unreachable_pub, rustdoc::all)]
+#![deny(unused_must_use, rust_2018_idioms)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod service;
+
+/// The generated protocol types.
+#[allow(missing_docs, unreachable_pub)]
+pub mod proto {
+    tonic::include_proto!("reth.events.v1");
+}
+
+pub use service::{start_server, ChainEventsService, MAX_REORG_DEPTH};
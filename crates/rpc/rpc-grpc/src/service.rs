@@ -0,0 +1,320 @@
+use crate::proto::{
+    chain_event::Event,
+    chain_events_server::{ChainEvents, ChainEventsServer},
+    AccountDiff, AccountInfo, BlockCommitted, BlockReverted, ChainEvent, StorageDiff,
+    SubscribeRequest,
+};
+use reth_interfaces::provider::ProviderError;
+use reth_primitives::{Block, BlockNumber, B256};
+use reth_provider::{
+    AccountReader, BlockReader, CanonStateNotifications, CanonStateSubscriptions, ChangeSetReader,
+    StateProvider, StateProviderFactory,
+};
+use reth_tasks::TaskSpawner;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    net::SocketAddr,
+};
+use tokio::{
+    net::TcpListener,
+    sync::{broadcast::error::RecvError, mpsc, oneshot},
+};
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::{transport::Server, Request, Response, Status};
+use tracing::{debug, error};
+
+/// The maximum depth of a reorg of streamed blocks that a stream can follow.
+///
+/// Deeper reorgs fail the stream with `FAILED_PRECONDITION`, like a resumed stream whose parent
+/// hash is no longer canonical.
+pub const MAX_REORG_DEPTH: usize = 256;
+
+/// The number of events buffered per stream before waiting for the client.
+const STREAM_BUFFER: usize = 16;
+
+/// Starts the gRPC server at the given address and returns the address it's bound to.
+///
+/// The server, the streams and their blocking database reads are spawned with the given executor.
+pub async fn start_server<Provider, Events>(
+    addr: SocketAddr,
+    provider: Provider,
+    events: Events,
+    executor: Box<dyn TaskSpawner>,
+) -> std::io::Result<SocketAddr>
+where
+    Provider: BlockReader + ChangeSetReader + StateProviderFactory + Clone + 'static,
+    Events: CanonStateSubscriptions + Clone + 'static,
+{
+    let listener = TcpListener::bind(addr).await?;
+    let local_addr = listener.local_addr()?;
+    let service =
+        ChainEventsServer::new(ChainEventsService::new(provider, events, executor.clone()));
+    executor.spawn_critical(
+        "grpc server",
+        Box::pin(async move {
+            let incoming = TcpListenerStream::new(listener);
+            if let Err(err) =
+                Server::builder().add_service(service).serve_with_incoming(incoming).await
+            {
+                error!(target: "rpc::grpc", %err, "gRPC server failed");
+            }
+        }),
+    );
+    Ok(local_addr)
+}
+
+/// Implementation of the `ChainEvents` gRPC service.
+#[derive(Debug, Clone)]
+pub struct ChainEventsService<Provider, Events> {
+    provider: Provider,
+    events: Events,
+    executor: Box<dyn TaskSpawner>,
+}
+
+impl<Provider, Events> ChainEventsService<Provider, Events> {
+    /// Creates a new service that reads blocks from the provider and follows the canonical chain
+    /// using the events. The streams and the database reads are spawned with the executor.
+    pub fn new(provider: Provider, events: Events, executor: Box<dyn TaskSpawner>) -> Self {
+        Self { provider, events, executor }
+    }
+}
+
+#[tonic::async_trait]
+impl<Provider, Events> ChainEvents for ChainEventsService<Provider, Events>
+where
+    Provider: BlockReader + ChangeSetReader + StateProviderFactory + Clone + 'static,
+    Events: CanonStateSubscriptions + Clone + 'static,
+{
+    type SubscribeStream = ReceiverStream<Result<ChainEvent, Status>>;
+
+    async fn subscribe(
+        &self,
+        request: Request<SubscribeRequest>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        let SubscribeRequest { start_block, parent_hash } = request.into_inner();
+
+        // the parent of the first block is the anchor that the stream is checked against
+        let mut streamed = VecDeque::new();
+        if let Some(parent) = start_block.checked_sub(1) {
+            let hash = match parent_hash {
+                Some(hash) => B256::try_from(hash.as_slice())
+                    .map_err(|_| Status::invalid_argument("parent hash must be 32 bytes"))?,
+                None => {
+                    let provider = self.provider.clone();
+                    read(&*self.executor, move || provider.block_hash(parent)).await?.ok_or_else(
+                        || Status::out_of_range(format!("block {parent} is not canonical yet")),
+                    )?
+                }
+            };
+            streamed.push_back((parent, hash));
+        }
+
+        // subscribe before the first sync, so no update is missed
+        let notifications = self.events.subscribe_to_canonical_state();
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        let stream = BlockStream {
+            provider: self.provider.clone(),
+            executor: self.executor.clone(),
+            next_block: start_block,
+            streamed,
+            tx,
+        };
+        debug!(target: "rpc::grpc", start_block, "New chain events stream");
+        self.executor.spawn(Box::pin(stream.run(notifications)));
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+/// Streams the canonical chain to a single client.
+struct BlockStream<Provider> {
+    provider: Provider,
+    /// Spawns the blocking database reads.
+    executor: Box<dyn TaskSpawner>,
+    /// The next block to stream.
+    next_block: BlockNumber,
+    /// Number and hash of the most recently streamed blocks, oldest first.
+    ///
+    /// The first entry is only used to detect reorgs and is never reverted.
+    streamed: VecDeque<(BlockNumber, B256)>,
+    tx: mpsc::Sender<Result<ChainEvent, Status>>,
+}
+
+impl<Provider> BlockStream<Provider>
+where
+    Provider: BlockReader + ChangeSetReader + StateProviderFactory + Clone + 'static,
+{
+    /// Streams the chain until the client disconnects or the stream fails.
+    ///
+    /// The notifications are only used as a signal that the chain changed, the blocks are always
+    /// read from the database. This is why a lagging receiver doesn't lose any blocks.
+    async fn run(mut self, mut notifications: CanonStateNotifications) {
+        loop {
+            if let Err(status) = self.sync().await {
+                let _ = self.tx.send(Err(status)).await;
+                return
+            }
+
+            tokio::select! {
+                _ = self.tx.closed() => return,
+                res = notifications.recv() => {
+                    if let Err(RecvError::Closed) = res {
+                        return
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reverts the reorged blocks and streams all canonical blocks up to the tip.
+    async fn sync(&mut self) -> Result<(), Status> {
+        'sync: loop {
+            self.revert_reorged().await?;
+
+            let tip = self.read(|provider| provider.best_block_number()).await?;
+            while self.next_block <= tip {
+                let number = self.next_block;
+                let Some(block) = self.read(move |provider| provider.block(number.into())).await?
+                else {
+                    return Ok(())
+                };
+                if self.streamed.back().is_some_and(|(_, hash)| *hash != block.parent_hash) {
+                    // the chain reorged since the tip was read
+                    continue 'sync
+                }
+                self.commit(block).await?;
+            }
+            return Ok(())
+        }
+    }
+
+    /// Sends [BlockReverted] events for all streamed blocks that are no longer canonical.
+    async fn revert_reorged(&mut self) -> Result<(), Status> {
+        while let Some(&(number, hash)) = self.streamed.back() {
+            if self.read(move |provider| provider.block_hash(number)).await? == Some(hash) {
+                return Ok(())
+            }
+            if self.streamed.len() == 1 {
+                return Err(Status::failed_precondition(format!(
+                    "block {number} ({hash}) is no longer canonical"
+                )))
+            }
+            self.streamed.pop_back();
+            self.send(Event::Reverted(BlockReverted { number, hash: hash.to_vec() })).await?;
+            self.next_block = number;
+        }
+        Ok(())
+    }
+
+    /// Sends the [BlockCommitted] event of the block.
+    async fn commit(&mut self, block: Block) -> Result<(), Status> {
+        let number = block.number;
+        let hash = block.header.hash_slow();
+
+        let (receipts, state_diff) = self
+            .read(move |provider| {
+                let receipts = provider
+                    .receipts_by_block(number.into())?
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|receipt| {
+                        let mut buf = Vec::new();
+                        receipt.with_bloom().encode_inner(&mut buf, false);
+                        buf
+                    })
+                    .collect::<Vec<_>>();
+                Ok((receipts, state_diff(provider, number)?))
+            })
+            .await?;
+
+        let event = BlockCommitted {
+            number,
+            hash: hash.to_vec(),
+            block: alloy_rlp::encode(&block),
+            receipts,
+            state_diff,
+        };
+        self.send(Event::Committed(event)).await?;
+
+        self.streamed.push_back((number, hash));
+        if self.streamed.len() > MAX_REORG_DEPTH {
+            self.streamed.pop_front();
+        }
+        self.next_block = number + 1;
+        Ok(())
+    }
+
+    /// Runs the read of the provider on a blocking task.
+    async fn read<T, F>(&self, f: F) -> Result<T, Status>
+    where
+        F: FnOnce(&Provider) -> Result<T, ProviderError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let provider = self.provider.clone();
+        read(&*self.executor, move || f(&provider)).await
+    }
+
+    async fn send(&self, event: Event) -> Result<(), Status> {
+        self.tx
+            .send(Ok(ChainEvent { event: Some(event) }))
+            .await
+            .map_err(|_| Status::cancelled("stream closed"))
+    }
+}
+
+/// Returns the accounts and storage slots changed by the block, with their values after it.
+fn state_diff<Provider>(
+    provider: &Provider,
+    number: BlockNumber,
+) -> Result<Vec<AccountDiff>, ProviderError>
+where
+    Provider: ChangeSetReader + StateProviderFactory,
+{
+    let mut changes = BTreeMap::<_, Vec<B256>>::new();
+    for account in provider.account_block_changeset(number)? {
+        changes.entry(account.address).or_default();
+    }
+    for (address, slot) in provider.storage_block_changeset(number)? {
+        changes.entry(address).or_default().push(slot.key);
+    }
+
+    let state = provider.history_by_block_number(number)?;
+    changes
+        .into_iter()
+        .map(|(address, slots)| {
+            let account = state.basic_account(address)?.map(|account| AccountInfo {
+                nonce: account.nonce,
+                balance: account.balance.to_be_bytes::<32>().to_vec(),
+                code_hash: account.bytecode_hash.map(|hash| hash.to_vec()),
+            });
+            let storage = slots
+                .into_iter()
+                .map(|slot| {
+                    let value = state.storage(address, slot)?.unwrap_or_default();
+                    Ok(StorageDiff {
+                        slot: slot.to_vec(),
+                        value: value.to_be_bytes::<32>().to_vec(),
+                    })
+                })
+                .collect::<Result<_, ProviderError>>()?;
+            Ok(AccountDiff { address: address.to_vec(), account, storage })
+        })
+        .collect()
+}
+
+/// Runs a database read on a blocking task of the executor, so it doesn't block the async runtime.
+async fn read<T, F>(executor: &dyn TaskSpawner, f: F) -> Result<T, Status>
+where
+    F: FnOnce() -> Result<T, ProviderError> + Send + 'static,
+    T: Send + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    executor.spawn_blocking(Box::pin(async move {
+        let _ = tx.send(f());
+    }));
+    rx.await.map_err(|_| Status::internal("database read was cancelled"))?.map_err(internal)
+}
+
+fn internal(err: ProviderError) -> Status {
+    Status::internal(err.to_string())
+}
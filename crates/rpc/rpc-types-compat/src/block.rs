@@ -144,7 +144,9 @@ pub fn from_primitive_with_hash(primitive_header: reth_primitives::SealedHeader)
     }
 }
 
-fn from_primitive_withdrawal(
+/// Converts a primitive [Withdrawal](reth_primitives::Withdrawal) to an rpc
+/// [Withdrawal](reth_rpc_types::Withdrawal).
+pub fn from_primitive_withdrawal(
     withdrawal: reth_primitives::Withdrawal,
 ) -> reth_rpc_types::Withdrawal {
     reth_rpc_types::Withdrawal {
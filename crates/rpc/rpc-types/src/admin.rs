@@ -28,6 +28,9 @@ pub struct NodeInfo {
     pub name: String,
     /// Networking protocols being run by the local node.
     pub protocols: Protocols,
+    /// The mode of the node, e.g. `archive` or `full`, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
 }
 
 impl NodeInfo {
@@ -41,6 +44,7 @@ impl NodeInfo {
             ports: Ports { discovery: enr.udp_port, listener: enr.tcp_port },
             name: status.client_version,
             protocols: Protocols { eth: status.eth_protocol_info, other: Default::default() },
+            mode: None,
         }
     }
 }
@@ -89,6 +93,18 @@ pub struct EthProtocolInfo {
     pub genesis: B256,
 }
 
+/// An open read transaction of the database, see `admin_dbReaders`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbReaderInfo {
+    /// ID of the database snapshot the transaction reads.
+    pub txn_id: u64,
+    /// Time since the transaction was opened, in milliseconds.
+    pub age_ms: u64,
+    /// Name of the thread that opened the transaction.
+    pub component: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
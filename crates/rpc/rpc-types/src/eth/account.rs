@@ -36,6 +36,18 @@ pub struct EIP1186AccountProofResponse {
     pub storage_proof: Vec<EIP1186StorageProof>,
 }
 
+/// Response for `debug_executionWitness`: the state accessed by a block.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionWitness {
+    /// The state root of the parent block that all proofs are against.
+    pub state_root: B256,
+    /// The proofs of the accounts and storage slots accessed by the block, before its execution.
+    pub accounts: Vec<EIP1186AccountProofResponse>,
+    /// The bytecodes of the accounts accessed by the block, before its execution.
+    pub codes: Vec<Bytes>,
+}
+
 /// Extended account information (used by `parity_allAccountInfo`).
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ExtAccountInfo {
@@ -30,5 +30,5 @@ pub use log::Log;
 pub use raw_log::{logs_bloom, Log as RawLog};
 pub use syncing::*;
 pub use transaction::*;
-pub use withdrawal::Withdrawal;
+pub use withdrawal::{BlockWithdrawal, Withdrawal};
 pub use work::Work;
@@ -16,6 +16,21 @@ pub struct SyncInfo {
     pub warp_chunks_amount: Option<U256>,
     /// Warp sync snapshot chunks processed.
     pub warp_chunks_processed: Option<U256>,
+    /// The progress of each stage of the sync pipeline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stages: Option<Vec<StageInfo>>,
+    /// Estimated number of seconds until the sync is finished.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eta_seconds: Option<U64>,
+}
+
+/// The progress of a single stage of the sync pipeline.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub struct StageInfo {
+    /// The name of the stage.
+    pub stage_name: String,
+    /// The block number the stage has reached.
+    pub block_number: U64,
 }
 
 /// Peers info
@@ -54,6 +69,25 @@ pub struct PeerInfo {
     pub network: PeerNetworkInfo,
     /// Protocols information
     pub protocols: PeerProtocolsInfo,
+    /// Latencies of the requests sent to the peer by request message type
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub latency: BTreeMap<String, PeerRequestLatency>,
+}
+
+/// Latencies of the requests of a single message type sent to a peer, in milliseconds
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerRequestLatency {
+    /// Number of responses received
+    pub count: u64,
+    /// Mean latency
+    pub mean_ms: u64,
+    /// Upper bound of the median latency
+    pub p50_ms: u64,
+    /// Upper bound of the 95th percentile latency
+    pub p95_ms: u64,
+    /// Maximum latency
+    pub max_ms: u64,
 }
 
 /// Peer network information
@@ -1,5 +1,5 @@
 use crate::serde_helpers::num::from_int_or_hex;
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_primitives::{Address, Bytes, B256, U256, U64};
 use serde::{Deserialize, Serialize};
 
 /// The response object for `debug_traceTransaction` with `"tracer": "callTracer"`
@@ -52,6 +52,9 @@ pub struct CallLogFrame {
     pub topics: Option<Vec<B256>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub data: Option<Bytes>,
+    /// The number of child calls of the frame that were made before the log was emitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<U64>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -115,4 +118,21 @@ mod tests {
         let _trace: CallFrame = serde_json::from_str(ONLY_TOP_CALL).unwrap();
         let _trace: CallFrame = serde_json::from_str(WITH_LOG).unwrap();
     }
+
+    #[test]
+    fn test_call_trace_roundtrip() {
+        let trace: CallFrame = serde_json::from_str(ONLY_TOP_CALL).unwrap();
+        similar_asserts::assert_eq!(
+            serde_json::to_value(trace).unwrap(),
+            serde_json::from_str::<serde_json::Value>(ONLY_TOP_CALL).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_call_log_position() {
+        let log = r#"{"address":"0xf4eced2f682ce333f96f2d8966c613ded8fc95dd","topics":["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"],"data":"0x","position":"0x1"}"#;
+        let frame: CallLogFrame = serde_json::from_str(log).unwrap();
+        assert_eq!(frame.position, Some(U64::from(1)));
+        assert_eq!(serde_json::to_string(&frame).unwrap(), log);
+    }
 }
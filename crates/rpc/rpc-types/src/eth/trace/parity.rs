@@ -180,7 +180,7 @@ impl Action {
 
     /// Returns true if this is a create action
     pub fn is_create(&self) -> bool {
-        matches!(self, Action::Call(_))
+        matches!(self, Action::Create(_))
     }
 
     /// Returns true if this is a selfdestruct action
@@ -529,6 +529,19 @@ mod tests {
     use serde_json::{json, Value};
     use std::str::FromStr;
 
+    #[test]
+    fn test_trace_results_roundtrip() {
+        // `trace_replayTransaction` with `trace`, `vmTrace` and `stateDiff`
+        let input = include_str!("../../../test_data/parity/trace_replay_transaction.json");
+        let results = serde_json::from_str::<TraceResults>(input).unwrap();
+        assert!(results.trace[0].action.is_call());
+        assert!(results.state_diff.as_ref().unwrap().values().any(|diff| diff.nonce.is_changed()));
+        assert_eq!(results.vm_trace.as_ref().unwrap().ops.len(), 4);
+
+        let expect = serde_json::from_str::<Value>(input).unwrap();
+        similar_asserts::assert_eq!(serde_json::to_value(results).unwrap(), expect);
+    }
+
     #[test]
     fn test_transaction_trace() {
         let s = r#"{
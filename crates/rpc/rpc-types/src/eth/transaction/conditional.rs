@@ -0,0 +1,114 @@
+//! Conditions of transactions submitted with `eth_sendRawTransactionConditional`.
+
+use alloy_primitives::{Address, B256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The expected storage of an account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KnownAccount {
+    /// The expected storage root of the account.
+    StorageRoot(B256),
+    /// The expected values of storage slots of the account.
+    Slots(BTreeMap<B256, B256>),
+}
+
+/// Conditions a transaction has to satisfy to be included in a block.
+///
+/// See also <https://notes.ethereum.org/@yoav/SkaX2lS9j>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionConditional {
+    /// The expected storage of accounts at the time of inclusion.
+    #[serde(default)]
+    pub known_accounts: BTreeMap<Address, KnownAccount>,
+    /// The minimum number of the including block.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::serde_helpers::u64_hex_opt"
+    )]
+    pub block_number_min: Option<u64>,
+    /// The maximum number of the including block.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::serde_helpers::u64_hex_opt"
+    )]
+    pub block_number_max: Option<u64>,
+    /// The minimum timestamp of the including block.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::serde_helpers::u64_hex_opt"
+    )]
+    pub timestamp_min: Option<u64>,
+    /// The maximum timestamp of the including block.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::serde_helpers::u64_hex_opt"
+    )]
+    pub timestamp_max: Option<u64>,
+}
+
+impl TransactionConditional {
+    /// Returns `true` if a block with the given number is within the block number range.
+    pub fn matches_block_number(&self, number: u64) -> bool {
+        self.block_number_min.map_or(true, |min| number >= min) &&
+            self.block_number_max.map_or(true, |max| number <= max)
+    }
+
+    /// Returns `true` if a block with the given timestamp is within the timestamp range.
+    pub fn matches_timestamp(&self, timestamp: u64) -> bool {
+        self.timestamp_min.map_or(true, |min| timestamp >= min) &&
+            self.timestamp_max.map_or(true, |max| timestamp <= max)
+    }
+
+    /// Returns `true` if no block after the one with the given number and timestamp can satisfy
+    /// the block number and timestamp ranges anymore.
+    pub fn has_expired(&self, number: u64, timestamp: u64) -> bool {
+        self.block_number_max.is_some_and(|max| number >= max) ||
+            self.timestamp_max.is_some_and(|max| timestamp >= max)
+    }
+
+    /// Returns the number of storage slots and storage roots that are expected.
+    pub fn known_accounts_cost(&self) -> usize {
+        self.known_accounts
+            .values()
+            .map(|account| match account {
+                KnownAccount::StorageRoot(_) => 1,
+                KnownAccount::Slots(slots) => slots.len(),
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn serde_transaction_conditional() {
+        let s = r#"{"knownAccounts":{"0x000000000000000000000000000000000000beef":{"0x0000000000000000000000000000000000000000000000000000000000000002":"0x0000000000000000000000000000000000000000000000000000000000000003"},"0x000000000000000000000000000000000000dead":"0x0000000000000000000000000000000000000000000000000000000000000001"},"blockNumberMin":"0x64","timestampMax":"0x65"}"#;
+        let conditional = serde_json::from_str::<TransactionConditional>(s).unwrap();
+        assert_eq!(conditional.block_number_min, Some(100));
+        assert_eq!(conditional.block_number_max, None);
+        assert_eq!(conditional.timestamp_max, Some(101));
+        assert_eq!(
+            conditional.known_accounts[&address!("000000000000000000000000000000000000dead")],
+            KnownAccount::StorageRoot(B256::with_last_byte(1))
+        );
+        assert_eq!(conditional.known_accounts_cost(), 2);
+        assert_eq!(serde_json::to_string(&conditional).unwrap(), s);
+
+        assert!(!conditional.matches_block_number(99));
+        assert!(conditional.matches_block_number(100));
+        assert!(conditional.matches_timestamp(101));
+        assert!(!conditional.matches_timestamp(102));
+        assert!(!conditional.has_expired(100, 100));
+        assert!(conditional.has_expired(100, 101));
+    }
+}
@@ -3,6 +3,7 @@
 pub use access_list::{AccessList, AccessListItem, AccessListWithGasUsed};
 use alloy_primitives::{Address, Bytes, B256, U128, U256, U64};
 pub use common::TransactionInfo;
+pub use conditional::{KnownAccount, TransactionConditional};
 pub use receipt::TransactionReceipt;
 pub use request::TransactionRequest;
 use serde::{Deserialize, Serialize};
@@ -11,6 +12,7 @@ pub use typed::*;
 
 mod access_list;
 mod common;
+mod conditional;
 pub mod kzg;
 mod receipt;
 mod request;
@@ -41,6 +41,18 @@ impl Withdrawal {
     }
 }
 
+/// A [Withdrawal] with the number of the block that included it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockWithdrawal {
+    /// Number of the block that included the withdrawal.
+    #[serde(with = "u64_hex")]
+    pub block_number: u64,
+    /// The withdrawal.
+    #[serde(flatten)]
+    pub withdrawal: Withdrawal,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -54,4 +66,15 @@ mod tests {
         let s = serde_json::to_string(&withdrawals).unwrap();
         assert_eq!(input, s);
     }
+
+    #[test]
+    fn test_block_withdrawal_serde_roundtrip() {
+        let input = r#"{"blockNumber":"0x10","index":"0x1","validatorIndex":"0x2","address":"0x0000000000000000000000000000000000001000","amount":"0x3"}"#;
+
+        let withdrawal: BlockWithdrawal = serde_json::from_str(input).unwrap();
+        assert_eq!(withdrawal.block_number, 16);
+        assert_eq!(withdrawal.withdrawal.validator_index, 2);
+        let s = serde_json::to_string(&withdrawal).unwrap();
+        assert_eq!(input, s);
+    }
 }
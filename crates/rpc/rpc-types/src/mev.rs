@@ -609,6 +609,18 @@ pub struct EthCallBundle {
     /// the timestamp to use for this bundle simulation, in seconds since the unix epoch
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timestamp: Option<u64>,
+    /// the coinbase to use for this bundle simulation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coinbase: Option<Address>,
+    /// the gas limit of the block to use for this bundle simulation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+    /// the difficulty of the block to use for this bundle simulation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<U256>,
+    /// the base fee of the block to use for this bundle simulation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee: Option<U256>,
 }
 
 /// Response for `eth_callBundle`
@@ -886,6 +898,25 @@ mod tests {
         let _call = serde_json::from_str::<EthCallBundleResponse>(s).unwrap();
     }
 
+    #[test]
+    fn can_deserialize_eth_call_bundle() {
+        let s = r#"{
+    "txs": ["0x02"],
+    "blockNumber": "0x4fb8d3",
+    "stateBlockNumber": "latest",
+    "timestamp": 1700000000,
+    "coinbase": "0x73625f59CAdc5009Cb458B751b3E7b6b48C06f2C",
+    "gasLimit": 30000000,
+    "baseFee": "0x7"
+  }"#;
+
+        let bundle = serde_json::from_str::<EthCallBundle>(s).unwrap();
+        assert_eq!(bundle.state_block_number, BlockNumberOrTag::Latest);
+        assert_eq!(bundle.gas_limit, Some(30_000_000));
+        assert_eq!(bundle.base_fee, Some(U256::from(7)));
+        assert_eq!(bundle.difficulty, None);
+    }
+
     #[test]
     fn can_serialize_deserialize_bundle_stats() {
         let fixtures = [
@@ -1,9 +1,11 @@
 use crate::{Block, BlockTransactions, Rich, Transaction, TransactionReceipt};
 use alloy_primitives::{Address, Bytes, U256};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Operation type enum for `InternalOperation` struct
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+///
+/// Serialized as its numeric value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum OperationType {
     /// Operation Transfer
     OpTransfer = 0,
@@ -15,67 +17,114 @@ pub enum OperationType {
     OpCreate2 = 3,
 }
 
+impl Serialize for OperationType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for OperationType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match u8::deserialize(deserializer)? {
+            0 => Ok(OperationType::OpTransfer),
+            1 => Ok(OperationType::OpSelfDestruct),
+            2 => Ok(OperationType::OpCreate),
+            3 => Ok(OperationType::OpCreate2),
+            value => Err(serde::de::Error::custom(format!("invalid operation type {value}"))),
+        }
+    }
+}
+
 /// Custom struct for otterscan `getInternalOperations` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct InternalOperation {
-    r#type: OperationType,
-    from: Address,
-    to: Address,
-    value: u128,
+    /// The type of the operation.
+    pub r#type: OperationType,
+    /// The address that sent the value or created the contract, or the destroyed contract.
+    pub from: Address,
+    /// The address that received the value, the created contract or the beneficiary of the
+    /// destroyed contract.
+    pub to: Address,
+    /// The transferred value.
+    pub value: U256,
 }
 
 /// Custom struct for otterscan `traceTransaction` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct TraceEntry {
-    r#type: String,
-    depth: u32,
-    from: Address,
-    to: Address,
-    value: u128,
-    input: Bytes,
+    /// The kind of the call, e.g. `CALL` or `SELFDESTRUCT`.
+    pub r#type: String,
+    /// The depth of the call, `0` for the call of the transaction.
+    pub depth: u32,
+    /// The caller.
+    pub from: Address,
+    /// The callee, the created contract or the beneficiary of the destroyed contract.
+    pub to: Address,
+    /// The transferred value, [None] for calls that can't transfer value.
+    pub value: Option<U256>,
+    /// The input of the call or the init code of the created contract.
+    pub input: Bytes,
 }
 
 /// Internal issuance struct for `BlockDetails` struct
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct InternalIssuance {
-    block_reward: U256,
-    uncle_reward: U256,
-    issuance: U256,
+    /// The reward of the block author, including the reward for the included uncles.
+    pub block_reward: U256,
+    /// The reward of the authors of the included uncles.
+    pub uncle_reward: U256,
+    /// The total issuance of the block.
+    pub issuance: U256,
 }
 
 /// Custom `Block` struct that includes transaction count for Otterscan responses
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OtsBlock {
+    /// The block.
     #[serde(flatten)]
-    block: Block,
-    transaction_count: usize,
+    pub block: Block,
+    /// The number of transactions in the block.
+    pub transaction_count: usize,
 }
 
 /// Custom struct for otterscan `getBlockDetails` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockDetails {
-    block: OtsBlock,
-    issuance: InternalIssuance,
-    total_fees: U256,
+    /// The block.
+    pub block: OtsBlock,
+    /// The ether issued with the block.
+    pub issuance: InternalIssuance,
+    /// The sum of the fees paid by the transactions of the block.
+    pub total_fees: U256,
 }
 
 /// Custom transaction receipt struct for otterscan `OtsBlockTransactions` struct
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OtsTransactionReceipt {
+    /// The receipt.
     #[serde(flatten)]
-    receipt: TransactionReceipt,
-    timestamp: u64,
+    pub receipt: TransactionReceipt,
+    /// The timestamp of the block that included the transaction.
+    pub timestamp: u64,
 }
 
 /// Custom struct for otterscan `getBlockTransactions` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct OtsBlockTransactions {
-    fullblock: OtsBlock,
-    receipts: Vec<OtsTransactionReceipt>,
+    /// The block, with the transactions of the page.
+    pub fullblock: OtsBlock,
+    /// The receipts of the transactions of the page.
+    pub receipts: Vec<OtsTransactionReceipt>,
 }
 
 /// Custom struct for otterscan `searchTransactionsAfter`and `searchTransactionsBefore` RPC
@@ -83,17 +132,23 @@ pub struct OtsBlockTransactions {
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionsWithReceipts {
-    txs: Vec<Transaction>,
-    receipts: Vec<OtsTransactionReceipt>,
-    first_page: bool,
-    last_page: bool,
+    /// The transactions, from the newest to the oldest.
+    pub txs: Vec<Transaction>,
+    /// The receipts of the transactions.
+    pub receipts: Vec<OtsTransactionReceipt>,
+    /// Whether the page contains the newest transactions.
+    pub first_page: bool,
+    /// Whether the page contains the oldest transactions.
+    pub last_page: bool,
 }
 
 /// Custom struct for otterscan `getContractCreator` RPC responses
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ContractCreator {
-    tx: Transaction,
-    creator: Address,
+    /// The transaction that created the contract.
+    pub tx: Transaction,
+    /// The address that created the contract.
+    pub creator: Address,
 }
 
 impl From<Block> for OtsBlock {
@@ -117,3 +172,30 @@ impl From<Rich<Block>> for BlockDetails {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_internal_operation() {
+        let operation = InternalOperation {
+            r#type: OperationType::OpCreate2,
+            from: Address::with_last_byte(1),
+            to: Address::with_last_byte(2),
+            value: U256::from(16),
+        };
+        let json = serde_json::to_value(&operation).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": 3,
+                "from": "0x0000000000000000000000000000000000000001",
+                "to": "0x0000000000000000000000000000000000000002",
+                "value": "0x10"
+            })
+        );
+        assert_eq!(serde_json::from_value::<InternalOperation>(json).unwrap(), operation);
+        assert!(serde_json::from_str::<OperationType>("4").is_err());
+    }
+}
@@ -1,10 +1,15 @@
 use crate::result::ToRpcResult;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use reth_interfaces::db::DatabaseReaders;
 use reth_network_api::{NetworkInfo, PeerKind, Peers};
 use reth_primitives::NodeRecord;
 use reth_rpc_api::AdminApiServer;
-use reth_rpc_types::{NodeInfo, PeerEthProtocolInfo, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo};
+use reth_rpc_types::{
+    DbReaderInfo, NodeInfo, PeerEthProtocolInfo, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo,
+    PeerRequestLatency,
+};
+use std::sync::Arc;
 
 /// `admin` API implementation.
 ///
@@ -12,12 +17,29 @@ use reth_rpc_types::{NodeInfo, PeerEthProtocolInfo, PeerInfo, PeerNetworkInfo, P
 pub struct AdminApi<N> {
     /// An interface to interact with the network
     network: N,
+    /// The open read transactions of the database, if available
+    db_readers: Option<Arc<dyn DatabaseReaders>>,
+    /// The mode of the node, e.g. `archive` or `full`, if known
+    node_mode: Option<String>,
 }
 
 impl<N> AdminApi<N> {
     /// Creates a new instance of `AdminApi`.
     pub fn new(network: N) -> Self {
-        AdminApi { network }
+        AdminApi { network, db_readers: None, node_mode: None }
+    }
+
+    /// Configures the mode of the node returned by `admin_nodeInfo`.
+    pub fn with_node_mode(mut self, node_mode: String) -> Self {
+        self.node_mode = Some(node_mode);
+        self
+    }
+
+    /// Configures the source of the open database read transactions returned by
+    /// `admin_dbReaders`.
+    pub fn with_db_readers(mut self, db_readers: Arc<dyn DatabaseReaders>) -> Self {
+        self.db_readers = Some(db_readers);
+        self
     }
 }
 
@@ -73,6 +95,21 @@ where
                     }),
                     pip: None,
                 },
+                latency: peer
+                    .latency
+                    .requests
+                    .iter()
+                    .map(|(id, latency)| {
+                        let latency = PeerRequestLatency {
+                            count: latency.count,
+                            mean_ms: latency.mean.as_millis() as u64,
+                            p50_ms: latency.p50.as_millis() as u64,
+                            p95_ms: latency.p95.as_millis() as u64,
+                            max_ms: latency.max.as_millis() as u64,
+                        };
+                        (format!("{id:?}"), latency)
+                    })
+                    .collect(),
             })
             .collect();
 
@@ -84,7 +121,9 @@ where
         let enr = self.network.local_node_record();
         let status = self.network.network_status().await.to_rpc_result()?;
 
-        Ok(NodeInfo::new(enr, status))
+        let mut info = NodeInfo::new(enr, status);
+        info.mode = self.node_mode.clone();
+        Ok(info)
     }
 
     /// Handler for `admin_peerEvents`
@@ -94,6 +133,19 @@ where
     ) -> jsonrpsee::core::SubscriptionResult {
         Err("admin_peerEvents is not implemented yet".into())
     }
+
+    /// Handler for `admin_dbReaders`
+    fn db_readers(&self) -> RpcResult<Vec<DbReaderInfo>> {
+        let readers = self.db_readers.as_ref().map(|db| db.readers()).unwrap_or_default();
+        Ok(readers
+            .into_iter()
+            .map(|reader| DbReaderInfo {
+                txn_id: reader.txn_id,
+                age_ms: reader.age.as_millis() as u64,
+                component: reader.component,
+            })
+            .collect())
+    }
 }
 
 impl<N> std::fmt::Debug for AdminApi<N> {
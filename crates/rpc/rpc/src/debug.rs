@@ -2,7 +2,8 @@ use crate::{
     eth::{
         error::{EthApiError, EthResult},
         revm_utils::{
-            clone_into_empty_db, inspect, inspect_and_return_db, prepare_call_env,
+            block_accounts, clone_into_empty_db, insert_preloaded_state, inspect,
+            inspect_and_return_db, pre_block_beacon_root_contract_call, prepare_call_env,
             replay_transactions_until, transact, EvmOverrides,
         },
         EthTransactions, TransactionSource,
@@ -22,7 +23,8 @@ use reth_primitives::{
     Address, Block, BlockId, BlockNumberOrTag, Bytes, TransactionSignedEcRecovered, B256,
 };
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProviderBox, TransactionVariant,
+    BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProvider, StateProviderBox,
+    TransactionVariant,
 };
 use reth_revm::{
     database::{StateProviderDatabase, SubState},
@@ -30,6 +32,7 @@ use reth_revm::{
         js::{JsDbRequest, JsInspector},
         FourByteInspector, TracingInspector, TracingInspectorConfig,
     },
+    witness::WitnessDatabase,
 };
 use reth_rpc_api::DebugApiServer;
 use reth_rpc_types::{
@@ -37,8 +40,9 @@ use reth_rpc_types::{
         BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
         GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, NoopFrame, TraceResult,
     },
-    BlockError, Bundle, CallRequest, RichBlock, StateContext,
+    BlockError, Bundle, CallRequest, ExecutionWitness, RichBlock, StateContext,
 };
+use reth_rpc_types_compat::proof::from_primitive_account_proof;
 use reth_tasks::TaskSpawner;
 use revm::{
     db::{CacheDB, EmptyDB},
@@ -98,7 +102,12 @@ where
             .eth_api
             .spawn_with_state_at_block(at, move |state| {
                 let mut results = Vec::with_capacity(transactions.len());
+
+                // load the state the block is expected to access in batches, instead of reading
+                // it key by key while tracing
+                let preloaded = state.preload(&block_accounts(&transactions, &block_env), &[])?;
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                insert_preloaded_state(&mut db, preloaded)?;
 
                 let mut transactions = transactions.into_iter().peekable();
                 while let Some(tx) = transactions.next() {
@@ -201,6 +210,89 @@ where
         .await
     }
 
+    /// Replays a block and returns the proofs and bytecodes of the state it accessed, against the
+    /// state of its parent block.
+    pub async fn debug_execution_witness(&self, block_id: BlockId) -> EthResult<ExecutionWitness> {
+        let block_hash = self
+            .inner
+            .provider
+            .block_hash_for_id(block_id)?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+
+        let ((cfg, block_env, _), block) = futures::try_join!(
+            self.inner.eth_api.evm_env_at(block_hash.into()),
+            self.inner.eth_api.block_by_id_with_senders(block_id),
+        )?;
+
+        let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        let state_at = block.parent_hash;
+        let block_number = block.number;
+        let parent_beacon_block_root = block.parent_beacon_block_root;
+        let chain_spec = self.inner.provider.chain_spec();
+        let state_root = self
+            .inner
+            .provider
+            .header(&state_at)?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?
+            .state_root;
+
+        // the block reward and the withdrawals change accounts outside of the transactions
+        let mut touched = block
+            .withdrawals
+            .iter()
+            .flatten()
+            .map(|withdrawal| withdrawal.address)
+            .collect::<Vec<_>>();
+        touched.push(block.beneficiary);
+        let transactions = block.into_transactions_ecrecovered().collect::<Vec<_>>();
+
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(state_at.into(), move |state| {
+                let mut db = CacheDB::new(WitnessDatabase::new(StateProviderDatabase::new(state)));
+                pre_block_beacon_root_contract_call(
+                    &mut db,
+                    chain_spec.as_ref(),
+                    block_number,
+                    &cfg,
+                    &block_env,
+                    parent_beacon_block_root,
+                )?;
+                for tx in transactions {
+                    let tx = tx_env_with_recovered(&tx);
+                    let env = Env { cfg: cfg.clone(), block: block_env.clone(), tx };
+                    let (res, _) = transact(&mut db, env)?;
+                    db.commit(res.state);
+                }
+
+                let (state, mut witness) = db.db.into_inner();
+                for address in touched {
+                    witness.accounts.entry(address).or_default();
+                }
+
+                let targets = witness
+                    .accounts
+                    .into_iter()
+                    .map(|(address, slots)| (address, slots.into_iter().collect::<Vec<_>>()))
+                    .collect::<Vec<_>>();
+                let accounts = state
+                    .0
+                    .proofs(&targets)?
+                    .into_iter()
+                    .map(from_primitive_account_proof)
+                    .collect();
+                let mut codes = Vec::with_capacity(witness.code_hashes.len());
+                for code_hash in witness.code_hashes {
+                    if let Some(code) = state.0.bytecode_by_hash(code_hash)? {
+                        codes.push(code.original_bytes());
+                    }
+                }
+
+                Ok(ExecutionWitness { state_root, accounts, codes })
+            })
+            .await
+    }
+
     /// Trace the transaction according to the provided options.
     ///
     /// Ref: <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers>
@@ -672,18 +764,7 @@ where
 {
     /// Handler for `debug_getRawHeader`
     async fn raw_header(&self, block_id: BlockId) -> RpcResult<Bytes> {
-        let header = match block_id {
-            BlockId::Hash(hash) => self.inner.provider.header(&hash.into()).to_rpc_result()?,
-            BlockId::Number(number_or_tag) => {
-                let number = self
-                    .inner
-                    .provider
-                    .convert_block_number(number_or_tag)
-                    .to_rpc_result()?
-                    .ok_or_else(|| internal_rpc_err("Pending block not supported".to_string()))?;
-                self.inner.provider.header_by_number(number).to_rpc_result()?
-            }
-        };
+        let header = self.inner.provider.header_by_id(block_id).to_rpc_result()?;
 
         let mut res = Vec::new();
         if let Some(header) = header {
@@ -823,6 +904,12 @@ where
         Ok(DebugApi::debug_trace_call_many(self, bundles, state_context, opts).await?)
     }
 
+    /// Handler for `debug_executionWitness`
+    async fn debug_execution_witness(&self, block_id: BlockId) -> RpcResult<ExecutionWitness> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(DebugApi::debug_execution_witness(self, block_id).await?)
+    }
+
     async fn debug_backtrace_at(&self, _location: &str) -> RpcResult<()> {
         Ok(())
     }
@@ -5,15 +5,16 @@ use std::sync::Arc;
 use crate::{
     eth::{
         api::transactions::build_transaction_receipt_with_block_receipts,
+        cache::BlockResponseKind,
         error::{EthApiError, EthResult},
     },
     EthApi,
 };
 use reth_network_api::NetworkInfo;
-use reth_primitives::{BlockId, TransactionMeta};
+use reth_primitives::{BlockId, TransactionMeta, B256};
 
 use reth_provider::{BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
-use reth_rpc_types::{Index, RichBlock, TransactionReceipt};
+use reth_rpc_types::{Block, Index, RichBlock, TransactionReceipt};
 
 use reth_rpc_types_compat::block::{from_block, uncle_block_from_header};
 use reth_transaction_pool::TransactionPool;
@@ -57,12 +58,53 @@ where
         Ok(uncle)
     }
 
+    /// Returns the hash of the block if it is finalized, so its responses can be cached.
+    pub(crate) fn finalized_block_hash(&self, block_id: BlockId) -> EthResult<Option<B256>> {
+        if block_id.is_pending() {
+            return Ok(None)
+        }
+        let Some(finalized) = self.provider().finalized_block_number()? else { return Ok(None) };
+        match self.provider().block_number_for_id(block_id)? {
+            Some(number) if number <= finalized => {
+                Ok(self.provider().block_hash_for_id(block_id)?)
+            }
+            _ => Ok(None),
+        }
+    }
+
     /// Returns all transaction receipts in the block.
     ///
-    /// Returns `None` if the block wasn't found.
+    /// Returns `None` if the block wasn't found. The receipts of finalized blocks are cached.
     pub(crate) async fn block_receipts(
         &self,
         block_id: BlockId,
+    ) -> EthResult<Option<Vec<TransactionReceipt>>> {
+        let finalized = self.finalized_block_hash(block_id)?;
+        if let Some(block_hash) = finalized {
+            if let Some(receipts) =
+                self.cache().finalized_responses().get(block_hash, BlockResponseKind::Receipts)
+            {
+                return Ok(Some(receipts))
+            }
+        }
+
+        let receipts = self.block_receipts_uncached(block_id).await?;
+
+        if let (Some(block_hash), Some(receipts)) = (finalized, &receipts) {
+            self.cache().finalized_responses().insert(
+                block_hash,
+                BlockResponseKind::Receipts,
+                receipts,
+            );
+        }
+        Ok(receipts)
+    }
+
+    /// Returns all transaction receipts in the block without consulting the cache of finalized
+    /// blocks.
+    async fn block_receipts_uncached(
+        &self,
+        block_id: BlockId,
     ) -> EthResult<Option<Vec<TransactionReceipt>>> {
         let mut block_and_receipts = None;
 
@@ -185,11 +227,22 @@ where
     ///
     /// If `full` is true, the block object will contain all transaction objects, otherwise it will
     /// only contain the transaction hashes.
+    ///
+    /// The block objects of finalized blocks are cached.
     pub(crate) async fn rpc_block(
         &self,
         block_id: impl Into<BlockId>,
         full: bool,
     ) -> EthResult<Option<RichBlock>> {
+        let block_id = block_id.into();
+        let kind = BlockResponseKind::Block { full };
+        let finalized = self.finalized_block_hash(block_id)?;
+        if let Some(block_hash) = finalized {
+            if let Some(block) = self.cache().finalized_responses().get::<Block>(block_hash, kind) {
+                return Ok(Some(block.into()))
+            }
+        }
+
         let block = match self.block_with_senders(block_id).await? {
             Some(block) => block,
             None => return Ok(None),
@@ -200,6 +253,10 @@ where
             .header_td_by_number(block.number)?
             .ok_or(EthApiError::UnknownBlockNumber)?;
         let block = from_block(block.unseal(), total_difficulty, full.into(), Some(block_hash))?;
+
+        if finalized.is_some() {
+            self.cache().finalized_responses().insert(block_hash, kind, &block);
+        }
         Ok(Some(block.into()))
     }
 }
@@ -6,7 +6,9 @@ use futures::{
     FutureExt, Stream, StreamExt,
 };
 use metrics::atomics::AtomicU64;
-use reth_primitives::{Receipt, SealedBlock, TransactionSigned, B256, U256};
+use reth_primitives::{
+    constants::eip4844::MAX_DATA_GAS_PER_BLOCK, Receipt, SealedBlock, TransactionSigned, B256, U256,
+};
 use reth_provider::{BlockReaderIdExt, CanonStateNotification, ChainSpecProvider};
 use reth_rpc_types::TxGasAndReward;
 use serde::{Deserialize, Serialize};
@@ -324,6 +326,10 @@ pub struct FeeHistoryEntry {
     pub base_fee_per_gas: u64,
     /// Gas used ratio this block.
     pub gas_used_ratio: f64,
+    /// The base fee per blob gas for this block, zero before EIP-4844.
+    pub base_fee_per_blob_gas: u128,
+    /// Blob gas used ratio of this block, zero before EIP-4844.
+    pub blob_gas_used_ratio: f64,
     /// Gas used by this block.
     pub gas_used: u64,
     /// Gas limit by this block.
@@ -342,6 +348,9 @@ impl FeeHistoryEntry {
         FeeHistoryEntry {
             base_fee_per_gas: block.base_fee_per_gas.unwrap_or_default(),
             gas_used_ratio: block.gas_used as f64 / block.gas_limit as f64,
+            base_fee_per_blob_gas: block.blob_fee().unwrap_or_default(),
+            blob_gas_used_ratio: block.blob_gas_used.unwrap_or_default() as f64 /
+                MAX_DATA_GAS_PER_BLOCK as f64,
             gas_used: block.gas_used,
             header_hash: block.hash,
             gas_limit: block.gas_limit,
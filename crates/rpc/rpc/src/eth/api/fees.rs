@@ -8,7 +8,10 @@ use crate::{
     EthApi,
 };
 use reth_network_api::NetworkInfo;
-use reth_primitives::{basefee::calculate_next_block_base_fee, BlockNumberOrTag, U256};
+use reth_primitives::{
+    basefee::calculate_next_block_base_fee, constants::eip4844::MAX_DATA_GAS_PER_BLOCK,
+    BlockNumberOrTag, U256,
+};
 use reth_provider::{BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
 use reth_rpc_types::FeeHistory;
 use reth_transaction_pool::TransactionPool;
@@ -107,6 +110,8 @@ where
         // Collect base fees, gas usage ratios and (optionally) reward percentile data
         let mut base_fee_per_gas: Vec<U256> = Vec::new();
         let mut gas_used_ratio: Vec<f64> = Vec::new();
+        let mut base_fee_per_blob_gas: Vec<U256> = Vec::new();
+        let mut blob_gas_used_ratio: Vec<f64> = Vec::new();
         let mut rewards: Vec<Vec<U256>> = Vec::new();
 
         // Check if the requested range is within the cache bounds
@@ -120,6 +125,8 @@ where
             for entry in &fee_entries {
                 base_fee_per_gas.push(U256::from(entry.base_fee_per_gas));
                 gas_used_ratio.push(entry.gas_used_ratio);
+                base_fee_per_blob_gas.push(U256::from(entry.base_fee_per_blob_gas));
+                blob_gas_used_ratio.push(entry.blob_gas_used_ratio);
 
                 if let Some(percentiles) = &reward_percentiles {
                     let mut block_rewards = Vec::with_capacity(percentiles.len());
@@ -131,11 +138,10 @@ where
             }
             let last_entry = fee_entries.last().expect("is not empty");
 
-            let last_entry_timestamp = self
-                .provider()
-                .header_by_hash_or_number(last_entry.header_hash.into())?
-                .map(|h| h.timestamp)
-                .unwrap_or_default();
+            let last_entry_header =
+                self.provider().header_by_hash_or_number(last_entry.header_hash.into())?;
+            let last_entry_timestamp =
+                last_entry_header.as_ref().map(|h| h.timestamp).unwrap_or_default();
 
             base_fee_per_gas.push(U256::from(calculate_next_block_base_fee(
                 last_entry.gas_used,
@@ -143,6 +149,9 @@ where
                 last_entry.base_fee_per_gas,
                 self.provider().chain_spec().base_fee_params(last_entry_timestamp),
             )));
+            base_fee_per_blob_gas.push(U256::from(
+                last_entry_header.and_then(|h| h.next_block_blob_fee()).unwrap_or_default(),
+            ));
         } else {
             // read the requested header range
             let headers = self.provider().sealed_headers_range(start_block..=end_block)?;
@@ -153,6 +162,10 @@ where
             for header in &headers {
                 base_fee_per_gas.push(U256::from(header.base_fee_per_gas.unwrap_or_default()));
                 gas_used_ratio.push(header.gas_used as f64 / header.gas_limit as f64);
+                base_fee_per_blob_gas.push(U256::from(header.blob_fee().unwrap_or_default()));
+                blob_gas_used_ratio.push(
+                    header.blob_gas_used.unwrap_or_default() as f64 / MAX_DATA_GAS_PER_BLOCK as f64,
+                );
 
                 // Percentiles were specified, so we need to collect reward percentile ino
                 if let Some(percentiles) = &reward_percentiles {
@@ -192,11 +205,15 @@ where
                 last_header.base_fee_per_gas.unwrap_or_default(),
                 self.provider().chain_spec().base_fee_params(last_header.timestamp),
             )));
+            base_fee_per_blob_gas
+                .push(U256::from(last_header.next_block_blob_fee().unwrap_or_default()));
         };
 
         Ok(FeeHistory {
             base_fee_per_gas,
             gas_used_ratio,
+            base_fee_per_blob_gas,
+            blob_gas_used_ratio,
             oldest_block: U256::from(start_block),
             reward: reward_percentiles.map(|_| rewards),
         })
@@ -9,21 +9,24 @@ use crate::eth::{
     cache::EthStateCache,
     error::{EthApiError, EthResult},
     gas_oracle::GasPriceOracle,
-    signer::EthSigner,
+    signer::{DevSigner, EthSigner, DEV_ACCOUNTS, DEV_MNEMONIC},
 };
 
 use async_trait::async_trait;
-use reth_interfaces::RethResult;
+use parking_lot::RwLock;
+use reth_interfaces::{db::set_reader_component, RethResult};
 use reth_network_api::NetworkInfo;
 use reth_primitives::{
     revm_primitives::{BlockEnv, CfgEnv},
+    stage::StageId,
     Address, BlockId, BlockNumberOrTag, ChainInfo, SealedBlockWithSenders, B256, U256, U64,
 };
 
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderBox, StateProviderFactory,
+    BlockReaderIdExt, BundleStateDataProvider, ChainSpecProvider, EvmEnvProvider,
+    StageCheckpointReader, StateProviderBox, StateProviderFactory,
 };
-use reth_rpc_types::{SyncInfo, SyncStatus};
+use reth_rpc_types::{StageInfo, SyncInfo, SyncStatus};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::TransactionPool;
 use std::{
@@ -146,6 +149,7 @@ where
             gas_oracle,
             gas_cap,
             starting_block: U256::from(latest_block),
+            started_at: Instant::now(),
             task_spawner,
             pending_block: Default::default(),
             blocking_task_pool,
@@ -157,6 +161,16 @@ where
         Self { inner: Arc::new(inner) }
     }
 
+    /// Adds the funded accounts of the dev chain to the signers, so `eth_accounts`,
+    /// `eth_sendTransaction` and `eth_sign` can be used without a keystore.
+    ///
+    /// The accounts are derived from [DEV_MNEMONIC].
+    pub fn with_dev_accounts(&self) {
+        let signer =
+            DevSigner::from_mnemonic(DEV_MNEMONIC, DEV_ACCOUNTS).expect("dev mnemonic is valid");
+        self.inner.signers.write().push(Arc::new(signer));
+    }
+
     /// Executes the future on a new blocking task.
     ///
     /// This accepts a closure that creates a new future using a clone of this type and spawns the
@@ -171,6 +185,7 @@ where
         let this = self.clone();
         let f = c(this);
         self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let _component = set_reader_component("rpc");
             let res = f.await;
             let _ = tx.send(res);
         }));
@@ -222,11 +237,46 @@ where
 {
     /// Returns the state at the given [BlockId] enum.
     ///
+    /// If this is [BlockNumberOrTag::Pending] and the CL did not provide a pending block, this
+    /// returns the state of the pending block that was built locally from the txpool, if any.
+    ///
     /// Note: if not [BlockNumberOrTag::Pending] then this will only return canonical state. See also <https://github.com/paradigmxyz/reth/issues/4515>
     pub fn state_at_block_id(&self, at: BlockId) -> EthResult<StateProviderBox> {
+        if at.is_pending() {
+            if let Some(state) = self.local_pending_state()? {
+                return Ok(state)
+            }
+        }
         Ok(self.provider().state_by_block_id(at)?)
     }
 
+    /// Returns the state of the locally built pending block if it is still up to date.
+    ///
+    /// This does not build a new pending block, see `local_pending_block`.
+    fn local_pending_state(&self) -> EthResult<Option<StateProviderBox>> {
+        if self.provider().pending_block_num_hash()?.is_some() {
+            // the CL provided a pending block
+            return Ok(None)
+        }
+
+        let state = {
+            // if the block is currently being rebuilt, fall back to the latest state
+            let Ok(lock) = self.inner.pending_block.try_lock() else { return Ok(None) };
+            match lock.as_ref() {
+                Some(pending) if Instant::now() <= pending.expires_at => pending.state_data(),
+                _ => return Ok(None),
+            }
+        };
+
+        // the pending block must extend the latest block
+        let latest = self.provider().latest_header()?;
+        if latest.map(|header| header.hash) != Some(state.canonical_fork().hash) {
+            return Ok(None)
+        }
+
+        Ok(Some(self.provider().pending_with_provider(Box::new(state))?))
+    }
+
     /// Returns the state at the given [BlockId] enum or the latest.
     ///
     /// Convenience function to interprets `None` as `BlockId::Number(BlockNumberOrTag::Latest)`
@@ -327,8 +377,8 @@ where
             }
 
             // we rebuild the block
-            let pending_block = match pending.build_block(this.provider(), this.pool()) {
-                Ok(block) => block,
+            let (pending_block, state) = match pending.build_block(this.provider(), this.pool()) {
+                Ok(built) => built,
                 Err(err) => {
                     tracing::debug!(target: "rpc", "Failed to build pending block: {:?}", err);
                     return Ok(None)
@@ -338,6 +388,7 @@ where
             let now = Instant::now();
             *lock = Some(PendingBlock {
                 block: pending_block.clone(),
+                state: Arc::new(state),
                 expires_at: now + Duration::from_secs(3),
             });
 
@@ -363,8 +414,12 @@ impl<Provider, Pool, Events> Clone for EthApi<Provider, Pool, Events> {
 impl<Provider, Pool, Network> EthApiSpec for EthApi<Provider, Pool, Network>
 where
     Pool: TransactionPool + Clone + 'static,
-    Provider:
-        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Provider: BlockReaderIdExt
+        + ChainSpecProvider
+        + StateProviderFactory
+        + EvmEnvProvider
+        + StageCheckpointReader
+        + 'static,
     Network: NetworkInfo + 'static,
 {
     /// Returns the current ethereum protocol version.
@@ -386,7 +441,7 @@ where
     }
 
     fn accounts(&self) -> Vec<Address> {
-        self.inner.signers.iter().flat_map(|s| s.accounts()).collect()
+        self.inner.signers.read().iter().flat_map(|s| s.accounts()).collect()
     }
 
     fn is_syncing(&self) -> bool {
@@ -394,17 +449,48 @@ where
     }
 
     /// Returns the [SyncStatus] of the network
+    ///
+    /// While syncing, this includes the checkpoint of each pipeline stage and an estimate of the
+    /// remaining sync time based on the average sync rate since the node started. The highest
+    /// block is estimated from the sync target requested by the consensus layer.
     fn sync_status(&self) -> RethResult<SyncStatus> {
         let status = if self.is_syncing() {
-            let current_block = U256::from(
-                self.provider().chain_info().map(|info| info.best_number).unwrap_or_default(),
-            );
+            let current_block =
+                self.provider().chain_info().map(|info| info.best_number).unwrap_or_default();
+
+            let mut highest_block = current_block;
+            let mut stages = Vec::with_capacity(StageId::ALL.len());
+            for (stage_id, checkpoint) in self.provider().get_stage_checkpoints()? {
+                highest_block = highest_block.max(checkpoint.block_number);
+                // the headers stage syncs to the block requested by the consensus layer, which is
+                // the best estimate of the network head
+                if let Some(headers) = checkpoint.headers_stage_checkpoint() {
+                    highest_block = highest_block.max(headers.block_range.to);
+                }
+                stages.push(StageInfo {
+                    stage_name: stage_id.to_string(),
+                    block_number: U64::from(checkpoint.block_number),
+                });
+            }
+
+            let starting_block = self.inner.starting_block.saturating_to::<u64>();
+            let synced_blocks = current_block.saturating_sub(starting_block);
+            let eta_seconds = (synced_blocks > 0).then(|| {
+                let remaining_blocks = highest_block - current_block;
+                U64::from(
+                    self.inner.started_at.elapsed().as_secs().saturating_mul(remaining_blocks) /
+                        synced_blocks,
+                )
+            });
+
             SyncStatus::Info(SyncInfo {
                 starting_block: self.inner.starting_block,
-                current_block,
-                highest_block: current_block,
+                current_block: U256::from(current_block),
+                highest_block: U256::from(highest_block),
                 warp_chunks_amount: None,
                 warp_chunks_processed: None,
+                stages: Some(stages),
+                eta_seconds,
             })
         } else {
             SyncStatus::None
@@ -451,7 +537,7 @@ struct EthApiInner<Provider, Pool, Network> {
     /// An interface to interact with the network
     network: Network,
     /// All configured Signers
-    signers: Vec<Box<dyn EthSigner>>,
+    signers: RwLock<Vec<Arc<dyn EthSigner>>>,
     /// The async cache frontend for eth related data
     eth_cache: EthStateCache,
     /// The async gas oracle frontend for gas price suggestions
@@ -460,6 +546,8 @@ struct EthApiInner<Provider, Pool, Network> {
     gas_cap: u64,
     /// The block number at which the node started
     starting_block: U256,
+    /// The time at which the node started
+    started_at: Instant,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
     /// Cached pending block if any
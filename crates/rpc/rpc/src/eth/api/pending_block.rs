@@ -1,6 +1,6 @@
 //! Support for building a pending block via local txpool.
 
-use crate::eth::error::{EthApiError, EthResult};
+use crate::eth::{error::EthResult, revm_utils::pre_block_beacon_root_contract_call};
 use reth_primitives::{
     constants::{eip4844::MAX_DATA_GAS_PER_BLOCK, BEACON_NONCE},
     proofs,
@@ -8,17 +8,19 @@ use reth_primitives::{
     revm_primitives::{
         BlockEnv, CfgEnv, EVMError, Env, InvalidTransaction, ResultAndState, SpecId,
     },
-    Block, BlockId, BlockNumberOrTag, ChainSpec, Header, IntoRecoveredTransaction, Receipt,
-    Receipts, SealedBlockWithSenders, SealedHeader, B256, EMPTY_OMMER_ROOT_HASH, U256,
+    Block, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag, Header,
+    IntoRecoveredTransaction, Receipt, Receipts, SealedBlockWithSenders, SealedHeader, B256,
+    EMPTY_OMMER_ROOT_HASH, U256,
+};
+use reth_provider::{
+    BundleStateDataProvider, BundleStateWithReceipts, ChainSpecProvider, StateProviderFactory,
 };
-use reth_provider::{BundleStateWithReceipts, ChainSpecProvider, StateProviderFactory};
 use reth_revm::{
-    database::StateProviderDatabase,
-    state_change::{apply_beacon_root_contract_call, post_block_withdrawals_balance_increments},
+    database::StateProviderDatabase, state_change::post_block_withdrawals_balance_increments,
 };
 use reth_transaction_pool::TransactionPool;
-use revm::{db::states::bundle_state::BundleRetention, Database, DatabaseCommit, State};
-use std::time::Instant;
+use revm::{db::states::bundle_state::BundleRetention, State};
+use std::{sync::Arc, time::Instant};
 
 /// Configured [BlockEnv] and [CfgEnv] for a pending block
 #[derive(Debug, Clone)]
@@ -34,6 +36,8 @@ pub(crate) struct PendingBlockEnv {
 impl PendingBlockEnv {
     /// Builds a pending block using the given client and pool.
     ///
+    /// Returns the block and the state changes of its transactions on top of the parent block.
+    ///
     /// If the origin is the actual pending block, the block is built with withdrawals.
     ///
     /// After Cancun, if the origin is the actual pending block, the block includes the EIP-4788 pre
@@ -42,7 +46,7 @@ impl PendingBlockEnv {
         self,
         client: &Client,
         pool: &Pool,
-    ) -> EthResult<SealedBlockWithSenders>
+    ) -> EthResult<(SealedBlockWithSenders, BundleStateWithReceipts)>
     where
         Client: StateProviderFactory + ChainSpecProvider,
         Pool: TransactionPool,
@@ -249,50 +253,10 @@ impl PendingBlockEnv {
 
         // seal the block
         let block = Block { header, body: executed_txs, ommers: vec![], withdrawals };
-        Ok(SealedBlockWithSenders { block: block.seal_slow(), senders })
+        Ok((SealedBlockWithSenders { block: block.seal_slow(), senders }, bundle))
     }
 }
 
-/// Apply the [EIP-4788](https://eips.ethereum.org/EIPS/eip-4788) pre block contract call.
-///
-/// This constructs a new [EVM](revm::EVM) with the given DB, and environment ([CfgEnv] and
-/// [BlockEnv]) to execute the pre block contract call.
-///
-/// This uses [apply_beacon_root_contract_call] to ultimately apply the beacon root contract state
-/// change.
-fn pre_block_beacon_root_contract_call<DB: Database + DatabaseCommit>(
-    db: &mut DB,
-    chain_spec: &ChainSpec,
-    block_number: u64,
-    initialized_cfg: &CfgEnv,
-    initialized_block_env: &BlockEnv,
-    parent_beacon_block_root: Option<B256>,
-) -> EthResult<()>
-where
-    DB::Error: std::fmt::Display,
-{
-    // Configure the environment for the block.
-    let env = Env {
-        cfg: initialized_cfg.clone(),
-        block: initialized_block_env.clone(),
-        ..Default::default()
-    };
-
-    // apply pre-block EIP-4788 contract call
-    let mut evm_pre_block = revm::EVM::with_env(env);
-    evm_pre_block.database(db);
-
-    // initialize a block from the env, because the pre block call needs the block itself
-    apply_beacon_root_contract_call(
-        chain_spec,
-        initialized_block_env.timestamp.to::<u64>(),
-        block_number,
-        parent_beacon_block_root,
-        &mut evm_pre_block,
-    )
-    .map_err(|err| EthApiError::Internal(err.into()))
-}
-
 /// The origin for a configured [PendingBlockEnv]
 #[derive(Clone, Debug)]
 pub(crate) enum PendingBlockEnvOrigin {
@@ -349,6 +313,43 @@ impl PendingBlockEnvOrigin {
 pub(crate) struct PendingBlock {
     /// The cached pending block
     pub(crate) block: SealedBlockWithSenders,
+    /// The state changes of the pending block
+    pub(crate) state: Arc<BundleStateWithReceipts>,
     /// Timestamp when the pending block is considered outdated
     pub(crate) expires_at: Instant,
 }
+
+impl PendingBlock {
+    /// Returns the state of the pending block on top of its parent.
+    pub(crate) fn state_data(&self) -> PendingBlockState {
+        PendingBlockState {
+            state: Arc::clone(&self.state),
+            parent: BlockNumHash::new(self.block.number - 1, self.block.parent_hash),
+        }
+    }
+}
+
+/// The state changes of a locally built pending block, used to create a state provider for the
+/// `pending` tag.
+#[derive(Debug, Clone)]
+pub(crate) struct PendingBlockState {
+    /// The state changes of the pending block.
+    state: Arc<BundleStateWithReceipts>,
+    /// The parent of the pending block, which is the latest block.
+    parent: BlockNumHash,
+}
+
+impl BundleStateDataProvider for PendingBlockState {
+    fn state(&self) -> &BundleStateWithReceipts {
+        &self.state
+    }
+
+    fn block_hash(&self, _block_number: BlockNumber) -> Option<BlockHash> {
+        // all ancestors of the pending block are canonical
+        None
+    }
+
+    fn canonical_fork(&self) -> BlockNumHash {
+        self.parent
+    }
+}
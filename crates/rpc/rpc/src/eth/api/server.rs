@@ -24,7 +24,7 @@ use reth_rpc_api::EthApiServer;
 use reth_rpc_types::{
     state::StateOverride, AccessListWithGasUsed, BlockOverrides, Bundle, CallRequest,
     EIP1186AccountProofResponse, EthCallResponse, FeeHistory, Index, RichBlock, StateContext,
-    SyncStatus, TransactionReceipt, TransactionRequest, Work,
+    SyncStatus, TransactionConditional, TransactionReceipt, TransactionRequest, Work,
 };
 use reth_transaction_pool::TransactionPool;
 use serde_json::Value;
@@ -354,6 +354,16 @@ where
         Ok(EthTransactions::send_raw_transaction(self, tx).await?)
     }
 
+    /// Handler for: `eth_sendRawTransactionConditional`
+    async fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        conditional: TransactionConditional,
+    ) -> Result<B256> {
+        trace!(target: "rpc::eth", ?tx, ?conditional, "Serving eth_sendRawTransactionConditional");
+        Ok(EthTransactions::send_raw_transaction_conditional(self, tx, conditional).await?)
+    }
+
     /// Handler for: `eth_sign`
     async fn sign(&self, address: Address, message: Bytes) -> Result<Bytes> {
         trace!(target: "rpc::eth", ?address, ?message, "Serving eth_sign");
@@ -680,6 +690,16 @@ mod tests {
             &fee_history.gas_used_ratio, &gas_used_ratios,
             "all: gas used ratio is incorrect"
         );
+        assert_eq!(
+            fee_history.base_fee_per_blob_gas,
+            vec![U256::ZERO; block_count as usize + 1],
+            "all: blocks before cancun have no blob base fee"
+        );
+        assert_eq!(
+            fee_history.blob_gas_used_ratio,
+            vec![0.; block_count as usize],
+            "all: blocks before cancun use no blob gas"
+        );
         assert_eq!(
             fee_history.oldest_block,
             U256::from(newest_block - block_count + 1),
@@ -10,7 +10,7 @@ use crate::{
 use alloy_dyn_abi::TypedData;
 use reth_primitives::{Address, Bytes};
 use serde_json::Value;
-use std::ops::Deref;
+use std::sync::Arc;
 
 impl<Provider, Pool, Network> EthApi<Provider, Pool, Network> {
     pub(crate) async fn sign(&self, account: Address, message: Bytes) -> EthResult<Bytes> {
@@ -27,15 +27,13 @@ impl<Provider, Pool, Network> EthApi<Provider, Pool, Network> {
         Ok(signature.to_hex_bytes())
     }
 
-    pub(crate) fn find_signer(
-        &self,
-        account: &Address,
-    ) -> Result<&(dyn EthSigner + 'static), SignError> {
+    pub(crate) fn find_signer(&self, account: &Address) -> Result<Arc<dyn EthSigner>, SignError> {
         self.inner
             .signers
+            .read()
             .iter()
             .find(|signer| signer.is_signer_for(account))
-            .map(|signer| signer.deref())
+            .cloned()
             .ok_or(SignError::NoAccount)
     }
 }
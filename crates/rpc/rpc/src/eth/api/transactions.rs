@@ -31,11 +31,14 @@ use reth_revm::{
     tracing::{TracingInspector, TracingInspectorConfig},
 };
 use reth_rpc_types::{
-    CallRequest, Index, Log, Transaction, TransactionInfo, TransactionReceipt, TransactionRequest,
-    TypedTransactionRequest,
+    CallRequest, Index, Log, Transaction, TransactionConditional, TransactionInfo,
+    TransactionReceipt, TransactionRequest, TypedTransactionRequest,
 };
 use reth_rpc_types_compat::transaction::from_recovered_with_block_context;
-use reth_transaction_pool::{TransactionOrigin, TransactionPool};
+use reth_transaction_pool::{
+    validate::{validate_conditional, ConditionalError},
+    PoolTransaction, TransactionOrigin, TransactionPool,
+};
 use revm::{
     db::CacheDB,
     primitives::{BlockEnv, CfgEnv},
@@ -151,6 +154,17 @@ pub trait EthTransactions: Send + Sync {
     /// Returns the hash of the transaction.
     async fn send_raw_transaction(&self, tx: Bytes) -> EthResult<B256>;
 
+    /// Decodes and recovers the transaction, checks that the next block satisfies the
+    /// [TransactionConditional] and submits the transaction to the pool, which only includes it in
+    /// blocks that satisfy the conditions.
+    ///
+    /// Returns the hash of the transaction.
+    async fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        conditional: TransactionConditional,
+    ) -> EthResult<B256>;
+
     /// Signs transaction with a matching signer, if any and submits the transaction to the pool.
     /// Returns the hash of the signed transaction.
     async fn send_transaction(&self, request: TransactionRequest) -> EthResult<B256>;
@@ -343,6 +357,11 @@ where
     async fn evm_env_at(&self, at: BlockId) -> EthResult<(CfgEnv, BlockEnv, BlockId)> {
         if at.is_pending() {
             let PendingBlockEnv { cfg, block_env, origin } = self.pending_block_env_and_cfg()?;
+            // use the state of the locally built pending block if there's no pending block from
+            // the CL
+            if !origin.is_actual_pending() && self.local_pending_block().await?.is_some() {
+                return Ok((cfg, block_env, BlockNumberOrTag::Pending.into()))
+            }
             Ok((cfg, block_env, origin.state_block_id()))
         } else {
             //  Use cached values if there is no pending block
@@ -481,21 +500,34 @@ where
                     None => return Ok(None),
                 };
 
+                // The derived fields only depend on the receipts of the block up to the
+                // transaction, so only these are loaded instead of all receipts of the block.
+                #[cfg(not(feature = "optimism"))]
+                if let Some(tx_id) = this.provider().transaction_id(hash)? {
+                    let first_tx_id = tx_id - meta.index;
+                    let mut receipts = this.provider().receipts_by_tx_range(first_tx_id..=tx_id)?;
+                    // fewer receipts are returned if some of them are pruned
+                    if receipts.len() as u64 == meta.index + 1 {
+                        let receipt = receipts.pop().expect("not empty");
+                        return Ok(Some((tx, meta, receipt, Some(receipts))))
+                    }
+                }
+
                 let receipt = match this.provider().receipt_by_hash(hash)? {
                     Some(recpt) => recpt,
                     None => return Ok(None),
                 };
 
-                Ok(Some((tx, meta, receipt)))
+                Ok(Some((tx, meta, receipt, None)))
             })
             .await?;
 
-        let (tx, meta, receipt) = match result {
-            Some((tx, meta, receipt)) => (tx, meta, receipt),
+        let (tx, meta, receipt, prev_receipts) = match result {
+            Some((tx, meta, receipt, prev_receipts)) => (tx, meta, receipt, prev_receipts),
             None => return Ok(None),
         };
 
-        self.build_transaction_receipt(tx, meta, receipt).await.map(Some)
+        self.build_transaction_receipt(tx, meta, receipt, prev_receipts).await.map(Some)
     }
 
     async fn send_raw_transaction(&self, tx: Bytes) -> EthResult<B256> {
@@ -513,6 +545,40 @@ where
         Ok(hash)
     }
 
+    async fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        conditional: TransactionConditional,
+    ) -> EthResult<B256> {
+        let recovered = recover_raw_transaction(tx)?;
+
+        // the conditions are checked against the latest state, because storage roots aren't
+        // available for the state of the pending block
+        let PendingBlockEnv { block_env, .. } = self.pending_block_env_and_cfg()?;
+        let state = self.latest_state()?;
+        validate_conditional(
+            &conditional,
+            block_env.number.saturating_to(),
+            block_env.timestamp.saturating_to(),
+            &*state,
+        )
+        .map_err(|err| match err {
+            ConditionalError::Provider(err) => err.into(),
+            err => EthApiError::TransactionConditionalNotMet(err),
+        })?;
+
+        let pool_transaction = <Pool::Transaction>::from_recovered_pooled_transaction(recovered)
+            .with_conditional(conditional)
+            .ok_or(EthApiError::Unsupported("transaction conditionals are not supported"))?;
+
+        // submit the transaction to the pool with a `Private` origin, so it isn't propagated to
+        // peers that would include it without checking the conditions
+        let hash =
+            self.pool().add_transaction(TransactionOrigin::Private, pool_transaction).await?;
+
+        Ok(hash)
+    }
+
     async fn send_transaction(&self, mut request: TransactionRequest) -> EthResult<B256> {
         let from = match request.from {
             Some(from) => from,
@@ -897,14 +963,20 @@ where
 {
     /// Helper function for `eth_getTransactionReceipt`
     ///
-    /// Returns the receipt
+    /// Returns the receipt. The receipts of the block before the transaction are fetched from the
+    /// cache if they aren't given.
     #[cfg(not(feature = "optimism"))]
     pub(crate) async fn build_transaction_receipt(
         &self,
         tx: TransactionSigned,
         meta: TransactionMeta,
         receipt: Receipt,
+        prev_receipts: Option<Vec<Receipt>>,
     ) -> EthResult<TransactionReceipt> {
+        if let Some(prev_receipts) = prev_receipts {
+            return build_transaction_receipt_with_block_receipts(tx, meta, receipt, &prev_receipts)
+        }
+
         // get all receipts for the block
         let all_receipts = match self.cache().get_receipts(meta.block_hash).await? {
             Some(recpts) => recpts,
@@ -922,6 +994,7 @@ where
         tx: TransactionSigned,
         meta: TransactionMeta,
         receipt: Receipt,
+        _prev_receipts: Option<Vec<Receipt>>,
     ) -> EthResult<TransactionReceipt> {
         let (block, receipts) = self
             .cache()
@@ -1027,7 +1100,7 @@ where
         from: &Address,
         request: TypedTransactionRequest,
     ) -> EthResult<TransactionSigned> {
-        for signer in self.inner.signers.iter() {
+        for signer in self.inner.signers.read().iter() {
             if signer.is_signer_for(from) {
                 return match signer.sign_transaction(request, from) {
                     Ok(tx) => Ok(tx),
@@ -1263,9 +1336,15 @@ mod tests {
         BlockingTaskPool, EthApi,
     };
     use reth_network_api::noop::NoopNetwork;
-    use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, hex_literal::hex, Bytes};
-    use reth_provider::test_utils::NoopProvider;
-    use reth_transaction_pool::{test_utils::testing_pool, TransactionPool};
+    use reth_primitives::{
+        constants::ETHEREUM_BLOCK_GAS_LIMIT, hex_literal::hex, proofs, Bytes, KnownAccount, MAINNET,
+    };
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider, NoopProvider};
+    use reth_transaction_pool::{
+        blobstore::InMemoryBlobStore, test_utils::testing_pool,
+        validate::EthTransactionValidatorBuilder, CoinbaseTipOrdering, Pool, TransactionPool,
+    };
+    use std::collections::BTreeMap;
 
     #[tokio::test]
     async fn send_raw_transaction() {
@@ -1313,4 +1392,62 @@ mod tests {
         assert!(pool.get(&tx_1_result).is_some(), "tx1 not found in the pool");
         assert!(pool.get(&tx_2_result).is_some(), "tx2 not found in the pool");
     }
+
+    #[tokio::test]
+    async fn send_raw_transaction_conditional() {
+        let provider = MockEthProvider::default();
+        provider.add_header(B256::random(), Header { number: 1, ..Default::default() });
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+        let contract = Address::with_last_byte(1);
+        let storage = vec![(B256::with_last_byte(1), U256::from(2))];
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO).extend_storage(storage.clone()),
+        );
+
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider.clone(), blob_store.clone());
+        let pool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            pool.clone(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+        );
+
+        let tx = Bytes::from(hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507"));
+
+        // the storage root of the contract doesn't match
+        let conditional = TransactionConditional {
+            known_accounts: BTreeMap::from([(contract, KnownAccount::StorageRoot(B256::ZERO))]),
+            ..Default::default()
+        };
+        let err = eth_api.send_raw_transaction_conditional(tx.clone(), conditional).await;
+        assert!(matches!(
+            err,
+            Err(EthApiError::TransactionConditionalNotMet(ConditionalError::StorageRootMismatch(
+                address
+            ))) if address == contract
+        ));
+        assert!(pool.is_empty());
+
+        let root = proofs::storage_root_unhashed(storage);
+        let conditional = TransactionConditional {
+            known_accounts: BTreeMap::from([(contract, KnownAccount::StorageRoot(root))]),
+            ..Default::default()
+        };
+        let hash = eth_api.send_raw_transaction_conditional(tx, conditional.clone()).await.unwrap();
+        let pooled = pool.get(&hash).expect("transaction not found in the pool");
+        assert_eq!(pooled.transaction.conditional(), Some(&conditional));
+        assert_eq!(pooled.origin, TransactionOrigin::Private);
+    }
 }
@@ -7,13 +7,14 @@ use crate::{
         utils::recover_raw_transaction,
         EthTransactions,
     },
+    result::internal_rpc_err,
     BlockingTaskGuard,
 };
 use jsonrpsee::core::RpcResult;
 use reth_primitives::{
     keccak256,
     revm_primitives::db::{DatabaseCommit, DatabaseRef},
-    U256,
+    B256, U256,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_api::EthCallBundleApiServer;
@@ -23,6 +24,7 @@ use revm::{
     primitives::{Env, ResultAndState, TxEnv},
 };
 use std::sync::Arc;
+use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
 /// `Eth` bundle implementation.
 pub struct EthBundle<Eth> {
@@ -41,12 +43,26 @@ impl<Eth> EthBundle<Eth>
 where
     Eth: EthTransactions + 'static,
 {
+    /// Acquires a permit to simulate a bundle.
+    async fn acquire_bundle_permit(&self) -> Result<OwnedSemaphorePermit, AcquireError> {
+        self.inner.blocking_task_guard.clone().acquire_owned().await
+    }
+
     /// Simulates a bundle of transactions at the top of a given block number with the state of
     /// another (or the same) block. This can be used to simulate future blocks with the current
     /// state, or it can be used to simulate a past block. The sender is responsible for signing the
     /// transactions and using the correct nonce and ensuring validity
     pub async fn call_bundle(&self, bundle: EthCallBundle) -> EthResult<EthCallBundleResponse> {
-        let EthCallBundle { txs, block_number, state_block_number, timestamp } = bundle;
+        let EthCallBundle {
+            txs,
+            block_number,
+            state_block_number,
+            timestamp,
+            coinbase,
+            gas_limit,
+            difficulty,
+            base_fee,
+        } = bundle;
         if txs.is_empty() {
             return Err(EthApiError::InvalidParams(
                 EthBundleError::EmptyBundleTransactions.to_string(),
@@ -74,11 +90,31 @@ where
         // use the block number of the request
         block_env.number = U256::from(block_number);
 
+        // apply the remaining block overrides of the request
+        if let Some(coinbase) = coinbase {
+            block_env.coinbase = coinbase;
+        }
+        if let Some(gas_limit) = gas_limit {
+            block_env.gas_limit = U256::from(gas_limit);
+        }
+        if let Some(difficulty) = difficulty {
+            block_env.difficulty = difficulty;
+        }
+        if let Some(base_fee) = base_fee {
+            block_env.basefee = base_fee;
+        }
+        let basefee = u64::try_from(block_env.basefee).map_err(|_| {
+            EthApiError::InvalidParams(
+                EthBundleError::BaseFeeTooHigh(block_env.basefee).to_string(),
+            )
+        })?;
+
         self.inner
             .eth_api
             .spawn_with_state_at_block(at, move |state| {
                 let coinbase = block_env.coinbase;
-                let basefee = Some(block_env.basefee.to::<u64>());
+                let block_gas_limit = block_env.gas_limit.saturating_to::<u64>();
+                let basefee = Some(basefee);
                 let env = Env { cfg, block: block_env, tx: TxEnv::default() };
                 let db = CacheDB::new(StateProviderDatabase::new(state));
 
@@ -100,6 +136,17 @@ where
                 while let Some(tx) = transactions.next() {
                     let tx = tx.into_ecrecovered_transaction();
                     hash_bytes.extend_from_slice(tx.hash().as_slice());
+
+                    // the bundle must fit into the block
+                    if total_gas_used.saturating_add(tx.gas_limit()) > block_gas_limit {
+                        return Err(EthApiError::InvalidParams(
+                            EthBundleError::BundleGasLimitExceeded {
+                                tx_hash: tx.hash(),
+                                block_gas_limit,
+                            }
+                            .to_string(),
+                        ))
+                    }
                     let gas_price = tx
                         .effective_tip_per_gas(basefee)
                         .ok_or_else(|| RpcInvalidTransactionError::FeeCapTooLow)?;
@@ -107,7 +154,7 @@ where
                     let ResultAndState { result, state } = evm.transact()?;
 
                     let gas_used = result.gas_used();
-                    total_gas_used += gas_used;
+                    total_gas_used = total_gas_used.saturating_add(gas_used);
 
                     let gas_fees = U256::from(gas_used) * U256::from(gas_price);
                     total_gas_fess += gas_fees;
@@ -183,6 +230,8 @@ where
     Eth: EthTransactions + 'static,
 {
     async fn call_bundle(&self, request: EthCallBundle) -> RpcResult<EthCallBundleResponse> {
+        let _permit =
+            self.acquire_bundle_permit().await.map_err(|err| internal_rpc_err(err.to_string()))?;
         Ok(EthBundle::call_bundle(self, request).await?)
     }
 }
@@ -192,8 +241,7 @@ where
 struct EthBundleInner<Eth> {
     /// Access to commonly used code of the `eth` namespace
     eth_api: Eth,
-    // restrict the number of concurrent tracing calls.
-    #[allow(dead_code)]
+    // restrict the number of concurrent bundle simulations.
     blocking_task_guard: BlockingTaskGuard,
 }
 
@@ -218,4 +266,15 @@ pub enum EthBundleError {
     /// Thrown if the bundle does not contain a block number, or block number is 0.
     #[error("bundle missing blockNumber")]
     BundleMissingBlockNumber,
+    /// Thrown if a transaction of the bundle does not fit into the remaining block gas.
+    #[error("transaction {tx_hash} exceeds the block gas limit of {block_gas_limit}")]
+    BundleGasLimitExceeded {
+        /// Hash of the transaction that does not fit.
+        tx_hash: B256,
+        /// Gas limit of the simulated block.
+        block_gas_limit: u64,
+    },
+    /// Thrown if the base fee of the bundle does not fit into 64 bits.
+    #[error("base fee {0} exceeds the maximum of 2^64-1")]
+    BaseFeeTooHigh(U256),
 }
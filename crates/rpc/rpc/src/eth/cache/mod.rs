@@ -32,6 +32,11 @@ mod metrics;
 mod multi_consumer;
 pub use multi_consumer::MultiConsumerLruCache;
 
+mod responses;
+pub use responses::{
+    BlockResponseKind, FinalizedResponseCache, DEFAULT_MAX_FINALIZED_RESPONSES_SIZE,
+};
+
 /// The type that can send the response to a requested [Block]
 type BlockTransactionsResponseSender =
     oneshot::Sender<ProviderResult<Option<Vec<TransactionSigned>>>>;
@@ -64,6 +69,8 @@ type EnvLruCache<L> = MultiConsumerLruCache<B256, (CfgEnv, BlockEnv), L, EnvResp
 #[derive(Debug, Clone)]
 pub struct EthStateCache {
     to_service: UnboundedSender<CacheAction>,
+    /// The serialized responses of finalized blocks, shared by the RPC handlers.
+    finalized_responses: FinalizedResponseCache,
 }
 
 impl EthStateCache {
@@ -87,7 +94,7 @@ impl EthStateCache {
             action_task_spawner,
             rate_limiter: Arc::new(Semaphore::new(max_concurrent_db_operations)),
         };
-        let cache = EthStateCache { to_service };
+        let cache = EthStateCache { to_service, finalized_responses: Default::default() };
         (cache, service)
     }
 
@@ -129,6 +136,11 @@ impl EthStateCache {
         this
     }
 
+    /// Returns the cache of the serialized RPC responses of finalized blocks.
+    pub fn finalized_responses(&self) -> &FinalizedResponseCache {
+        &self.finalized_responses
+    }
+
     /// Requests the [Block] for the block hash
     ///
     /// Returns `None` if the block does not exist.
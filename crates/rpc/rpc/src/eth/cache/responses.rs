@@ -0,0 +1,123 @@
+//! Cache of the serialized RPC responses of finalized blocks.
+
+use parking_lot::Mutex;
+use reth_primitives::B256;
+use schnellru::{LruMap, Unlimited};
+use serde::{de::DeserializeOwned, Serialize};
+use std::sync::Arc;
+
+/// The default maximum size of the cached responses, in bytes of their JSON encoding.
+pub const DEFAULT_MAX_FINALIZED_RESPONSES_SIZE: usize = 64 * 1024 * 1024;
+
+/// The kind of a cached response of a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlockResponseKind {
+    /// `eth_getBlockByHash` and `eth_getBlockByNumber`, with the full transactions or not.
+    Block {
+        /// Whether the block contains the full transactions.
+        full: bool,
+    },
+    /// `eth_getBlockReceipts`.
+    Receipts,
+    /// `trace_block`.
+    Traces,
+    /// `trace_replayBlockTransactions`, with the requested trace types as a bit mask.
+    ReplayTraces(u8),
+}
+
+/// An LRU cache of the serialized RPC responses of finalized blocks, keyed by the block hash and
+/// bounded by the total size of the responses.
+///
+/// Finalized blocks can't be reorged, so the entries never have to be invalidated. The responses
+/// are stored as JSON, which is also what their size is measured by.
+#[derive(Debug, Clone)]
+pub struct FinalizedResponseCache {
+    inner: Arc<Mutex<FinalizedResponses>>,
+}
+
+impl FinalizedResponseCache {
+    /// Creates a new cache with the given maximum size of the responses, in bytes.
+    pub fn new(max_size: usize) -> Self {
+        let inner = FinalizedResponses { entries: LruMap::new(Unlimited), size: 0, max_size };
+        Self { inner: Arc::new(Mutex::new(inner)) }
+    }
+
+    /// Returns the cached response of the block and marks it as the most recently used.
+    pub fn get<T: DeserializeOwned>(&self, block_hash: B256, kind: BlockResponseKind) -> Option<T> {
+        let json = self.inner.lock().entries.get(&(block_hash, kind)).cloned()?;
+        serde_json::from_slice(&json).ok()
+    }
+
+    /// Caches the response of the block, evicting the least recently used responses until the
+    /// cache fits into its maximum size. Responses larger than the maximum size aren't cached.
+    pub fn insert<T: Serialize>(&self, block_hash: B256, kind: BlockResponseKind, response: &T) {
+        let Ok(json) = serde_json::to_vec(response) else { return };
+        self.inner.lock().insert((block_hash, kind), json.into());
+    }
+}
+
+impl Default for FinalizedResponseCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_FINALIZED_RESPONSES_SIZE)
+    }
+}
+
+/// The entries of the [FinalizedResponseCache].
+#[derive(Debug)]
+struct FinalizedResponses {
+    /// The JSON encoded responses.
+    entries: LruMap<(B256, BlockResponseKind), Arc<[u8]>, Unlimited>,
+    /// The total size of the cached responses.
+    size: usize,
+    /// The maximum total size of the cached responses.
+    max_size: usize,
+}
+
+impl FinalizedResponses {
+    fn insert(&mut self, key: (B256, BlockResponseKind), json: Arc<[u8]>) {
+        if json.len() > self.max_size {
+            return
+        }
+        if let Some(previous) = self.entries.remove(&key) {
+            self.size -= previous.len();
+        }
+        while self.size + json.len() > self.max_size {
+            let Some((_, evicted)) = self.entries.pop_oldest() else { break };
+            self.size -= evicted.len();
+        }
+        self.size += json.len();
+        self.entries.insert(key, json);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finalized_response_cache_evicts_by_size() {
+        let response = |len: usize| vec![0u8; len];
+        let size = serde_json::to_vec(&response(4)).unwrap().len();
+        let cache = FinalizedResponseCache::new(2 * size);
+        let kind = BlockResponseKind::Receipts;
+
+        cache.insert(B256::with_last_byte(1), kind, &response(4));
+        cache.insert(B256::with_last_byte(2), kind, &response(4));
+        assert_eq!(cache.inner.lock().size, 2 * size);
+
+        // the least recently used response is evicted
+        assert_eq!(cache.get::<Vec<u8>>(B256::with_last_byte(1), kind), Some(response(4)));
+        cache.insert(B256::with_last_byte(3), kind, &response(4));
+        assert!(cache.get::<Vec<u8>>(B256::with_last_byte(1), kind).is_some());
+        assert!(cache.get::<Vec<u8>>(B256::with_last_byte(2), kind).is_none());
+        assert_eq!(cache.inner.lock().size, 2 * size);
+
+        // responses of other kinds are cached separately
+        assert!(cache.get::<Vec<u8>>(B256::with_last_byte(1), BlockResponseKind::Traces).is_none());
+
+        // responses larger than the cache aren't cached
+        cache.insert(B256::with_last_byte(4), kind, &response(12));
+        assert!(cache.get::<Vec<u8>>(B256::with_last_byte(4), kind).is_none());
+        assert_eq!(cache.inner.lock().size, 2 * size);
+    }
+}
@@ -10,9 +10,12 @@ use reth_interfaces::RethError;
 use reth_primitives::{revm_primitives::InvalidHeader, Address, Bytes, U256};
 use reth_revm::tracing::js::JsInspectorError;
 use reth_rpc_types::{error::EthRpcErrorCode, BlockError, CallInputError};
-use reth_transaction_pool::error::{
-    Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind,
-    PoolTransactionError,
+use reth_transaction_pool::{
+    error::{
+        Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind,
+        PoolTransactionError,
+    },
+    validate::ConditionalError,
 };
 use revm::primitives::{EVMError, ExecutionResult, Halt, OutOfGasError};
 use std::time::Duration;
@@ -100,6 +103,10 @@ pub enum EthApiError {
     InternalJsTracerError(String),
     #[error(transparent)]
     CallInputError(#[from] CallInputError),
+    /// Thrown if the next block doesn't satisfy the conditions of a transaction submitted with
+    /// `eth_sendRawTransactionConditional`
+    #[error("transaction conditional not met: {0}")]
+    TransactionConditionalNotMet(ConditionalError),
     /// Optimism related error
     #[error(transparent)]
     #[cfg(feature = "optimism")]
@@ -161,6 +168,9 @@ impl From<EthApiError> for ErrorObject<'static> {
             err @ EthApiError::InternalBlockingTaskError => internal_rpc_err(err.to_string()),
             err @ EthApiError::InternalEthError => internal_rpc_err(err.to_string()),
             err @ EthApiError::CallInputError(_) => invalid_params_rpc_err(err.to_string()),
+            err @ EthApiError::TransactionConditionalNotMet(_) => {
+                rpc_error_with_code(EthRpcErrorCode::TransactionRejected.code(), err.to_string())
+            }
             #[cfg(feature = "optimism")]
             EthApiError::Optimism(err) => match err {
                 OptimismEthApiError::HyperError(err) => internal_rpc_err(err.to_string()),
@@ -10,13 +10,14 @@ use crate::{
 use core::fmt;
 
 use async_trait::async_trait;
+use futures::StreamExt;
 use jsonrpsee::{core::RpcResult, server::IdProvider};
-use reth_primitives::{IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, ProviderError};
+use reth_primitives::{BlockNumber, IntoRecoveredTransaction, TxHash, U64};
+use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, LogsBloomLevel, ProviderError};
 use reth_rpc_api::EthFilterApiServer;
 use reth_rpc_types::{
-    BlockNumHash, Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams, Log,
-    PendingTransactionFilterKind,
+    BlockNumHash, BloomFilter, Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams,
+    Log, PendingTransactionFilterKind,
 };
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
@@ -28,7 +29,7 @@ use std::{
     time::{Duration, Instant},
 };
 use tokio::{
-    sync::{mpsc::Receiver, Mutex},
+    sync::{mpsc::Receiver, oneshot, Mutex},
     time::MissedTickBehavior,
 };
 use tracing::trace;
@@ -36,6 +37,14 @@ use tracing::trace;
 /// The maximum number of headers we read at once when handling a range filter.
 const MAX_HEADERS_RANGE: u64 = 1_000; // with ~530bytes per header this is ~500kb
 
+/// The number of blocks of a range filter that are processed together on one blocking task.
+///
+/// This is a multiple of the smallest range of the logs bloom index.
+const LOGS_CHUNK_SIZE: u64 = 4 * LogsBloomLevel::Blocks4K.range_size();
+
+/// The default maximum number of chunks of a range filter that are processed concurrently.
+pub const DEFAULT_MAX_CONCURRENT_LOGS_CHUNKS: usize = 4;
+
 /// `Eth` filter RPC implementation.
 pub struct EthFilter<Provider, Pool> {
     /// All nested fields bundled together
@@ -62,8 +71,13 @@ where
         config: EthFilterConfig,
         task_spawner: Box<dyn TaskSpawner>,
     ) -> Self {
-        let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
-            config;
+        let EthFilterConfig {
+            max_blocks_per_filter,
+            max_logs_per_response,
+            stale_filter_ttl,
+            max_concurrent_logs_chunks,
+            logs_query_timeout,
+        } = config;
         let inner = EthFilterInner {
             provider,
             active_filters: Default::default(),
@@ -76,6 +90,8 @@ where
             // if not set, use the max value, which is effectively no limit
             max_blocks_per_filter: max_blocks_per_filter.unwrap_or(u64::MAX),
             max_logs_per_response: max_logs_per_response.unwrap_or(usize::MAX),
+            max_concurrent_logs_chunks: max_concurrent_logs_chunks.max(1),
+            logs_query_timeout,
         };
 
         let eth_filter = Self { inner: Arc::new(inner) };
@@ -330,6 +346,10 @@ struct EthFilterInner<Provider, Pool> {
     max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a response
     max_logs_per_response: usize,
+    /// Maximum number of chunks of a range filter that are processed concurrently
+    max_concurrent_logs_chunks: usize,
+    /// Maximum duration of a range filter query
+    logs_query_timeout: Option<Duration>,
     /// The async cache frontend for eth related data
     eth_cache: EthStateCache,
     /// maximum number of headers to read at once for range filter
@@ -346,7 +366,7 @@ where
     Pool: TransactionPool + 'static,
 {
     /// Returns logs matching given filter object.
-    async fn logs_for_filter(&self, filter: Filter) -> Result<Vec<Log>, FilterError> {
+    async fn logs_for_filter(self: &Arc<Self>, filter: Filter) -> Result<Vec<Log>, FilterError> {
         match filter.block_option {
             FilterBlockOption::AtBlockHash(block_hash) => {
                 let mut all_logs = Vec::new();
@@ -405,11 +425,17 @@ where
 
     /// Returns all logs in the given _inclusive_ range that match the filter
     ///
+    /// The range is split into chunks that are processed concurrently on the blocking pool, and
+    /// their logs are collected in order.
+    ///
     /// Returns an error if:
     ///  - underlying database error
     ///  - amount of matches exceeds configured limit
+    ///  - the query takes longer than the configured timeout
+    ///
+    /// The limit errors contain a narrower range that can be queried instead.
     async fn get_logs_in_block_range(
-        &self,
+        self: &Arc<Self>,
         filter: &Filter,
         from_block: u64,
         to_block: u64,
@@ -420,59 +446,175 @@ where
             return Err(FilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter))
         }
 
+        let deadline = self.logs_query_timeout.map(|timeout| Instant::now() + timeout);
+        // size check but only if range is multiple blocks, so we always return all logs of a
+        // single block
+        let max_logs = if from_block == to_block { usize::MAX } else { self.max_logs_per_response };
+
+        // the chunks are aligned to their size, so they consist of complete indexed ranges
+        let chunks = (from_block / LOGS_CHUNK_SIZE..=to_block / LOGS_CHUNK_SIZE).map(|index| {
+            let start = (index * LOGS_CHUNK_SIZE).max(from_block);
+            let end = (index * LOGS_CHUNK_SIZE + LOGS_CHUNK_SIZE - 1).min(to_block);
+            (start, end)
+        });
+        let mut chunks = futures::stream::iter(chunks)
+            .map(|(from, to)| {
+                self.logs_in_chunk_on_blocking_task(filter, from, to, max_logs, deadline)
+            })
+            .buffered(self.max_concurrent_logs_chunks);
+
         let mut all_logs = Vec::new();
-        let filter_params = FilteredParams::new(Some(filter.clone()));
+        while let Some(chunk) = chunks.next().await {
+            let LogsChunk { logs, timed_out_at } = chunk?;
+            all_logs.extend(logs);
+
+            if all_logs.len() > max_logs {
+                // the logs of the blocks before the first log over the limit fit into a response
+                let excess_block = all_logs[max_logs].block_number.unwrap_or_default().to::<u64>();
+                return Err(FilterError::QueryExceedsMaxResults {
+                    max_logs,
+                    suggested_range: from_block..=excess_block.saturating_sub(1).max(from_block),
+                })
+            }
+            if let Some(block) = timed_out_at {
+                return Err(FilterError::QueryTimeout {
+                    suggested_range: from_block..=block.saturating_sub(1).max(from_block),
+                })
+            }
+        }
+
+        Ok(all_logs)
+    }
+
+    /// Returns the logs in the given _inclusive_ chunk of a range that match the filter, see
+    /// [Self::logs_in_chunk].
+    ///
+    /// The chunk is processed on a new blocking task.
+    async fn logs_in_chunk_on_blocking_task(
+        self: &Arc<Self>,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+        max_logs: usize,
+        deadline: Option<Instant>,
+    ) -> Result<LogsChunk, FilterError> {
+        let (tx, rx) = oneshot::channel();
+        let this = Arc::clone(self);
+        let filter = filter.clone();
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let res = this.logs_in_chunk(filter, from_block, to_block, max_logs, deadline).await;
+            let _ = tx.send(res);
+        }));
+        rx.await.map_err(|_| FilterError::InternalError)?
+    }
+
+    /// Returns the logs in the given _inclusive_ chunk of a range that match the filter.
+    ///
+    /// Stops after the first block that exceeds `max_logs`, or when the deadline has passed.
+    async fn logs_in_chunk(
+        &self,
+        filter: Filter,
+        from_block: u64,
+        to_block: u64,
+        max_logs: usize,
+        deadline: Option<Instant>,
+    ) -> Result<LogsChunk, FilterError> {
+        let mut logs = Vec::new();
+        let is_expired = || deadline.is_some_and(|deadline| Instant::now() >= deadline);
 
         // derive bloom filters from filter input
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
+        let filter_params = FilteredParams::new(Some(filter));
+
+        // loop over the indexed ranges of blocks, skipping those whose combined bloom doesn't
+        // match the filter
+        let mut range_start = from_block;
+        while range_start <= to_block {
+            if is_expired() {
+                return Ok(LogsChunk { logs, timed_out_at: Some(range_start) })
+            }
+            if let Some(next) =
+                self.skip_unmatched_range(range_start, &address_filter, &topics_filter)?
+            {
+                range_start = next;
+                continue
+            }
+            let level = LogsBloomLevel::Blocks4K;
+            let range_end =
+                (*level.block_range(level.range_index(range_start)).end()).min(to_block);
+
+            // loop over the range of new blocks and check logs if the filter matches the log's
+            // bloom filter
+            for (from, to) in
+                BlockRangeInclusiveIter::new(range_start..=range_end, self.max_headers_range)
+            {
+                let headers = self.provider.headers_range(from..=to)?;
+
+                for (idx, header) in headers.iter().enumerate() {
+                    // only if filter matches
+                    if FilteredParams::matches_address(header.logs_bloom, &address_filter) &&
+                        FilteredParams::matches_topics(header.logs_bloom, &topics_filter)
+                    {
+                        if is_expired() {
+                            return Ok(LogsChunk { logs, timed_out_at: Some(header.number) })
+                        }
 
-        // loop over the range of new blocks and check logs if the filter matches the log's bloom
-        // filter
-        for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
-        {
-            let headers = self.provider.headers_range(from..=to)?;
-
-            for (idx, header) in headers.iter().enumerate() {
-                // only if filter matches
-                if FilteredParams::matches_address(header.logs_bloom, &address_filter) &&
-                    FilteredParams::matches_topics(header.logs_bloom, &topics_filter)
-                {
-                    // these are consecutive headers, so we can use the parent hash of the next
-                    // block to get the current header's hash
-                    let block_hash = match headers.get(idx + 1) {
-                        Some(parent) => parent.parent_hash,
-                        None => self
-                            .provider
-                            .block_hash(header.number)?
-                            .ok_or(ProviderError::BlockNotFound(header.number.into()))?,
-                    };
-
-                    if let Some(receipts) = self.eth_cache.get_receipts(block_hash).await? {
-                        append_matching_block_logs(
-                            &mut all_logs,
-                            &self.provider,
-                            &filter_params,
-                            BlockNumHash::new(header.number, block_hash),
-                            &receipts,
-                            false,
-                        )?;
-
-                        // size check but only if range is multiple blocks, so we always return all
-                        // logs of a single block
-                        let is_multi_block_range = from_block != to_block;
-                        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
-                            return Err(FilterError::QueryExceedsMaxResults(
-                                self.max_logs_per_response,
-                            ))
+                        // these are consecutive headers, so we can use the parent hash of the
+                        // next block to get the current header's hash
+                        let block_hash = match headers.get(idx + 1) {
+                            Some(parent) => parent.parent_hash,
+                            None => self
+                                .provider
+                                .block_hash(header.number)?
+                                .ok_or(ProviderError::BlockNotFound(header.number.into()))?,
+                        };
+
+                        if let Some(receipts) = self.eth_cache.get_receipts(block_hash).await? {
+                            append_matching_block_logs(
+                                &mut logs,
+                                &self.provider,
+                                &filter_params,
+                                BlockNumHash::new(header.number, block_hash),
+                                &receipts,
+                                false,
+                            )?;
+
+                            // the range is rejected anyway, so the remaining blocks don't matter
+                            if logs.len() > max_logs {
+                                return Ok(LogsChunk { logs, timed_out_at: None })
+                            }
                         }
                     }
                 }
             }
+
+            range_start = range_end + 1;
         }
 
-        Ok(all_logs)
+        Ok(LogsChunk { logs, timed_out_at: None })
+    }
+
+    /// Returns the first block after the indexed range that contains the block, if the combined
+    /// logs bloom of the range doesn't match the filter.
+    ///
+    /// The largest ranges are checked first.
+    fn skip_unmatched_range(
+        &self,
+        block: BlockNumber,
+        address_filter: &BloomFilter,
+        topics_filter: &[BloomFilter],
+    ) -> Result<Option<BlockNumber>, FilterError> {
+        for level in LogsBloomLevel::ALL {
+            let index = level.range_index(block);
+            let Some(bloom) = self.provider.range_logs_bloom(level, index)? else { continue };
+            if !FilteredParams::matches_address(bloom, address_filter) ||
+                !FilteredParams::matches_topics(bloom, topics_filter)
+            {
+                return Ok(Some(level.block_range(index).end() + 1))
+            }
+        }
+        Ok(None)
     }
 }
 
@@ -492,6 +634,13 @@ pub struct EthFilterConfig {
     /// A filter is considered stale if it has not been polled for longer than this duration and
     /// will be removed.
     pub stale_filter_ttl: Duration,
+    /// Maximum number of chunks of the block range of a log query that are processed
+    /// concurrently on the blocking pool.
+    pub max_concurrent_logs_chunks: usize,
+    /// Maximum duration of a log query over a range of blocks.
+    ///
+    /// If `None` then no deadline is enforced.
+    pub logs_query_timeout: Option<Duration>,
 }
 
 impl EthFilterConfig {
@@ -513,6 +662,18 @@ impl EthFilterConfig {
         self.stale_filter_ttl = duration;
         self
     }
+
+    /// Sets the maximum number of chunks of a log query that are processed concurrently.
+    pub fn max_concurrent_logs_chunks(mut self, num: usize) -> Self {
+        self.max_concurrent_logs_chunks = num;
+        self
+    }
+
+    /// Sets the maximum duration of a log query over a range of blocks.
+    pub fn logs_query_timeout(mut self, duration: Duration) -> Self {
+        self.logs_query_timeout = Some(duration);
+        self
+    }
 }
 
 impl Default for EthFilterConfig {
@@ -522,6 +683,8 @@ impl Default for EthFilterConfig {
             max_logs_per_response: None,
             // 5min
             stale_filter_ttl: Duration::from_secs(5 * 60),
+            max_concurrent_logs_chunks: DEFAULT_MAX_CONCURRENT_LOGS_CHUNKS,
+            logs_query_timeout: None,
         }
     }
 }
@@ -639,6 +802,15 @@ enum FilterKind {
     Block,
     PendingTransaction(PendingTransactionKind),
 }
+
+/// The matching logs of a chunk of a range filter.
+#[derive(Debug)]
+struct LogsChunk {
+    /// The logs, in block order.
+    logs: Vec<Log>,
+    /// The first block whose logs haven't been collected because the deadline passed, if any.
+    timed_out_at: Option<u64>,
+}
 /// Errors that can occur in the handler implementation
 #[derive(Debug, thiserror::Error)]
 pub enum FilterError {
@@ -646,8 +818,20 @@ pub enum FilterError {
     FilterNotFound(FilterId),
     #[error("query exceeds max block range {0}")]
     QueryExceedsMaxBlocks(u64),
-    #[error("query exceeds max results {0}")]
-    QueryExceedsMaxResults(usize),
+    /// Error thrown when the logs of a query exceed the maximum number of logs in a response.
+    #[error("query exceeds max results {max_logs}, try a narrower block range")]
+    QueryExceedsMaxResults {
+        /// The maximum number of logs in a response.
+        max_logs: usize,
+        /// A narrower range whose logs fit into a response.
+        suggested_range: RangeInclusive<u64>,
+    },
+    /// Error thrown when the logs of a query couldn't be collected before the deadline.
+    #[error("query timed out, try a narrower block range")]
+    QueryTimeout {
+        /// A narrower range whose logs could be collected before the deadline.
+        suggested_range: RangeInclusive<u64>,
+    },
     #[error(transparent)]
     EthAPIError(#[from] EthApiError),
     /// Error thrown when a spawned task failed to deliver a response.
@@ -670,13 +854,30 @@ impl From<FilterError> for jsonrpsee::types::error::ErrorObject<'static> {
             err @ FilterError::QueryExceedsMaxBlocks(_) => {
                 rpc_error_with_code(jsonrpsee::types::error::INVALID_PARAMS_CODE, err.to_string())
             }
-            err @ FilterError::QueryExceedsMaxResults(_) => {
-                rpc_error_with_code(jsonrpsee::types::error::INVALID_PARAMS_CODE, err.to_string())
+            FilterError::QueryExceedsMaxResults { ref suggested_range, .. } |
+            FilterError::QueryTimeout { ref suggested_range } => {
+                let data = SuggestedBlockRange {
+                    from_block: U64::from(*suggested_range.start()),
+                    to_block: U64::from(*suggested_range.end()),
+                };
+                jsonrpsee::types::error::ErrorObject::owned(
+                    jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                    err.to_string(),
+                    Some(data),
+                )
             }
         }
     }
 }
 
+/// The narrower block range that's suggested in the data of a log query error.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SuggestedBlockRange {
+    from_block: U64,
+    to_block: U64,
+}
+
 impl From<ProviderError> for FilterError {
     fn from(err: ProviderError) -> Self {
         FilterError::EthAPIError(err.into())
@@ -737,4 +938,12 @@ mod tests {
             assert_eq!(end, *range.end());
         }
     }
+
+    #[test]
+    fn test_suggested_range_error_data() {
+        let err = FilterError::QueryExceedsMaxResults { max_logs: 10, suggested_range: 16..=31 };
+        let err = jsonrpsee::types::error::ErrorObject::from(err);
+        assert_eq!(err.code(), jsonrpsee::types::error::INVALID_PARAMS_CODE);
+        assert_eq!(err.data().unwrap().get(), r#"{"fromBlock":"0x10","toBlock":"0x1f"}"#);
+    }
 }
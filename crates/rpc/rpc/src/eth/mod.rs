@@ -12,6 +12,7 @@ mod pubsub;
 pub mod revm_utils;
 mod signer;
 pub(crate) mod utils;
+pub mod withdrawals;
 
 pub use api::{
     fee_history::{fee_history_cache_new_blocks_task, FeeHistoryCache, FeeHistoryCacheConfig},
@@ -19,6 +20,8 @@ pub use api::{
 };
 
 pub use bundle::EthBundle;
-pub use filter::{EthFilter, EthFilterConfig};
+pub use filter::{EthFilter, EthFilterConfig, DEFAULT_MAX_CONCURRENT_LOGS_CHUNKS};
 pub use id_provider::EthSubscriptionIdProvider;
 pub use pubsub::EthPubSub;
+pub use signer::{DEV_ACCOUNTS, DEV_MNEMONIC};
+pub use withdrawals::EthWithdrawals;
@@ -2,15 +2,20 @@
 
 use crate::eth::error::{EthApiError, EthResult, RpcInvalidTransactionError};
 use reth_primitives::{
-    revm::env::{fill_tx_env, fill_tx_env_with_recovered},
-    Address, TransactionSigned, TransactionSignedEcRecovered, TxHash, B256, U256,
+    revm::{
+        compat::into_revm_acc,
+        env::{fill_tx_env, fill_tx_env_with_recovered},
+    },
+    Address, ChainSpec, TransactionSigned, TransactionSignedEcRecovered, TxHash, B256, U256,
 };
+use reth_provider::PreloadedState;
+use reth_revm::state_change::apply_beacon_root_contract_call;
 use reth_rpc_types::{
     state::{AccountOverride, StateOverride},
     BlockOverrides, CallRequest,
 };
 use revm::{
-    db::{CacheDB, EmptyDB},
+    db::{CacheDB, DbAccount, EmptyDB},
     precompile::{Precompiles, SpecId as PrecompilesSpecId},
     primitives::{BlockEnv, CfgEnv, Env, ResultAndState, SpecId, TransactTo, TxEnv},
     Database, Inspector,
@@ -195,6 +200,46 @@ where
     Ok(())
 }
 
+/// Apply the [EIP-4788](https://eips.ethereum.org/EIPS/eip-4788) pre block contract call.
+///
+/// This constructs a new [EVM](revm::EVM) with the given DB, and environment ([CfgEnv] and
+/// [BlockEnv]) to execute the pre block contract call.
+///
+/// This uses [apply_beacon_root_contract_call] to ultimately apply the beacon root contract state
+/// change.
+pub(crate) fn pre_block_beacon_root_contract_call<DB: Database + DatabaseCommit>(
+    db: &mut DB,
+    chain_spec: &ChainSpec,
+    block_number: u64,
+    initialized_cfg: &CfgEnv,
+    initialized_block_env: &BlockEnv,
+    parent_beacon_block_root: Option<B256>,
+) -> EthResult<()>
+where
+    DB::Error: std::fmt::Display,
+{
+    // Configure the environment for the block.
+    let env = Env {
+        cfg: initialized_cfg.clone(),
+        block: initialized_block_env.clone(),
+        ..Default::default()
+    };
+
+    // apply pre-block EIP-4788 contract call
+    let mut evm_pre_block = revm::EVM::with_env(env);
+    evm_pre_block.database(db);
+
+    // initialize a block from the env, because the pre block call needs the block itself
+    apply_beacon_root_contract_call(
+        chain_spec,
+        initialized_block_env.timestamp.to::<u64>(),
+        block_number,
+        parent_beacon_block_root,
+        &mut evm_pre_block,
+    )
+    .map_err(|err| EthApiError::Internal(err.into()))
+}
+
 /// Prepares the [Env] for execution.
 ///
 /// Does not commit any changes to the underlying database.
@@ -587,6 +632,55 @@ where
     Ok(())
 }
 
+/// Returns the accounts a block is known to access before executing it: the senders and recipients
+/// of its transactions and the beneficiary.
+pub(crate) fn block_accounts(
+    transactions: &[TransactionSignedEcRecovered],
+    block_env: &BlockEnv,
+) -> Vec<Address> {
+    transactions
+        .iter()
+        .flat_map(|tx| [Some(tx.signer()), tx.to()])
+        .flatten()
+        .chain(std::iter::once(block_env.coinbase))
+        .collect()
+}
+
+/// Inserts the [PreloadedState] into the [CacheDB], so it isn't read from the database again.
+///
+/// This must be called before executing anything on top of the [CacheDB], the preloaded state
+/// replaces the cached one.
+pub(crate) fn insert_preloaded_state<DB>(
+    db: &mut CacheDB<DB>,
+    state: PreloadedState,
+) -> EthResult<()>
+where
+    DB: DatabaseRef,
+    EthApiError: From<<DB as DatabaseRef>::Error>,
+{
+    for (address, account) in state.accounts {
+        match account {
+            Some(account) => {
+                let mut info = into_revm_acc(account);
+                info.code = account
+                    .bytecode_hash
+                    .and_then(|code_hash| state.bytecodes.get(&code_hash))
+                    .map(|bytecode| bytecode.0.clone());
+                db.insert_account_info(address, info);
+            }
+            None => {
+                db.accounts.insert(address, DbAccount::new_not_existing());
+            }
+        }
+    }
+    for (address, storage) in state.storage {
+        for (slot, value) in storage {
+            db.insert_account_storage(address, U256::from_be_bytes(slot.0), value)?;
+        }
+    }
+    Ok(())
+}
+
 /// This clones and transforms the given [CacheDB] with an arbitrary [DatabaseRef] into a new
 /// [CacheDB] with [EmptyDB] as the database type
 #[inline]
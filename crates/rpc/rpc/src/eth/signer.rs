@@ -2,17 +2,26 @@
 
 use crate::eth::error::SignError;
 use alloy_dyn_abi::TypedData;
+use coins_bip32::path::DerivationPath;
+use coins_bip39::{English, Mnemonic};
 use reth_primitives::{
-    eip191_hash_message, sign_message, Address, Signature, TransactionSigned, B256,
+    eip191_hash_message, public_key_to_address, sign_message, Address, Signature,
+    TransactionSigned, B256,
 };
 use reth_rpc_types::TypedTransactionRequest;
 
 use reth_rpc_types_compat::transaction::to_primitive_transaction;
-use secp256k1::SecretKey;
-use std::collections::HashMap;
+use secp256k1::{PublicKey, SecretKey, SECP256K1};
+use std::{collections::HashMap, str::FromStr};
 
 type Result<T> = std::result::Result<T, SignError>;
 
+/// The mnemonic of the accounts that are funded in the genesis of the dev chain.
+pub const DEV_MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+/// The number of accounts that are funded in the genesis of the dev chain.
+pub const DEV_ACCOUNTS: u32 = 20;
+
 /// An Ethereum Signer used via RPC.
 #[async_trait::async_trait]
 pub(crate) trait EthSigner: Send + Sync {
@@ -45,6 +54,30 @@ pub(crate) struct DevSigner {
 }
 
 impl DevSigner {
+    /// Derives the first `num_accounts` accounts of the mnemonic on the default Ethereum
+    /// derivation path `m/44'/60'/0'/0/{index}`.
+    pub(crate) fn from_mnemonic(phrase: &str, num_accounts: u32) -> Result<Self> {
+        let mnemonic =
+            Mnemonic::<English>::new_from_phrase(phrase).map_err(|_| SignError::CouldNotSign)?;
+
+        let mut addresses = Vec::with_capacity(num_accounts as usize);
+        let mut accounts = HashMap::with_capacity(num_accounts as usize);
+        for index in 0..num_accounts {
+            let path = DerivationPath::from_str(&format!("m/44'/60'/0'/0/{index}"))
+                .map_err(|_| SignError::CouldNotSign)?;
+            let xpriv = mnemonic.derive_key(&path, None).map_err(|_| SignError::CouldNotSign)?;
+            let key: &coins_bip32::prelude::SigningKey = xpriv.as_ref();
+            let secret =
+                SecretKey::from_slice(&key.to_bytes()).map_err(|_| SignError::CouldNotSign)?;
+
+            let address = public_key_to_address(PublicKey::from_secret_key(SECP256K1, &secret));
+            addresses.push(address);
+            accounts.insert(address, secret);
+        }
+
+        Ok(Self { addresses, accounts })
+    }
+
     fn get_key(&self, account: Address) -> Result<&SecretKey> {
         self.accounts.get(&account).ok_or(SignError::NoAccount)
     }
@@ -215,4 +248,21 @@ mod test {
         };
         assert_eq!(sig, expected)
     }
+
+    #[test]
+    fn test_dev_accounts() {
+        let signer = DevSigner::from_mnemonic(DEV_MNEMONIC, DEV_ACCOUNTS).unwrap();
+        let accounts = signer.accounts();
+        assert_eq!(accounts.len(), DEV_ACCOUNTS as usize);
+        // the first and last accounts funded in the dev genesis
+        assert_eq!(
+            accounts[0],
+            Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap()
+        );
+        assert_eq!(
+            accounts[19],
+            Address::from_str("0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199").unwrap()
+        );
+        assert!(signer.is_signer_for(&accounts[0]));
+    }
 }
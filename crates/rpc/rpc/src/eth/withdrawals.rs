@@ -0,0 +1,132 @@
+//! `eth_getWithdrawals` implementation.
+
+use crate::eth::error::{EthApiError, EthResult};
+use jsonrpsee::core::RpcResult;
+use reth_primitives::{Address, BlockNumberOrTag};
+use reth_provider::{BlockIdReader, WithdrawalHistoryReader};
+use reth_rpc_api::EthWithdrawalsApiServer;
+use reth_rpc_types::BlockWithdrawal;
+use reth_rpc_types_compat::block::from_primitive_withdrawal;
+use reth_tasks::TaskSpawner;
+use std::{future::Future, sync::Arc};
+use tokio::sync::oneshot;
+
+/// The maximum number of blocks that can be queried with `eth_getWithdrawals`.
+const MAX_WITHDRAWALS_BLOCK_RANGE: u64 = 100_000;
+
+/// `Eth` withdrawals implementation, serving the withdrawals to an address from the withdrawal
+/// history index.
+pub struct EthWithdrawals<Provider> {
+    /// All nested fields bundled together.
+    inner: Arc<EthWithdrawalsInner<Provider>>,
+}
+
+impl<Provider> EthWithdrawals<Provider> {
+    /// Create a new `EthWithdrawals` instance.
+    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        Self { inner: Arc::new(EthWithdrawalsInner { provider, task_spawner }) }
+    }
+}
+
+impl<Provider> EthWithdrawals<Provider>
+where
+    Provider: BlockIdReader + WithdrawalHistoryReader + 'static,
+{
+    /// Executes the future on a new blocking task.
+    async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
+    where
+        C: FnOnce(Self) -> F,
+        F: Future<Output = EthResult<R>> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let this = self.clone();
+        let f = c(this);
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let res = f.await;
+            let _ = tx.send(res);
+        }));
+        rx.await.map_err(|_| EthApiError::InternalEthError)?
+    }
+
+    /// Returns the withdrawals to the address that were included in the blocks in the range.
+    pub async fn withdrawals(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> EthResult<Vec<BlockWithdrawal>> {
+        self.on_blocking_task(
+            |this| async move { this.try_withdrawals(address, from_block, to_block) },
+        )
+        .await
+    }
+
+    fn try_withdrawals(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> EthResult<Vec<BlockWithdrawal>> {
+        let provider = &self.inner.provider;
+        let (Some(from), Some(to)) =
+            (provider.convert_block_number(from_block)?, provider.convert_block_number(to_block)?)
+        else {
+            return Err(EthApiError::UnknownBlockNumber)
+        };
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        if to - from >= MAX_WITHDRAWALS_BLOCK_RANGE {
+            return Err(EthApiError::InvalidParams(format!(
+                "query exceeds max block range {MAX_WITHDRAWALS_BLOCK_RANGE}"
+            )))
+        }
+
+        let withdrawals = provider
+            .withdrawals_by_address(address, from..=to)?
+            .into_iter()
+            .map(|(block_number, withdrawal)| BlockWithdrawal {
+                block_number,
+                withdrawal: from_primitive_withdrawal(withdrawal),
+            })
+            .collect();
+        Ok(withdrawals)
+    }
+}
+
+#[async_trait::async_trait]
+impl<Provider> EthWithdrawalsApiServer for EthWithdrawals<Provider>
+where
+    Provider: BlockIdReader + WithdrawalHistoryReader + 'static,
+{
+    /// Handler for `eth_getWithdrawals`
+    async fn withdrawals(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<Vec<BlockWithdrawal>> {
+        Ok(EthWithdrawals::withdrawals(self, address, from_block, to_block).await?)
+    }
+}
+
+/// Container type for `EthWithdrawals` internals
+struct EthWithdrawalsInner<Provider> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+impl<Provider> std::fmt::Debug for EthWithdrawals<Provider> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EthWithdrawals").finish_non_exhaustive()
+    }
+}
+
+impl<Provider> Clone for EthWithdrawals<Provider> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
@@ -325,6 +325,34 @@ mod tests {
         assert!(matches!(result, Err(JwtError::UnsupportedSignatureAlgorithm)));
     }
 
+    #[test]
+    fn validation_ok_iat_within_window() {
+        let secret = JwtSecret::random();
+        let offset = Duration::from_secs(JWT_MAX_IAT_DIFF.as_secs() - 1);
+
+        for iat in [
+            SystemTime::now().checked_sub(offset).unwrap(),
+            SystemTime::now().checked_add(offset).unwrap(),
+        ] {
+            let claims = Claims { iat: to_u64(iat), exp: None };
+            let jwt: String = secret.encode(&claims).unwrap();
+            assert!(matches!(secret.validate(jwt), Ok(())));
+        }
+    }
+
+    #[test]
+    fn validation_error_none_algorithm() {
+        let secret = JwtSecret::random();
+        let claims = Claims { iat: to_u64(SystemTime::now()), exp: None };
+        let jwt: String = secret.encode(&claims).unwrap();
+
+        // Replace the header with `{"alg":"none","typ":"JWT"}` and strip the signature.
+        let payload = jwt.split('.').nth(1).unwrap();
+        let jwt = format!("eyJhbGciOiJub25lIiwidHlwIjoiSldUIn0.{payload}.");
+
+        assert!(secret.validate(jwt).is_err());
+    }
+
     #[test]
     fn valid_without_exp_claim() {
         let secret = JwtSecret::random();
@@ -1,32 +1,349 @@
-use crate::result::internal_rpc_err;
+use crate::{
+    eth::{
+        error::{EthApiError, EthResult},
+        EthTransactions,
+    },
+    result::ToRpcResult,
+};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, TxHash, B256};
+use reth_consensus_common::calc::{base_block_reward, block_reward, ommer_reward};
+use reth_primitives::{
+    stage::StageId, Address, BlockId, BlockNumber, BlockNumberOrTag, Bloom, Bytes, TxHash,
+    TxNumber, B256, U256,
+};
+use reth_provider::{
+    AddressAppearancesReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader,
+    ContractCreationsReader, StageCheckpointReader, TransactionVariant,
+};
+use reth_revm::tracing::{
+    types::{CallKind, CallTraceNode},
+    TracingInspectorConfig,
+};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_types::{
-    BlockDetails, ContractCreator, InternalOperation, OtsBlockTransactions, TraceEntry,
+    BlockDetails, BlockTransactions, ContractCreator, InternalIssuance, InternalOperation,
+    OperationType, OtsBlock, OtsBlockTransactions, OtsTransactionReceipt, RichBlock, TraceEntry,
     Transaction, TransactionsWithReceipts,
 };
+use revm::{interpreter::InstructionResult, primitives::ExecutionResult};
+use std::{
+    cmp::Reverse,
+    ops::{Range, RangeInclusive},
+};
 
 const API_LEVEL: u64 = 8;
 
+/// The maximum number of transactions that are returned per page, larger page sizes are capped.
+const MAX_PAGE_SIZE: usize = 100;
+
 /// Otterscan API.
 #[derive(Debug)]
-pub struct OtterscanApi<Eth> {
+pub struct OtterscanApi<Provider, Eth> {
+    provider: Provider,
     eth: Eth,
 }
 
-impl<Eth> OtterscanApi<Eth> {
+impl<Provider, Eth> OtterscanApi<Provider, Eth> {
     /// Creates a new instance of `Otterscan`.
-    pub fn new(eth: Eth) -> Self {
-        Self { eth }
+    pub fn new(provider: Provider, eth: Eth) -> Self {
+        Self { provider, eth }
+    }
+}
+
+impl<Provider, Eth> OtterscanApi<Provider, Eth>
+where
+    Provider: BlockReaderIdExt
+        + ChainSpecProvider
+        + ChangeSetReader
+        + ContractCreationsReader
+        + AddressAppearancesReader
+        + StageCheckpointReader
+        + 'static,
+    Eth: EthApiServer + EthTransactions + 'static,
+{
+    /// Returns the details of the block, with the ether issued with it and the fees paid by its
+    /// transactions.
+    async fn block_details(&self, block: RichBlock) -> RpcResult<BlockDetails> {
+        let number = block.header.number.unwrap_or_default().to::<BlockNumber>();
+        let receipts = self.eth.block_receipts(BlockId::Number(number.into())).await?;
+        let total_fees = receipts.unwrap_or_default().iter().fold(U256::ZERO, |fees, receipt| {
+            fees + receipt.gas_used.unwrap_or_default() *
+                U256::from(receipt.effective_gas_price.to::<u128>())
+        });
+
+        let mut details = BlockDetails::from(block);
+        details.issuance = self.issuance(number)?;
+        details.total_fees = total_fees;
+        Ok(details)
+    }
+
+    /// Returns the ether issued with the block, which is zero after the merge.
+    fn issuance(&self, number: BlockNumber) -> EthResult<InternalIssuance> {
+        let (Some(header), Some(total_difficulty)) =
+            (self.provider.header_by_number(number)?, self.provider.header_td_by_number(number)?)
+        else {
+            return Ok(InternalIssuance::default())
+        };
+        let Some(base_block_reward) = base_block_reward(
+            &self.provider.chain_spec(),
+            number,
+            header.difficulty,
+            total_difficulty,
+        ) else {
+            return Ok(InternalIssuance::default())
+        };
+
+        let ommers = self.provider.ommers(number.into())?.unwrap_or_default();
+        let block_reward = block_reward(base_block_reward, ommers.len());
+        let uncle_reward = ommers
+            .iter()
+            .map(|ommer| ommer_reward(base_block_reward, number, ommer.number))
+            .sum::<u128>();
+        Ok(InternalIssuance {
+            block_reward: U256::from(block_reward),
+            uncle_reward: U256::from(uncle_reward),
+            issuance: U256::from(block_reward + uncle_reward),
+        })
+    }
+
+    /// Returns the number of the block, `0` if the block is the genesis block.
+    fn block_number(&self, block_number: BlockNumberOrTag) -> EthResult<BlockNumber> {
+        self.provider.convert_block_number(block_number)?.ok_or(EthApiError::UnknownBlockNumber)
+    }
+
+    /// Returns the numbers of the transactions of the block.
+    fn block_tx_range(&self, number: BlockNumber) -> EthResult<Range<TxNumber>> {
+        let indices =
+            self.provider.block_body_indices(number)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        Ok(indices.tx_num_range())
+    }
+
+    /// Returns the numbers of the transactions of the block that contains the transaction.
+    fn tx_range_of(&self, tx_number: TxNumber) -> EthResult<Range<TxNumber>> {
+        let number =
+            self.provider.transaction_block(tx_number)?.ok_or(EthApiError::TransactionNotFound)?;
+        self.block_tx_range(number)
+    }
+
+    /// Returns whether the optional address appearance index is enabled, in which case the
+    /// transactions of an address are searched with it.
+    fn has_address_appearances(&self) -> EthResult<bool> {
+        Ok(self.provider.get_stage_checkpoint(StageId::IndexAddressAppearances)?.is_some())
+    }
+
+    /// Returns the numbers of the transactions in which the address appears before the block, from
+    /// the newest to the oldest, and whether transactions are left.
+    ///
+    /// The page is completed with the remaining appearances in the block of its last transaction,
+    /// so it only ends after a block and can contain more transactions than its size.
+    fn appearances_before(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        page_size: usize,
+    ) -> EthResult<(Vec<TxNumber>, bool)> {
+        // `0` searches from the tip of the chain
+        let end = if block_number == 0 {
+            TxNumber::MAX
+        } else {
+            self.block_tx_range(block_number)?.start
+        };
+        let mut tx_numbers = self.provider.address_appearances_before(address, end, page_size)?;
+        let Some(&last) = tx_numbers.last() else { return Ok((tx_numbers, false)) };
+        let block_start = self.tx_range_of(last)?.start;
+        let remaining = (last - block_start) as usize;
+        tx_numbers.extend(self.provider.address_appearances_before(address, last, remaining)?);
+        let has_more =
+            !self.provider.address_appearances_before(address, block_start, 1)?.is_empty();
+        Ok((tx_numbers, has_more))
+    }
+
+    /// Returns the numbers of the transactions in which the address appears after the block, from
+    /// the newest to the oldest, and whether transactions are left.
+    ///
+    /// The page is completed like in [Self::appearances_before].
+    fn appearances_after(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        page_size: usize,
+    ) -> EthResult<(Vec<TxNumber>, bool)> {
+        // `0` searches from the genesis block
+        let start = if block_number == 0 { 0 } else { self.block_tx_range(block_number)?.end };
+        let mut tx_numbers = self.provider.address_appearances_after(address, start, page_size)?;
+        let Some(&last) = tx_numbers.last() else { return Ok((tx_numbers, false)) };
+        let block_end = self.tx_range_of(last)?.end;
+        let remaining = (block_end - last - 1) as usize;
+        tx_numbers.extend(self.provider.address_appearances_after(address, last + 1, remaining)?);
+        let has_more = !self.provider.address_appearances_after(address, block_end, 1)?.is_empty();
+        tx_numbers.reverse();
+        Ok((tx_numbers, has_more))
+    }
+
+    /// Returns the transactions with the numbers and their receipts, in the order of the numbers.
+    ///
+    /// The transactions of the same block are expected to be adjacent, each block is loaded once.
+    async fn transactions_by_numbers(
+        &self,
+        tx_numbers: Vec<TxNumber>,
+    ) -> RpcResult<(Vec<Transaction>, Vec<OtsTransactionReceipt>)> {
+        let mut blocks = Vec::<(BlockNumber, Vec<TxNumber>)>::new();
+        for tx_number in tx_numbers {
+            let number = self
+                .provider
+                .transaction_block(tx_number)
+                .to_rpc_result()?
+                .ok_or(EthApiError::TransactionNotFound)?;
+            match blocks.last_mut() {
+                Some((last, numbers)) if *last == number => numbers.push(tx_number),
+                _ => blocks.push((number, vec![tx_number])),
+            }
+        }
+
+        let (mut txs, mut receipts) = (Vec::new(), Vec::new());
+        for (number, tx_numbers) in blocks {
+            let first_tx_number = self.block_tx_range(number)?.start;
+            let block = self.eth.block_by_number(number.into(), true);
+            let block_receipts = self.eth.block_receipts(BlockId::Number(number.into()));
+            let (Some(block), Some(block_receipts)) = futures::try_join!(block, block_receipts)?
+            else {
+                continue
+            };
+            let timestamp = block.header.timestamp.to::<u64>();
+            let BlockTransactions::Full(transactions) = block.inner.transactions else { continue };
+            for tx_number in tx_numbers {
+                let index = (tx_number - first_tx_number) as usize;
+                if let (Some(tx), Some(receipt)) =
+                    (transactions.get(index), block_receipts.get(index))
+                {
+                    txs.push(tx.clone());
+                    receipts.push(OtsTransactionReceipt { receipt: receipt.clone(), timestamp });
+                }
+            }
+        }
+        Ok((txs, receipts))
+    }
+
+    /// Returns the numbers of the blocks in the range that changed the account or its storage, in
+    /// ascending order.
+    fn history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> EthResult<Vec<BlockNumber>> {
+        let mut blocks = self.provider.account_history_blocks(address, range.clone())?;
+        blocks.extend(self.provider.storage_history_blocks(address, range)?);
+        blocks.sort_unstable();
+        blocks.dedup();
+        Ok(blocks)
+    }
+
+    /// Returns the transactions of the blocks that were sent from or to the address, created the
+    /// contract at the address, or emitted a log of or mentioning the address, e.g. a token
+    /// transfer, from the newest to the oldest, and whether blocks are left.
+    ///
+    /// The blocks are searched until the page is full. Since the page only ends after a block, it
+    /// can contain more transactions than its size.
+    ///
+    /// Only the blocks in which the account or its storage changed are searched, so the token
+    /// transfers to an address that didn't change otherwise aren't found. This is the fallback if
+    /// the address appearance index is disabled.
+    async fn search_blocks(
+        &self,
+        address: Address,
+        mut blocks: impl Iterator<Item = BlockNumber> + Send,
+        page_size: usize,
+    ) -> RpcResult<(Vec<Transaction>, Vec<OtsTransactionReceipt>, bool)> {
+        let topic = address.into_word();
+        let mut found = Vec::new();
+        while found.len() < page_size {
+            let Some(number) = blocks.next() else { break };
+            let block = self.eth.block_by_number(number.into(), true);
+            let receipts = self.eth.block_receipts(BlockId::Number(number.into()));
+            let (Some(block), Some(receipts)) = futures::try_join!(block, receipts)? else {
+                continue
+            };
+            let timestamp = block.header.timestamp.to::<u64>();
+            let BlockTransactions::Full(transactions) = block.inner.transactions else { continue };
+            found.extend(
+                transactions
+                    .into_iter()
+                    .zip(receipts)
+                    .filter(|(tx, receipt)| {
+                        tx.from == address ||
+                            tx.to == Some(address) ||
+                            receipt.contract_address == Some(address) ||
+                            receipt
+                                .logs
+                                .iter()
+                                .any(|log| log.address == address || log.topics.contains(&topic))
+                    })
+                    .map(|(tx, receipt)| (tx, OtsTransactionReceipt { receipt, timestamp })),
+            );
+        }
+        let has_more = blocks.next().is_some();
+
+        found.sort_by_key(|(tx, _)| Reverse((tx.block_number, tx.transaction_index)));
+        let (txs, receipts) = found.into_iter().unzip();
+        Ok((txs, receipts, has_more))
+    }
+
+    /// Returns the hash of the transaction of the sender with the nonce.
+    ///
+    /// The transaction is in the last block that changed the sender's account and in which the
+    /// sender's nonce before the block was at most the nonce.
+    fn transaction_hash_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> EthResult<Option<TxHash>> {
+        let blocks = self.provider.account_history_blocks(sender, 0..=BlockNumber::MAX)?;
+        let nonce_before = |number| -> EthResult<u64> {
+            let changes = self.provider.account_block_changeset(number)?;
+            Ok(changes
+                .into_iter()
+                .find(|change| change.address == sender)
+                .and_then(|change| change.info)
+                .map(|account| account.nonce)
+                .unwrap_or_default())
+        };
+
+        // the nonce never decreases, so the blocks are searched with a binary search
+        let (mut low, mut high) = (0, blocks.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            if nonce_before(blocks[mid])? <= nonce {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        let Some(number) = low.checked_sub(1).map(|index| blocks[index]) else { return Ok(None) };
+
+        let Some(block) =
+            self.provider.block_with_senders(number.into(), TransactionVariant::WithHash)?
+        else {
+            return Ok(None)
+        };
+        Ok(block
+            .transactions_with_sender()
+            .find(|(from, tx)| **from == sender && tx.nonce() == nonce)
+            .map(|(_, tx)| tx.hash))
     }
 }
 
 #[async_trait]
-impl<Eth> OtterscanServer for OtterscanApi<Eth>
+impl<Provider, Eth> OtterscanServer for OtterscanApi<Provider, Eth>
 where
-    Eth: EthApiServer,
+    Provider: BlockReaderIdExt
+        + ChainSpecProvider
+        + ChangeSetReader
+        + ContractCreationsReader
+        + AddressAppearancesReader
+        + StageCheckpointReader
+        + 'static,
+    Eth: EthApiServer + EthTransactions + 'static,
 {
     /// Handler for `ots_hasCode`
     async fn has_code(&self, address: Address, block_number: Option<BlockId>) -> RpcResult<bool> {
@@ -39,18 +356,59 @@ where
     }
 
     /// Handler for `ots_getInternalOperations`
-    async fn get_internal_operations(&self, _tx_hash: TxHash) -> RpcResult<Vec<InternalOperation>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn get_internal_operations(&self, tx_hash: TxHash) -> RpcResult<Vec<InternalOperation>> {
+        let operations = self
+            .eth
+            .spawn_trace_transaction_in_block(
+                tx_hash,
+                TracingInspectorConfig::default_parity(),
+                |_, inspector, _, _| {
+                    let mut operations = Vec::new();
+                    let nodes = inspector.get_traces().nodes();
+                    if !nodes.is_empty() {
+                        collect_internal_operations(nodes, 0, &mut operations);
+                    }
+                    Ok(operations)
+                },
+            )
+            .await?;
+        Ok(operations.ok_or(EthApiError::TransactionNotFound)?)
     }
 
     /// Handler for `ots_getTransactionError`
-    async fn get_transaction_error(&self, _tx_hash: TxHash) -> RpcResult<String> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn get_transaction_error(&self, tx_hash: TxHash) -> RpcResult<String> {
+        let output = self
+            .eth
+            .spawn_trace_transaction_in_block(
+                tx_hash,
+                TracingInspectorConfig::default_parity(),
+                |_, _, res, _| match res.result {
+                    ExecutionResult::Revert { output, .. } => Ok(output),
+                    _ => Ok(Bytes::new()),
+                },
+            )
+            .await?;
+        Ok(output.ok_or(EthApiError::TransactionNotFound)?.to_string())
     }
 
     /// Handler for `ots_traceTransaction`
-    async fn trace_transaction(&self, _tx_hash: TxHash) -> RpcResult<TraceEntry> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<Vec<TraceEntry>> {
+        let entries = self
+            .eth
+            .spawn_trace_transaction_in_block(
+                tx_hash,
+                TracingInspectorConfig::default_parity(),
+                |_, inspector, _, _| {
+                    let mut entries = Vec::new();
+                    let nodes = inspector.get_traces().nodes();
+                    if !nodes.is_empty() {
+                        collect_trace_entries(nodes, 0, &mut entries);
+                    }
+                    Ok(entries)
+                },
+            )
+            .await?;
+        Ok(entries.ok_or(EthApiError::TransactionNotFound)?)
     }
 
     /// Handler for `ots_getBlockDetails`
@@ -58,57 +416,215 @@ where
         &self,
         block_number: BlockNumberOrTag,
     ) -> RpcResult<Option<BlockDetails>> {
-        let block = self.eth.block_by_number(block_number, true).await?;
-        Ok(block.map(Into::into))
+        match self.eth.block_by_number(block_number, true).await? {
+            Some(block) => self.block_details(block).await.map(Some),
+            None => Ok(None),
+        }
     }
 
     /// Handler for `getBlockDetailsByHash`
     async fn get_block_details_by_hash(&self, block_hash: B256) -> RpcResult<Option<BlockDetails>> {
-        let block = self.eth.block_by_hash(block_hash, true).await?;
-        Ok(block.map(Into::into))
+        match self.eth.block_by_hash(block_hash, true).await? {
+            Some(block) => self.block_details(block).await.map(Some),
+            None => Ok(None),
+        }
     }
 
     /// Handler for `getBlockTransactions`
     async fn get_block_transactions(
         &self,
-        _block_number: BlockNumberOrTag,
-        _page_number: usize,
-        _page_size: usize,
+        block_number: BlockNumberOrTag,
+        page_number: usize,
+        page_size: usize,
     ) -> RpcResult<OtsBlockTransactions> {
-        Err(internal_rpc_err("unimplemented"))
+        let block = self.eth.block_by_number(block_number, true);
+        let receipts = self.eth.block_receipts(BlockId::Number(block_number));
+        let (Some(block), Some(receipts)) = futures::try_join!(block, receipts)? else {
+            return Err(EthApiError::UnknownBlockNumber.into())
+        };
+        let mut block = block.inner;
+        let timestamp = block.header.timestamp.to::<u64>();
+        let BlockTransactions::Full(transactions) = &mut block.transactions else {
+            return Err(EthApiError::InternalEthError.into())
+        };
+
+        // the pages start at the end of the block
+        let page_size = page_size.min(MAX_PAGE_SIZE);
+        let transaction_count = transactions.len();
+        let page_end = transaction_count.saturating_sub(page_number.saturating_mul(page_size));
+        let page_start = page_end.saturating_sub(page_size);
+        transactions.truncate(page_end);
+        transactions.drain(..page_start);
+        let receipts = receipts
+            .into_iter()
+            .skip(page_start)
+            .take(page_end - page_start)
+            .map(|mut receipt| {
+                // the logs aren't needed to list the transactions
+                receipt.logs.clear();
+                receipt.logs_bloom = Bloom::ZERO;
+                OtsTransactionReceipt { receipt, timestamp }
+            })
+            .collect();
+
+        Ok(OtsBlockTransactions { fullblock: OtsBlock { block, transaction_count }, receipts })
     }
 
     /// Handler for `searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let block_number = self.block_number(block_number)?;
+        let page_size = page_size.min(MAX_PAGE_SIZE);
+        let (txs, receipts, has_more) = if self.has_address_appearances()? {
+            let (tx_numbers, has_more) =
+                self.appearances_before(address, block_number, page_size)?;
+            let (txs, receipts) = self.transactions_by_numbers(tx_numbers).await?;
+            (txs, receipts, has_more)
+        } else {
+            // `0` searches from the tip of the chain
+            let end = block_number.checked_sub(1).unwrap_or(BlockNumber::MAX);
+            let blocks = self.history_blocks(address, 0..=end)?;
+            self.search_blocks(address, blocks.into_iter().rev(), page_size).await?
+        };
+        Ok(TransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page: block_number == 0,
+            last_page: !has_more,
+        })
     }
 
     /// Handler for `searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let block_number = self.block_number(block_number)?;
+        let page_size = page_size.min(MAX_PAGE_SIZE);
+        let (txs, receipts, has_more) = if self.has_address_appearances()? {
+            let (tx_numbers, has_more) =
+                self.appearances_after(address, block_number, page_size)?;
+            let (txs, receipts) = self.transactions_by_numbers(tx_numbers).await?;
+            (txs, receipts, has_more)
+        } else {
+            // `0` searches from the genesis block
+            let start = if block_number == 0 { 0 } else { block_number.saturating_add(1) };
+            let blocks = self.history_blocks(address, start..=BlockNumber::MAX)?;
+            self.search_blocks(address, blocks.into_iter(), page_size).await?
+        };
+        Ok(TransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page: !has_more,
+            last_page: block_number == 0,
+        })
     }
 
     /// Handler for `getTransactionBySenderAndNonce`
     async fn get_transaction_by_sender_and_nonce(
         &self,
-        _sender: Address,
-        _nonce: u64,
+        sender: Address,
+        nonce: u64,
     ) -> RpcResult<Option<Transaction>> {
-        Err(internal_rpc_err("unimplemented"))
+        match self.transaction_hash_by_sender_and_nonce(sender, nonce)? {
+            Some(hash) => EthApiServer::transaction_by_hash(&self.eth, hash).await,
+            None => Ok(None),
+        }
     }
 
     /// Handler for `getContractCreator`
-    async fn get_contract_creator(&self, _address: Address) -> RpcResult<Option<ContractCreator>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn get_contract_creator(&self, address: Address) -> RpcResult<Option<ContractCreator>> {
+        let Some(creation) = self.provider.contract_creation(address).to_rpc_result()? else {
+            return Ok(None)
+        };
+        let Some(tx) = self.provider.transaction_by_id(creation.tx_number).to_rpc_result()? else {
+            return Ok(None)
+        };
+        let Some(tx) = EthApiServer::transaction_by_hash(&self.eth, tx.hash).await? else {
+            return Ok(None)
+        };
+        Ok(Some(ContractCreator { creator: creation.creator, tx }))
+    }
+}
+
+/// Collects the trace entries of the call at the index and its subcalls, in the order they were
+/// made.
+fn collect_trace_entries(nodes: &[CallTraceNode], index: usize, entries: &mut Vec<TraceEntry>) {
+    let node = &nodes[index];
+    let trace = &node.trace;
+    let value = match trace.kind {
+        CallKind::StaticCall | CallKind::DelegateCall => None,
+        _ => Some(trace.value),
+    };
+    entries.push(TraceEntry {
+        r#type: trace.kind.to_string(),
+        depth: trace.depth as u32,
+        from: trace.caller,
+        to: trace.address,
+        value,
+        input: trace.data.clone(),
+    });
+
+    for child in &node.children {
+        collect_trace_entries(nodes, *child, entries);
+    }
+
+    // the contract is destroyed once its subcalls returned
+    if trace.status == InstructionResult::SelfDestruct {
+        entries.push(TraceEntry {
+            r#type: "SELFDESTRUCT".to_string(),
+            depth: trace.depth as u32 + 1,
+            from: trace.address,
+            to: trace.selfdestruct_refund_target.unwrap_or_default(),
+            value: Some(trace.value),
+            input: Bytes::new(),
+        });
+    }
+}
+
+/// Collects the internal operations of the call at the index and its subcalls, in the order they
+/// were made.
+///
+/// The call of the transaction itself isn't an internal operation.
+fn collect_internal_operations(
+    nodes: &[CallTraceNode],
+    index: usize,
+    operations: &mut Vec<InternalOperation>,
+) {
+    let node = &nodes[index];
+    let trace = &node.trace;
+    let r#type = match trace.kind {
+        _ if trace.depth == 0 => None,
+        CallKind::Call if trace.value != U256::ZERO => Some(OperationType::OpTransfer),
+        CallKind::Create => Some(OperationType::OpCreate),
+        CallKind::Create2 => Some(OperationType::OpCreate2),
+        _ => None,
+    };
+    if let Some(r#type) = r#type {
+        operations.push(InternalOperation {
+            r#type,
+            from: trace.caller,
+            to: trace.address,
+            value: trace.value,
+        });
+    }
+
+    for child in &node.children {
+        collect_internal_operations(nodes, *child, operations);
+    }
+
+    if trace.status == InstructionResult::SelfDestruct {
+        operations.push(InternalOperation {
+            r#type: OperationType::OpSelfDestruct,
+            from: trace.address,
+            to: trace.selfdestruct_refund_target.unwrap_or_default(),
+            value: trace.value,
+        });
     }
 }
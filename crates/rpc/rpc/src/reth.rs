@@ -2,13 +2,18 @@ use crate::eth::error::{EthApiError, EthResult};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_interfaces::RethResult;
-use reth_primitives::{Address, BlockId, U256};
-use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, InternalTransfer, U256};
+use reth_provider::{
+    BlockReaderIdExt, ChangeSetReader, InternalTransfersReader, StateProviderFactory,
+};
 use reth_rpc_api::RethApiServer;
 use reth_tasks::TaskSpawner;
 use std::{collections::HashMap, future::Future, sync::Arc};
 use tokio::sync::oneshot;
 
+/// The maximum number of blocks that can be queried with `reth_getInternalTransfers`.
+const MAX_INTERNAL_TRANSFERS_BLOCK_RANGE: u64 = 10_000;
+
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
@@ -33,7 +38,11 @@ impl<Provider> RethApi<Provider> {
 
 impl<Provider> RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StateProviderFactory
+        + InternalTransfersReader
+        + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -81,12 +90,58 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the indexed internal transfers of the blocks in the range, optionally only the ones
+    /// sent from or to the address.
+    pub async fn internal_transfers(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        address: Option<Address>,
+    ) -> EthResult<Vec<InternalTransfer>> {
+        self.on_blocking_task(|this| async move {
+            this.try_internal_transfers(from_block, to_block, address)
+        })
+        .await
+    }
+
+    fn try_internal_transfers(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        address: Option<Address>,
+    ) -> EthResult<Vec<InternalTransfer>> {
+        let (Some(from), Some(to)) = (
+            self.provider().convert_block_number(from_block)?,
+            self.provider().convert_block_number(to_block)?,
+        ) else {
+            return Err(EthApiError::UnknownBlockNumber)
+        };
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        if to - from >= MAX_INTERNAL_TRANSFERS_BLOCK_RANGE {
+            return Err(EthApiError::InvalidParams(format!(
+                "query exceeds max block range {MAX_INTERNAL_TRANSFERS_BLOCK_RANGE}"
+            )))
+        }
+
+        let transfers = match address {
+            Some(address) => self.provider().internal_transfers_by_address(address, from..=to)?,
+            None => self.provider().internal_transfers_by_block_range(from..=to)?,
+        };
+        Ok(transfers)
+    }
 }
 
 #[async_trait]
 impl<Provider> RethApiServer for RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StateProviderFactory
+        + InternalTransfersReader
+        + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -95,6 +150,16 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(RethApi::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_getInternalTransfers`
+    async fn reth_get_internal_transfers(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        address: Option<Address>,
+    ) -> RpcResult<Vec<InternalTransfer>> {
+        Ok(RethApi::internal_transfers(self, from_block, to_block, address).await?)
+    }
 }
 
 impl<Provider> std::fmt::Debug for RethApi<Provider> {
@@ -1,5 +1,6 @@
 use crate::{
     eth::{
+        cache::{BlockResponseKind, EthStateCache},
         error::{EthApiError, EthResult},
         revm_utils::{inspect, inspect_and_return_db, prepare_call_env, EvmOverrides},
         utils::recover_raw_transaction,
@@ -14,7 +15,7 @@ use reth_primitives::{
     revm::env::tx_env_with_recovered, revm_primitives::db::DatabaseCommit, BlockId,
     BlockNumberOrTag, Bytes, SealedHeader, B256, U256,
 };
-use reth_provider::{BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
+use reth_provider::{BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
 use reth_revm::{
     database::StateProviderDatabase,
     tracing::{parity::populate_state_diff, TracingInspector, TracingInspectorConfig},
@@ -45,8 +46,13 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
     }
 
     /// Create a new instance of the [TraceApi]
-    pub fn new(provider: Provider, eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(TraceApiInner { provider, eth_api, blocking_task_guard });
+    pub fn new(
+        provider: Provider,
+        eth_api: Eth,
+        eth_cache: EthStateCache,
+        blocking_task_guard: BlockingTaskGuard,
+    ) -> Self {
+        let inner = Arc::new(TraceApiInner { provider, eth_api, eth_cache, blocking_task_guard });
         Self { inner }
     }
 
@@ -62,7 +68,8 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
 
 impl<Provider, Eth> TraceApi<Provider, Eth>
 where
-    Provider: BlockReader + StateProviderFactory + EvmEnvProvider + ChainSpecProvider + 'static,
+    Provider:
+        BlockReaderIdExt + StateProviderFactory + EvmEnvProvider + ChainSpecProvider + 'static,
     Eth: EthTransactions + 'static,
 {
     /// Executes the given call and returns a number of possible traces for it.
@@ -175,11 +182,28 @@ where
     }
 
     /// Replays a transaction, returning the traces.
+    ///
+    /// The traces of a transaction in a finalized block are taken from the cached replay of its
+    /// block, if any.
     pub async fn replay_transaction(
         &self,
         hash: B256,
         trace_types: HashSet<TraceType>,
     ) -> EthResult<TraceResults> {
+        if let Some(block_hash) = self.finalized_transaction_block(hash)? {
+            let kind = BlockResponseKind::ReplayTraces(trace_types_mask(&trace_types));
+            let cached = self
+                .inner
+                .eth_cache
+                .finalized_responses()
+                .get::<Vec<TraceResultsWithTransactionHash>>(block_hash, kind);
+            if let Some(traces) = cached
+                .and_then(|traces| traces.into_iter().find(|trace| trace.transaction_hash == hash))
+            {
+                return Ok(traces.full_trace)
+            }
+        }
+
         let config = TracingInspectorConfig::from_parity_config(&trace_types);
         self.inner
             .eth_api
@@ -313,10 +337,30 @@ where
     }
 
     /// Returns all traces for the given transaction hash
+    ///
+    /// The traces of a transaction in a finalized block are taken from the cached traces of its
+    /// block, if any. Otherwise only the transaction is traced.
     pub async fn trace_transaction(
         &self,
         hash: B256,
     ) -> EthResult<Option<Vec<LocalizedTransactionTrace>>> {
+        if let Some(block_hash) = self.finalized_transaction_block(hash)? {
+            let cached = self
+                .inner
+                .eth_cache
+                .finalized_responses()
+                .get::<Vec<LocalizedTransactionTrace>>(block_hash, BlockResponseKind::Traces);
+            if let Some(traces) = cached {
+                let traces = traces
+                    .into_iter()
+                    .filter(|trace| trace.transaction_hash == Some(hash))
+                    .collect::<Vec<_>>();
+                if !traces.is_empty() {
+                    return Ok(Some(traces))
+                }
+            }
+        }
+
         self.inner
             .eth_api
             .spawn_trace_transaction_in_block(
@@ -334,9 +378,53 @@ where
     }
 
     /// Returns traces created at given block.
+    ///
+    /// The traces of finalized blocks can't change anymore, so they're cached.
     pub async fn trace_block(
         &self,
         block_id: BlockId,
+    ) -> EthResult<Option<Vec<LocalizedTransactionTrace>>> {
+        let responses = self.inner.eth_cache.finalized_responses();
+        let finalized = self.finalized_block_hash(block_id)?;
+        if let Some(block_hash) = finalized {
+            if let Some(traces) = responses.get(block_hash, BlockResponseKind::Traces) {
+                return Ok(Some(traces))
+            }
+        }
+
+        let traces = self.trace_block_uncached(block_id).await?;
+
+        if let (Some(block_hash), Some(traces)) = (finalized, &traces) {
+            responses.insert(block_hash, BlockResponseKind::Traces, traces);
+        }
+        Ok(traces)
+    }
+
+    /// Returns the hash of the block if it is finalized.
+    fn finalized_block_hash(&self, block_id: BlockId) -> EthResult<Option<B256>> {
+        let Some(finalized) = self.provider().finalized_block_number()? else { return Ok(None) };
+        match self.provider().block_number_for_id(block_id)? {
+            Some(number) if number <= finalized => {
+                Ok(self.provider().block_hash_for_id(block_id)?)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the hash of the block of the transaction if the block is finalized.
+    fn finalized_transaction_block(&self, hash: B256) -> EthResult<Option<B256>> {
+        let Some(finalized) = self.provider().finalized_block_number()? else { return Ok(None) };
+        Ok(self
+            .provider()
+            .transaction_by_hash_with_meta(hash)?
+            .filter(|(_, meta)| meta.block_number <= finalized)
+            .map(|(_, meta)| meta.block_hash))
+    }
+
+    /// Traces the block without consulting the cache.
+    async fn trace_block_uncached(
+        &self,
+        block_id: BlockId,
     ) -> EthResult<Option<Vec<LocalizedTransactionTrace>>> {
         let traces = self.inner.eth_api.trace_block_with(
             block_id,
@@ -394,10 +482,35 @@ where
     }
 
     /// Replays all transactions in a block
+    ///
+    /// The traces of finalized blocks are cached.
     pub async fn replay_block_transactions(
         &self,
         block_id: BlockId,
         trace_types: HashSet<TraceType>,
+    ) -> EthResult<Option<Vec<TraceResultsWithTransactionHash>>> {
+        let responses = self.inner.eth_cache.finalized_responses();
+        let kind = BlockResponseKind::ReplayTraces(trace_types_mask(&trace_types));
+        let finalized = self.finalized_block_hash(block_id)?;
+        if let Some(block_hash) = finalized {
+            if let Some(traces) = responses.get(block_hash, kind) {
+                return Ok(Some(traces))
+            }
+        }
+
+        let traces = self.replay_block_transactions_uncached(block_id, trace_types).await?;
+
+        if let (Some(block_hash), Some(traces)) = (finalized, &traces) {
+            responses.insert(block_hash, kind, traces);
+        }
+        Ok(traces)
+    }
+
+    /// Replays all transactions in a block without consulting the cache.
+    async fn replay_block_transactions_uncached(
+        &self,
+        block_id: BlockId,
+        trace_types: HashSet<TraceType>,
     ) -> EthResult<Option<Vec<TraceResultsWithTransactionHash>>> {
         self.inner
             .eth_api
@@ -428,7 +541,8 @@ where
 #[async_trait]
 impl<Provider, Eth> TraceApiServer for TraceApi<Provider, Eth>
 where
-    Provider: BlockReader + StateProviderFactory + EvmEnvProvider + ChainSpecProvider + 'static,
+    Provider:
+        BlockReaderIdExt + StateProviderFactory + EvmEnvProvider + ChainSpecProvider + 'static,
     Eth: EthTransactions + 'static,
 {
     /// Executes the given call and returns a number of possible traces for it.
@@ -545,10 +659,23 @@ struct TraceApiInner<Provider, Eth> {
     provider: Provider,
     /// Access to commonly used code of the `eth` namespace
     eth_api: Eth,
+    /// The cache of the eth handlers, whose responses of finalized blocks include their traces.
+    eth_cache: EthStateCache,
     // restrict the number of concurrent calls to `trace_*`
     blocking_task_guard: BlockingTaskGuard,
 }
 
+/// Returns the trace types as a bit mask, for the [BlockResponseKind::ReplayTraces] key.
+fn trace_types_mask(trace_types: &HashSet<TraceType>) -> u8 {
+    trace_types.iter().fold(0, |mask, trace_type| {
+        mask | match trace_type {
+            TraceType::Trace => 1,
+            TraceType::VmTrace => 2,
+            TraceType::StateDiff => 4,
+        }
+    })
+}
+
 /// Helper to construct a [`LocalizedTransactionTrace`] that describes a reward to the block
 /// beneficiary.
 fn reward_trace(header: &SealedHeader, reward: RewardAction) -> LocalizedTransactionTrace {
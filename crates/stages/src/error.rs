@@ -1,7 +1,7 @@
 use crate::pipeline::PipelineEvent;
 use reth_interfaces::{
     consensus, db::DatabaseError as DbError, executor, p2p::error::DownloadError,
-    provider::ProviderError, RethError,
+    provider::ProviderError, ErrorSeverity, RethError,
 };
 use reth_primitives::SealedHeader;
 use thiserror::Error;
@@ -93,19 +93,34 @@ pub enum StageError {
 }
 
 impl StageError {
+    /// Returns the [ErrorSeverity] of the error.
+    ///
+    /// Block errors are retried after unwinding the invalid block, download errors are classified
+    /// by the downloader.
+    pub fn severity(&self) -> ErrorSeverity {
+        match self {
+            StageError::Block { .. } | StageError::DetachedHead { .. } => ErrorSeverity::Retryable,
+            StageError::Download(err) => err.severity(),
+            StageError::Internal(err) => err.severity(),
+            StageError::PruningConfiguration(_) | StageError::Recoverable(_) => {
+                ErrorSeverity::Retryable
+            }
+            StageError::Database(_) |
+            StageError::DatabaseIntegrity(_) |
+            StageError::StageCheckpoint(_) |
+            StageError::MissingDownloadBuffer |
+            StageError::MissingSyncGap |
+            StageError::ChannelClosed |
+            StageError::Fatal(_) => ErrorSeverity::Fatal,
+        }
+    }
+
     /// If the error is fatal the pipeline will stop.
+    ///
+    /// Block errors make the pipeline unwind, other non-fatal errors end the pipeline run, see
+    /// [Pipeline::run_as_fut](crate::Pipeline::run_as_fut).
     pub fn is_fatal(&self) -> bool {
-        matches!(
-            self,
-            StageError::Database(_) |
-                StageError::Download(_) |
-                StageError::DatabaseIntegrity(_) |
-                StageError::StageCheckpoint(_) |
-                StageError::MissingDownloadBuffer |
-                StageError::MissingSyncGap |
-                StageError::ChannelClosed |
-                StageError::Fatal(_)
-        )
+        self.severity().is_fatal()
     }
 }
 
@@ -127,4 +142,22 @@ pub enum PipelineError {
     /// The stage encountered an internal error.
     #[error(transparent)]
     Internal(Box<dyn std::error::Error + Send + Sync>),
+    /// The pipeline was cancelled at a checkpoint, see
+    /// [Pipeline::cancel_handle](crate::Pipeline::cancel_handle).
+    #[error("pipeline was cancelled")]
+    Cancelled,
+}
+
+impl PipelineError {
+    /// Returns the [ErrorSeverity] of the error.
+    pub fn severity(&self) -> ErrorSeverity {
+        match self {
+            PipelineError::Stage(err) => err.severity(),
+            PipelineError::Database(_) |
+            PipelineError::Provider(_) |
+            PipelineError::Channel(_) |
+            PipelineError::Internal(_) |
+            PipelineError::Cancelled => ErrorSeverity::Fatal,
+        }
+    }
 }
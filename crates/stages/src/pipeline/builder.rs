@@ -2,6 +2,7 @@ use crate::{pipeline::BoxedStage, MetricEventsSender, Pipeline, Stage, StageSet}
 use reth_db::database::Database;
 use reth_primitives::{stage::StageId, BlockNumber, B256};
 use reth_provider::ProviderFactory;
+use std::collections::HashMap;
 use tokio::sync::watch;
 
 /// Builds a [`Pipeline`].
@@ -14,6 +15,8 @@ where
     stages: Vec<BoxedStage<DB>>,
     /// The maximum block number to sync to.
     max_block: Option<BlockNumber>,
+    /// The number of blocks a stage may run ahead of the stages after it, by stage.
+    windows: HashMap<StageId, u64>,
     /// A receiver for the current chain tip to sync to.
     tip_tx: Option<watch::Sender<B256>>,
     metrics_tx: Option<MetricEventsSender>,
@@ -54,6 +57,21 @@ where
         self
     }
 
+    /// Limits the given stage to the given number of blocks ahead of the stages after it.
+    ///
+    /// Instead of running the stage to the checkpoint of the stage before it, the pipeline runs it
+    /// at most `window` blocks past the lowest checkpoint of the following stages and repeats the
+    /// pass until the target is reached. In the meantime, the stage may prepare the blocks after
+    /// the window, see [Stage::set_prefetch_target].
+    ///
+    /// A window of zero is ignored.
+    pub fn with_stage_window(mut self, stage_id: StageId, window: u64) -> Self {
+        if window > 0 {
+            self.windows.insert(stage_id, window);
+        }
+        self
+    }
+
     /// Set the tip sender.
     pub fn with_tip_sender(mut self, tip_tx: watch::Sender<B256>) -> Self {
         self.tip_tx = Some(tip_tx);
@@ -68,22 +86,30 @@ where
 
     /// Builds the final [`Pipeline`] using the given database.
     pub fn build(self, provider_factory: ProviderFactory<DB>) -> Pipeline<DB> {
-        let Self { stages, max_block, tip_tx, metrics_tx } = self;
+        let Self { stages, max_block, windows, tip_tx, metrics_tx } = self;
         Pipeline {
             provider_factory,
             stages,
             max_block,
+            windows,
             tip_tx,
             listeners: Default::default(),
             progress: Default::default(),
             metrics_tx,
+            cancel: Default::default(),
         }
     }
 }
 
 impl<DB: Database> Default for PipelineBuilder<DB> {
     fn default() -> Self {
-        Self { stages: Vec::new(), max_block: None, tip_tx: None, metrics_tx: None }
+        Self {
+            stages: Vec::new(),
+            max_block: None,
+            windows: HashMap::new(),
+            tip_tx: None,
+            metrics_tx: None,
+        }
     }
 }
 
@@ -92,6 +118,7 @@ impl<DB: Database> std::fmt::Debug for PipelineBuilder<DB> {
         f.debug_struct("PipelineBuilder")
             .field("stages", &self.stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>())
             .field("max_block", &self.max_block)
+            .field("windows", &self.windows)
             .finish()
     }
 }
@@ -0,0 +1,27 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// A handle to stop a [Pipeline](crate::Pipeline) run, see [Pipeline::cancel_handle].
+///
+/// The pipeline checks the handle before each stage and after each commit of a stage, so a run
+/// stops at its next checkpoint with [PipelineError::Cancelled]. Once cancelled, every following
+/// run of the pipeline is cancelled right away.
+///
+/// [Pipeline::cancel_handle]: crate::Pipeline::cancel_handle
+/// [PipelineError::Cancelled]: crate::PipelineError::Cancelled
+#[derive(Debug, Clone, Default)]
+pub struct PipelineCancelHandle(Arc<AtomicBool>);
+
+impl PipelineCancelHandle {
+    /// Cancels the pipeline.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the pipeline was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
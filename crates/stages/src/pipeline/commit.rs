@@ -0,0 +1,79 @@
+use reth_codecs::Compact;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{stage::StageId, BlockNumber, Header, SealedHeader};
+use reth_provider::{StageCheckpointReader, StageCheckpointWriter};
+
+/// The key under which the [CommitRecord] is stored in the stage progress table.
+pub(crate) const COMMIT_RECORD_ID: StageId = StageId::Other("PipelineCommit");
+
+/// A record of a pipeline operation that spans multiple database transactions.
+///
+/// Stage execution writes all of its tables and the new stage checkpoint in a single transaction,
+/// so it is either committed fully or not at all. Unwinding, on the other hand, commits once per
+/// stage. The record is committed before the first of these transactions and cleared in the
+/// same transaction as the last one, so a record found at startup means that the previous run
+/// crashed in the middle of the operation and the remaining stages still need to be unwound.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CommitRecord {
+    /// The block the stages are unwound to.
+    pub(crate) unwind_to: BlockNumber,
+    /// The header of the bad block that caused the unwind, if any, so it can still be reported
+    /// once the unwind is resumed.
+    pub(crate) bad_block: Option<SealedHeader>,
+}
+
+impl CommitRecord {
+    /// Reads the pending commit record, if any.
+    pub(crate) fn read(provider: impl StageCheckpointReader) -> ProviderResult<Option<Self>> {
+        Ok(provider
+            .get_stage_checkpoint_progress(COMMIT_RECORD_ID)?
+            .and_then(|buf| Self::decode(&buf)))
+    }
+
+    /// Writes the commit record.
+    pub(crate) fn write(&self, provider: impl StageCheckpointWriter) -> ProviderResult<()> {
+        provider.save_stage_checkpoint_progress(COMMIT_RECORD_ID, self.encode())
+    }
+
+    /// Clears the commit record.
+    pub(crate) fn clear(provider: impl StageCheckpointWriter) -> ProviderResult<()> {
+        provider.save_stage_checkpoint_progress(COMMIT_RECORD_ID, Vec::new())
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&self.unwind_to.to_be_bytes());
+        if let Some(bad_block) = &self.bad_block {
+            bad_block.header.clone().to_compact(&mut buf);
+        }
+        buf
+    }
+
+    fn decode(buf: &[u8]) -> Option<Self> {
+        if buf.len() < 8 {
+            return None
+        }
+        let (unwind_to, bad_block) = buf.split_at(8);
+        let unwind_to = BlockNumber::from_be_bytes(unwind_to.try_into().ok()?);
+        let bad_block = (!bad_block.is_empty())
+            .then(|| Header::from_compact(bad_block, bad_block.len()).0.seal_slow());
+        Some(Self { unwind_to, bad_block })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commit_record_roundtrip() {
+        let bad_block = Header { number: 11, ..Default::default() }.seal_slow();
+        for record in [
+            CommitRecord { unwind_to: 10, bad_block: None },
+            CommitRecord { unwind_to: 10, bad_block: Some(bad_block) },
+        ] {
+            assert_eq!(CommitRecord::decode(&record.encode()), Some(record));
+        }
+        assert_eq!(CommitRecord::decode(&[]), None);
+    }
+}
@@ -1,22 +1,24 @@
 use crate::{
-    error::*, BlockErrorKind, ExecInput, ExecOutput, MetricEvent, MetricEventsSender, Stage,
-    StageError, StageExt, UnwindInput,
+    error::*, util::opt, BlockErrorKind, ExecInput, ExecOutput, MetricEvent, MetricEventsSender,
+    Stage, StageError, StageExt, UnwindInput,
 };
 use futures_util::Future;
 use reth_db::database::Database;
 use reth_primitives::{
     constants::BEACON_CONSENSUS_REORG_UNWIND_DEPTH,
     stage::{StageCheckpoint, StageId},
-    BlockNumber, B256,
+    BlockNumber, SealedHeader, B256,
 };
 use reth_provider::{ProviderFactory, StageCheckpointReader, StageCheckpointWriter};
 use reth_tokio_util::EventListeners;
-use std::pin::Pin;
+use std::{collections::HashMap, pin::Pin, time::Duration};
 use tokio::sync::watch;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::*;
 
 mod builder;
+mod cancel;
+mod commit;
 mod ctrl;
 mod event;
 mod progress;
@@ -24,6 +26,8 @@ mod set;
 
 pub use crate::pipeline::ctrl::ControlFlow;
 pub use builder::*;
+pub use cancel::PipelineCancelHandle;
+use commit::CommitRecord;
 pub use event::*;
 use progress::*;
 pub use set::*;
@@ -31,6 +35,16 @@ pub use set::*;
 /// A container for a queued stage.
 pub(crate) type BoxedStage<DB> = Box<dyn Stage<DB>>;
 
+/// The delay before the first retry of a retryable error in [Pipeline::run], doubled for every
+/// consecutive retry.
+const INITIAL_RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// The maximum delay between two retries in [Pipeline::run].
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// The number of consecutive retryable errors after which [Pipeline::run] returns the error.
+const MAX_RETRIES: u32 = 10;
+
 /// The future that returns the owned pipeline and the result of the pipeline run. See
 /// [Pipeline::run_as_fut].
 pub type PipelineFut<DB> = Pin<Box<dyn Future<Output = PipelineWithResult<DB>> + Send>>;
@@ -57,6 +71,18 @@ pub type PipelineWithResult<DB> = (Pipeline<DB>, Result<ControlFlow, PipelineErr
 /// pipeline will unwind the stages in reverse order of execution. It is also possible to
 /// request an unwind manually (see [Pipeline::unwind]).
 ///
+/// The output of a stage and its checkpoint are always committed in the same database transaction,
+/// while an unwind spans one transaction per stage. An unwind is therefore recorded in the database
+/// before it starts, and an unwind that was interrupted is finished before the pipeline runs again.
+///
+/// # Windows
+///
+/// By default, a stage runs until it reaches the checkpoint of the stage before it, before the next
+/// stage starts. A stage can instead be limited to a window of blocks ahead of the stages after it
+/// (see [PipelineBuilder::with_stage_window]), so the pipeline runs several passes over smaller
+/// ranges. This lets a downloading stage, like the bodies stage, download the next range in the
+/// background while the following stages, like the execution, process the current one.
+///
 /// # Defaults
 ///
 /// The [DefaultStages](crate::sets::DefaultStages) are used to fully sync reth.
@@ -67,6 +93,8 @@ pub struct Pipeline<DB: Database> {
     stages: Vec<BoxedStage<DB>>,
     /// The maximum block number to sync to.
     max_block: Option<BlockNumber>,
+    /// The number of blocks a stage may run ahead of the stages after it, by stage.
+    windows: HashMap<StageId, u64>,
     /// All listeners for events the pipeline emits.
     listeners: EventListeners<PipelineEvent>,
     /// Keeps track of the progress of the pipeline.
@@ -74,6 +102,8 @@ pub struct Pipeline<DB: Database> {
     /// A receiver for the current chain tip to sync to.
     tip_tx: Option<watch::Sender<B256>>,
     metrics_tx: Option<MetricEventsSender>,
+    /// Stops the pipeline at its next checkpoint once cancelled.
+    cancel: PipelineCancelHandle,
 }
 
 impl<DB> Pipeline<DB>
@@ -99,6 +129,11 @@ where
         });
     }
 
+    /// Returns a handle to cancel the pipeline, even while it's running.
+    pub fn cancel_handle(&self) -> PipelineCancelHandle {
+        self.cancel.clone()
+    }
+
     /// Listen for events on the pipeline.
     pub fn events(&mut self) -> UnboundedReceiverStream<PipelineEvent> {
         self.listeners.new_listener()
@@ -122,6 +157,9 @@ where
 
     /// Consume the pipeline and run it until it reaches the provided tip, if set. Return the
     /// pipeline and its result as a future.
+    ///
+    /// Retryable stage errors end the run, so the caller can run the pipeline again after a
+    /// backoff.
     #[track_caller]
     pub fn run_as_fut(mut self, tip: Option<B256>) -> PipelineFut<DB> {
         // TODO: fix this in a follow up PR. ideally, consensus engine would be responsible for
@@ -132,7 +170,12 @@ where
             if let Some(tip) = tip {
                 self.set_tip(tip);
             }
-            let result = self.run_loop().await;
+            let result = match self.resume_interrupted_unwind() {
+                // the bad block of the resumed unwind is reported like the one of a new unwind
+                Ok(Some(ctrl)) => Ok(ctrl),
+                Ok(None) => self.run_loop().await,
+                Err(err) => Err(err),
+            };
             trace!(target: "sync::pipeline", ?tip, ?result, "Pipeline finished");
             (self, result)
         })
@@ -140,11 +183,35 @@ where
 
     /// Run the pipeline in an infinite loop. Will terminate early if the user has specified
     /// a `max_block` in the pipeline.
+    ///
+    /// Retryable stage errors are retried with an exponential backoff, up to [MAX_RETRIES] times
+    /// in a row.
     pub async fn run(&mut self) -> Result<(), PipelineError> {
         let _ = self.register_metrics(); // ignore error
+        self.resume_interrupted_unwind()?;
 
+        let mut retries = 0;
         loop {
-            let next_action = self.run_loop().await?;
+            let next_action = match self.run_loop().await {
+                Ok(next_action) => {
+                    retries = 0;
+                    next_action
+                }
+                Err(err) if err.severity().is_retryable() && retries < MAX_RETRIES => {
+                    let backoff = retry_backoff(retries);
+                    retries += 1;
+                    warn!(
+                        target: "sync::pipeline",
+                        %err,
+                        ?backoff,
+                        retries,
+                        "Pipeline encountered a non-fatal error. Retrying..."
+                    );
+                    tokio::time::sleep(backoff).await;
+                    continue
+                }
+                Err(err) => return Err(err),
+            };
 
             // Terminate the loop early if it's reached the maximum user
             // configured block.
@@ -177,56 +244,133 @@ where
     /// This will be [ControlFlow::Continue] or [ControlFlow::NoProgress] of the _last_ stage in the
     /// pipeline (for example the `Finish` stage). Or [ControlFlow::Unwind] of the stage that caused
     /// the unwind.
+    ///
+    /// Returns [PipelineError::Cancelled] if the pipeline was cancelled, see
+    /// [Pipeline::cancel_handle].
+    ///
+    /// If a stage was held back by its window, the pass is repeated as long as the stages make
+    /// progress.
     pub async fn run_loop(&mut self) -> Result<ControlFlow, PipelineError> {
-        let mut previous_stage = None;
-        for stage_index in 0..self.stages.len() {
-            let stage = &self.stages[stage_index];
-            let stage_id = stage.id();
+        loop {
+            let mut previous_stage = None;
+            // the progress is only recorded for the last pass
+            let mut reached_blocks = Vec::with_capacity(self.stages.len());
+            let mut held_back = false;
+            let mut made_progress = false;
+
+            for stage_index in 0..self.stages.len() {
+                self.ensure_not_cancelled()?;
+
+                let stage = &self.stages[stage_index];
+                let stage_id = stage.id();
+
+                let target = self.max_block.or(previous_stage);
+                let window_target = self.window_target(stage_index, target)?;
+                held_back |= window_target.is_some();
 
-            trace!(target: "sync::pipeline", stage = %stage_id, "Executing stage");
-            let next = self.execute_stage_to_completion(previous_stage, stage_index).await?;
+                trace!(target: "sync::pipeline", stage = %stage_id, ?window_target, "Executing stage");
+                let next =
+                    self.execute_stage_to_completion(window_target.or(target), stage_index).await?;
 
-            trace!(target: "sync::pipeline", stage = %stage_id, ?next, "Completed stage");
+                trace!(target: "sync::pipeline", stage = %stage_id, ?next, "Completed stage");
 
-            match next {
-                ControlFlow::NoProgress { block_number } => {
-                    if let Some(block_number) = block_number {
-                        self.progress.update(block_number);
+                match next {
+                    ControlFlow::NoProgress { block_number } => {
+                        reached_blocks.extend(block_number);
+                    }
+                    ControlFlow::Continue { block_number } => {
+                        made_progress = true;
+                        reached_blocks.push(block_number);
+                    }
+                    ControlFlow::Unwind { target, bad_block } => {
+                        self.unwind(target, Some(&bad_block))?;
+                        return Ok(ControlFlow::Unwind { target, bad_block });
                     }
                 }
-                ControlFlow::Continue { block_number } => self.progress.update(block_number),
-                ControlFlow::Unwind { target, bad_block } => {
-                    self.unwind(target, Some(bad_block.number))?;
-                    return Ok(ControlFlow::Unwind { target, bad_block });
+
+                previous_stage = Some(
+                    self.provider_factory
+                        .provider()?
+                        .get_stage_checkpoint(stage_id)?
+                        .unwrap_or_default()
+                        .block_number,
+                );
+            }
+
+            if !(held_back && made_progress) {
+                for block_number in reached_blocks {
+                    self.progress.update(block_number);
                 }
+                return Ok(self.progress.next_ctrl())
             }
+            trace!(target: "sync::pipeline", "Stages were held back by their windows, running another pass");
+        }
+    }
 
-            previous_stage = Some(
-                self.provider_factory
-                    .provider()?
-                    .get_stage_checkpoint(stage_id)?
-                    .unwrap_or_default()
-                    .block_number,
-            );
+    /// Returns the target of the stage if it's limited by its window, see
+    /// [PipelineBuilder::with_stage_window].
+    ///
+    /// The window starts at the lowest checkpoint of the stages after the stage. If the stage is
+    /// held back, it's told the full target, so it can prepare the blocks after the window.
+    fn window_target(
+        &mut self,
+        stage_index: usize,
+        target: Option<BlockNumber>,
+    ) -> Result<Option<BlockNumber>, PipelineError> {
+        let Some(target) = target else { return Ok(None) };
+        let Some(window) = self.windows.get(&self.stages[stage_index].id()).copied() else {
+            return Ok(None)
+        };
+
+        let provider = self.provider_factory.provider()?;
+        let mut consumer_checkpoint = None;
+        for stage in &self.stages[stage_index + 1..] {
+            let checkpoint = provider.get_stage_checkpoint(stage.id())?.unwrap_or_default();
+            consumer_checkpoint = opt::min(consumer_checkpoint, checkpoint.block_number);
         }
 
-        Ok(self.progress.next_ctrl())
+        let Some(window_target) = consumer_checkpoint
+            .map(|checkpoint| checkpoint.saturating_add(window))
+            .filter(|window_target| *window_target < target)
+        else {
+            return Ok(None)
+        };
+        self.stages[stage_index].set_prefetch_target(target);
+        Ok(Some(window_target))
     }
 
     /// Unwind the stages to the target block.
     ///
-    /// If the unwind is due to a bad block the number of that block should be specified.
+    /// If the unwind is due to a bad block the header of that block should be specified.
     pub fn unwind(
         &mut self,
         to: BlockNumber,
-        bad_block: Option<BlockNumber>,
+        bad_block: Option<&SealedHeader>,
     ) -> Result<(), PipelineError> {
-        // Unwind stages in reverse order of execution
-        let unwind_pipeline = self.stages.iter_mut().rev();
+        let record = CommitRecord { unwind_to: to, bad_block: bad_block.cloned() };
+        let bad_block = bad_block.map(|block| block.number);
 
+        // Each stage is unwound in its own transaction, so record the unwind before touching any
+        // stage to be able to resume it if the node crashes midway. The record is cleared in the
+        // last transaction of the last stage that is unwound.
         let mut provider_rw = self.provider_factory.provider_rw()?;
+        let mut last_stage = None;
+        for (index, stage) in self.stages.iter().rev().enumerate() {
+            let checkpoint = provider_rw.get_stage_checkpoint(stage.id())?.unwrap_or_default();
+            if checkpoint.block_number > to {
+                last_stage = Some(index);
+            }
+        }
+        if last_stage.is_some() {
+            record.write(&*provider_rw)?;
+        } else {
+            CommitRecord::clear(&*provider_rw)?;
+        }
+        provider_rw.commit()?;
+        provider_rw = self.provider_factory.provider_rw()?;
 
-        for stage in unwind_pipeline {
+        // Unwind stages in reverse order of execution
+        for (index, stage) in self.stages.iter_mut().rev().enumerate() {
             let stage_id = stage.id();
             let span = info_span!("Unwinding", stage = %stage_id);
             let _enter = span.enter();
@@ -276,6 +420,9 @@ where
                             });
                         }
                         provider_rw.save_stage_checkpoint(stage_id, checkpoint)?;
+                        if checkpoint.block_number <= to && last_stage == Some(index) {
+                            CommitRecord::clear(&*provider_rw)?;
+                        }
 
                         self.listeners
                             .notify(PipelineEvent::Unwound { stage_id, result: unwind_output });
@@ -294,9 +441,41 @@ where
         Ok(())
     }
 
+    /// Returns [PipelineError::Cancelled] if the pipeline was cancelled.
+    fn ensure_not_cancelled(&self) -> Result<(), PipelineError> {
+        if self.cancel.is_cancelled() {
+            debug!(target: "sync::pipeline", "Pipeline cancelled");
+            return Err(PipelineError::Cancelled)
+        }
+        Ok(())
+    }
+
+    /// Finishes the unwind that was interrupted by a crash or shutdown of the node, if any.
+    ///
+    /// Returns [ControlFlow::Unwind] if the unwind was caused by a bad block. See
+    /// [Pipeline::unwind].
+    fn resume_interrupted_unwind(&mut self) -> Result<Option<ControlFlow>, PipelineError> {
+        let Some(CommitRecord { unwind_to, bad_block }) =
+            CommitRecord::read(&self.provider_factory)?
+        else {
+            return Ok(None)
+        };
+        warn!(
+            target: "sync::pipeline",
+            unwind_to,
+            bad_block = ?bad_block.as_ref().map(|block| block.num_hash()),
+            "Resuming interrupted unwind"
+        );
+        self.unwind(unwind_to, bad_block.as_ref())?;
+        Ok(bad_block.map(|bad_block| ControlFlow::Unwind {
+            target: unwind_to,
+            bad_block: Box::new(bad_block),
+        }))
+    }
+
     async fn execute_stage_to_completion(
         &mut self,
-        previous_stage: Option<BlockNumber>,
+        target: Option<BlockNumber>,
         stage_index: usize,
     ) -> Result<ControlFlow, PipelineError> {
         let total_stages = self.stages.len();
@@ -304,7 +483,6 @@ where
         let stage = &mut self.stages[stage_index];
         let stage_id = stage.id();
         let mut made_progress = false;
-        let target = self.max_block.or(previous_stage);
 
         loop {
             let prev_checkpoint = self.provider_factory.get_stage_checkpoint(stage_id)?;
@@ -382,6 +560,9 @@ where
                             ControlFlow::NoProgress { block_number: Some(block_number) }
                         });
                     }
+
+                    // the progress of the stage is committed, so this is a safe point to stop
+                    self.ensure_not_cancelled()?;
                 }
                 Err(err) => {
                     drop(provider_rw);
@@ -458,26 +639,37 @@ fn on_stage_error<DB: Database>(
                 }))
             }
         }
-    } else if err.is_fatal() {
-        error!(target: "sync::pipeline", stage = %stage_id, "Stage encountered a fatal error: {err}");
-        Err(err.into())
     } else {
-        // On other errors we assume they are recoverable if we discard the
-        // transaction and run the stage again.
-        warn!(
-            target: "sync::pipeline",
-            stage = %stage_id,
-            "Stage encountered a non-fatal error: {err}. Retrying..."
-        );
-        Ok(None)
+        if err.is_fatal() {
+            error!(
+                target: "sync::pipeline",
+                stage = %stage_id,
+                "Stage encountered a fatal error: {err}"
+            );
+        } else {
+            // The error is transient, e.g. there are no peers to download from. The pass is ended,
+            // so the caller decides when to run the stage again.
+            warn!(
+                target: "sync::pipeline",
+                stage = %stage_id,
+                "Stage encountered a non-fatal error: {err}"
+            );
+        }
+        Err(err.into())
     }
 }
 
+/// Returns the delay before the retry that follows `retries` consecutive retries.
+fn retry_backoff(retries: u32) -> Duration {
+    INITIAL_RETRY_BACKOFF.saturating_mul(2u32.saturating_pow(retries)).min(MAX_RETRY_BACKOFF)
+}
+
 impl<DB: Database> std::fmt::Debug for Pipeline<DB> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Pipeline")
             .field("stages", &self.stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>())
             .field("max_block", &self.max_block)
+            .field("windows", &self.windows)
             .field("listeners", &self.listeners)
             .finish()
     }
@@ -490,6 +682,7 @@ mod tests {
     use assert_matches::assert_matches;
     use reth_interfaces::{
         consensus,
+        p2p::error::DownloadError,
         provider::ProviderError,
         test_utils::{generators, generators::random_header},
     };
@@ -577,6 +770,75 @@ mod tests {
         );
     }
 
+    /// A cancelled pipeline doesn't run any stage.
+    #[tokio::test]
+    async fn cancelled_pipeline() {
+        let provider_factory = create_test_provider_factory();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .with_max_block(10)
+            .build(provider_factory.clone());
+
+        pipeline.cancel_handle().cancel();
+        assert_matches!(pipeline.run_loop().await, Err(PipelineError::Cancelled));
+        assert_eq!(provider_factory.get_stage_checkpoint(StageId::Other("A")).unwrap(), None);
+    }
+
+    /// A stage with a window runs at most the window ahead of the stages after it, and the
+    /// pipeline repeats the pass until the stage reaches its target.
+    #[tokio::test]
+    async fn run_pipeline_with_stage_window() {
+        let provider_factory = create_test_provider_factory();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("B"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(5), done: true }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("C"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(5), done: true }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .with_stage_window(StageId::Other("B"), 5)
+            .build(provider_factory);
+        let events = pipeline.events();
+
+        assert_eq!(pipeline.run_loop().await.unwrap(), ControlFlow::Continue { block_number: 10 });
+        // only the last pass is recorded
+        assert_eq!(pipeline.minimum_block_number(), Some(10));
+        drop(pipeline);
+
+        let targets = events
+            .filter_map(|event| match event {
+                PipelineEvent::Run { stage_id, target, .. } => Some((stage_id, target)),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            targets,
+            vec![
+                (StageId::Other("A"), None),
+                (StageId::Other("B"), Some(5)),
+                (StageId::Other("C"), Some(5)),
+                (StageId::Other("A"), None),
+                (StageId::Other("B"), Some(10)),
+                (StageId::Other("C"), Some(10)),
+            ]
+        );
+    }
+
     /// Unwinds a simple pipeline.
     #[tokio::test]
     async fn unwind_pipeline() {
@@ -871,6 +1133,48 @@ mod tests {
         );
     }
 
+    /// Checks that an unwind interrupted midway is resumed when the pipeline starts, and that the
+    /// bad block that caused it is still reported.
+    #[tokio::test]
+    async fn resume_interrupted_unwind() {
+        let provider_factory = create_test_provider_factory();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true }))
+                    .add_unwind(Ok(UnwindOutput { checkpoint: StageCheckpoint::new(1) })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("B"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .with_max_block(10)
+            .build(provider_factory);
+        pipeline.run().await.expect("Could not run pipeline");
+
+        // Simulate a crash after stage B was unwound, but before stage A was.
+        let bad_block = random_header(&mut generators::rng(), 5, None);
+        let provider_rw = pipeline.provider_factory.provider_rw().unwrap();
+        CommitRecord { unwind_to: 1, bad_block: Some(bad_block.clone()) }
+            .write(&*provider_rw)
+            .unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Other("B"), StageCheckpoint::new(1)).unwrap();
+        provider_rw.commit().unwrap();
+
+        assert_eq!(
+            pipeline.resume_interrupted_unwind().expect("Could not resume unwind"),
+            Some(ControlFlow::Unwind { target: 1, bad_block: Box::new(bad_block) })
+        );
+
+        let provider = pipeline.provider_factory.provider().unwrap();
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::Other("A")).unwrap(),
+            Some(StageCheckpoint::new(1))
+        );
+        assert_eq!(CommitRecord::read(&provider).unwrap(), None);
+    }
+
     /// Checks that the pipeline re-runs stages on non-fatal errors and stops on fatal ones.
     #[tokio::test]
     async fn pipeline_error_handling() {
@@ -887,6 +1191,48 @@ mod tests {
         let result = pipeline.run().await;
         assert_matches!(result, Ok(()));
 
+        // Non-fatal errors end a single run, so they can be retried by the caller
+        let provider_factory = create_test_provider_factory();
+        let pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("NonFatal"))
+                    .add_exec(Err(StageError::Recoverable(Box::new(std::fmt::Error))))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .with_max_block(10)
+            .build(provider_factory);
+        let (pipeline, result) = pipeline.run_as_fut(None).await;
+        assert_matches!(result, Err(PipelineError::Stage(StageError::Recoverable(_))));
+        let (_, result) = pipeline.run_as_fut(None).await;
+        assert_matches!(result, Ok(ControlFlow::Continue { block_number: 10 }));
+
+        // Invalid responses are retried with other peers
+        let provider_factory = create_test_provider_factory();
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("InvalidResponse"))
+                    .add_exec(Err(StageError::Download(DownloadError::EmptyResponse)))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .with_max_block(10)
+            .build(provider_factory);
+        let result = pipeline.run().await;
+        assert_matches!(result, Ok(()));
+
+        // Persistent non-fatal errors are returned once the retries are exhausted
+        let provider_factory = create_test_provider_factory();
+        let mut stage = TestStage::new(StageId::Other("NonFatal"));
+        for _ in 0..=MAX_RETRIES {
+            stage = stage.add_exec(Err(StageError::Download(DownloadError::Timeout)));
+        }
+        let mut pipeline = Pipeline::builder().add_stage(stage).build(provider_factory);
+        tokio::time::pause();
+        let result = pipeline.run().await;
+        assert_matches!(
+            result,
+            Err(PipelineError::Stage(StageError::Download(DownloadError::Timeout)))
+        );
+
         // Fatal
         let provider_factory = create_test_provider_factory();
         let mut pipeline = Pipeline::builder()
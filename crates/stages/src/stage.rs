@@ -238,6 +238,13 @@ pub trait Stage<DB: Database>: Send + Sync {
         input: ExecInput,
     ) -> Result<ExecOutput, StageError>;
 
+    /// Sets the block the stage may prepare for beyond the target of its next [ExecInput], for
+    /// example by downloading ahead in the background.
+    ///
+    /// The pipeline calls this before it executes a stage that is held back by its window, see
+    /// [PipelineBuilder::with_stage_window](crate::PipelineBuilder::with_stage_window).
+    fn set_prefetch_target(&mut self, _target: BlockNumber) {}
+
     /// Unwind the stage.
     fn unwind(
         &mut self,
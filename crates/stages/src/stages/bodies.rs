@@ -9,9 +9,15 @@ use reth_db::{
     DatabaseError,
 };
 use reth_interfaces::p2p::bodies::{downloader::BodyDownloader, response::BlockResponse};
-use reth_primitives::stage::{EntitiesCheckpoint, StageCheckpoint, StageId};
-use reth_provider::DatabaseProviderRW;
-use std::task::{ready, Context, Poll};
+use reth_primitives::{
+    stage::{EntitiesCheckpoint, StageCheckpoint, StageId},
+    Address, BlockNumber,
+};
+use reth_provider::{DatabaseProviderRW, HistoryWriter};
+use std::{
+    collections::BTreeMap,
+    task::{ready, Context, Poll},
+};
 use tracing::*;
 
 // TODO(onbjerg): Metrics and events (gradual status for e.g. CLI)
@@ -36,6 +42,8 @@ use tracing::*;
 /// - [`BlockBodies`][reth_db::tables::BlockBodyIndices]
 /// - [`Transactions`][reth_db::tables::Transactions]
 /// - [`TransactionBlock`][reth_db::tables::TransactionBlock]
+/// - [`BlockWithdrawals`][reth_db::tables::BlockWithdrawals]
+/// - [`WithdrawalHistory`][reth_db::tables::WithdrawalHistory]
 ///
 /// # Genesis
 ///
@@ -45,18 +53,26 @@ use tracing::*;
 /// - The [`BlockOmmers`][reth_db::tables::BlockOmmers] table
 /// - The [`BlockBodies`][reth_db::tables::BlockBodyIndices] table
 /// - The [`Transactions`][reth_db::tables::Transactions] table
+///
+/// # Prefetching
+///
+/// If the pipeline holds the stage back by a window, the downloader keeps downloading towards the
+/// prefetch target (see [Stage::set_prefetch_target]) while the following stages run. Downloaded
+/// blocks past the target of an execution are kept for the next one.
 #[derive(Debug)]
 pub struct BodyStage<D: BodyDownloader> {
     /// The body downloader.
     downloader: D,
     /// Block response buffer.
     buffer: Option<Vec<BlockResponse>>,
+    /// The block the downloader downloads towards, if it's past the target of the stage.
+    prefetch_target: Option<BlockNumber>,
 }
 
 impl<D: BodyDownloader> BodyStage<D> {
     /// Create new bodies stage from downloader.
     pub fn new(downloader: D) -> Self {
-        Self { downloader, buffer: None }
+        Self { downloader, buffer: None, prefetch_target: None }
     }
 }
 
@@ -71,12 +87,23 @@ impl<DB: Database, D: BodyDownloader> Stage<DB> for BodyStage<D> {
         cx: &mut Context<'_>,
         input: ExecInput,
     ) -> Poll<Result<(), StageError>> {
-        if input.target_reached() || self.buffer.is_some() {
+        if input.target_reached() {
             return Poll::Ready(Ok(()))
         }
 
+        // Blocks left over from the previous execution are only used if they continue the
+        // checkpoint, the bodies could have been written by someone else in the meantime.
+        if let Some(buffer) = &self.buffer {
+            if buffer.first().map(|response| response.block_number()) == Some(input.next_block()) {
+                return Poll::Ready(Ok(()))
+            }
+            self.buffer = None;
+        }
+
         // Update the header range on the downloader
-        self.downloader.set_download_range(input.next_block_range())?;
+        let (from_block, to_block) = input.next_block_range().into_inner();
+        let download_to = self.prefetch_target.map_or(to_block, |target| target.max(to_block));
+        self.downloader.set_download_range(from_block..=download_to)?;
 
         // Poll next downloader item.
         let maybe_next_result = ready!(self.downloader.try_poll_next_unpin(cx));
@@ -119,9 +146,14 @@ impl<DB: Database, D: BodyDownloader> Stage<DB> for BodyStage<D> {
 
         debug!(target: "sync::stages::bodies", stage_progress = from_block, target = to_block, start_tx_id = next_tx_num, "Commencing sync");
 
-        let buffer = self.buffer.take().ok_or(StageError::MissingDownloadBuffer)?;
+        let mut buffer = self.buffer.take().ok_or(StageError::MissingDownloadBuffer)?;
+        // keep the prefetched blocks past the target for the next execution
+        if let Some(split) = buffer.iter().position(|response| response.block_number() > to_block) {
+            self.buffer = Some(buffer.split_off(split));
+        }
         trace!(target: "sync::stages::bodies", bodies_len = buffer.len(), "Writing blocks");
         let mut highest_block = from_block;
+        let mut withdrawal_transitions = BTreeMap::<Address, Vec<u64>>::new();
         for response in buffer {
             // Write block
             let block_number = response.block_number();
@@ -157,6 +189,13 @@ impl<DB: Database, D: BodyDownloader> Stage<DB> for BodyStage<D> {
                     // Write withdrawals if any
                     if let Some(withdrawals) = block.withdrawals {
                         if !withdrawals.is_empty() {
+                            for withdrawal in &withdrawals {
+                                let blocks =
+                                    withdrawal_transitions.entry(withdrawal.address).or_default();
+                                if blocks.last() != Some(&block_number) {
+                                    blocks.push(block_number);
+                                }
+                            }
                             withdrawals_cursor
                                 .append(block_number, StoredBlockWithdrawals { withdrawals })?;
                         }
@@ -171,6 +210,9 @@ impl<DB: Database, D: BodyDownloader> Stage<DB> for BodyStage<D> {
             highest_block = block_number;
         }
 
+        // Index the withdrawals by recipient
+        provider.insert_withdrawal_history_index(withdrawal_transitions)?;
+
         // The stage is "done" if:
         // - We got fewer blocks than our target
         // - We reached our target and the target was not limited by the batch size of the stage
@@ -182,6 +224,10 @@ impl<DB: Database, D: BodyDownloader> Stage<DB> for BodyStage<D> {
         })
     }
 
+    fn set_prefetch_target(&mut self, target: BlockNumber) {
+        self.prefetch_target = Some(target);
+    }
+
     /// Unwind the stage.
     fn unwind(
         &mut self,
@@ -189,51 +235,28 @@ impl<DB: Database, D: BodyDownloader> Stage<DB> for BodyStage<D> {
         input: UnwindInput,
     ) -> Result<UnwindOutput, StageError> {
         self.buffer.take();
+        self.prefetch_target.take();
 
         let tx = provider.tx_ref();
-        // Cursors to unwind bodies, ommers
-        let mut body_cursor = tx.cursor_write::<tables::BlockBodyIndices>()?;
-        let mut transaction_cursor = tx.cursor_write::<tables::Transactions>()?;
-        let mut ommers_cursor = tx.cursor_write::<tables::BlockOmmers>()?;
-        let mut withdrawals_cursor = tx.cursor_write::<tables::BlockWithdrawals>()?;
-        // Cursors to unwind transitions
-        let mut tx_block_cursor = tx.cursor_write::<tables::TransactionBlock>()?;
-
-        let mut rev_walker = body_cursor.walk_back(None)?;
-        while let Some((number, block_meta)) = rev_walker.next().transpose()? {
-            if number <= input.unwind_to {
-                break
-            }
-
-            // Delete the ommers entry if any
-            if ommers_cursor.seek_exact(number)?.is_some() {
-                ommers_cursor.delete_current()?;
-            }
-
-            // Delete the withdrawals entry if any
-            if withdrawals_cursor.seek_exact(number)?.is_some() {
-                withdrawals_cursor.delete_current()?;
-            }
-
-            // Delete all transaction to block values.
-            if !block_meta.is_empty() &&
-                tx_block_cursor.seek_exact(block_meta.last_tx_num())?.is_some()
-            {
-                tx_block_cursor.delete_current()?;
-            }
-
-            // Delete all transactions that belong to this block
-            for tx_id in block_meta.tx_num_range() {
-                // First delete the transaction
-                if transaction_cursor.seek_exact(tx_id)?.is_some() {
-                    transaction_cursor.delete_current()?;
-                }
-            }
-
-            // Delete the current body value
-            rev_walker.delete_current()?;
+        let unwound_blocks = input.unwind_to + 1..;
+
+        // Unwind the withdrawal history index, while the withdrawals are still present
+        provider.unwind_withdrawal_history_indices(input.unwind_to + 1..=BlockNumber::MAX)?;
+
+        // Delete all transactions and transaction to block values that belong to the unwound
+        // blocks
+        if let Some((_, first_body)) =
+            tx.cursor_read::<tables::BlockBodyIndices>()?.seek(input.unwind_to + 1)?
+        {
+            tx.clear_range::<tables::Transactions>(first_body.first_tx_num..)?;
+            tx.clear_range::<tables::TransactionBlock>(first_body.first_tx_num..)?;
         }
 
+        // Delete the ommers, withdrawals and body values
+        tx.clear_range::<tables::BlockOmmers>(unwound_blocks.clone())?;
+        tx.clear_range::<tables::BlockWithdrawals>(unwound_blocks.clone())?;
+        tx.clear_range::<tables::BlockBodyIndices>(unwound_blocks)?;
+
         Ok(UnwindOutput {
             checkpoint: StageCheckpoint::new(input.unwind_to)
                 .with_entities_stage_checkpoint(stage_checkpoint(provider)?),
@@ -18,8 +18,8 @@ use reth_primitives::{
     BlockNumber, Header, PruneModes, U256,
 };
 use reth_provider::{
-    BlockReader, DatabaseProviderRW, ExecutorFactory, HeaderProvider, LatestStateProviderRef,
-    OriginalValuesKnown, ProviderError, TransactionVariant,
+    BlockReader, BytecodeRefCounter, DatabaseProviderRW, ExecutorFactory, HeaderProvider,
+    LatestStateProviderRef, OriginalValuesKnown, ProviderError, TransactionVariant,
 };
 use std::{
     ops::RangeInclusive,
@@ -47,6 +47,7 @@ use tracing::*;
 /// - [tables::PlainAccountState]
 /// - [tables::PlainStorageState]
 /// - [tables::Bytecodes]
+/// - [tables::BytecodeRefs]
 /// - [tables::AccountChangeSet]
 /// - [tables::StorageChangeSet]
 ///
@@ -55,14 +56,20 @@ use tracing::*;
 /// - [tables::AccountHistory] to remove change set and apply old values to
 /// - [tables::PlainAccountState] [tables::StorageHistory] to remove change set and apply old values
 /// to [tables::PlainStorageState]
+///
+/// An [ExecutionIndexer] can be set to write what an inspector of the executor collected while
+/// blocks were executed.
 // false positive, we cannot derive it if !DB: Debug.
 #[allow(missing_debug_implementations)]
-pub struct ExecutionStage<EF: ExecutorFactory> {
+pub struct ExecutionStage<EF: ExecutorFactory, I = NoopExecutionIndexer> {
     metrics_tx: Option<MetricEventsSender>,
     /// The stage's internal executor
     executor_factory: EF,
     /// The commit thresholds of the execution stage.
     thresholds: ExecutionStageThresholds,
+    /// Adjusts the maximum number of blocks per batch, overriding
+    /// [ExecutionStageThresholds::max_blocks].
+    adaptive_batch_size: Option<AdaptiveBatchSize>,
     /// The highest threshold (in number of blocks) for switching between incremental
     /// and full calculations across [`super::MerkleStage`], [`super::AccountHashingStage`] and
     /// [`super::StorageHashingStage`]. This is required to figure out if can prune or not
@@ -70,6 +77,8 @@ pub struct ExecutionStage<EF: ExecutorFactory> {
     external_clean_threshold: u64,
     /// Pruning configuration.
     prune_modes: PruneModes,
+    /// Writes the data collected by the inspector of the executor.
+    indexer: I,
 }
 
 impl<EF: ExecutorFactory> ExecutionStage<EF> {
@@ -85,7 +94,9 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
             external_clean_threshold,
             executor_factory,
             thresholds,
+            adaptive_batch_size: None,
             prune_modes,
+            indexer: NoopExecutionIndexer,
         }
     }
 
@@ -100,23 +111,55 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
             PruneModes::none(),
         )
     }
+}
 
+impl<EF: ExecutorFactory, I> ExecutionStage<EF, I> {
     /// Set the metric events sender.
     pub fn with_metrics_tx(mut self, metrics_tx: MetricEventsSender) -> Self {
         self.metrics_tx = Some(metrics_tx);
         self
     }
 
+    /// Set the [AdaptiveBatchSize] that adjusts the maximum number of blocks per batch.
+    pub fn with_adaptive_batch_size(
+        mut self,
+        adaptive_batch_size: Option<AdaptiveBatchSize>,
+    ) -> Self {
+        self.adaptive_batch_size = adaptive_batch_size;
+        self
+    }
+
+    /// Set the [ExecutionIndexer] that writes the data collected by the inspector of the executor.
+    ///
+    /// The inspector and the transactions it inspects are configured on the executor factory.
+    pub fn with_indexer<I2>(self, indexer: I2) -> ExecutionStage<EF, I2> {
+        ExecutionStage {
+            metrics_tx: self.metrics_tx,
+            executor_factory: self.executor_factory,
+            thresholds: self.thresholds,
+            adaptive_batch_size: self.adaptive_batch_size,
+            external_clean_threshold: self.external_clean_threshold,
+            prune_modes: self.prune_modes,
+            indexer,
+        }
+    }
+
     /// Execute the stage.
     pub fn execute_inner<DB: Database>(
         &mut self,
         provider: &DatabaseProviderRW<DB>,
         input: ExecInput,
-    ) -> Result<ExecOutput, StageError> {
+    ) -> Result<ExecOutput, StageError>
+    where
+        I: ExecutionIndexer<DB>,
+    {
         if input.target_reached() {
             return Ok(ExecOutput::done(input.checkpoint()));
         }
 
+        // discard what was collected for blocks of a failed attempt
+        self.indexer.reset();
+
         let start_block = input.next_block();
         let max_block = input.target();
         let prune_modes = self.adjust_prune_modes(provider, start_block, max_block)?;
@@ -138,6 +181,8 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
         // Execute block range
 
         let mut cumulative_gas = 0;
+        let mut bundle_size_hint = 0;
+        let thresholds = self.batch_thresholds();
 
         for block_number in start_block..=max_block {
             let time = Instant::now();
@@ -159,9 +204,16 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
 
             let time = Instant::now();
             // Execute the block
-            executor.execute_and_verify_receipt(&block, td).map_err(|error| StageError::Block {
-                block: Box::new(block.header.clone().seal_slow()),
-                error: BlockErrorKind::Execution(error),
+            executor.execute_and_verify_receipt(&block, td).map_err(|error| {
+                if error.is_fatal() {
+                    // Not caused by the block, so there is nothing to unwind.
+                    StageError::Fatal(Box::new(error))
+                } else {
+                    StageError::Block {
+                        block: Box::new(block.header.clone().seal_slow()),
+                        error: BlockErrorKind::Execution(error),
+                    }
+                }
             })?;
 
             execution_duration += time.elapsed();
@@ -177,8 +229,8 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
             stage_checkpoint.progress.processed += block.gas_used;
 
             // Check if we should commit now
-            let bundle_size_hint = executor.size_hint().unwrap_or_default() as u64;
-            if self.thresholds.is_end_of_batch(
+            bundle_size_hint = executor.size_hint().unwrap_or_default() as u64;
+            if thresholds.is_end_of_batch(
                 block_number - start_block,
                 bundle_size_hint,
                 cumulative_gas,
@@ -192,7 +244,17 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
 
         let time = Instant::now();
         // write output
-        state.write_to_db(provider.tx_ref(), OriginalValuesKnown::Yes)?;
+        let write_report = state.write_to_db(provider.tx_ref(), OriginalValuesKnown::Yes)?;
+        for (table, stats) in write_report.tables() {
+            trace!(
+                target: "sync::stages::execution",
+                table,
+                entries = stats.entries,
+                duration = ?stats.duration,
+                "Wrote state table"
+            );
+        }
+        self.indexer.write(provider)?;
         let db_write_duration = time.elapsed();
         debug!(
             target: "sync::stages::execution",
@@ -200,9 +262,20 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
             execution = ?execution_duration,
             write_preperation = ?write_preparation_duration,
             write = ?db_write_duration,
+            written_state_entries = write_report.entries(),
             "Execution time"
         );
 
+        if let Some(adaptive_batch_size) = &mut self.adaptive_batch_size {
+            let memory_limited =
+                thresholds.max_changes.is_some_and(|max_changes| bundle_size_hint >= max_changes);
+            adaptive_batch_size.on_batch_written(
+                stage_progress - start_block + 1,
+                memory_limited,
+                db_write_duration,
+            );
+        }
+
         executor.stats().log_info();
 
         let done = stage_progress == max_block;
@@ -213,6 +286,18 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
         })
     }
 
+    /// Returns the thresholds of the next batch, with the maximum number of blocks of the
+    /// [AdaptiveBatchSize], if set.
+    fn batch_thresholds(&self) -> ExecutionStageThresholds {
+        match &self.adaptive_batch_size {
+            Some(adaptive_batch_size) => ExecutionStageThresholds {
+                max_blocks: Some(adaptive_batch_size.max_blocks()),
+                ..self.thresholds.clone()
+            },
+            None => self.thresholds.clone(),
+        }
+    }
+
     /// Adjusts the prune modes related to changesets.
     ///
     /// This function verifies whether the [`super::MerkleStage`] or Hashing stages will run from
@@ -328,7 +413,9 @@ fn calculate_gas_used_from_headers<DB: Database>(
     Ok(gas_total)
 }
 
-impl<EF: ExecutorFactory, DB: Database> Stage<DB> for ExecutionStage<EF> {
+impl<EF: ExecutorFactory, I: ExecutionIndexer<DB>, DB: Database> Stage<DB>
+    for ExecutionStage<EF, I>
+{
     /// Return the id of the stage
     fn id(&self) -> StageId {
         StageId::Execution
@@ -363,17 +450,25 @@ impl<EF: ExecutorFactory, DB: Database> Stage<DB> for ExecutionStage<EF> {
             });
         }
 
+        self.indexer.unwind(provider, range.clone())?;
+
         // get all batches for account change
         // Check if walk and walk_dup would do the same thing
         let account_changeset_batch =
             account_changeset.walk_range(range.clone())?.collect::<Result<Vec<_>, _>>()?;
 
         // revert all changes to PlainState
+        let mut plain_accounts_cursor = tx.cursor_write::<tables::PlainAccountState>()?;
+        let mut bytecode_refs = BytecodeRefCounter::new(tx.cursor_write::<tables::BytecodeRefs>()?);
         for (_, changeset) in account_changeset_batch.into_iter().rev() {
+            let existing = plain_accounts_cursor.seek_exact(changeset.address)?.map(|kv| kv.1);
+            // the changeset is discarded below, so it no longer references the bytecode
+            bytecode_refs.decrement(changeset.info.as_ref())?;
+            bytecode_refs.replace(existing.as_ref(), changeset.info.as_ref())?;
             if let Some(account_info) = changeset.info {
-                tx.put::<tables::PlainAccountState>(changeset.address, account_info)?;
-            } else {
-                tx.delete::<tables::PlainAccountState>(changeset.address, None)?;
+                plain_accounts_cursor.upsert(changeset.address, account_info)?;
+            } else if existing.is_some() {
+                plain_accounts_cursor.delete_current()?;
             }
         }
 
@@ -442,6 +537,101 @@ impl<EF: ExecutorFactory, DB: Database> Stage<DB> for ExecutionStage<EF> {
     }
 }
 
+/// Writes the data that an inspector of the executor collected while the [ExecutionStage] executed
+/// blocks, e.g. into a custom table, so it doesn't have to be collected by executing the blocks
+/// again after the sync.
+///
+/// The inspector is supplied to the executor factory, which also selects the transactions that are
+/// inspected, and shared with the indexer.
+pub trait ExecutionIndexer<DB: Database>: Send + Sync {
+    /// Discards the data collected for blocks that weren't written.
+    ///
+    /// This is called before the stage executes blocks.
+    fn reset(&mut self);
+
+    /// Writes the data collected for the executed blocks.
+    ///
+    /// This is called after the executed state is written.
+    fn write(&mut self, provider: &DatabaseProviderRW<DB>) -> Result<(), StageError>;
+
+    /// Removes the data written for the blocks in the range.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<(), StageError>;
+}
+
+impl<DB: Database, I: ExecutionIndexer<DB>> ExecutionIndexer<DB> for Option<I> {
+    fn reset(&mut self) {
+        if let Some(indexer) = self {
+            indexer.reset();
+        }
+    }
+
+    fn write(&mut self, provider: &DatabaseProviderRW<DB>) -> Result<(), StageError> {
+        match self {
+            Some(indexer) => indexer.write(provider),
+            None => Ok(()),
+        }
+    }
+
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<(), StageError> {
+        match self {
+            Some(indexer) => indexer.unwind(provider, range),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<DB: Database, A: ExecutionIndexer<DB>, B: ExecutionIndexer<DB>> ExecutionIndexer<DB>
+    for (A, B)
+{
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+
+    fn write(&mut self, provider: &DatabaseProviderRW<DB>) -> Result<(), StageError> {
+        self.0.write(provider)?;
+        self.1.write(provider)
+    }
+
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<(), StageError> {
+        self.0.unwind(provider, range.clone())?;
+        self.1.unwind(provider, range)
+    }
+}
+
+/// An [ExecutionIndexer] that doesn't write anything.
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct NoopExecutionIndexer;
+
+impl<DB: Database> ExecutionIndexer<DB> for NoopExecutionIndexer {
+    fn reset(&mut self) {}
+
+    fn write(&mut self, _provider: &DatabaseProviderRW<DB>) -> Result<(), StageError> {
+        Ok(())
+    }
+
+    fn unwind(
+        &mut self,
+        _provider: &DatabaseProviderRW<DB>,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> Result<(), StageError> {
+        Ok(())
+    }
+}
+
 /// The thresholds at which the execution stage writes state changes to the database.
 ///
 /// If either of the thresholds (`max_blocks` and `max_changes`) are hit, then the execution stage
@@ -485,6 +675,75 @@ impl ExecutionStageThresholds {
     }
 }
 
+/// Adjusts the maximum number of blocks per batch of the execution stage to the observed write
+/// durations and memory usage, so it doesn't have to be tuned for every machine.
+///
+/// The batch size is halved if writing the last batch took longer than the target duration, and
+/// set to the number of executed blocks if the last batch was cut short by
+/// [ExecutionStageThresholds::max_changes], which bounds the memory used by the executor. It grows
+/// by half if the last batch was full and written in less than half of the target duration.
+#[derive(Debug, Clone)]
+pub struct AdaptiveBatchSize {
+    /// The lower bound of the batch size.
+    min_blocks: u64,
+    /// The upper bound of the batch size.
+    max_blocks: u64,
+    /// The targeted duration of writing a batch to the database.
+    target_write_duration: Duration,
+    /// The current batch size.
+    current: u64,
+}
+
+impl AdaptiveBatchSize {
+    /// Creates a new controller that keeps the batch size within `min_blocks..=max_blocks`.
+    ///
+    /// The batch size starts at `min_blocks`.
+    pub fn new(min_blocks: u64, max_blocks: u64, target_write_duration: Duration) -> Self {
+        let min_blocks = min_blocks.max(1);
+        let max_blocks = max_blocks.max(min_blocks);
+        Self { min_blocks, max_blocks, target_write_duration, current: min_blocks }
+    }
+
+    /// Returns the maximum number of blocks of the next batch.
+    pub fn max_blocks(&self) -> u64 {
+        self.current
+    }
+
+    /// Adjusts the batch size after a batch of `blocks` blocks was written to the database.
+    ///
+    /// `memory_limited` is `true` if the batch was cut short because too many state changes were
+    /// kept in memory.
+    pub fn on_batch_written(
+        &mut self,
+        blocks: u64,
+        memory_limited: bool,
+        write_duration: Duration,
+    ) {
+        let next = if write_duration > self.target_write_duration {
+            self.current / 2
+        } else if memory_limited {
+            blocks
+        } else if blocks >= self.current && write_duration < self.target_write_duration / 2 {
+            self.current.saturating_add(self.current / 2 + 1)
+        } else {
+            self.current
+        };
+        let next = next.clamp(self.min_blocks, self.max_blocks);
+
+        if next != self.current {
+            debug!(
+                target: "sync::stages::execution",
+                previous = self.current,
+                next,
+                ?write_duration,
+                memory_limited,
+                "Adjusted execution batch size"
+            );
+            self.current = next;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -492,7 +751,7 @@ mod tests {
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
     use reth_db::{models::AccountBeforeTx, test_utils::create_test_rw_db};
-    use reth_interfaces::executor::BlockValidationError;
+    use reth_interfaces::executor::{BlockExecutionError, BlockValidationError};
     use reth_primitives::{
         address, hex_literal::hex, keccak256, stage::StageUnitCheckpoint, Account, Bytecode,
         ChainSpecBuilder, PruneModes, SealedBlock, StorageEntry, B256, MAINNET, U256,
@@ -542,6 +801,37 @@ mod tests {
         assert_eq!(stage_checkpoint, Ok(previous_stage_checkpoint));
     }
 
+    #[test]
+    fn adaptive_batch_size() {
+        let target = Duration::from_secs(10);
+        let mut batch_size = AdaptiveBatchSize::new(100, 1_000, target);
+        assert_eq!(batch_size.max_blocks(), 100);
+
+        // full batches that are written fast grow
+        batch_size.on_batch_written(101, false, Duration::from_secs(1));
+        assert_eq!(batch_size.max_blocks(), 151);
+        // batches that weren't full don't
+        batch_size.on_batch_written(10, false, Duration::from_secs(1));
+        assert_eq!(batch_size.max_blocks(), 151);
+        // up to the upper bound
+        for _ in 0..10 {
+            batch_size.on_batch_written(batch_size.max_blocks() + 1, false, Duration::ZERO);
+        }
+        assert_eq!(batch_size.max_blocks(), 1_000);
+
+        // slow writes shrink the batch
+        batch_size.on_batch_written(1_001, false, Duration::from_secs(11));
+        assert_eq!(batch_size.max_blocks(), 500);
+        // and so does hitting the changes limit
+        batch_size.on_batch_written(300, true, Duration::from_secs(1));
+        assert_eq!(batch_size.max_blocks(), 300);
+        // down to the lower bound
+        for _ in 0..10 {
+            batch_size.on_batch_written(1, false, Duration::from_secs(11));
+        }
+        assert_eq!(batch_size.max_blocks(), 100);
+    }
+
     #[test]
     fn execution_checkpoint_precedes() {
         let state_db = create_test_rw_db();
@@ -755,6 +1045,64 @@ mod tests {
         );
     }
 
+    /// A block whose receipts root doesn't match the executed receipts fails the stage with an
+    /// error pointing at the block, and none of its state is persisted.
+    #[tokio::test]
+    async fn receipts_root_mismatch() {
+        let state_db = create_test_rw_db();
+        let factory = ProviderFactory::new(state_db.as_ref(), MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(1), checkpoint: None };
+        let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
+        let genesis = SealedBlock::decode(&mut genesis_rlp).unwrap();
+        let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
+        let mut block = SealedBlock::decode(&mut block_rlp).unwrap().unseal();
+        block.header.receipts_root = B256::with_last_byte(1);
+        let block = block.seal_slow();
+        provider.insert_block(genesis.try_seal_with_senders().unwrap(), None).unwrap();
+        provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None).unwrap();
+
+        // insert pre state
+        let db_tx = provider.tx_ref();
+        let acc1 = address!("1000000000000000000000000000000000000000");
+        let acc2 = address!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+        let code = hex!("5a465a905090036002900360015500");
+        let balance = U256::from(0x3635c9adc5dea00000u128);
+        let code_hash = keccak256(code);
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc1,
+                Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+            )
+            .unwrap();
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc2,
+                Account { nonce: 0, balance, bytecode_hash: None },
+            )
+            .unwrap();
+        db_tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into())).unwrap();
+        provider.commit().unwrap();
+
+        let provider = factory.provider_rw().unwrap();
+        let mut execution_stage = stage();
+        assert_matches!(
+            execution_stage.execute(&provider, input),
+            Err(StageError::Block {
+                block: bad_block,
+                error: BlockErrorKind::Execution(BlockExecutionError::Validation(
+                    BlockValidationError::ReceiptRootDiff(_)
+                )),
+            }) if bad_block.hash == block.hash
+        );
+        assert_eq!(
+            provider.basic_account(acc2),
+            Ok(Some(Account { nonce: 0, balance, bytecode_hash: None })),
+            "Pre state is unchanged"
+        );
+        assert_eq!(provider.tx_ref().entries::<tables::Receipts>(), Ok(0));
+    }
+
     #[tokio::test]
     async fn sanity_execute_unwind() {
         // TODO cleanup the setup after https://github.com/paradigmxyz/reth/issues/332
@@ -2,6 +2,7 @@ use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput}
 use itertools::Itertools;
 use rayon::slice::ParallelSliceMut;
 use reth_db::{
+    bulk::BulkAppender,
     cursor::{DbCursorRO, DbCursorRW},
     database::Database,
     tables,
@@ -55,10 +56,9 @@ impl Default for AccountHashingStage {
 /// stage.
 ///
 /// In order to check the "full hashing" mode of the stage you want to generate more
-/// transitions than `AccountHashingStage.clean_threshold`. This requires:
-/// 1. Creating enough blocks so there's enough transactions to generate
-/// the required transition keys in the `BlockTransitionIndex` (which depends on the
-/// `TxTransitionIndex` internally)
+/// changes than `AccountHashingStage.clean_threshold`. This requires:
+/// 1. Creating enough blocks with transactions, so there are account changes in the
+/// `AccountChangeSet` table, which is keyed by block number
 /// 2. Setting `blocks.len() > clean_threshold` so that there's enough diffs to actually
 /// take the 2nd codepath
 #[derive(Clone, Debug)]
@@ -76,8 +76,8 @@ impl AccountHashingStage {
     /// Initializes the `PlainAccountState` table with `num_accounts` having some random state
     /// at the target block, with `txs_range` transactions in each block.
     ///
-    /// Proceeds to go to the `BlockTransitionIndex` end, go back `transitions` and change the
-    /// account state in the `AccountChangeSet` table.
+    /// Then writes the previous state of one account per block of `opts.blocks` to the
+    /// `AccountChangeSet` table.
     pub fn seed<DB: Database>(
         provider: &DatabaseProviderRW<DB>,
         opts: SeedOpts,
@@ -217,9 +217,7 @@ impl<DB: Database> Stage<DB> for AccountHashingStage {
 
                 // iterate and put presorted hashed accounts
                 if start_address.is_none() {
-                    hashed_batch
-                        .into_iter()
-                        .try_for_each(|(k, v)| hashed_account_cursor.append(k, v))?;
+                    BulkAppender::from(hashed_batch).flush(&mut hashed_account_cursor)?;
                 } else {
                     hashed_batch
                         .into_iter()
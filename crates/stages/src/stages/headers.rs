@@ -1,7 +1,8 @@
 use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
 use futures_util::StreamExt;
 use reth_db::{
-    cursor::{DbCursorRO, DbCursorRW},
+    bulk::BulkAppender,
+    cursor::DbCursorRO,
     database::Database,
     tables,
     transaction::{DbTx, DbTxMut},
@@ -81,8 +82,9 @@ where
     ) -> Result<Option<BlockNumber>, StageError> {
         trace!(target: "sync::stages::headers", len = headers.len(), "writing headers");
 
-        let mut cursor_header = tx.cursor_write::<tables::Headers>()?;
-        let mut cursor_canonical = tx.cursor_write::<tables::CanonicalHeaders>()?;
+        let mut headers_appender = BulkAppender::<tables::Headers>::with_capacity(headers.len());
+        let mut canonical_appender =
+            BulkAppender::<tables::CanonicalHeaders>::with_capacity(headers.len());
 
         let mut latest = None;
         // Since the headers were returned in descending order,
@@ -99,10 +101,13 @@ where
 
             // NOTE: HeaderNumbers are not sorted and can't be inserted with cursor.
             tx.put::<tables::HeaderNumbers>(header_hash, header_number)?;
-            cursor_header.insert(header_number, header)?;
-            cursor_canonical.insert(header_number, header_hash)?;
+            headers_appender.push(header_number, header);
+            canonical_appender.push(header_number, header_hash);
         }
 
+        headers_appender.flush(&mut tx.cursor_write::<tables::Headers>()?)?;
+        canonical_appender.flush(&mut tx.cursor_write::<tables::CanonicalHeaders>()?)?;
+
         Ok(latest)
     }
 }
@@ -394,6 +394,36 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn unwind_past_shard_boundary() {
+        // init
+        let db = TestStageDB::default();
+
+        // setup
+        partial_setup(&db);
+        db.commit(|tx| {
+            tx.put::<tables::AccountHistory>(shard(3), list(&[1, 2, 3])).unwrap();
+            Ok(())
+        })
+        .unwrap();
+
+        run(&db, 5);
+
+        // verify
+        let table = cast(db.table::<tables::AccountHistory>().unwrap());
+        assert_eq!(
+            table,
+            BTreeMap::from([(shard(3), vec![1, 2, 3]), (shard(u64::MAX), vec![4, 5])])
+        );
+
+        // unwind
+        unwind(&db, 5, 0);
+
+        // verify that the last shard is removed and the previous shard became the last one
+        let table = cast(db.table::<tables::AccountHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![1, 2, 3])]));
+    }
+
     #[tokio::test]
     async fn insert_index_with_prune_mode() {
         // init
@@ -0,0 +1,154 @@
+use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use reth_db::database::Database;
+use reth_primitives::stage::{StageCheckpoint, StageId};
+use reth_provider::{AddressAppearancesWriter, DatabaseProviderRW};
+use std::fmt::Debug;
+use tracing::*;
+
+/// Stage is indexing the transactions in which each address appears, as the sender or the
+/// recipient, in the logs or in the internal transfers. For more information take a look at
+/// [`reth_db::tables::AddressAppearances`].
+///
+/// The stage is optional and isn't part of the default stages, the node only adds it if the index
+/// is enabled. Its checkpoint then also follows the blocks inserted by the blockchain tree.
+#[derive(Debug)]
+pub struct IndexAddressAppearancesStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+}
+
+impl IndexAddressAppearancesStage {
+    /// Create new instance of [IndexAddressAppearancesStage].
+    pub fn new(commit_threshold: u64) -> Self {
+        Self { commit_threshold }
+    }
+}
+
+impl Default for IndexAddressAppearancesStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000 }
+    }
+}
+
+impl<DB: Database> Stage<DB> for IndexAddressAppearancesStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexAddressAppearances
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+
+        let appearances = provider.insert_address_appearances(range.clone())?;
+        debug!(
+            target: "sync::stages::index_address_appearances",
+            ?range,
+            appearances,
+            "Indexed address appearances"
+        );
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_address_appearances(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use reth_interfaces::test_utils::{generators, generators::random_block};
+    use reth_primitives::{Address, Log, Receipt, B256};
+    use reth_provider::AddressAppearancesReader;
+
+    fn run(db: &TestStageDB, run_to: u64) {
+        let input = ExecInput { target: Some(run_to), ..Default::default() };
+        let mut stage = IndexAddressAppearancesStage::default();
+        let provider = db.factory.provider_rw().unwrap();
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(run_to), done: true });
+        provider.commit().unwrap();
+    }
+
+    fn unwind(db: &TestStageDB, unwind_from: u64, unwind_to: u64) {
+        let input = UnwindInput {
+            checkpoint: StageCheckpoint::new(unwind_from),
+            unwind_to,
+            ..Default::default()
+        };
+        let mut stage = IndexAddressAppearancesStage::default();
+        let provider = db.factory.provider_rw().unwrap();
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(unwind_to) });
+        provider.commit().unwrap();
+    }
+
+    #[tokio::test]
+    async fn index_appearances() {
+        // init
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        // setup: three blocks with two transactions each, the first one emits a token transfer
+        let blocks = (0..=2)
+            .map(|number| random_block(&mut rng, number, None, Some(2), None))
+            .collect::<Vec<_>>();
+        db.insert_blocks(blocks.iter(), None).unwrap();
+        let emitter = Address::with_last_byte(1);
+        let token_recipient = Address::with_last_byte(2);
+        let log = Log {
+            address: emitter,
+            topics: vec![B256::with_last_byte(3), token_recipient.into_word()],
+            ..Default::default()
+        };
+        db.insert_receipts([(0, Receipt { logs: vec![log], ..Default::default() })]).unwrap();
+        let sender = blocks[2].body[1].recover_signer().unwrap();
+        let recipient = blocks[2].body[1].to().unwrap();
+
+        // run
+        run(&db, 2);
+
+        // verify
+        let provider = db.factory.provider().unwrap();
+        assert_eq!(provider.address_appearances_after(emitter, 0, 10).unwrap(), vec![0]);
+        assert_eq!(provider.address_appearances_after(emitter, 1, 10).unwrap(), vec![]);
+        assert_eq!(provider.address_appearances_after(token_recipient, 0, 10).unwrap(), vec![0]);
+        assert!(provider
+            .address_appearances_after(Address::with_last_byte(3), 0, 10)
+            .unwrap()
+            .is_empty());
+        assert_eq!(provider.address_appearances_before(sender, u64::MAX, 10).unwrap(), vec![5]);
+        assert_eq!(provider.address_appearances_before(recipient, 5, 10).unwrap(), vec![]);
+        drop(provider);
+
+        // unwind
+        unwind(&db, 2, 1);
+
+        // verify
+        let provider = db.factory.provider().unwrap();
+        assert_eq!(provider.address_appearances_after(sender, 0, 10).unwrap(), vec![]);
+        assert_eq!(provider.address_appearances_after(emitter, 0, 10).unwrap(), vec![0]);
+    }
+}
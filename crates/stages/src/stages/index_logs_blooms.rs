@@ -0,0 +1,131 @@
+use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use reth_db::database::Database;
+use reth_primitives::stage::{StageCheckpoint, StageId};
+use reth_provider::{DatabaseProviderRW, LogsBloomIndexWriter};
+use std::fmt::Debug;
+
+/// Stage is indexing the combined logs blooms of the headers of fixed block ranges, so that log
+/// queries can skip entire ranges. For more information take a look at
+/// [`reth_db::tables::LogsBlooms4K`] and [`reth_db::tables::LogsBlooms64K`]
+#[derive(Debug)]
+pub struct IndexLogsBloomsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+}
+
+impl IndexLogsBloomsStage {
+    /// Create new instance of [IndexLogsBloomsStage].
+    pub fn new(commit_threshold: u64) -> Self {
+        Self { commit_threshold }
+    }
+}
+
+impl Default for IndexLogsBloomsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000 }
+    }
+}
+
+impl<DB: Database> Stage<DB> for IndexLogsBloomsStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogsBlooms
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+
+        // The ranges that are only completed by these blocks are indexed as well.
+        provider.insert_logs_bloom_index(range.clone())?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_logs_bloom_index(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use reth_db::{tables, transaction::DbTxMut};
+    use reth_primitives::{Bloom, Header};
+    use reth_provider::LogsBloomLevel;
+
+    fn run(db: &TestStageDB, run_to: u64) {
+        let input = ExecInput { target: Some(run_to), ..Default::default() };
+        let mut stage = IndexLogsBloomsStage::default();
+        let provider = db.factory.provider_rw().unwrap();
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(run_to), done: true });
+        provider.commit().unwrap();
+    }
+
+    fn unwind(db: &TestStageDB, unwind_from: u64, unwind_to: u64) {
+        let input = UnwindInput {
+            checkpoint: StageCheckpoint::new(unwind_from),
+            unwind_to,
+            ..Default::default()
+        };
+        let mut stage = IndexLogsBloomsStage::default();
+        let provider = db.factory.provider_rw().unwrap();
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(unwind_to) });
+        provider.commit().unwrap();
+    }
+
+    #[tokio::test]
+    async fn index_complete_ranges() {
+        // init
+        let db = TestStageDB::default();
+        let range_size = LogsBloomLevel::Blocks4K.range_size();
+
+        // setup
+        db.commit(|tx| {
+            for number in 0..=2 * range_size {
+                let logs_bloom = Bloom::with_last_byte(number as u8);
+                let header = Header { number, logs_bloom, ..Default::default() };
+                tx.put::<tables::Headers>(number, header).unwrap();
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        // run
+        run(&db, 2 * range_size);
+
+        // verify
+        let full =
+            (0..=u8::MAX).fold(Bloom::ZERO, |bloom, byte| bloom | Bloom::with_last_byte(byte));
+        assert_eq!(db.table::<tables::LogsBlooms4K>().unwrap(), vec![(0, full), (1, full)]);
+        assert!(db.table::<tables::LogsBlooms64K>().unwrap().is_empty());
+
+        // unwind
+        unwind(&db, 2 * range_size, range_size);
+
+        // verify
+        assert_eq!(db.table::<tables::LogsBlooms4K>().unwrap(), vec![(0, full)]);
+    }
+}
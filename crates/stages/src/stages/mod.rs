@@ -12,6 +12,10 @@ mod hashing_storage;
 mod headers;
 /// Index history of account changes
 mod index_account_history;
+/// Index transactions in which addresses appear
+mod index_address_appearances;
+/// Index logs blooms of block ranges
+mod index_logs_blooms;
 /// Index history of storage changes
 mod index_storage_history;
 /// Stage for computing state root.
@@ -30,6 +34,8 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_address_appearances::*;
+pub use index_logs_blooms::*;
 pub use index_storage_history::*;
 pub use merkle::*;
 pub use sender_recovery::*;
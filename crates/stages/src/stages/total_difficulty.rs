@@ -195,6 +195,38 @@ mod tests {
         assert!(runner.validate_execution(first_input, result.ok()).is_ok(), "validation failed");
     }
 
+    #[tokio::test]
+    async fn unwind_keeps_target_block() {
+        let (stage_progress, previous_stage) = (100, 200);
+        let unwind_to = 150;
+
+        let mut runner = TotalDifficultyTestRunner::default();
+        let input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+        runner.seed_execution(input).expect("failed to seed execution");
+        let result = runner.execute(input).await.unwrap();
+        assert_matches!(result, Ok(ExecOutput { done: true, .. }));
+
+        let unwind_input = UnwindInput {
+            unwind_to,
+            checkpoint: StageCheckpoint::new(previous_stage),
+            bad_block: None,
+        };
+        let result = runner.unwind(unwind_input).await;
+        assert_matches!(
+            result,
+            Ok(UnwindOutput { checkpoint: StageCheckpoint { block_number, .. } })
+                if block_number == unwind_to
+        );
+        assert!(runner.validate_unwind(unwind_input).is_ok(), "unwind validation failed");
+
+        // the total difficulty of the block unwound to stays in the database
+        let td = runner.db.query(|tx| Ok(tx.get::<tables::HeaderTD>(unwind_to)?)).unwrap();
+        assert!(td.is_some());
+    }
+
     struct TotalDifficultyTestRunner {
         db: TestStageDB,
         consensus: Arc<TestConsensus>,
@@ -15,6 +15,7 @@ pub fn maybe_generate_tests(args: TokenStream, ast: &DeriveInput) -> TokenStream
     let mut default_cases = 256;
 
     let mut traits = vec![];
+    let mut inputs = vec![quote! { field: super::#type_ident }];
     let mut roundtrips = vec![];
     let mut additional_tests = vec![];
 
@@ -31,6 +32,9 @@ pub fn maybe_generate_tests(args: TokenStream, ast: &DeriveInput) -> TokenStream
             });
         } else if arg.to_string() == "rlp" {
             traits.push(quote! { use alloy_rlp::{Encodable, Decodable}; });
+            // generated by proptest so that failing cases are reproducible and shrunk
+            inputs.push(quote! { truncated: proptest::sample::Index });
+            inputs.push(quote! { mutations: [(proptest::sample::Index, u8); 4] });
             roundtrips.push(quote! {
                 {
                     let mut buf = vec![];
@@ -41,6 +45,12 @@ pub fn maybe_generate_tests(args: TokenStream, ast: &DeriveInput) -> TokenStream
                     // ensure buffer is fully consumed by decode
                     assert!(b.is_empty(), "buffer was not consumed entirely");
 
+                    // malformed input, e.g. sent by a peer, must be rejected without panicking
+                    let _ = super::#type_ident::decode(&mut &buf[..truncated.index(buf.len())]);
+                    for (index, byte) in mutations {
+                        buf[index.index(buf.len())] = byte;
+                    }
+                    let _ = super::#type_ident::decode(&mut buf.as_slice());
                 }
             });
             additional_tests.push(quote! {
@@ -93,7 +103,7 @@ pub fn maybe_generate_tests(args: TokenStream, ast: &DeriveInput) -> TokenStream
                 fn proptest() {
                     let mut config = proptest::prelude::ProptestConfig::with_cases(#default_cases as u32);
 
-                    proptest::proptest!(config, |(field: super::#type_ident)| {
+                    proptest::proptest!(config, |(#(#inputs),*)| {
                         #(#roundtrips)*
                     });
                 }
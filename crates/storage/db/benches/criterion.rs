@@ -3,6 +3,7 @@ use criterion::{
 };
 use pprof::criterion::{Output, PProfProfiler};
 use reth_db::{
+    bulk::BulkAppender,
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
     table::{Decode, Decompress, DupSort},
     tables::*,
@@ -175,6 +176,58 @@ where
         )
     });
 
+    group.bench_function(format!("{}.UnsortedUpsert", T::NAME), |b| {
+        b.iter_with_setup(
+            || {
+                // Reset DB
+                let _ = fs::remove_dir_all(bench_db_path);
+                (
+                    input.clone(),
+                    Arc::try_unwrap(create_test_rw_db_with_path(bench_db_path)).unwrap(),
+                )
+            },
+            |(input, db)| {
+                // Create TX
+                let tx = db.tx_mut().expect("tx");
+                let mut crsr = tx.cursor_write::<T>().expect("cursor");
+
+                black_box({
+                    for (k, _, v, _) in input.into_iter().rev() {
+                        crsr.upsert(k, v).expect("submit");
+                    }
+
+                    tx.inner.commit().unwrap()
+                });
+            },
+        )
+    });
+
+    group.bench_function(format!("{}.UnsortedBulkAppend", T::NAME), |b| {
+        b.iter_with_setup(
+            || {
+                // Reset DB
+                let _ = fs::remove_dir_all(bench_db_path);
+                (
+                    input.clone(),
+                    Arc::try_unwrap(create_test_rw_db_with_path(bench_db_path)).unwrap(),
+                )
+            },
+            |(input, db)| {
+                // Create TX
+                let tx = db.tx_mut().expect("tx");
+                let mut crsr = tx.cursor_write::<T>().expect("cursor");
+
+                black_box({
+                    let mut appender = BulkAppender::<T>::with_capacity(input.len());
+                    appender.extend(input.into_iter().rev().map(|(k, _, v, _)| (k, v)));
+                    appender.flush(&mut crsr).expect("submit");
+
+                    tx.inner.commit().unwrap()
+                });
+            },
+        )
+    });
+
     group.bench_function(format!("{}.SeqRead", T::NAME), |b| {
         let db = set_up_db::<T>(bench_db_path, input);
 
@@ -0,0 +1,120 @@
+use crate::{
+    cursor::{DbCursorRW, DbDupCursorRW},
+    table::{DupSort, Table},
+    DatabaseError,
+};
+use std::fmt;
+
+/// Buffers rows of table `T` and writes them in bulk, sorted by key.
+///
+/// Appending rows is considerably faster than inserting or upserting them, because the database
+/// doesn't need to search for the position of each key and only ever fills the last page of the
+/// table. This requires the rows to be written in ascending key order and every key to be greater
+/// than the last key of the table.
+///
+/// On [flush](BulkAppender::flush), the buffered rows are sorted and appended, so all of their
+/// keys have to be greater than the last key of the table, or the flush fails. If the same key was
+/// pushed more than once, the last value wins. `DupSort` tables are written with
+/// [flush_dup](BulkAppender::flush_dup) instead.
+///
+/// NOTE: Appending assumes that the [Ord] implementation of the key matches the ordering of the
+/// encoded key.
+pub struct BulkAppender<T: Table> {
+    rows: Vec<(T::Key, T::Value)>,
+}
+
+impl<T: Table> BulkAppender<T> {
+    /// Creates a new appender with space for `capacity` rows pre-allocated.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { rows: Vec::with_capacity(capacity) }
+    }
+
+    /// Buffers the row.
+    pub fn push(&mut self, key: T::Key, value: T::Value) {
+        self.rows.push((key, value));
+    }
+
+    /// Returns the number of buffered rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if there are no buffered rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Sorts the buffered rows by key and appends them using the cursor.
+    ///
+    /// Returns the number of written rows. The appender is empty afterwards and keeps its
+    /// allocation, so it can be reused for the next batch.
+    pub fn flush<C>(&mut self, cursor: &mut C) -> Result<usize, DatabaseError>
+    where
+        C: DbCursorRW<T>,
+    {
+        // the sort is stable, so the last pushed value of a duplicate key is the last one
+        self.rows.sort_by(|a, b| a.0.cmp(&b.0));
+        self.rows.dedup_by(|next, prev| {
+            if next.0 == prev.0 {
+                std::mem::swap(next, prev);
+                true
+            } else {
+                false
+            }
+        });
+
+        let written = self.rows.len();
+        for (key, value) in self.rows.drain(..) {
+            cursor.append(key, value)?;
+        }
+
+        Ok(written)
+    }
+}
+
+impl<T: DupSort> BulkAppender<T> {
+    /// Sorts the buffered rows by key and appends them as duplicates using the cursor.
+    ///
+    /// The sort is stable, so the values of each key have to be pushed in ascending subkey order.
+    /// The keys have to be greater than or equal to the last key of the table, and the values of
+    /// the last key greater than its last value, or the flush fails.
+    ///
+    /// Returns the number of written rows, the appender is empty afterwards.
+    pub fn flush_dup<C>(&mut self, cursor: &mut C) -> Result<usize, DatabaseError>
+    where
+        C: DbDupCursorRW<T>,
+    {
+        self.rows.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let written = self.rows.len();
+        for (key, value) in self.rows.drain(..) {
+            cursor.append_dup(key, value)?;
+        }
+
+        Ok(written)
+    }
+}
+
+impl<T: Table> Default for BulkAppender<T> {
+    fn default() -> Self {
+        Self { rows: Vec::new() }
+    }
+}
+
+impl<T: Table> From<Vec<(T::Key, T::Value)>> for BulkAppender<T> {
+    fn from(rows: Vec<(T::Key, T::Value)>) -> Self {
+        Self { rows }
+    }
+}
+
+impl<T: Table> Extend<(T::Key, T::Value)> for BulkAppender<T> {
+    fn extend<I: IntoIterator<Item = (T::Key, T::Value)>>(&mut self, iter: I) {
+        self.rows.extend(iter)
+    }
+}
+
+impl<T: Table> fmt::Debug for BulkAppender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BulkAppender").field("table", &T::NAME).field("rows", &self.len()).finish()
+    }
+}
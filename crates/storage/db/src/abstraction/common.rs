@@ -30,6 +30,7 @@ mod sealed {
     impl<DB: Database> Sealed for Arc<DB> {}
     impl Sealed for DatabaseEnv {}
     impl Sealed for DatabaseMock {}
+    impl<DB: Database> Sealed for crate::overlay::OverlayDatabase<DB> {}
 
     #[cfg(any(test, feature = "test-utils"))]
     impl<DB: Database> Sealed for crate::test_utils::TempDatabase<DB> {}
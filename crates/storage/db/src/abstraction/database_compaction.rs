@@ -0,0 +1,58 @@
+use crate::DatabaseError;
+use std::{path::Path, sync::Arc};
+
+/// The number of pages a database occupies on disk and how many of them are free.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageUsage {
+    /// The size of a page in bytes.
+    pub page_size: u32,
+    /// The number of pages up to the last page that is used.
+    pub total_pages: usize,
+    /// The number of pages on the freelist, which are reused for writes but never returned to the
+    /// file system.
+    pub free_pages: usize,
+}
+
+impl PageUsage {
+    /// Returns the share of free pages, between `0.0` and `1.0`.
+    pub fn free_ratio(&self) -> f64 {
+        if self.total_pages == 0 {
+            return 0.0
+        }
+        self.free_pages as f64 / self.total_pages as f64
+    }
+
+    /// Returns the size of the database in bytes.
+    pub fn size(&self) -> u64 {
+        self.total_pages as u64 * self.page_size as u64
+    }
+
+    /// Returns the estimated size in bytes of the database without its free pages.
+    pub fn compacted_size(&self) -> u64 {
+        self.total_pages.saturating_sub(self.free_pages) as u64 * self.page_size as u64
+    }
+}
+
+/// A database that can be copied without its free pages.
+///
+/// Deleting data, e.g. by pruning, adds the pages to the freelist of the database but never shrinks
+/// the file. A compacted copy is the only way to reclaim this space.
+pub trait DatabaseCompaction {
+    /// Returns the [PageUsage] of the database.
+    fn page_usage(&self) -> Result<PageUsage, DatabaseError>;
+
+    /// Writes a compacted copy of the database to the empty directory at `dest`.
+    ///
+    /// The copy is consistent, the database can be written to while it's copied.
+    fn copy_compacted(&self, dest: &Path) -> Result<(), DatabaseError>;
+}
+
+impl<DB: DatabaseCompaction> DatabaseCompaction for Arc<DB> {
+    fn page_usage(&self) -> Result<PageUsage, DatabaseError> {
+        <DB as DatabaseCompaction>::page_usage(self)
+    }
+
+    fn copy_compacted(&self, dest: &Path) -> Result<(), DatabaseError> {
+        <DB as DatabaseCompaction>::copy_compacted(self, dest)
+    }
+}
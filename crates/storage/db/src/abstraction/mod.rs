@@ -1,13 +1,19 @@
+/// Bulk writes.
+pub mod bulk;
 /// Common types used throughout the abstraction.
 pub mod common;
 /// Cursor database traits.
 pub mod cursor;
 /// Database traits.
 pub mod database;
+/// Database compaction trait extensions.
+pub mod database_compaction;
 /// Database metrics trait extensions.
 pub mod database_metrics;
 /// mock
 pub mod mock;
+/// In-memory overlay transactions.
+pub mod overlay;
 /// Table traits
 pub mod table;
 /// Transaction database traits.
@@ -0,0 +1,1042 @@
+//! In-memory overlay transactions.
+//!
+//! An [OverlayTx] buffers all writes in memory on top of a read-only transaction of the base
+//! database. Reads merge the buffered writes with the base transaction, so the overlay behaves
+//! like a read-write transaction whose changes never reach the base database.
+
+use crate::{
+    common::{PairResult, ValueOnlyResult},
+    cursor::{
+        DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, DupWalker, RangeWalker,
+        ReverseWalker, Walker,
+    },
+    database::Database,
+    table::{Compress, Decode, Decompress, DupSort, Encode, Table, TableImporter},
+    tables::{TableType, Tables},
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use parking_lot::RwLock;
+use reth_interfaces::db::{DatabaseWriteError, DatabaseWriteOperation};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    str::FromStr,
+    sync::Arc,
+};
+
+/// The error code of an insert of a key that already exists, the same as MDBX returns.
+const KEY_EXIST: i32 = -30799;
+/// The error code of an append that isn't in order, the same as MDBX returns.
+const KEY_MISMATCH: i32 = -30418;
+
+/// An encoded key with a compressed value.
+type RawEntry = (Vec<u8>, Vec<u8>);
+
+/// A database whose read-write transactions are [OverlayTx]s on top of the read-only transactions
+/// of the base database.
+///
+/// Committed transactions are kept in memory and seen by all transactions opened afterwards, the
+/// base database is never written to. Transactions share the committed writes and buffer their own
+/// writes on top of them, committing adds the writes in place unless an older transaction is still
+/// open. Like with the base database, only one read-write transaction should be open at a time.
+#[derive(Debug, Clone)]
+pub struct OverlayDatabase<DB> {
+    /// The base database.
+    db: DB,
+    /// The writes of the committed transactions.
+    committed: Arc<RwLock<Arc<Overlay>>>,
+}
+
+impl<DB> OverlayDatabase<DB> {
+    /// Creates a new overlay on top of the database.
+    pub fn new(db: DB) -> Self {
+        Self { db, committed: Default::default() }
+    }
+
+    /// Returns the base database.
+    pub fn inner(&self) -> &DB {
+        &self.db
+    }
+}
+
+impl<DB: Database> Database for OverlayDatabase<DB> {
+    type TX = OverlayTx<DB::TX>;
+    type TXMut = OverlayTx<DB::TX>;
+
+    fn tx(&self) -> Result<Self::TX, DatabaseError> {
+        Ok(OverlayTx::with_committed(self.db.tx()?, self.committed.read().clone()))
+    }
+
+    fn tx_mut(&self) -> Result<Self::TXMut, DatabaseError> {
+        let mut tx = OverlayTx::with_committed(self.db.tx()?, self.committed.read().clone());
+        tx.publish = Some(self.committed.clone());
+        Ok(tx)
+    }
+}
+
+/// The writes buffered by an [OverlayTx], by table.
+#[derive(Debug, Clone, Default)]
+pub struct Overlay {
+    tables: HashMap<&'static str, TableOverlay>,
+}
+
+impl Overlay {
+    /// Returns `true` if nothing was written.
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    fn table<T: Table>(&self) -> Option<&TableOverlay> {
+        self.tables.get(T::NAME)
+    }
+
+    fn table_mut<T: Table>(&mut self) -> &mut TableOverlay {
+        self.tables.entry(T::NAME).or_default()
+    }
+
+    /// Applies the writes on top of these writes.
+    fn extend(&mut self, overlay: Overlay) {
+        for (name, table) in overlay.tables {
+            self.tables.entry(name).or_default().extend(table);
+        }
+    }
+}
+
+/// The writes of a table.
+#[derive(Debug, Clone, Default)]
+struct TableOverlay {
+    /// If `true`, the table was cleared and all entries of the base transaction are hidden.
+    cleared: bool,
+    /// The keys whose values in the base transaction are all hidden.
+    replaced: BTreeSet<Vec<u8>>,
+    /// The values of the base transaction that were deleted, by key. Only for dupsort tables.
+    deleted: BTreeMap<Vec<u8>, BTreeSet<Vec<u8>>>,
+    /// The written values, by key. Keys without values are removed.
+    values: BTreeMap<Vec<u8>, BTreeSet<Vec<u8>>>,
+}
+
+impl TableOverlay {
+    /// Returns `true` if the key was written.
+    fn is_written(&self, key: &[u8]) -> bool {
+        self.cleared ||
+            self.replaced.contains(key) ||
+            self.deleted.contains_key(key) ||
+            self.values.contains_key(key)
+    }
+
+    /// Returns `true` if the entry of the base transaction isn't hidden by the writes.
+    ///
+    /// An entry that was written again is hidden too, so it's returned only once.
+    fn is_visible(&self, (key, value): &RawEntry) -> bool {
+        !self.cleared &&
+            !self.replaced.contains(key) &&
+            !self.deleted.get(key).is_some_and(|deleted| deleted.contains(value)) &&
+            !self.values.get(key).is_some_and(|values| values.contains(value))
+    }
+
+    /// Returns the first written entry in the bound that is visible.
+    fn first(
+        &self,
+        bound: LowerBound<'_>,
+        visible: impl Fn(&RawEntry) -> bool,
+    ) -> Option<RawEntry> {
+        let start = bound.key().map_or(Bound::Unbounded, Bound::Included);
+        self.values.range::<[u8], _>((start, Bound::Unbounded)).find_map(|(key, values)| {
+            values
+                .range::<[u8], _>((bound.values(key)?, Bound::Unbounded))
+                .map(|value| (key.clone(), value.clone()))
+                .find(&visible)
+        })
+    }
+
+    /// Returns the last written entry that is less than the given one, or the last written entry
+    /// if [None], that is visible.
+    fn last_before(
+        &self,
+        entry: Option<(&[u8], &[u8])>,
+        visible: impl Fn(&RawEntry) -> bool,
+    ) -> Option<RawEntry> {
+        let end = entry.map_or(Bound::Unbounded, |(key, _)| Bound::Included(key));
+        self.values.range::<[u8], _>((Bound::Unbounded, end)).rev().find_map(|(key, values)| {
+            let end = match entry {
+                Some((entry_key, entry_value)) if key.as_slice() == entry_key => {
+                    Bound::Excluded(entry_value)
+                }
+                _ => Bound::Unbounded,
+            };
+            values
+                .range::<[u8], _>((Bound::Unbounded, end))
+                .rev()
+                .map(|value| (key.clone(), value.clone()))
+                .find(&visible)
+        })
+    }
+
+    /// Writes the entry. Values of dupsort tables are added to the values of the key, the values
+    /// of other tables are replaced.
+    fn put(&mut self, (key, value): RawEntry, dupsort: bool) {
+        if dupsort {
+            if let Some(deleted) = self.deleted.get_mut(&key) {
+                deleted.remove(&value);
+            }
+        } else {
+            self.delete_key(key.clone());
+        }
+        self.values.entry(key).or_default().insert(value);
+    }
+
+    /// Deletes all values of the key.
+    fn delete_key(&mut self, key: Vec<u8>) {
+        self.values.remove(&key);
+        self.deleted.remove(&key);
+        if !self.cleared {
+            self.replaced.insert(key);
+        }
+    }
+
+    /// Deletes a single value of a key of a dupsort table.
+    fn delete_value(&mut self, (key, value): RawEntry) {
+        if let Some(values) = self.values.get_mut(&key) {
+            values.remove(&value);
+            if values.is_empty() {
+                self.values.remove(&key);
+            }
+        }
+        if !self.cleared && !self.replaced.contains(&key) {
+            self.deleted.entry(key).or_default().insert(value);
+        }
+    }
+
+    /// Applies the writes on top of these writes.
+    fn extend(&mut self, table: TableOverlay) {
+        if table.cleared {
+            *self = table;
+            return
+        }
+        for key in table.replaced {
+            self.delete_key(key);
+        }
+        for (key, values) in table.deleted {
+            for value in values {
+                self.delete_value((key.clone(), value));
+            }
+        }
+        for (key, values) in table.values {
+            for value in values {
+                self.put((key.clone(), value), true);
+            }
+        }
+    }
+}
+
+/// The writes of a table seen by an [OverlayTx], the buffered writes on top of the committed
+/// writes of the [OverlayDatabase].
+#[derive(Debug, Clone, Copy)]
+struct TableLayers<'a> {
+    /// The committed writes of the [OverlayDatabase].
+    committed: Option<&'a TableOverlay>,
+    /// The buffered writes of the transaction.
+    written: Option<&'a TableOverlay>,
+}
+
+impl<'a> TableLayers<'a> {
+    fn new<T: Table>(committed: &'a Overlay, written: &'a Overlay) -> Self {
+        Self { committed: committed.table::<T>(), written: written.table::<T>() }
+    }
+
+    /// Returns `true` if nothing was written to the table.
+    fn is_empty(&self) -> bool {
+        self.committed.is_none() && self.written.is_none()
+    }
+
+    /// Returns `true` if the table was cleared and all entries of the base transaction are hidden.
+    fn is_cleared(&self) -> bool {
+        self.layers().any(|table| table.cleared)
+    }
+
+    /// Returns `true` if the key was written.
+    fn is_written(&self, key: &[u8]) -> bool {
+        self.layers().any(|table| table.is_written(key))
+    }
+
+    /// Returns `true` if the entry of the base transaction isn't hidden by the writes.
+    fn is_visible(&self, entry: &RawEntry) -> bool {
+        self.layers().all(|table| table.is_visible(entry))
+    }
+
+    /// Returns the first written entry in the bound.
+    fn first(&self, bound: LowerBound<'_>) -> Option<RawEntry> {
+        let written = self.written.and_then(|table| table.first(bound, |_| true));
+        let committed = self.committed.and_then(|table| {
+            table.first(bound, |entry| {
+                self.written.map_or(true, |written| written.is_visible(entry))
+            })
+        });
+        written.into_iter().chain(committed).min()
+    }
+
+    /// Returns the last written entry that is less than the given one, or the last written entry
+    /// if [None].
+    fn last_before(&self, entry: Option<(&[u8], &[u8])>) -> Option<RawEntry> {
+        let written = self.written.and_then(|table| table.last_before(entry, |_| true));
+        let committed = self.committed.and_then(|table| {
+            table.last_before(entry, |entry| {
+                self.written.map_or(true, |written| written.is_visible(entry))
+            })
+        });
+        written.into_iter().chain(committed).max()
+    }
+
+    /// Returns the last written value of the key.
+    fn last_value(&self, key: &[u8]) -> Option<&'a Vec<u8>> {
+        self.layers().filter_map(|table| table.values.get(key)?.last()).max()
+    }
+
+    fn layers(&self) -> impl Iterator<Item = &'a TableOverlay> {
+        self.committed.into_iter().chain(self.written)
+    }
+}
+
+/// A lower bound of the entries of a table, ordered by key and then by value.
+#[derive(Debug, Clone, Copy)]
+enum LowerBound<'a> {
+    /// All entries.
+    Unbounded,
+    /// The entries with a greater or equal key.
+    Key(&'a [u8]),
+    /// The entries with a greater key.
+    AfterKey(&'a [u8]),
+    /// The entries that are greater than or equal to the entry.
+    Entry(&'a [u8], &'a [u8]),
+    /// The entries that are greater than the entry.
+    AfterEntry(&'a [u8], &'a [u8]),
+}
+
+impl<'a> LowerBound<'a> {
+    /// Returns the least key of the bound.
+    fn key(&self) -> Option<&'a [u8]> {
+        match *self {
+            Self::Unbounded => None,
+            Self::Key(key) |
+            Self::AfterKey(key) |
+            Self::Entry(key, _) |
+            Self::AfterEntry(key, _) => Some(key),
+        }
+    }
+
+    /// Returns the bound of the values of the key, or [None] if no entry of the key is in the
+    /// bound.
+    fn values(&self, key: &[u8]) -> Option<Bound<&'a [u8]>> {
+        match *self {
+            Self::Unbounded => Some(Bound::Unbounded),
+            Self::Key(start) => (key >= start).then_some(Bound::Unbounded),
+            Self::AfterKey(start) => (key > start).then_some(Bound::Unbounded),
+            Self::Entry(start, value) => match key.cmp(start) {
+                std::cmp::Ordering::Less => None,
+                std::cmp::Ordering::Equal => Some(Bound::Included(value)),
+                std::cmp::Ordering::Greater => Some(Bound::Unbounded),
+            },
+            Self::AfterEntry(start, value) => match key.cmp(start) {
+                std::cmp::Ordering::Less => None,
+                std::cmp::Ordering::Equal => Some(Bound::Excluded(value)),
+                std::cmp::Ordering::Greater => Some(Bound::Unbounded),
+            },
+        }
+    }
+
+    /// Returns `true` if the entry is in the bound.
+    fn contains(&self, (key, value): &RawEntry) -> bool {
+        self.values(key).is_some_and(|bound| (bound, Bound::Unbounded).contains(value.as_slice()))
+    }
+}
+
+/// A transaction that buffers its writes in memory on top of a read-only transaction.
+///
+/// Committing the transaction publishes the writes to the [OverlayDatabase] it was opened from,
+/// if any, the writes of a standalone transaction are discarded.
+#[derive(Debug)]
+pub struct OverlayTx<TX> {
+    /// The base transaction.
+    tx: TX,
+    /// The committed writes below the buffered writes, shared with other transactions.
+    committed: Arc<Overlay>,
+    /// The buffered writes.
+    overlay: Arc<RwLock<Overlay>>,
+    /// The committed writes of the [OverlayDatabase] to add the buffered writes to on commit.
+    publish: Option<Arc<RwLock<Arc<Overlay>>>>,
+}
+
+impl<TX: DbTx> OverlayTx<TX> {
+    /// Creates a new overlay on top of the transaction.
+    pub fn new(tx: TX) -> Self {
+        Self::with_committed(tx, Default::default())
+    }
+
+    /// Creates a new overlay on top of the transaction and already committed writes.
+    pub fn with_committed(tx: TX, committed: Arc<Overlay>) -> Self {
+        Self { tx, committed, overlay: Default::default(), publish: None }
+    }
+
+    /// Returns a copy of the buffered writes, without the committed writes below them.
+    pub fn overlay(&self) -> Overlay {
+        self.overlay.read().clone()
+    }
+}
+
+impl<TX: DbTx> DbTx for OverlayTx<TX> {
+    type Cursor<T: Table> = OverlayCursor<T, TX::Cursor<T>>;
+    type DupCursor<T: DupSort> = OverlayCursor<T, TX::DupCursor<T>>;
+
+    fn get<T: Table>(&self, key: T::Key) -> Result<Option<T::Value>, DatabaseError> {
+        let encoded = key.clone().encode();
+        if !TableLayers::new::<T>(&self.committed, &self.overlay.read())
+            .is_written(encoded.as_ref())
+        {
+            return self.tx.get::<T>(key)
+        }
+        Ok(self.cursor_read::<T>()?.seek_exact(key)?.map(|(_, value)| value))
+    }
+
+    fn commit(self) -> Result<bool, DatabaseError> {
+        let Self { tx, committed, overlay, publish } = self;
+        if let Some(publish) = publish {
+            // release the shared committed writes first, so they're only copied if another
+            // transaction still reads them
+            drop(committed);
+            let overlay = Arc::try_unwrap(overlay)
+                .map(RwLock::into_inner)
+                .unwrap_or_else(|overlay| overlay.read().clone());
+            Arc::make_mut(&mut publish.write()).extend(overlay);
+        }
+        tx.commit()
+    }
+
+    fn abort(self) {
+        self.tx.abort()
+    }
+
+    fn cursor_read<T: Table>(&self) -> Result<Self::Cursor<T>, DatabaseError> {
+        Ok(OverlayCursor::new(
+            self.tx.cursor_read::<T>()?,
+            self.committed.clone(),
+            self.overlay.clone(),
+        ))
+    }
+
+    fn cursor_dup_read<T: DupSort>(&self) -> Result<Self::DupCursor<T>, DatabaseError> {
+        Ok(OverlayCursor::new(
+            self.tx.cursor_dup_read::<T>()?,
+            self.committed.clone(),
+            self.overlay.clone(),
+        ))
+    }
+
+    fn entries<T: Table>(&self) -> Result<usize, DatabaseError> {
+        if TableLayers::new::<T>(&self.committed, &self.overlay.read()).is_empty() {
+            return self.tx.entries::<T>()
+        }
+        self.cursor_read::<T>()?
+            .walk(None)?
+            .try_fold(0, |entries, entry| entry.map(|_| entries + 1))
+    }
+}
+
+impl<TX: DbTx> DbTxMut for OverlayTx<TX> {
+    type CursorMut<T: Table> = OverlayCursor<T, TX::Cursor<T>>;
+    type DupCursorMut<T: DupSort> = OverlayCursor<T, TX::DupCursor<T>>;
+
+    fn put<T: Table>(&self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        self.overlay
+            .write()
+            .table_mut::<T>()
+            .put(encode_entry::<T>((key, value)), is_dupsort::<T>());
+        Ok(())
+    }
+
+    fn delete<T: Table>(
+        &self,
+        key: T::Key,
+        value: Option<T::Value>,
+    ) -> Result<bool, DatabaseError> {
+        let mut cursor = self.cursor_write::<T>()?;
+        if cursor.seek_exact(key)?.is_none() {
+            return Ok(false)
+        }
+
+        let Some(value) = value else {
+            if let Some((key, _)) = cursor.current.take() {
+                self.overlay.write().table_mut::<T>().delete_key(key);
+            }
+            return Ok(true)
+        };
+
+        // delete the first value of the key that matches
+        let value = value.compress();
+        while let Some((current_key, current_value)) = &cursor.current {
+            if current_value.as_slice() == value.as_ref() {
+                cursor.delete_current()?;
+                return Ok(true)
+            }
+
+            let key = current_key.clone();
+            cursor.next()?;
+            if cursor.current.as_ref().map_or(true, |(next_key, _)| *next_key != key) {
+                break
+            }
+        }
+        Ok(false)
+    }
+
+    fn clear<T: Table>(&self) -> Result<(), DatabaseError> {
+        *self.overlay.write().table_mut::<T>() =
+            TableOverlay { cleared: true, ..Default::default() };
+        Ok(())
+    }
+
+    fn cursor_write<T: Table>(&self) -> Result<Self::CursorMut<T>, DatabaseError> {
+        self.cursor_read::<T>()
+    }
+
+    fn cursor_dup_write<T: DupSort>(&self) -> Result<Self::DupCursorMut<T>, DatabaseError> {
+        self.cursor_dup_read::<T>()
+    }
+}
+
+impl<TX: DbTx> TableImporter for OverlayTx<TX> {}
+
+/// A cursor of an [OverlayTx] that merges the buffered writes with a cursor of the base
+/// transaction.
+#[derive(Debug)]
+pub struct OverlayCursor<T: Table, C> {
+    /// The cursor of the base transaction.
+    base: C,
+    /// The committed writes below the buffered writes of the transaction.
+    committed: Arc<Overlay>,
+    /// The buffered writes of the transaction.
+    overlay: Arc<RwLock<Overlay>>,
+    /// If `true`, the table is a dupsort table.
+    dupsort: bool,
+    /// The entry the cursor is positioned at.
+    current: Option<RawEntry>,
+    /// The first visible entry of the base cursor that isn't less than the current entry, if the
+    /// base cursor is known to be positioned at it. `Some(None)` if there is no such entry.
+    base_next: Option<Option<RawEntry>>,
+    _table: PhantomData<T>,
+}
+
+impl<T: Table, C: DbCursorRO<T>> OverlayCursor<T, C> {
+    fn new(base: C, committed: Arc<Overlay>, overlay: Arc<RwLock<Overlay>>) -> Self {
+        Self {
+            base,
+            committed,
+            overlay,
+            dupsort: is_dupsort::<T>(),
+            current: None,
+            base_next: None,
+            _table: PhantomData,
+        }
+    }
+
+    /// Returns the entry the cursor is positioned at.
+    fn current_entry(&self) -> PairResult<T> {
+        self.current.as_ref().map(decode_entry::<T>).transpose()
+    }
+
+    /// Returns the first entry of the base cursor in the bound and positions the base cursor at
+    /// it, starting from the known position of the base cursor if it's not in front of the bound.
+    fn base_first(&mut self, bound: LowerBound<'_>) -> Result<Option<RawEntry>, DatabaseError> {
+        let mut base = match self.base_next.take() {
+            Some(base) => base,
+            None => match bound.key() {
+                Some(key) => self.base.seek(T::Key::decode(key)?)?,
+                None => self.base.first()?,
+            }
+            .map(encode_entry::<T>),
+        };
+        while let Some(entry) = &base {
+            if bound.contains(entry) {
+                break
+            }
+            base = self.base.next()?.map(encode_entry::<T>);
+        }
+        Ok(base)
+    }
+
+    /// Positions the cursor at the first visible entry in the bound.
+    ///
+    /// The base cursor must be positioned at `base`, its first entry in the bound.
+    fn forward(&mut self, bound: LowerBound<'_>, mut base: Option<RawEntry>) -> PairResult<T> {
+        let overlay = self.overlay.read();
+        let table = TableLayers::new::<T>(&self.committed, &overlay);
+        if table.is_cleared() {
+            base = None;
+        }
+        // skip the entries of the base cursor that are hidden by the writes
+        while let Some(entry) = &base {
+            if table.is_visible(entry) {
+                break
+            }
+            base = self.base.next()?.map(encode_entry::<T>);
+        }
+        let written = table.first(bound);
+        drop(overlay);
+
+        self.base_next = Some(base.clone());
+        self.current = match (base, written) {
+            (Some(base), Some(written)) => Some(base.min(written)),
+            (base, written) => base.or(written),
+        };
+        self.current_entry()
+    }
+
+    /// Positions the cursor at the last visible entry that is less than the given one, or at the
+    /// last visible entry if [None].
+    ///
+    /// The base cursor must be positioned at `base`, the first entry that isn't less than the
+    /// given one.
+    fn backward(&mut self, entry: Option<RawEntry>, base: Option<RawEntry>) -> PairResult<T> {
+        let overlay = self.overlay.read();
+        let table = TableLayers::new::<T>(&self.committed, &overlay);
+        let mut base = match (table.is_cleared(), base) {
+            (true, _) => None,
+            (false, Some(_)) => self.base.prev()?.map(encode_entry::<T>),
+            (false, None) => self.base.last()?.map(encode_entry::<T>),
+        };
+        while let Some(base_entry) = &base {
+            if table.is_visible(base_entry) &&
+                entry.as_ref().map_or(true, |entry| base_entry < entry)
+            {
+                break
+            }
+            base = self.base.prev()?.map(encode_entry::<T>);
+        }
+        let written = table
+            .last_before(entry.as_ref().map(|(key, value)| (key.as_slice(), value.as_slice())));
+        drop(overlay);
+
+        self.current = match (base, written) {
+            (Some(base), Some(written)) if written > base => {
+                self.base_next = None;
+                Some(written)
+            }
+            (Some(base), _) => {
+                self.base_next = Some(Some(base.clone()));
+                Some(base)
+            }
+            (None, written) => {
+                self.base_next = None;
+                written
+            }
+        };
+        self.current_entry()
+    }
+
+    /// Writes the entry and positions the cursor at it.
+    fn write(&mut self, entry: RawEntry) {
+        self.overlay.write().table_mut::<T>().put(entry.clone(), self.dupsort);
+        self.current = Some(entry);
+        self.base_next = None;
+    }
+}
+
+impl<T: Table, C: DbCursorRO<T>> DbCursorRO<T> for OverlayCursor<T, C> {
+    fn first(&mut self) -> PairResult<T> {
+        self.base_next = None;
+        let base = self.base_first(LowerBound::Unbounded)?;
+        self.forward(LowerBound::Unbounded, base)
+    }
+
+    fn seek_exact(&mut self, key: T::Key) -> PairResult<T> {
+        let encoded = key.clone().encode();
+        let entry = self.seek(key)?;
+        if self.current.as_ref().is_some_and(|(key, _)| key.as_slice() == encoded.as_ref()) {
+            Ok(entry)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn seek(&mut self, key: T::Key) -> PairResult<T> {
+        let encoded: Vec<u8> = key.encode().into();
+        self.base_next = None;
+        let base = self.base_first(LowerBound::Key(&encoded))?;
+        self.forward(LowerBound::Key(&encoded), base)
+    }
+
+    fn next(&mut self) -> PairResult<T> {
+        let Some((key, value)) = self.current.take() else { return self.first() };
+        let base = self.base_first(LowerBound::AfterEntry(&key, &value))?;
+        self.forward(LowerBound::AfterEntry(&key, &value), base)
+    }
+
+    fn prev(&mut self) -> PairResult<T> {
+        let Some((key, value)) = self.current.take() else { return self.last() };
+        let base = self.base_first(LowerBound::Entry(&key, &value))?;
+        self.backward(Some((key, value)), base)
+    }
+
+    fn last(&mut self) -> PairResult<T> {
+        self.base_next = None;
+        self.backward(None, None)
+    }
+
+    fn current(&mut self) -> PairResult<T> {
+        self.current_entry()
+    }
+
+    fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
+        let start = match start_key {
+            Some(start_key) => self.seek(start_key),
+            None => self.first(),
+        }
+        .transpose();
+        Ok(Walker::new(self, start))
+    }
+
+    fn walk_range(
+        &mut self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<RangeWalker<'_, T, Self>, DatabaseError> {
+        let start = match range.start_bound().cloned() {
+            Bound::Included(key) => self.seek(key),
+            Bound::Excluded(_key) => {
+                unreachable!("Rust doesn't allow for Bound::Excluded in starting bounds");
+            }
+            Bound::Unbounded => self.first(),
+        }
+        .transpose();
+        Ok(RangeWalker::new(self, start, range.end_bound().cloned()))
+    }
+
+    fn walk_back(
+        &mut self,
+        start_key: Option<T::Key>,
+    ) -> Result<ReverseWalker<'_, T, Self>, DatabaseError> {
+        let start = match start_key {
+            Some(start_key) => self.seek(start_key),
+            None => self.last(),
+        }
+        .transpose();
+        Ok(ReverseWalker::new(self, start))
+    }
+}
+
+impl<T: DupSort, C: DbDupCursorRO<T> + DbCursorRO<T>> DbDupCursorRO<T> for OverlayCursor<T, C> {
+    fn next_dup(&mut self) -> PairResult<T> {
+        let Some(current) = self.current.clone() else { return Ok(None) };
+        let entry = self.next()?;
+        if self.current.as_ref().is_some_and(|(key, _)| *key == current.0) {
+            return Ok(entry)
+        }
+
+        // stay at the last value of the key
+        self.current = Some(current);
+        self.base_next = None;
+        Ok(None)
+    }
+
+    fn next_no_dup(&mut self) -> PairResult<T> {
+        let Some((key, _)) = self.current.take() else { return self.first() };
+        if matches!(&self.base_next, Some(Some((base_key, _))) if *base_key == key) {
+            self.base_next = Some(self.base.next_no_dup()?.map(encode_entry::<T>));
+        }
+        let base = self.base_first(LowerBound::AfterKey(&key))?;
+        self.forward(LowerBound::AfterKey(&key), base)
+    }
+
+    fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
+        Ok(self.next_dup()?.map(|(_, value)| value))
+    }
+
+    fn seek_by_key_subkey(&mut self, key: T::Key, subkey: T::SubKey) -> ValueOnlyResult<T> {
+        let encoded_key: Vec<u8> = key.clone().encode().into();
+        let encoded_subkey: Vec<u8> = subkey.clone().encode().into();
+
+        let base = match self.base.seek_by_key_subkey(key.clone(), subkey)? {
+            Some(value) => Some((encoded_key.clone(), value.compress().as_ref().to_vec())),
+            // no value of the key is in the bound, continue with the next key
+            None => match self.base.seek(key)?.map(encode_entry::<T>) {
+                Some((base_key, _)) if base_key == encoded_key => {
+                    self.base.next_no_dup()?.map(encode_entry::<T>)
+                }
+                base => base,
+            },
+        };
+        let entry = self.forward(LowerBound::Entry(&encoded_key, &encoded_subkey), base)?;
+        Ok(entry
+            .filter(|_| self.current.as_ref().is_some_and(|(key, _)| *key == encoded_key))
+            .map(|(_, value)| value))
+    }
+
+    fn walk_dup(
+        &mut self,
+        key: Option<T::Key>,
+        subkey: Option<T::SubKey>,
+    ) -> Result<DupWalker<'_, T, Self>, DatabaseError> {
+        let start = match (key, subkey) {
+            (Some(key), Some(subkey)) => {
+                self.seek_by_key_subkey(key.clone(), subkey)?.map(|value| Ok((key, value)))
+            }
+            (Some(key), None) => self.seek_exact(key).transpose(),
+            (None, Some(subkey)) => match self.first()? {
+                Some((key, _)) => {
+                    self.seek_by_key_subkey(key.clone(), subkey)?.map(|value| Ok((key, value)))
+                }
+                None => None,
+            },
+            (None, None) => self.first().transpose(),
+        };
+        Ok(DupWalker { cursor: self, start })
+    }
+}
+
+impl<T: Table, C: DbCursorRO<T>> DbCursorRW<T> for OverlayCursor<T, C> {
+    fn upsert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        self.write(encode_entry::<T>((key, value)));
+        Ok(())
+    }
+
+    fn insert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let entry = encode_entry::<T>((key.clone(), value));
+        if self.seek_exact(key)?.is_some() {
+            return Err(write_error::<T>(KEY_EXIST, DatabaseWriteOperation::CursorInsert, entry.0))
+        }
+        self.write(entry);
+        Ok(())
+    }
+
+    fn append(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let entry = encode_entry::<T>((key, value));
+        self.last()?;
+        if let Some(last) = &self.current {
+            let in_order = if self.dupsort { entry > *last } else { entry.0 > last.0 };
+            if !in_order {
+                return Err(write_error::<T>(
+                    KEY_MISMATCH,
+                    DatabaseWriteOperation::CursorAppend,
+                    entry.0,
+                ))
+            }
+        }
+        self.write(entry);
+        Ok(())
+    }
+
+    fn delete_current(&mut self) -> Result<(), DatabaseError> {
+        if let Some(entry) = self.current.clone() {
+            let mut overlay = self.overlay.write();
+            let table = overlay.table_mut::<T>();
+            if self.dupsort {
+                table.delete_value(entry);
+            } else {
+                table.delete_key(entry.0);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: DupSort, C: DbDupCursorRO<T> + DbCursorRO<T>> DbDupCursorRW<T> for OverlayCursor<T, C> {
+    fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
+        if let Some((key, _)) = self.current.clone() {
+            self.overlay.write().table_mut::<T>().delete_key(key);
+        }
+        Ok(())
+    }
+
+    /// Appends the value to the values of the key.
+    ///
+    /// Unlike with MDBX, the order is only checked against the buffered values of the key.
+    fn append_dup(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let entry = encode_entry::<T>((key, value));
+        let in_order = TableLayers::new::<T>(&self.committed, &self.overlay.read())
+            .last_value(&entry.0)
+            .map_or(true, |last| entry.1 > *last);
+        if !in_order {
+            return Err(write_error::<T>(
+                KEY_MISMATCH,
+                DatabaseWriteOperation::CursorAppendDup,
+                entry.0,
+            ))
+        }
+        self.write(entry);
+        Ok(())
+    }
+}
+
+/// Returns `true` if the table is a dupsort table.
+fn is_dupsort<T: Table>() -> bool {
+    Tables::from_str(T::NAME).is_ok_and(|table| table.table_type() == TableType::DupSort)
+}
+
+/// Encodes the key and compresses the value of the entry.
+fn encode_entry<T: Table>((key, value): (T::Key, T::Value)) -> RawEntry {
+    (key.encode().into(), value.compress().as_ref().to_vec())
+}
+
+/// Decodes the key and decompresses the value of the entry.
+fn decode_entry<T: Table>((key, value): &RawEntry) -> Result<(T::Key, T::Value), DatabaseError> {
+    Ok((T::Key::decode(key)?, T::Value::decompress(value)?))
+}
+
+fn write_error<T: Table>(
+    code: i32,
+    operation: DatabaseWriteOperation,
+    key: Vec<u8>,
+) -> DatabaseError {
+    DatabaseWriteError { code, operation, table_name: T::NAME, key }.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tables, test_utils::create_test_rw_db};
+    use reth_primitives::{Address, StorageEntry, B256, U256};
+
+    fn storage(key: u8, value: u64) -> StorageEntry {
+        StorageEntry { key: B256::with_last_byte(key), value: U256::from(value) }
+    }
+
+    #[test]
+    fn overlay_table() {
+        let db = create_test_rw_db();
+        db.update(|tx| {
+            for number in [1, 3, 5] {
+                tx.put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8))
+                    .unwrap();
+            }
+        })
+        .unwrap();
+
+        let tx = OverlayTx::new(db.tx().unwrap());
+        tx.put::<tables::CanonicalHeaders>(2, B256::with_last_byte(2)).unwrap();
+        tx.put::<tables::CanonicalHeaders>(3, B256::with_last_byte(33)).unwrap();
+        assert!(tx.delete::<tables::CanonicalHeaders>(5, None).unwrap());
+        assert!(!tx.delete::<tables::CanonicalHeaders>(6, None).unwrap());
+
+        assert_eq!(tx.get::<tables::CanonicalHeaders>(1).unwrap(), Some(B256::with_last_byte(1)));
+        assert_eq!(tx.get::<tables::CanonicalHeaders>(3).unwrap(), Some(B256::with_last_byte(33)));
+        assert_eq!(tx.get::<tables::CanonicalHeaders>(5).unwrap(), None);
+        assert_eq!(tx.entries::<tables::CanonicalHeaders>().unwrap(), 3);
+
+        let mut cursor = tx.cursor_write::<tables::CanonicalHeaders>().unwrap();
+        let entries = cursor.walk(None).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                (1, B256::with_last_byte(1)),
+                (2, B256::with_last_byte(2)),
+                (3, B256::with_last_byte(33))
+            ]
+        );
+        assert_eq!(cursor.last().unwrap(), Some((3, B256::with_last_byte(33))));
+        assert_eq!(cursor.prev().unwrap(), Some((2, B256::with_last_byte(2))));
+        assert_eq!(cursor.seek(4).unwrap(), None);
+        assert!(cursor.insert(1, B256::ZERO).is_err());
+        assert!(cursor.append(2, B256::ZERO).is_err());
+        cursor.append(4, B256::with_last_byte(4)).unwrap();
+
+        // delete while walking
+        let mut walker = cursor.walk_range(2..4).unwrap();
+        while walker.next().transpose().unwrap().is_some() {
+            walker.delete_current().unwrap();
+        }
+        let entries = cursor.walk_back(None).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(entries, vec![(4, B256::with_last_byte(4)), (1, B256::with_last_byte(1))]);
+
+        tx.clear::<tables::CanonicalHeaders>().unwrap();
+        assert_eq!(tx.entries::<tables::CanonicalHeaders>().unwrap(), 0);
+
+        // the base database is unchanged
+        drop(cursor);
+        tx.commit().unwrap();
+        assert_eq!(db.tx().unwrap().entries::<tables::CanonicalHeaders>().unwrap(), 3);
+    }
+
+    #[test]
+    fn overlay_dupsort_table() {
+        let address = Address::with_last_byte(1);
+        let db = create_test_rw_db();
+        db.update(|tx| {
+            for entry in [storage(1, 1), storage(3, 3), storage(5, 5)] {
+                tx.put::<tables::PlainStorageState>(address, entry).unwrap();
+            }
+            tx.put::<tables::PlainStorageState>(Address::with_last_byte(2), storage(1, 1)).unwrap();
+        })
+        .unwrap();
+
+        let tx = OverlayTx::new(db.tx().unwrap());
+        let mut cursor = tx.cursor_dup_write::<tables::PlainStorageState>().unwrap();
+        cursor.upsert(address, storage(4, 4)).unwrap();
+        assert_eq!(
+            cursor.seek_by_key_subkey(address, B256::with_last_byte(3)).unwrap(),
+            Some(storage(3, 3))
+        );
+        cursor.delete_current().unwrap();
+        assert!(tx.delete::<tables::PlainStorageState>(address, Some(storage(5, 5))).unwrap());
+        assert!(!tx.delete::<tables::PlainStorageState>(address, Some(storage(5, 5))).unwrap());
+        assert_eq!(
+            cursor.seek_by_key_subkey(address, B256::with_last_byte(2)).unwrap(),
+            Some(storage(4, 4))
+        );
+        assert_eq!(cursor.seek_by_key_subkey(address, B256::with_last_byte(5)).unwrap(), None);
+
+        let entries =
+            cursor.walk_dup(Some(address), None).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(entries, vec![(address, storage(1, 1)), (address, storage(4, 4))]);
+        assert_eq!(cursor.next_dup().unwrap(), None);
+        assert_eq!(
+            cursor.next_no_dup().unwrap(),
+            Some((Address::with_last_byte(2), storage(1, 1)))
+        );
+
+        cursor.seek_exact(address).unwrap();
+        cursor.delete_current_duplicates().unwrap();
+        assert_eq!(tx.get::<tables::PlainStorageState>(address).unwrap(), None);
+        cursor.append_dup(address, storage(2, 2)).unwrap();
+        assert!(cursor.append_dup(address, storage(1, 1)).is_err());
+        assert_eq!(tx.entries::<tables::PlainStorageState>().unwrap(), 2);
+    }
+
+    #[test]
+    fn overlay_database() {
+        let db = OverlayDatabase::new(create_test_rw_db());
+        db.update(|tx| tx.put::<tables::CanonicalHeaders>(1, B256::with_last_byte(1)))
+            .unwrap()
+            .unwrap();
+
+        // committed writes are seen by the following transactions only
+        let tx = db.tx_mut().unwrap();
+        tx.put::<tables::CanonicalHeaders>(2, B256::with_last_byte(2)).unwrap();
+        assert_eq!(db.tx().unwrap().entries::<tables::CanonicalHeaders>().unwrap(), 1);
+        tx.commit().unwrap();
+        assert_eq!(db.tx().unwrap().entries::<tables::CanonicalHeaders>().unwrap(), 2);
+
+        // writes on top of the committed writes, while an older transaction still reads them
+        let reader = db.tx().unwrap();
+        let tx = db.tx_mut().unwrap();
+        assert!(tx.delete::<tables::CanonicalHeaders>(1, None).unwrap());
+        tx.put::<tables::CanonicalHeaders>(2, B256::with_last_byte(22)).unwrap();
+        tx.put::<tables::CanonicalHeaders>(3, B256::with_last_byte(3)).unwrap();
+        tx.commit().unwrap();
+        assert_eq!(
+            reader.get::<tables::CanonicalHeaders>(2).unwrap(),
+            Some(B256::with_last_byte(2))
+        );
+        let entries = db
+            .tx()
+            .unwrap()
+            .cursor_read::<tables::CanonicalHeaders>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(entries, vec![(2, B256::with_last_byte(22)), (3, B256::with_last_byte(3))]);
+
+        // the base database is never written to
+        assert_eq!(db.inner().tx().unwrap().entries::<tables::CanonicalHeaders>().unwrap(), 0);
+    }
+}
@@ -57,6 +57,15 @@ pub trait Decode: Send + Sync + Sized + Debug {
     fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError>;
 }
 
+/// Trait for keys whose encoding always has the same length.
+///
+/// Only these keys can be followed by other keys in a composite key, e.g. `(Address, BlockNumber)`,
+/// because the encoding of the composite key has to be split again when decoding it.
+pub trait FixedSizeKey: Encode + Decode {
+    /// The length of the encoding.
+    const ENCODED_SIZE: usize;
+}
+
 /// Generic trait that enforces the database key to implement [`Encode`] and [`Decode`].
 pub trait Key: Encode + Decode + Ord + Clone + Serialize + for<'a> Deserialize<'a> {}
 
@@ -3,6 +3,7 @@ use crate::{
     table::{DupSort, Table},
     DatabaseError,
 };
+use std::ops::RangeBounds;
 
 /// Read only transaction
 pub trait DbTx: Send + Sync {
@@ -45,6 +46,21 @@ pub trait DbTxMut: Send + Sync {
         -> Result<bool, DatabaseError>;
     /// Clears database.
     fn clear<T: Table>(&self) -> Result<(), DatabaseError>;
+    /// Deletes all entries with keys in the range.
+    /// Returns number of deleted entries.
+    fn clear_range<T: Table>(
+        &self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<usize, DatabaseError> {
+        let mut cursor = self.cursor_write::<T>()?;
+        let mut walker = cursor.walk_range(range)?;
+        let mut deleted = 0;
+        while walker.next().transpose()?.is_some() {
+            walker.delete_current()?;
+            deleted += 1;
+        }
+        Ok(deleted)
+    }
     /// Cursor mut
     fn cursor_write<T: Table>(&self) -> Result<Self::CursorMut<T>, DatabaseError>;
     /// DupCursor mut.
@@ -0,0 +1,108 @@
+//! Compaction of the database into a copy without free pages.
+//!
+//! The copy is made from a read transaction, which keeps the database from reusing the pages freed
+//! while it's open, and a running node would keep writing blocks the copy doesn't have. The
+//! database should be opened with [open_db_exclusive](crate::open_db_exclusive) to compact it.
+
+use crate::{database_compaction::DatabaseCompaction, version::DB_VERSION_FILE_NAME};
+use eyre::WrapErr;
+use std::{
+    fs,
+    path::Path,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+/// The name of the MDBX data file.
+pub const DATA_FILE_NAME: &str = "mdbx.dat";
+
+/// Writes a compacted copy of the database at `db_path` to the directory at `dest`, which must not
+/// exist yet. The database version file is copied along, so `dest` can be opened as a database.
+///
+/// The copy is written on a separate thread, `on_progress` is called with the number of bytes
+/// written so far every `progress_interval`.
+pub fn compact<DB: DatabaseCompaction + Sync>(
+    db: &DB,
+    db_path: &Path,
+    dest: &Path,
+    progress_interval: Duration,
+    mut on_progress: impl FnMut(u64),
+) -> eyre::Result<()> {
+    fs::create_dir(dest)
+        .wrap_err_with(|| format!("Could not create directory {}", dest.display()))?;
+
+    let data_file = dest.join(DATA_FILE_NAME);
+    thread::scope(|scope| {
+        let (tx, rx) = mpsc::channel();
+        scope.spawn(move || {
+            let _ = tx.send(db.copy_compacted(dest));
+        });
+        loop {
+            match rx.recv_timeout(progress_interval) {
+                Ok(res) => return res,
+                Err(RecvTimeoutError::Timeout) => {
+                    if let Ok(metadata) = fs::metadata(&data_file) {
+                        on_progress(metadata.len());
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    panic!("database copy thread exited without a result")
+                }
+            }
+        }
+    })?;
+
+    let version_file = db_path.join(DB_VERSION_FILE_NAME);
+    if version_file.exists() {
+        fs::copy(version_file, dest.join(DB_VERSION_FILE_NAME))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        database::Database,
+        init_db, open_db, open_db_exclusive, tables,
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_primitives::B256;
+
+    #[test]
+    fn compact_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = init_db(dir.path(), None).unwrap();
+
+        for number in 0..1000 {
+            let tx = db.tx_mut().unwrap();
+            tx.put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8)).unwrap();
+            tx.commit().unwrap();
+        }
+        let tx = db.tx_mut().unwrap();
+        for number in 0..500 {
+            tx.delete::<tables::CanonicalHeaders>(number, None).unwrap();
+        }
+        tx.commit().unwrap();
+
+        let usage = db.page_usage().unwrap();
+        assert!(usage.free_pages > 0);
+        drop(db);
+
+        let db = open_db_exclusive(dir.path(), None).unwrap();
+        let dest = dir.path().join("compacted");
+        compact(&db, dir.path(), &dest, Duration::from_millis(1), |_| {}).unwrap();
+        assert!(dest.join(DB_VERSION_FILE_NAME).exists());
+        drop(db);
+
+        let db = open_db(&dest, None).unwrap();
+        assert!(db.page_usage().unwrap().total_pages < usage.total_pages);
+        let tx = db.tx().unwrap();
+        assert_eq!(tx.entries::<tables::CanonicalHeaders>().unwrap(), 500);
+        assert_eq!(
+            tx.get::<tables::CanonicalHeaders>(999).unwrap(),
+            Some(B256::with_last_byte(999u64 as u8))
+        );
+    }
+}
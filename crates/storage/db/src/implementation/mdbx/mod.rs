@@ -2,6 +2,7 @@
 
 use crate::{
     database::Database,
+    database_compaction::{DatabaseCompaction, PageUsage},
     database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics},
     tables::{TableType, Tables},
     utils::default_page_size,
@@ -9,7 +10,8 @@ use crate::{
 };
 use eyre::Context;
 use metrics::{gauge, Label};
-use reth_interfaces::db::LogLevel;
+use readers::{ReadTransactions, ReadTxLimits};
+use reth_interfaces::db::{DatabaseReader, DatabaseReaders, LogLevel};
 use reth_libmdbx::{
     DatabaseFlags, Environment, EnvironmentFlags, Geometry, Mode, PageSize, SyncMode, RO, RW,
 };
@@ -17,7 +19,9 @@ use reth_tracing::tracing::error;
 use std::{ops::Deref, path::Path};
 use tx::Tx;
 
+pub mod compaction;
 pub mod cursor;
+pub mod readers;
 pub mod tx;
 
 const GIGABYTE: usize = 1024 * 1024 * 1024;
@@ -33,6 +37,8 @@ pub enum DatabaseEnvKind {
     RO,
     /// Read-write MDBX environment.
     RW,
+    /// Read-write MDBX environment that can't be opened by any other process at the same time.
+    Exclusive,
 }
 
 /// Wrapper for the libmdbx environment: [Environment]
@@ -42,6 +48,8 @@ pub struct DatabaseEnv {
     inner: Environment,
     /// Whether to record metrics or not.
     with_metrics: bool,
+    /// The open read transactions.
+    read_transactions: ReadTransactions,
 }
 
 impl Database for DatabaseEnv {
@@ -49,10 +57,9 @@ impl Database for DatabaseEnv {
     type TXMut = tx::Tx<RW>;
 
     fn tx(&self) -> Result<Self::TX, DatabaseError> {
-        Ok(Tx::new_with_metrics(
-            self.inner.begin_ro_txn().map_err(|e| DatabaseError::InitTx(e.into()))?,
-            self.with_metrics,
-        ))
+        let inner = self.inner.begin_ro_txn().map_err(|e| DatabaseError::InitTx(e.into()))?;
+        let reader = self.read_transactions.register(inner.clone());
+        Ok(Tx::new_with_metrics(inner, self.with_metrics).with_reader(reader))
     }
 
     fn tx_mut(&self) -> Result<Self::TXMut, DatabaseError> {
@@ -128,16 +135,51 @@ impl DatabaseMetrics for DatabaseEnv {
             metrics.push(("db.freelist", freelist as f64, vec![]));
         }
 
+        let readers = self.read_transactions.readers();
+        metrics.push(("db.read_transactions", readers.len() as f64, vec![]));
+        let oldest_reader_age = readers.first().map_or(0.0, |reader| reader.age.as_secs_f64());
+        metrics.push(("db.oldest_read_transaction_age_seconds", oldest_reader_age, vec![]));
+
         metrics
     }
 }
 
+impl DatabaseReaders for DatabaseEnv {
+    fn readers(&self) -> Vec<DatabaseReader> {
+        self.read_transactions.readers()
+    }
+
+    fn check_readers(&self) {
+        self.read_transactions.check_readers()
+    }
+}
+
 impl DatabaseMetadata for DatabaseEnv {
     fn metadata(&self) -> DatabaseMetadataValue {
         DatabaseMetadataValue::new(self.freelist().ok())
     }
 }
 
+impl DatabaseCompaction for DatabaseEnv {
+    fn page_usage(&self) -> Result<PageUsage, DatabaseError> {
+        let stat = self.inner.stat().map_err(|e| DatabaseError::Stats(e.into()))?;
+        let info = self.inner.info().map_err(|e| DatabaseError::Stats(e.into()))?;
+        let free_pages = self.inner.freelist().map_err(|e| DatabaseError::Stats(e.into()))?;
+        Ok(PageUsage {
+            page_size: stat.page_size(),
+            // page numbers are zero-based
+            total_pages: info.last_pgno() + 1,
+            free_pages,
+        })
+    }
+
+    fn copy_compacted(&self, dest: &Path) -> Result<(), DatabaseError> {
+        self.inner
+            .copy(&dest.join(compaction::DATA_FILE_NAME), true)
+            .map_err(|e| DatabaseError::Copy(e.into()))
+    }
+}
+
 impl DatabaseEnv {
     /// Opens the database at the specified path with the given `EnvKind`.
     ///
@@ -151,7 +193,7 @@ impl DatabaseEnv {
 
         let mode = match kind {
             DatabaseEnvKind::RO => Mode::ReadOnly,
-            DatabaseEnvKind::RW => {
+            DatabaseEnvKind::RW | DatabaseEnvKind::Exclusive => {
                 // enable writemap mode in RW mode
                 inner_env.write_map();
                 Mode::ReadWrite { sync_mode: SyncMode::Durable }
@@ -174,6 +216,7 @@ impl DatabaseEnv {
             // worsens it for random access (which is our access pattern outside of sync)
             no_rdahead: true,
             coalesce: true,
+            exclusive: matches!(kind, DatabaseEnvKind::Exclusive),
             ..Default::default()
         });
         // configure more readers
@@ -234,6 +277,7 @@ impl DatabaseEnv {
         let env = DatabaseEnv {
             inner: inner_env.open(path).map_err(|e| DatabaseError::Open(e.into()))?,
             with_metrics: false,
+            read_transactions: ReadTransactions::default(),
         };
 
         Ok(env)
@@ -245,6 +289,19 @@ impl DatabaseEnv {
         self
     }
 
+    /// Sets the thresholds for the duration of read transactions.
+    ///
+    /// Only applies to the read transactions that are opened afterwards.
+    pub fn with_read_tx_limits(mut self, limits: ReadTxLimits) -> Self {
+        self.read_transactions = ReadTransactions::new(limits);
+        self
+    }
+
+    /// Returns the registry of the open read transactions.
+    pub fn read_transactions(&self) -> ReadTransactions {
+        self.read_transactions.clone()
+    }
+
     /// Creates all the defined tables, if necessary.
     pub fn create_tables(&self) -> Result<(), DatabaseError> {
         let tx = self.inner.begin_rw_txn().map_err(|e| DatabaseError::InitTx(e.into()))?;
@@ -278,6 +335,7 @@ mod tests {
     use super::*;
     use crate::{
         abstraction::table::{Encode, Table},
+        bulk::BulkAppender,
         cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, ReverseWalker, Walker},
         database::Database,
         models::{AccountBeforeTx, ShardedKey},
@@ -685,6 +743,32 @@ mod tests {
         assert!(dup_cursor.insert(key, entry2).is_err());
     }
 
+    #[test]
+    fn db_clear_range() {
+        let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
+
+        // PUT (0, 0), (1, 0), (2, 0), (3, 0), (4, 0)
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        vec![0, 1, 2, 3, 4]
+            .into_iter()
+            .try_for_each(|key| tx.put::<CanonicalHeaders>(key, B256::ZERO))
+            .expect(ERROR_PUT);
+
+        // [1, 3)
+        assert_eq!(tx.clear_range::<CanonicalHeaders>(1..3), Ok(2));
+        // [3, ∞)
+        assert_eq!(tx.clear_range::<CanonicalHeaders>(3..), Ok(2));
+        // nothing left in the range
+        assert_eq!(tx.clear_range::<CanonicalHeaders>(1..), Ok(0));
+        tx.commit().expect(ERROR_COMMIT);
+
+        let tx = db.tx().expect(ERROR_INIT_TX);
+        let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+        let mut walker = cursor.walk(None).unwrap();
+        assert_eq!(walker.next(), Some(Ok((0, B256::ZERO))));
+        assert_eq!(walker.next(), None);
+    }
+
     #[test]
     fn db_cursor_delete_current_non_existent() {
         let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
@@ -773,6 +857,91 @@ mod tests {
         tx.commit().expect(ERROR_COMMIT);
     }
 
+    #[test]
+    fn db_bulk_appender() {
+        let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
+
+        // PUT
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        vec![0, 2, 4]
+            .into_iter()
+            .try_for_each(|key| tx.put::<CanonicalHeaders>(key, B256::ZERO))
+            .expect(ERROR_PUT);
+        tx.commit().expect(ERROR_COMMIT);
+
+        // BULK APPEND unsorted rows with one duplicate
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        let mut cursor = tx.cursor_write::<CanonicalHeaders>().unwrap();
+        let mut appender = BulkAppender::<CanonicalHeaders>::with_capacity(3);
+        appender.extend([
+            (6, B256::with_last_byte(1)),
+            (5, B256::with_last_byte(1)),
+            (5, B256::with_last_byte(2)),
+        ]);
+        assert_eq!(appender.flush(&mut cursor), Ok(2));
+        assert!(appender.is_empty());
+
+        // BULK APPEND a row below the last key
+        appender.push(3, B256::with_last_byte(1));
+        assert_eq!(
+            appender.flush(&mut cursor),
+            Err(DatabaseWriteError {
+                code: -30418,
+                operation: DatabaseWriteOperation::CursorAppend,
+                table_name: CanonicalHeaders::NAME,
+                key: 3u64.encode().into(),
+            }
+            .into())
+        );
+        tx.commit().expect(ERROR_COMMIT);
+
+        // Confirm the result
+        let tx = db.tx().expect(ERROR_INIT_TX);
+        let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+        let res = cursor.walk(None).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(
+            res,
+            vec![
+                (0, B256::ZERO),
+                (2, B256::ZERO),
+                (4, B256::ZERO),
+                (5, B256::with_last_byte(2)),
+                (6, B256::with_last_byte(1)),
+            ]
+        );
+        tx.commit().expect(ERROR_COMMIT);
+    }
+
+    #[test]
+    fn db_bulk_appender_dup() {
+        let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
+        let entry =
+            |address| AccountBeforeTx { address: Address::with_last_byte(address), info: None };
+
+        // PUT
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        tx.put::<AccountChangeSet>(1, entry(1)).expect(ERROR_PUT);
+        tx.commit().expect(ERROR_COMMIT);
+
+        // BULK APPEND DUP rows of unsorted keys, the values of each key in order
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        let mut cursor = tx.cursor_dup_write::<AccountChangeSet>().unwrap();
+        let mut appender = BulkAppender::<AccountChangeSet>::default();
+        appender.extend([(2, entry(1)), (1, entry(2)), (2, entry(3)), (1, entry(3))]);
+        assert_eq!(appender.flush_dup(&mut cursor), Ok(4));
+        tx.commit().expect(ERROR_COMMIT);
+
+        // Confirm the result
+        let tx = db.tx().expect(ERROR_INIT_TX);
+        let mut cursor = tx.cursor_read::<AccountChangeSet>().unwrap();
+        let res = cursor.walk(None).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(
+            res,
+            vec![(1, entry(1)), (1, entry(2)), (1, entry(3)), (2, entry(1)), (2, entry(3))]
+        );
+        tx.commit().expect(ERROR_COMMIT);
+    }
+
     #[test]
     fn db_cursor_append_failure() {
         let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
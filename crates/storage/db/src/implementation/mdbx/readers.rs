@@ -0,0 +1,255 @@
+//! Tracking of the open read transactions.
+//!
+//! A read transaction keeps the snapshot it reads alive, so MDBX can't reuse the pages that were
+//! freed after the snapshot was taken until the transaction is closed. Long-lived read transactions
+//! therefore make the database grow.
+
+use crate::{metrics::ReadTransactionMetrics, DatabaseError};
+use reth_interfaces::db::{reader_component, DatabaseReader, DatabaseReaders};
+use reth_libmdbx::{Transaction, RO};
+use reth_tracing::tracing::debug;
+use std::{
+    backtrace::Backtrace,
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
+
+/// Duration after which a read transaction is reported as long-lived by default.
+pub const DEFAULT_READ_TX_WARN_AFTER: Duration = Duration::from_secs(60);
+
+/// The number of shards of the registry, so transactions opened concurrently rarely contend on the
+/// same lock.
+const SHARDS: usize = 16;
+
+/// Thresholds for the duration of read transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadTxLimits {
+    /// Duration after which the transaction is reported as long-lived, with the backtrace of the
+    /// operation that found it.
+    pub warn_after: Duration,
+    /// Duration after which the transaction is reset, which releases its snapshot: every
+    /// following operation on it or its cursors fails, so the caller drops it.
+    ///
+    /// If [None], read transactions never time out.
+    pub kill_after: Option<Duration>,
+}
+
+impl Default for ReadTxLimits {
+    fn default() -> Self {
+        Self { warn_after: DEFAULT_READ_TX_WARN_AFTER, kill_after: None }
+    }
+}
+
+/// The registry of the open read transactions of a database, see [DatabaseReaders].
+#[derive(Debug, Clone, Default)]
+pub struct ReadTransactions {
+    inner: Arc<ReadTransactionsInner>,
+}
+
+impl ReadTransactions {
+    /// Creates a new registry with the given limits.
+    pub fn new(limits: ReadTxLimits) -> Self {
+        Self { inner: Arc::new(ReadTransactionsInner { limits, ..Default::default() }) }
+    }
+
+    /// Returns the limits of the read transactions.
+    pub fn limits(&self) -> ReadTxLimits {
+        self.inner.limits
+    }
+
+    /// Registers a new read transaction until the returned guard is dropped.
+    pub(crate) fn register(&self, txn: Transaction<RO>) -> ReaderGuard {
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        let reader = Arc::new(OpenReader {
+            txn_id: txn.id(),
+            txn,
+            start: Instant::now(),
+            component: reader_component(),
+            thread: thread::current(),
+            warned: AtomicBool::new(false),
+            timed_out: AtomicBool::new(false),
+        });
+        self.inner.shard(id).insert(id, reader.clone());
+        ReaderGuard { registry: self.clone(), id, reader }
+    }
+
+    /// Returns the open read transactions, the oldest first.
+    fn open_readers(&self) -> Vec<Arc<OpenReader>> {
+        let mut readers = self
+            .inner
+            .shards
+            .iter()
+            .flat_map(|shard| {
+                let shard = lock(shard);
+                shard.iter().map(|(id, reader)| (*id, reader.clone())).collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        // the IDs are assigned in the order the transactions were opened
+        readers.sort_unstable_by_key(|(id, _)| *id);
+        readers.into_iter().map(|(_, reader)| reader).collect()
+    }
+}
+
+impl DatabaseReaders for ReadTransactions {
+    fn readers(&self) -> Vec<DatabaseReader> {
+        self.open_readers()
+            .into_iter()
+            .map(|reader| DatabaseReader {
+                txn_id: reader.txn_id,
+                age: reader.start.elapsed(),
+                component: reader.component().to_string(),
+            })
+            .collect()
+    }
+
+    fn check_readers(&self) {
+        for reader in self.open_readers() {
+            let _ = reader.check(&self.inner.limits, false);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ReadTransactionsInner {
+    limits: ReadTxLimits,
+    next_id: AtomicU64,
+    shards: [Mutex<HashMap<u64, Arc<OpenReader>>>; SHARDS],
+}
+
+impl ReadTransactionsInner {
+    fn shard(&self, id: u64) -> MutexGuard<'_, HashMap<u64, Arc<OpenReader>>> {
+        lock(&self.shards[id as usize % SHARDS])
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[derive(Debug)]
+struct OpenReader {
+    /// The transaction, to reset it once it timed out.
+    txn: Transaction<RO>,
+    txn_id: u64,
+    start: Instant,
+    /// The component the thread that opened the transaction was labeled with.
+    component: Option<&'static str>,
+    /// The thread that opened the transaction, whose name is used if it isn't labeled.
+    thread: Thread,
+    /// If `true`, the transaction has already been reported as long-lived.
+    warned: AtomicBool,
+    /// If `true`, the transaction has already been reset.
+    timed_out: AtomicBool,
+}
+
+impl OpenReader {
+    fn component(&self) -> &str {
+        self.component.or_else(|| self.thread.name()).unwrap_or("unnamed")
+    }
+
+    /// Checks the age of the transaction against the [ReadTxLimits].
+    ///
+    /// Reports the transaction once it's older than [ReadTxLimits::warn_after], with the
+    /// backtrace of the current call if `backtrace` is set, and resets it and returns an error
+    /// once it's older than [ReadTxLimits::kill_after].
+    fn check(&self, limits: &ReadTxLimits, backtrace: bool) -> Result<(), DatabaseError> {
+        let age = self.start.elapsed();
+
+        if age > limits.warn_after && !self.warned.swap(true, Ordering::Relaxed) {
+            ReadTransactionMetrics::default().long_lived_total.increment(1);
+            let backtrace = backtrace.then(Backtrace::force_capture);
+            debug!(
+                target: "storage::db::mdbx",
+                ?age,
+                component = self.component(),
+                ?backtrace,
+                "The database read transaction has been open for too long"
+            );
+        }
+
+        if let Some(kill_after) = limits.kill_after.filter(|kill_after| age > *kill_after) {
+            if !self.timed_out.swap(true, Ordering::Relaxed) {
+                ReadTransactionMetrics::default().timed_out_total.increment(1);
+                // the snapshot is released right away instead of once the transaction is dropped
+                if let Err(err) = self.txn.reset() {
+                    debug!(target: "storage::db::mdbx", %err, "Failed to reset the read transaction");
+                }
+            }
+            return Err(DatabaseError::ReadTransactionTimeout(kill_after))
+        }
+        Ok(())
+    }
+}
+
+/// Keeps a read transaction registered in [ReadTransactions] until it's dropped.
+#[derive(Debug)]
+pub(crate) struct ReaderGuard {
+    registry: ReadTransactions,
+    id: u64,
+    reader: Arc<OpenReader>,
+}
+
+impl ReaderGuard {
+    /// Checks the age of the transaction against the [ReadTxLimits] before an operation.
+    ///
+    /// Reports the backtrace of the current call once the transaction is older than
+    /// [ReadTxLimits::warn_after] and returns an error once it's older than
+    /// [ReadTxLimits::kill_after].
+    pub(crate) fn check(&self) -> Result<(), DatabaseError> {
+        self.reader.check(&self.registry.inner.limits, true)
+    }
+}
+
+impl Drop for ReaderGuard {
+    fn drop(&mut self) {
+        self.registry.inner.shard(self.id).remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_rw_db;
+    use reth_interfaces::db::set_reader_component;
+
+    #[test]
+    fn registers_readers() {
+        let db = create_test_rw_db();
+        let registry = ReadTransactions::new(ReadTxLimits {
+            warn_after: Duration::ZERO,
+            kill_after: Some(Duration::from_millis(10)),
+        });
+
+        let component = set_reader_component("test");
+        let first = registry.register(db.db().inner.begin_ro_txn().unwrap());
+        drop(component);
+        let second = registry.register(db.db().inner.begin_ro_txn().unwrap());
+        let readers = registry.readers();
+        assert_eq!(
+            readers.iter().map(|reader| reader.txn_id).collect::<Vec<_>>(),
+            vec![first.reader.txn_id, second.reader.txn_id]
+        );
+        assert_eq!(readers[0].component, "test");
+        assert_ne!(readers[1].component, "test");
+        assert!(first.check().is_ok());
+
+        drop(second);
+        assert_eq!(registry.readers().len(), 1);
+
+        // the idle transaction is reset by the periodic check
+        thread::sleep(Duration::from_millis(20));
+        registry.check_readers();
+        assert!(first.reader.timed_out.load(Ordering::Relaxed));
+        assert_eq!(
+            first.check(),
+            Err(DatabaseError::ReadTransactionTimeout(Duration::from_millis(10)))
+        );
+        drop(first);
+        assert!(registry.readers().is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 //! Transaction wrapper for libmdbx-sys.
 
-use super::cursor::Cursor;
+use super::{cursor::Cursor, readers::ReaderGuard};
 use crate::{
     metrics::{
         Operation, OperationMetrics, TransactionMetrics, TransactionMode, TransactionOutcome,
@@ -13,20 +13,7 @@ use crate::{
 use parking_lot::RwLock;
 use reth_interfaces::db::{DatabaseWriteError, DatabaseWriteOperation};
 use reth_libmdbx::{ffi::DBI, CommitLatency, Transaction, TransactionKind, WriteFlags, RW};
-use reth_tracing::tracing::debug;
-use std::{
-    backtrace::Backtrace,
-    marker::PhantomData,
-    str::FromStr,
-    sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
-    },
-    time::{Duration, Instant},
-};
-
-/// Duration after which we emit the log about long-lived database transactions.
-const LONG_TRANSACTION_DURATION: Duration = Duration::from_secs(60);
+use std::{marker::PhantomData, str::FromStr, sync::Arc, time::Instant};
 
 /// Wrapper for the libmdbx transaction.
 #[derive(Debug)]
@@ -40,12 +27,17 @@ pub struct Tx<K: TransactionKind> {
     ///
     /// If [Some], then metrics are reported.
     metrics_handler: Option<MetricsHandler<K>>,
+    /// Registration of the read transaction in the
+    /// [ReadTransactions](super::readers::ReadTransactions) of the database.
+    ///
+    /// If [Some], the operations fail once the transaction timed out.
+    reader: Option<ReaderGuard>,
 }
 
 impl<K: TransactionKind> Tx<K> {
     /// Creates new `Tx` object with a `RO` or `RW` transaction.
     pub fn new(inner: Transaction<K>) -> Self {
-        Self { inner, db_handles: Default::default(), metrics_handler: None }
+        Self { inner, db_handles: Default::default(), metrics_handler: None, reader: None }
     }
 
     /// Creates new `Tx` object with a `RO` or `RW` transaction and optionally enables metrics.
@@ -55,7 +47,19 @@ impl<K: TransactionKind> Tx<K> {
             TransactionMetrics::record_open(handler.transaction_mode());
             handler
         });
-        Self { inner, db_handles: Default::default(), metrics_handler }
+        Self { inner, db_handles: Default::default(), metrics_handler, reader: None }
+    }
+
+    /// Sets the registration of the read transaction that limits its lifetime.
+    pub(crate) fn with_reader(mut self, reader: ReaderGuard) -> Self {
+        self.reader = Some(reader);
+        self
+    }
+
+    /// Returns an error if the read transaction timed out, see
+    /// [ReadTxLimits](super::readers::ReadTxLimits).
+    fn ensure_not_timed_out(&self) -> Result<(), DatabaseError> {
+        self.reader.as_ref().map_or(Ok(()), ReaderGuard::check)
     }
 
     /// Gets this transaction ID.
@@ -84,6 +88,7 @@ impl<K: TransactionKind> Tx<K> {
 
     /// Create db Cursor
     pub fn new_cursor<T: Table>(&self) -> Result<Cursor<K, T>, DatabaseError> {
+        self.ensure_not_timed_out()?;
         let inner = self
             .inner
             .cursor_with_dbi(self.get_dbi::<T>()?)
@@ -103,7 +108,6 @@ impl<K: TransactionKind> Tx<K> {
     ) -> R {
         if let Some(mut metrics_handler) = self.metrics_handler.take() {
             metrics_handler.close_recorded = true;
-            metrics_handler.log_backtrace_on_long_transaction();
 
             let start = Instant::now();
             let (result, commit_latency) = f(self);
@@ -134,8 +138,7 @@ impl<K: TransactionKind> Tx<K> {
         value_size: Option<usize>,
         f: impl FnOnce(&Transaction<K>) -> R,
     ) -> R {
-        if let Some(metrics_handler) = &self.metrics_handler {
-            metrics_handler.log_backtrace_on_long_transaction();
+        if self.metrics_handler.is_some() {
             OperationMetrics::record(T::NAME, operation, value_size, || f(&self.inner))
         } else {
             f(&self.inner)
@@ -152,21 +155,12 @@ struct MetricsHandler<K: TransactionKind> {
     /// If `true`, the metric about transaction closing has already been recorded and we don't need
     /// to do anything on [Drop::drop].
     close_recorded: bool,
-    /// If `true`, the backtrace of transaction has already been recorded and logged.
-    /// See [MetricsHandler::log_backtrace_on_long_transaction].
-    backtrace_recorded: AtomicBool,
     _marker: PhantomData<K>,
 }
 
 impl<K: TransactionKind> MetricsHandler<K> {
     fn new(txn_id: u64) -> Self {
-        Self {
-            txn_id,
-            start: Instant::now(),
-            close_recorded: false,
-            backtrace_recorded: AtomicBool::new(false),
-            _marker: PhantomData,
-        }
+        Self { txn_id, start: Instant::now(), close_recorded: false, _marker: PhantomData }
     }
 
     const fn transaction_mode(&self) -> TransactionMode {
@@ -176,38 +170,11 @@ impl<K: TransactionKind> MetricsHandler<K> {
             TransactionMode::ReadWrite
         }
     }
-
-    /// Logs the backtrace of current call if the duration that the read transaction has been open
-    /// is more than [LONG_TRANSACTION_DURATION].
-    /// The backtrace is recorded and logged just once, guaranteed by `backtrace_recorded` atomic.
-    ///
-    /// NOTE: Backtrace is recorded using [Backtrace::force_capture], so `RUST_BACKTRACE` env var is
-    /// not needed.
-    fn log_backtrace_on_long_transaction(&self) {
-        if !self.backtrace_recorded.load(Ordering::Relaxed) &&
-            self.transaction_mode().is_read_only()
-        {
-            let open_duration = self.start.elapsed();
-            if open_duration > LONG_TRANSACTION_DURATION {
-                self.backtrace_recorded.store(true, Ordering::Relaxed);
-
-                let backtrace = Backtrace::force_capture();
-                debug!(
-                    target: "storage::db::mdbx",
-                    ?open_duration,
-                    ?backtrace,
-                    "The database read transaction has been open for too long"
-                );
-            }
-        }
-    }
 }
 
 impl<K: TransactionKind> Drop for MetricsHandler<K> {
     fn drop(&mut self) {
         if !self.close_recorded {
-            self.log_backtrace_on_long_transaction();
-
             TransactionMetrics::record_close(
                 self.transaction_mode(),
                 TransactionOutcome::Drop,
@@ -226,6 +193,7 @@ impl<K: TransactionKind> DbTx for Tx<K> {
     type DupCursor<T: DupSort> = Cursor<K, T>;
 
     fn get<T: Table>(&self, key: T::Key) -> Result<Option<<T as Table>::Value>, DatabaseError> {
+        self.ensure_not_timed_out()?;
         self.execute_with_operation_metric::<T, _>(Operation::Get, None, |tx| {
             tx.get(self.get_dbi::<T>()?, key.encode().as_ref())
                 .map_err(|e| DatabaseError::Read(e.into()))?
@@ -261,6 +229,7 @@ impl<K: TransactionKind> DbTx for Tx<K> {
 
     /// Returns number of entries in the table using cheap DB stats invocation.
     fn entries<T: Table>(&self) -> Result<usize, DatabaseError> {
+        self.ensure_not_timed_out()?;
         Ok(self
             .inner
             .db_stat_with_dbi(self.get_dbi::<T>()?)
@@ -149,14 +149,34 @@ pub fn open_db(path: &Path, log_level: Option<LogLevel>) -> eyre::Result<Databas
     }
 }
 
+/// Opens up an existing database in exclusive mode. Read/Write mode with WriteMap enabled. Fails if
+/// the database is already open in another process, like a running node.
+pub fn open_db_exclusive(path: &Path, log_level: Option<LogLevel>) -> eyre::Result<DatabaseEnv> {
+    #[cfg(feature = "mdbx")]
+    {
+        DatabaseEnv::open(path, DatabaseEnvKind::Exclusive, log_level).with_context(|| {
+            format!(
+                "Could not open database at path: {}, is it open in another process?",
+                path.display()
+            )
+        })
+    }
+    #[cfg(not(feature = "mdbx"))]
+    {
+        unimplemented!();
+    }
+}
+
 /// Collection of database test utilities
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils {
     use super::*;
     use crate::{
         database::Database,
+        database_compaction::{DatabaseCompaction, PageUsage},
         database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics},
     };
+    use reth_interfaces::db::{DatabaseReader, DatabaseReaders};
     use reth_primitives::fs;
     use std::{path::PathBuf, sync::Arc};
 
@@ -226,6 +246,26 @@ pub mod test_utils {
         }
     }
 
+    impl<DB: DatabaseReaders> DatabaseReaders for TempDatabase<DB> {
+        fn readers(&self) -> Vec<DatabaseReader> {
+            self.db().readers()
+        }
+
+        fn check_readers(&self) {
+            self.db().check_readers()
+        }
+    }
+
+    impl<DB: DatabaseCompaction> DatabaseCompaction for TempDatabase<DB> {
+        fn page_usage(&self) -> Result<PageUsage, DatabaseError> {
+            self.db().page_usage()
+        }
+
+        fn copy_compacted(&self, dest: &Path) -> Result<(), DatabaseError> {
+            self.db().copy_compacted(dest)
+        }
+    }
+
     /// Get a temporary directory path to use for the database
     pub fn tempdir_path() -> PathBuf {
         let builder = tempfile::Builder::new().prefix("reth-test-").rand_bytes(8).tempdir();
@@ -19,11 +19,6 @@ impl TransactionMode {
             TransactionMode::ReadWrite => "read-write",
         }
     }
-
-    /// Returns `true` if the transaction mode is read-only.
-    pub(crate) const fn is_read_only(&self) -> bool {
-        matches!(self, TransactionMode::ReadOnly)
-    }
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -201,3 +196,12 @@ impl OperationMetrics {
         }
     }
 }
+
+#[derive(Metrics, Clone)]
+#[metrics(scope = "database.read_transaction")]
+pub(crate) struct ReadTransactionMetrics {
+    /// Total number of read transactions that were open for longer than the warning threshold
+    pub(crate) long_lived_total: Counter,
+    /// Total number of read transactions that timed out and were reset
+    pub(crate) timed_out_total: Counter,
+}
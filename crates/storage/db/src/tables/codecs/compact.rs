@@ -33,6 +33,7 @@ impl_compression_for_compact!(
     Account,
     Log,
     Receipt,
+    Bloom,
     TxType,
     StorageEntry,
     StoredBranchNode,
@@ -42,14 +43,44 @@ impl_compression_for_compact!(
     StoredBlockBodyIndices,
     StoredBlockOmmers,
     StoredBlockWithdrawals,
+    StoredBlockInternalTransfers,
     Bytecode,
     AccountBeforeTx,
+    StoredContractCreation,
     TransactionSignedNoHash,
     CompactU256,
-    StageCheckpoint,
     PruneCheckpoint
 );
 
+/// [StageCheckpoint]s are stored with their [Compact] encoding, which is the encoding that existing
+/// databases and previous releases use, so that a database stays readable by both.
+impl Compress for StageCheckpoint {
+    type Compressed = Vec<u8>;
+
+    fn compress_to_buf<B: bytes::BufMut + AsMut<[u8]>>(self, buf: &mut B) {
+        let _ = Compact::to_compact(self, buf);
+    }
+}
+
+impl Decompress for StageCheckpoint {
+    /// Decodes the [Compact] encoding and the block number as big-endian `u64`, which was stored
+    /// before stage-specific checkpoints existed.
+    fn decompress<B: AsRef<[u8]>>(value: B) -> Result<StageCheckpoint, crate::DatabaseError> {
+        let value = value.as_ref();
+        match value {
+            [] => Err(crate::DatabaseError::Decode),
+            // the compact encoding of a checkpoint without any flag set is a single byte, and the
+            // compact flags of a checkpoint fit into the lower five bits, so this can only be a
+            // legacy block number below 2^56
+            [0, ..] if value.len() == std::mem::size_of::<BlockNumber>() => {
+                let block_number = BlockNumber::from_be_bytes(value.try_into().expect("8 bytes"));
+                Ok(StageCheckpoint::new(block_number))
+            }
+            _ => Ok(Compact::from_compact(value, value.len()).0),
+        }
+    }
+}
+
 macro_rules! impl_compression_fixed_compact {
     ($($name:tt),+) => {
         $(
@@ -117,3 +148,36 @@ macro_rules! add_wrapper_struct {
 
 add_wrapper_struct!((U256, CompactU256));
 add_wrapper_struct!((u64, CompactU64));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::stage::EntitiesCheckpoint;
+
+    #[test]
+    fn stage_checkpoint_roundtrip() {
+        for checkpoint in [
+            StageCheckpoint::default(),
+            StageCheckpoint::new(u64::MAX),
+            StageCheckpoint::new(18_000_000).with_entities_stage_checkpoint(EntitiesCheckpoint {
+                processed: 1_000,
+                total: 2_000_000_000,
+            }),
+        ] {
+            // the encoding stays the compact one, which previous releases can read
+            let mut encoded = Vec::new();
+            checkpoint.to_compact(&mut encoded);
+            let compressed = checkpoint.compress();
+            assert_eq!(compressed, encoded);
+            assert_eq!(StageCheckpoint::decompress(compressed), Ok(checkpoint));
+        }
+    }
+
+    #[test]
+    fn decompress_legacy_stage_checkpoint() {
+        let legacy = 18_000_000u64.to_be_bytes();
+        assert_eq!(StageCheckpoint::decompress(legacy), Ok(StageCheckpoint::new(18_000_000)));
+        assert_eq!(StageCheckpoint::decompress(0u64.to_be_bytes()), Ok(StageCheckpoint::new(0)));
+        assert_eq!(StageCheckpoint::decompress([]), Err(crate::DatabaseError::Decode));
+    }
+}
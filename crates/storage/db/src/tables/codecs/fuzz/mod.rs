@@ -25,6 +25,9 @@ macro_rules! impl_fuzzer_with_input {
                 #[allow(unused_imports)]
                 use crate::tables::models::*;
 
+                #[allow(unused_imports)]
+                use crate::tables::models::storage_sharded_key::*;
+
                 /// Encodes and decodes table types returning its encoded size and the decoded object.
                 /// This method is used for benchmarking, so its parameter should be the actual type that is being tested.
                 pub fn encode_and_decode(obj: $name) -> (usize, $name)
@@ -66,11 +69,10 @@ macro_rules! impl_fuzzer_key {
 
 /// Fuzzer generates a random instance of the object and proceeds to compress and decompress it. It
 /// then makes sure that it matches the original object.
-#[allow(unused_macros)]
 macro_rules! impl_fuzzer_value {
     ($($name:tt),+) => {
         $(
-            impl_fuzzer_value_with_input!($name, $name);
+            impl_fuzzer_value_with_input!(($name, $name));
         )+
     };
 }
@@ -86,5 +88,18 @@ macro_rules! impl_fuzzer_value_with_input {
     };
 }
 
-impl_fuzzer_key!(BlockNumberAddress);
+impl_fuzzer_key!(BlockNumberAddress, StorageShardedKey);
+impl_fuzzer_value!(
+    Header,
+    Account,
+    Receipt,
+    StorageEntry,
+    StoredBlockBodyIndices,
+    StoredBlockOmmers,
+    StoredBlockWithdrawals,
+    StoredBlockInternalTransfers,
+    Bytecode,
+    TransactionSignedNoHash,
+    StageCheckpoint
+);
 impl_fuzzer_value_with_input!((IntegerList, IntegerListInput));
@@ -27,18 +27,20 @@ use crate::{
     tables::{
         codecs::CompactU256,
         models::{
-            accounts::{AccountBeforeTx, BlockNumberAddress},
+            accounts::{AccountBeforeTx, BlockNumberAddress, StoredContractCreation},
             blocks::{HeaderHash, StoredBlockOmmers},
             storage_sharded_key::StorageShardedKey,
-            ShardedKey, StoredBlockBodyIndices, StoredBlockWithdrawals,
+            ShardedKey, StoredBlockBodyIndices, StoredBlockInternalTransfers,
+            StoredBlockWithdrawals,
         },
     },
 };
 use reth_primitives::{
     stage::StageCheckpoint,
     trie::{StorageTrieEntry, StoredBranchNode, StoredNibbles, StoredNibblesSubKey},
-    Account, Address, BlockHash, BlockNumber, Bytecode, Header, IntegerList, PruneCheckpoint,
-    PruneSegment, Receipt, StorageEntry, TransactionSignedNoHash, TxHash, TxNumber, B256,
+    Account, Address, BlockHash, BlockNumber, Bloom, Bytecode, Header, IntegerList,
+    PruneCheckpoint, PruneSegment, Receipt, StorageEntry, TransactionSignedNoHash, TxHash,
+    TxNumber, B256,
 };
 
 /// Enum for the types of tables present in libmdbx.
@@ -51,7 +53,7 @@ pub enum TableType {
 }
 
 /// Number of tables that should be present inside database.
-pub const NUM_TABLES: usize = 26;
+pub const NUM_TABLES: usize = 34;
 
 /// The general purpose of this is to use with a combination of Tables enum,
 /// by implementing a `TableViewer` trait you can operate on db tables in an abstract way.
@@ -208,6 +210,7 @@ tables!([
             Receipts,
             PlainAccountState,
             Bytecodes,
+            BytecodeRefs,
             AccountHistory,
             StorageHistory,
             HashedAccount,
@@ -215,7 +218,14 @@ tables!([
             TxSenders,
             SyncStage,
             SyncStageProgress,
-            PruneCheckpoints
+            PruneCheckpoints,
+            HotAccounts,
+            InternalTransfers,
+            WithdrawalHistory,
+            LogsBlooms4K,
+            LogsBlooms64K,
+            ContractCreations,
+            AddressAppearances
         ]
     ),
     (
@@ -327,14 +337,35 @@ table!(
     ( Receipts ) TxNumber | Receipt
 );
 
+table!(
+    /// Stores the combined logs bloom of each complete range of 4096 blocks, keyed by the index
+    /// of the range, i.e. the block number divided by 4096.
+    ( LogsBlooms4K ) u64 | Bloom
+);
+
+table!(
+    /// Stores the combined logs bloom of each complete range of 65536 blocks, keyed by the index
+    /// of the range, i.e. the block number divided by 65536.
+    ( LogsBlooms64K ) u64 | Bloom
+);
+
 table!(
     /// Stores all smart contract bytecodes.
-    /// There will be multiple accounts that have same bytecode
-    /// So we would need to introduce reference counter.
-    /// This will be small optimization on state.
+    /// There will be multiple accounts that have same bytecode,
+    /// so the number of references is tracked in [`BytecodeRefs`].
     ( Bytecodes ) B256 | Bytecode
 );
 
+table!(
+    /// Stores the number of references to each bytecode in [`Bytecodes`].
+    ///
+    /// Both the current account state in [`PlainAccountState`] and the historical account states in
+    /// [`AccountChangeSet`] are counted, so a bytecode without references is not needed to
+    /// serve or unwind any state that is still in the database. Bytecodes without references
+    /// have no entry.
+    ( BytecodeRefs ) B256 | u64
+);
+
 table!(
     /// Stores the current state of an [`Account`].
     ( PlainAccountState ) Address | Account
@@ -451,6 +482,50 @@ table!(
     ( PruneCheckpoints ) PruneSegment | PruneCheckpoint
 );
 
+table!(
+    /// Stores the accounts changed by the most recent canonical blocks, with the number of the last
+    /// block that changed them.
+    ///
+    /// The accounts are loaded when the node starts, so their state is warm before the first
+    /// blocks are executed.
+    ( HotAccounts ) Address | BlockNumber
+);
+
+table!(
+    /// Stores the internal transfers of the transactions of each block.
+    ///
+    /// The table is only populated if the node indexes internal transfers, see
+    /// [`InternalTransfer`](reth_primitives::InternalTransfer).
+    ( InternalTransfers ) BlockNumber | StoredBlockInternalTransfers
+);
+
+table!(
+    /// Stores pointers to the blocks with withdrawals to each address.
+    ///
+    /// The shards are laid out like the ones of [`AccountHistory`], the last shard of an address
+    /// has `u64::MAX` as its highest block number.
+    ( WithdrawalHistory ) ShardedKey<Address> | BlockNumberList
+);
+
+table!(
+    /// Stores the transaction and the address that created each contract.
+    ///
+    /// The contracts created by other contracts are included. The table is only populated if the
+    /// index is enabled, by an inspector while the blocks are executed.
+    ( ContractCreations ) Address | StoredContractCreation
+);
+
+table!(
+    /// Stores the numbers of the transactions in which each address appears, as the sender or the
+    /// recipient of the transaction, as the emitter or an indexed parameter of one of its logs, or
+    /// in one of its internal transfers.
+    ///
+    /// The shards are laid out like the ones of [`AccountHistory`], but they contain transaction
+    /// numbers instead of block numbers. The last shard of an address has `u64::MAX` as its
+    /// highest number.
+    ( AddressAppearances ) ShardedKey<Address> | BlockNumberList
+);
+
 /// Alias Types
 
 /// List with transaction numbers.
@@ -477,6 +552,7 @@ mod tests {
         (TableType::Table, Receipts::NAME),
         (TableType::Table, PlainAccountState::NAME),
         (TableType::Table, Bytecodes::NAME),
+        (TableType::Table, BytecodeRefs::NAME),
         (TableType::Table, AccountHistory::NAME),
         (TableType::Table, StorageHistory::NAME),
         (TableType::Table, HashedAccount::NAME),
@@ -485,6 +561,13 @@ mod tests {
         (TableType::Table, SyncStage::NAME),
         (TableType::Table, SyncStageProgress::NAME),
         (TableType::Table, PruneCheckpoints::NAME),
+        (TableType::Table, HotAccounts::NAME),
+        (TableType::Table, InternalTransfers::NAME),
+        (TableType::Table, WithdrawalHistory::NAME),
+        (TableType::Table, LogsBlooms4K::NAME),
+        (TableType::Table, LogsBlooms64K::NAME),
+        (TableType::Table, ContractCreations::NAME),
+        (TableType::Table, AddressAppearances::NAME),
         (TableType::DupSort, PlainStorageState::NAME),
         (TableType::DupSort, AccountChangeSet::NAME),
         (TableType::DupSort, StorageChangeSet::NAME),
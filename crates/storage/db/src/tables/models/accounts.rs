@@ -4,11 +4,11 @@ use std::ops::{Range, RangeInclusive};
 
 use crate::{
     impl_fixed_arbitrary,
-    table::{Decode, Encode},
+    table::{Decode, Encode, FixedSizeKey},
     DatabaseError,
 };
-use reth_codecs::{derive_arbitrary, Compact};
-use reth_primitives::{Account, Address, BlockNumber, Buf};
+use reth_codecs::{derive_arbitrary, main_codec, Compact};
+use reth_primitives::{Account, Address, BlockNumber, Buf, TxNumber};
 use serde::{Deserialize, Serialize};
 
 /// Account as it is saved inside [`AccountChangeSet`][crate::tables::AccountChangeSet].
@@ -56,10 +56,23 @@ impl Compact for AccountBeforeTx {
     }
 }
 
+/// The creation of a contract as it is saved inside
+/// [`ContractCreations`][crate::tables::ContractCreations].
+#[main_codec]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct StoredContractCreation {
+    /// The number of the transaction that created the contract.
+    pub tx_number: TxNumber,
+    /// The address that created the contract, the sender of the transaction or a contract it
+    /// called.
+    pub creator: Address,
+}
+
 /// [`BlockNumber`] concatenated with [`Address`]. Used as the key for
 /// [`StorageChangeSet`](crate::tables::StorageChangeSet)
 ///
-/// Since it's used as a key, it isn't compressed when encoding it.
+/// Since it's used as a key, it isn't compressed when encoding it. It's encoded like the tuple
+/// `(BlockNumber, Address)`, but into an array, so it doesn't allocate.
 #[derive(
     Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Ord, PartialOrd, Hash,
 )]
@@ -73,7 +86,7 @@ impl BlockNumberAddress {
         (*range.start(), Address::ZERO).into()..(*range.end() + 1, Address::ZERO).into()
     }
 
-    /// Return the transition id
+    /// Return the block number
     pub fn block_number(&self) -> BlockNumber {
         self.0 .0
     }
@@ -112,14 +125,14 @@ impl Encode for BlockNumberAddress {
 
 impl Decode for BlockNumberAddress {
     fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
-        let value = value.as_ref();
-        let num = u64::from_be_bytes(value[..8].try_into().map_err(|_| DatabaseError::Decode)?);
-        let hash = Address::from_slice(&value[8..]);
-
-        Ok(BlockNumberAddress((num, hash)))
+        <(BlockNumber, Address)>::decode(value).map(BlockNumberAddress)
     }
 }
 
+impl FixedSizeKey for BlockNumberAddress {
+    const ENCODED_SIZE: usize = <(BlockNumber, Address)>::ENCODED_SIZE;
+}
+
 impl_fixed_arbitrary!(BlockNumberAddress, 28);
 
 #[cfg(test)]
@@ -140,6 +153,7 @@ mod test {
 
         let encoded = Encode::encode(key);
         assert_eq!(encoded, bytes);
+        assert_eq!(encoded.to_vec(), Encode::encode((num, hash)));
 
         let decoded: BlockNumberAddress = Decode::decode(encoded).unwrap();
         assert_eq!(decoded, key);
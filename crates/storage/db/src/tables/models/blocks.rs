@@ -1,7 +1,7 @@
 //! Block related models and types.
 
 use reth_codecs::{main_codec, Compact};
-use reth_primitives::{Header, TxNumber, Withdrawal, B256};
+use reth_primitives::{Header, InternalTransfer, TxNumber, Withdrawal, B256};
 use std::ops::Range;
 
 /// Total number of transactions.
@@ -83,6 +83,14 @@ pub struct StoredBlockWithdrawals {
     pub withdrawals: Vec<Withdrawal>,
 }
 
+/// The storage representation of the internal transfers of a block.
+#[main_codec]
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct StoredBlockInternalTransfers {
+    /// The internal transfers, in the order they were made.
+    pub transfers: Vec<InternalTransfer>,
+}
+
 /// Hash of the block header. Value for [`CanonicalHeaders`][crate::tables::CanonicalHeaders]
 pub type HeaderHash = B256;
 
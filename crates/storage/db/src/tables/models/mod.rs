@@ -1,7 +1,7 @@
 //! Implements data structures specific to the database
 
 use crate::{
-    table::{Decode, Encode},
+    table::{Decode, Encode, FixedSizeKey},
     DatabaseError,
 };
 use reth_codecs::Compact;
@@ -41,12 +41,61 @@ macro_rules! impl_uints {
                     )
                 }
             }
+
+            impl FixedSizeKey for $name {
+                const ENCODED_SIZE: usize = std::mem::size_of::<$name>();
+            }
         )+
     };
 }
 
 impl_uints!(u64, u32, u16, u8);
 
+/// Macro that implements [`Encode`] and [`Decode`] for tuples of keys.
+///
+/// The keys are encoded one after another, so the tuples are ordered by their keys from first to
+/// last, like the big-endian encoding of each key orders that key. All keys except the last have
+/// to be [`FixedSizeKey`]s, so the encoding can be split again.
+macro_rules! impl_tuple_keys {
+    ($(($($fixed:ident $fixed_key:ident),+; $last:ident $last_key:ident)),+) => {
+        $(
+            impl<$($fixed: FixedSizeKey,)+ $last: Encode> Encode for ($($fixed,)+ $last) {
+                type Encoded = Vec<u8>;
+
+                fn encode(self) -> Self::Encoded {
+                    let ($($fixed_key,)+ $last_key) = self;
+                    let mut buf = Vec::new();
+                    $(buf.extend_from_slice($fixed_key.encode().as_ref());)+
+                    buf.extend_from_slice($last_key.encode().as_ref());
+                    buf
+                }
+            }
+
+            impl<$($fixed: FixedSizeKey,)+ $last: Decode> Decode for ($($fixed,)+ $last) {
+                fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
+                    let value = value.as_ref();
+                    $(
+                        if value.len() < $fixed::ENCODED_SIZE {
+                            return Err(DatabaseError::Decode)
+                        }
+                        let ($fixed_key, value) = value.split_at($fixed::ENCODED_SIZE);
+                        let $fixed_key = $fixed::decode($fixed_key)?;
+                    )+
+                    Ok(($($fixed_key,)+ $last::decode(value)?))
+                }
+            }
+
+            impl<$($fixed: FixedSizeKey,)+ $last: FixedSizeKey> FixedSizeKey
+                for ($($fixed,)+ $last)
+            {
+                const ENCODED_SIZE: usize = $($fixed::ENCODED_SIZE +)+ $last::ENCODED_SIZE;
+            }
+        )+
+    };
+}
+
+impl_tuple_keys!((A a; B b), (A a, B b; C c));
+
 impl Encode for Vec<u8> {
     type Encoded = Vec<u8>;
 
@@ -75,6 +124,10 @@ impl Decode for Address {
     }
 }
 
+impl FixedSizeKey for Address {
+    const ENCODED_SIZE: usize = 20;
+}
+
 impl Encode for B256 {
     type Encoded = [u8; 32];
 
@@ -89,6 +142,10 @@ impl Decode for B256 {
     }
 }
 
+impl FixedSizeKey for B256 {
+    const ENCODED_SIZE: usize = 32;
+}
+
 impl Encode for String {
     type Encoded = Vec<u8>;
 
@@ -139,6 +196,11 @@ impl Decode for StoredNibblesSubKey {
     }
 }
 
+impl FixedSizeKey for StoredNibblesSubKey {
+    // the nibbles are right-padded to 64 bytes and followed by their length
+    const ENCODED_SIZE: usize = 65;
+}
+
 impl Encode for PruneSegment {
     type Encoded = [u8; 1];
 
@@ -155,3 +217,42 @@ impl Decode for PruneSegment {
         Ok(Self::from_compact(buf, buf.len()).0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{BlockNumber, TxNumber};
+
+    #[test]
+    fn tuple_keys_roundtrip() {
+        let key = (Address::with_last_byte(1), 2u64);
+        let encoded = key.encode();
+        assert_eq!(encoded.len(), <(Address, BlockNumber)>::ENCODED_SIZE);
+        assert_eq!(<(Address, BlockNumber)>::decode(&encoded), Ok(key));
+
+        // the last key can have any length
+        let key = (3u64, Address::with_last_byte(4), StoredNibbles::from(vec![5, 6, 7]));
+        let encoded = key.clone().encode();
+        assert_eq!(<(TxNumber, Address, StoredNibbles)>::decode(encoded), Ok(key));
+
+        assert_eq!(<(TxNumber, Address)>::decode([0u8; 7]), Err(DatabaseError::Decode));
+    }
+
+    #[test]
+    fn tuple_keys_order() {
+        let mut keys = vec![
+            (Address::with_last_byte(2), 1u64),
+            (Address::with_last_byte(1), 256),
+            (Address::with_last_byte(1), 1),
+            (Address::with_last_byte(1), u64::MAX),
+        ];
+        let mut encoded = keys.iter().map(|key| key.encode()).collect::<Vec<_>>();
+        keys.sort();
+        encoded.sort();
+        assert_eq!(encoded, keys.into_iter().map(Encode::encode).collect::<Vec<_>>());
+
+        let key = |nibbles: &[u8]| (1u64, StoredNibblesSubKey::from(nibbles.to_vec()));
+        assert!(key(&[1]).encode() < key(&[1, 0]).encode());
+        assert!(key(&[1, 0]).encode() < key(&[2]).encode());
+    }
+}
@@ -16,16 +16,16 @@ pub const NUM_OF_INDICES_IN_SHARD: usize = 2_000;
 /// Sometimes data can be too big to be saved for a single key. This helps out by dividing the data
 /// into different shards. Example:
 ///
-/// `Address | Storagekey | 200` -> data is from transition 0 to 200.
+/// `Address | Storagekey | 200` -> data is from block 0 to 200.
 ///
-/// `Address | StorageKey | 300` -> data is from transition 201 to 300.
+/// `Address | StorageKey | 300` -> data is from block 201 to 300.
 #[derive(
     Debug, Default, Clone, Eq, Ord, PartialOrd, PartialEq, AsRef, Serialize, Deserialize, Hash,
 )]
 pub struct StorageShardedKey {
     /// Storage account address.
     pub address: Address,
-    /// Storage slot with highest transition id.
+    /// Storage slot with highest block number.
     #[as_ref]
     pub sharded_key: ShardedKey<B256>,
 }
@@ -177,27 +177,25 @@ where
 
     /// Commits the transaction and returns table handles permanently open until dropped.
     pub fn commit_and_rebind_open_dbs(self) -> Result<(bool, CommitLatency, Vec<Database>)> {
-        let result = {
-            let result = self.txn_execute(|txn| {
-                if K::ONLY_CLEAN {
-                    let mut latency = CommitLatency::new();
-                    mdbx_result(unsafe {
-                        ffi::mdbx_txn_commit_ex(txn, latency.mdb_commit_latency())
-                    })
+        let result = self.txn_execute(|txn| {
+            let result = if K::ONLY_CLEAN {
+                let mut latency = CommitLatency::new();
+                mdbx_result(unsafe { ffi::mdbx_txn_commit_ex(txn, latency.mdb_commit_latency()) })
                     .map(|v| (v, latency))
-                } else {
-                    let (sender, rx) = sync_channel(0);
-                    self.env()
-                        .ensure_txn_manager()
-                        .unwrap()
-                        .send(TxnManagerMessage::Commit { tx: TxnPtr(txn), sender })
-                        .unwrap();
-                    rx.recv().unwrap()
-                }
-            });
+            } else {
+                let (sender, rx) = sync_channel(0);
+                self.env()
+                    .ensure_txn_manager()
+                    .unwrap()
+                    .send(TxnManagerMessage::Commit { tx: TxnPtr(txn), sender })
+                    .unwrap();
+                rx.recv().unwrap()
+            };
+            // marked while the lock is held, so a concurrent reset doesn't use the freed
+            // transaction
             self.inner.set_committed();
             result
-        };
+        });
         result.map(|(v, latency)| {
             (
                 v,
@@ -493,6 +491,19 @@ impl Transaction<RO> {
 
         Ok(())
     }
+
+    /// Resets the transaction, which releases the snapshot it reads.
+    ///
+    /// All following operations on the transaction and its cursors fail, so it has to be dropped.
+    /// Unlike the other operations, this can be called while another thread uses the transaction.
+    pub fn reset(&self) -> Result<()> {
+        self.txn_execute(|txn| {
+            if self.inner.has_committed() {
+                return Ok(())
+            }
+            mdbx_result(unsafe { ffi::mdbx_txn_reset(txn) }).map(|_| ())
+        })
+    }
 }
 
 impl Transaction<RW> {
@@ -154,3 +154,40 @@ fn test_freelist() {
     freelist = env.freelist().unwrap();
     assert!(freelist > 0);
 }
+
+#[test]
+fn test_copy_compact() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    for i in 0..64 {
+        let mut value = [0u8; 8];
+        LittleEndian::write_u64(&mut value, i);
+        let tx = env.begin_rw_txn().expect("begin_rw_txn");
+        tx.put(tx.open_db(None).unwrap().dbi(), value, value, WriteFlags::default())
+            .expect("tx.put");
+        tx.commit().expect("tx.commit");
+    }
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let db = tx.open_db(None).unwrap();
+    for i in 0..32 {
+        let mut key = [0u8; 8];
+        LittleEndian::write_u64(&mut key, i);
+        tx.del(db.dbi(), key, None).expect("tx.del");
+    }
+    tx.commit().expect("tx.commit");
+
+    // the destination file must not exist yet
+    let copy_dir = tempdir().unwrap();
+    let dest = copy_dir.path().join("mdbx.dat");
+    env.copy(&dest, true).unwrap();
+    assert!(env.copy(&dest, true).is_err());
+
+    let copy = Environment::builder().open(copy_dir.path()).unwrap();
+    assert_eq!(copy.freelist().unwrap(), 0);
+    assert!(copy.info().unwrap().last_pgno() <= env.info().unwrap().last_pgno());
+
+    let tx = copy.begin_ro_txn().unwrap();
+    let db = tx.open_db(None).unwrap();
+    assert_eq!(tx.db_stat(&db).unwrap().entries(), 32);
+}
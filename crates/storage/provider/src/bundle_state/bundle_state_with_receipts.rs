@@ -1,10 +1,14 @@
+use super::{
+    state_reverts::offset_number,
+    write_report::{StateWriteReport, WriteErrorContext},
+};
 use crate::{StateChanges, StateReverts};
 use reth_db::{
     cursor::{DbCursorRO, DbCursorRW},
     tables,
     transaction::{DbTx, DbTxMut},
 };
-use reth_interfaces::db::DatabaseError;
+use reth_interfaces::provider::ProviderResult;
 use reth_primitives::{
     keccak256, logs_bloom,
     revm::compat::{into_reth_acc, into_revm_acc},
@@ -14,14 +18,18 @@ use reth_primitives::{
 use reth_trie::{
     hashed_cursor::{HashedPostState, HashedPostStateCursorFactory, HashedStorage},
     updates::TrieUpdates,
-    StateRoot, StateRootError,
+    StateRoot, StateRootError, StorageRoot, StorageRootError,
 };
 use revm::{db::states::BundleState, primitives::AccountInfo};
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Instant};
 
 pub use revm::db::states::OriginalValuesKnown;
 
 /// Bundle state of post execution changes and reverts
+///
+/// The state of a block range keeps the reverts and receipts of every block, starting at
+/// `first_block`, so the boundaries of its blocks are known and it can be split with
+/// [Self::split_at].
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct BundleStateWithReceipts {
     /// Bundle state with reverts.
@@ -224,6 +232,24 @@ impl BundleStateWithReceipts {
         self.state_root_calculator(tx, &hashed_post_state).root_with_updates()
     }
 
+    /// Calculates the storage root of the account with the given address after this [BundleState]
+    /// is applied on top of the hashed storage of the database.
+    /// See [Self::state_root_slow] for more info.
+    pub fn storage_root_slow<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: Address,
+    ) -> Result<B256, StorageRootError> {
+        let hashed_post_state = self.hash_state_slow();
+        let hashed_address = keccak256(address);
+        let (_, mut storage_prefix_sets) = hashed_post_state.construct_prefix_sets();
+        let hashed_cursor_factory = HashedPostStateCursorFactory::new(tx, &hashed_post_state);
+        StorageRoot::from_tx_hashed(tx, hashed_address)
+            .with_hashed_cursor_factory(hashed_cursor_factory)
+            .with_changed_prefixes(storage_prefix_sets.remove(&hashed_address).unwrap_or_default())
+            .root()
+    }
+
     /// Transform block number to the index of block.
     fn block_number_to_index(&self, block_number: BlockNumber) -> Option<usize> {
         if self.first_block > block_number {
@@ -367,44 +393,58 @@ impl BundleStateWithReceipts {
         std::mem::swap(&mut self.bundle, &mut other)
     }
 
-    /// Write bundle state to database.
+    /// Write bundle state to database and return the entries written per table.
     ///
     /// `omit_changed_check` should be set to true of bundle has some of it data
     /// detached, This would make some original values not known.
+    ///
+    /// A failed write returns
+    /// [ProviderError::StateWrite](reth_interfaces::provider::ProviderError::StateWrite) with the
+    /// table and the key it failed at.
     pub fn write_to_db<TX: DbTxMut + DbTx>(
         self,
         tx: &TX,
         is_value_known: OriginalValuesKnown,
-    ) -> Result<(), DatabaseError> {
+    ) -> ProviderResult<StateWriteReport> {
         let (plain_state, reverts) = self.bundle.into_plain_state_and_reverts(is_value_known);
 
-        StateReverts(reverts).write_to_db(tx, self.first_block)?;
+        let mut report = StateReverts(reverts).write_to_db(tx, self.first_block)?;
 
         // write receipts
+        let started_at = Instant::now();
+        let mut entries = 0;
         let mut bodies_cursor = tx.cursor_read::<tables::BlockBodyIndices>()?;
         let mut receipts_cursor = tx.cursor_write::<tables::Receipts>()?;
 
         for (idx, receipts) in self.receipts.into_iter().enumerate() {
             if !receipts.is_empty() {
-                let block_number = self.first_block + idx as u64;
-                let (_, body_indices) =
-                    bodies_cursor.seek_exact(block_number)?.unwrap_or_else(|| {
-                        let last_available = bodies_cursor.last().ok().flatten().map(|(number, _)| number);
+                let block_number = offset_number(self.first_block, idx)?;
+                let (_, body_indices) = bodies_cursor
+                    .seek_exact(block_number)
+                    .with_key::<tables::BlockBodyIndices>(block_number)?
+                    .unwrap_or_else(|| {
+                        let last_available =
+                            bodies_cursor.last().ok().flatten().map(|(number, _)| number);
                         panic!("body indices for block {block_number} must exist. last available block number: {last_available:?}");
                     });
 
                 let first_tx_index = body_indices.first_tx_num();
                 for (tx_idx, receipt) in receipts.into_iter().enumerate() {
                     if let Some(receipt) = receipt {
-                        receipts_cursor.append(first_tx_index + tx_idx as u64, receipt)?;
+                        let tx_number = offset_number(first_tx_index, tx_idx)?;
+                        receipts_cursor
+                            .append(tx_number, receipt)
+                            .with_key::<tables::Receipts>(tx_number)?;
+                        entries += 1;
                     }
                 }
             }
         }
+        report.record::<tables::Receipts>(entries, started_at);
 
-        StateChanges(plain_state).write_to_db(tx)?;
+        report.extend(StateChanges(plain_state).write_to_db(tx)?);
 
-        Ok(())
+        Ok(report)
     }
 }
 
@@ -415,11 +455,12 @@ mod tests {
     use reth_db::{
         cursor::{DbCursorRO, DbDupCursorRO},
         database::Database,
-        models::{AccountBeforeTx, BlockNumberAddress},
+        models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices},
         tables,
         test_utils::create_test_rw_db,
-        transaction::DbTx,
+        transaction::{DbTx, DbTxMut},
     };
+    use reth_interfaces::provider::ProviderError;
     use reth_primitives::{
         revm::compat::into_reth_acc, Address, Receipt, Receipts, StorageEntry, B256, U256,
     };
@@ -429,7 +470,7 @@ mod tests {
             states::{
                 bundle_state::{BundleRetention, OriginalValuesKnown},
                 changes::PlainStorageRevert,
-                PlainStorageChangeset,
+                PlainStateReverts, PlainStorageChangeset, StateChangeset,
             },
             BundleState, EmptyDB,
         },
@@ -486,14 +527,17 @@ mod tests {
         let plain_state = revm_bundle_state.into_plain_state(OriginalValuesKnown::Yes);
         assert!(plain_state.storage.is_empty());
         assert!(plain_state.contracts.is_empty());
-        StateChanges(plain_state)
+        let report = StateChanges(plain_state)
             .write_to_db(provider.tx_ref())
             .expect("Could not write plain state to DB");
+        assert_eq!(report.table::<tables::PlainAccountState>().map(|stats| stats.entries), Some(2));
 
         assert_eq!(reverts.storage, [[]]);
-        StateReverts(reverts)
+        let report = StateReverts(reverts)
             .write_to_db(provider.tx_ref(), 1)
             .expect("Could not write reverts to DB");
+        assert_eq!(report.table::<tables::AccountChangeSet>().map(|stats| stats.entries), Some(2));
+        assert_eq!(report.table::<tables::StorageChangeSet>().map(|stats| stats.entries), Some(0));
 
         let reth_account_a = into_reth_acc(account_a);
         let reth_account_b = into_reth_acc(account_b);
@@ -1075,6 +1119,150 @@ mod tests {
         assert_eq!(storage_changes.next(), None);
     }
 
+    #[test]
+    fn write_to_db_appends_and_upserts() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let address_low = Address::ZERO;
+        let address_mid = Address::repeat_byte(0x11);
+        let address_high = Address::repeat_byte(0xff);
+        let account = |nonce| RevmAccountInfo { nonce, ..Default::default() };
+        let storage = |address, wipe_storage, storage: Vec<(u64, u64)>| PlainStorageChangeset {
+            address,
+            wipe_storage,
+            storage: storage.into_iter().map(|(k, v)| (U256::from(k), U256::from(v))).collect(),
+        };
+
+        // The first write appends to the empty tables.
+        StateChanges(StateChangeset {
+            accounts: vec![(address_mid, Some(account(1)))],
+            storage: vec![storage(address_mid, false, vec![(1, 1), (2, 2)])],
+            contracts: vec![],
+        })
+        .write_to_db(provider.tx_ref())
+        .expect("Could not write plain state to DB");
+
+        // Entries before and at the last key are upserted, entries after it are appended.
+        let report = StateChanges(StateChangeset {
+            accounts: vec![
+                (address_high, Some(account(3))),
+                (address_mid, Some(account(2))),
+                (address_low, Some(account(1))),
+            ],
+            storage: vec![
+                storage(address_high, false, vec![(2, 2), (1, 1), (3, 0)]),
+                storage(address_mid, true, vec![(3, 3)]),
+                storage(address_low, false, vec![(1, 1), (4, 0)]),
+            ],
+            contracts: vec![],
+        })
+        .write_to_db(provider.tx_ref())
+        .expect("Could not write plain state to DB");
+        // The wipe and the written slots are counted, the zero slots without an entry aren't.
+        assert_eq!(report.table::<tables::PlainStorageState>().map(|stats| stats.entries), Some(5));
+
+        for (address, nonce) in [(address_low, 1), (address_mid, 2), (address_high, 3)] {
+            assert_eq!(
+                provider.basic_account(address).expect("Could not read account state"),
+                Some(into_reth_acc(account(nonce))),
+                "Account {address} state is wrong"
+            );
+        }
+
+        let storage_entries = provider
+            .tx_ref()
+            .cursor_dup_read::<tables::PlainStorageState>()
+            .expect("Could not open plain storage state cursor")
+            .walk(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let entry = |address, key, value| {
+            (address, StorageEntry { key: B256::with_last_byte(key), value: U256::from(value) })
+        };
+        assert_eq!(
+            storage_entries,
+            vec![
+                entry(address_low, 1, 1),
+                entry(address_mid, 3, 3),
+                entry(address_high, 1, 1),
+                entry(address_high, 2, 2),
+            ]
+        );
+
+        // A zero value deletes the existing entry.
+        let report = StateChanges(StateChangeset {
+            accounts: vec![],
+            storage: vec![storage(address_low, false, vec![(1, 0)])],
+            contracts: vec![],
+        })
+        .write_to_db(provider.tx_ref())
+        .expect("Could not write plain state to DB");
+        assert_eq!(report.table::<tables::PlainStorageState>().map(|stats| stats.entries), Some(1));
+        assert_eq!(provider.tx_ref().get::<tables::PlainStorageState>(address_low), Ok(None));
+    }
+
+    #[test]
+    fn write_to_db_block_number_overflow() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let address = Address::with_last_byte(1);
+
+        // the reverts of the last possible block are written
+        let reverts =
+            PlainStateReverts { accounts: vec![vec![(address, None)]], storage: vec![vec![]] };
+        StateReverts(reverts)
+            .write_to_db(provider.tx_ref(), u64::MAX)
+            .expect("Could not write reverts to DB");
+        assert_eq!(
+            provider.tx_ref().get::<tables::AccountChangeSet>(u64::MAX),
+            Ok(Some(AccountBeforeTx { address, info: None }))
+        );
+
+        // the block after it overflows instead of wrapping around to the genesis block
+        let reverts = PlainStateReverts {
+            accounts: vec![vec![], vec![(address, None)]],
+            storage: vec![vec![], vec![]],
+        };
+        assert_eq!(
+            StateReverts(reverts).write_to_db(provider.tx_ref(), u64::MAX),
+            Err(ProviderError::NumberOverflow { start: u64::MAX, offset: 1 })
+        );
+        assert_eq!(provider.tx_ref().entries::<tables::AccountChangeSet>(), Ok(1));
+    }
+
+    #[test]
+    fn write_to_db_receipt_tx_number_overflow() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        // the block starts at the last possible transaction number
+        provider
+            .tx_ref()
+            .put::<tables::BlockBodyIndices>(
+                1,
+                StoredBlockBodyIndices { first_tx_num: u64::MAX, tx_count: 2 },
+            )
+            .unwrap();
+
+        // the receipt of its second transaction overflows instead of overwriting the first one
+        let receipts = Receipts::from_vec(vec![vec![
+            Some(Receipt { cumulative_gas_used: 1, ..Default::default() }),
+            Some(Receipt { cumulative_gas_used: 2, ..Default::default() }),
+        ]]);
+        assert_eq!(
+            BundleStateWithReceipts::new(BundleState::default(), receipts, 1)
+                .write_to_db(provider.tx_ref(), OriginalValuesKnown::Yes),
+            Err(ProviderError::NumberOverflow { start: u64::MAX, offset: 1 })
+        );
+        assert_eq!(
+            provider.tx_ref().get::<tables::Receipts>(u64::MAX),
+            Ok(Some(Receipt { cumulative_gas_used: 1, ..Default::default() }))
+        );
+        assert_eq!(provider.tx_ref().entries::<tables::Receipts>(), Ok(1));
+    }
+
     #[test]
     fn storage_change_after_selfdestruct_within_block() {
         let factory = create_test_provider_factory();
@@ -1179,6 +1367,116 @@ mod tests {
         assert_eq!(storage_changes.next(), None);
     }
 
+    #[test]
+    fn create2_redeploy_within_block() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let address1 = Address::random();
+        let account1 = RevmAccountInfo { nonce: 1, ..Default::default() };
+
+        // Block #0: initial state.
+        let mut init_state = State::builder().with_bundle_update().build();
+        init_state.insert_not_existing(address1);
+        init_state.commit(HashMap::from([(
+            address1,
+            RevmAccount {
+                info: account1.clone(),
+                status: AccountStatus::Touched | AccountStatus::Created,
+                // 0x00 => 0 => 1
+                // 0x01 => 0 => 2
+                storage: HashMap::from([
+                    (
+                        U256::ZERO,
+                        StorageSlot { present_value: U256::from(1), ..Default::default() },
+                    ),
+                    (
+                        U256::from(1),
+                        StorageSlot { present_value: U256::from(2), ..Default::default() },
+                    ),
+                ]),
+            },
+        )]));
+        init_state.merge_transitions(BundleRetention::Reverts);
+        BundleStateWithReceipts::new(init_state.take_bundle(), Receipts::new(), 0)
+            .write_to_db(provider.tx_ref(), OriginalValuesKnown::Yes)
+            .expect("Could not write init bundle state to DB");
+
+        let mut state = State::builder().with_bundle_update().build();
+        state.insert_account_with_storage(
+            address1,
+            account1.clone(),
+            HashMap::from([(U256::ZERO, U256::from(1)), (U256::from(1), U256::from(2))]),
+        );
+
+        // Block #1: destroy and redeploy at the same address, the new code writes a new slot.
+        state.commit(HashMap::from([(
+            address1,
+            RevmAccount {
+                status: AccountStatus::Touched | AccountStatus::SelfDestructed,
+                info: account1.clone(),
+                storage: HashMap::default(),
+            },
+        )]));
+        state.commit(HashMap::from([(
+            address1,
+            RevmAccount {
+                status: AccountStatus::Touched | AccountStatus::Created,
+                info: account1.clone(),
+                // 0x02 => 0 => 5
+                storage: HashMap::from([(
+                    U256::from(2),
+                    StorageSlot { present_value: U256::from(5), ..Default::default() },
+                )]),
+            },
+        )]));
+        state.merge_transitions(BundleRetention::Reverts);
+        let bundle = BundleStateWithReceipts::new(state.take_bundle(), Receipts::new(), 1);
+
+        // Slots of the destroyed contract must not be read from the database.
+        assert_eq!(bundle.storage(&address1, U256::ZERO), Some(U256::ZERO));
+        assert_eq!(bundle.storage(&address1, U256::from(1)), Some(U256::ZERO));
+        assert_eq!(bundle.storage(&address1, U256::from(2)), Some(U256::from(5)));
+
+        bundle
+            .write_to_db(provider.tx_ref(), OriginalValuesKnown::Yes)
+            .expect("Could not write bundle state to DB");
+
+        // Only the storage written after the redeployment is left.
+        let plain_storage = provider
+            .tx_ref()
+            .cursor_dup_read::<tables::PlainStorageState>()
+            .expect("Could not open plain storage state cursor")
+            .walk(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            plain_storage,
+            vec![(address1, StorageEntry { key: B256::with_last_byte(2), value: U256::from(5) })]
+        );
+
+        // The changeset of block #1 restores the storage of the destroyed contract.
+        let mut storage_changeset_cursor = provider
+            .tx_ref()
+            .cursor_dup_read::<tables::StorageChangeSet>()
+            .expect("Could not open storage changeset cursor");
+        let changes = storage_changeset_cursor
+            .walk_range(BlockNumberAddress::range(1..=1))
+            .unwrap()
+            .map(|entry| entry.map(|(_, entry)| (entry.key, entry.value)))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                (B256::with_last_byte(0), U256::from(1)),
+                (B256::with_last_byte(1), U256::from(2)),
+                (B256::with_last_byte(2), U256::ZERO),
+            ]
+        );
+    }
+
     #[test]
     fn revert_to_indices() {
         let base = BundleStateWithReceipts {
@@ -0,0 +1,139 @@
+use reth_db::{
+    cursor::{DbCursorRO, DbCursorRW},
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_interfaces::db::DatabaseError;
+use reth_primitives::{Account, B256};
+
+/// The key of the marker in [tables::SyncStageProgress] that is set when [tables::BytecodeRefs]
+/// counts the references of the whole state in the database.
+const BYTECODE_REFS_TRACKED_KEY: &str = "BytecodeRefs";
+
+/// Records that [tables::BytecodeRefs] counts the references of the whole state in the database.
+///
+/// Must only be called when the whole state is written with its references counted, e.g. when the
+/// genesis state is written.
+pub fn mark_bytecode_refs_tracked<TX: DbTxMut>(tx: &TX) -> Result<(), DatabaseError> {
+    tx.put::<tables::SyncStageProgress>(BYTECODE_REFS_TRACKED_KEY.to_string(), Vec::new())
+}
+
+/// Returns whether [tables::BytecodeRefs] counts the references of the whole state in the
+/// database.
+///
+/// Databases created before references were tracked miss the references of older states, so
+/// a bytecode without references may still be used.
+pub fn bytecode_refs_tracked<TX: DbTx>(tx: &TX) -> Result<bool, DatabaseError> {
+    Ok(tx.get::<tables::SyncStageProgress>(BYTECODE_REFS_TRACKED_KEY.to_string())?.is_some())
+}
+
+/// Maintains the reference counts of bytecodes in the [tables::BytecodeRefs] table.
+///
+/// Every account in [tables::PlainAccountState] and every account state in
+/// [tables::AccountChangeSet] that has a bytecode counts as one reference to it.
+#[derive(Debug)]
+pub struct BytecodeRefCounter<C> {
+    cursor: C,
+}
+
+impl<C> BytecodeRefCounter<C>
+where
+    C: DbCursorRO<tables::BytecodeRefs> + DbCursorRW<tables::BytecodeRefs>,
+{
+    /// Creates a new reference counter using the cursor.
+    pub fn new(cursor: C) -> Self {
+        Self { cursor }
+    }
+
+    /// Adds a reference to the bytecode of the account, if it has one.
+    pub fn increment(&mut self, account: Option<&Account>) -> Result<(), DatabaseError> {
+        let Some(hash) = account.and_then(|account| account.bytecode_hash) else { return Ok(()) };
+        let refs = self.cursor.seek_exact(hash)?.map(|(_, refs)| refs).unwrap_or_default();
+        self.cursor.upsert(hash, refs + 1)
+    }
+
+    /// Removes a reference to the bytecode of the account, if it has one.
+    ///
+    /// Returns the hash of the bytecode if it was its last reference.
+    pub fn decrement(&mut self, account: Option<&Account>) -> Result<Option<B256>, DatabaseError> {
+        let Some(hash) = account.and_then(|account| account.bytecode_hash) else { return Ok(None) };
+        match self.cursor.seek_exact(hash)? {
+            Some((_, refs)) if refs > 1 => {
+                self.cursor.upsert(hash, refs - 1)?;
+                Ok(None)
+            }
+            Some(_) => {
+                self.cursor.delete_current()?;
+                Ok(Some(hash))
+            }
+            // The database was created before references were tracked.
+            None => Ok(None),
+        }
+    }
+
+    /// Moves a reference from the bytecode of the old account to the bytecode of the new one.
+    ///
+    /// Returns the hash of the old bytecode if it was its last reference.
+    pub fn replace(
+        &mut self,
+        old: Option<&Account>,
+        new: Option<&Account>,
+    ) -> Result<Option<B256>, DatabaseError> {
+        let old_hash = old.and_then(|account| account.bytecode_hash);
+        if old_hash == new.and_then(|account| account.bytecode_hash) {
+            return Ok(None)
+        }
+        self.increment(new)?;
+        self.decrement(old)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_provider_factory;
+
+    #[test]
+    fn bytecode_refs() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        let mut refs = BytecodeRefCounter::new(tx.cursor_write::<tables::BytecodeRefs>().unwrap());
+
+        let code_a = B256::with_last_byte(1);
+        let code_b = B256::with_last_byte(2);
+        let account_a = Account { bytecode_hash: Some(code_a), ..Default::default() };
+        let account_b = Account { bytecode_hash: Some(code_b), ..Default::default() };
+        let eoa = Account::default();
+
+        // accounts without bytecode are not counted
+        refs.increment(Some(&eoa)).unwrap();
+        refs.increment(None).unwrap();
+        assert_eq!(tx.entries::<tables::BytecodeRefs>().unwrap(), 0);
+
+        refs.increment(Some(&account_a)).unwrap();
+        refs.increment(Some(&account_a)).unwrap();
+        assert_eq!(tx.get::<tables::BytecodeRefs>(code_a).unwrap(), Some(2));
+
+        // replacing the account with one with the same bytecode doesn't change the references
+        assert_eq!(refs.replace(Some(&account_a), Some(&account_a)).unwrap(), None);
+        assert_eq!(tx.get::<tables::BytecodeRefs>(code_a).unwrap(), Some(2));
+
+        assert_eq!(refs.replace(Some(&account_a), Some(&account_b)).unwrap(), None);
+        assert_eq!(tx.get::<tables::BytecodeRefs>(code_a).unwrap(), Some(1));
+        assert_eq!(tx.get::<tables::BytecodeRefs>(code_b).unwrap(), Some(1));
+
+        // the last reference removes the entry
+        assert_eq!(refs.replace(Some(&account_a), Some(&eoa)).unwrap(), Some(code_a));
+        assert_eq!(tx.get::<tables::BytecodeRefs>(code_a).unwrap(), None);
+        assert_eq!(refs.decrement(Some(&account_b)).unwrap(), Some(code_b));
+        assert_eq!(tx.entries::<tables::BytecodeRefs>().unwrap(), 0);
+
+        // untracked bytecodes are never reported as unreferenced
+        assert_eq!(refs.decrement(Some(&account_a)).unwrap(), None);
+
+        assert!(!bytecode_refs_tracked(tx).unwrap());
+        mark_bytecode_refs_tracked(tx).unwrap();
+        assert!(bytecode_refs_tracked(tx).unwrap());
+    }
+}
@@ -1,13 +1,18 @@
 //! Bundle state module.
 //! This module contains all the logic related to bundle state.
 mod bundle_state_with_receipts;
+mod bytecode_refs;
 mod hashed_state_changes;
 mod state_changes;
 mod state_reverts;
+mod write_report;
 
 pub use bundle_state_with_receipts::{
     AccountRevertInit, BundleStateInit, BundleStateWithReceipts, OriginalValuesKnown, RevertsInit,
 };
+pub use bytecode_refs::{bytecode_refs_tracked, mark_bytecode_refs_tracked, BytecodeRefCounter};
 pub use hashed_state_changes::HashedStateChanges;
 pub use state_changes::StateChanges;
+pub(crate) use state_reverts::offset_number;
 pub use state_reverts::StateReverts;
+pub use write_report::{StateWriteReport, TableWriteStats};
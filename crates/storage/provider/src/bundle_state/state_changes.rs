@@ -1,12 +1,15 @@
+use super::write_report::{StateWriteReport, WriteErrorContext};
+use crate::BytecodeRefCounter;
 use rayon::slice::ParallelSliceMut;
 use reth_db::{
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
     tables,
     transaction::{DbTx, DbTxMut},
 };
-use reth_interfaces::db::DatabaseError;
+use reth_interfaces::provider::ProviderResult;
 use reth_primitives::{revm::compat::into_reth_acc, Bytecode, StorageEntry, U256};
 use revm::db::states::{PlainStorageChangeset, StateChangeset};
+use std::time::Instant;
 
 /// A change to the state of the world.
 #[derive(Debug, Default)]
@@ -19,8 +22,10 @@ impl From<StateChangeset> for StateChanges {
 }
 
 impl StateChanges {
-    /// Write the bundle state to the database.
-    pub fn write_to_db<TX: DbTxMut + DbTx>(mut self, tx: &TX) -> Result<(), DatabaseError> {
+    /// Write the bundle state to the database and return the entries written per table.
+    pub fn write_to_db<TX: DbTxMut + DbTx>(mut self, tx: &TX) -> ProviderResult<StateWriteReport> {
+        let mut report = StateWriteReport::default();
+
         // sort all entries so they can be written to database in more performant way.
         // and take smaller memory footprint.
         self.0.accounts.par_sort_by_key(|a| a.0);
@@ -29,32 +34,95 @@ impl StateChanges {
 
         // Write new account state
         tracing::trace!(target: "provider::bundle_state", len = self.0.accounts.len(), "Writing new account state");
+        let started_at = Instant::now();
+        let mut entries = 0;
         let mut accounts_cursor = tx.cursor_write::<tables::PlainAccountState>()?;
+        let mut bytecode_refs = BytecodeRefCounter::new(tx.cursor_write::<tables::BytecodeRefs>()?);
+        // Accounts after the last account in the table don't exist yet and are appended, the rest
+        // is looked up and upserted.
+        let mut last_address = accounts_cursor.last()?.map(|(address, _)| address);
         // write account to database.
         for (address, account) in self.0.accounts.into_iter() {
+            let append = last_address.map_or(true, |last| address > last);
+            let existing = if append {
+                None
+            } else {
+                accounts_cursor
+                    .seek_exact(address)
+                    .with_key::<tables::PlainAccountState>(address)?
+                    .map(|(_, account)| account)
+            };
             if let Some(account) = account {
                 tracing::trace!(target: "provider::bundle_state", ?address, "Updating plain state account");
-                accounts_cursor.upsert(address, into_reth_acc(account))?;
-            } else if accounts_cursor.seek_exact(address)?.is_some() {
+                let account = into_reth_acc(account);
+                bytecode_refs
+                    .replace(existing.as_ref(), Some(&account))
+                    .with_key::<tables::BytecodeRefs>(account.bytecode_hash)?;
+                if append {
+                    accounts_cursor
+                        .append(address, account)
+                        .with_key::<tables::PlainAccountState>(address)?;
+                    last_address = Some(address);
+                } else {
+                    accounts_cursor
+                        .upsert(address, account)
+                        .with_key::<tables::PlainAccountState>(address)?;
+                }
+                entries += 1;
+            } else if let Some(existing) = existing {
                 tracing::trace!(target: "provider::bundle_state", ?address, "Deleting plain state account");
-                accounts_cursor.delete_current()?;
+                bytecode_refs
+                    .decrement(Some(&existing))
+                    .with_key::<tables::BytecodeRefs>(existing.bytecode_hash)?;
+                accounts_cursor.delete_current().with_key::<tables::PlainAccountState>(address)?;
+                entries += 1;
             }
         }
+        report.record::<tables::PlainAccountState>(entries, started_at);
 
         // Write bytecode
         tracing::trace!(target: "provider::bundle_state", len = self.0.contracts.len(), "Writing bytecodes");
+        let started_at = Instant::now();
+        let mut entries = 0;
         let mut bytecodes_cursor = tx.cursor_write::<tables::Bytecodes>()?;
+        let mut last_hash = bytecodes_cursor.last()?.map(|(hash, _)| hash);
         for (hash, bytecode) in self.0.contracts.into_iter() {
-            bytecodes_cursor.upsert(hash, Bytecode(bytecode))?;
+            if last_hash.map_or(true, |last| hash > last) {
+                bytecodes_cursor
+                    .append(hash, Bytecode(bytecode))
+                    .with_key::<tables::Bytecodes>(hash)?;
+                last_hash = Some(hash);
+            } else {
+                bytecodes_cursor
+                    .upsert(hash, Bytecode(bytecode))
+                    .with_key::<tables::Bytecodes>(hash)?;
+            }
+            entries += 1;
         }
+        report.record::<tables::Bytecodes>(entries, started_at);
 
         // Write new storage state and wipe storage if needed.
         tracing::trace!(target: "provider::bundle_state", len = self.0.storage.len(), "Writing new storage state");
+        let started_at = Instant::now();
+        let mut entries = 0;
         let mut storages_cursor = tx.cursor_dup_write::<tables::PlainStorageState>()?;
+        // Addresses after the last address in the table have no storage to wipe or update, their
+        // slots are appended.
+        let mut last_address = storages_cursor.last()?.map(|(address, _)| address);
         for PlainStorageChangeset { address, wipe_storage, storage } in self.0.storage.into_iter() {
+            let append = last_address.map_or(true, |last| address > last);
             // Wiping of storage.
-            if wipe_storage && storages_cursor.seek_exact(address)?.is_some() {
-                storages_cursor.delete_current_duplicates()?;
+            if !append &&
+                wipe_storage &&
+                storages_cursor
+                    .seek_exact(address)
+                    .with_key::<tables::PlainStorageState>(address)?
+                    .is_some()
+            {
+                storages_cursor
+                    .delete_current_duplicates()
+                    .with_key::<tables::PlainStorageState>(address)?;
+                entries += 1;
             }
             // cast storages to B256.
             let mut storage = storage
@@ -66,17 +134,44 @@ impl StateChanges {
 
             for entry in storage.into_iter() {
                 tracing::trace!(target: "provider::bundle_state", ?address, ?entry.key, "Updating plain state storage");
-                if let Some(db_entry) = storages_cursor.seek_by_key_subkey(address, entry.key)? {
+                if append {
+                    if entry.value != U256::ZERO {
+                        storages_cursor
+                            .append_dup(address, entry)
+                            .with_key::<tables::PlainStorageState>((address, entry.key))?;
+                        last_address = Some(address);
+                        entries += 1;
+                    }
+                    continue
+                }
+
+                let mut deleted = false;
+                if let Some(db_entry) =
+                    storages_cursor
+                        .seek_by_key_subkey(address, entry.key)
+                        .with_key::<tables::PlainStorageState>((address, entry.key))?
+                {
                     if db_entry.key == entry.key {
-                        storages_cursor.delete_current()?;
+                        storages_cursor
+                            .delete_current()
+                            .with_key::<tables::PlainStorageState>((address, entry.key))?;
+                        deleted = true;
                     }
                 }
 
+                // a zero value of a slot that isn't in the table writes nothing
                 if entry.value != U256::ZERO {
-                    storages_cursor.upsert(address, entry)?;
+                    storages_cursor
+                        .upsert(address, entry)
+                        .with_key::<tables::PlainStorageState>((address, entry.key))?;
+                    entries += 1;
+                } else if deleted {
+                    entries += 1;
                 }
             }
         }
-        Ok(())
+        report.record::<tables::PlainStorageState>(entries, started_at);
+
+        Ok(report)
     }
 }
@@ -1,3 +1,5 @@
+use super::write_report::{StateWriteReport, WriteErrorContext};
+use crate::BytecodeRefCounter;
 use rayon::slice::ParallelSliceMut;
 use reth_db::{
     cursor::{DbCursorRO, DbDupCursorRO, DbDupCursorRW},
@@ -5,10 +7,10 @@ use reth_db::{
     tables,
     transaction::{DbTx, DbTxMut},
 };
-use reth_interfaces::db::DatabaseError;
+use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_primitives::{revm::compat::into_reth_acc, BlockNumber, StorageEntry, B256, U256};
 use revm::db::states::{PlainStateReverts, PlainStorageRevert, RevertToSlot};
-use std::iter::Peekable;
+use std::{iter::Peekable, time::Instant};
 
 /// Revert of the state.
 #[derive(Debug, Default)]
@@ -21,20 +23,24 @@ impl From<PlainStateReverts> for StateReverts {
 }
 
 impl StateReverts {
-    /// Write reverts to database.
+    /// Write reverts to database and return the entries written per table.
     ///
     /// Note:: Reverts will delete all wiped storage from plain state.
     pub fn write_to_db<TX: DbTxMut + DbTx>(
         self,
         tx: &TX,
         first_block: BlockNumber,
-    ) -> Result<(), DatabaseError> {
+    ) -> ProviderResult<StateWriteReport> {
+        let mut report = StateWriteReport::default();
+
         // Write storage changes
         tracing::trace!(target: "provider::reverts", "Writing storage changes");
+        let started_at = Instant::now();
+        let mut entries = 0;
         let mut storages_cursor = tx.cursor_dup_write::<tables::PlainStorageState>()?;
         let mut storage_changeset_cursor = tx.cursor_dup_write::<tables::StorageChangeSet>()?;
         for (block_index, mut storage_changes) in self.0.storage.into_iter().enumerate() {
-            let block_number = first_block + block_index as BlockNumber;
+            let block_number = offset_number(first_block, block_index)?;
 
             tracing::trace!(target: "provider::reverts", block_number, "Writing block change");
             // sort changes by address.
@@ -56,9 +62,15 @@ impl StateReverts {
                 let mut wiped_storage = Vec::new();
                 if wiped {
                     tracing::trace!(target: "provider::reverts", ?address, "Wiping storage");
-                    if let Some((_, entry)) = storages_cursor.seek_exact(address)? {
+                    if let Some((_, entry)) = storages_cursor
+                        .seek_exact(address)
+                        .with_key::<tables::PlainStorageState>(address)?
+                    {
                         wiped_storage.push((entry.key, entry.value));
-                        while let Some(entry) = storages_cursor.next_dup_val()? {
+                        while let Some(entry) = storages_cursor
+                            .next_dup_val()
+                            .with_key::<tables::PlainStorageState>(address)?
+                        {
                             wiped_storage.push((entry.key, entry.value))
                         }
                     }
@@ -66,30 +78,49 @@ impl StateReverts {
 
                 tracing::trace!(target: "provider::reverts", ?address, ?storage, "Writing storage reverts");
                 for (key, value) in StorageRevertsIter::new(storage, wiped_storage) {
-                    storage_changeset_cursor.append_dup(storage_id, StorageEntry { key, value })?;
+                    storage_changeset_cursor
+                        .append_dup(storage_id, StorageEntry { key, value })
+                        .with_key::<tables::StorageChangeSet>((storage_id, key))?;
+                    entries += 1;
                 }
             }
         }
+        report.record::<tables::StorageChangeSet>(entries, started_at);
 
         // Write account changes
         tracing::trace!(target: "provider::reverts", "Writing account changes");
+        let started_at = Instant::now();
+        let mut entries = 0;
         let mut account_changeset_cursor = tx.cursor_dup_write::<tables::AccountChangeSet>()?;
+        let mut bytecode_refs = BytecodeRefCounter::new(tx.cursor_write::<tables::BytecodeRefs>()?);
         for (block_index, mut account_block_reverts) in self.0.accounts.into_iter().enumerate() {
-            let block_number = first_block + block_index as BlockNumber;
+            let block_number = offset_number(first_block, block_index)?;
             // Sort accounts by address.
             account_block_reverts.par_sort_by_key(|a| a.0);
             for (address, info) in account_block_reverts {
-                account_changeset_cursor.append_dup(
-                    block_number,
-                    AccountBeforeTx { address, info: info.map(into_reth_acc) },
-                )?;
+                let info = info.map(into_reth_acc);
+                bytecode_refs
+                    .increment(info.as_ref())
+                    .with_key::<tables::BytecodeRefs>(info.and_then(|info| info.bytecode_hash))?;
+                account_changeset_cursor
+                    .append_dup(block_number, AccountBeforeTx { address, info })
+                    .with_key::<tables::AccountChangeSet>((block_number, address))?;
+                entries += 1;
             }
         }
+        report.record::<tables::AccountChangeSet>(entries, started_at);
 
-        Ok(())
+        Ok(report)
     }
 }
 
+/// Returns the block or transaction number at the offset from the first one, or an error instead of
+/// wrapping around, which would write the changes under the keys of other blocks.
+pub(crate) fn offset_number(start: u64, offset: usize) -> ProviderResult<u64> {
+    let offset = offset as u64;
+    start.checked_add(offset).ok_or(ProviderError::NumberOverflow { start, offset })
+}
+
 /// Iterator over storage reverts.
 /// See [StorageRevertsIter::next] for more details.
 struct StorageRevertsIter<R: Iterator, W: Iterator> {
@@ -0,0 +1,82 @@
+use reth_db::table::Table;
+use reth_interfaces::{
+    db::DatabaseError,
+    provider::{ProviderError, ProviderResult},
+};
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    time::{Duration, Instant},
+};
+
+/// The number of entries written and the time spent per table while writing the state to the
+/// database.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StateWriteReport {
+    tables: BTreeMap<&'static str, TableWriteStats>,
+}
+
+/// The entries written to a table and the time it took.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TableWriteStats {
+    /// The number of written or deleted entries.
+    pub entries: usize,
+    /// The time spent writing the table, including the reads needed to write it.
+    pub duration: Duration,
+}
+
+impl StateWriteReport {
+    /// Returns the stats of the written tables, by table name.
+    pub fn tables(&self) -> impl Iterator<Item = (&'static str, &TableWriteStats)> {
+        self.tables.iter().map(|(table, stats)| (*table, stats))
+    }
+
+    /// Returns the stats of the table, if it was written.
+    pub fn table<T: Table>(&self) -> Option<&TableWriteStats> {
+        self.tables.get(T::NAME)
+    }
+
+    /// Returns the number of entries written to all tables.
+    pub fn entries(&self) -> usize {
+        self.tables.values().map(|stats| stats.entries).sum()
+    }
+
+    /// Returns the time spent writing all tables.
+    pub fn duration(&self) -> Duration {
+        self.tables.values().map(|stats| stats.duration).sum()
+    }
+
+    /// Adds the stats of another report to this one.
+    pub fn extend(&mut self, other: Self) {
+        for (table, stats) in other.tables {
+            self.add(table, stats.entries, stats.duration);
+        }
+    }
+
+    /// Records the entries written to the table since the given instant.
+    pub(crate) fn record<T: Table>(&mut self, entries: usize, started_at: Instant) {
+        self.add(T::NAME, entries, started_at.elapsed());
+    }
+
+    fn add(&mut self, table: &'static str, entries: usize, duration: Duration) {
+        let stats = self.tables.entry(table).or_default();
+        stats.entries += entries;
+        stats.duration += duration;
+    }
+}
+
+/// Adds the table and the key a database operation failed at to its error.
+pub(crate) trait WriteErrorContext<R> {
+    /// Wraps the error into [ProviderError::StateWrite].
+    fn with_key<T: Table>(self, key: impl Debug) -> ProviderResult<R>;
+}
+
+impl<R> WriteErrorContext<R> for Result<R, DatabaseError> {
+    fn with_key<T: Table>(self, key: impl Debug) -> ProviderResult<R> {
+        self.map_err(|error| ProviderError::StateWrite {
+            table: T::NAME,
+            key: format!("{key:?}"),
+            error,
+        })
+    }
+}
@@ -20,8 +20,9 @@ pub use traits::*;
 /// Provider trait implementations.
 pub mod providers;
 pub use providers::{
-    DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW, HistoricalStateProvider,
-    HistoricalStateProviderRef, LatestStateProvider, LatestStateProviderRef, ProviderFactory,
+    DatabaseProvider, DatabaseProviderOverlay, DatabaseProviderRO, DatabaseProviderRW,
+    HistoricalStateProvider, HistoricalStateProviderRef, LatestStateProvider,
+    LatestStateProviderRef, ProviderFactory, MAX_PROOF_REVERT_BLOCKS,
 };
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -35,7 +36,9 @@ pub mod chain;
 pub use chain::{Chain, DisplayBlocksChain};
 
 pub mod bundle_state;
-pub use bundle_state::{BundleStateWithReceipts, OriginalValuesKnown, StateChanges, StateReverts};
+pub use bundle_state::{
+    BundleStateWithReceipts, BytecodeRefCounter, OriginalValuesKnown, StateChanges, StateReverts,
+};
 
 pub(crate) fn to_range<R: std::ops::RangeBounds<u64>>(bounds: R) -> std::ops::Range<u64> {
     let start = match bounds.start_bound() {
@@ -74,6 +74,16 @@ impl<SP: StateProvider, BSDP: BundleStateDataProvider> StateRootProvider
         state.extend(bundle_state.clone());
         self.state_provider.state_root_with_updates(&state)
     }
+
+    fn storage_root(
+        &self,
+        address: Address,
+        bundle_state: &BundleStateWithReceipts,
+    ) -> ProviderResult<B256> {
+        let mut state = self.bundle_state_data_provider.state().clone();
+        state.extend(bundle_state.clone());
+        self.state_provider.storage_root(address, &state)
+    }
 }
 
 impl<SP: StateProvider, BSDP: BundleStateDataProvider> StateProvider
@@ -0,0 +1,224 @@
+use parking_lot::RwLock;
+use reth_primitives::{BlockHash, BlockNumHash, BlockNumber, SealedHeader, B256};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+};
+
+/// The default number of canonical block hashes kept in memory.
+///
+/// This covers the 256 blocks accessible with the `BLOCKHASH` opcode, with some room for reorgs.
+pub const DEFAULT_MAX_CANONICAL_HASHES: usize = 1024;
+
+/// Keeps the hashes of the most recent canonical blocks and the canonical head, safe and finalized
+/// blocks in memory, so hash lookups by number don't have to read the database.
+///
+/// The cached hashes always form a chain: a block is only appended if it's the child of the
+/// latest cached block, otherwise the cache is restarted from the block. Blocks that are reorged
+/// out are removed, so a cached hash is always canonical, as long as the tracker is informed about
+/// all changes of the canonical chain before they're read.
+///
+/// For this reason the tracker is updated synchronously with the canonical chain of the blockchain
+/// tree, see [CanonicalChainTracker::set_canonical_hashes], instead of from the canonical state
+/// notifications, which are received with a delay.
+#[derive(Debug, Clone)]
+pub struct CanonicalChainTracker {
+    inner: Arc<RwLock<CanonicalChainInner>>,
+}
+
+impl CanonicalChainTracker {
+    /// Creates a new tracker that keeps at most `max_hashes` canonical block hashes.
+    pub fn new(max_hashes: usize) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(CanonicalChainInner {
+                max_hashes: max_hashes.max(1),
+                first_number: 0,
+                hashes: VecDeque::new(),
+                safe: None,
+                finalized: None,
+            })),
+        }
+    }
+
+    /// Returns the cached hash of the canonical block with the given number, if any.
+    pub fn block_hash(&self, number: BlockNumber) -> Option<B256> {
+        self.inner.read().block_hash(number)
+    }
+
+    /// Returns the cached hashes of the canonical blocks in the range `start..end`, or `None` if
+    /// any of them isn't cached.
+    pub fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> Option<Vec<B256>> {
+        let inner = self.inner.read();
+        (start..end).map(|number| inner.block_hash(number)).collect()
+    }
+
+    /// Returns the number and hash of the canonical head, if known.
+    pub fn head(&self) -> Option<BlockNumHash> {
+        let inner = self.inner.read();
+        let hash = *inner.hashes.back()?;
+        Some(BlockNumHash::new(inner.first_number + inner.hashes.len() as u64 - 1, hash))
+    }
+
+    /// Returns the number and hash of the safe block, if known.
+    pub fn safe(&self) -> Option<BlockNumHash> {
+        self.inner.read().safe
+    }
+
+    /// Returns the number and hash of the finalized block, if known.
+    pub fn finalized(&self) -> Option<BlockNumHash> {
+        self.inner.read().finalized
+    }
+
+    /// Sets the canonical head.
+    ///
+    /// All cached blocks after the head are removed.
+    pub fn set_canonical_head(&self, header: &SealedHeader) {
+        self.inner.write().insert(header.number, header.hash(), header.parent_hash);
+    }
+
+    /// Sets the safe block.
+    pub fn set_safe(&self, num_hash: BlockNumHash) {
+        self.inner.write().safe = Some(num_hash);
+    }
+
+    /// Sets the finalized block.
+    pub fn set_finalized(&self, num_hash: BlockNumHash) {
+        self.inner.write().finalized = Some(num_hash);
+    }
+
+    /// Removes all cached hashes.
+    pub fn clear(&self) {
+        self.inner.write().hashes.clear();
+    }
+
+    /// Replaces the cached hashes with the hashes of the given canonical blocks.
+    ///
+    /// Only the most recent consecutive blocks are cached.
+    pub fn set_canonical_hashes(&self, hashes: &BTreeMap<BlockNumber, BlockHash>) {
+        let mut inner = self.inner.write();
+        inner.hashes.clear();
+        for (&number, &hash) in hashes.iter().rev().take(inner.max_hashes) {
+            if !inner.hashes.is_empty() && number + 1 != inner.first_number {
+                break
+            }
+            inner.hashes.push_front(hash);
+            inner.first_number = number;
+        }
+    }
+}
+
+impl Default for CanonicalChainTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CANONICAL_HASHES)
+    }
+}
+
+/// Container type for the cached canonical chain.
+#[derive(Debug)]
+struct CanonicalChainInner {
+    /// The maximum number of cached hashes.
+    max_hashes: usize,
+    /// The number of the block of the first cached hash.
+    first_number: BlockNumber,
+    /// The hashes of consecutive canonical blocks, starting at `first_number`.
+    hashes: VecDeque<B256>,
+    /// The block that the beacon node considers safe.
+    safe: Option<BlockNumHash>,
+    /// The block that the beacon node considers finalized.
+    finalized: Option<BlockNumHash>,
+}
+
+impl CanonicalChainInner {
+    fn block_hash(&self, number: BlockNumber) -> Option<B256> {
+        let index = number.checked_sub(self.first_number)?;
+        self.hashes.get(index as usize).copied()
+    }
+
+    /// Removes the hashes of all blocks starting at `number`.
+    fn truncate(&mut self, number: BlockNumber) {
+        let len = number.saturating_sub(self.first_number) as usize;
+        self.hashes.truncate(len);
+    }
+
+    /// Makes the block the latest cached block, removing all cached blocks after it.
+    fn insert(&mut self, number: BlockNumber, hash: BlockHash, parent_hash: BlockHash) {
+        self.truncate(number);
+
+        // after truncating, the parent can only be cached as the latest block
+        let is_child =
+            number.checked_sub(1).and_then(|parent| self.block_hash(parent)) == Some(parent_hash);
+        if !is_child {
+            self.hashes.clear();
+            self.first_number = number;
+        }
+
+        self.hashes.push_back(hash);
+        if self.hashes.len() > self.max_hashes {
+            self.hashes.pop_front();
+            self.first_number += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_interfaces::test_utils::generators::{self, random_header_range};
+
+    #[test]
+    fn caches_chain_of_headers() {
+        let mut rng = generators::rng();
+        let headers = random_header_range(&mut rng, 0..10, B256::ZERO);
+        let tracker = CanonicalChainTracker::new(4);
+        for header in &headers {
+            tracker.set_canonical_head(header);
+        }
+
+        assert_eq!(tracker.head(), Some(headers[9].num_hash()));
+        assert_eq!(tracker.block_hash(5), None);
+        assert_eq!(tracker.block_hash(6), Some(headers[6].hash()));
+        assert_eq!(
+            tracker.canonical_hashes_range(7, 10),
+            Some(headers[7..10].iter().map(|h| h.hash()).collect())
+        );
+        assert_eq!(tracker.canonical_hashes_range(5, 10), None);
+
+        // moving the head back removes the blocks after it
+        tracker.set_canonical_head(&headers[8]);
+        assert_eq!(tracker.head(), Some(headers[8].num_hash()));
+        assert_eq!(tracker.block_hash(9), None);
+
+        // a head that isn't a child of the cached chain restarts the cache
+        let other = random_header_range(&mut rng, 9..11, B256::random());
+        tracker.set_canonical_head(&other[1]);
+        assert_eq!(tracker.block_hash(8), None);
+        assert_eq!(tracker.head(), Some(other[1].num_hash()));
+    }
+
+    #[test]
+    fn replaces_canonical_hashes() {
+        let mut rng = generators::rng();
+        let headers = random_header_range(&mut rng, 0..10, B256::ZERO);
+        let tracker = CanonicalChainTracker::new(4);
+
+        // only the consecutive blocks at the tip are cached
+        let mut hashes = headers.iter().map(|h| (h.number, h.hash())).collect::<BTreeMap<_, _>>();
+        hashes.remove(&7);
+        tracker.set_canonical_hashes(&hashes);
+        assert_eq!(tracker.head(), Some(headers[9].num_hash()));
+        assert_eq!(tracker.block_hash(8), Some(headers[8].hash()));
+        assert_eq!(tracker.block_hash(6), None);
+
+        // blocks extending the cached chain are appended
+        let next = random_header_range(&mut rng, 10..11, headers[9].hash());
+        tracker.set_canonical_head(&next[0]);
+        assert_eq!(tracker.canonical_hashes_range(8, 11).map(|hashes| hashes.len()), Some(3));
+
+        tracker.set_canonical_hashes(&BTreeMap::new());
+        assert_eq!(tracker.head(), None);
+    }
+}
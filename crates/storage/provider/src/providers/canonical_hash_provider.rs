@@ -0,0 +1,163 @@
+use crate::{
+    bundle_state::BundleStateWithReceipts, providers::CanonicalChainTracker, AccountReader,
+    BlockHashReader, StateProvider, StateRootProvider,
+};
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{
+    trie::AccountProof, Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, B256,
+};
+use reth_trie::updates::TrieUpdates;
+
+/// A state provider that resolves the hashes of recent canonical blocks, e.g. for the `BLOCKHASH`
+/// opcode, from the [CanonicalChainTracker], and everything else from the underlying state
+/// provider.
+///
+/// The underlying state provider must be the state of a canonical block, because the tracker only
+/// knows the hashes of the canonical chain.
+#[derive(Debug)]
+pub struct CanonicalHashStateProvider<SP: StateProvider> {
+    /// The inner state provider.
+    pub(crate) state_provider: SP,
+    /// The cached hashes of the canonical chain.
+    pub(crate) canonical_chain: CanonicalChainTracker,
+}
+
+impl<SP: StateProvider> CanonicalHashStateProvider<SP> {
+    /// Create new canonical hash state provider
+    pub fn new(state_provider: SP, canonical_chain: CanonicalChainTracker) -> Self {
+        Self { state_provider, canonical_chain }
+    }
+}
+
+/* Implement StateProvider traits */
+
+impl<SP: StateProvider> BlockHashReader for CanonicalHashStateProvider<SP> {
+    fn block_hash(&self, block_number: BlockNumber) -> ProviderResult<Option<B256>> {
+        if let Some(hash) = self.canonical_chain.block_hash(block_number) {
+            return Ok(Some(hash))
+        }
+        self.state_provider.block_hash(block_number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        if let Some(hashes) = self.canonical_chain.canonical_hashes_range(start, end) {
+            return Ok(hashes)
+        }
+        self.state_provider.canonical_hashes_range(start, end)
+    }
+}
+
+impl<SP: StateProvider> AccountReader for CanonicalHashStateProvider<SP> {
+    fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
+        self.state_provider.basic_account(address)
+    }
+}
+
+impl<SP: StateProvider> StateRootProvider for CanonicalHashStateProvider<SP> {
+    fn state_root(&self, bundle_state: &BundleStateWithReceipts) -> ProviderResult<B256> {
+        self.state_provider.state_root(bundle_state)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        bundle_state: &BundleStateWithReceipts,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.state_provider.state_root_with_updates(bundle_state)
+    }
+
+    fn storage_root(
+        &self,
+        address: Address,
+        bundle_state: &BundleStateWithReceipts,
+    ) -> ProviderResult<B256> {
+        self.state_provider.storage_root(address, bundle_state)
+    }
+}
+
+impl<SP: StateProvider> StateProvider for CanonicalHashStateProvider<SP> {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        self.state_provider.storage(account, storage_key)
+    }
+
+    fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        self.state_provider.bytecode_by_hash(code_hash)
+    }
+
+    fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof> {
+        self.state_provider.proof(address, keys)
+    }
+
+    fn proofs(&self, targets: &[(Address, Vec<B256>)]) -> ProviderResult<Vec<AccountProof>> {
+        self.state_provider.proofs(targets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockEthProvider;
+    use reth_interfaces::test_utils::generators::{self, random_block_range};
+    use reth_primitives::BlockHash;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn block_hash_falls_back_to_state_provider() {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(&mut rng, 0..=9, B256::ZERO, 0..1);
+        let db = MockEthProvider::default();
+        db.extend_blocks(blocks.iter().map(|b| (b.hash(), b.clone().unseal())));
+
+        let tracker = CanonicalChainTracker::new(4);
+        tracker.set_canonical_hashes(&blocks.iter().map(|b| (b.number, b.hash())).collect());
+        let provider = CanonicalHashStateProvider::new(db, tracker);
+
+        // the cached blocks
+        assert_eq!(provider.block_hash(9).unwrap(), Some(blocks[9].hash()));
+        assert_eq!(
+            provider.canonical_hashes_range(6, 10).unwrap(),
+            blocks[6..10].iter().map(|b| b.hash()).collect::<Vec<_>>()
+        );
+        // the blocks before the cached blocks
+        assert_eq!(provider.block_hash(2).unwrap(), Some(blocks[2].hash()));
+        assert_eq!(
+            provider.canonical_hashes_range(4, 8).unwrap(),
+            blocks[4..8].iter().map(|b| b.hash()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn reorg_invalidates_cached_hashes() {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(&mut rng, 0..=9, B256::ZERO, 0..1);
+        let fork = random_block_range(&mut rng, 8..=8, blocks[7].hash(), 0..1);
+        let hashes = |blocks: &[reth_primitives::SealedBlock]| {
+            blocks.iter().map(|b| (b.number, b.hash())).collect::<BTreeMap<_, BlockHash>>()
+        };
+
+        let tracker = CanonicalChainTracker::new(4);
+        tracker.set_canonical_hashes(&hashes(&blocks));
+        let provider = CanonicalHashStateProvider::new(MockEthProvider::default(), tracker.clone());
+        assert_eq!(provider.block_hash(8).unwrap(), Some(blocks[8].hash()));
+
+        // the fork replaces the blocks 8 and 9
+        let mut reorged = hashes(&blocks[..8]);
+        reorged.extend(hashes(&fork));
+        tracker.set_canonical_hashes(&reorged);
+        assert_eq!(provider.block_hash(7).unwrap(), Some(blocks[7].hash()));
+        assert_eq!(provider.block_hash(8).unwrap(), Some(fork[0].hash()));
+        assert_eq!(provider.block_hash(9).unwrap(), None);
+
+        // a head that isn't a child of the fork drops all cached hashes
+        tracker.set_canonical_head(&blocks[9].header);
+        assert_eq!(provider.block_hash(8).unwrap(), None);
+        assert_eq!(provider.block_hash(9).unwrap(), Some(blocks[9].hash()));
+    }
+}
@@ -46,6 +46,8 @@ pub(crate) enum Action {
     InsertState,
     InsertHashes,
     InsertHistoryIndices,
+    InsertLogsBloomIndex,
+    InsertAddressAppearances,
     UpdatePipelineStages,
     InsertCanonicalHeaders,
     InsertHeaders,
@@ -56,6 +58,7 @@ pub(crate) enum Action {
     InsertTransactions,
     InsertTxHashNumbers,
     InsertBlockWithdrawals,
+    InsertWithdrawalHistory,
     InsertBlockBodyIndices,
     InsertTransactionBlock,
 
@@ -73,6 +76,8 @@ impl Action {
             Action::InsertState => "insert state",
             Action::InsertHashes => "insert hashes",
             Action::InsertHistoryIndices => "insert history indices",
+            Action::InsertLogsBloomIndex => "insert logs bloom index",
+            Action::InsertAddressAppearances => "insert address appearances",
             Action::UpdatePipelineStages => "update pipeline stages",
             Action::InsertCanonicalHeaders => "insert canonical headers",
             Action::InsertHeaders => "insert headers",
@@ -83,6 +88,7 @@ impl Action {
             Action::InsertTransactions => "insert transactions",
             Action::InsertTxHashNumbers => "insert tx hash numbers",
             Action::InsertBlockWithdrawals => "insert block withdrawals",
+            Action::InsertWithdrawalHistory => "insert withdrawal history",
             Action::InsertBlockBodyIndices => "insert block body indices",
             Action::InsertTransactionBlock => "insert transaction block",
             Action::GetNextTxNum => "get next tx num",
@@ -4,20 +4,27 @@ use crate::{
         SnapshotProvider,
     },
     traits::{BlockSource, ReceiptProvider},
-    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, EvmEnvProvider,
-    HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HeaderSyncMode, ProviderError,
-    PruneCheckpointReader, StageCheckpointReader, StateProviderBox, TransactionVariant,
-    TransactionsProvider, WithdrawalsProvider,
+    AddressAppearancesReader, BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider,
+    ContractCreationsReader, EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
+    HeaderSyncMode, InternalTransfersReader, LogsBloomLevel, ProviderError, PruneCheckpointReader,
+    StageCheckpointReader, StateProviderBox, TransactionVariant, TransactionsProvider,
+    WithdrawalHistoryReader, WithdrawalsProvider,
+};
+use reth_db::{
+    database::Database,
+    init_db,
+    models::{StoredBlockBodyIndices, StoredContractCreation},
+    overlay::OverlayTx,
+    DatabaseEnv,
 };
-use reth_db::{database::Database, init_db, models::StoredBlockBodyIndices, DatabaseEnv};
 use reth_interfaces::{db::LogLevel, provider::ProviderResult, RethError, RethResult};
 use reth_primitives::{
     snapshot::HighestSnapshots,
     stage::{StageCheckpoint, StageId},
-    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, ChainInfo,
-    ChainSpec, Header, PruneCheckpoint, PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
-    Withdrawal, B256, U256,
+    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bloom, ChainInfo,
+    ChainSpec, Header, InternalTransfer, PruneCheckpoint, PruneSegment, Receipt, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, B256, U256,
 };
 use revm::primitives::{BlockEnv, CfgEnv};
 use std::{
@@ -31,7 +38,9 @@ use tracing::trace;
 mod metrics;
 mod provider;
 
-pub use provider::{DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW};
+pub use provider::{
+    DatabaseProvider, DatabaseProviderOverlay, DatabaseProviderRO, DatabaseProviderRW,
+};
 
 /// A common provider that fetches data from a database.
 ///
@@ -125,6 +134,20 @@ impl<DB: Database> ProviderFactory<DB> {
         Ok(DatabaseProviderRW(provider))
     }
 
+    /// Returns a provider with an [`OverlayTx`] inside, which buffers all updates in memory on top
+    /// of a read database transaction, so the database itself is never changed. This may fail if
+    /// the inner read database transaction fails to open.
+    pub fn provider_overlay(&self) -> ProviderResult<DatabaseProviderOverlay<DB>> {
+        let mut provider =
+            DatabaseProvider::new_rw(OverlayTx::new(self.db.tx()?), self.chain_spec.clone());
+
+        if let Some(snapshot_provider) = &self.snapshot_provider {
+            provider = provider.with_snapshot_provider(snapshot_provider.clone());
+        }
+
+        Ok(provider)
+    }
+
     /// Storage provider for latest block
     pub fn latest(&self) -> ProviderResult<StateProviderBox> {
         trace!(target: "providers::db", "Returning latest state provider");
@@ -246,6 +269,10 @@ impl<DB: Database> HeaderProvider for ProviderFactory<DB> {
     ) -> ProviderResult<Vec<SealedHeader>> {
         self.provider()?.sealed_headers_while(range, predicate)
     }
+
+    fn range_logs_bloom(&self, level: LogsBloomLevel, index: u64) -> ProviderResult<Option<Bloom>> {
+        self.provider()?.range_logs_bloom(level, index)
+    }
 }
 
 impl<DB: Database> BlockHashReader for ProviderFactory<DB> {
@@ -496,17 +523,85 @@ impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
     }
 }
 
+impl<DB: Database> InternalTransfersReader for ProviderFactory<DB> {
+    fn internal_transfers_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<InternalTransfer>> {
+        self.provider()?.internal_transfers_by_block_range(range)
+    }
+
+    fn internal_transfers_by_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<InternalTransfer>> {
+        self.provider()?.internal_transfers_by_address(address, range)
+    }
+}
+
+impl<DB: Database> ContractCreationsReader for ProviderFactory<DB> {
+    fn contract_creation(
+        &self,
+        address: Address,
+    ) -> ProviderResult<Option<StoredContractCreation>> {
+        self.provider()?.contract_creation(address)
+    }
+}
+
+impl<DB: Database> AddressAppearancesReader for ProviderFactory<DB> {
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        start: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.provider()?.address_appearances_after(address, start, limit)
+    }
+
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        end: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.provider()?.address_appearances_before(address, end, limit)
+    }
+}
+
+impl<DB: Database> WithdrawalHistoryReader for ProviderFactory<DB> {
+    fn withdrawals_by_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Withdrawal)>> {
+        self.provider()?.withdrawals_by_address(address, range)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::ProviderFactory;
     use crate::{
-        test_utils::create_test_provider_factory, BlockHashReader, BlockNumReader, BlockWriter,
-        HeaderSyncGapProvider, HeaderSyncMode, TransactionsProvider,
+        test_utils::create_test_provider_factory, BlockHashReader, BlockNumReader, BlockReader,
+        BlockWriter, ChangeSetReader, ContractCreationsReader, ContractCreationsWriter,
+        HeaderProvider, HeaderSyncGapProvider, HeaderSyncMode, HistoryWriter,
+        InternalTransfersReader, LogsBloomIndexWriter, LogsBloomLevel, TransactionVariant,
+        TransactionsProvider, WithdrawalHistoryReader,
     };
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
     use rand::Rng;
-    use reth_db::{tables, test_utils::ERROR_TEMPDIR, transaction::DbTxMut, DatabaseEnv};
+    use reth_db::{
+        models::{
+            storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey,
+            StoredBlockBodyIndices, StoredBlockInternalTransfers, StoredContractCreation,
+        },
+        tables,
+        test_utils::ERROR_TEMPDIR,
+        transaction::DbTxMut,
+        BlockNumberList, DatabaseEnv,
+    };
     use reth_interfaces::{
         provider::ProviderError,
         test_utils::{
@@ -516,7 +611,8 @@ mod tests {
         RethError,
     };
     use reth_primitives::{
-        hex_literal::hex, ChainSpecBuilder, PruneMode, PruneModes, SealedBlock, TxNumber, B256,
+        hex_literal::hex, Address, Bloom, ChainSpecBuilder, Header, InternalTransfer, PruneMode,
+        PruneModes, SealedBlock, TxNumber, Withdrawal, B256, U256,
     };
     use std::{ops::RangeInclusive, sync::Arc};
     use tokio::sync::watch;
@@ -602,6 +698,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn insert_block_tx_number_overflow() {
+        let factory = create_test_provider_factory();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 1, None, Some(1), Some(0));
+
+        // the last possible transaction number is already taken
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::Transactions>(u64::MAX, block.body[0].clone().into())
+            .unwrap();
+
+        assert_matches!(
+            provider.insert_block(block.try_seal_with_senders().unwrap(), None),
+            Err(ProviderError::NumberOverflow { start: u64::MAX, offset: 1 })
+        );
+    }
+
+    #[test]
+    fn ommers_of_block_without_ommers() {
+        let factory = create_test_provider_factory();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 1, None, Some(0), Some(0));
+
+        let provider = factory.provider_rw().unwrap();
+        assert_matches!(
+            provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None),
+            Ok(_)
+        );
+
+        assert_eq!(provider.ommers(block.number.into()), Ok(Some(vec![])));
+        assert_eq!(provider.ommers(block.hash().into()), Ok(Some(vec![])));
+        assert_eq!(provider.ommers(2u64.into()), Ok(None));
+    }
+
+    #[test]
+    fn block_with_senders_recovers_pruned_senders() {
+        let factory = create_test_provider_factory();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(3), Some(0));
+        let expected = block.clone().try_seal_with_senders().unwrap();
+
+        let provider = factory.provider_rw().unwrap();
+        let prune_modes =
+            PruneModes { sender_recovery: Some(PruneMode::Full), ..PruneModes::none() };
+        assert_matches!(provider.insert_block(expected.clone(), Some(&prune_modes)), Ok(_));
+        assert_eq!(provider.senders_by_tx_range(0..3), Ok(vec![]));
+
+        let block_with_senders =
+            provider.block_with_senders(0.into(), TransactionVariant::WithHash).unwrap().unwrap();
+        assert_eq!(block_with_senders.senders, expected.senders);
+        assert_eq!(block_with_senders.body, expected.body);
+    }
+
     #[test]
     fn get_take_block_transaction_range_recover_senders() {
         let factory = create_test_provider_factory();
@@ -710,4 +864,225 @@ mod tests {
             Err(RethError::Provider(ProviderError::InconsistentHeaderGap))
         );
     }
+
+    #[test]
+    fn internal_transfers_by_address() {
+        let factory = create_test_provider_factory();
+        let transfer = |block_number, from, to| InternalTransfer {
+            block_number,
+            transaction_hash: B256::random(),
+            from: Address::with_last_byte(from),
+            to: Address::with_last_byte(to),
+            value: U256::from(1),
+        };
+        let transfers = [transfer(1, 1, 2), transfer(1, 2, 3), transfer(3, 3, 1)];
+
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::InternalTransfers>(
+                1,
+                StoredBlockInternalTransfers { transfers: transfers[..2].to_vec() },
+            )
+            .unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::InternalTransfers>(
+                3,
+                StoredBlockInternalTransfers { transfers: transfers[2..].to_vec() },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(factory.internal_transfers_by_block_range(0..=3).unwrap(), transfers);
+        assert_eq!(factory.internal_transfers_by_block_range(2..=3).unwrap(), transfers[2..]);
+        assert_eq!(
+            factory.internal_transfers_by_address(Address::with_last_byte(1), 0..=3).unwrap(),
+            vec![transfers[0], transfers[2]]
+        );
+        assert_eq!(
+            factory.internal_transfers_by_address(Address::with_last_byte(3), 0..=2).unwrap(),
+            transfers[1..2]
+        );
+    }
+
+    #[test]
+    fn account_history_blocks() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let shard = |address, highest_block_number, blocks: &[usize]| {
+            (
+                ShardedKey::new(address, highest_block_number),
+                BlockNumberList::new_pre_sorted(blocks),
+            )
+        };
+
+        let provider = factory.provider_rw().unwrap();
+        for (key, list) in [
+            shard(address, 5, &[1, 3, 5]),
+            shard(address, u64::MAX, &[8, 13]),
+            shard(Address::with_last_byte(2), u64::MAX, &[2]),
+        ] {
+            provider.tx_ref().put::<tables::AccountHistory>(key, list).unwrap();
+        }
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.account_history_blocks(address, 0..=u64::MAX).unwrap(),
+            [1, 3, 5, 8, 13]
+        );
+        assert_eq!(provider.account_history_blocks(address, 4..=8).unwrap(), [5, 8]);
+        assert!(provider.account_history_blocks(address, 6..=7).unwrap().is_empty());
+    }
+
+    #[test]
+    fn storage_history_blocks() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let shard = |address, slot, highest_block_number, blocks: &[usize]| {
+            (
+                StorageShardedKey::new(address, B256::with_last_byte(slot), highest_block_number),
+                BlockNumberList::new_pre_sorted(blocks),
+            )
+        };
+
+        let provider = factory.provider_rw().unwrap();
+        for (key, list) in [
+            shard(address, 1, 5, &[1, 3, 5]),
+            shard(address, 1, u64::MAX, &[8, 13]),
+            shard(address, 2, u64::MAX, &[3, 6]),
+            shard(Address::with_last_byte(2), 1, u64::MAX, &[2]),
+        ] {
+            provider.tx_ref().put::<tables::StorageHistory>(key, list).unwrap();
+        }
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.storage_history_blocks(address, 0..=u64::MAX).unwrap(),
+            [1, 3, 5, 6, 8, 13]
+        );
+        assert_eq!(provider.storage_history_blocks(address, 4..=6).unwrap(), [5, 6]);
+        assert!(provider.storage_history_blocks(address, 14..=20).unwrap().is_empty());
+    }
+
+    #[test]
+    fn contract_creations() {
+        let factory = create_test_provider_factory();
+        let contract = |byte| Address::with_last_byte(byte);
+        let creation = |tx_number| StoredContractCreation { tx_number, creator: contract(9) };
+        let created = |byte| factory.contract_creation(contract(byte)).unwrap();
+
+        // two blocks with five transactions each
+        let provider = factory.provider_rw().unwrap();
+        for block_number in 1..=2 {
+            let indices =
+                StoredBlockBodyIndices { first_tx_num: block_number * 5 - 5, tx_count: 5 };
+            provider.tx_ref().put::<tables::BlockBodyIndices>(block_number, indices).unwrap();
+        }
+        // the second block changes the contracts it created, the third contract again, and an
+        // account that isn't a contract
+        for address in [contract(2), contract(3), contract(4)] {
+            let account_before = AccountBeforeTx { address, info: None };
+            provider.tx_ref().put::<tables::AccountChangeSet>(2, account_before).unwrap();
+        }
+        provider
+            .insert_contract_creations([(contract(1), creation(3)), (contract(3), creation(2))])
+            .unwrap();
+        provider
+            .insert_contract_creations([(contract(2), creation(7)), (contract(3), creation(8))])
+            .unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(created(1), Some(creation(3)));
+        assert_eq!(created(2), Some(creation(7)));
+        // a contract that was created again keeps its first creation
+        assert_eq!(created(3), Some(creation(2)));
+        assert_eq!(created(4), None);
+
+        // only the contracts created in the unwound block are removed
+        let provider = factory.provider_rw().unwrap();
+        assert_eq!(provider.unwind_contract_creations(2..=2).unwrap(), 1);
+        provider.commit().unwrap();
+
+        assert_eq!(created(1), Some(creation(3)));
+        assert_eq!(created(2), None);
+        assert_eq!(created(3), Some(creation(2)));
+    }
+
+    #[test]
+    fn withdrawals_by_address() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+        let withdrawal = |index, address| Withdrawal {
+            index,
+            validator_index: index,
+            address: Address::with_last_byte(address),
+            amount: 1,
+        };
+        let withdrawals = vec![
+            vec![],
+            vec![withdrawal(0, 1), withdrawal(1, 2)],
+            vec![withdrawal(2, 2)],
+            vec![withdrawal(3, 1), withdrawal(4, 1)],
+        ];
+
+        let provider = factory.provider_rw().unwrap();
+        for (number, withdrawals) in withdrawals.iter().enumerate() {
+            let mut block = random_block(&mut rng, number as u64, None, Some(0), Some(0));
+            block.withdrawals = Some(withdrawals.clone());
+            provider.insert_block(block.try_seal_with_senders().unwrap(), None).unwrap();
+        }
+
+        let address = Address::with_last_byte(1);
+        assert_eq!(
+            provider.withdrawals_by_address(address, 0..=3).unwrap(),
+            vec![
+                (1, withdrawals[1][0].clone()),
+                (3, withdrawals[3][0].clone()),
+                (3, withdrawals[3][1].clone())
+            ]
+        );
+        assert_eq!(
+            provider.withdrawals_by_address(Address::with_last_byte(2), 2..=3).unwrap(),
+            vec![(2, withdrawals[2][0].clone())]
+        );
+
+        // unwinding the last block removes it from the index
+        assert_eq!(provider.unwind_withdrawal_history_indices(3..=3), Ok(2));
+        assert_eq!(
+            provider.withdrawals_by_address(address, 0..=3).unwrap(),
+            vec![(1, withdrawals[1][0].clone())]
+        );
+    }
+
+    #[test]
+    fn logs_bloom_index() {
+        let factory = create_test_provider_factory();
+        let bloom = |byte| Bloom::with_last_byte(byte);
+
+        let provider = factory.provider_rw().unwrap();
+        for number in 0..3 * LogsBloomLevel::Blocks4K.range_size() - 1 {
+            let logs_bloom = match number {
+                1 => bloom(1),
+                5000 => bloom(2),
+                _ => Bloom::ZERO,
+            };
+            let header = Header { number, logs_bloom, ..Default::default() };
+            provider.tx_ref().put::<tables::Headers>(number, header).unwrap();
+        }
+
+        // only the complete ranges are indexed
+        provider.insert_logs_bloom_index(0..=3 * 4096 - 2).unwrap();
+        assert_eq!(provider.range_logs_bloom(LogsBloomLevel::Blocks4K, 0), Ok(Some(bloom(1))));
+        assert_eq!(provider.range_logs_bloom(LogsBloomLevel::Blocks4K, 1), Ok(Some(bloom(2))));
+        assert_eq!(provider.range_logs_bloom(LogsBloomLevel::Blocks4K, 2), Ok(None));
+        assert_eq!(provider.range_logs_bloom(LogsBloomLevel::Blocks64K, 0), Ok(None));
+
+        // unwinding a block removes the range that contains it
+        provider.unwind_logs_bloom_index(5000..=3 * 4096 - 2).unwrap();
+        assert_eq!(provider.range_logs_bloom(LogsBloomLevel::Blocks4K, 0), Ok(Some(bloom(1))));
+        assert_eq!(provider.range_logs_bloom(LogsBloomLevel::Blocks4K, 1), Ok(None));
+    }
 }
@@ -1,15 +1,21 @@
 use crate::{
-    bundle_state::{BundleStateInit, BundleStateWithReceipts, HashedStateChanges, RevertsInit},
+    bundle_state::{
+        offset_number, BundleStateInit, BundleStateWithReceipts, BytecodeRefCounter,
+        HashedStateChanges, RevertsInit,
+    },
     providers::{database::metrics, SnapshotProvider},
     to_range,
     traits::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
     },
-    AccountReader, BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter,
-    Chain, EvmEnvProvider, HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
-    HeaderSyncMode, HistoryWriter, OriginalValuesKnown, ProviderError, PruneCheckpointReader,
-    PruneCheckpointWriter, StageCheckpointReader, StorageReader, TransactionVariant,
-    TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
+    AccountReader, AddressAppearancesReader, AddressAppearancesWriter, BlockExecutionWriter,
+    BlockHashReader, BlockNumReader, BlockReader, BlockWriter, Chain, ContractCreationsReader,
+    ContractCreationsWriter, EvmEnvProvider, HashingWriter, HeaderProvider, HeaderSyncGap,
+    HeaderSyncGapProvider, HeaderSyncMode, HistoryWriter, InternalTransfersReader,
+    LogsBloomIndexWriter, LogsBloomLevel, OriginalValuesKnown, ProviderError,
+    PruneCheckpointReader, PruneCheckpointWriter, StageCheckpointReader, StorageReader,
+    TransactionVariant, TransactionsProvider, TransactionsProviderExt, WithdrawalHistoryReader,
+    WithdrawalsProvider,
 };
 use ahash::{AHashMap, AHashSet};
 use itertools::{izip, Itertools};
@@ -20,7 +26,9 @@ use reth_db::{
     models::{
         sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
         ShardedKey, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals,
+        StoredContractCreation,
     },
+    overlay::OverlayTx,
     table::{Table, TableRow},
     tables,
     transaction::{DbTx, DbTxMut},
@@ -39,11 +47,12 @@ use reth_primitives::{
     },
     stage::{StageCheckpoint, StageId},
     trie::Nibbles,
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
-    ChainInfo, ChainSpec, GotExpected, Hardfork, Head, Header, PruneCheckpoint, PruneModes,
-    PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, SnapshotSegment,
-    StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedEcRecovered,
-    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, B256, U256,
+    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bloom,
+    ChainInfo, ChainSpec, GotExpected, Hardfork, Head, Header, InternalTransfer, PruneCheckpoint,
+    PruneModes, PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader,
+    SnapshotSegment, StorageEntry, TransactionMeta, TransactionSigned,
+    TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, B256,
+    U256,
 };
 use reth_trie::{
     hashed_cursor::HashedPostState, prefix_set::PrefixSetMut, updates::TrieUpdates, StateRoot,
@@ -61,6 +70,10 @@ use tracing::{debug, warn};
 /// A [`DatabaseProvider`] that holds a read-only database transaction.
 pub type DatabaseProviderRO<DB> = DatabaseProvider<<DB as Database>::TX>;
 
+/// A [`DatabaseProvider`] that buffers its writes in memory on top of a read-only database
+/// transaction. The writes are never written to the database.
+pub type DatabaseProviderOverlay<DB> = DatabaseProvider<OverlayTx<<DB as Database>::TX>>;
+
 /// A [`DatabaseProvider`] that holds a read-write database transaction.
 ///
 /// Ideally this would be an alias type. However, there's some weird compiler error (<https://github.com/rust-lang/rust/issues/102211>), that forces us to wrap this in a struct instead.
@@ -354,6 +367,102 @@ impl<TX: DbTx> DatabaseProvider<TX> {
             |_| true,
         )
     }
+
+    /// Returns the numbers of the blocks in the range from the sharded history index of the
+    /// address, in ascending order.
+    fn history_block_numbers<T>(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>
+    where
+        T: Table<Key = ShardedKey<Address>, Value = BlockNumberList>,
+    {
+        // The first shard with a highest block number above the start of the range is the first
+        // one that can contain a block in range.
+        let mut block_numbers = Vec::new();
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        'shards: for entry in cursor.walk(Some(ShardedKey::new(address, *range.start())))? {
+            let (sharded_key, list) = entry?;
+            if sharded_key.key != address {
+                break
+            }
+            for block_number in list.iter(0).map(|number| number as BlockNumber) {
+                if block_number > *range.end() {
+                    break 'shards
+                }
+                if block_number >= *range.start() {
+                    block_numbers.push(block_number);
+                }
+            }
+        }
+        Ok(block_numbers)
+    }
+
+    /// Returns the numbers of the transactions of the blocks in the range in which each address
+    /// appears, in ascending order.
+    ///
+    /// An address appears in a transaction if it's the sender or the recipient, if it emitted one
+    /// of the logs or if it sent or received one of the internal transfers. The logs of pruned
+    /// receipts and the internal transfers of blocks that weren't indexed are missing.
+    fn address_appearances_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<TxNumber>>> {
+        let first_tx_num = self
+            .block_body_indices(*range.start())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*range.start()))?
+            .first_tx_num();
+        let next_tx_num = self
+            .block_body_indices(*range.end())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*range.end()))?
+            .next_tx_num();
+        let tx_range = first_tx_num..next_tx_num;
+
+        let mut appearances = BTreeMap::<Address, BTreeSet<TxNumber>>::new();
+        let mut tx_numbers = HashMap::new();
+        let transactions = self.transactions_with_senders_by_tx_range(tx_range.clone())?;
+        for (tx_number, transaction) in tx_range.clone().zip(transactions) {
+            appearances.entry(transaction.signer()).or_default().insert(tx_number);
+            if let Some(to) = transaction.to() {
+                appearances.entry(to).or_default().insert(tx_number);
+            }
+            tx_numbers.insert(transaction.hash(), tx_number);
+        }
+
+        for entry in self.tx.cursor_read::<tables::Receipts>()?.walk_range(tx_range)? {
+            let (tx_number, receipt) = entry?;
+            for log in receipt.logs {
+                appearances.entry(log.address).or_default().insert(tx_number);
+                // the indexed address parameters of the event, e.g. the sender and the recipient
+                // of a token transfer, are the topics after the event signature that hold an
+                // address
+                for topic in log.topics.iter().skip(1) {
+                    if topic[..12] == [0; 12] && topic[12..] != [0; 20] {
+                        appearances
+                            .entry(Address::from_word(*topic))
+                            .or_default()
+                            .insert(tx_number);
+                    }
+                }
+            }
+        }
+
+        for entry in self.tx.cursor_read::<tables::InternalTransfers>()?.walk_range(range)? {
+            let (_, block_transfers) = entry?;
+            for transfer in block_transfers.transfers {
+                if let Some(tx_number) = tx_numbers.get(&transfer.transaction_hash) {
+                    appearances.entry(transfer.from).or_default().insert(*tx_number);
+                    appearances.entry(transfer.to).or_default().insert(*tx_number);
+                }
+            }
+        }
+
+        Ok(appearances
+            .into_iter()
+            .map(|(address, tx_numbers)| (address, tx_numbers.into_iter().collect()))
+            .collect())
+    }
 }
 
 impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
@@ -424,12 +533,18 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         // anything.
         let mut plain_accounts_cursor = self.tx.cursor_write::<tables::PlainAccountState>()?;
         let mut plain_storage_cursor = self.tx.cursor_dup_write::<tables::PlainStorageState>()?;
+        let mut bytecode_refs =
+            BytecodeRefCounter::new(self.tx.cursor_write::<tables::BytecodeRefs>()?);
 
         let mut reverts: RevertsInit = HashMap::new();
 
         // add account changeset changes
         for (block_number, account_before) in account_changeset.into_iter().rev() {
             let AccountBeforeTx { info: old_info, address } = account_before;
+            if UNWIND {
+                // the changeset was removed, so it no longer references the bytecode.
+                bytecode_refs.decrement(old_info.as_ref())?;
+            }
             match state.entry(address) {
                 hash_map::Entry::Vacant(entry) => {
                     let new_info = plain_accounts_cursor.seek_exact(address)?.map(|kv| kv.1);
@@ -484,6 +599,7 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
             for (address, (old_account, new_account, storage)) in state.iter() {
                 // revert account if needed.
                 if old_account != new_account {
+                    bytecode_refs.replace(new_account.as_ref(), old_account.as_ref())?;
                     let existing_entry = plain_accounts_cursor.seek_exact(*address)?;
                     if let Some(account) = old_account {
                         plain_accounts_cursor.upsert(*address, *account)?;
@@ -793,35 +909,7 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
     where
         T: Table<Key = u64>,
     {
-        self.unwind_table::<T, _>(num, |key| key)
-    }
-
-    /// Unwind the table to a provided number key.
-    /// Returns number of rows unwound.
-    ///
-    /// Note: Key is not inclusive and specified key would stay in db.
-    pub(crate) fn unwind_table<T, F>(
-        &self,
-        key: u64,
-        mut selector: F,
-    ) -> Result<usize, DatabaseError>
-    where
-        T: Table,
-        F: FnMut(T::Key) -> u64,
-    {
-        let mut cursor = self.tx.cursor_write::<T>()?;
-        let mut reverse_walker = cursor.walk_back(None)?;
-        let mut deleted = 0;
-
-        while let Some(Ok((entry_key, _))) = reverse_walker.next() {
-            if selector(entry_key.clone()) <= key {
-                break;
-            }
-            reverse_walker.delete_current()?;
-            deleted += 1;
-        }
-
-        Ok(deleted)
+        self.tx.clear_range::<T>((num + 1)..)
     }
 
     /// Unwind a table forward by a [Walker][reth_db::abstraction::cursor::Walker] on another table
@@ -1027,6 +1115,68 @@ impl<TX: DbTx> ChangeSetReader for DatabaseProvider<TX> {
             })
             .collect()
     }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        let range = BlockNumberAddress::range(block_number..=block_number);
+        self.tx
+            .cursor_read::<tables::StorageChangeSet>()?
+            .walk_range(range)?
+            .map(|result| -> ProviderResult<_> {
+                let (key, storage_before) = result?;
+                Ok((key.address(), storage_before))
+            })
+            .collect()
+    }
+
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.history_block_numbers::<tables::AccountHistory>(address, range)
+    }
+
+    fn storage_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        // The shards of each slot are walked from the first one that can contain a block in range,
+        // until a block after the range is found, then the next slot is sought.
+        let mut block_numbers = BTreeSet::new();
+        let mut cursor = self.tx.cursor_read::<tables::StorageHistory>()?;
+        let mut entry = cursor.seek(StorageShardedKey::new(address, B256::ZERO, *range.start()))?;
+        while let Some((key, list)) = entry {
+            if key.address != address {
+                break
+            }
+            let mut slot_done = false;
+            for block_number in list.iter(0).map(|number| number as BlockNumber) {
+                if block_number > *range.end() {
+                    slot_done = true;
+                    break
+                }
+                if block_number >= *range.start() {
+                    block_numbers.insert(block_number);
+                }
+            }
+            entry = if slot_done {
+                let slot = U256::from_be_bytes(key.sharded_key.key.0);
+                let Some(next_slot) = slot.checked_add(U256::from(1)) else { break };
+                cursor.seek(StorageShardedKey::new(
+                    address,
+                    B256::from(next_slot),
+                    *range.start(),
+                ))?
+            } else {
+                cursor.next()?
+            };
+        }
+        Ok(block_numbers.into_iter().collect())
+    }
 }
 
 impl<TX: DbTx> HeaderSyncGapProvider for DatabaseProvider<TX> {
@@ -1178,6 +1328,14 @@ impl<TX: DbTx> HeaderProvider for DatabaseProvider<TX> {
             predicate,
         )
     }
+
+    fn range_logs_bloom(&self, level: LogsBloomLevel, index: u64) -> ProviderResult<Option<Bloom>> {
+        let bloom = match level {
+            LogsBloomLevel::Blocks4K => self.tx.get::<tables::LogsBlooms4K>(index)?,
+            LogsBloomLevel::Blocks64K => self.tx.get::<tables::LogsBlooms64K>(index)?,
+        };
+        Ok(bloom)
+    }
 }
 
 impl<TX: DbTx> BlockHashReader for DatabaseProvider<TX> {
@@ -1286,8 +1444,15 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
                 return Ok(Some(Vec::new()));
             }
 
-            let ommers = self.tx.get::<tables::BlockOmmers>(number)?.map(|o| o.ommers);
-            return Ok(ommers);
+            if let Some(ommers) = self.tx.get::<tables::BlockOmmers>(number)? {
+                return Ok(Some(ommers.ommers))
+            }
+
+            // Blocks without ommers have no entry in the ommers table, so return empty ommers if
+            // the body is known.
+            if self.tx.get::<tables::BlockBodyIndices>(number)?.is_some() {
+                return Ok(Some(Vec::new()))
+            }
         }
 
         Ok(None)
@@ -1329,6 +1494,17 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
 
         let tx_range = body.tx_num_range();
 
+        if transaction_kind == TransactionVariant::WithHash {
+            // the hashes and senders of all transactions are computed in one pass
+            let (body, senders) = self
+                .transactions_with_senders_by_tx_range(tx_range)?
+                .into_iter()
+                .map(|tx| tx.to_components())
+                .unzip();
+            let block = Block { header, body, ommers, withdrawals };
+            return Ok(Some(BlockWithSenders { block, senders }))
+        }
+
         let (transactions, senders) = if tx_range.is_empty() {
             (vec![], vec![])
         } else {
@@ -1337,14 +1513,11 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
 
         let body = transactions
             .into_iter()
-            .map(|tx| match transaction_kind {
-                TransactionVariant::NoHash => TransactionSigned {
-                    // Caller explicitly asked for no hash, so we don't calculate it
-                    hash: Default::default(),
-                    signature: tx.signature,
-                    transaction: tx.transaction,
-                },
-                TransactionVariant::WithHash => tx.with_hash(),
+            .map(|tx| TransactionSigned {
+                // Caller explicitly asked for no hash, so we don't calculate it
+                hash: Default::default(),
+                signature: tx.signature,
+                transaction: tx.transaction,
             })
             .collect();
 
@@ -1719,6 +1892,126 @@ impl<TX: DbTx> WithdrawalsProvider for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> WithdrawalHistoryReader for DatabaseProvider<TX> {
+    fn withdrawals_by_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Withdrawal)>> {
+        let block_numbers =
+            self.history_block_numbers::<tables::WithdrawalHistory>(address, range)?;
+
+        let mut withdrawals = Vec::new();
+        let mut withdrawals_cursor = self.tx.cursor_read::<tables::BlockWithdrawals>()?;
+        for block_number in block_numbers {
+            if let Some((_, block_withdrawals)) = withdrawals_cursor.seek_exact(block_number)? {
+                withdrawals.extend(
+                    block_withdrawals
+                        .withdrawals
+                        .into_iter()
+                        .filter(|withdrawal| withdrawal.address == address)
+                        .map(|withdrawal| (block_number, withdrawal)),
+                );
+            }
+        }
+        Ok(withdrawals)
+    }
+}
+
+impl<TX: DbTx> InternalTransfersReader for DatabaseProvider<TX> {
+    fn internal_transfers_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<InternalTransfer>> {
+        let mut transfers = Vec::new();
+        for entry in self.tx.cursor_read::<tables::InternalTransfers>()?.walk_range(range)? {
+            let (_, block_transfers) = entry?;
+            transfers.extend(block_transfers.transfers);
+        }
+        Ok(transfers)
+    }
+
+    fn internal_transfers_by_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<InternalTransfer>> {
+        let mut transfers = self.internal_transfers_by_block_range(range)?;
+        transfers.retain(|transfer| transfer.involves(address));
+        Ok(transfers)
+    }
+}
+
+impl<TX: DbTx> ContractCreationsReader for DatabaseProvider<TX> {
+    fn contract_creation(
+        &self,
+        address: Address,
+    ) -> ProviderResult<Option<StoredContractCreation>> {
+        Ok(self.tx.get::<tables::ContractCreations>(address)?)
+    }
+}
+
+impl<TX: DbTx> AddressAppearancesReader for DatabaseProvider<TX> {
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        start: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        // The first shard with a highest number above the start is the first one that can contain
+        // appearances from the start on.
+        let mut tx_numbers = Vec::new();
+        let mut cursor = self.tx.cursor_read::<tables::AddressAppearances>()?;
+        'shards: for entry in cursor.walk(Some(ShardedKey::new(address, start)))? {
+            let (sharded_key, list) = entry?;
+            if sharded_key.key != address {
+                break
+            }
+            for tx_number in list.iter(0).map(|number| number as TxNumber) {
+                if tx_number < start {
+                    continue
+                }
+                if tx_numbers.len() == limit {
+                    break 'shards
+                }
+                tx_numbers.push(tx_number);
+            }
+        }
+        Ok(tx_numbers)
+    }
+
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        end: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        // The shards are walked back from the first one with a highest number above the end. The
+        // last shard of an address has the highest number `u64::MAX`, so it's always found if the
+        // address is indexed.
+        let mut tx_numbers = Vec::new();
+        let mut cursor = self.tx.cursor_read::<tables::AddressAppearances>()?;
+        'shards: for entry in cursor.walk_back(Some(ShardedKey::new(address, end)))? {
+            let (sharded_key, list) = entry?;
+            if sharded_key.key != address {
+                break
+            }
+            let shard = list
+                .iter(0)
+                .map(|number| number as TxNumber)
+                .take_while(|tx_number| *tx_number < end)
+                .collect::<Vec<_>>();
+            for tx_number in shard.into_iter().rev() {
+                if tx_numbers.len() == limit {
+                    break 'shards
+                }
+                tx_numbers.push(tx_number);
+            }
+        }
+        Ok(tx_numbers)
+    }
+}
+
 impl<TX: DbTx> EvmEnvProvider for DatabaseProvider<TX> {
     fn fill_env_at(
         &self,
@@ -2150,18 +2443,19 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
         &self,
         range: Range<BlockNumberAddress>,
     ) -> ProviderResult<usize> {
-        let mut storage_changesets = self
-            .tx
-            .cursor_read::<tables::StorageChangeSet>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(BlockNumberAddress((bn, address)), storage)| (address, storage.key, bn))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-        storage_changesets.sort_by_key(|(address, key, _)| (*address, *key));
+        // Collect the lowest changed block of each storage slot. Changesets are walked in ascending
+        // order, so the first entry of a slot is the lowest one. Unwinding each slot once from its
+        // lowest block also removes the indices of all higher blocks.
+        let mut changesets = 0;
+        let mut storage_changesets = BTreeMap::new();
+        for entry in self.tx.cursor_read::<tables::StorageChangeSet>()?.walk_range(range)? {
+            let (BlockNumberAddress((block_number, address)), storage) = entry?;
+            storage_changesets.entry((address, storage.key)).or_insert(block_number);
+            changesets += 1;
+        }
 
         let mut cursor = self.tx.cursor_write::<tables::StorageHistory>()?;
-        for &(address, storage_key, rem_index) in &storage_changesets {
+        for ((address, storage_key), rem_index) in storage_changesets {
             let partial_shard = unwind_history_shards::<_, tables::StorageHistory, _>(
                 &mut cursor,
                 StorageShardedKey::last(address, storage_key),
@@ -2182,7 +2476,6 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
             }
         }
 
-        let changesets = storage_changesets.len();
         Ok(changesets)
     }
 
@@ -2190,17 +2483,19 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<usize> {
-        let mut last_indices = self
-            .tx
-            .cursor_read::<tables::AccountChangeSet>()?
-            .walk_range(range)?
-            .map(|entry| entry.map(|(index, account)| (account.address, index)))
-            .collect::<Result<Vec<_>, _>>()?;
-        last_indices.sort_by_key(|(a, _)| *a);
+        // Collect the lowest changed block of each account, see
+        // `unwind_storage_history_indices`.
+        let mut changesets = 0;
+        let mut last_indices = BTreeMap::new();
+        for entry in self.tx.cursor_read::<tables::AccountChangeSet>()?.walk_range(range)? {
+            let (block_number, account) = entry?;
+            last_indices.entry(account.address).or_insert(block_number);
+            changesets += 1;
+        }
 
         // Unwind the account history index.
         let mut cursor = self.tx.cursor_write::<tables::AccountHistory>()?;
-        for &(address, rem_index) in &last_indices {
+        for (address, rem_index) in last_indices {
             let partial_shard = unwind_history_shards::<_, tables::AccountHistory, _>(
                 &mut cursor,
                 ShardedKey::last(address),
@@ -2218,9 +2513,204 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
             }
         }
 
-        let changesets = last_indices.len();
         Ok(changesets)
     }
+
+    fn unwind_withdrawal_history_indices(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        // Collect the lowest block with withdrawals to each address, see
+        // `unwind_storage_history_indices`.
+        let mut withdrawals = 0;
+        let mut last_indices = BTreeMap::new();
+        for entry in self.tx.cursor_read::<tables::BlockWithdrawals>()?.walk_range(range)? {
+            let (block_number, block_withdrawals) = entry?;
+            for withdrawal in block_withdrawals.withdrawals {
+                last_indices.entry(withdrawal.address).or_insert(block_number);
+                withdrawals += 1;
+            }
+        }
+
+        // Unwind the withdrawal history index.
+        let mut cursor = self.tx.cursor_write::<tables::WithdrawalHistory>()?;
+        for (address, rem_index) in last_indices {
+            let partial_shard = unwind_history_shards::<_, tables::WithdrawalHistory, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                rem_index,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(withdrawals)
+    }
+
+    fn insert_withdrawal_history_index(
+        &self,
+        withdrawal_transitions: BTreeMap<Address, Vec<u64>>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::WithdrawalHistory>(
+            withdrawal_transitions,
+            ShardedKey::new,
+        )
+    }
+}
+
+impl<TX: DbTxMut + DbTx> LogsBloomIndexWriter for DatabaseProvider<TX> {
+    fn insert_logs_bloom_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
+        let end = *range.end();
+
+        // Combine the blooms of the headers of the completed small ranges. The first range can
+        // start before the inserted blocks.
+        let level = LogsBloomLevel::Blocks4K;
+        for index in level.range_index(*range.start())..=level.range_index(end) {
+            let blocks = level.block_range(index);
+            if *blocks.end() > end {
+                break
+            }
+            let headers = self.headers_range(blocks)?;
+            if headers.len() as u64 != level.range_size() {
+                continue
+            }
+            let bloom = headers.iter().fold(Bloom::ZERO, |bloom, header| bloom | header.logs_bloom);
+            self.tx.put::<tables::LogsBlooms4K>(index, bloom)?;
+        }
+
+        // Combine the blooms of the small ranges of the completed large ranges. A large range is
+        // skipped if any of its small ranges is missing, e.g. because it was synced before the
+        // index existed.
+        let level = LogsBloomLevel::Blocks64K;
+        let ranges_per_level = level.range_size() / LogsBloomLevel::Blocks4K.range_size();
+        for index in level.range_index(*range.start())..=level.range_index(end) {
+            if *level.block_range(index).end() > end {
+                break
+            }
+            let small_ranges = index * ranges_per_level..(index + 1) * ranges_per_level;
+            let mut bloom = Bloom::ZERO;
+            let mut count = 0;
+            for entry in self.tx.cursor_read::<tables::LogsBlooms4K>()?.walk_range(small_ranges)? {
+                bloom |= entry?.1;
+                count += 1;
+            }
+            if count == ranges_per_level {
+                self.tx.put::<tables::LogsBlooms64K>(index, bloom)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn unwind_logs_bloom_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
+        // The ranges that contain the first unwound block are incomplete after the unwind.
+        let start = *range.start();
+        self.get_or_take::<tables::LogsBlooms4K, true>(
+            LogsBloomLevel::Blocks4K.range_index(start)..,
+        )?;
+        self.get_or_take::<tables::LogsBlooms64K, true>(
+            LogsBloomLevel::Blocks64K.range_index(start)..,
+        )?;
+        Ok(())
+    }
+}
+
+impl<TX: DbTxMut + DbTx> ContractCreationsWriter for DatabaseProvider<TX> {
+    fn insert_contract_creations(
+        &self,
+        creations: impl IntoIterator<Item = (Address, StoredContractCreation)>,
+    ) -> ProviderResult<()> {
+        let mut cursor = self.tx.cursor_write::<tables::ContractCreations>()?;
+        for (address, creation) in creations {
+            // a contract that was created again at the same address keeps its first creation
+            if cursor.seek_exact(address)?.is_none() {
+                cursor.upsert(address, creation)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn unwind_contract_creations(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let first_tx_num = self
+            .block_body_indices(*range.start())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*range.start()))?
+            .first_tx_num();
+
+        // the created contracts are among the accounts changed by the blocks
+        let changed_accounts = self
+            .tx
+            .cursor_read::<tables::AccountChangeSet>()?
+            .walk_range(range)?
+            .map(|entry| entry.map(|(_, account_before)| account_before.address))
+            .collect::<Result<BTreeSet<_>, _>>()?;
+
+        let mut cursor = self.tx.cursor_write::<tables::ContractCreations>()?;
+        let mut count = 0;
+        for address in changed_accounts {
+            if cursor
+                .seek_exact(address)?
+                .is_some_and(|(_, creation)| creation.tx_number >= first_tx_num)
+            {
+                cursor.delete_current()?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+}
+
+impl<TX: DbTxMut + DbTx> AddressAppearancesWriter for DatabaseProvider<TX> {
+    fn insert_address_appearances(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let appearances = self.address_appearances_by_block_range(range)?;
+        let count = appearances.values().map(Vec::len).sum();
+        self.append_history_index::<_, tables::AddressAppearances>(appearances, ShardedKey::new)?;
+        Ok(count)
+    }
+
+    fn unwind_address_appearances(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        // Unwinding each address once from its lowest appearance also removes all higher ones.
+        let appearances = self.address_appearances_by_block_range(range)?;
+        let count = appearances.values().map(Vec::len).sum();
+
+        let mut cursor = self.tx.cursor_write::<tables::AddressAppearances>()?;
+        for (address, tx_numbers) in appearances {
+            let Some(rem_index) = tx_numbers.first() else { continue };
+            let partial_shard = unwind_history_shards::<_, tables::AddressAppearances, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                *rem_index,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(count)
+    }
 }
 
 impl<TX: DbTxMut + DbTx> BlockExecutionWriter for DatabaseProvider<TX> {
@@ -2266,6 +2756,30 @@ impl<TX: DbTxMut + DbTx> BlockExecutionWriter for DatabaseProvider<TX> {
             // Unwind storage history indices.
             self.unwind_storage_history_indices(storage_range)?;
 
+            // Unwind withdrawal history indices, before the withdrawals are removed with the
+            // blocks.
+            self.unwind_withdrawal_history_indices(range.clone())?;
+
+            // Unwind logs bloom index.
+            self.unwind_logs_bloom_index(range.clone())?;
+
+            // Unwind contract creations, before the account changesets are removed.
+            self.unwind_contract_creations(range.clone())?;
+
+            // Unwind the optional address appearance index if it covers the blocks, before the
+            // transactions and receipts are removed.
+            let appearances_checkpoint =
+                self.get_stage_checkpoint(StageId::IndexAddressAppearances)?.unwrap_or_default();
+            if appearances_checkpoint.block_number >= *range.start() {
+                self.unwind_address_appearances(
+                    *range.start()..=appearances_checkpoint.block_number.min(*range.end()),
+                )?;
+                self.save_stage_checkpoint(
+                    StageId::IndexAddressAppearances,
+                    StageCheckpoint::new(range.start().saturating_sub(1)),
+                )?;
+            }
+
             // Calculate the reverted merkle root.
             // This is the same as `StateRoot::incremental_root_with_updates`, only the prefix sets
             // are pre-loaded.
@@ -2308,6 +2822,8 @@ impl<TX: DbTxMut + DbTx> BlockExecutionWriter for DatabaseProvider<TX> {
         // remove block bodies it is needed for both get block range and get block execution results
         // that is why it is deleted afterwards.
         if TAKE {
+            // rm internal transfers
+            self.get_or_take::<tables::InternalTransfers, TAKE>(range.clone())?;
             // rm block bodies
             self.get_or_take::<tables::BlockBodyIndices, TAKE>(range)?;
 
@@ -2363,14 +2879,14 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
             durations_recorder.record_relative(metrics::Action::InsertBlockOmmers);
         }
 
-        let mut next_tx_num = self
+        let first_tx_num = self
             .tx
             .cursor_read::<tables::Transactions>()?
             .last()?
-            .map(|(n, _)| n + 1)
+            .map(|(n, _)| offset_number(n, 1))
+            .transpose()?
             .unwrap_or_default();
         durations_recorder.record_relative(metrics::Action::GetNextTxNum);
-        let first_tx_num = next_tx_num;
 
         let tx_count = block.block.body.len() as u64;
 
@@ -2379,7 +2895,10 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
         let mut transactions_elapsed = Duration::default();
         let mut tx_hash_numbers_elapsed = Duration::default();
 
-        for (transaction, sender) in block.block.body.into_iter().zip(block.senders.iter()) {
+        for (tx_idx, (transaction, sender)) in
+            block.block.body.into_iter().zip(block.senders.iter()).enumerate()
+        {
+            let tx_num = offset_number(first_tx_num, tx_idx)?;
             let hash = transaction.hash();
 
             if prune_modes
@@ -2388,18 +2907,18 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
                 .is_none()
             {
                 let start = Instant::now();
-                self.tx.put::<tables::TxSenders>(next_tx_num, *sender)?;
+                self.tx.put::<tables::TxSenders>(tx_num, *sender)?;
                 tx_senders_elapsed += start.elapsed();
             }
 
             let start = Instant::now();
-            self.tx.put::<tables::Transactions>(next_tx_num, transaction.into())?;
+            self.tx.put::<tables::Transactions>(tx_num, transaction.into())?;
             let elapsed = start.elapsed();
             if elapsed > Duration::from_secs(1) {
                 warn!(
                     target: "providers::db",
                     ?block_number,
-                    tx_num = %next_tx_num,
+                    %tx_num,
                     hash = %hash,
                     ?elapsed,
                     "Transaction insertion took too long"
@@ -2413,10 +2932,9 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
                 .is_none()
             {
                 let start = Instant::now();
-                self.tx.put::<tables::TxHashNumber>(hash, next_tx_num)?;
+                self.tx.put::<tables::TxHashNumber>(hash, tx_num)?;
                 tx_hash_numbers_elapsed += start.elapsed();
             }
-            next_tx_num += 1;
         }
         durations_recorder.record_duration(metrics::Action::InsertTxSenders, tx_senders_elapsed);
         durations_recorder
@@ -2426,11 +2944,18 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
 
         if let Some(withdrawals) = block.block.withdrawals {
             if !withdrawals.is_empty() {
+                let recipients = withdrawals
+                    .iter()
+                    .map(|withdrawal| (withdrawal.address, vec![block_number]))
+                    .collect::<BTreeMap<_, _>>();
                 self.tx.put::<tables::BlockWithdrawals>(
                     block_number,
                     StoredBlockWithdrawals { withdrawals },
                 )?;
                 durations_recorder.record_relative(metrics::Action::InsertBlockWithdrawals);
+
+                self.insert_withdrawal_history_index(recipients)?;
+                durations_recorder.record_relative(metrics::Action::InsertWithdrawalHistory);
             }
         }
 
@@ -2494,6 +3019,23 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
         self.update_history_indices(first_number..=last_block_number)?;
         durations_recorder.record_relative(metrics::Action::InsertHistoryIndices);
 
+        self.insert_logs_bloom_index(first_number..=last_block_number)?;
+        durations_recorder.record_relative(metrics::Action::InsertLogsBloomIndex);
+
+        // The optional address appearance index is only extended if it's enabled, i.e. its stage
+        // has indexed all blocks up to these.
+        let appearances_checkpoint = self.get_stage_checkpoint(StageId::IndexAddressAppearances)?;
+        if appearances_checkpoint.is_some_and(|checkpoint| {
+            first_number > 0 && checkpoint.block_number == first_number - 1
+        }) {
+            self.insert_address_appearances(first_number..=last_block_number)?;
+            self.save_stage_checkpoint(
+                StageId::IndexAddressAppearances,
+                StageCheckpoint::new(last_block_number),
+            )?;
+            durations_recorder.record_relative(metrics::Action::InsertAddressAppearances);
+        }
+
         // Update pipeline progress
         self.update_pipeline_stages(last_block_number, false)?;
         durations_recorder.record_relative(metrics::Action::UpdatePipelineStages);
@@ -1,12 +1,16 @@
 use crate::{
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockchainTreePendingStateProvider, BundleStateDataProvider, CanonChainTracker,
-    CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    EvmEnvProvider, HeaderProvider, ProviderError, PruneCheckpointReader, ReceiptProvider,
+    AccountReader, AddressAppearancesReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, BlockchainTreePendingStateProvider, BundleStateDataProvider,
+    CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider,
+    ChangeSetReader, ContractCreationsReader, EvmEnvProvider, HeaderProvider,
+    InternalTransfersReader, LogsBloomLevel, ProviderError, PruneCheckpointReader, ReceiptProvider,
     ReceiptProviderIdExt, StageCheckpointReader, StateProviderBox, StateProviderFactory,
-    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    TransactionVariant, TransactionsProvider, WithdrawalHistoryReader, WithdrawalsProvider,
+};
+use reth_db::{
+    database::Database,
+    models::{StoredBlockBodyIndices, StoredContractCreation},
 };
-use reth_db::{database::Database, models::StoredBlockBodyIndices};
 use reth_interfaces::{
     blockchain_tree::{BlockchainTreeEngine, BlockchainTreeViewer},
     consensus::ForkchoiceState,
@@ -16,9 +20,10 @@ use reth_interfaces::{
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
-    BlockNumberOrTag, BlockWithSenders, ChainInfo, ChainSpec, Header, PruneCheckpoint,
-    PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, TransactionMeta,
-    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, B256, U256,
+    BlockNumberOrTag, BlockWithSenders, Bloom, ChainInfo, ChainSpec, Header, InternalTransfer,
+    PruneCheckpoint, PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader,
+    StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
+    Withdrawal, B256, U256,
 };
 use revm::primitives::{BlockEnv, CfgEnv};
 use std::{
@@ -30,11 +35,13 @@ use std::{
 use tracing::trace;
 
 pub use state::{
-    historical::{HistoricalStateProvider, HistoricalStateProviderRef},
+    historical::{HistoricalStateProvider, HistoricalStateProviderRef, MAX_PROOF_REVERT_BLOCKS},
     latest::{LatestStateProvider, LatestStateProviderRef},
 };
 
 mod bundle_state_provider;
+mod canonical_chain;
+mod canonical_hash_provider;
 mod chain_info;
 mod database;
 mod snapshot;
@@ -42,6 +49,8 @@ pub use snapshot::{SnapshotJarProvider, SnapshotProvider};
 mod state;
 use crate::{providers::chain_info::ChainInfoTracker, traits::BlockSource};
 pub use bundle_state_provider::BundleStateProvider;
+pub use canonical_chain::{CanonicalChainTracker, DEFAULT_MAX_CANONICAL_HASHES};
+pub use canonical_hash_provider::CanonicalHashStateProvider;
 pub use database::*;
 use reth_db::models::AccountBeforeTx;
 use reth_interfaces::blockchain_tree::{
@@ -61,13 +70,31 @@ pub struct BlockchainProvider<DB, Tree> {
     tree: Tree,
     /// Tracks the chain info wrt forkchoice updates
     chain_info: ChainInfoTracker,
+    /// Caches the hashes of the most recent canonical blocks
+    canonical_chain: CanonicalChainTracker,
 }
 
 impl<DB, Tree> BlockchainProvider<DB, Tree> {
     /// Create new  provider instance that wraps the database and the blockchain tree, using the
     /// provided latest header to initialize the chain info tracker.
     pub fn with_latest(database: ProviderFactory<DB>, tree: Tree, latest: SealedHeader) -> Self {
-        Self { database, tree, chain_info: ChainInfoTracker::new(latest) }
+        let canonical_chain = CanonicalChainTracker::default();
+        canonical_chain.set_canonical_head(&latest);
+        Self { database, tree, chain_info: ChainInfoTracker::new(latest), canonical_chain }
+    }
+
+    /// Returns the tracker of the most recent canonical block hashes.
+    ///
+    /// The tracker is updated whenever the canonical chain of the tree changes through this
+    /// provider and on forkchoice updates.
+    pub fn canonical_chain(&self) -> &CanonicalChainTracker {
+        &self.canonical_chain
+    }
+
+    /// Wraps the state provider of a canonical block, so it resolves the hashes of the recent
+    /// canonical blocks from memory.
+    fn with_canonical_hashes(&self, state: StateProviderBox) -> StateProviderBox {
+        Box::new(CanonicalHashStateProvider::new(state, self.canonical_chain.clone()))
     }
 }
 
@@ -157,6 +184,10 @@ where
     ) -> ProviderResult<Vec<SealedHeader>> {
         self.database.provider()?.sealed_headers_while(range, predicate)
     }
+
+    fn range_logs_bloom(&self, level: LogsBloomLevel, index: u64) -> ProviderResult<Option<Bloom>> {
+        self.database.provider()?.range_logs_bloom(level, index)
+    }
 }
 
 impl<DB, Tree> BlockHashReader for BlockchainProvider<DB, Tree>
@@ -165,6 +196,9 @@ where
     Tree: Send + Sync,
 {
     fn block_hash(&self, number: u64) -> ProviderResult<Option<B256>> {
+        if let Some(hash) = self.canonical_chain.block_hash(number) {
+            return Ok(Some(hash))
+        }
         self.database.provider()?.block_hash(number)
     }
 
@@ -173,6 +207,9 @@ where
         start: BlockNumber,
         end: BlockNumber,
     ) -> ProviderResult<Vec<B256>> {
+        if let Some(hashes) = self.canonical_chain.canonical_hashes_range(start, end) {
+            return Ok(hashes)
+        }
         self.database.provider()?.canonical_hashes_range(start, end)
     }
 }
@@ -503,6 +540,78 @@ where
     }
 }
 
+impl<DB, Tree> InternalTransfersReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Send + Sync,
+{
+    fn internal_transfers_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<InternalTransfer>> {
+        self.database.provider()?.internal_transfers_by_block_range(range)
+    }
+
+    fn internal_transfers_by_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<InternalTransfer>> {
+        self.database.provider()?.internal_transfers_by_address(address, range)
+    }
+}
+
+impl<DB, Tree> ContractCreationsReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Send + Sync,
+{
+    fn contract_creation(
+        &self,
+        address: Address,
+    ) -> ProviderResult<Option<StoredContractCreation>> {
+        self.database.provider()?.contract_creation(address)
+    }
+}
+
+impl<DB, Tree> AddressAppearancesReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Send + Sync,
+{
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        start: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.provider()?.address_appearances_after(address, start, limit)
+    }
+
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        end: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.provider()?.address_appearances_before(address, end, limit)
+    }
+}
+
+impl<DB, Tree> WithdrawalHistoryReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Send + Sync,
+{
+    fn withdrawals_by_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Withdrawal)>> {
+        self.database.provider()?.withdrawals_by_address(address, range)
+    }
+}
+
 impl<DB, Tree> ChainSpecProvider for BlockchainProvider<DB, Tree>
 where
     DB: Send + Sync,
@@ -521,7 +630,7 @@ where
     /// Storage provider for latest block
     fn latest(&self) -> ProviderResult<StateProviderBox> {
         trace!(target: "providers::blockchain", "Getting latest block state provider");
-        self.database.latest()
+        Ok(self.with_canonical_hashes(self.database.latest()?))
     }
 
     fn history_by_block_number(
@@ -530,12 +639,12 @@ where
     ) -> ProviderResult<StateProviderBox> {
         trace!(target: "providers::blockchain", ?block_number, "Getting history by block number");
         self.ensure_canonical_block(block_number)?;
-        self.database.history_by_block_number(block_number)
+        Ok(self.with_canonical_hashes(self.database.history_by_block_number(block_number)?))
     }
 
     fn history_by_block_hash(&self, block_hash: BlockHash) -> ProviderResult<StateProviderBox> {
         trace!(target: "providers::blockchain", ?block_hash, "Getting history by block hash");
-        self.database.history_by_block_hash(block_hash)
+        Ok(self.with_canonical_hashes(self.database.history_by_block_hash(block_hash)?))
     }
 
     fn state_by_block_hash(&self, block: BlockHash) -> ProviderResult<StateProviderBox> {
@@ -615,19 +724,42 @@ where
         &self,
         last_finalized_block: BlockNumber,
     ) -> RethResult<()> {
-        self.tree.connect_buffered_blocks_to_canonical_hashes_and_finalize(last_finalized_block)
+        let res = self
+            .tree
+            .connect_buffered_blocks_to_canonical_hashes_and_finalize(last_finalized_block);
+        self.sync_canonical_chain();
+        res
     }
 
     fn connect_buffered_blocks_to_canonical_hashes(&self) -> RethResult<()> {
-        self.tree.connect_buffered_blocks_to_canonical_hashes()
+        let res = self.tree.connect_buffered_blocks_to_canonical_hashes();
+        self.sync_canonical_chain();
+        res
     }
 
     fn make_canonical(&self, block_hash: &BlockHash) -> RethResult<CanonicalOutcome> {
-        self.tree.make_canonical(block_hash)
+        let res = self.tree.make_canonical(block_hash);
+        self.sync_canonical_chain();
+        res
     }
 
     fn unwind(&self, unwind_to: BlockNumber) -> RethResult<()> {
-        self.tree.unwind(unwind_to)
+        let res = self.tree.unwind(unwind_to);
+        self.sync_canonical_chain();
+        res
+    }
+}
+
+impl<DB, Tree> BlockchainProvider<DB, Tree>
+where
+    Tree: BlockchainTreeViewer,
+{
+    /// Replaces the cached canonical hashes with the canonical chain of the tree.
+    ///
+    /// This is done right after the tree changed the canonical chain, before the hashes are read
+    /// by anyone that expects the new chain.
+    fn sync_canonical_chain(&self) {
+        self.canonical_chain.set_canonical_hashes(&self.tree.canonical_blocks());
     }
 }
 
@@ -721,14 +853,17 @@ where
     }
 
     fn set_canonical_head(&self, header: SealedHeader) {
+        self.canonical_chain.set_canonical_head(&header);
         self.chain_info.set_canonical_head(header);
     }
 
     fn set_safe(&self, header: SealedHeader) {
+        self.canonical_chain.set_safe(header.num_hash());
         self.chain_info.set_safe(header);
     }
 
     fn set_finalized(&self, header: SealedHeader) {
+        self.canonical_chain.set_finalized(header.num_hash());
         self.chain_info.set_finalized(header);
     }
 }
@@ -805,9 +940,12 @@ where
         match id {
             BlockId::Number(num) => self.ommers_by_number_or_tag(num),
             BlockId::Hash(hash) => {
-                // TODO: EIP-1898 question, see above
-                // here it is not handled
-                self.ommers(BlockHashOrNumber::Hash(hash.block_hash))
+                let mut ommers = self.ommers(BlockHashOrNumber::Hash(hash.block_hash))?;
+                if ommers.is_none() && !hash.require_canonical.unwrap_or(false) {
+                    // the block might not be canonical yet
+                    ommers = self.tree.block_by_hash(hash.block_hash).map(|block| block.ommers);
+                }
+                Ok(ommers)
             }
         }
     }
@@ -847,6 +985,29 @@ where
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         self.database.provider()?.account_block_changeset(block_number)
     }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        self.database.provider()?.storage_block_changeset(block_number)
+    }
+
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.account_history_blocks(address, range)
+    }
+
+    fn storage_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.storage_history_blocks(address, range)
+    }
 }
 
 impl<DB, Tree> AccountReader for BlockchainProvider<DB, Tree>
@@ -1,10 +1,12 @@
 use crate::{
     providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    BundleStateWithReceipts, ProviderError, StateProvider, StateRootProvider,
+    BundleStateWithReceipts, PreloadedState, ProviderError, StateProvider, StateRootProvider,
 };
 use reth_db::{
     cursor::{DbCursorRO, DbDupCursorRO},
-    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    models::{
+        storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress, ShardedKey,
+    },
     table::Table,
     tables,
     transaction::DbTx,
@@ -12,9 +14,21 @@ use reth_db::{
 };
 use reth_interfaces::provider::ProviderResult;
 use reth_primitives::{
-    trie::AccountProof, Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, B256,
+    keccak256, trie::AccountProof, Account, Address, BlockNumber, Bytecode, StorageEntry,
+    StorageKey, StorageValue, B256, U256,
 };
-use reth_trie::updates::TrieUpdates;
+use reth_trie::{
+    hashed_cursor::{HashedPostState, HashedPostStateCursorFactory, HashedStorage},
+    proof::Proof,
+    updates::TrieUpdates,
+};
+use std::collections::{BTreeSet, HashMap};
+
+/// The maximum number of blocks whose changesets are reverted to serve a historical proof.
+///
+/// Historical proofs are generated from the latest trie, with every key changed since the block
+/// recomputed, so their cost grows with the distance to the tip.
+pub const MAX_PROOF_REVERT_BLOCKS: u64 = 256;
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -63,6 +77,23 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
 
     /// Lookup an account in the AccountHistory table
     pub fn account_history_lookup(&self, address: Address) -> ProviderResult<HistoryInfo> {
+        self.account_history_lookup_with(&mut self.tx.cursor_read()?, address)
+    }
+
+    /// Lookup a storage key in the StorageHistory table
+    pub fn storage_history_lookup(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<HistoryInfo> {
+        self.storage_history_lookup_with(&mut self.tx.cursor_read()?, address, storage_key)
+    }
+
+    fn account_history_lookup_with(
+        &self,
+        cursor: &mut TX::Cursor<tables::AccountHistory>,
+        address: Address,
+    ) -> ProviderResult<HistoryInfo> {
         if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
             return Err(ProviderError::StateAtBlockPruned(self.block_number))
         }
@@ -70,15 +101,16 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         // history key to search IntegerList of block number changesets.
         let history_key = ShardedKey::new(address, self.block_number);
         self.history_info::<tables::AccountHistory, _>(
+            cursor,
             history_key,
             |key| key.key == address,
             self.lowest_available_blocks.account_history_block_number,
         )
     }
 
-    /// Lookup a storage key in the StorageHistory table
-    pub fn storage_history_lookup(
+    fn storage_history_lookup_with(
         &self,
+        cursor: &mut TX::Cursor<tables::StorageHistory>,
         address: Address,
         storage_key: StorageKey,
     ) -> ProviderResult<HistoryInfo> {
@@ -89,14 +121,135 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         // history key to search IntegerList of block number changesets.
         let history_key = StorageShardedKey::new(address, storage_key, self.block_number);
         self.history_info::<tables::StorageHistory, _>(
+            cursor,
             history_key,
             |key| key.address == address && key.sharded_key.key == storage_key,
             self.lowest_available_blocks.storage_history_block_number,
         )
     }
 
+    /// Returns the hashed state that reverts the latest hashed state to the state at the start of
+    /// the block.
+    ///
+    /// The first changeset entry of a key at or after the block holds its value at the block.
+    fn revert_state(&self) -> ProviderResult<HashedPostState> {
+        if !self.lowest_available_blocks.is_account_history_available(self.block_number) ||
+            !self.lowest_available_blocks.is_storage_history_available(self.block_number)
+        {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+        }
+
+        let last_account_change =
+            self.tx.cursor_read::<tables::AccountChangeSet>()?.last()?.map(|(number, _)| number);
+        let last_storage_change = self
+            .tx
+            .cursor_read::<tables::StorageChangeSet>()?
+            .last()?
+            .map(|(key, _)| key.block_number());
+        if let Some(tip) = last_account_change.max(last_storage_change) {
+            if tip.saturating_sub(self.block_number) >= MAX_PROOF_REVERT_BLOCKS {
+                return Err(ProviderError::ProofTooDeep {
+                    block_number: self.block_number,
+                    max_blocks: MAX_PROOF_REVERT_BLOCKS,
+                })
+            }
+        }
+
+        let mut accounts = HashMap::new();
+        for entry in
+            self.tx.cursor_read::<tables::AccountChangeSet>()?.walk_range(self.block_number..)?
+        {
+            let (_, AccountBeforeTx { address, info }) = entry?;
+            accounts.entry(address).or_insert(info);
+        }
+
+        let mut storages = HashMap::<Address, HashMap<B256, U256>>::new();
+        let start = BlockNumberAddress((self.block_number, Address::ZERO));
+        for entry in self.tx.cursor_read::<tables::StorageChangeSet>()?.walk_range(start..)? {
+            let (BlockNumberAddress((_, address)), StorageEntry { key, value }) = entry?;
+            storages.entry(address).or_default().entry(key).or_insert(value);
+        }
+
+        let mut state = HashedPostState::default();
+        for (address, info) in accounts {
+            let hashed_address = keccak256(address);
+            match info {
+                Some(account) => state.insert_account(hashed_address, account),
+                None => state.insert_destroyed_account(hashed_address),
+            }
+        }
+        for (address, storage) in storages {
+            let mut hashed_storage = HashedStorage::new(false);
+            for (slot, value) in storage {
+                let hashed_slot = keccak256(slot);
+                if value == U256::ZERO {
+                    hashed_storage.insert_zero_valued_slot(hashed_slot);
+                } else {
+                    hashed_storage.insert_non_zero_valued_storage(hashed_slot, value);
+                }
+            }
+            state.insert_hashed_storage(keccak256(address), hashed_storage);
+        }
+        Ok(state.sorted())
+    }
+
+    /// Returns the account at the block, looked up with the given cursors.
+    fn account_at(
+        &self,
+        history: &mut TX::Cursor<tables::AccountHistory>,
+        changesets: &mut TX::DupCursor<tables::AccountChangeSet>,
+        plain_state: &mut TX::Cursor<tables::PlainAccountState>,
+        address: Address,
+    ) -> ProviderResult<Option<Account>> {
+        match self.account_history_lookup_with(history, address)? {
+            HistoryInfo::NotYetWritten => Ok(None),
+            HistoryInfo::InChangeset(changeset_block_number) => Ok(changesets
+                .seek_by_key_subkey(changeset_block_number, address)?
+                .filter(|acc| acc.address == address)
+                .ok_or(ProviderError::AccountChangesetNotFound {
+                    block_number: changeset_block_number,
+                    address,
+                })?
+                .info),
+            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
+                Ok(plain_state.seek_exact(address)?.map(|(_, account)| account))
+            }
+        }
+    }
+
+    /// Returns the storage value at the block, looked up with the given cursors.
+    fn storage_at(
+        &self,
+        history: &mut TX::Cursor<tables::StorageHistory>,
+        changesets: &mut TX::DupCursor<tables::StorageChangeSet>,
+        plain_state: &mut TX::DupCursor<tables::PlainStorageState>,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        match self.storage_history_lookup_with(history, address, storage_key)? {
+            HistoryInfo::NotYetWritten => Ok(None),
+            HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
+                changesets
+                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
+                    .filter(|entry| entry.key == storage_key)
+                    .ok_or_else(|| ProviderError::StorageChangesetNotFound {
+                        block_number: changeset_block_number,
+                        address,
+                        storage_key: Box::new(storage_key),
+                    })?
+                    .value,
+            )),
+            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(plain_state
+                .seek_by_key_subkey(address, storage_key)?
+                .filter(|entry| entry.key == storage_key)
+                .map(|entry| entry.value)
+                .or(Some(StorageValue::ZERO))),
+        }
+    }
+
     fn history_info<T, K>(
         &self,
+        cursor: &mut TX::Cursor<T>,
         key: K,
         key_filter: impl Fn(&K) -> bool,
         lowest_available_block_number: Option<BlockNumber>,
@@ -104,8 +257,6 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
     where
         T: Table<Key = K, Value = BlockNumberList>,
     {
-        let mut cursor = self.tx.cursor_read::<T>()?;
-
         // Lookup the history chunk in the history index. If they key does not appear in the
         // index, the first chunk for the next key will be returned so we filter out chunks that
         // have a different key.
@@ -155,22 +306,12 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
 impl<'b, TX: DbTx> AccountReader for HistoricalStateProviderRef<'b, TX> {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
-        match self.account_history_lookup(address)? {
-            HistoryInfo::NotYetWritten => Ok(None),
-            HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx
-                .cursor_dup_read::<tables::AccountChangeSet>()?
-                .seek_by_key_subkey(changeset_block_number, address)?
-                .filter(|acc| acc.address == address)
-                .ok_or(ProviderError::AccountChangesetNotFound {
-                    block_number: changeset_block_number,
-                    address,
-                })?
-                .info),
-            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
-                Ok(self.tx.get::<tables::PlainAccountState>(address)?)
-            }
-        }
+        self.account_at(
+            &mut self.tx.cursor_read()?,
+            &mut self.tx.cursor_dup_read()?,
+            &mut self.tx.cursor_read()?,
+            address,
+        )
     }
 }
 
@@ -209,6 +350,14 @@ impl<'b, TX: DbTx> StateRootProvider for HistoricalStateProviderRef<'b, TX> {
     ) -> ProviderResult<(B256, TrieUpdates)> {
         Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
     }
+
+    fn storage_root(
+        &self,
+        _address: Address,
+        _bundle_state: &BundleStateWithReceipts,
+    ) -> ProviderResult<B256> {
+        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    }
 }
 
 impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
@@ -218,28 +367,13 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
         address: Address,
         storage_key: StorageKey,
     ) -> ProviderResult<Option<StorageValue>> {
-        match self.storage_history_lookup(address, storage_key)? {
-            HistoryInfo::NotYetWritten => Ok(None),
-            HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx
-                    .cursor_dup_read::<tables::StorageChangeSet>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
-                    .ok_or_else(|| ProviderError::StorageChangesetNotFound {
-                        block_number: changeset_block_number,
-                        address,
-                        storage_key: Box::new(storage_key),
-                    })?
-                    .value,
-            )),
-            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(self
-                .tx
-                .cursor_dup_read::<tables::PlainStorageState>()?
-                .seek_by_key_subkey(address, storage_key)?
-                .filter(|entry| entry.key == storage_key)
-                .map(|entry| entry.value)
-                .or(Some(StorageValue::ZERO))),
-        }
+        self.storage_at(
+            &mut self.tx.cursor_read()?,
+            &mut self.tx.cursor_dup_read()?,
+            &mut self.tx.cursor_dup_read()?,
+            address,
+            storage_key,
+        )
     }
 
     /// Get account code by its hash
@@ -248,8 +382,123 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
     }
 
     /// Get account and storage proofs.
-    fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
-        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    ///
+    /// The proofs are generated from the intermediate nodes of the latest state, with the keys
+    /// changed since the block recomputed from the changesets.
+    fn proof(&self, address: Address, slots: &[B256]) -> ProviderResult<AccountProof> {
+        let revert_state = self.revert_state()?;
+        let (account_prefixes, storage_prefixes) = revert_state.construct_prefix_sets_mut();
+        Ok(Proof::new(self.tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(self.tx, &revert_state))
+            .with_changed_account_prefixes(account_prefixes)
+            .with_changed_storage_prefixes(storage_prefixes)
+            .account_proof(address, slots)
+            .map_err(Into::<reth_db::DatabaseError>::into)?)
+    }
+
+    /// Get account and storage proofs for multiple accounts.
+    ///
+    /// The changesets are reverted once and shared by all proofs.
+    fn proofs(&self, targets: &[(Address, Vec<B256>)]) -> ProviderResult<Vec<AccountProof>> {
+        let revert_state = self.revert_state()?;
+        let (account_prefixes, storage_prefixes) = revert_state.construct_prefix_sets_mut();
+        let mut proofs = Vec::with_capacity(targets.len());
+        for (address, slots) in targets {
+            // only the storage prefixes of the proven account are used
+            let hashed_address = keccak256(address);
+            let storage_prefixes = storage_prefixes
+                .get(&hashed_address)
+                .map(|prefixes| (hashed_address, prefixes.clone()))
+                .into_iter()
+                .collect();
+            proofs.push(
+                Proof::new(self.tx)
+                    .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(
+                        self.tx,
+                        &revert_state,
+                    ))
+                    .with_changed_account_prefixes(account_prefixes.clone())
+                    .with_changed_storage_prefixes(storage_prefixes)
+                    .account_proof(*address, slots)
+                    .map_err(Into::<reth_db::DatabaseError>::into)?,
+            );
+        }
+        Ok(proofs)
+    }
+
+    /// Preloads the given keys and all keys changed in the block.
+    ///
+    /// The changesets of the block hold the values at its start of the keys it changed, so they
+    /// are read in one walk per table. The remaining keys are looked up in ascending order, reusing
+    /// the same cursors.
+    fn preload(
+        &self,
+        accounts: &[Address],
+        slots: &[(Address, B256)],
+    ) -> ProviderResult<PreloadedState> {
+        let mut state = PreloadedState::default();
+
+        if self.lowest_available_blocks.is_account_history_available(self.block_number) {
+            let mut changesets = self.tx.cursor_dup_read::<tables::AccountChangeSet>()?;
+            for entry in changesets.walk_dup(Some(self.block_number), None)? {
+                let (_, AccountBeforeTx { address, info }) = entry?;
+                state.accounts.insert(address, info);
+            }
+        }
+        if self.lowest_available_blocks.is_storage_history_available(self.block_number) {
+            let start = BlockNumberAddress((self.block_number, Address::ZERO));
+            let end = BlockNumberAddress((self.block_number, Address::repeat_byte(0xff)));
+            for entry in
+                self.tx.cursor_read::<tables::StorageChangeSet>()?.walk_range(start..=end)?
+            {
+                let (BlockNumberAddress((_, address)), StorageEntry { key, value }) = entry?;
+                state.storage.entry(address).or_default().insert(key, value);
+            }
+        }
+
+        let accounts = accounts
+            .iter()
+            .filter(|address| !state.accounts.contains_key(*address))
+            .copied()
+            .collect::<BTreeSet<_>>();
+        if !accounts.is_empty() {
+            let mut history = self.tx.cursor_read()?;
+            let mut changesets = self.tx.cursor_dup_read()?;
+            let mut plain_state = self.tx.cursor_read()?;
+            for address in accounts {
+                let account =
+                    self.account_at(&mut history, &mut changesets, &mut plain_state, address)?;
+                state.accounts.insert(address, account);
+            }
+        }
+
+        let slots = slots
+            .iter()
+            .filter(|(address, slot)| {
+                !state.storage.get(address).is_some_and(|storage| storage.contains_key(slot))
+            })
+            .copied()
+            .collect::<BTreeSet<_>>();
+        if !slots.is_empty() {
+            let mut history = self.tx.cursor_read()?;
+            let mut changesets = self.tx.cursor_dup_read()?;
+            let mut plain_state = self.tx.cursor_dup_read()?;
+            for (address, slot) in slots {
+                let value = self
+                    .storage_at(&mut history, &mut changesets, &mut plain_state, address, slot)?
+                    .unwrap_or_default();
+                state.storage.entry(address).or_default().insert(slot, value);
+            }
+        }
+
+        let mut bytecodes = self.tx.cursor_read::<tables::Bytecodes>()?;
+        for code_hash in state.code_hashes() {
+            if let Some((_, bytecode)) = bytecodes.seek_exact(code_hash)? {
+                state.bytecodes.insert(code_hash, bytecode);
+            }
+        }
+
+        Ok(state)
     }
 }
 
@@ -334,8 +583,11 @@ impl LowestAvailableBlocks {
 #[cfg(test)]
 mod tests {
     use crate::{
-        providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
-        AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
+        providers::state::historical::{
+            HistoryInfo, LowestAvailableBlocks, MAX_PROOF_REVERT_BLOCKS,
+        },
+        AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, PreloadedState,
+        StateProvider,
     };
     use reth_db::{
         database::Database,
@@ -346,7 +598,11 @@ mod tests {
         BlockNumberList,
     };
     use reth_interfaces::provider::ProviderError;
-    use reth_primitives::{address, b256, Account, Address, StorageEntry, B256, U256};
+    use reth_primitives::{
+        address, b256, keccak256, Account, Address, Bytecode, Bytes, StorageEntry, B256, U256,
+    };
+    use reth_trie::{proof::Proof, StateRoot};
+    use std::collections::HashMap;
 
     const ADDRESS: Address = address!("0000000000000000000000000000000000000001");
     const HIGHER_ADDRESS: Address = address!("0000000000000000000000000000000000000005");
@@ -557,6 +813,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn history_provider_preload() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+
+        let bytecode = Bytecode::new_raw(Bytes::from_static(&[0x00]));
+        let code_hash = bytecode.hash_slow();
+        let account_at7 =
+            Account { nonce: 7, bytecode_hash: Some(code_hash), ..Default::default() };
+        let account_plain = Account { nonce: 100, ..Default::default() };
+        let entry_at7 = StorageEntry { key: STORAGE, value: U256::from(7) };
+        let entry_plain = StorageEntry { key: STORAGE, value: U256::from(100) };
+
+        // setup
+        tx.put::<tables::AccountHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([7]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::StorageHistory>(
+            StorageShardedKey {
+                address: ADDRESS,
+                sharded_key: ShardedKey { key: STORAGE, highest_block_number: u64::MAX },
+            },
+            BlockNumberList::new([7]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSet>(
+            7,
+            AccountBeforeTx { address: ADDRESS, info: Some(account_at7) },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSet>((7, ADDRESS).into(), entry_at7).unwrap();
+        tx.put::<tables::PlainAccountState>(ADDRESS, account_plain).unwrap();
+        tx.put::<tables::PlainStorageState>(ADDRESS, entry_plain).unwrap();
+        tx.put::<tables::Bytecodes>(code_hash, bytecode.clone()).unwrap();
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+        let expected = PreloadedState {
+            accounts: HashMap::from([(ADDRESS, Some(account_at7)), (HIGHER_ADDRESS, None)]),
+            storage: HashMap::from([
+                (ADDRESS, HashMap::from([(STORAGE, entry_at7.value)])),
+                (HIGHER_ADDRESS, HashMap::from([(STORAGE, U256::ZERO)])),
+            ]),
+            bytecodes: HashMap::from([(code_hash, bytecode)]),
+        };
+
+        // the keys changed in block 7 are read from its changesets without being requested
+        let provider = HistoricalStateProviderRef::new(&tx, 7);
+        assert_eq!(
+            provider.preload(&[HIGHER_ADDRESS], &[(HIGHER_ADDRESS, STORAGE)]),
+            Ok(expected.clone())
+        );
+
+        // the keys are looked up in the history before the block
+        let provider = HistoricalStateProviderRef::new(&tx, 4);
+        assert_eq!(
+            provider.preload(
+                &[ADDRESS, HIGHER_ADDRESS],
+                &[(ADDRESS, STORAGE), (HIGHER_ADDRESS, STORAGE)]
+            ),
+            Ok(expected)
+        );
+    }
+
     #[test]
     fn history_provider_unavailable() {
         let db = create_test_rw_db();
@@ -613,4 +935,72 @@ mod tests {
             Ok(HistoryInfo::MaybeInPlainState)
         );
     }
+
+    #[test]
+    fn history_provider_proof() {
+        let hashed_address = keccak256(ADDRESS);
+        let acc_at5 = Account { nonce: 5, balance: U256::from(1), bytecode_hash: None };
+        let acc_plain = Account { nonce: 6, balance: U256::from(2), bytecode_hash: None };
+        let higher_acc = Account { nonce: 4, balance: U256::ZERO, bytecode_hash: None };
+        let slot_at5 = StorageEntry { key: STORAGE, value: U256::from(1) };
+
+        // the state at the start of block 5
+        let expected_db = create_test_rw_db();
+        let expected_tx = expected_db.tx_mut().unwrap();
+        expected_tx.put::<tables::HashedAccount>(hashed_address, acc_at5).unwrap();
+        expected_tx.put::<tables::HashedAccount>(keccak256(HIGHER_ADDRESS), higher_acc).unwrap();
+        expected_tx
+            .put::<tables::HashedStorage>(
+                hashed_address,
+                StorageEntry { key: keccak256(STORAGE), value: slot_at5.value },
+            )
+            .unwrap();
+        let (_, updates) = StateRoot::from_tx(&expected_tx).root_with_updates().unwrap();
+        updates.flush(&expected_tx).unwrap();
+
+        // the latest state, block 5 changed the account and cleared the slot
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+        tx.put::<tables::HashedAccount>(hashed_address, acc_plain).unwrap();
+        tx.put::<tables::HashedAccount>(keccak256(HIGHER_ADDRESS), higher_acc).unwrap();
+        let (_, updates) = StateRoot::from_tx(&tx).root_with_updates().unwrap();
+        updates.flush(&tx).unwrap();
+        tx.put::<tables::AccountChangeSet>(
+            5,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at5) },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSet>((5, ADDRESS).into(), slot_at5).unwrap();
+
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 5).proof(ADDRESS, &[STORAGE]).unwrap(),
+            Proof::new(&expected_tx).account_proof(ADDRESS, &[STORAGE]).unwrap()
+        );
+        // the changesets before the block aren't reverted
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 6).proof(ADDRESS, &[STORAGE]).unwrap(),
+            Proof::new(&tx).account_proof(ADDRESS, &[STORAGE]).unwrap()
+        );
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 5)
+                .proofs(&[(ADDRESS, vec![STORAGE]), (HIGHER_ADDRESS, vec![])])
+                .unwrap(),
+            vec![
+                Proof::new(&expected_tx).account_proof(ADDRESS, &[STORAGE]).unwrap(),
+                Proof::new(&expected_tx).account_proof(HIGHER_ADDRESS, &[]).unwrap(),
+            ]
+        );
+
+        // states too far behind the last changeset aren't proven
+        tx.put::<tables::AccountChangeSet>(
+            5 + MAX_PROOF_REVERT_BLOCKS,
+            AccountBeforeTx { address: HIGHER_ADDRESS, info: Some(higher_acc) },
+        )
+        .unwrap();
+        assert!(matches!(
+            HistoricalStateProviderRef::new(&tx, 5).proof(ADDRESS, &[STORAGE]),
+            Err(ProviderError::ProofTooDeep { block_number: 5, .. })
+        ));
+        assert!(HistoricalStateProviderRef::new(&tx, 6).proof(ADDRESS, &[STORAGE]).is_ok());
+    }
 }
@@ -71,6 +71,16 @@ impl<'b, TX: DbTx> StateRootProvider for LatestStateProviderRef<'b, TX> {
             .state_root_slow_with_updates(self.db)
             .map_err(|err| ProviderError::Database(err.into()))
     }
+
+    fn storage_root(
+        &self,
+        address: Address,
+        bundle_state: &BundleStateWithReceipts,
+    ) -> ProviderResult<B256> {
+        bundle_state
+            .storage_root_slow(self.db, address)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
 }
 
 impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
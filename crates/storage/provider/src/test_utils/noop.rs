@@ -1,21 +1,23 @@
 use crate::{
     bundle_state::BundleStateWithReceipts,
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, PruneCheckpointReader,
+    AccountReader, AddressAppearancesReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, ContractCreationsReader,
+    EvmEnvProvider, HeaderProvider, InternalTransfersReader, PruneCheckpointReader,
     ReceiptProviderIdExt, StageCheckpointReader, StateProvider, StateProviderBox,
     StateProviderFactory, StateRootProvider, TransactionVariant, TransactionsProvider,
-    WithdrawalsProvider,
+    WithdrawalHistoryReader, WithdrawalsProvider,
 };
-use reth_db::models::{AccountBeforeTx, StoredBlockBodyIndices};
+use reth_db::models::{AccountBeforeTx, StoredBlockBodyIndices, StoredContractCreation};
 use reth_interfaces::provider::ProviderResult;
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
     trie::AccountProof,
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber, Bytecode,
-    ChainInfo, ChainSpec, Header, PruneCheckpoint, PruneSegment, Receipt, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, StorageKey, StorageValue, TransactionMeta,
-    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, B256, MAINNET, U256,
+    ChainInfo, ChainSpec, Header, InternalTransfer, PruneCheckpoint, PruneSegment, Receipt,
+    SealedBlock, SealedBlockWithSenders, SealedHeader, StorageEntry, StorageKey, StorageValue,
+    TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, B256, MAINNET,
+    U256,
 };
 use reth_trie::updates::TrieUpdates;
 use revm::primitives::{BlockEnv, CfgEnv};
@@ -280,6 +282,13 @@ impl ChangeSetReader for NoopProvider {
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         Ok(Vec::default())
     }
+
+    fn storage_block_changeset(
+        &self,
+        _block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        Ok(Vec::default())
+    }
 }
 
 impl StateRootProvider for NoopProvider {
@@ -293,6 +302,14 @@ impl StateRootProvider for NoopProvider {
     ) -> ProviderResult<(B256, TrieUpdates)> {
         Ok((B256::default(), TrieUpdates::default()))
     }
+
+    fn storage_root(
+        &self,
+        _address: Address,
+        _bundle_state: &BundleStateWithReceipts,
+    ) -> ProviderResult<B256> {
+        Ok(B256::default())
+    }
 }
 
 impl StateProvider for NoopProvider {
@@ -413,6 +430,16 @@ impl WithdrawalsProvider for NoopProvider {
     }
 }
 
+impl WithdrawalHistoryReader for NoopProvider {
+    fn withdrawals_by_address(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, reth_primitives::Withdrawal)>> {
+        Ok(Vec::new())
+    }
+}
+
 impl PruneCheckpointReader for NoopProvider {
     fn get_prune_checkpoint(
         &self,
@@ -421,3 +448,49 @@ impl PruneCheckpointReader for NoopProvider {
         Ok(None)
     }
 }
+
+impl InternalTransfersReader for NoopProvider {
+    fn internal_transfers_by_block_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<InternalTransfer>> {
+        Ok(Vec::new())
+    }
+
+    fn internal_transfers_by_address(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<InternalTransfer>> {
+        Ok(Vec::new())
+    }
+}
+
+impl ContractCreationsReader for NoopProvider {
+    fn contract_creation(
+        &self,
+        _address: Address,
+    ) -> ProviderResult<Option<StoredContractCreation>> {
+        Ok(None)
+    }
+}
+
+impl AddressAppearancesReader for NoopProvider {
+    fn address_appearances_after(
+        &self,
+        _address: Address,
+        _start: TxNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn address_appearances_before(
+        &self,
+        _address: Address,
+        _end: TxNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::default())
+    }
+}
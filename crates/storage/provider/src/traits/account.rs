@@ -1,7 +1,7 @@
 use auto_impl::auto_impl;
 use reth_db::models::AccountBeforeTx;
 use reth_interfaces::provider::ProviderResult;
-use reth_primitives::{Account, Address, BlockNumber};
+use reth_primitives::{Account, Address, BlockNumber, StorageEntry};
 use std::{
     collections::{BTreeMap, BTreeSet},
     ops::{RangeBounds, RangeInclusive},
@@ -52,4 +52,36 @@ pub trait ChangeSetReader: Send + Sync {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>>;
+
+    /// Iterate over storage changesets and return the storage slots from before this block,
+    /// together with the address of their account.
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>>;
+
+    /// Get the numbers of the blocks in the range that changed the account, in ascending order.
+    ///
+    /// Only the blocks in the account history index are returned, none if the provider has no
+    /// history index.
+    fn account_history_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+
+    /// Get the numbers of the blocks in the range that changed the storage of the account, in
+    /// ascending order.
+    ///
+    /// Only the blocks in the storage history index are returned, none if the provider has no
+    /// history index.
+    fn storage_history_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
 }
@@ -0,0 +1,57 @@
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Address, BlockNumber, TxNumber};
+use std::ops::RangeInclusive;
+
+/// Client trait for fetching the transactions in which addresses appear, see
+/// [`AddressAppearances`](reth_db::tables::AddressAppearances).
+///
+/// Addresses are only indexed if the node was configured to do so, otherwise no transactions are
+/// returned.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait AddressAppearancesReader: Send + Sync {
+    /// Get the numbers of the transactions in which the address appears, starting at the
+    /// transaction number, in ascending order.
+    ///
+    /// At most `limit` transaction numbers are returned, the next page starts after the last one.
+    fn address_appearances_after(
+        &self,
+        address: Address,
+        start: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
+
+    /// Get the numbers of the transactions in which the address appears, before the transaction
+    /// number, in descending order.
+    ///
+    /// At most `limit` transaction numbers are returned, the next page ends at the last one.
+    fn address_appearances_before(
+        &self,
+        address: Address,
+        end: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
+}
+
+/// Address appearances index writer
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait AddressAppearancesWriter: Send + Sync {
+    /// Index the addresses that appear in the transactions of the blocks in the range, which must
+    /// directly follow the already indexed blocks.
+    ///
+    /// Returns the number of indexed appearances.
+    fn insert_address_appearances(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
+
+    /// Remove the appearances in the transactions of the blocks in the range from the index.
+    ///
+    /// The blocks are read to find the addresses, so they must be unwound before the blocks and
+    /// their receipts are removed.
+    ///
+    /// Returns the number of unwound appearances.
+    fn unwind_address_appearances(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
+}
@@ -285,11 +285,11 @@ pub trait BlockExecutionWriter: BlockWriter + BlockReader + Send + Sync {
 /// Block Writer
 #[auto_impl(&, Arc, Box)]
 pub trait BlockWriter: Send + Sync {
-    /// Insert full block and make it canonical. Parent tx num and transition id is taken from
-    /// parent block in database.
+    /// Insert full block and make it canonical. Parent tx num is taken from parent block in
+    /// database.
     ///
-    /// Return [StoredBlockBodyIndices] that contains indices of the first and last transactions and
-    /// transition in the block.
+    /// Return [StoredBlockBodyIndices] that contains indices of the first and last transactions in
+    /// the block.
     fn insert_block(
         &self,
         block: SealedBlockWithSenders,
@@ -74,29 +74,38 @@ pub trait BlockIdReader: BlockNumReader + Send + Sync {
     }
 
     /// Get the hash of the block by matching the given id.
+    ///
+    /// The `safe` and `finalized` tags resolve to the hashes of the latest forkchoice update, so
+    /// they also resolve if the block isn't persisted yet.
     fn block_hash_for_id(&self, block_id: BlockId) -> ProviderResult<Option<B256>> {
         match block_id {
             BlockId::Hash(hash) => Ok(Some(hash.into())),
-            BlockId::Number(num) => {
-                if matches!(num, BlockNumberOrTag::Latest) {
-                    return Ok(Some(self.chain_info()?.best_hash))
-                }
-
-                if matches!(num, BlockNumberOrTag::Pending) {
-                    return self
-                        .pending_block_num_hash()
-                        .map(|res_opt| res_opt.map(|num_hash| num_hash.hash))
-                }
-
-                self.convert_block_number(num)?
+            BlockId::Number(num) => match num {
+                BlockNumberOrTag::Latest => Ok(Some(self.chain_info()?.best_hash)),
+                BlockNumberOrTag::Pending => self
+                    .pending_block_num_hash()
+                    .map(|res_opt| res_opt.map(|num_hash| num_hash.hash)),
+                BlockNumberOrTag::Finalized => match self.finalized_block_hash()? {
+                    Some(hash) => Ok(Some(hash)),
+                    None => Err(ProviderError::FinalizedBlockNotFound),
+                },
+                BlockNumberOrTag::Safe => match self.safe_block_hash()? {
+                    Some(hash) => Ok(Some(hash)),
+                    None => Err(ProviderError::SafeBlockNotFound),
+                },
+                BlockNumberOrTag::Earliest | BlockNumberOrTag::Number(_) => self
+                    .convert_block_number(num)?
                     .map(|num| self.block_hash(num))
                     .transpose()
-                    .map(|maybe_hash| maybe_hash.flatten())
-            }
+                    .map(|maybe_hash| maybe_hash.flatten()),
+            },
         }
     }
 
     /// Get the number of the block by matching the given id.
+    ///
+    /// This is the entrypoint for resolving a [BlockId] to a block number, the tags are resolved
+    /// like in [BlockIdReader::convert_block_number].
     fn block_number_for_id(&self, block_id: BlockId) -> ProviderResult<Option<BlockNumber>> {
         match block_id {
             BlockId::Hash(hash) => self.block_number(hash.into()),
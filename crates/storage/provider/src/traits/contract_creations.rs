@@ -0,0 +1,41 @@
+use reth_db::models::StoredContractCreation;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Address, BlockNumber};
+use std::ops::RangeInclusive;
+
+/// Client trait for fetching the transactions that created contracts.
+///
+/// This includes the contracts created by other contracts, the index is populated by an inspector
+/// while blocks are executed.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ContractCreationsReader: Send + Sync {
+    /// Get the transaction and the address that created the contract at the address.
+    ///
+    /// If a contract was created at the same address more than once, the first creation is
+    /// returned.
+    fn contract_creation(&self, address: Address)
+        -> ProviderResult<Option<StoredContractCreation>>;
+}
+
+/// Contract creations index writer
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait ContractCreationsWriter: Send + Sync {
+    /// Index the contracts with their creations.
+    ///
+    /// Contracts that are already indexed keep their first creation.
+    fn insert_contract_creations(
+        &self,
+        creations: impl IntoIterator<Item = (Address, StoredContractCreation)>,
+    ) -> ProviderResult<()>;
+
+    /// Remove the contracts created in the blocks of the range from the index.
+    ///
+    /// The created contracts are taken from the account changesets of the blocks, so this has to
+    /// be called before they're removed.
+    ///
+    /// Returns the number of removed contracts.
+    fn unwind_contract_creations(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
+}
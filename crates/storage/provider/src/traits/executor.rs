@@ -78,6 +78,8 @@ pub trait PrunableBlockExecutor: BlockExecutor {
 /// Block execution statistics. Contains duration of each step of block execution.
 #[derive(Clone, Debug, Default)]
 pub struct BlockExecutorStats {
+    /// Time needed to load the accounts and storage slots in the access lists of the transactions.
+    pub prewarm_duration: Duration,
     /// Execution duration.
     pub execution_duration: Duration,
     /// Time needed to apply output of revm execution to revm cached state.
@@ -96,6 +98,7 @@ impl BlockExecutorStats {
     pub fn log_info(&self) {
         debug!(
             target: "evm",
+            prewarm = ?self.prewarm_duration,
             evm_transact = ?self.execution_duration,
             apply_state = ?self.apply_state_duration,
             apply_post_state = ?self.apply_post_execution_state_changes_duration,
@@ -1,6 +1,9 @@
+use crate::LogsBloomLevel;
 use auto_impl::auto_impl;
 use reth_interfaces::provider::ProviderResult;
-use reth_primitives::{BlockHash, BlockHashOrNumber, BlockNumber, Header, SealedHeader, U256};
+use reth_primitives::{
+    BlockHash, BlockHashOrNumber, BlockNumber, Bloom, Header, SealedHeader, U256,
+};
 use std::ops::RangeBounds;
 
 /// Client trait for fetching `Header` related data.
@@ -54,4 +57,15 @@ pub trait HeaderProvider: Send + Sync {
         range: impl RangeBounds<BlockNumber>,
         predicate: impl FnMut(&SealedHeader) -> bool,
     ) -> ProviderResult<Vec<SealedHeader>>;
+
+    /// Get the combined logs bloom of the headers in the block range with the index at the level.
+    ///
+    /// Returns `None` if the range isn't indexed.
+    fn range_logs_bloom(
+        &self,
+        _level: LogsBloomLevel,
+        _index: u64,
+    ) -> ProviderResult<Option<Bloom>> {
+        Ok(None)
+    }
 }
@@ -38,6 +38,23 @@ pub trait HistoryWriter: Send + Sync {
         storage_transitions: BTreeMap<(Address, B256), Vec<u64>>,
     ) -> ProviderResult<()>;
 
+    /// Unwind and clear the withdrawal history indices of the blocks in the range.
+    ///
+    /// The withdrawals of the blocks are read to find the recipients, so this has to be called
+    /// before they are removed.
+    ///
+    /// Returns number of withdrawals walked.
+    fn unwind_withdrawal_history_indices(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
+
+    /// Insert withdrawal recipient index to database. Used when block bodies are inserted.
+    fn insert_withdrawal_history_index(
+        &self,
+        withdrawal_transitions: BTreeMap<Address, Vec<u64>>,
+    ) -> ProviderResult<()>;
+
     /// Read account/storage changesets and update account/storage history indices.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
@@ -0,0 +1,24 @@
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Address, BlockNumber, InternalTransfer};
+use std::ops::RangeInclusive;
+
+/// Client trait for fetching the indexed [InternalTransfer]s.
+///
+/// Internal transfers are only indexed if the node was configured to do so, otherwise no transfers
+/// are returned.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait InternalTransfersReader: Send + Sync {
+    /// Get the internal transfers of the blocks in the range, in the order they were made.
+    fn internal_transfers_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<InternalTransfer>>;
+
+    /// Get the internal transfers of the blocks in the range that were sent from or to the
+    /// address, in the order they were made.
+    fn internal_transfers_by_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<InternalTransfer>>;
+}
@@ -0,0 +1,59 @@
+use auto_impl::auto_impl;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::BlockNumber;
+use std::ops::RangeInclusive;
+
+/// A level of the logs bloom index.
+///
+/// Each level stores the combined logs bloom of consecutive block ranges of a fixed size, so log
+/// queries can skip all blocks of a range whose bloom doesn't match. The range with index `i`
+/// contains the blocks `i * size..=(i + 1) * size - 1`.
+///
+/// Only complete ranges are indexed, the blocks of the range at the tip of the chain have to be
+/// checked individually.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogsBloomLevel {
+    /// Ranges of 4096 blocks, see [`LogsBlooms4K`](reth_db::tables::LogsBlooms4K).
+    Blocks4K,
+    /// Ranges of 65536 blocks, see [`LogsBlooms64K`](reth_db::tables::LogsBlooms64K).
+    Blocks64K,
+}
+
+impl LogsBloomLevel {
+    /// All levels, from the largest to the smallest ranges.
+    pub const ALL: [LogsBloomLevel; 2] = [LogsBloomLevel::Blocks64K, LogsBloomLevel::Blocks4K];
+
+    /// Returns the number of blocks in a range of this level.
+    pub const fn range_size(&self) -> u64 {
+        match self {
+            LogsBloomLevel::Blocks4K => 1 << 12,
+            LogsBloomLevel::Blocks64K => 1 << 16,
+        }
+    }
+
+    /// Returns the index of the range that contains the block.
+    pub const fn range_index(&self, block_number: BlockNumber) -> u64 {
+        block_number / self.range_size()
+    }
+
+    /// Returns the blocks of the range with the index.
+    pub const fn block_range(&self, index: u64) -> RangeInclusive<BlockNumber> {
+        index * self.range_size()..=(index + 1) * self.range_size() - 1
+    }
+}
+
+/// Logs bloom index writer
+#[auto_impl(&, Arc, Box)]
+pub trait LogsBloomIndexWriter: Send + Sync {
+    /// Index the logs blooms of the blocks in the range, which must directly follow the already
+    /// indexed blocks.
+    ///
+    /// The bloom of each range of a [LogsBloomLevel] is written once its last block is indexed.
+    fn insert_logs_bloom_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
+
+    /// Unwind the logs bloom index of the blocks in the range, which must be the highest indexed
+    /// blocks.
+    ///
+    /// Removes the blooms of all ranges that contain one of the blocks.
+    fn unwind_logs_bloom_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
+}
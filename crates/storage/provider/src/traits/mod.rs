@@ -35,15 +35,24 @@ pub use receipts::{ReceiptProvider, ReceiptProviderIdExt};
 
 mod state;
 pub use state::{
-    BlockchainTreePendingStateProvider, BundleStateDataProvider, StateProvider, StateProviderBox,
-    StateProviderFactory, StateRootProvider,
+    BlockchainTreePendingStateProvider, BundleStateDataProvider, PreloadedState, StateProvider,
+    StateProviderBox, StateProviderFactory, StateRootProvider,
 };
 
 mod transactions;
 pub use transactions::{TransactionsProvider, TransactionsProviderExt};
 
 mod withdrawals;
-pub use withdrawals::WithdrawalsProvider;
+pub use withdrawals::{WithdrawalHistoryReader, WithdrawalsProvider};
+
+mod internal_transfers;
+pub use internal_transfers::InternalTransfersReader;
+
+mod contract_creations;
+pub use contract_creations::{ContractCreationsReader, ContractCreationsWriter};
+
+mod address_appearances;
+pub use address_appearances::{AddressAppearancesReader, AddressAppearancesWriter};
 
 mod executor;
 pub use executor::{BlockExecutor, BlockExecutorStats, ExecutorFactory, PrunableBlockExecutor};
@@ -66,5 +75,8 @@ pub use hashing::HashingWriter;
 mod history;
 pub use history::HistoryWriter;
 
+mod logs_bloom;
+pub use logs_bloom::{LogsBloomIndexWriter, LogsBloomLevel};
+
 mod prune_checkpoint;
 pub use prune_checkpoint::{PruneCheckpointReader, PruneCheckpointWriter};
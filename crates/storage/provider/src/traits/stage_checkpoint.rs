@@ -12,6 +12,16 @@ pub trait StageCheckpointReader: Send + Sync {
 
     /// Get stage checkpoint progress.
     fn get_stage_checkpoint_progress(&self, id: StageId) -> ProviderResult<Option<Vec<u8>>>;
+
+    /// Fetch the checkpoints of all stages, in the order of [StageId::ALL].
+    ///
+    /// Stages without a checkpoint have the default checkpoint.
+    fn get_stage_checkpoints(&self) -> ProviderResult<Vec<(StageId, StageCheckpoint)>> {
+        StageId::ALL
+            .into_iter()
+            .map(|id| Ok((id, self.get_stage_checkpoint(id)?.unwrap_or_default())))
+            .collect()
+    }
 }
 
 /// The trait for updating stage checkpoint related data.
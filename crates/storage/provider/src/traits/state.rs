@@ -3,10 +3,11 @@ use crate::{BlockHashReader, BlockIdReader, BundleStateWithReceipts};
 use auto_impl::auto_impl;
 use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_primitives::{
-    trie::AccountProof, Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag,
-    Bytecode, StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
+    trie::AccountProof, Account, Address, BlockHash, BlockId, BlockNumHash, BlockNumber,
+    BlockNumberOrTag, Bytecode, StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
 };
 use reth_trie::updates::TrieUpdates;
+use std::collections::HashMap;
 
 /// Type alias of boxed [StateProvider].
 pub type StateProviderBox = Box<dyn StateProvider>;
@@ -27,6 +28,40 @@ pub trait StateProvider: BlockHashReader + AccountReader + StateRootProvider + S
     /// Get account and storage proofs.
     fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof>;
 
+    /// Get account and storage proofs for multiple accounts.
+    ///
+    /// Implementations may share work between the proofs. The default implementation generates
+    /// them one by one.
+    fn proofs(&self, targets: &[(Address, Vec<B256>)]) -> ProviderResult<Vec<AccountProof>> {
+        targets.iter().map(|(address, keys)| self.proof(*address, keys)).collect()
+    }
+
+    /// Loads the given accounts and storage slots, and the bytecodes of the loaded accounts, ahead
+    /// of executing on top of the state.
+    ///
+    /// Implementations may load more keys they expect to be accessed. The default implementation
+    /// looks up the given keys one by one.
+    fn preload(
+        &self,
+        accounts: &[Address],
+        slots: &[(Address, B256)],
+    ) -> ProviderResult<PreloadedState> {
+        let mut state = PreloadedState::default();
+        for &address in accounts {
+            state.accounts.insert(address, self.basic_account(address)?);
+        }
+        for &(address, slot) in slots {
+            let value = self.storage(address, slot)?.unwrap_or_default();
+            state.storage.entry(address).or_default().insert(slot, value);
+        }
+        for code_hash in state.code_hashes() {
+            if let Some(bytecode) = self.bytecode_by_hash(code_hash)? {
+                state.bytecodes.insert(code_hash, bytecode);
+            }
+        }
+        Ok(state)
+    }
+
     /// Get account code by its address.
     ///
     /// Returns `None` if the account doesn't exist or account is not a contract
@@ -75,6 +110,33 @@ pub trait StateProvider: BlockHashReader + AccountReader + StateRootProvider + S
     }
 }
 
+/// State loaded in one batch by [StateProvider::preload].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PreloadedState {
+    /// The accounts by address, [None] if the account doesn't exist.
+    pub accounts: HashMap<Address, Option<Account>>,
+    /// The storage values by address and slot.
+    pub storage: HashMap<Address, HashMap<B256, U256>>,
+    /// The bytecodes of the loaded accounts by code hash.
+    pub bytecodes: HashMap<B256, Bytecode>,
+}
+
+impl PreloadedState {
+    /// Returns the sorted code hashes of the loaded accounts that have code.
+    pub fn code_hashes(&self) -> Vec<B256> {
+        let mut code_hashes = self
+            .accounts
+            .values()
+            .flatten()
+            .filter_map(|account| account.bytecode_hash)
+            .filter(|code_hash| *code_hash != KECCAK_EMPTY)
+            .collect::<Vec<_>>();
+        code_hashes.sort_unstable();
+        code_hashes.dedup();
+        code_hashes
+    }
+}
+
 /// Light wrapper that returns `StateProvider` implementations that correspond to the given
 /// `BlockNumber`, the latest state, or the pending state.
 ///
@@ -243,4 +305,12 @@ pub trait StateRootProvider: Send + Sync {
         &self,
         bundle_state: &BundleStateWithReceipts,
     ) -> ProviderResult<(B256, TrieUpdates)>;
+
+    /// Returns the storage root of the account with the given address after the `BundleState` is
+    /// applied on top of the current state.
+    fn storage_root(
+        &self,
+        address: Address,
+        bundle_state: &BundleStateWithReceipts,
+    ) -> ProviderResult<B256>;
 }
@@ -2,7 +2,7 @@ use crate::{BlockNumReader, BlockReader};
 use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_primitives::{
     Address, BlockHashOrNumber, BlockNumber, TransactionMeta, TransactionSigned,
-    TransactionSignedNoHash, TxHash, TxNumber,
+    TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber,
 };
 use std::ops::{Range, RangeBounds, RangeInclusive};
 
@@ -61,6 +61,36 @@ pub trait TransactionsProvider: BlockNumReader + Send + Sync {
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>>;
 
+    /// Get transactions with their hashes and senders by tx range.
+    ///
+    /// The senders are read in one batch and only recovered from the signatures if they are
+    /// missing, e.g. because they were pruned.
+    fn transactions_with_senders_by_tx_range(
+        &self,
+        range: Range<TxNumber>,
+    ) -> ProviderResult<Vec<TransactionSignedEcRecovered>> {
+        if range.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        let transactions =
+            TransactionSignedNoHash::with_hashes(self.transactions_by_tx_range(range.clone())?);
+        let mut senders = self.senders_by_tx_range(range)?;
+        if senders.len() != transactions.len() {
+            // Note: we're using unchecked here because the transactions are already part of the
+            // chain, so pre EIP-2 transactions are allowed
+            senders =
+                TransactionSigned::recover_signers_unchecked(&transactions, transactions.len())
+                    .ok_or(ProviderError::SenderRecoveryError)?;
+        }
+
+        Ok(transactions
+            .into_iter()
+            .zip(senders)
+            .map(|(tx, sender)| tx.with_signer(sender))
+            .collect())
+    }
+
     /// Get transaction sender.
     ///
     /// Returns None if the transaction is not found.
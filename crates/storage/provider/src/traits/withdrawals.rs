@@ -1,5 +1,6 @@
 use reth_interfaces::provider::ProviderResult;
-use reth_primitives::{BlockHashOrNumber, Withdrawal};
+use reth_primitives::{Address, BlockHashOrNumber, BlockNumber, Withdrawal};
+use std::ops::RangeInclusive;
 
 ///  Client trait for fetching [Withdrawal] related data.
 #[auto_impl::auto_impl(&, Arc)]
@@ -14,3 +15,15 @@ pub trait WithdrawalsProvider: Send + Sync {
     /// Get latest withdrawal from this block or earlier .
     fn latest_withdrawal(&self) -> ProviderResult<Option<Withdrawal>>;
 }
+
+/// Client trait for fetching [Withdrawal]s by their recipient, using the withdrawal history index.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait WithdrawalHistoryReader: Send + Sync {
+    /// Get the withdrawals to the address in the blocks in the range, with the number of the block
+    /// that included them, in the order they were made.
+    fn withdrawals_by_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Withdrawal)>>;
+}
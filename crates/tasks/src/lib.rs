@@ -10,22 +10,20 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 use crate::{
-    metrics::{IncCounterOnDrop, TaskExecutorMetrics},
-    shutdown::{signal, GracefulShutdown, GracefulShutdownGuard, Shutdown, Signal},
+    metrics::{IncCounterOnDrop, TaskExecutorMetrics, TaskMetrics},
+    shutdown::{signal, GracefulShutdown, GracefulShutdownGuard, GracefulTasks, Shutdown, Signal},
 };
 use dyn_clone::DynClone;
 use futures_util::{
-    future::{select, BoxFuture},
-    pin_mut, Future, FutureExt, TryFutureExt,
+    future::{select, BoxFuture, Either},
+    pin_mut, Future, FutureExt,
 };
 use std::{
     any::Any,
     fmt::{Display, Formatter},
+    panic::AssertUnwindSafe,
     pin::Pin,
-    sync::{
-        atomic::{AtomicUsize, Ordering},
-        Arc,
-    },
+    sync::Arc,
     task::{ready, Context, Poll},
 };
 use tokio::{
@@ -86,6 +84,14 @@ pub trait TaskSpawner: Send + Sync + Unpin + std::fmt::Debug + DynClone {
     /// This spawns a critical task onto the runtime.
     fn spawn_critical(&self, name: &'static str, fut: BoxFuture<'static, ()>) -> JoinHandle<()>;
 
+    /// Spawns a named task onto the runtime.
+    ///
+    /// Unlike a critical task, a panic of the task doesn't affect other tasks. By default, the name
+    /// is ignored and the task is spawned with [TaskSpawner::spawn].
+    fn spawn_named(&self, _name: &'static str, fut: BoxFuture<'static, ()>) -> JoinHandle<()> {
+        self.spawn(fut)
+    }
+
     /// Spawns a blocking task onto the runtime.
     fn spawn_blocking(&self, fut: BoxFuture<'static, ()>) -> JoinHandle<()>;
 
@@ -138,6 +144,9 @@ impl TaskSpawner for TokioTaskExecutor {
 /// diagnostic purposes, since tokio task essentially fail silently. Therefore, this type is a
 /// Stream that yields the name of panicked task, See [`TaskExecutor::spawn_critical`]. In order to
 /// execute Tasks use the [`TaskExecutor`] type [`TaskManager::executor`].
+///
+/// Subsystems can get their own [`TaskManager`] with [`TaskExecutor::child_manager`], which
+/// contains the panics of their critical tasks and shuts down their tasks together.
 #[derive(Debug)]
 #[must_use = "TaskManager must be polled to monitor critical tasks"]
 pub struct TaskManager {
@@ -156,7 +165,7 @@ pub struct TaskManager {
     /// Receiver of the shutdown signal.
     on_shutdown: Shutdown,
     /// How many [GracefulShutdown] tasks are currently active
-    graceful_tasks: Arc<AtomicUsize>,
+    graceful_tasks: Arc<GracefulTasks>,
 }
 
 // === impl TaskManager ===
@@ -182,7 +191,32 @@ impl TaskManager {
             panicked_tasks_rx,
             signal: Some(signal),
             on_shutdown,
-            graceful_tasks: Arc::new(AtomicUsize::new(0)),
+            graceful_tasks: Default::default(),
+        }
+    }
+
+    /// Creates a new instance whose tasks are also shut down when the tasks of the given executor
+    /// are shut down.
+    fn child(parent: &TaskExecutor) -> Self {
+        let (panicked_tasks_tx, panicked_tasks_rx) = unbounded_channel();
+        // fires when the child is dropped
+        let (signal, on_drop) = signal();
+        let (child_signal, on_shutdown) = signal();
+        let parent_shutdown = parent.on_shutdown.clone();
+        parent.handle.spawn(async move {
+            let _ = select(parent_shutdown, on_drop).await;
+            child_signal.fire();
+        });
+
+        Self {
+            handle: parent.handle.clone(),
+            panicked_tasks_tx,
+            panicked_tasks_rx,
+            signal: Some(signal),
+            on_shutdown,
+            graceful_tasks: Arc::new(GracefulTasks::with_parent(Arc::clone(
+                &parent.graceful_tasks,
+            ))),
         }
     }
 
@@ -213,7 +247,7 @@ impl TaskManager {
     fn do_graceful_shutdown(self, timeout: Option<std::time::Duration>) -> bool {
         drop(self.signal);
         let when = timeout.map(|t| std::time::Instant::now() + t);
-        while self.graceful_tasks.load(Ordering::Relaxed) > 0 {
+        while self.graceful_tasks.active() > 0 {
             if when.map(|when| std::time::Instant::now() > when).unwrap_or(false) {
                 debug!("graceful shutdown timed out");
                 return false
@@ -268,6 +302,11 @@ impl PanickedTaskError {
 
         Self { task_name, error }
     }
+
+    /// Returns the name of the task that panicked.
+    pub fn task_name(&self) -> &'static str {
+        self.task_name
+    }
 }
 
 /// A type that can spawn new tokio tasks
@@ -284,7 +323,7 @@ pub struct TaskExecutor {
     // Task Executor Metrics
     metrics: TaskExecutorMetrics,
     /// How many [GracefulShutdown] tasks are currently active
-    graceful_tasks: Arc<AtomicUsize>,
+    graceful_tasks: Arc<GracefulTasks>,
 }
 
 // === impl TaskExecutor ===
@@ -300,6 +339,77 @@ impl TaskExecutor {
         &self.on_shutdown
     }
 
+    /// Returns a new [TaskManager] for a subsystem, whose tasks form a subtree of the tasks of
+    /// this executor.
+    ///
+    /// The tasks of the child are shut down when the child is dropped, or when the tasks of this
+    /// executor are shut down. A graceful shutdown of this executor's manager also waits for the
+    /// graceful tasks of the child.
+    ///
+    /// Panics of critical tasks spawned by the child are only reported to the child, so its owner
+    /// decides whether the failure of the subsystem is fatal: for example,
+    /// [TaskExecutor::forward_panics] reports them to the manager of this executor, while
+    /// dropping the child shuts down the remaining tasks of the subsystem only.
+    pub fn child_manager(&self) -> TaskManager {
+        TaskManager::child(self)
+    }
+
+    /// Spawns a task that owns the given child [TaskManager] and reports the panic of one of its
+    /// critical tasks to the [TaskManager] of this executor, as if a critical task of this
+    /// executor panicked.
+    ///
+    /// The task resolves once the panic is reported or the [Shutdown] signal is received.
+    pub fn forward_panics(&self, child: TaskManager) -> JoinHandle<()> {
+        let on_shutdown = self.on_shutdown.clone();
+        let panicked_tasks_tx = self.panicked_tasks_tx.clone();
+        let task = async move {
+            if let Either::Right((err, _)) = select(on_shutdown, child).await {
+                let _ = panicked_tasks_tx.send(err);
+            }
+        };
+
+        self.handle.spawn(task.in_current_span())
+    }
+
+    /// Wraps the task to record the [TaskMetrics] of the given task name and catch its panic.
+    ///
+    /// The panic of a critical task is reported to the [TaskManager], the panic of a regular task
+    /// is only logged.
+    fn monitor_task<F>(
+        &self,
+        name: &'static str,
+        fut: F,
+        critical: bool,
+    ) -> impl Future<Output = ()> + Send + 'static
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let metrics = TaskMetrics::for_task(name);
+        metrics.spawned.increment(1);
+        let finished = IncCounterOnDrop::new(metrics.finished.clone());
+        let panicked_tasks = self.metrics.panicked_tasks.clone();
+        let panicked_tasks_tx = critical.then(|| self.panicked_tasks_tx.clone());
+
+        // wrap the task in catch unwind
+        AssertUnwindSafe(fut)
+            .catch_unwind()
+            .map(move |result| {
+                let _finished = finished;
+                let Err(error) = result else { return };
+                metrics.panicked.increment(1);
+                panicked_tasks.increment(1);
+
+                let task_error = PanickedTaskError::new(name, error);
+                if let Some(panicked_tasks_tx) = panicked_tasks_tx {
+                    error!("{task_error}");
+                    let _ = panicked_tasks_tx.send(task_error);
+                } else {
+                    error!(task = name, error = ?task_error.error, "Task panicked");
+                }
+            })
+            .in_current_span()
+    }
+
     /// Spawns a future on the tokio runtime depending on the [TaskKind]
     fn spawn_on_rt<F>(&self, fut: F, task_kind: TaskKind) -> JoinHandle<()>
     where
@@ -359,6 +469,18 @@ impl TaskExecutor {
         self.spawn_task_as(fut, TaskKind::Blocking)
     }
 
+    /// Spawns a named task onto the runtime.
+    /// The given future resolves as soon as the [Shutdown] signal is received.
+    ///
+    /// Unlike [TaskExecutor::spawn_critical], a panic of this task is only logged and recorded in
+    /// the [TaskMetrics] of the task, the [TaskManager] isn't notified.
+    pub fn spawn_named<F>(&self, name: &'static str, fut: F) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_task_as(self.monitor_task(name, fut, false), TaskKind::Default)
+    }
+
     /// Spawns the task onto the runtime.
     /// The given future resolves as soon as the [Shutdown] signal is received.
     ///
@@ -385,18 +507,8 @@ impl TaskExecutor {
     where
         F: Future<Output = ()> + Send + 'static,
     {
-        let panicked_tasks_tx = self.panicked_tasks_tx.clone();
         let on_shutdown = self.on_shutdown.clone();
-
-        // wrap the task in catch unwind
-        let task = std::panic::AssertUnwindSafe(fut)
-            .catch_unwind()
-            .map_err(move |error| {
-                let task_error = PanickedTaskError::new(name, error);
-                error!("{task_error}");
-                let _ = panicked_tasks_tx.send(task_error);
-            })
-            .in_current_span();
+        let task = self.monitor_task(name, fut, true);
 
         // Clone only the specific counter that we need.
         let finished_critical_tasks_metrics = self.metrics.finished_critical_tasks.clone();
@@ -443,20 +555,10 @@ impl TaskExecutor {
     where
         F: Future<Output = ()> + Send + 'static,
     {
-        let panicked_tasks_tx = self.panicked_tasks_tx.clone();
         let on_shutdown = self.on_shutdown.clone();
         let fut = f(on_shutdown);
 
-        // wrap the task in catch unwind
-        let task = std::panic::AssertUnwindSafe(fut)
-            .catch_unwind()
-            .map_err(move |error| {
-                let task_error = PanickedTaskError::new(name, error);
-                error!("{task_error}");
-                let _ = panicked_tasks_tx.send(task_error);
-            })
-            .map(|_| ())
-            .in_current_span();
+        let task = self.monitor_task(name, fut, true);
 
         self.handle.spawn(task)
     }
@@ -489,23 +591,13 @@ impl TaskExecutor {
     where
         F: Future<Output = ()> + Send + 'static,
     {
-        let panicked_tasks_tx = self.panicked_tasks_tx.clone();
         let on_shutdown = GracefulShutdown::new(
             self.on_shutdown.clone(),
             GracefulShutdownGuard::new(Arc::clone(&self.graceful_tasks)),
         );
         let fut = f(on_shutdown);
 
-        // wrap the task in catch unwind
-        let task = std::panic::AssertUnwindSafe(fut)
-            .catch_unwind()
-            .map_err(move |error| {
-                let task_error = PanickedTaskError::new(name, error);
-                error!("{task_error}");
-                let _ = panicked_tasks_tx.send(task_error);
-            })
-            .map(|_| ())
-            .in_current_span();
+        let task = self.monitor_task(name, fut, true);
 
         self.handle.spawn(task)
     }
@@ -522,6 +614,11 @@ impl TaskSpawner for TaskExecutor {
         TaskExecutor::spawn_critical(self, name, fut)
     }
 
+    fn spawn_named(&self, name: &'static str, fut: BoxFuture<'static, ()>) -> JoinHandle<()> {
+        self.metrics.inc_regular_tasks();
+        TaskExecutor::spawn_named(self, name, fut)
+    }
+
     fn spawn_blocking(&self, fut: BoxFuture<'static, ()>) -> JoinHandle<()> {
         self.spawn_blocking(fut)
     }
@@ -574,7 +671,10 @@ enum TaskKind {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::{sync::atomic::AtomicBool, time::Duration};
+    use std::{
+        sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+        time::Duration,
+    };
 
     #[test]
     fn test_cloneable() {
@@ -609,6 +709,93 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_named_task_panic_is_contained() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let mut manager = TaskManager::new(handle);
+        let executor = manager.executor();
+
+        let task = executor.spawn_named("named", async { panic!("intentionally panic") });
+
+        runtime.block_on(async move {
+            // the panic is caught in the task
+            assert!(task.await.is_ok());
+            assert!((&mut manager).now_or_never().is_none());
+        })
+    }
+
+    #[test]
+    fn test_child_manager() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let mut manager = TaskManager::new(handle.clone());
+        let executor = manager.executor();
+
+        let child = executor.child_manager();
+        child.executor().spawn_critical("child", async { panic!("intentionally panic") });
+
+        let (signal, shutdown) = signal();
+        child.executor().spawn(async move {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            drop(signal);
+        });
+
+        runtime.block_on(async move {
+            // the panic is only reported to the child
+            let err = child.await;
+            assert_eq!(err.task_name(), "child");
+            assert!((&mut manager).now_or_never().is_none());
+
+            // dropping the child shuts down its tasks
+            shutdown.await;
+        })
+    }
+
+    #[test]
+    fn test_forward_panics() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let manager = TaskManager::new(handle);
+        let executor = manager.executor();
+
+        let child = executor.child_manager();
+        let child_executor = child.executor();
+        executor.forward_panics(child);
+        child_executor.spawn_critical("child", async { panic!("intentionally panic") });
+
+        runtime.block_on(async move {
+            let err = manager.await;
+            assert_eq!(err.task_name(), "child");
+            assert_eq!(err.error, Some("intentionally panic".to_string()));
+        })
+    }
+
+    // Tests that the tasks of a child are terminated if the parent `TaskManager` drops
+    #[test]
+    fn test_child_manager_parent_shutdown() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let manager = TaskManager::new(handle.clone());
+        let child = manager.executor().child_manager();
+
+        let val = Arc::new(AtomicBool::new(false));
+        let c = val.clone();
+        child.executor().spawn_critical_with_graceful_shutdown_signal(
+            "grace",
+            |shutdown| async move {
+                let _guard = shutdown.await;
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                c.store(true, Ordering::Relaxed);
+            },
+        );
+
+        // the parent waits for the graceful tasks of the child
+        manager.graceful_shutdown();
+        assert!(val.load(Ordering::Relaxed));
+        drop(child);
+    }
+
     // Tests that spawned tasks are terminated if the `TaskManager` drops
     #[test]
     fn test_manager_shutdown_critical() {
@@ -16,6 +16,8 @@ pub struct TaskExecutorMetrics {
     pub(crate) regular_tasks: Counter,
     /// Number of finished spawned regular tasks
     pub(crate) finished_regular_tasks: Counter,
+    /// Number of spawned critical and named tasks that panicked
+    pub(crate) panicked_tasks: Counter,
 }
 
 impl TaskExecutorMetrics {
@@ -31,6 +33,25 @@ impl TaskExecutorMetrics {
     }
 }
 
+/// Metrics of the spawned named tasks, labeled by the name of the task
+#[derive(Metrics, Clone)]
+#[metrics(scope = "executor.task")]
+pub struct TaskMetrics {
+    /// Number of spawned tasks
+    pub(crate) spawned: Counter,
+    /// Number of finished tasks, including the ones that panicked or were shut down
+    pub(crate) finished: Counter,
+    /// Number of tasks that panicked
+    pub(crate) panicked: Counter,
+}
+
+impl TaskMetrics {
+    /// Returns the metrics of the tasks with the given name.
+    pub(crate) fn for_task(name: &'static str) -> Self {
+        Self::new_with_labels(&[("task", name)])
+    }
+}
+
 /// Helper type for increasing counters even if a task fails
 pub struct IncCounterOnDrop(Counter);
 
@@ -7,7 +7,10 @@ use futures_util::{
 use std::{
     future::Future,
     pin::Pin,
-    sync::{atomic::AtomicUsize, Arc},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     task::{ready, Context, Poll},
 };
 use tokio::sync::oneshot;
@@ -49,18 +52,54 @@ impl Clone for GracefulShutdown {
 /// [GracefulShutdown] has completed.
 #[derive(Debug)]
 #[must_use = "if unused the task will not be gracefully shutdown"]
-pub struct GracefulShutdownGuard(Arc<AtomicUsize>);
+pub struct GracefulShutdownGuard(Arc<GracefulTasks>);
 
 impl GracefulShutdownGuard {
-    pub(crate) fn new(counter: Arc<AtomicUsize>) -> Self {
-        counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-        Self(counter)
+    pub(crate) fn new(tasks: Arc<GracefulTasks>) -> Self {
+        tasks.increment();
+        Self(tasks)
     }
 }
 
 impl Drop for GracefulShutdownGuard {
     fn drop(&mut self) {
-        self.0.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+        self.0.decrement();
+    }
+}
+
+/// Counts the active [GracefulShutdown] tasks of a [TaskManager](crate::TaskManager).
+///
+/// The tasks of a child manager are also counted by its parents, so a graceful shutdown of the
+/// parent waits for them as well.
+#[derive(Debug, Default)]
+pub(crate) struct GracefulTasks {
+    active: AtomicUsize,
+    parent: Option<Arc<GracefulTasks>>,
+}
+
+impl GracefulTasks {
+    /// Creates a new counter for a child of the given counter.
+    pub(crate) fn with_parent(parent: Arc<GracefulTasks>) -> Self {
+        Self { active: AtomicUsize::new(0), parent: Some(parent) }
+    }
+
+    /// Returns the number of active tasks, including the tasks of the children.
+    pub(crate) fn active(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    fn increment(&self) {
+        self.active.fetch_add(1, Ordering::SeqCst);
+        if let Some(parent) = &self.parent {
+            parent.increment();
+        }
+    }
+
+    fn decrement(&self) {
+        self.active.fetch_sub(1, Ordering::SeqCst);
+        if let Some(parent) = &self.parent {
+            parent.decrement();
+        }
     }
 }
 
@@ -24,8 +24,8 @@ impl DiskFileBlobStore {
         opts: DiskFileBlobStoreConfig,
     ) -> Result<Self, DiskFileBlobStoreError> {
         let blob_dir = blob_dir.into();
-        let DiskFileBlobStoreConfig { max_cached_entries, .. } = opts;
-        let inner = DiskFileBlobStoreInner::new(blob_dir, max_cached_entries);
+        let DiskFileBlobStoreConfig { max_cached_entries, max_size, .. } = opts;
+        let inner = DiskFileBlobStoreInner::new(blob_dir, max_cached_entries, max_size);
 
         // initialize the blob store
         inner.delete_all()?;
@@ -50,6 +50,14 @@ impl BlobStore for DiskFileBlobStore {
         self.inner.insert_one(tx, data)
     }
 
+    fn ensure_capacity(
+        &self,
+        tx: B256,
+        data: &BlobTransactionSidecar,
+    ) -> Result<(), BlobStoreError> {
+        self.inner.ensure_capacity(tx, data.fields_len())
+    }
+
     fn insert_all(&self, txs: Vec<(B256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError> {
         if txs.is_empty() {
             return Ok(())
@@ -109,16 +117,20 @@ struct DiskFileBlobStoreInner {
     blob_dir: PathBuf,
     blob_cache: Mutex<LruMap<TxHash, BlobTransactionSidecar, ByLength>>,
     size_tracker: BlobStoreSize,
+    /// The maximum size of all blob files on disk, if limited.
+    max_size: Option<usize>,
     file_lock: RwLock<()>,
 }
 
 impl DiskFileBlobStoreInner {
-    /// Creates a new empty disk file blob store with the given maximum length of the blob cache.
-    fn new(blob_dir: PathBuf, max_length: u32) -> Self {
+    /// Creates a new empty disk file blob store with the given maximum length of the blob cache
+    /// and the given maximum size of the stored blob files.
+    fn new(blob_dir: PathBuf, max_length: u32, max_size: Option<usize>) -> Self {
         Self {
             blob_dir,
             blob_cache: Mutex::new(LruMap::new(ByLength::new(max_length))),
             size_tracker: Default::default(),
+            max_size,
             file_lock: Default::default(),
         }
     }
@@ -144,6 +156,7 @@ impl DiskFileBlobStoreInner {
     fn insert_one(&self, tx: B256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
         let mut buf = Vec::with_capacity(data.fields_len());
         data.encode(&mut buf);
+        self.ensure_capacity(tx, buf.len())?;
         self.blob_cache.lock().insert(tx, data);
         let size = self.write_one_encoded(tx, &buf)?;
 
@@ -162,6 +175,13 @@ impl DiskFileBlobStoreInner {
             })
             .collect::<Vec<_>>();
 
+        // reject the entire batch if it doesn't fit
+        let mut size = 0;
+        for ((tx, _), (_, data)) in txs.iter().zip(&raw) {
+            size += data.len();
+            self.ensure_capacity(*tx, size)?;
+        }
+
         {
             let mut cache = self.blob_cache.lock();
             for (tx, data) in txs {
@@ -187,6 +207,19 @@ impl DiskFileBlobStoreInner {
         Ok(())
     }
 
+    /// Returns an error if storing `size` additional bytes would exceed the size limit of the
+    /// store.
+    #[inline]
+    fn ensure_capacity(&self, tx: B256, size: usize) -> Result<(), BlobStoreError> {
+        if let Some(max_size) = self.max_size {
+            if self.size_tracker.data_size() + size > max_size {
+                debug!(target:"txpool::blob", ?tx, size, max_size, "Blob store is full");
+                return Err(BlobStoreError::Full(tx))
+            }
+        }
+        Ok(())
+    }
+
     /// Returns true if the blob for the given transaction hash is in the blob cache or on disk.
     fn contains(&self, tx: B256) -> Result<bool, BlobStoreError> {
         if self.blob_cache.lock().get(&tx).is_some() {
@@ -279,34 +312,59 @@ impl DiskFileBlobStoreInner {
         Ok(data.len())
     }
 
-    /// Retries the blob data for the given transaction hash.
+    /// Deletes the blob data for the given transaction hash.
     #[inline]
     fn delete_one(&self, tx: B256) -> Result<(), DiskFileBlobStoreError> {
         trace!( target:"txpool::blob", "[{:?}] deleting blob file", tx);
+        self.blob_cache.lock().remove(&tx);
         let path = self.blob_disk_file(tx);
 
-        let _lock = self.file_lock.write();
-        fs::remove_file(&path).map_err(|e| DiskFileBlobStoreError::WriteFile(tx, path, e))?;
+        let size = {
+            let _lock = self.file_lock.write();
+            let size = fs::metadata(&path).map(|meta| meta.len() as usize).unwrap_or_default();
+            fs::remove_file(&path).map_err(|e| DiskFileBlobStoreError::DeleteFile(tx, path, e))?;
+            size
+        };
 
+        self.size_tracker.sub_size(size);
+        self.size_tracker.sub_len(1);
         Ok(())
     }
 
-    /// Retries the blob data for the given transaction hash.
+    /// Deletes the blob data for the given transaction hashes.
     #[inline]
     fn delete_many(
         &self,
         txs: impl IntoIterator<Item = TxHash>,
     ) -> Result<(), DiskFileBlobStoreError> {
-        let _lock = self.file_lock.write();
-        for tx in txs.into_iter() {
-            trace!( target:"txpool::blob", "[{:?}] deleting blob file", tx);
-            let path = self.blob_disk_file(tx);
+        let txs = txs.into_iter().collect::<Vec<_>>();
+        {
+            let mut cache = self.blob_cache.lock();
+            for tx in &txs {
+                cache.remove(tx);
+            }
+        }
+
+        let mut sub = 0;
+        let mut num = 0;
+        {
+            let _lock = self.file_lock.write();
+            for tx in txs {
+                trace!( target:"txpool::blob", "[{:?}] deleting blob file", tx);
+                let path = self.blob_disk_file(tx);
 
-            let _ = fs::remove_file(&path).map_err(|e| {
-                let err = DiskFileBlobStoreError::WriteFile(tx, path, e);
-                debug!( target:"txpool::blob", ?err);
-            });
+                let size = fs::metadata(&path).map(|meta| meta.len() as usize).unwrap_or_default();
+                if let Err(err) = fs::remove_file(&path) {
+                    let err = DiskFileBlobStoreError::DeleteFile(tx, path, err);
+                    debug!( target:"txpool::blob", ?err);
+                } else {
+                    sub += size;
+                    num += 1;
+                }
+            }
         }
+        self.size_tracker.sub_size(sub);
+        self.size_tracker.sub_len(num);
 
         Ok(())
     }
@@ -391,13 +449,22 @@ impl From<DiskFileBlobStoreError> for BlobStoreError {
 pub struct DiskFileBlobStoreConfig {
     /// The maximum number of blobs to keep in the in memory blob cache.
     pub max_cached_entries: u32,
+    /// The maximum size in bytes of all blob files on disk.
+    ///
+    /// Inserting a sidecar that would exceed this limit fails with [BlobStoreError::Full].
+    /// Unlimited if `None`.
+    pub max_size: Option<usize>,
     /// How to open the blob store.
     pub open: OpenDiskFileBlobStore,
 }
 
 impl Default for DiskFileBlobStoreConfig {
     fn default() -> Self {
-        Self { max_cached_entries: DEFAULT_MAX_CACHED_BLOBS, open: Default::default() }
+        Self {
+            max_cached_entries: DEFAULT_MAX_CACHED_BLOBS,
+            max_size: None,
+            open: Default::default(),
+        }
     }
 }
 
@@ -462,5 +529,41 @@ mod tests {
 
         assert!(!store.contains(all_hashes[0]).unwrap());
         assert!(store.get_exact(all_hashes).is_err());
+
+        assert_eq!(store.data_size_hint(), Some(0));
+        assert_eq!(store.blobs_len(), 0);
+    }
+
+    #[test]
+    fn disk_insert_exceeding_max_size() {
+        let blobs = rng_blobs(2);
+        let size = |blob: &BlobTransactionSidecar| {
+            let mut buf = Vec::new();
+            blob.encode(&mut buf);
+            buf.len()
+        };
+        let first_size = size(&blobs[0].1);
+        // enough space for either sidecar, but not for both
+        let max_size = first_size.max(size(&blobs[1].1));
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = DiskFileBlobStoreConfig { max_size: Some(max_size), ..Default::default() };
+        let store = DiskFileBlobStore::open(dir.path(), config).unwrap();
+
+        store.insert(blobs[0].0, blobs[0].1.clone()).unwrap();
+        assert_eq!(store.data_size_hint(), Some(first_size));
+
+        assert!(store.ensure_capacity(blobs[1].0, &blobs[1].1).is_err());
+        let err = store.insert(blobs[1].0, blobs[1].1.clone()).unwrap_err();
+        assert!(matches!(err, BlobStoreError::Full(tx) if tx == blobs[1].0));
+        assert!(!store.contains(blobs[1].0).unwrap());
+        assert!(store.insert_all(vec![blobs[1].clone()]).is_err());
+
+        // deleting a sidecar frees up space
+        store.delete(blobs[0].0).unwrap();
+        assert!(!store.is_cached(&blobs[0].0));
+        assert_eq!(store.data_size_hint(), Some(0));
+        store.ensure_capacity(blobs[1].0, &blobs[1].1).unwrap();
+        store.insert(blobs[1].0, blobs[1].1.clone()).unwrap();
     }
 }
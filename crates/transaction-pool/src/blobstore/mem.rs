@@ -24,6 +24,14 @@ impl BlobStore for InMemoryBlobStore {
         Ok(())
     }
 
+    fn ensure_capacity(
+        &self,
+        _tx: B256,
+        _data: &BlobTransactionSidecar,
+    ) -> Result<(), BlobStoreError> {
+        Ok(())
+    }
+
     fn insert_all(&self, txs: Vec<(B256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError> {
         if txs.is_empty() {
             return Ok(())
@@ -22,6 +22,14 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     /// Inserts the blob sidecar into the store
     fn insert(&self, tx: B256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError>;
 
+    /// Returns [BlobStoreError::Full] if the blob sidecar of the transaction doesn't fit into the
+    /// store.
+    fn ensure_capacity(
+        &self,
+        tx: B256,
+        data: &BlobTransactionSidecar,
+    ) -> Result<(), BlobStoreError>;
+
     /// Inserts multiple blob sidecars into the store
     fn insert_all(&self, txs: Vec<(B256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError>;
 
@@ -67,6 +75,10 @@ pub enum BlobStoreError {
     /// Thrown if the blob sidecar is not found for a given transaction hash but was required.
     #[error("blob sidecar not found for transaction {0:?}")]
     MissingSidecar(B256),
+    /// Thrown if the blob store has reached its size limit and cannot store the sidecar of the
+    /// given transaction.
+    #[error("blob store is full, cannot store sidecar for transaction {0:?}")]
+    Full(B256),
     /// Failed to decode the stored blob data.
     #[error("failed to decode blob data: {0}")]
     DecodeError(#[from] alloy_rlp::Error),
@@ -103,6 +115,11 @@ impl BlobStoreSize {
         self.num_blobs.fetch_add(add, std::sync::atomic::Ordering::Relaxed);
     }
 
+    #[inline]
+    pub(crate) fn sub_len(&self, sub: usize) {
+        self.num_blobs.fetch_sub(sub, std::sync::atomic::Ordering::Relaxed);
+    }
+
     #[inline]
     pub(crate) fn data_size(&self) -> usize {
         self.data_size.load(std::sync::atomic::Ordering::Relaxed)
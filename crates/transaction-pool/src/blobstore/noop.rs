@@ -11,6 +11,14 @@ impl BlobStore for NoopBlobStore {
         Ok(())
     }
 
+    fn ensure_capacity(
+        &self,
+        _tx: B256,
+        _data: &BlobTransactionSidecar,
+    ) -> Result<(), BlobStoreError> {
+        Ok(())
+    }
+
     fn insert_all(&self, _txs: Vec<(B256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError> {
         Ok(())
     }
@@ -4,7 +4,9 @@ use crate::{
     blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
     error::PoolError,
     metrics::MaintainPoolMetrics,
-    traits::{CanonicalStateUpdate, ChangedAccount, TransactionPool, TransactionPoolExt},
+    traits::{
+        CanonicalStateUpdate, ChangedAccount, PoolTransaction, TransactionPool, TransactionPoolExt,
+    },
     BlockInfo,
 };
 use futures_util::{
@@ -344,6 +346,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     mined_transactions: new_mined_transactions.into_iter().collect(),
                 };
                 pool.on_canonical_state_change(update);
+                remove_expired_conditional_transactions(&pool, new_tip.number, new_tip.timestamp);
 
                 // all transactions that were mined in the old chain but not in the new chain need
                 // to be re-injected
@@ -423,6 +426,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     mined_transactions,
                 };
                 pool.on_canonical_state_change(update);
+                remove_expired_conditional_transactions(&pool, tip.number, tip.timestamp);
 
                 // keep track of mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&blocks);
@@ -552,6 +556,28 @@ fn changed_accounts_iter(
         .map(|(address, acc)| ChangedAccount { address, nonce: acc.nonce, balance: acc.balance })
 }
 
+/// Removes the transactions submitted with `eth_sendRawTransactionConditional` whose conditions
+/// can't be satisfied by any block after the tip with the given number and timestamp.
+fn remove_expired_conditional_transactions<P>(pool: &P, number: BlockNumber, timestamp: u64)
+where
+    P: TransactionPool,
+{
+    let expired: Vec<_> = pool
+        .get_conditional_transactions()
+        .into_iter()
+        .filter(|tx| {
+            tx.transaction
+                .conditional()
+                .is_some_and(|conditional| conditional.has_expired(number, timestamp))
+        })
+        .map(|tx| *tx.hash())
+        .collect();
+    if !expired.is_empty() {
+        debug!(target: "txpool", count = expired.len(), "removing expired conditional transactions");
+        pool.remove_transactions(expired);
+    }
+}
+
 /// Loads transactions from a file, decodes them from the RLP format, and inserts them
 /// into the transaction pool on node boot up.
 /// The file is removed after the transactions have been successfully processed.
@@ -666,7 +692,8 @@ mod tests {
         CoinbaseTipOrdering, EthPooledTransaction, Pool, PoolTransaction, TransactionOrigin,
     };
     use reth_primitives::{
-        fs, hex, FromRecoveredPooledTransaction, PooledTransactionsElement, MAINNET, U256,
+        fs, hex, FromRecoveredPooledTransaction, PooledTransactionsElement, TransactionConditional,
+        MAINNET, U256,
     };
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
     use reth_tasks::TaskManager;
@@ -679,6 +706,36 @@ mod tests {
         assert!(changed_acc.eq(&ChangedAccountEntry(copy)));
     }
 
+    #[tokio::test]
+    async fn removes_expired_conditional_transactions() {
+        let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
+        let tx = PooledTransactionsElement::decode_enveloped(tx_bytes.into()).unwrap();
+        let provider = MockEthProvider::default();
+        let transaction = EthPooledTransaction::from_recovered_pooled_transaction(
+            tx.try_into_ecrecovered().unwrap(),
+        )
+        .with_conditional(TransactionConditional {
+            block_number_max: Some(10),
+            ..Default::default()
+        })
+        .unwrap();
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider, blob_store.clone());
+        let txpool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+
+        let hash = txpool.add_transaction(TransactionOrigin::Private, transaction).await.unwrap();
+
+        remove_expired_conditional_transactions(&txpool, 9, 0);
+        assert!(txpool.contains(&hash));
+
+        remove_expired_conditional_transactions(&txpool, 10, 0);
+        assert!(!txpool.contains(&hash));
+    }
+
     const EXTENSION: &str = "rlp";
     const FILENAME: &str = "test_transactions_backup";
 
@@ -231,6 +231,10 @@ impl TransactionPool for NoopTransactionPool {
     ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
         vec![]
     }
+
+    fn get_conditional_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        vec![]
+    }
 }
 
 /// A [`TransactionValidator`] that does nothing.
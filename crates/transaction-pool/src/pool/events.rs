@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 pub enum FullTransactionEvent<T: PoolTransaction> {
     /// Transaction has been added to the pending pool.
     Pending(TxHash),
-    /// Transaction has been added to the queued pool.
+    /// Transaction has been added to the queued pool or was moved there from the pending pool.
     Queued(TxHash),
     /// Transaction has been included in the block belonging to this hash.
     Mined {
@@ -60,7 +60,7 @@ impl<T: PoolTransaction> Clone for FullTransactionEvent<T> {
 pub enum TransactionEvent {
     /// Transaction has been added to the pending pool.
     Pending,
-    /// Transaction has been added to the queued pool.
+    /// Transaction has been added to the queued pool or was moved there from the pending pool.
     Queued,
     /// Transaction has been included in the block belonging to this hash.
     Mined(B256),
@@ -99,7 +99,7 @@ pub use events::{FullTransactionEvent, TransactionEvent};
 
 mod listener;
 use crate::{
-    blobstore::BlobStore,
+    blobstore::{BlobStore, BlobStoreError},
     metrics::BlobStoreMetrics,
     pool::txpool::UpdateOutcome,
     traits::{GetPooledTransactionLimit, NewBlobSidecar, TransactionListenerKind},
@@ -369,11 +369,12 @@ where
     /// This will either promote or discard transactions based on the new account state.
     pub(crate) fn update_accounts(&self, accounts: Vec<ChangedAccount>) {
         let changed_senders = self.changed_senders(accounts.into_iter());
-        let UpdateOutcome { promoted, discarded } =
+        let UpdateOutcome { promoted, demoted, discarded } =
             self.pool.write().update_accounts(changed_senders);
         let mut listener = self.event_listener.write();
 
         promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
+        demoted.iter().for_each(|tx| listener.queued(tx.hash()));
         discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
 
         // This deletes outdated blob txs from the blob store, based on the account's nonce. This is
@@ -413,6 +414,18 @@ where
                     }
                 };
 
+                // the transaction can't be served without its sidecar, so it's rejected before it
+                // replaces or evicts any other transaction
+                if let Some(sidecar) = &maybe_sidecar {
+                    let hash = *transaction.hash();
+                    if let Err(err) = self.blob_store.ensure_capacity(hash, sidecar) {
+                        warn!(target: "txpool", ?err, "[{:?}] failed to insert blob", hash);
+                        self.blob_store_metrics.blobstore_failed_inserts.increment(1);
+                        self.event_listener.write().discarded(&hash);
+                        return Err(PoolError::new(hash, PoolErrorKind::DiscardedOnInsert))
+                    }
+                }
+
                 let tx = ValidPoolTransaction {
                     transaction,
                     transaction_id,
@@ -424,17 +437,25 @@ where
                 let added = self.pool.write().add_transaction(tx, balance, state_nonce)?;
                 let hash = *added.hash();
 
+                if let Some(replaced) = added.replaced_blob_transaction() {
+                    // delete the replaced transaction from the blob store
+                    self.delete_blob(replaced);
+                }
+
                 // transaction was successfully inserted into the pool
                 if let Some(sidecar) = maybe_sidecar {
+                    // store the sidecar in the blob store
+                    if let Err(BlobStoreError::Full(_)) = self.insert_blob(hash, sidecar.clone()) {
+                        // the store was filled up by a concurrent insert since the capacity check,
+                        // so the transaction is evicted right away
+                        if let Some(discarded) = added.discarded_transactions() {
+                            self.delete_discarded_blobs(discarded.iter());
+                        }
+                        self.remove_transactions(vec![hash]);
+                        return Err(PoolError::new(hash, PoolErrorKind::DiscardedOnInsert))
+                    }
                     // notify blob sidecar listeners
                     self.on_new_blob_sidecar(&hash, &sidecar);
-                    // store the sidecar in the blob store
-                    self.insert_blob(hash, sidecar);
-                }
-
-                if let Some(replaced) = added.replaced_blob_transaction() {
-                    // delete the replaced transaction from the blob store
-                    self.delete_blob(replaced);
                 }
 
                 // Notify about new pending transactions
@@ -584,13 +605,15 @@ where
             })
         }
 
-        let OnNewCanonicalStateOutcome { mined, promoted, discarded, block_hash } = outcome;
+        let OnNewCanonicalStateOutcome { mined, promoted, demoted, discarded, block_hash } =
+            outcome;
 
         // broadcast specific transaction events
         let mut listener = self.event_listener.write();
 
         mined.iter().for_each(|tx| listener.mined(tx, block_hash));
         promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
+        demoted.iter().for_each(|tx| listener.queued(tx.hash()));
         discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
     }
 
@@ -600,17 +623,20 @@ where
 
         match tx {
             AddedTransaction::Pending(tx) => {
-                let AddedPendingTransaction { transaction, promoted, discarded, replaced } = tx;
+                let AddedPendingTransaction { transaction, promoted, demoted, discarded, replaced } =
+                    tx;
 
                 listener.pending(transaction.hash(), replaced.clone());
                 promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
+                demoted.iter().for_each(|tx| listener.queued(tx.hash()));
                 discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
             }
-            AddedTransaction::Parked { transaction, replaced, .. } => {
+            AddedTransaction::Parked { transaction, replaced, demoted, .. } => {
                 listener.queued(transaction.hash());
                 if let Some(replaced) = replaced {
                     listener.replaced(replaced.clone(), *transaction.hash());
                 }
+                demoted.iter().for_each(|tx| listener.queued(tx.hash()));
             }
         }
     }
@@ -710,6 +736,13 @@ where
         self.get_pool_data().all().transactions_iter().filter(|tx| tx.origin == origin).collect()
     }
 
+    /// Returns all transactions that were submitted with conditions.
+    pub(crate) fn get_conditional_transactions(
+        &self,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.get_pool_data().all().conditional_transactions_iter().collect()
+    }
+
     /// Returns all the transactions belonging to the hashes.
     ///
     /// If no transaction exists, it is skipped.
@@ -749,12 +782,18 @@ where
     }
 
     /// Inserts a blob transaction into the blob store
-    fn insert_blob(&self, hash: TxHash, blob: BlobTransactionSidecar) {
-        if let Err(err) = self.blob_store.insert(hash, blob) {
+    fn insert_blob(
+        &self,
+        hash: TxHash,
+        blob: BlobTransactionSidecar,
+    ) -> Result<(), BlobStoreError> {
+        let res = self.blob_store.insert(hash, blob);
+        if let Err(err) = &res {
             warn!(target: "txpool", ?err, "[{:?}] failed to insert blob", hash);
             self.blob_store_metrics.blobstore_failed_inserts.increment(1);
         }
         self.update_blob_store_metrics();
+        res
     }
 
     /// Delete a blob from the blob store
@@ -893,6 +932,8 @@ pub struct AddedPendingTransaction<T: PoolTransaction> {
     replaced: Option<Arc<ValidPoolTransaction<T>>>,
     /// transactions promoted to the pending queue
     promoted: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// transactions of the same sender moved from the pending pool to one of the parked pools
+    demoted: Vec<Arc<ValidPoolTransaction<T>>>,
     /// transactions that failed and became discarded
     discarded: Vec<Arc<ValidPoolTransaction<T>>>,
 }
@@ -981,6 +1022,9 @@ pub enum AddedTransaction<T: PoolTransaction> {
         replaced: Option<Arc<ValidPoolTransaction<T>>>,
         /// The subpool it was moved to.
         subpool: SubPool,
+        /// Transactions of the same sender moved from the pending pool to one of the parked
+        /// pools, e.g. because the replacement is more expensive.
+        demoted: Vec<Arc<ValidPoolTransaction<T>>>,
     },
 }
 
@@ -1062,6 +1106,8 @@ pub(crate) struct OnNewCanonicalStateOutcome<T: PoolTransaction> {
     pub(crate) mined: Vec<TxHash>,
     /// Transactions promoted to the pending pool.
     pub(crate) promoted: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// Transactions moved from the pending pool to one of the parked pools.
+    pub(crate) demoted: Vec<Arc<ValidPoolTransaction<T>>>,
     /// transaction that were discarded during the update
     pub(crate) discarded: Vec<Arc<ValidPoolTransaction<T>>>,
 }
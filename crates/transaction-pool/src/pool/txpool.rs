@@ -440,11 +440,17 @@ impl<T: TransactionOrdering> TxPool<T> {
             }
         }
 
-        let UpdateOutcome { promoted, discarded } = self.update_accounts(changed_senders);
+        let UpdateOutcome { promoted, demoted, discarded } = self.update_accounts(changed_senders);
 
         self.metrics.performed_state_updates.increment(1);
 
-        OnNewCanonicalStateOutcome { block_hash, mined: mined_transactions, promoted, discarded }
+        OnNewCanonicalStateOutcome {
+            block_hash,
+            mined: mined_transactions,
+            promoted,
+            demoted,
+            discarded,
+        }
     }
 
     /// Update sub-pools size metrics.
@@ -506,7 +512,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                 self.add_new_transaction(transaction.clone(), replaced_tx.clone(), move_to);
                 // Update inserted transactions metric
                 self.metrics.inserted_transactions.increment(1);
-                let UpdateOutcome { promoted, discarded } = self.process_updates(updates);
+                let UpdateOutcome { promoted, demoted, discarded } = self.process_updates(updates);
 
                 let replaced = replaced_tx.map(|(tx, _)| tx);
 
@@ -515,11 +521,12 @@ impl<T: TransactionOrdering> TxPool<T> {
                     AddedTransaction::Pending(AddedPendingTransaction {
                         transaction,
                         promoted,
+                        demoted,
                         discarded,
                         replaced,
                     })
                 } else {
-                    AddedTransaction::Parked { transaction, subpool: move_to, replaced }
+                    AddedTransaction::Parked { transaction, subpool: move_to, replaced, demoted }
                 };
 
                 Ok(res)
@@ -596,9 +603,11 @@ impl<T: TransactionOrdering> TxPool<T> {
                 Destination::Pool(move_to) => {
                     debug_assert!(!move_to.eq(&current), "destination must be different");
                     let moved = self.move_transaction(current, move_to, &id);
-                    if matches!(move_to, SubPool::Pending) {
-                        if let Some(tx) = moved {
+                    if let Some(tx) = moved {
+                        if move_to.is_pending() {
                             outcome.promoted.push(tx);
+                        } else if current.is_pending() {
+                            outcome.demoted.push(tx);
                         }
                     }
                 }
@@ -883,6 +892,9 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     max_account_slots: usize,
     /// _All_ transactions identified by their hash.
     by_hash: HashMap<TxHash, Arc<ValidPoolTransaction<T>>>,
+    /// The hashes of the transactions that were submitted with conditions, see
+    /// [PoolTransaction::conditional].
+    conditional: HashSet<TxHash>,
     /// _All_ transaction in the pool sorted by their sender and nonce pair.
     txs: BTreeMap<TransactionId, PoolInternalTransaction<T>>,
     /// Tracks the number of transactions by sender that are currently in the pool.
@@ -923,6 +935,13 @@ impl<T: PoolTransaction> AllTransactions<T> {
         self.by_hash.values().cloned()
     }
 
+    /// Returns an iterator over all transactions that were submitted with conditions.
+    pub(crate) fn conditional_transactions_iter(
+        &self,
+    ) -> impl Iterator<Item = Arc<ValidPoolTransaction<T>>> + '_ {
+        self.conditional.iter().filter_map(|hash| self.by_hash.get(hash).cloned())
+    }
+
     /// Returns if the transaction for the given hash is already included in this pool
     pub(crate) fn contains(&self, tx_hash: &TxHash) -> bool {
         self.by_hash.contains_key(tx_hash)
@@ -1203,6 +1222,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         tx_hash: &B256,
     ) -> Option<(Arc<ValidPoolTransaction<T>>, SubPool)> {
         let tx = self.by_hash.remove(tx_hash)?;
+        self.conditional.remove(tx_hash);
         let internal = self.txs.remove(&tx.transaction_id)?;
         // decrement the counter for the sender.
         self.tx_decr(tx.sender_id());
@@ -1223,6 +1243,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         // decrement the counter for the sender.
         self.tx_decr(internal.transaction.sender_id());
 
+        self.conditional.remove(internal.transaction.hash());
         self.by_hash.remove(internal.transaction.hash()).map(|tx| (tx, internal.subpool))
     }
 
@@ -1507,6 +1528,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 let new_hash = *pool_tx.transaction.hash();
                 let new_transaction = pool_tx.transaction.clone();
                 let replaced = entry.insert(pool_tx);
+                self.conditional.remove(replaced.transaction.hash());
                 self.by_hash.remove(replaced.transaction.hash());
                 self.by_hash.insert(new_hash, new_transaction);
                 // also remove the hash
@@ -1514,6 +1536,11 @@ impl<T: PoolTransaction> AllTransactions<T> {
             }
         }
 
+        // track the transactions with conditions, so that they can be evicted once they expire
+        if transaction.transaction.conditional().is_some() {
+            self.conditional.insert(*transaction.hash());
+        }
+
         // The next transaction of this sender
         let on_chain_id = TransactionId::new(transaction.sender_id(), on_chain_nonce);
         {
@@ -1617,6 +1644,10 @@ impl<T: PoolTransaction> AllTransactions<T> {
     #[cfg(any(test, feature = "test-utils"))]
     pub(crate) fn assert_invariants(&self) {
         assert_eq!(self.by_hash.len(), self.txs.len(), "by_hash.len() != txs.len()");
+        assert!(
+            self.conditional.iter().all(|hash| self.by_hash.contains_key(hash)),
+            "conditional transactions not in by_hash"
+        );
     }
 }
 
@@ -1635,6 +1666,7 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             block_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             by_hash: Default::default(),
+            conditional: Default::default(),
             txs: Default::default(),
             tx_counter: Default::default(),
             last_seen_block_number: Default::default(),
@@ -1743,13 +1775,15 @@ impl<T: PoolTransaction> PoolInternalTransaction<T> {
 pub(crate) struct UpdateOutcome<T: PoolTransaction> {
     /// transactions promoted to the pending pool
     pub(crate) promoted: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// transactions moved from the pending pool to one of the parked pools
+    pub(crate) demoted: Vec<Arc<ValidPoolTransaction<T>>>,
     /// transaction that failed and were discarded
     pub(crate) discarded: Vec<Arc<ValidPoolTransaction<T>>>,
 }
 
 impl<T: PoolTransaction> Default for UpdateOutcome<T> {
     fn default() -> Self {
-        Self { promoted: vec![], discarded: vec![] }
+        Self { promoted: vec![], demoted: vec![], discarded: vec![] }
     }
 }
 
@@ -2617,4 +2651,56 @@ mod tests {
         assert_eq!(outcome.discarded.len(), 1);
         assert_eq!(pool.pending_pool.len(), 1);
     }
+
+    #[test]
+    fn demote_and_promote_on_balance_change() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let tx = MockTransaction::eip1559().inc_price_by(10).with_value(U256::from(1_000));
+        let validated = f.validated(tx.clone());
+        let id = *validated.id();
+        pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+        assert_eq!(pool.pending_pool.len(), 1);
+
+        // the sender can no longer afford the transaction
+        let mut changed_senders = HashMap::new();
+        changed_senders.insert(id.sender, SenderInfo { state_nonce: 0, balance: U256::from(999) });
+        let outcome = pool.update_accounts(changed_senders.clone());
+        assert!(outcome.promoted.is_empty());
+        assert_eq!(outcome.demoted.len(), 1);
+        assert_eq!(*outcome.demoted[0].hash(), *tx.hash());
+        assert_eq!(pool.pending_pool.len(), 0);
+        assert_eq!(pool.queued_pool.len(), 1);
+
+        // the sender was funded again
+        changed_senders
+            .insert(id.sender, SenderInfo { state_nonce: 0, balance: U256::from(1_000) });
+        let outcome = pool.update_accounts(changed_senders);
+        assert_eq!(outcome.promoted.len(), 1);
+        assert!(outcome.demoted.is_empty());
+        assert_eq!(pool.pending_pool.len(), 1);
+    }
+
+    #[test]
+    fn demote_descendant_on_replacement() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+        let on_chain_balance = U256::from(2_000);
+
+        let tx = MockTransaction::eip1559().inc_price_by(10).with_value(U256::from(1_000));
+        let next = tx.next();
+        pool.add_transaction(f.validated(tx.clone()), on_chain_balance, 0).unwrap();
+        pool.add_transaction(f.validated(next.clone()), on_chain_balance, 0).unwrap();
+        assert_eq!(pool.pending_pool.len(), 2);
+
+        // the replacement is more expensive, so the sender can no longer afford the next one
+        let replacement = tx.rng_hash().inc_price().with_value(U256::from(1_500));
+        let added = pool.add_transaction(f.validated(replacement), on_chain_balance, 0).unwrap();
+        let AddedTransaction::Pending(added) = added else { panic!("replacement is pending") };
+        assert_eq!(added.demoted.len(), 1);
+        assert_eq!(*added.demoted[0].hash(), *next.hash());
+        assert_eq!(pool.pending_pool.len(), 1);
+        assert_eq!(pool.queued_pool.len(), 1);
+    }
 }
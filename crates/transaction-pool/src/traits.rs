@@ -9,8 +9,8 @@ use reth_primitives::{
     AccessList, Address, BlobTransactionSidecar, BlobTransactionValidationError,
     FromRecoveredPooledTransaction, FromRecoveredTransaction, IntoRecoveredTransaction, PeerId,
     PooledTransactionsElement, PooledTransactionsElementEcRecovered, SealedBlock, Transaction,
-    TransactionKind, TransactionSignedEcRecovered, TxEip4844, TxHash, B256, EIP1559_TX_TYPE_ID,
-    EIP4844_TX_TYPE_ID, U256,
+    TransactionConditional, TransactionKind, TransactionSignedEcRecovered, TxEip4844, TxHash, B256,
+    EIP1559_TX_TYPE_ID, EIP4844_TX_TYPE_ID, U256,
 };
 use std::{
     collections::{HashMap, HashSet},
@@ -334,6 +334,10 @@ pub trait TransactionPool: Send + Sync + Clone {
         self.get_transactions_by_origin(TransactionOrigin::External)
     }
 
+    /// Returns all transactions that were submitted with conditions, see
+    /// [PoolTransaction::conditional].
+    fn get_conditional_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
     /// Returns a set of all senders of transactions in the pool
     fn unique_senders(&self) -> HashSet<Address>;
 
@@ -784,6 +788,22 @@ pub trait PoolTransaction:
     /// Returns chain_id
     fn chain_id(&self) -> Option<u64>;
 
+    /// Returns the conditions the transaction has to satisfy to be included in a block, if it was
+    /// submitted with any.
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        None
+    }
+
+    /// Attaches the conditions the transaction has to satisfy to be included in a block.
+    ///
+    /// Returns `None` if this transaction type does not support conditions.
+    fn with_conditional(self, _conditional: TransactionConditional) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
     /// Returns whether or not the transaction is an Optimism Deposited transaction.
     #[cfg(feature = "optimism")]
     fn is_deposit(&self) -> bool;
@@ -832,6 +852,9 @@ pub struct EthPooledTransaction {
 
     /// The blob side car for this transaction
     pub(crate) blob_sidecar: EthBlobTransactionSidecar,
+
+    /// The conditions of a transaction submitted with `eth_sendRawTransactionConditional`.
+    pub(crate) conditional: Option<Box<TransactionConditional>>,
 }
 
 /// Represents the blob sidecar of the [EthPooledTransaction].
@@ -871,7 +894,7 @@ impl EthPooledTransaction {
             cost += U256::from(blob_tx.max_fee_per_blob_gas * blob_tx.blob_gas() as u128);
         }
 
-        Self { transaction, cost, encoded_length, blob_sidecar }
+        Self { transaction, cost, encoded_length, blob_sidecar, conditional: None }
     }
 
     /// Return the reference to the underlying transaction.
@@ -1015,6 +1038,15 @@ impl PoolTransaction for EthPooledTransaction {
         self.transaction.chain_id()
     }
 
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        self.conditional.as_deref()
+    }
+
+    fn with_conditional(mut self, conditional: TransactionConditional) -> Option<Self> {
+        self.conditional = Some(Box::new(conditional));
+        Some(self)
+    }
+
     /// Returns whether or not the transaction is an Optimism Deposited transaction.
     #[cfg(feature = "optimism")]
     fn is_deposit(&self) -> bool {
@@ -0,0 +1,151 @@
+//! Validation of the conditions of transactions submitted with
+//! `eth_sendRawTransactionConditional`.
+
+use reth_primitives::{Address, KnownAccount, TransactionConditional, B256};
+use reth_provider::{BundleStateWithReceipts, ProviderError, StateProvider};
+
+/// The maximum number of storage slots and storage roots a [TransactionConditional] can expect,
+/// see [TransactionConditional::known_accounts_cost].
+pub const MAX_KNOWN_ACCOUNTS_COST: usize = 1000;
+
+/// Errors of [validate_conditional].
+#[derive(Debug, thiserror::Error)]
+pub enum ConditionalError {
+    /// Thrown if the conditional expects too many storage slots and storage roots.
+    #[error(
+        "conditional expects {0} storage slots and roots, max is {max}",
+        max = MAX_KNOWN_ACCOUNTS_COST
+    )]
+    TooManyKnownAccounts(usize),
+    /// Thrown if the block number is out of range.
+    #[error("block number {0} is out of range")]
+    BlockNumberOutOfRange(u64),
+    /// Thrown if the block timestamp is out of range.
+    #[error("timestamp {0} is out of range")]
+    TimestampOutOfRange(u64),
+    /// Thrown if the storage root of an account doesn't match.
+    #[error("storage root of account {0} does not match")]
+    StorageRootMismatch(Address),
+    /// Thrown if a storage slot of an account doesn't match.
+    #[error("storage slot {1} of account {0} does not match")]
+    StorageSlotMismatch(Address, B256),
+    /// Thrown if the state couldn't be read.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+/// Checks whether a transaction with the [TransactionConditional] can be included in the block
+/// with the given number and timestamp, which is built on top of the given state.
+pub fn validate_conditional(
+    conditional: &TransactionConditional,
+    block_number: u64,
+    timestamp: u64,
+    state: &dyn StateProvider,
+) -> Result<(), ConditionalError> {
+    let cost = conditional.known_accounts_cost();
+    if cost > MAX_KNOWN_ACCOUNTS_COST {
+        return Err(ConditionalError::TooManyKnownAccounts(cost))
+    }
+    if !conditional.matches_block_number(block_number) {
+        return Err(ConditionalError::BlockNumberOutOfRange(block_number))
+    }
+    if !conditional.matches_timestamp(timestamp) {
+        return Err(ConditionalError::TimestampOutOfRange(timestamp))
+    }
+
+    for (address, account) in &conditional.known_accounts {
+        match account {
+            KnownAccount::StorageRoot(root) => {
+                let storage_root =
+                    state.storage_root(*address, &BundleStateWithReceipts::default())?;
+                if storage_root != *root {
+                    return Err(ConditionalError::StorageRootMismatch(*address))
+                }
+            }
+            KnownAccount::Slots(slots) => {
+                for (slot, value) in slots {
+                    let current = state.storage(*address, *slot)?.unwrap_or_default();
+                    if B256::from(current) != *value {
+                        return Err(ConditionalError::StorageSlotMismatch(*address, *slot))
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{proofs, U256};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn validate_slots_and_ranges() {
+        let address = Address::with_last_byte(1);
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            address,
+            ExtendedAccount::new(0, U256::ZERO)
+                .extend_storage(vec![(B256::with_last_byte(1), U256::from(2))]),
+        );
+
+        let mut conditional = TransactionConditional {
+            known_accounts: BTreeMap::from([(
+                address,
+                KnownAccount::Slots(BTreeMap::from([(
+                    B256::with_last_byte(1),
+                    B256::with_last_byte(2),
+                )])),
+            )]),
+            block_number_min: Some(10),
+            timestamp_max: Some(100),
+            ..Default::default()
+        };
+        assert!(validate_conditional(&conditional, 10, 100, &provider).is_ok());
+        assert!(matches!(
+            validate_conditional(&conditional, 9, 100, &provider),
+            Err(ConditionalError::BlockNumberOutOfRange(9))
+        ));
+        assert!(matches!(
+            validate_conditional(&conditional, 10, 101, &provider),
+            Err(ConditionalError::TimestampOutOfRange(101))
+        ));
+
+        conditional.known_accounts.insert(
+            Address::with_last_byte(2),
+            KnownAccount::Slots(BTreeMap::from([(B256::ZERO, B256::with_last_byte(1))])),
+        );
+        assert!(matches!(
+            validate_conditional(&conditional, 10, 100, &provider),
+            Err(ConditionalError::StorageSlotMismatch(..))
+        ));
+    }
+
+    #[test]
+    fn validate_storage_root() {
+        let address = Address::with_last_byte(1);
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            address,
+            ExtendedAccount::new(0, U256::ZERO)
+                .extend_storage(vec![(B256::with_last_byte(1), U256::from(2))]),
+        );
+        let root = proofs::storage_root_unhashed(vec![(B256::with_last_byte(1), U256::from(2))]);
+
+        let mut conditional = TransactionConditional {
+            known_accounts: BTreeMap::from([(address, KnownAccount::StorageRoot(root))]),
+            ..Default::default()
+        };
+        assert!(validate_conditional(&conditional, 1, 1, &provider).is_ok());
+
+        conditional.known_accounts.insert(address, KnownAccount::StorageRoot(B256::ZERO));
+        assert!(matches!(
+            validate_conditional(&conditional, 1, 1, &provider),
+            Err(ConditionalError::StorageRootMismatch(a)) if a == address
+        ));
+    }
+}
@@ -11,6 +11,7 @@ use reth_primitives::{
 };
 use std::{fmt, time::Instant};
 
+mod conditional;
 mod constants;
 mod eth;
 mod task;
@@ -21,6 +22,9 @@ pub use eth::*;
 /// A spawnable task that performs transaction validation.
 pub use task::{TransactionValidationTaskExecutor, ValidationTask};
 
+/// Validation of the conditions of conditional transactions.
+pub use conditional::{validate_conditional, ConditionalError, MAX_KNOWN_ACCOUNTS_COST};
+
 /// Validation constants.
 pub use constants::{MAX_CODE_SIZE, MAX_INIT_CODE_SIZE, TX_MAX_SIZE, TX_SLOT_SIZE};
 
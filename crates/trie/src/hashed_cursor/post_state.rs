@@ -147,6 +147,16 @@ impl HashedPostState {
     /// The prefix sets contain the hashed account and storage keys that have been changed in the
     /// post state.
     pub fn construct_prefix_sets(&self) -> (PrefixSet, AHashMap<B256, PrefixSet>) {
+        let (account_prefix_set, storage_prefix_set) = self.construct_prefix_sets_mut();
+        (
+            account_prefix_set.freeze(),
+            storage_prefix_set.into_iter().map(|(k, v)| (k, v.freeze())).collect(),
+        )
+    }
+
+    /// Construct the mutable prefix sets of the keys that have been changed in the post state,
+    /// see [HashedPostState::construct_prefix_sets].
+    pub fn construct_prefix_sets_mut(&self) -> (PrefixSetMut, AHashMap<B256, PrefixSetMut>) {
         // Initialize prefix sets.
         let mut account_prefix_set = PrefixSetMut::default();
         let mut storage_prefix_set: AHashMap<B256, PrefixSetMut> = AHashMap::default();
@@ -172,10 +182,7 @@ impl HashedPostState {
             }
         }
 
-        (
-            account_prefix_set.freeze(),
-            storage_prefix_set.into_iter().map(|(k, v)| (k, v.freeze())).collect(),
-        )
+        (account_prefix_set, storage_prefix_set)
     }
 }
 